@@ -53,6 +53,10 @@ impl BatchRequest {
                         crate::api::ipc::IpcCommand::Navigate {
                             tab_id: tab(tab_id),
                             url: url.clone(),
+                            bypass_cache: false,
+                            max_redirects: None,
+                            referrer: None,
+                            referrer_policy: None,
                         }
                     }
                     BatchCommand::Click { selector, tab_id } => {
@@ -91,6 +95,7 @@ impl BatchRequest {
                         clip_width: None,
                         clip_height: None,
                         clip_scale: None,
+                        include_scrollbars: false,
                     },
                     BatchCommand::Evaluate { script, tab_id } => {
                         crate::api::ipc::IpcCommand::EvaluateScript {
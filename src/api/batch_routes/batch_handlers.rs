@@ -504,6 +504,7 @@ async fn extract_screenshot(ipc: &std::sync::Arc<crate::api::ipc::IpcChannel>, t
         clip_width: None,
         clip_height: None,
         clip_scale: None,
+        include_scrollbars: false,
     };
     if let Ok(resp) = ipc.send_command(IpcMessage::Command(cmd)).await {
         if resp.success {
@@ -30,22 +30,6 @@ pub(super) fn parse_ipc_json_result(data: &serde_json::Value) -> Option<serde_js
     }
 }
 
-/// Parse a list of `CookieInfo` from an IPC response data value.
-///
-/// The JS script returns a JSON string; the IPC response may wrap it
-/// as a string value or as a parsed JSON value.
-pub(super) fn parse_cookies_from_response(data: Option<serde_json::Value>) -> Vec<CookieInfo> {
-    let Some(data) = data else {
-        return Vec::new();
-    };
-
-    let parsed = parse_ipc_json_result(&data);
-    match parsed {
-        Some(val) => serde_json::from_value::<Vec<CookieInfo>>(val).unwrap_or_default(),
-        None => Vec::new(),
-    }
-}
-
 /// Parse a localStorage/sessionStorage map from an IPC response.
 pub(super) fn parse_storage_from_response(data: Option<serde_json::Value>) -> HashMap<String, String> {
     let Some(data) = data else {
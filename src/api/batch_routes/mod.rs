@@ -49,9 +49,13 @@ pub fn batch_session_routes() -> Router<AppState> {
         // Session key-value storage
         .route("/session/:id/storage", post(session_handlers::set_storage))
         .route("/session/:id/storage/:key", get(session_handlers::get_storage))
-        // Cookie management via JS injection
-        .route("/tabs/:tab_id/cookies", get(session_handlers::get_cookies))
-        .route("/tabs/:tab_id/cookies", post(session_handlers::set_cookies))
+        // Cookie management through the engine's cookie store
+        .route(
+            "/tabs/:tab_id/cookies",
+            get(session_handlers::get_cookies)
+                .post(session_handlers::set_cookies)
+                .delete(session_handlers::delete_cookie),
+        )
         // LocalStorage via JS injection
         .route("/tabs/:tab_id/local-storage", get(session_handlers::get_local_storage))
         // Session snapshots
@@ -66,36 +70,6 @@ mod tests {
     use std::collections::HashMap;
     use crate::api::session::CookieInfo;
 
-    #[test]
-    fn test_parse_cookies_from_response_none() {
-        let result = parse_cookies_from_response(None);
-        assert!(result.is_empty());
-    }
-
-    #[test]
-    fn test_parse_cookies_from_response_string() {
-        let json = serde_json::json!(r#"[{"name":"sid","value":"abc","domain":"example.com","path":"/","expires":null,"http_only":false,"secure":true,"same_site":null}]"#);
-        let result = parse_cookies_from_response(Some(json));
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].name, "sid");
-        assert_eq!(result[0].value, "abc");
-    }
-
-    #[test]
-    fn test_parse_cookies_from_response_array() {
-        let json = serde_json::json!([{
-            "name": "token",
-            "value": "xyz",
-            "domain": "test.com",
-            "path": "/",
-            "http_only": false,
-            "secure": false
-        }]);
-        let result = parse_cookies_from_response(Some(json));
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].name, "token");
-    }
-
     #[test]
     fn test_parse_storage_from_response_none() {
         let result = parse_storage_from_response(None);
@@ -197,6 +171,14 @@ mod tests {
         assert!(req.secure);
         assert_eq!(req.path, "/"); // default
         assert!(req.domain.is_none());
+        assert!(!req.http_only);
+
+        let json = r#"{"name": "sid", "value": "v", "http_only": true,
+            "expires": "2030-01-01T00:00:00Z", "same_site": "Strict"}"#;
+        let req: SetCookieRequest = serde_json::from_str(json).unwrap();
+        assert!(req.http_only);
+        assert_eq!(req.expires.map(|e| e.timestamp()), Some(1_893_456_000));
+        assert_eq!(req.same_site, Some(crate::browser::SameSitePolicy::Strict));
     }
 
     #[test]
@@ -4,7 +4,7 @@
 use std::collections::HashMap;
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -14,8 +14,8 @@ use tracing::{error, info};
 use crate::api::ipc::{IpcCommand, IpcMessage};
 use crate::api::routes::ApiResponse;
 use crate::api::server::AppState;
-use crate::api::session::{CookieInfo, SessionManager, SessionSnapshot, TabSnapshot};
-use super::helpers::{parse_cookies_from_response, parse_storage_from_response, parse_tab_snapshot};
+use crate::api::session::{SessionManager, SessionSnapshot, TabSnapshot};
+use super::helpers::{parse_storage_from_response, parse_tab_snapshot};
 use super::types::*;
 use super::SESSION_MANAGER;
 
@@ -119,56 +119,61 @@ pub(super) async fn get_storage(
     }
 }
 
-/// GET /tabs/{tab_id}/cookies - Get cookies for a tab via JavaScript.
-///
-/// Uses `document.cookie` to read cookies visible to the page. Note that
-/// `httpOnly` cookies are not accessible from JavaScript.
-pub(super) async fn get_cookies(
-    State(state): State<AppState>,
-    Path(tab_id): Path<String>,
-) -> impl IntoResponse {
-    if !state.is_enabled().await {
-        return (
-            StatusCode::SERVICE_UNAVAILABLE,
-            Json(ApiResponse::<Vec<CookieInfo>>::error("API is disabled")),
-        )
-            .into_response();
-    }
-
-    let cmd = IpcCommand::EvaluateScript {
-        tab_id: tab_id.clone(),
-        script: SessionManager::get_cookies_script().to_string(),
-        await_promise: false,
-        frame_id: None,
-    };
-
-    match state.ipc_channel.send_command(IpcMessage::Command(cmd)).await {
+/// Sends a cookie command and converts the IPC response.
+async fn send_cookie_command(
+    state: &AppState,
+    tab_id: &str,
+    command: IpcCommand,
+    fallback_error: &str,
+) -> axum::response::Response {
+    match state.ipc_channel.send_command(IpcMessage::Command(command)).await {
         Ok(resp) if resp.success => {
-            let cookies = parse_cookies_from_response(resp.data);
-            Json(ApiResponse::success(cookies)).into_response()
+            let cookies = resp.data.and_then(|data| data.get("cookies").cloned());
+            Json(ApiResponse::success(cookies.unwrap_or(serde_json::Value::Null))).into_response()
         }
         Ok(resp) => (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::<Vec<CookieInfo>>::error(
-                resp.error.unwrap_or_else(|| "Failed to get cookies".to_string()),
+            Json(ApiResponse::<()>::error(
+                resp.error.unwrap_or_else(|| fallback_error.to_string()),
             )),
         )
             .into_response(),
         Err(e) => {
-            error!("Failed to get cookies for tab {}: {}", tab_id, e);
+            error!("{} for tab {}: {}", fallback_error, tab_id, e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::<Vec<CookieInfo>>::error(format!(
-                    "IPC error: {}",
-                    e
-                ))),
+                Json(ApiResponse::<()>::error(format!("IPC error: {}", e))),
             )
                 .into_response()
         }
     }
 }
 
-/// POST /tabs/{tab_id}/cookies - Set a cookie on a tab via JavaScript.
+/// GET /tabs/{tab_id}/cookies - List the browser's cookies.
+///
+/// Includes `httpOnly` cookies. With `?url=`, only the cookies sent with a
+/// request to that URL are listed.
+pub(super) async fn get_cookies(
+    State(state): State<AppState>,
+    Path(tab_id): Path<String>,
+    Query(query): Query<CookieQuery>,
+) -> impl IntoResponse {
+    if !state.is_enabled().await {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::<()>::error("API is disabled")),
+        )
+            .into_response();
+    }
+
+    let cmd = IpcCommand::GetCookies {
+        tab_id: tab_id.clone(),
+        url: query.url,
+    };
+    send_cookie_command(&state, &tab_id, cmd, "Failed to get cookies").await
+}
+
+/// POST /tabs/{tab_id}/cookies - Set a cookie, including `httpOnly` ones.
 pub(super) async fn set_cookies(
     State(state): State<AppState>,
     Path(tab_id): Path<String>,
@@ -182,45 +187,40 @@ pub(super) async fn set_cookies(
             .into_response();
     }
 
-    let cookie_info = CookieInfo {
+    let cmd = IpcCommand::SetCookie {
+        tab_id: tab_id.clone(),
         name: request.name,
         value: request.value,
-        domain: request.domain.unwrap_or_default(),
-        path: request.path,
-        expires: request.expires,
-        http_only: false, // Cannot set httpOnly via JS
-        secure: request.secure,
+        domain: request.domain,
+        path: Some(request.path),
+        secure: Some(request.secure),
+        http_only: Some(request.http_only),
+        expires: request.expires.map(|e| e.timestamp()),
         same_site: request.same_site,
     };
+    send_cookie_command(&state, &tab_id, cmd, "Failed to set cookie").await
+}
 
-    let script = SessionManager::set_cookie_script(&cookie_info);
-    let cmd = IpcCommand::EvaluateScript {
-        tab_id: tab_id.clone(),
-        script,
-        await_promise: false,
-        frame_id: None,
-    };
-
-    match state.ipc_channel.send_command(IpcMessage::Command(cmd)).await {
-        Ok(resp) if resp.success => {
-            Json(ApiResponse::success(())).into_response()
-        }
-        Ok(resp) => (
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::<()>::error(
-                resp.error.unwrap_or_else(|| "Failed to set cookie".to_string()),
-            )),
+/// DELETE /tabs/{tab_id}/cookies?name=&domain= - Delete a cookie on all paths.
+pub(super) async fn delete_cookie(
+    State(state): State<AppState>,
+    Path(tab_id): Path<String>,
+    Query(query): Query<DeleteCookieQuery>,
+) -> impl IntoResponse {
+    if !state.is_enabled().await {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::<()>::error("API is disabled")),
         )
-            .into_response(),
-        Err(e) => {
-            error!("Failed to set cookie for tab {}: {}", tab_id, e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::<()>::error(format!("IPC error: {}", e))),
-            )
-                .into_response()
-        }
+            .into_response();
     }
+
+    let cmd = IpcCommand::DeleteCookie {
+        tab_id: tab_id.clone(),
+        name: query.name,
+        domain: query.domain,
+    };
+    send_cookie_command(&state, &tab_id, cmd, "Failed to delete cookie").await
 }
 
 /// GET /tabs/{tab_id}/local-storage - Get all localStorage entries via JavaScript.
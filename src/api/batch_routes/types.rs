@@ -1,7 +1,10 @@
 //! Request and response types for batch operations and session management routes.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::browser::SameSitePolicy;
+
 /// Request body for creating a new session.
 #[derive(Debug, Deserialize)]
 pub struct CreateSessionRequest {
@@ -26,21 +29,40 @@ pub struct StorageValueResponse {
     pub value: serde_json::Value,
 }
 
-/// Request body for setting a cookie via JavaScript.
+/// Request body for setting a cookie.
 #[derive(Debug, Deserialize)]
 pub struct SetCookieRequest {
     pub name: String,
     pub value: String,
+    /// Cookie domain; defaults to the host of the tab.
     #[serde(default)]
     pub domain: Option<String>,
     #[serde(default = "default_path")]
     pub path: String,
+    /// Expiry (RFC 3339); `None` for a session cookie.
     #[serde(default)]
-    pub expires: Option<String>,
+    pub expires: Option<DateTime<Utc>>,
     #[serde(default)]
     pub secure: bool,
     #[serde(default)]
-    pub same_site: Option<String>,
+    pub http_only: bool,
+    #[serde(default)]
+    pub same_site: Option<SameSitePolicy>,
+}
+
+/// Query parameters for listing cookies.
+#[derive(Debug, Deserialize)]
+pub struct CookieQuery {
+    /// Only list the cookies sent with a request to this URL.
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Query parameters for deleting a cookie.
+#[derive(Debug, Deserialize)]
+pub struct DeleteCookieQuery {
+    pub name: String,
+    pub domain: String,
 }
 
 fn default_path() -> String {
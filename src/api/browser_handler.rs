@@ -13,8 +13,17 @@ use crate::api::ipc::{IpcCommand, IpcProcessor, IpcResponse};
 
 #[cfg(feature = "cef-browser")]
 use crate::browser::CefBrowserEngine;
+#[cfg(feature = "cef-browser")]
+use crate::retry::{retry, RetryPolicy};
 
-use crate::browser::{BrowserEngine, MockBrowserEngine, ScreenshotFormat, ScreenshotOptions};
+use crate::browser::permissions::normalize_origin;
+use crate::browser::cookies::url_host;
+use crate::browser::{
+    BlockListInterceptor, BrowserEngine, Cookie, EvalOptions, MockBrowserEngine, NavigateOptions,
+    Permission, ReferrerPolicy, RequestInterceptor, SameSitePolicy, ScreenshotFormat,
+    ScreenshotOptions,
+};
+use crate::error::BrowserError;
 
 /// Parameters for drag operations between two screen coordinates
 struct DragParams {
@@ -33,6 +42,7 @@ struct ScreenshotParams<'a> {
     full_page: bool,
     selector: Option<&'a str>,
     clip: Option<(f64, f64, f64, f64, f64)>,
+    include_scrollbars: bool,
 }
 
 /// Browser engine wrapper that abstracts over different implementations
@@ -180,8 +190,12 @@ impl BrowserCommandHandler {
             IpcCommand::CloseTab { tab_id } => {
                 self.handle_close_tab(&engine_guard, &tab_id).await
             }
-            IpcCommand::Navigate { tab_id, url } => {
-                self.handle_navigate(&engine_guard, &tab_id, &url).await
+            IpcCommand::Navigate { tab_id, url, bypass_cache, max_redirects, referrer, referrer_policy } => {
+                let mut options = NavigateOptions { bypass_cache, referrer, ..Default::default() };
+                if let Some(max_redirects) = max_redirects {
+                    options = options.max_redirects(max_redirects);
+                }
+                self.handle_navigate(&engine_guard, &tab_id, &url, options, referrer_policy).await
             }
             IpcCommand::ClickCoordinates { tab_id, x, y, button, modifiers: _ } => {
                 self.handle_click(&engine_guard, &tab_id, x, y, &button).await
@@ -205,7 +219,7 @@ impl BrowserCommandHandler {
             IpcCommand::Scroll { tab_id, x, y, delta_x, delta_y, selector, behavior, frame_id } => {
                 self.handle_scroll(&engine_guard, &tab_id, x, y, delta_x, delta_y, selector, behavior, frame_id.as_deref()).await
             }
-            IpcCommand::CaptureScreenshot { tab_id, format, quality, full_page, selector, clip_x, clip_y, clip_width, clip_height, clip_scale } => {
+            IpcCommand::CaptureScreenshot { tab_id, format, quality, full_page, selector, clip_x, clip_y, clip_width, clip_height, clip_scale, include_scrollbars } => {
                 let clip = if let (Some(x), Some(y), Some(w), Some(h)) = (clip_x, clip_y, clip_width, clip_height) {
                     Some((x, y, w, h, clip_scale.unwrap_or(1.0)))
                 } else {
@@ -213,12 +227,15 @@ impl BrowserCommandHandler {
                 };
                 self.handle_screenshot(&engine_guard, &tab_id, ScreenshotParams {
                     format: &format, quality, full_page,
-                    selector: selector.as_deref(), clip,
+                    selector: selector.as_deref(), clip, include_scrollbars,
                 }).await
             }
             IpcCommand::EvaluateScript { tab_id, script, await_promise: _, frame_id } => {
                 self.handle_evaluate(&engine_guard, &tab_id, &script, frame_id.as_deref()).await
             }
+            IpcCommand::EvaluateJs { tab_id, script, timeout_ms } => {
+                self.handle_evaluate_js(&engine_guard, &tab_id, &script, timeout_ms).await
+            }
             IpcCommand::GetTabs => {
                 self.handle_get_tabs(&engine_guard).await
             }
@@ -245,6 +262,60 @@ impl BrowserCommandHandler {
                 ];
                 self.handle_annotate(&engine_guard, &tab_id, types, None, false, String::new()).await
             }
+            IpcCommand::SetActiveTab { tab_id } => {
+                self.handle_set_active_tab(&engine_guard, &tab_id).await
+            }
+            IpcCommand::GetActiveTab => {
+                self.handle_get_active_tab(&engine_guard).await
+            }
+            IpcCommand::GetRecentEvents { tab_id, limit } => {
+                self.handle_get_recent_events(&engine_guard, tab_id.as_deref(), limit).await
+            }
+            IpcCommand::ClearEventBuffer { tab_id } => {
+                self.handle_clear_event_buffer(&engine_guard, tab_id.as_deref()).await
+            }
+            IpcCommand::SetCpuThrottling { tab_id, rate } => {
+                self.handle_set_cpu_throttling(&engine_guard, &tab_id, rate).await
+            }
+            IpcCommand::GrantPermissions { tab_id, origin, permissions } => {
+                self.handle_grant_permissions(&engine_guard, &tab_id, Some((&origin, permissions)))
+                    .await
+            }
+            IpcCommand::ResetPermissions { tab_id } => {
+                self.handle_grant_permissions(&engine_guard, &tab_id, None).await
+            }
+            IpcCommand::SetRequestBlockList { patterns } => {
+                self.handle_set_request_block_list(&engine_guard, patterns).await
+            }
+            IpcCommand::StartHarRecording { tab_id } => {
+                self.handle_start_har_recording(&engine_guard, &tab_id).await
+            }
+            IpcCommand::StopHarRecording { tab_id } => {
+                self.handle_stop_har_recording(&engine_guard, &tab_id).await
+            }
+            IpcCommand::GetCookies { tab_id, url } => {
+                self.handle_get_cookies(&engine_guard, &tab_id, url.as_deref()).await
+            }
+            IpcCommand::SetCookie {
+                tab_id, name, value, domain, path, secure, http_only, expires, same_site,
+            } => {
+                let mut cookie = Cookie::new(name, value, domain.unwrap_or_default());
+                if let Some(path) = path {
+                    cookie.path = path;
+                }
+                cookie.secure = secure.unwrap_or(false);
+                cookie.http_only = http_only.unwrap_or(false);
+                cookie.expires = match expires.map(|secs| chrono::DateTime::from_timestamp(secs, 0)) {
+                    Some(None) => return IpcResponse::error("Cookie expiry out of range"),
+                    Some(expires) => expires,
+                    None => None,
+                };
+                cookie.same_site = same_site.unwrap_or(SameSitePolicy::Unspecified);
+                self.handle_set_cookie(&engine_guard, &tab_id, cookie).await
+            }
+            IpcCommand::DeleteCookie { tab_id, name, domain } => {
+                self.handle_delete_cookie(&engine_guard, &tab_id, &name, &domain).await
+            }
             IpcCommand::Shutdown => {
                 info!("Shutdown command received");
                 IpcResponse::success()
@@ -415,15 +486,16 @@ impl BrowserCommandHandler {
         cdp: &Arc<crate::api::cdp_client::CdpClient>,
         targets_before: &std::collections::HashSet<String>,
     ) -> Option<String> {
-        for _ in 0..30 {
-            if let Ok(ids) = cdp.list_page_target_ids().await {
-                if let Some(new_id) = ids.iter().find(|id| !targets_before.contains(*id)) {
-                    return Some(cdp.page_ws_url(new_id));
-                }
-            }
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-        }
-        None
+        let policy = RetryPolicy::fixed(30, std::time::Duration::from_millis(100));
+        let new_id = retry(&policy, || async move {
+            let ids = cdp.list_page_target_ids().await?;
+            ids.into_iter()
+                .find(|id| !targets_before.contains(id))
+                .ok_or_else(|| "new target not listed yet".to_string())
+        })
+        .await
+        .ok()?;
+        Some(cdp.page_ws_url(&new_id))
     }
 
     /// Registers the complete stealth identity of a tab on its CDP target:
@@ -564,11 +636,414 @@ impl BrowserCommandHandler {
         }
     }
 
+    async fn handle_set_active_tab(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        tab_id: &str,
+    ) -> IpcResponse {
+        let uuid = match Uuid::parse_str(tab_id) {
+            Ok(u) => u,
+            Err(_) => return IpcResponse::error("Invalid tab ID"),
+        };
+
+        let result = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => e.set_active_tab(uuid).await,
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => e.set_active_tab(uuid).await,
+            None => return IpcResponse::error("No browser engine available for SetActiveTab"),
+        };
+
+        match result {
+            Ok(()) => IpcResponse::success_with_tab(uuid.to_string()),
+            Err(e) => IpcResponse::error(e.to_string()),
+        }
+    }
+
+    async fn handle_get_active_tab(&self, engine: &Option<BrowserEngineWrapper>) -> IpcResponse {
+        let active = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => e.active_tab().await,
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => e.active_tab().await,
+            None => return IpcResponse::error("No browser engine available for GetActiveTab"),
+        };
+
+        IpcResponse::success_with_data(serde_json::json!({
+            "tab_id": active.map(|id| id.to_string()),
+        }))
+    }
+
+    async fn handle_get_recent_events(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        tab_id: Option<&str>,
+        limit: usize,
+    ) -> IpcResponse {
+        let uuid = match tab_id.map(Uuid::parse_str).transpose() {
+            Ok(u) => u,
+            Err(_) => return IpcResponse::error("Invalid tab ID"),
+        };
+
+        let result = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => e.get_recent_events(uuid, limit).await,
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => e.get_recent_events(uuid, limit).await,
+            None => return IpcResponse::error("No browser engine available for GetRecentEvents"),
+        };
+
+        match result {
+            Ok(events) => IpcResponse::success_with_data(serde_json::json!({
+                "count": events.len(),
+                "events": events,
+            })),
+            Err(e) => IpcResponse::error(e.to_string()),
+        }
+    }
+
+    async fn handle_clear_event_buffer(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        tab_id: Option<&str>,
+    ) -> IpcResponse {
+        let uuid = match tab_id.map(Uuid::parse_str).transpose() {
+            Ok(u) => u,
+            Err(_) => return IpcResponse::error("Invalid tab ID"),
+        };
+
+        let result = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => e.clear_event_buffer(uuid).await,
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => e.clear_event_buffer(uuid).await,
+            None => return IpcResponse::error("No browser engine available for ClearEventBuffer"),
+        };
+
+        match result {
+            Ok(()) => IpcResponse::success(),
+            Err(e) => IpcResponse::error(e.to_string()),
+        }
+    }
+
+    async fn handle_set_request_block_list(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        patterns: Vec<String>,
+    ) -> IpcResponse {
+        let interceptor: Option<Arc<dyn RequestInterceptor>> = if patterns.is_empty() {
+            None
+        } else {
+            match BlockListInterceptor::new(&patterns) {
+                Ok(blocker) => Some(Arc::new(blocker)),
+                Err(e) => return IpcResponse::error(e.to_string()),
+            }
+        };
+
+        let result = match (engine, interceptor) {
+            (Some(BrowserEngineWrapper::Mock(e)), Some(i)) => e.set_request_interceptor(i).await,
+            (Some(BrowserEngineWrapper::Mock(e)), None) => e.clear_request_interceptor().await,
+            #[cfg(feature = "cef-browser")]
+            (Some(BrowserEngineWrapper::Cef(e)), Some(i)) => e.set_request_interceptor(i).await,
+            #[cfg(feature = "cef-browser")]
+            (Some(BrowserEngineWrapper::Cef(e)), None) => e.clear_request_interceptor().await,
+            (None, _) => {
+                return IpcResponse::error("No browser engine available for SetRequestBlockList")
+            }
+        };
+
+        match result {
+            Ok(()) => IpcResponse::success_with_data(serde_json::json!({ "patterns": patterns })),
+            Err(e) => IpcResponse::error(e.to_string()),
+        }
+    }
+
+    async fn handle_start_har_recording(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        tab_id: &str,
+    ) -> IpcResponse {
+        let uuid = match Uuid::parse_str(tab_id) {
+            Ok(u) => u,
+            Err(_) => return IpcResponse::error("Invalid tab ID"),
+        };
+
+        let result = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => e.enable_har_recording(uuid).await,
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => e.enable_har_recording(uuid).await,
+            None => return IpcResponse::error("No browser engine available for StartHarRecording"),
+        };
+
+        match result {
+            Ok(_) => IpcResponse::success_with_data(serde_json::json!({ "recording": true })),
+            Err(e) => IpcResponse::error(e.to_string()),
+        }
+    }
+
+    /// Returns the complete HAR document (`{"log": ...}`) as data.
+    async fn handle_stop_har_recording(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        tab_id: &str,
+    ) -> IpcResponse {
+        let uuid = match Uuid::parse_str(tab_id) {
+            Ok(u) => u,
+            Err(_) => return IpcResponse::error("Invalid tab ID"),
+        };
+
+        let result = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => e.stop_har_recording(uuid).await,
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => e.stop_har_recording(uuid).await,
+            None => return IpcResponse::error("No browser engine available for StopHarRecording"),
+        };
+
+        match result {
+            Ok(log) => IpcResponse::success_with_data(log.to_value()),
+            Err(e) => IpcResponse::error(e.to_string()),
+        }
+    }
+
+    async fn handle_get_cookies(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        tab_id: &str,
+        url: Option<&str>,
+    ) -> IpcResponse {
+        let uuid = match Uuid::parse_str(tab_id) {
+            Ok(u) => u,
+            Err(_) => return IpcResponse::error("Invalid tab ID"),
+        };
+
+        let result = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => e.get_cookies(uuid, url).await,
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => e.get_cookies(uuid, url).await,
+            None => return IpcResponse::error("No browser engine available for GetCookies"),
+        };
+
+        match result {
+            Ok(cookies) => IpcResponse::success_with_data(serde_json::json!({
+                "count": cookies.len(),
+                "cookies": cookies,
+            })),
+            Err(e) => IpcResponse::error(e.to_string()),
+        }
+    }
+
+    /// Sets `cookie`; an empty domain defaults to the host of the tab.
+    async fn handle_set_cookie(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        tab_id: &str,
+        mut cookie: Cookie,
+    ) -> IpcResponse {
+        let uuid = match Uuid::parse_str(tab_id) {
+            Ok(u) => u,
+            Err(_) => return IpcResponse::error("Invalid tab ID"),
+        };
+
+        let result = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => {
+                if cookie.domain.is_empty() {
+                    let url = e.get_tab(uuid).await.ok().flatten().map(|t| t.url);
+                    cookie.domain = url.as_deref().and_then(url_host).unwrap_or_default();
+                }
+                e.set_cookie(uuid, cookie).await
+            }
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => {
+                if cookie.domain.is_empty() {
+                    let url = e.get_tabs_sync().into_iter().find(|t| t.id == uuid).map(|t| t.url);
+                    cookie.domain = url.as_deref().and_then(url_host).unwrap_or_default();
+                }
+                e.set_cookie(uuid, cookie).await
+            }
+            None => return IpcResponse::error("No browser engine available for SetCookie"),
+        };
+
+        match result {
+            Ok(()) => IpcResponse::success(),
+            Err(e) => IpcResponse::error(e.to_string()),
+        }
+    }
+
+    async fn handle_delete_cookie(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        tab_id: &str,
+        name: &str,
+        domain: &str,
+    ) -> IpcResponse {
+        let uuid = match Uuid::parse_str(tab_id) {
+            Ok(u) => u,
+            Err(_) => return IpcResponse::error("Invalid tab ID"),
+        };
+
+        let result = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => e.delete_cookie(uuid, name, domain).await,
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => e.delete_cookie(uuid, name, domain).await,
+            None => return IpcResponse::error("No browser engine available for DeleteCookie"),
+        };
+
+        match result {
+            Ok(()) => IpcResponse::success(),
+            Err(e) => IpcResponse::error(e.to_string()),
+        }
+    }
+
+    async fn handle_set_cpu_throttling(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        tab_id: &str,
+        rate: f64,
+    ) -> IpcResponse {
+        let uuid = match Uuid::parse_str(tab_id) {
+            Ok(u) => u,
+            Err(_) => return IpcResponse::error("Invalid tab ID"),
+        };
+        if let Err(e) = crate::browser::validate_cpu_throttling_rate(rate) {
+            return IpcResponse::error(e.to_string());
+        }
+
+        let result = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => e.set_cpu_throttling(uuid, rate).await,
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) if e.capabilities().cpu_throttling => {
+                e.set_cpu_throttling(uuid, rate).await
+            }
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => {
+                // CEF cannot throttle natively; go through DevTools instead
+                let Some(ref cdp) = self.cdp_client else {
+                    return IpcResponse::error(
+                        "CPU throttling requires a CDP port with the CEF engine",
+                    );
+                };
+                let Some(url) = e
+                    .get_tabs_sync()
+                    .into_iter()
+                    .find(|t| t.id == uuid)
+                    .map(|t| t.url.clone())
+                else {
+                    return IpcResponse::error(format!("Tab not found: {}", tab_id));
+                };
+                match self.resolve_ws_url_result(&uuid, &url).await {
+                    Ok(ws_url) => cdp
+                        .set_cpu_throttling_rate(&ws_url, rate)
+                        .await
+                        .map_err(|e| anyhow::anyhow!(e)),
+                    Err(e) => Err(anyhow::anyhow!(e)),
+                }
+            }
+            None => return IpcResponse::error("No browser engine available for SetCpuThrottling"),
+        };
+
+        if let Err(e) = result {
+            return IpcResponse::error(e.to_string());
+        }
+
+        // Throttled pages need slower input to keep event spacing human
+        #[cfg(feature = "cef-browser")]
+        for warning in crate::browser::CefInputConfig::default()
+            .validate_realism_under_throttling(&crate::input::HumanTiming::fast(), rate)
+        {
+            warn!("Input realism warning for tab {}: {}", tab_id, warning);
+        }
+
+        IpcResponse::success_with_data(serde_json::json!({
+            "tab_id": tab_id,
+            "rate": rate,
+        }))
+    }
+
+    /// Grants permissions to an origin, or resets all grants when `grant`
+    /// is `None`.
+    async fn handle_grant_permissions(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        tab_id: &str,
+        grant: Option<(&str, Vec<Permission>)>,
+    ) -> IpcResponse {
+        let uuid = match Uuid::parse_str(tab_id) {
+            Ok(u) => u,
+            Err(_) => return IpcResponse::error("Invalid tab ID"),
+        };
+        let grant = match grant {
+            Some((origin, permissions)) => match normalize_origin(origin) {
+                Ok(origin) => Some((origin, permissions)),
+                Err(e) => return IpcResponse::error(e.to_string()),
+            },
+            None => None,
+        };
+
+        let result = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => match grant.clone() {
+                Some((origin, permissions)) => {
+                    e.grant_permissions(uuid, &origin, permissions).await
+                }
+                None => e.reset_permissions(uuid).await,
+            },
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) if e.capabilities().permission_grants => {
+                match grant.clone() {
+                    Some((origin, permissions)) => {
+                        e.grant_permissions(uuid, &origin, permissions).await
+                    }
+                    None => e.reset_permissions(uuid).await,
+                }
+            }
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => {
+                // CEF cannot grant natively; go through DevTools instead
+                let Some(ref cdp) = self.cdp_client else {
+                    return IpcResponse::error(
+                        "Permission grants require a CDP port with the CEF engine",
+                    );
+                };
+                let Some(url) = e
+                    .get_tabs_sync()
+                    .into_iter()
+                    .find(|t| t.id == uuid)
+                    .map(|t| t.url.clone())
+                else {
+                    return IpcResponse::error(format!("Tab not found: {}", tab_id));
+                };
+                match self.resolve_ws_url_result(&uuid, &url).await {
+                    Ok(ws_url) => match grant.clone() {
+                        Some((origin, permissions)) => {
+                            let names: Vec<&str> =
+                                permissions.iter().map(|p| p.cdp_name()).collect();
+                            cdp.grant_permissions(&ws_url, &origin, &names).await
+                        }
+                        None => cdp.reset_permissions(&ws_url).await,
+                    }
+                    .map_err(|e| anyhow::anyhow!(e)),
+                    Err(e) => Err(anyhow::anyhow!(e)),
+                }
+            }
+            None => return IpcResponse::error("No browser engine available for permission grants"),
+        };
+
+        if let Err(e) = result {
+            return IpcResponse::error(e.to_string());
+        }
+
+        match grant {
+            Some((origin, permissions)) => IpcResponse::success_with_data(serde_json::json!({
+                "tab_id": tab_id,
+                "origin": origin,
+                "permissions": permissions,
+            })),
+            None => IpcResponse::success(),
+        }
+    }
+
     async fn handle_navigate(
         &self,
         engine: &Option<BrowserEngineWrapper>,
         tab_id: &str,
         url: &str,
+        options: NavigateOptions,
+        referrer_policy: Option<ReferrerPolicy>,
     ) -> IpcResponse {
         let uuid = match Uuid::parse_str(tab_id) {
             Ok(u) => u,
@@ -580,6 +1055,18 @@ impl BrowserCommandHandler {
             return IpcResponse::error(format!("Invalid URL scheme: URL must start with http://, https://, about:, or data: — got: {}", url));
         }
 
+        if let Some(policy) = referrer_policy {
+            let result = match engine {
+                Some(BrowserEngineWrapper::Mock(e)) => e.set_referrer_policy(uuid, policy).await,
+                #[cfg(feature = "cef-browser")]
+                Some(BrowserEngineWrapper::Cef(e)) => e.set_referrer_policy(uuid, policy).await,
+                None => Err(anyhow::anyhow!("No browser engine available")),
+            };
+            if let Err(e) = result {
+                return IpcResponse::error(e.to_string());
+            }
+        }
+
         // Before navigating: ensure the tab's stealth identity is registered on
         // its CDP target. Tabs created via handle_create_tab were initialized
         // synchronously at creation (deterministic ws_url stored). Legacy tabs
@@ -647,9 +1134,15 @@ impl BrowserCommandHandler {
         }
 
         match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => {
+                match e.navigate_with_options(uuid, url, options).await {
+                    Ok(_) => IpcResponse::success(),
+                    Err(e) => IpcResponse::error(e.to_string()),
+                }
+            }
             #[cfg(feature = "cef-browser")]
             Some(BrowserEngineWrapper::Cef(e)) => {
-                match e.navigate(uuid, url).await {
+                match e.navigate_with_options(uuid, url, options).await {
                     Ok(_) => {
                         // Fallback only: when no init-script could be registered for
                         // this tab (no CDP target found), re-inject the tab's OWN
@@ -810,31 +1303,26 @@ impl BrowserCommandHandler {
             }
         }
 
-        // Strategy 2: Main frame click via CEF JS
+        // Strategy 2: Main frame click; the element is scrolled into view
+        // first so its center lies inside the viewport
         match engine {
             #[cfg(feature = "cef-browser")]
             Some(BrowserEngineWrapper::Cef(e)) => {
-                let escaped = selector.replace('\\', "\\\\").replace('\'', "\\'");
-                let js = format!(
-                    r#"(function(){{var el=document.querySelector('{}');if(!el)return null;var r=el.getBoundingClientRect();return {{x:r.x+r.width/2,y:r.y+r.height/2}}}})()"#,
-                    escaped
-                );
-                match e.execute_js_with_result(_uuid, &js).await {
-                    Ok(Some(json_str)) => {
-                        match serde_json::from_str::<serde_json::Value>(&json_str) {
-                            Ok(coords) if !coords.is_null() => {
-                                let cx = coords["x"].as_f64().unwrap_or(0.0) as i32;
-                                let cy = coords["y"].as_f64().unwrap_or(0.0) as i32;
-                                match e.click(_uuid, cx, cy, 0).await {
-                                    Ok(_) => IpcResponse::success(),
-                                    Err(e) => IpcResponse::error(e.to_string()),
-                                }
-                            }
-                            _ => IpcResponse::error(format!("Element not found: {}", selector)),
+                let align = crate::browser::ScrollAlign::default();
+                match e.scroll_into_view(_uuid, selector, align).await {
+                    Ok(bounding_box) => {
+                        let (cx, cy) = bounding_box.center();
+                        match e.click(_uuid, cx as i32, cy as i32, 0).await {
+                            Ok(_) => IpcResponse::success(),
+                            Err(e) => IpcResponse::error(e.to_string()),
                         }
                     }
-                    Ok(None) => IpcResponse::error(format!("Element not found: {}", selector)),
-                    Err(e) => IpcResponse::error(e.to_string()),
+                    Err(err) => match err.downcast_ref::<BrowserError>() {
+                        Some(BrowserError::DomQueryFailed { .. }) => {
+                            IpcResponse::error(format!("Element not found: {}", selector))
+                        }
+                        _ => IpcResponse::error(err.to_string()),
+                    },
                 }
             }
             _ => {
@@ -958,6 +1446,7 @@ impl BrowserCommandHandler {
 
     /// Attach already-saved local files to a file input via CDP, so a headless
     /// browser (which has no native file dialog) can still complete uploads.
+    /// Without CDP the CEF engine injects the files into the page instead.
     async fn handle_set_file_input(
         &self,
         engine: &Option<BrowserEngineWrapper>,
@@ -988,7 +1477,17 @@ impl BrowserCommandHandler {
                 }
             }
         }
-        IpcResponse::error("CDP not available or tab not found for file input")
+        match engine {
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => {
+                let paths: Vec<std::path::PathBuf> = paths.iter().map(Into::into).collect();
+                match e.set_input_files(uuid, selector, &paths).await {
+                    Ok(()) => IpcResponse::success(),
+                    Err(e) => IpcResponse::error(e.to_string()),
+                }
+            }
+            _ => IpcResponse::error("CDP not available or tab not found for file input"),
+        }
     }
 
     async fn handle_scroll(
@@ -1094,7 +1593,7 @@ impl BrowserCommandHandler {
         tab_id: &str,
         params: ScreenshotParams<'_>,
     ) -> IpcResponse {
-        let ScreenshotParams { format, quality, full_page, selector: _selector, clip } = params;
+        let ScreenshotParams { format, quality, full_page, selector: _selector, clip, include_scrollbars } = params;
         let uuid = match Uuid::parse_str(tab_id) {
             Ok(u) => u,
             Err(_) => return IpcResponse::error("Invalid tab ID"),
@@ -1103,6 +1602,7 @@ impl BrowserCommandHandler {
         let screenshot_format = match format.to_lowercase().as_str() {
             "jpeg" | "jpg" => ScreenshotFormat::Jpeg,
             "webp" => ScreenshotFormat::WebP,
+            "avif" => ScreenshotFormat::Avif,
             _ => ScreenshotFormat::Png,
         };
 
@@ -1115,6 +1615,7 @@ impl BrowserCommandHandler {
             quality: quality.unwrap_or(90),
             full_page,
             clip_region,
+            include_scrollbars,
             ..Default::default()
         };
 
@@ -1127,7 +1628,9 @@ impl BrowserCommandHandler {
                             "screenshot": screenshot.data,
                             "width": screenshot.width,
                             "height": screenshot.height,
-                            "format": format
+                            "format": format,
+                            "encoding": screenshot.encoding,
+                            "truncated": screenshot.truncated
                         }))
                     }
                     Err(e) => IpcResponse::error(e.to_string()),
@@ -1184,7 +1687,10 @@ impl BrowserCommandHandler {
                             }
                         } else {
                             // No frame_id — evaluate in main context
-                            match cdp.evaluate(&ws_url, script).await {
+                            match cdp
+                                .evaluate_with_options(&ws_url, script, &EvalOptions::default())
+                                .await
+                            {
                                 Ok(result) => {
                                     debug!("CDP evaluate succeeded for tab {}", tab_id);
                                     let value: serde_json::Value = serde_json::from_str(&result)
@@ -1202,6 +1708,10 @@ impl BrowserCommandHandler {
                                         "result": value
                                     }));
                                 }
+                                // Re-running an oversized result through CEF would not help
+                                Err(e @ BrowserError::ResultTooLarge { .. }) => {
+                                    return IpcResponse::error(e.to_string());
+                                }
                                 Err(e) => {
                                     debug!("CDP evaluate failed ({}), falling back to CEF", e);
                                 }
@@ -1253,6 +1763,35 @@ impl BrowserCommandHandler {
         }
     }
 
+    /// Evaluates `script` through the engine's V8 round trip; unlike
+    /// [`handle_evaluate`](Self::handle_evaluate) there is no CDP path, so
+    /// the timeout applies to the engine call itself.
+    async fn handle_evaluate_js(
+        &self,
+        engine: &Option<BrowserEngineWrapper>,
+        tab_id: &str,
+        script: &str,
+        timeout_ms: u64,
+    ) -> IpcResponse {
+        let uuid = match Uuid::parse_str(tab_id) {
+            Ok(u) => u,
+            Err(_) => return IpcResponse::error("Invalid tab ID"),
+        };
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+
+        let result = match engine {
+            Some(BrowserEngineWrapper::Mock(e)) => e.execute_js_with_timeout(uuid, script, timeout).await,
+            #[cfg(feature = "cef-browser")]
+            Some(BrowserEngineWrapper::Cef(e)) => e.execute_js_with_timeout(uuid, script, timeout).await,
+            None => return IpcResponse::error("No browser engine available for EvaluateJs"),
+        };
+
+        match result {
+            Ok(value) => IpcResponse::eval_result(value),
+            Err(e) => IpcResponse::error(e.to_string()),
+        }
+    }
+
     async fn handle_annotate(
         &self,
         engine: &Option<BrowserEngineWrapper>,
@@ -1710,4 +2249,66 @@ mod tests {
         assert!(ws_url_free_for_tab(&ws("A"), &tab_a, &bindings));
         assert!(!ws_url_free_for_tab(&ws("B"), &tab_a, &bindings));
     }
+
+    #[tokio::test]
+    async fn test_execute_endpoint_returns_script_result() {
+        use axum::body::{to_bytes, Body};
+        use axum::http::{Request, StatusCode};
+        use serde_json::{json, Value};
+        use std::time::Duration;
+        use tower::ServiceExt;
+
+        use crate::api::ipc::{IpcChannel, IpcProcessorTask};
+        use crate::api::server::AppState;
+        use crate::browser::BrowserConfig;
+
+        async fn execute(app: &axum::Router, tab_id: &str, body: Value) -> (StatusCode, Value) {
+            let request = Request::post(format!("/tabs/{}/execute", tab_id))
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, serde_json::from_slice(&body).unwrap())
+        }
+
+        let engine = Arc::new(MockBrowserEngine::new(BrowserConfig::default()).await.unwrap());
+        let tab = engine.create_tab("https://example.com/").await.unwrap();
+        let script = "({ title: document.title, links: document.links.length })";
+        let result = json!({ "title": "Example Domain", "links": 1 });
+        engine.set_script_result(tab.id, script, result.clone()).await.unwrap();
+        let slow_tab = engine.create_tab("https://example.com/").await.unwrap();
+        engine.set_call_duration(slow_tab.id, Duration::from_secs(5)).await.unwrap();
+
+        let handler = BrowserCommandHandler::new();
+        handler.set_engine(BrowserEngineWrapper::Mock(engine)).await;
+        let channel = IpcChannel::new();
+        let task = IpcProcessorTask::spawn(&channel, move |mut processor| async move {
+            handler.run(&mut processor).await;
+        });
+        let app = crate::api::routes::create_router(AppState::new(channel));
+
+        let tab_id = tab.id.to_string();
+        let (status, body) = execute(&app, &tab_id, json!({ "script": script, "timeout_ms": 1000 })).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!({ "result": result, "error": null }));
+
+        // `undefined` is a null result, not an error; the timeout defaults
+        let (status, body) = execute(&app, &tab_id, json!({ "script": "void 0" })).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!({ "result": null, "error": null }));
+
+        let (status, body) =
+            execute(&app, &slow_tab.id.to_string(), json!({ "script": script, "timeout_ms": 20 })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["result"], Value::Null);
+        assert!(body["error"].as_str().unwrap().starts_with("Timeout after 20ms"));
+
+        let (status, body) = execute(&app, "not-a-uuid", json!({ "script": script })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Invalid tab ID");
+
+        assert!(task.shutdown(Duration::from_secs(1)).await);
+    }
 }
@@ -18,6 +18,10 @@ use tokio::sync::RwLock;
 use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
 use tracing::{debug, error, warn};
 
+use crate::browser::EvalOptions;
+use crate::error::BrowserError;
+use crate::retry::{retry, RetryPolicy};
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -73,6 +77,8 @@ pub struct CdpClient {
     cmd_id: AtomicI64,
     /// Cache: target_id -> WebSocket connection
     connections: RwLock<HashMap<String, Arc<RwLock<WsStream>>>>,
+    /// Retry policy for WebSocket connects
+    retry_policy: RetryPolicy,
 }
 
 impl CdpClient {
@@ -82,9 +88,16 @@ impl CdpClient {
             port,
             cmd_id: AtomicI64::new(1),
             connections: RwLock::new(HashMap::new()),
+            retry_policy: RetryPolicy::default(),
         }
     }
 
+    /// Set the retry policy used when connecting to a target.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
     /// Get the next command ID.
     fn next_id(&self) -> i64 {
         self.cmd_id.fetch_add(1, Ordering::Relaxed)
@@ -199,6 +212,38 @@ impl CdpClient {
         Ok(())
     }
 
+    /// Slows down the target's CPU via CDP `Emulation.setCPUThrottlingRate`.
+    /// A rate of 4 is a 4x slowdown; 1 disables throttling.
+    pub async fn set_cpu_throttling_rate(&self, ws_url: &str, rate: f64) -> Result<(), String> {
+        let params = serde_json::json!({ "rate": rate });
+        self.send_command(ws_url, "Emulation.setCPUThrottlingRate", params)
+            .await?;
+        Ok(())
+    }
+
+    /// Grants `permissions` (CDP `PermissionType` names) to `origin` via
+    /// `Browser.grantPermissions`; all other permissions of the origin are
+    /// denied. Applies to the target's whole browser context.
+    pub async fn grant_permissions(
+        &self,
+        ws_url: &str,
+        origin: &str,
+        permissions: &[&str],
+    ) -> Result<(), String> {
+        let params = serde_json::json!({ "origin": origin, "permissions": permissions });
+        self.send_command(ws_url, "Browser.grantPermissions", params)
+            .await?;
+        Ok(())
+    }
+
+    /// Revokes all grants made with [`grant_permissions`](Self::grant_permissions)
+    /// via `Browser.resetPermissions`.
+    pub async fn reset_permissions(&self, ws_url: &str) -> Result<(), String> {
+        self.send_command(ws_url, "Browser.resetPermissions", serde_json::json!({}))
+            .await?;
+        Ok(())
+    }
+
     // ========================================================================
     // WebSocket Connection Management
     // ========================================================================
@@ -215,7 +260,7 @@ impl CdpClient {
 
         // Create new connection
         debug!("CDP: connecting to {}", ws_url);
-        let (ws_stream, _) = connect_async(ws_url)
+        let (ws_stream, _) = retry(&self.retry_policy, || connect_async(ws_url))
             .await
             .map_err(|e| format!("CDP WebSocket connect failed: {}", e))?;
 
@@ -366,7 +411,44 @@ impl CdpClient {
             "awaitPromise": true,
             "userGesture": true
         });
+        self.run_evaluate(ws_url, params).await
+    }
+
+    /// Like [`evaluate`](Self::evaluate), within the budget of `options`.
+    ///
+    /// V8 terminates a script still running after `options.timeout_ms`
+    /// (the `timeout` parameter of `Runtime.evaluate`), and a result longer
+    /// than `options.max_result_bytes` fails with
+    /// [`BrowserError::ResultTooLarge`]. Other failures are
+    /// [`BrowserError::ScriptEvaluationFailed`].
+    pub async fn evaluate_with_options(
+        &self,
+        ws_url: &str,
+        expression: &str,
+        options: &EvalOptions,
+    ) -> Result<String, BrowserError> {
+        let params = serde_json::json!({
+            "expression": expression,
+            "returnByValue": true,
+            "awaitPromise": true,
+            "userGesture": true,
+            "timeout": options.timeout_ms
+        });
+        let result = self
+            .run_evaluate(ws_url, params)
+            .await
+            .map_err(|reason| BrowserError::ScriptEvaluationFailed { reason })?;
+        if result.len() > options.max_result_bytes {
+            return Err(BrowserError::ResultTooLarge {
+                size: result.len(),
+                max_bytes: options.max_result_bytes,
+            });
+        }
+        Ok(result)
+    }
 
+    /// Sends `Runtime.evaluate` and unwraps its result value.
+    async fn run_evaluate(&self, ws_url: &str, params: serde_json::Value) -> Result<String, String> {
         let result = self.send_command(ws_url, "Runtime.evaluate", params).await?;
 
         // Parse CDP result format: { "result": { "type": "string", "value": "..." } }
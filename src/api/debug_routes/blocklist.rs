@@ -0,0 +1,99 @@
+//! Request block list endpoints.
+//!
+//! Installs a static block list as the engine's request interceptor: every
+//! request to a matching URL, in every tab, is cancelled and shows up among
+//! the failed requests as `net::ERR_BLOCKED_BY_CLIENT`.
+//!
+//! ## Endpoints
+//! - `PUT /debug/blocklist`    – Replace the block list
+//! - `DELETE /debug/blocklist` – Remove the block list
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::put,
+    Json, Router,
+};
+use serde::Deserialize;
+use tracing::error;
+
+use crate::api::ipc::{IpcCommand, IpcMessage};
+use crate::api::routes::ApiResponse;
+use crate::api::server::AppState;
+
+// ============================================================================
+// Request Body
+// ============================================================================
+
+/// Request body for `PUT /debug/blocklist`.
+#[derive(Debug, Deserialize)]
+pub struct BlockListRequest {
+    /// URL patterns to block, e.g. `*.doubleclick.net` or `regex:/ads/`.
+    /// An empty list removes the block list.
+    pub patterns: Vec<String>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Installs `patterns` as the block list and converts the IPC response.
+async fn send_block_list(state: &AppState, patterns: Vec<String>) -> axum::response::Response {
+    let command = IpcCommand::SetRequestBlockList { patterns };
+    match state
+        .ipc_channel
+        .send_command(IpcMessage::Command(command))
+        .await
+    {
+        Ok(response) if response.success => Json(ApiResponse::success(
+            response.data.unwrap_or(serde_json::Value::Null),
+        ))
+        .into_response(),
+        Ok(response) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(
+                response
+                    .error
+                    .unwrap_or_else(|| "Block list update failed".to_string()),
+            )),
+        )
+            .into_response(),
+        Err(e) => {
+            error!("Block list update failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(format!(
+                    "Block list update failed: {}",
+                    e
+                ))),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// PUT /debug/blocklist — Replace the block list.
+async fn set_block_list(
+    State(state): State<AppState>,
+    Json(request): Json<BlockListRequest>,
+) -> impl IntoResponse {
+    send_block_list(&state, request.patterns).await
+}
+
+/// DELETE /debug/blocklist — Remove the block list.
+async fn clear_block_list(State(state): State<AppState>) -> impl IntoResponse {
+    send_block_list(&state, Vec::new()).await
+}
+
+// ============================================================================
+// Router
+// ============================================================================
+
+/// Creates the router fragment for block list endpoints.
+pub fn blocklist_routes() -> Router<AppState> {
+    Router::new().route(
+        "/debug/blocklist",
+        put(set_block_list).delete(clear_block_list),
+    )
+}
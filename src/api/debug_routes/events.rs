@@ -0,0 +1,122 @@
+//! Engine event buffer endpoints.
+//!
+//! Exposes the engine's ring buffer of recent events (tab lifecycle,
+//! navigation, console, failed requests, ...) for inspection without a live
+//! WebSocket connection, and lets operators reset it. Unlike most debug
+//! routes, an omitted `tab_id` means all tabs rather than the active tab.
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use tracing::error;
+
+use crate::api::debug_routes::types::TabQuery;
+use crate::api::ipc::{IpcCommand, IpcMessage};
+use crate::api::routes::ApiResponse;
+use crate::api::server::AppState;
+
+// ============================================================================
+// Query Parameters
+// ============================================================================
+
+/// Query parameters for `GET /debug/events`.
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    #[serde(default)]
+    pub tab_id: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    100
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Sends `command` and converts the IPC response into an API response.
+async fn send_event_command(state: &AppState, command: IpcCommand) -> axum::response::Response {
+    match state
+        .ipc_channel
+        .send_command(IpcMessage::Command(command))
+        .await
+    {
+        Ok(response) if response.success => Json(ApiResponse::success(
+            response.data.unwrap_or(serde_json::Value::Null),
+        ))
+        .into_response(),
+        Ok(response) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(response.error.unwrap_or_else(
+                || "Event buffer request failed".to_string(),
+            ))),
+        )
+            .into_response(),
+        Err(e) => {
+            error!("Event buffer request failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(format!(
+                    "Event buffer request failed: {}",
+                    e
+                ))),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// GET /debug/events?tab_id=&limit= — Recent engine events, oldest first.
+async fn get_events(
+    State(state): State<AppState>,
+    Query(query): Query<EventsQuery>,
+) -> impl IntoResponse {
+    let command = IpcCommand::GetRecentEvents {
+        tab_id: query.tab_id,
+        limit: query.limit,
+    };
+    send_event_command(&state, command).await
+}
+
+/// DELETE /debug/events?tab_id= — Clear buffered events.
+async fn clear_events(
+    State(state): State<AppState>,
+    Query(query): Query<TabQuery>,
+) -> impl IntoResponse {
+    let command = IpcCommand::ClearEventBuffer {
+        tab_id: query.tab_id,
+    };
+    send_event_command(&state, command).await
+}
+
+// ============================================================================
+// Router
+// ============================================================================
+
+/// Creates the router fragment for event buffer endpoints.
+pub fn event_routes() -> Router<AppState> {
+    Router::new().route("/debug/events", get(get_events).delete(clear_events))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_query_defaults() {
+        let query: EventsQuery = serde_json::from_str("{}").expect("deserialize");
+        assert!(query.tab_id.is_none());
+        assert_eq!(query.limit, 100);
+    }
+}
@@ -1,20 +1,26 @@
 //! Debug route handlers for website inspection and debugging.
 //!
 //! Provides REST endpoints for:
+//! - **Block list**: URL patterns whose requests are cancelled in all tabs
 //! - **Console**: Captured browser console messages (log, warn, error)
 //! - **Cookies**: CRUD operations for browser cookies
 //! - **CSS Inspector**: Computed styles, matched rules, box model
+//! - **Events**: Recent engine events from the event buffer
 //! - **Network**: Request/response capture via JS instrumentation
 //! - **Performance**: Navigation timing, resource timing, Web Vitals, memory
+//! - **Permissions**: Pre-granted permissions per origin
 
 pub mod types;
+pub mod blocklist;
 pub mod captcha;
 pub mod console;
 pub mod consent;
 pub mod cookies;
 pub mod css_inspector;
+pub mod events;
 pub mod network;
 pub mod performance;
+pub mod permissions;
 pub mod popups;
 
 use axum::Router;
@@ -25,12 +31,15 @@ pub use console::{ConsoleLogBuffer, ConsoleLogEntry, create_log_entry};
 /// Combined router for all debug endpoints under /debug/*.
 pub fn debug_routes() -> Router<AppState> {
     Router::new()
+        .merge(blocklist::blocklist_routes())
         .merge(captcha::captcha_routes())
         .merge(console::console_routes())
         .merge(consent::consent_routes())
         .merge(cookies::cookie_routes())
         .merge(css_inspector::css_routes())
+        .merge(events::event_routes())
         .merge(network::network_routes())
         .merge(performance::performance_routes())
+        .merge(permissions::permission_routes())
         .merge(popups::popup_routes())
 }
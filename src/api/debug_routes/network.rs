@@ -17,6 +17,7 @@ use serde::{Deserialize, Serialize};
 use crate::api::debug_routes::types::{evaluate_in_tab, resolve_tab_id, TabQuery};
 use crate::api::routes::ApiResponse;
 use crate::api::server::AppState;
+use crate::browser::UrlPattern;
 
 // ============================================================================
 // Types
@@ -61,6 +62,9 @@ pub struct NetworkEntriesQuery {
     pub resource_type: Option<String>,
     #[serde(default)]
     pub url_contains: Option<String>,
+    /// Exact, glob, or `regex:` pattern (see [`UrlPattern`]).
+    #[serde(default)]
+    pub url_pattern: Option<String>,
     #[serde(default = "default_limit")]
     pub limit: usize,
 }
@@ -222,7 +226,7 @@ async fn stop_capture(
     }
 }
 
-/// GET /debug/network/entries?tab_id=&resource_type=&url_contains=&limit= — Retrieve captured entries.
+/// GET /debug/network/entries?tab_id=&resource_type=&url_contains=&url_pattern=&limit= — Retrieve captured entries.
 async fn get_entries(
     State(state): State<AppState>,
     Query(query): Query<NetworkEntriesQuery>,
@@ -250,6 +254,19 @@ async fn get_entries(
                     if let Some(contains) = &query.url_contains {
                         resp.entries.retain(|e| e.url.contains(contains.as_str()));
                     }
+                    if let Some(raw_pattern) = &query.url_pattern {
+                        let pattern = match UrlPattern::parse(raw_pattern) {
+                            Ok(p) => p,
+                            Err(e) => {
+                                return (
+                                    StatusCode::BAD_REQUEST,
+                                    Json(ApiResponse::<NetworkEntriesResponse>::error(e.to_string())),
+                                )
+                                    .into_response();
+                            }
+                        };
+                        resp.entries.retain(|e| pattern.matches(&e.url));
+                    }
                     // Apply limit
                     let limit = query.limit;
                     if resp.entries.len() > limit {
@@ -420,6 +437,7 @@ mod tests {
         assert!(q.tab_id.is_none());
         assert!(q.resource_type.is_none());
         assert!(q.url_contains.is_none());
+        assert!(q.url_pattern.is_none());
         assert_eq!(q.limit, 200);
     }
 
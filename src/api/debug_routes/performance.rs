@@ -9,17 +9,19 @@
 //! - `GET /debug/performance/resources` – Resource timing entries
 //! - `GET /debug/performance/vitals`   – Core Web Vitals (LCP, FCP, CLS, TTFB)
 //! - `GET /debug/performance/memory`   – JS heap memory (Chrome-only)
+//! - `POST /debug/performance/cpu-throttling` – Emulate a slower CPU
 
 use axum::{
     extract::{Query, State},
     http::StatusCode,
     response::IntoResponse,
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::api::debug_routes::types::{resolve_tab_id, evaluate_in_tab, TabQuery};
+use crate::api::ipc::{IpcCommand, IpcMessage};
 use crate::api::routes::ApiResponse;
 use crate::api::server::AppState;
 
@@ -113,6 +115,16 @@ fn default_resource_limit() -> usize {
     100
 }
 
+/// Request body for the CPU throttling endpoint.
+#[derive(Debug, Deserialize)]
+pub struct CpuThrottlingRequest {
+    /// Target tab ID. If omitted, uses the active tab.
+    #[serde(default)]
+    pub tab_id: Option<String>,
+    /// Slowdown factor: 4 runs the page 4x slower, 1 disables throttling.
+    pub rate: f64,
+}
+
 // ============================================================================
 // Handlers
 // ============================================================================
@@ -375,6 +387,53 @@ async fn memory(
     }
 }
 
+/// `POST /debug/performance/cpu-throttling`
+///
+/// Slows down the tab's CPU to test how a page behaves on low-end devices.
+/// With the CEF engine this needs the CDP port (`Emulation.setCPUThrottlingRate`).
+async fn cpu_throttling(
+    State(state): State<AppState>,
+    Json(request): Json<CpuThrottlingRequest>,
+) -> impl IntoResponse {
+    let tab_id = match resolve_tab_id(&state, request.tab_id).await {
+        Some(id) => id,
+        None => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<()>::error("No active tab")),
+            )
+                .into_response();
+        }
+    };
+
+    let command = IpcCommand::SetCpuThrottling {
+        tab_id,
+        rate: request.rate,
+    };
+    match state
+        .ipc_channel
+        .send_command(IpcMessage::Command(command))
+        .await
+    {
+        Ok(response) if response.success => Json(ApiResponse::success(
+            response.data.unwrap_or(serde_json::Value::Null),
+        ))
+        .into_response(),
+        Ok(response) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(response.error.unwrap_or_else(
+                || "CPU throttling failed".to_string(),
+            ))),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!("IPC error: {}", e))),
+        )
+            .into_response(),
+    }
+}
+
 // ============================================================================
 // Router
 // ============================================================================
@@ -388,6 +447,7 @@ pub fn performance_routes() -> Router<AppState> {
         .route("/debug/performance/resources", get(resources))
         .route("/debug/performance/vitals", get(vitals))
         .route("/debug/performance/memory", get(memory))
+        .route("/debug/performance/cpu-throttling", post(cpu_throttling))
 }
 
 // ============================================================================
@@ -0,0 +1,129 @@
+//! Permission grant endpoints.
+//!
+//! Pre-grants permissions such as geolocation or notifications to an origin
+//! so pages never show a permission prompt, and revokes them again. With the
+//! CEF engine this needs the CDP port (`Browser.grantPermissions`).
+//!
+//! ## Endpoints
+//! - `POST /debug/permissions`   – Grant permissions to an origin
+//! - `DELETE /debug/permissions` – Revoke all granted permissions
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::post,
+    Json, Router,
+};
+use serde::Deserialize;
+use tracing::error;
+
+use crate::api::debug_routes::types::{resolve_tab_id, TabQuery};
+use crate::api::ipc::{IpcCommand, IpcMessage};
+use crate::api::routes::ApiResponse;
+use crate::api::server::AppState;
+use crate::browser::Permission;
+
+// ============================================================================
+// Request Body
+// ============================================================================
+
+/// Request body for `POST /debug/permissions`.
+#[derive(Debug, Deserialize)]
+pub struct GrantPermissionsRequest {
+    /// Target tab ID. If omitted, uses the active tab.
+    #[serde(default)]
+    pub tab_id: Option<String>,
+    /// Origin to grant to, e.g. `https://maps.example.com`.
+    pub origin: String,
+    /// Permissions to grant; all others are denied for the origin.
+    pub permissions: Vec<Permission>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Sends `command` and converts the IPC response into an API response.
+async fn send_permission_command(
+    state: &AppState,
+    command: IpcCommand,
+) -> axum::response::Response {
+    match state
+        .ipc_channel
+        .send_command(IpcMessage::Command(command))
+        .await
+    {
+        Ok(response) if response.success => Json(ApiResponse::success(
+            response.data.unwrap_or(serde_json::Value::Null),
+        ))
+        .into_response(),
+        Ok(response) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(
+                response
+                    .error
+                    .unwrap_or_else(|| "Permission request failed".to_string()),
+            )),
+        )
+            .into_response(),
+        Err(e) => {
+            error!("Permission request failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(format!(
+                    "Permission request failed: {}",
+                    e
+                ))),
+            )
+                .into_response()
+        }
+    }
+}
+
+fn no_active_tab() -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ApiResponse::<()>::error("No active tab")),
+    )
+        .into_response()
+}
+
+/// POST /debug/permissions — Grant permissions to an origin.
+async fn grant_permissions(
+    State(state): State<AppState>,
+    Json(request): Json<GrantPermissionsRequest>,
+) -> impl IntoResponse {
+    let Some(tab_id) = resolve_tab_id(&state, request.tab_id).await else {
+        return no_active_tab();
+    };
+    let command = IpcCommand::GrantPermissions {
+        tab_id,
+        origin: request.origin,
+        permissions: request.permissions,
+    };
+    send_permission_command(&state, command).await
+}
+
+/// DELETE /debug/permissions?tab_id= — Revoke all granted permissions.
+async fn reset_permissions(
+    State(state): State<AppState>,
+    Query(query): Query<TabQuery>,
+) -> impl IntoResponse {
+    let Some(tab_id) = resolve_tab_id(&state, query.tab_id).await else {
+        return no_active_tab();
+    };
+    send_permission_command(&state, IpcCommand::ResetPermissions { tab_id }).await
+}
+
+// ============================================================================
+// Router
+// ============================================================================
+
+/// Creates the router fragment for permission grant endpoints.
+pub fn permission_routes() -> Router<AppState> {
+    Router::new().route(
+        "/debug/permissions",
+        post(grant_permissions).delete(reset_permissions),
+    )
+}
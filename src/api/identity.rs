@@ -16,10 +16,11 @@
 //! so `Accept-Language` header == `navigator.languages` and
 //! HTTP `User-Agent` == `navigator.userAgent`.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::stealth::fingerprint::ScreenResolution;
-use crate::stealth::StealthConfig;
+use crate::stealth::{StealthConfig, TimezoneConfig};
 
 // ============================================================================
 // API types
@@ -29,7 +30,7 @@ use crate::stealth::StealthConfig;
 ///
 /// Deserializes from either a string (`"random"`, `"consistent:<seed>"`)
 /// or an explicit object with per-field overrides.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum IdentitySpec {
     /// Named profile: `"random"` or `"consistent:<seed>"`.
@@ -39,7 +40,7 @@ pub enum IdentitySpec {
 }
 
 /// Screen dimensions for an explicit identity.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScreenSpec {
     pub width: u32,
     pub height: u32,
@@ -48,7 +49,7 @@ pub struct ScreenSpec {
 /// Explicit identity field overrides. All fields optional; anything omitted
 /// is filled consistently from the base profile (`random_chrome()` or
 /// `consistent:<seed>` when `seed` is given).
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct IdentityOverrides {
     /// Optional seed for the base profile (deterministic fill of missing fields).
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -176,6 +177,10 @@ fn apply_overrides(config: &mut StealthConfig, o: &IdentityOverrides) -> Result<
         config.navigator.device_memory = dm;
     }
 
+    for warning in config.navigator.validate() {
+        tracing::warn!("Identity override: {}", warning);
+    }
+
     if let Some(vendor) = &o.webgl_vendor {
         config.webgl.vendor = vendor.clone();
     }
@@ -196,6 +201,7 @@ fn apply_overrides(config: &mut StealthConfig, o: &IdentityOverrides) -> Result<
             return Err("timezone must not be empty".to_string());
         }
         config.fingerprint.timezone = tz.clone();
+        config.timezone = TimezoneConfig::new(tz.clone());
         if let Some(offset) = timezone_offset_minutes(tz) {
             config.fingerprint.timezone_offset = offset;
         }
@@ -206,7 +212,7 @@ fn apply_overrides(config: &mut StealthConfig, o: &IdentityOverrides) -> Result<
 }
 
 /// UTC offset in minutes for common IANA timezones (same sign convention as
-/// `FingerprintGenerator::get_timezone`: positive = east of UTC).
+/// `LocaleProfile::default_locales`: positive = east of UTC).
 fn timezone_offset_minutes(tz: &str) -> Option<i32> {
     let offset = match tz {
         "America/New_York" => -300,
@@ -7,9 +7,15 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tokio::sync::{mpsc, oneshot, RwLock};
-use tracing::{debug, error, warn};
+use tokio::sync::{mpsc, oneshot, watch, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::api::websocket::{BinaryFrame, WebSocketHandler};
+use crate::browser::{Permission, ReferrerPolicy, SameSitePolicy};
 
 /// Command ID counter for correlation
 static NEXT_COMMAND_ID: AtomicU64 = AtomicU64::new(1);
@@ -18,7 +24,7 @@ static NEXT_COMMAND_ID: AtomicU64 = AtomicU64::new(1);
 type IpcCommandMessage = (u64, IpcCommand, oneshot::Sender<IpcResponse>);
 
 /// IPC commands for browser control
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "type")]
 pub enum IpcCommand {
     /// Create a new tab
@@ -45,6 +51,18 @@ pub enum IpcCommand {
     Navigate {
         tab_id: String,
         url: String,
+        /// Skip the HTTP cache for this navigation
+        #[serde(default)]
+        bypass_cache: bool,
+        /// Redirect limit; the engine default when absent
+        #[serde(default)]
+        max_redirects: Option<u32>,
+        /// `Referer` of the top-level request
+        #[serde(default)]
+        referrer: Option<String>,
+        /// Referrer policy for this and later navigations of the tab
+        #[serde(default)]
+        referrer_policy: Option<ReferrerPolicy>,
     },
 
     /// Go back in history
@@ -131,6 +149,14 @@ pub enum IpcCommand {
         frame_id: Option<String>,
     },
 
+    /// Evaluate JavaScript in the main frame and wait up to `timeout_ms`
+    /// for its JSON result (answered with [`IpcResponse::eval_result`])
+    EvaluateJs {
+        tab_id: String,
+        script: String,
+        timeout_ms: u64,
+    },
+
     /// Capture screenshot
     CaptureScreenshot {
         tab_id: String,
@@ -143,6 +169,9 @@ pub enum IpcCommand {
         clip_width: Option<f64>,
         clip_height: Option<f64>,
         clip_scale: Option<f64>,
+        /// Keep native scrollbars in the capture (hidden by default)
+        #[serde(default)]
+        include_scrollbars: bool,
     },
 
     /// Scroll page
@@ -275,6 +304,54 @@ pub enum IpcCommand {
         tab_id: String,
     },
 
+    /// Get recently emitted engine events (oldest first)
+    GetRecentEvents {
+        /// Only events of this tab; all events when absent
+        #[serde(default)]
+        tab_id: Option<String>,
+        limit: usize,
+    },
+
+    /// Clear the engine's event buffer
+    ClearEventBuffer {
+        /// Only events of this tab; all events when absent
+        #[serde(default)]
+        tab_id: Option<String>,
+    },
+
+    /// Emulate a slower CPU (rate 4 = 4x slowdown, 1 = off)
+    SetCpuThrottling {
+        tab_id: String,
+        rate: f64,
+    },
+
+    /// Grant permissions to an origin without prompting
+    GrantPermissions {
+        tab_id: String,
+        origin: String,
+        permissions: Vec<Permission>,
+    },
+
+    /// Revoke all granted permissions
+    ResetPermissions {
+        tab_id: String,
+    },
+
+    /// Block requests to URLs matching any pattern, in all tabs (empty = unblock)
+    SetRequestBlockList {
+        patterns: Vec<String>,
+    },
+
+    /// Start recording the tab's network traffic as a HAR log
+    StartHarRecording {
+        tab_id: String,
+    },
+
+    /// Stop the tab's HAR recording and return the HAR document
+    StopHarRecording {
+        tab_id: String,
+    },
+
     /// Set viewport size
     SetViewport {
         tab_id: String,
@@ -294,13 +371,14 @@ pub enum IpcCommand {
         domain: Option<String>,
     },
 
-    /// Get cookies
+    /// Get cookies, all of them or those sent to `url`
     GetCookies {
         tab_id: String,
         url: Option<String>,
     },
 
-    /// Set cookie
+    /// Set cookie (`domain` defaults to the tab's host, `expires` is in
+    /// Unix seconds)
     SetCookie {
         tab_id: String,
         name: String,
@@ -310,6 +388,15 @@ pub enum IpcCommand {
         secure: Option<bool>,
         http_only: Option<bool>,
         expires: Option<i64>,
+        #[serde(default)]
+        same_site: Option<SameSitePolicy>,
+    },
+
+    /// Delete the cookies named `name` of a domain
+    DeleteCookie {
+        tab_id: String,
+        name: String,
+        domain: String,
     },
 
     /// Handle dialog (alert, confirm, prompt)
@@ -402,7 +489,7 @@ fn default_include_text() -> bool {
 }
 
 /// Response to an IPC command
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct IpcResponse {
     /// Whether the command succeeded
     pub success: bool,
@@ -451,6 +538,12 @@ impl IpcResponse {
         }
     }
 
+    /// Create the response to [`IpcCommand::EvaluateJs`]: data is
+    /// `{"value": <result or null>}`
+    pub fn eval_result(value: Option<serde_json::Value>) -> Self {
+        Self::success_with_data(serde_json::json!({ "value": value }))
+    }
+
     /// Create an error response
     pub fn error(message: impl Into<String>) -> Self {
         Self {
@@ -471,6 +564,15 @@ pub enum IpcMessage {
     /// Response from browser to API
     Response(IpcResponse),
 
+    /// Binary data from the browser engine for WebSocket clients
+    ///
+    /// `tag` is one of the `BINARY_TAG_*` constants of [`crate::api::websocket`].
+    BinaryPayload {
+        tag: u8,
+        tab_id: Uuid,
+        data: Vec<u8>,
+    },
+
     /// Shutdown signal
     Shutdown,
 }
@@ -586,6 +688,11 @@ impl IpcChannel {
             IpcMessage::Response(_) => {
                 return Err(IpcError::InvalidMessage("Cannot send response as command".to_string()));
             }
+            IpcMessage::BinaryPayload { .. } => {
+                return Err(IpcError::InvalidMessage(
+                    "Binary payloads are forwarded by the IpcProcessor".to_string(),
+                ));
+            }
         };
 
         let (response_tx, response_rx) = oneshot::channel();
@@ -653,17 +760,66 @@ pub enum IpcError {
 pub struct IpcProcessor {
     /// Receiver for commands
     receiver: mpsc::Receiver<IpcCommandMessage>,
+
+    /// Shutdown signal; once it reads `true` no further commands are received
+    shutdown: Option<watch::Receiver<bool>>,
+
+    /// Destination of binary payloads from the browser engine
+    binary_sink: Option<Arc<WebSocketHandler>>,
 }
 
 impl IpcProcessor {
     /// Create a new processor from a channel
     pub async fn new(channel: &IpcChannel) -> Option<Self> {
-        channel.take_receiver().await.map(|receiver| Self { receiver })
+        channel.take_receiver().await.map(|receiver| Self {
+            receiver,
+            shutdown: None,
+            binary_sink: None,
+        })
+    }
+
+    /// Forward binary payloads to the clients of `ws_handler`
+    pub fn with_binary_sink(mut self, ws_handler: Arc<WebSocketHandler>) -> Self {
+        self.binary_sink = Some(ws_handler);
+        self
+    }
+
+    /// Forward an [`IpcMessage::BinaryPayload`] to WebSocket clients as a binary frame
+    ///
+    /// Fails for other message kinds and when no binary sink is set.
+    pub async fn forward_binary(&self, message: IpcMessage) -> Result<(), IpcError> {
+        let IpcMessage::BinaryPayload { tag, tab_id, data } = message else {
+            return Err(IpcError::InvalidMessage(
+                "Only binary payloads can be forwarded".to_string(),
+            ));
+        };
+        let sink = self.binary_sink.as_ref().ok_or(IpcError::ChannelClosed)?;
+        sink.send_frame(BinaryFrame::new(tag, tab_id, data)).await;
+        Ok(())
+    }
+
+    /// Stop receiving commands once `shutdown` is set to `true`
+    ///
+    /// A command that is already being handled still completes.
+    pub fn with_shutdown(mut self, shutdown: watch::Receiver<bool>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
     }
 
     /// Receive the next command
+    ///
+    /// Returns `None` when the channel is closed or shutdown was signaled.
     pub async fn recv(&mut self) -> Option<IpcCommandMessage> {
-        self.receiver.recv().await
+        let Some(shutdown) = self.shutdown.as_mut() else {
+            return self.receiver.recv().await;
+        };
+        if *shutdown.borrow() {
+            return None;
+        }
+        tokio::select! {
+            message = self.receiver.recv() => message,
+            _ = shutdown.wait_for(|stop| *stop) => None,
+        }
     }
 
     /// Process commands with a handler function (sequentially)
@@ -672,7 +828,7 @@ impl IpcProcessor {
         F: FnMut(IpcCommand) -> Fut,
         Fut: std::future::Future<Output = IpcResponse>,
     {
-        while let Some((command_id, command, response_tx)) = self.receiver.recv().await {
+        while let Some((command_id, command, response_tx)) = self.recv().await {
             debug!("Processing IPC command {}: {:?}", command_id, command);
 
             let response = handler(command).await;
@@ -682,7 +838,75 @@ impl IpcProcessor {
             }
         }
     }
+}
+
+/// Background task running an [`IpcProcessor`], with a shutdown handle
+///
+/// Spawned tasks are otherwise never joined: the API keeps the command
+/// sender alive, so the processor would wait for commands (and keep its
+/// handler's resources) until the runtime is torn down.
+pub struct IpcProcessorTask {
+    /// Signals the processor to stop receiving commands
+    shutdown_tx: watch::Sender<bool>,
+
+    /// Handle of the spawned task
+    handle: JoinHandle<()>,
+}
 
+impl IpcProcessorTask {
+    /// Take the channel's receiver and spawn `run` with the resulting processor
+    ///
+    /// `run` should drive the processor until it stops yielding commands,
+    /// e.g. via [`IpcProcessor::process`]. If the receiver was already taken,
+    /// the task exits immediately.
+    pub fn spawn<F, Fut>(channel: &IpcChannel, run: F) -> Self
+    where
+        F: FnOnce(IpcProcessor) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let channel = channel.clone();
+        let handle = tokio::spawn(async move {
+            match IpcProcessor::new(&channel).await {
+                Some(processor) => run(processor.with_shutdown(shutdown_rx)).await,
+                None => warn!("IPC receiver already taken, processor not started"),
+            }
+        });
+        Self {
+            shutdown_tx,
+            handle,
+        }
+    }
+
+    /// Whether the task has terminated
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Signal the processor to stop and wait up to `timeout` for it
+    ///
+    /// The command in progress, if any, is allowed to finish. If the task
+    /// does not terminate in time it is aborted. Returns `true` if it
+    /// stopped on its own.
+    pub async fn shutdown(self, timeout: Duration) -> bool {
+        let _ = self.shutdown_tx.send(true);
+        let abort = self.handle.abort_handle();
+        match tokio::time::timeout(timeout, self.handle).await {
+            Ok(Ok(())) => {
+                info!("IPC processor stopped");
+                true
+            }
+            Ok(Err(e)) => {
+                error!("IPC processor task failed: {}", e);
+                true
+            }
+            Err(_) => {
+                warn!("IPC processor did not stop within {:?}, aborting", timeout);
+                abort.abort();
+                false
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -708,6 +932,10 @@ mod tests {
         let command = IpcCommand::Navigate {
             tab_id: "tab_1".to_string(),
             url: "https://example.com".to_string(),
+            bypass_cache: false,
+            max_redirects: None,
+            referrer: None,
+            referrer_policy: None,
         };
 
         let json = serde_json::to_string(&command).unwrap();
@@ -769,4 +997,60 @@ mod tests {
 
         handler.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_ipc_processor_task_stops_on_shutdown() {
+        let channel = IpcChannel::new();
+        let task = IpcProcessorTask::spawn(&channel, |mut processor| async move {
+            processor
+                .process(|_| async { IpcResponse::success() })
+                .await;
+        });
+
+        // Commands are processed while the task runs
+        let response = channel
+            .send_command(IpcMessage::Command(IpcCommand::GetTabs))
+            .await
+            .unwrap();
+        assert!(response.success);
+        assert!(!task.is_finished());
+
+        // The sender is still alive, yet the task terminates when signaled
+        assert!(channel.is_open());
+        assert!(task.shutdown(Duration::from_secs(1)).await);
+    }
+
+    #[tokio::test]
+    async fn test_processor_forwards_binary_payload() {
+        use crate::api::websocket::{BrowserEvent, BINARY_TAG_SCREENSHOT};
+
+        let channel = IpcChannel::new();
+        let ws_handler = Arc::new(WebSocketHandler::new());
+        let mut events = ws_handler.subscribe();
+        let processor = IpcProcessor::new(&channel)
+            .await
+            .unwrap()
+            .with_binary_sink(ws_handler);
+
+        let tab_id = Uuid::new_v4();
+        processor
+            .forward_binary(IpcMessage::BinaryPayload {
+                tag: BINARY_TAG_SCREENSHOT,
+                tab_id,
+                data: vec![1, 2, 3],
+            })
+            .await
+            .unwrap();
+
+        let BrowserEvent::BinaryData { payload, .. } = events.try_recv().unwrap() else {
+            unreachable!("Expected BinaryData event");
+        };
+        let frame = BinaryFrame::decode(&payload).unwrap();
+        assert_eq!(frame.tag, BINARY_TAG_SCREENSHOT);
+        assert_eq!(frame.tab_id, tab_id);
+        assert_eq!(&frame.payload[..], &[1, 2, 3]);
+
+        let result = processor.forward_binary(IpcMessage::Shutdown).await;
+        assert!(matches!(result, Err(IpcError::InvalidMessage(_))));
+    }
 }
@@ -16,6 +16,8 @@ pub mod ipc;
 pub mod ocr_routes;
 pub mod openapi;
 pub mod routes;
+pub mod schema;
+pub mod screenshot_chunks;
 pub mod server;
 pub mod session;
 pub mod session_store;
@@ -37,17 +39,17 @@ pub use batch::{
 pub use browser_handler::{BrowserCommandHandler, BrowserEngineWrapper};
 pub use cdp_mapping::CdpTabMapping;
 pub use identity::{IdentityOverrides, IdentitySpec};
-pub use ipc::{IpcChannel, IpcCommand, IpcMessage, IpcProcessor, IpcResponse};
+pub use ipc::{IpcChannel, IpcCommand, IpcMessage, IpcProcessor, IpcProcessorTask, IpcResponse};
 pub use batch_routes::batch_session_routes;
 pub use extraction_routes::extraction_routes;
 pub use ocr_routes::ocr_routes;
 pub use routes::create_router;
-pub use server::{ApiServer, AppState};
+pub use server::{ApiServer, AppState, ScreenshotDefaults};
 pub use session_store::{Bundle, CookieSpec, FingerprintSpec, SessionMeta, SessionStore, StorageEntry};
 pub use session::{
     CookieInfo, HistoryEntry, Session, SessionManager, SessionSnapshot, TabSnapshot,
 };
 pub use gui_routes::gui_routes;
 pub use vision_routes::vision_routes;
-pub use websocket::{BrowserEvent, WebSocketHandler};
+pub use websocket::{BrowserEvent, WebSocketHandler, WebSocketKeepalive};
 pub use debug_routes::debug_routes;
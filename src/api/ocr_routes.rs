@@ -347,6 +347,7 @@ async fn run_ocr(
         clip_width: None,
         clip_height: None,
         clip_scale: None,
+        include_scrollbars: false,
     };
 
     let png_data = match state
@@ -8,9 +8,9 @@ use utoipa::OpenApi;
 
 use crate::api::routes::{
     ApiStatusResponse, ApiToggleRequest, BoundingBox, ClickRequest, CloseTabRequest, ElementInfo,
-    EvaluateRequest, EvaluateResponse, FindElementQuery, HealthResponse, NavigateRequest,
-    NewTabRequest, NewTabResponse, ScreenshotQuery, ScreenshotResponse, ScrollRequest, TabInfo,
-    TabsResponse, TypeRequest,
+    EvaluateRequest, EvaluateResponse, ExecuteJsRequest, ExecuteJsResponse, FindElementQuery,
+    HealthResponse, NavigateRequest, NewTabRequest, NewTabResponse, ScreenshotQuery,
+    ScreenshotResponse, ScrollRequest, TabInfo, TabsResponse, TypeRequest,
 };
 
 /// OpenAPI documentation for the ki-browser REST API.
@@ -31,15 +31,20 @@ use crate::api::routes::{
         crate::api::routes::tabs::create_tab,
         crate::api::routes::tabs::close_tab,
         crate::api::routes::tabs::get_tab_identity,
+        crate::api::routes::tabs::start_har_recording,
+        crate::api::routes::tabs::stop_har_recording,
         crate::api::routes::navigation::navigate,
         crate::api::routes::navigation::click,
         crate::api::routes::navigation::type_text,
         crate::api::routes::navigation::evaluate,
+        crate::api::routes::navigation::execute_js,
         crate::api::routes::navigation::screenshot,
+        crate::api::screenshot_chunks::get_screenshot_chunk,
         crate::api::routes::navigation::scroll,
         crate::api::routes::dom::find_element,
         crate::api::routes::misc::toggle_api,
         crate::api::routes::misc::api_status,
+        crate::api::schema::get_schema,
         crate::api::routes::session::import_session,
         crate::api::routes::session::export_session,
         crate::api::routes::session::list_sessions,
@@ -57,8 +62,11 @@ use crate::api::routes::{
         TypeRequest,
         EvaluateRequest,
         EvaluateResponse,
+        ExecuteJsRequest,
+        ExecuteJsResponse,
         ScreenshotQuery,
         ScreenshotResponse,
+        crate::api::screenshot_chunks::ScreenshotHandle,
         ScrollRequest,
         FindElementQuery,
         ElementInfo,
@@ -257,8 +257,9 @@ pub async fn viewer_page() -> impl IntoResponse {
 ///
 /// Multipart form: `file` (the binary), `tab_id` (optional, defaults to active
 /// tab), `selector` (optional, defaults to `input[type=file]`). The file is
-/// written under /tmp/ki-uploads and attached through CDP DOM.setFileInputFiles,
-/// so a headless browser with no native file dialog can still complete uploads.
+/// written under /tmp/ki-uploads and attached through CDP DOM.setFileInputFiles
+/// (or injected into the page without CDP), so a headless browser with no
+/// native file dialog can still complete uploads.
 pub async fn upload_file(
     State(state): State<AppState>,
     mut multipart: axum::extract::Multipart,
@@ -320,6 +321,7 @@ pub async fn list_endpoints() -> impl IntoResponse {
                 EndpointInfo { method: "GET", path: "/api/status", description: "API status (enabled, port, clients)" },
                 EndpointInfo { method: "POST", path: "/api/toggle", description: "API aktivieren/deaktivieren" },
                 EndpointInfo { method: "GET", path: "/api/endpoints", description: "Alle API-Endpoints auflisten (dieser Endpoint)" },
+                EndpointInfo { method: "GET", path: "/schema", description: "JSON-Schema des IPC/WebSocket-Protokolls (Commands, Responses, Events)" },
             ],
         },
         EndpointCategory {
@@ -329,6 +331,8 @@ pub async fn list_endpoints() -> impl IntoResponse {
                 EndpointInfo { method: "POST", path: "/tabs/new", description: "Neuen Tab erstellen (optional: url, active, identity: \"random\"|\"consistent:<seed>\"|{user_agent,platform,languages,hardware_concurrency,device_memory,webgl_vendor,webgl_renderer,screen,timezone}) — optional session_bundle (inline) oder session_id (gespeichert) zum Login-Erben; Antwort enthaelt die aufgeloeste Identitaet" },
                 EndpointInfo { method: "POST", path: "/tabs/close", description: "Tab schliessen (tab_id)" },
                 EndpointInfo { method: "GET", path: "/tabs/{tab_id}/identity", description: "Aktive Stealth-Identitaet des Tabs (UA, Accept-Language==navigator.languages, WebGL, Screen, Timezone)" },
+                EndpointInfo { method: "POST", path: "/tabs/{tab_id}/har/start", description: "Netzwerk-Traffic des Tabs als HAR aufzeichnen (ersetzt eine laufende Aufzeichnung)" },
+                EndpointInfo { method: "GET", path: "/tabs/{tab_id}/har/stop", description: "HAR-Aufzeichnung beenden -> HAR-1.2-Dokument (log.entries mit request/response/timings)" },
             ],
         },
         EndpointCategory {
@@ -348,8 +352,10 @@ pub async fn list_endpoints() -> impl IntoResponse {
                 EndpointInfo { method: "POST", path: "/drag", description: "Drag-Operation (tab_id, from_x/y, to_x/y)" },
                 EndpointInfo { method: "POST", path: "/type", description: "Text eingeben (tab_id, selector, text, clear_first=true). Default ERSETZT den Feldinhalt (selektiert + ueberschreibt) und feuert korrekte input-Events fuer React/Vue. clear_first:false haengt am Cursor an. Tippe in das SICHTBARE Feld des aktiven Tabs — mehrdeutige Selektoren (z.B. zwei email-Felder fuer Login+Registrieren) treffen sonst das falsche." },
                 EndpointInfo { method: "POST", path: "/evaluate", description: "JavaScript ausfuehren (tab_id, script)" },
+                EndpointInfo { method: "POST", path: "/tabs/{tab_id}/execute", description: "JavaScript auswerten und Rueckgabewert liefern (script, timeout_ms=5000) -> {result, error}" },
                 EndpointInfo { method: "POST", path: "/scroll", description: "Seite scrollen (tab_id, direction, amount)" },
-                EndpointInfo { method: "GET", path: "/screenshot", description: "Screenshot als PNG/JPEG binary (?tab_id, ?format, ?raw=false fuer JSON)" },
+                EndpointInfo { method: "GET", path: "/screenshot", description: "Screenshot als PNG/JPEG binary (?tab_id, ?format, ?raw=false fuer JSON, ?chunked=true fuer Handle)" },
+                EndpointInfo { method: "GET", path: "/screenshots/:handle", description: "Byte-Bereich eines gespeicherten Screenshots (?offset, ?len)" },
                 EndpointInfo { method: "GET", path: "/frames", description: "Frame-Baum abrufen (?tab_id)" },
             ],
         },
@@ -383,8 +389,9 @@ pub async fn list_endpoints() -> impl IntoResponse {
                 EndpointInfo { method: "GET", path: "/session/:id/storage/:key", description: "Storage-Wert abrufen" },
                 EndpointInfo { method: "POST", path: "/session/:id/snapshot", description: "Session Snapshot erstellen" },
                 EndpointInfo { method: "GET", path: "/session/:id/snapshots", description: "Snapshots auflisten" },
-                EndpointInfo { method: "GET", path: "/tabs/:tab_id/cookies", description: "Cookies eines Tabs abrufen" },
-                EndpointInfo { method: "POST", path: "/tabs/:tab_id/cookies", description: "Cookies setzen" },
+                EndpointInfo { method: "GET", path: "/tabs/:tab_id/cookies", description: "Cookies abrufen, inkl. httpOnly (optional: ?url= nur fuer diese URL gesendete)" },
+                EndpointInfo { method: "POST", path: "/tabs/:tab_id/cookies", description: "Cookie setzen (name, value, domain, path, expires, secure, http_only, same_site)" },
+                EndpointInfo { method: "DELETE", path: "/tabs/:tab_id/cookies", description: "Cookie loeschen (?name=&domain=)" },
                 EndpointInfo { method: "GET", path: "/tabs/:tab_id/local-storage", description: "Local Storage abrufen" },
             ],
         },
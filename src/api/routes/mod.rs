@@ -19,8 +19,10 @@ pub mod session;
 pub use types::*;
 
 // Re-export all handler functions for use in create_router and external references
-pub use tabs::{list_tabs, create_tab, close_tab, get_tab_identity};
-pub use navigation::{navigate, click, drag, type_text, evaluate, screenshot, scroll};
+pub use tabs::{
+    list_tabs, create_tab, close_tab, get_tab_identity, start_har_recording, stop_har_recording,
+};
+pub use navigation::{navigate, click, drag, type_text, evaluate, execute_js, screenshot, scroll};
 pub use dom::{find_element, annotate_elements, dom_snapshot, get_frames};
 pub use misc::{health_check, toggle_api, api_status, cdp_targets, cdp_target_by_tab, list_endpoints};
 pub(crate) use misc::cdp_info;
@@ -50,6 +52,9 @@ pub fn create_router(state: AppState) -> Router {
         .route("/tabs/new", post(create_tab))
         .route("/tabs/close", post(close_tab))
         .route("/tabs/:tab_id/identity", get(get_tab_identity))
+        .route("/tabs/:tab_id/har/start", post(start_har_recording))
+        .route("/tabs/:tab_id/har/stop", get(stop_har_recording))
+        .route("/tabs/:tab_id/execute", post(execute_js))
 
         // Navigation and interaction
         .route("/navigate", post(navigate))
@@ -58,6 +63,7 @@ pub fn create_router(state: AppState) -> Router {
         .route("/type", post(type_text))
         .route("/evaluate", post(evaluate))
         .route("/screenshot", get(screenshot))
+        .merge(crate::api::screenshot_chunks::screenshot_chunk_routes())
         .route("/scroll", post(scroll))
         .route("/frames", get(get_frames))
 
@@ -85,6 +91,9 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/status", get(api_status))
         .route("/api/endpoints", get(list_endpoints))
 
+        // JSON Schema of the IPC/WebSocket protocol
+        .merge(crate::api::schema::schema_routes())
+
         // DOM extraction routes (structured data, content, forms)
         .merge(extraction_routes())
 
@@ -2,7 +2,7 @@
 //! dragging, typing, scrolling, evaluating JavaScript, and capturing screenshots.
 
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::{StatusCode, header},
     response::IntoResponse,
     Json,
@@ -52,6 +52,10 @@ pub async fn navigate(
     let command = IpcCommand::Navigate {
         tab_id: tab_id.clone(),
         url: request.url.clone(),
+        bypass_cache: request.bypass_cache,
+        max_redirects: request.max_redirects,
+        referrer: request.referrer.clone(),
+        referrer_policy: request.referrer_policy,
     };
 
     match state.ipc_channel.send_command(IpcMessage::Command(command)).await {
@@ -371,6 +375,65 @@ pub async fn evaluate(
     }
 }
 
+/// POST /tabs/{tab_id}/execute - Evaluate JavaScript and return its result
+///
+/// Waits up to `timeout_ms` for the result. The body is always
+/// `{"result": ..., "error": ...}`; a thrown exception or timeout is
+/// reported in `error` with status 400.
+#[utoipa::path(
+    post,
+    path = "/tabs/{tab_id}/execute",
+    tag = "navigation",
+    params(("tab_id" = String, Path, description = "Tab UUID")),
+    request_body = ExecuteJsRequest,
+    responses(
+        (status = 200, description = "Script evaluated", body = ExecuteJsResponse),
+        (status = 400, description = "Invalid tab, exception or timeout", body = ExecuteJsResponse),
+        (status = 503, description = "API is disabled", body = ExecuteJsResponse)
+    )
+)]
+pub async fn execute_js(
+    State(state): State<AppState>,
+    Path(tab_id): Path<String>,
+    Json(request): Json<ExecuteJsRequest>,
+) -> impl IntoResponse {
+    if !state.is_enabled().await {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ExecuteJsResponse::error("API is disabled")),
+        ).into_response();
+    }
+
+    let command = IpcCommand::EvaluateJs {
+        tab_id,
+        script: request.script,
+        timeout_ms: request.timeout_ms,
+    };
+
+    match state.ipc_channel.send_command(IpcMessage::Command(command)).await {
+        Ok(response) if response.success => {
+            let value = response
+                .data
+                .and_then(|mut data| data.get_mut("value").map(serde_json::Value::take))
+                .filter(|value| !value.is_null());
+            Json(ExecuteJsResponse::result(value)).into_response()
+        }
+        Ok(response) => (
+            StatusCode::BAD_REQUEST,
+            Json(ExecuteJsResponse::error(
+                response.error.unwrap_or_else(|| "Evaluation failed".to_string()),
+            )),
+        ).into_response(),
+        Err(e) => {
+            error!("Failed to execute script: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ExecuteJsResponse::error(format!("Failed to execute script: {}", e))),
+            ).into_response()
+        }
+    }
+}
+
 /// GET /screenshot - Capture a screenshot of the current page
 #[utoipa::path(
     get,
@@ -387,10 +450,16 @@ pub async fn screenshot(
     State(state): State<AppState>,
     Query(query): Query<ScreenshotQuery>,
 ) -> impl IntoResponse {
-    let raw = query.raw.unwrap_or(true);
+    let chunked = query.chunked.unwrap_or(false);
+    // Chunked mode always answers with a JSON handle
+    let raw = query.raw.unwrap_or(true) && !chunked;
+    let format = query
+        .format
+        .unwrap_or_else(|| state.screenshot_defaults.format.clone());
+    let quality = query.quality.or(state.screenshot_defaults.quality);
 
     if !state.is_enabled().await {
-        return raw_error_or_json(raw, StatusCode::SERVICE_UNAVAILABLE, "API is disabled", &query.format);
+        return raw_error_or_json(raw, StatusCode::SERVICE_UNAVAILABLE, "API is disabled", &format);
     }
 
     let tab_id = match query.tab_id.or({
@@ -399,14 +468,14 @@ pub async fn screenshot(
     }) {
         Some(id) => id,
         None => {
-            return raw_error_or_json(raw, StatusCode::BAD_REQUEST, "No tab specified and no active tab", &query.format);
+            return raw_error_or_json(raw, StatusCode::BAD_REQUEST, "No tab specified and no active tab", &format);
         }
     };
 
     let command = IpcCommand::CaptureScreenshot {
         tab_id,
-        format: query.format.clone(),
-        quality: query.quality,
+        format: format.clone(),
+        quality,
         full_page: query.full_page.unwrap_or(false),
         selector: query.selector,
         clip_x: query.clip_x,
@@ -414,6 +483,7 @@ pub async fn screenshot(
         clip_width: query.clip_width,
         clip_height: query.clip_height,
         clip_scale: query.clip_scale,
+        include_scrollbars: query.include_scrollbars.unwrap_or(false),
     };
 
     match state.ipc_channel.send_command(IpcMessage::Command(command)).await {
@@ -425,15 +495,32 @@ pub async fn screenshot(
                         let width = data.get("width").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
                         let height = data.get("height").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
 
+                        let content_type = match format.as_str() {
+                            "jpeg" | "jpg" => "image/jpeg",
+                            "webp" => "image/webp",
+                            "avif" => "image/avif",
+                            _ => "image/png",
+                        };
+
+                        if chunked {
+                            use base64::Engine;
+                            return match base64::engine::general_purpose::STANDARD.decode(screenshot) {
+                                Ok(bytes) => {
+                                    let handle = state.screenshot_store.insert(bytes, content_type);
+                                    Json(ApiResponse::success(handle)).into_response()
+                                }
+                                Err(e) => {
+                                    error!("Failed to decode base64 screenshot: {}", e);
+                                    raw_error_or_json(raw, StatusCode::INTERNAL_SERVER_ERROR, "Failed to decode screenshot data", &format)
+                                }
+                            };
+                        }
+
                         if raw {
                             // Return raw binary image
                             use base64::Engine;
                             match base64::engine::general_purpose::STANDARD.decode(screenshot) {
                                 Ok(bytes) => {
-                                    let content_type = match query.format.as_str() {
-                                        "jpeg" | "jpg" => "image/jpeg",
-                                        _ => "image/png",
-                                    };
                                     return (
                                         [
                                             (header::CONTENT_TYPE, content_type.to_string()),
@@ -444,28 +531,28 @@ pub async fn screenshot(
                                 }
                                 Err(e) => {
                                     error!("Failed to decode base64 screenshot: {}", e);
-                                    return raw_error_or_json(raw, StatusCode::INTERNAL_SERVER_ERROR, "Failed to decode screenshot data", &query.format);
+                                    return raw_error_or_json(raw, StatusCode::INTERNAL_SERVER_ERROR, "Failed to decode screenshot data", &format);
                                 }
                             }
                         }
 
                         return Json(ApiResponse::success(ScreenshotResponse {
                             data: screenshot.to_string(),
-                            format: query.format,
+                            format,
                             width,
                             height,
                         })).into_response();
                     }
                 }
-                raw_error_or_json(raw, StatusCode::INTERNAL_SERVER_ERROR, "Invalid screenshot response", &query.format)
+                raw_error_or_json(raw, StatusCode::INTERNAL_SERVER_ERROR, "Invalid screenshot response", &format)
             } else {
                 let msg = response.error.unwrap_or_else(|| "Screenshot failed".to_string());
-                raw_error_or_json(raw, StatusCode::BAD_REQUEST, &msg, &query.format)
+                raw_error_or_json(raw, StatusCode::BAD_REQUEST, &msg, &format)
             }
         }
         Err(e) => {
             error!("Failed to capture screenshot: {}", e);
-            raw_error_or_json(raw, StatusCode::INTERNAL_SERVER_ERROR, &format!("Failed to capture screenshot: {}", e), &query.format)
+            raw_error_or_json(raw, StatusCode::INTERNAL_SERVER_ERROR, &format!("Failed to capture screenshot: {}", e), &format)
         }
     }
 }
@@ -638,3 +725,66 @@ pub async fn scroll(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::api::ipc::{IpcChannel, IpcProcessorTask, IpcResponse};
+    use crate::api::server::ScreenshotDefaults;
+
+    #[tokio::test]
+    async fn test_screenshot_uses_configured_defaults() {
+        let channel = IpcChannel::new();
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let seen = requested.clone();
+        let _task = IpcProcessorTask::spawn(&channel, move |mut processor| async move {
+            processor
+                .process(|command| {
+                    if let IpcCommand::CaptureScreenshot { format, quality, .. } = command {
+                        seen.lock().unwrap().push((format, quality));
+                    }
+                    async {
+                        IpcResponse::success_with_data(serde_json::json!({
+                            "screenshot": "AAAA",
+                            "width": 1,
+                            "height": 1
+                        }))
+                    }
+                })
+                .await;
+        });
+
+        let mut state = AppState::new(channel);
+        state.screenshot_defaults = ScreenshotDefaults {
+            format: "jpeg".to_string(),
+            quality: Some(80),
+        };
+        let query = |json| serde_json::from_value::<ScreenshotQuery>(json).unwrap();
+
+        let response = screenshot(
+            State(state.clone()),
+            Query(query(serde_json::json!({ "tab_id": "tab_1" }))),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/jpeg");
+
+        let response = screenshot(
+            State(state),
+            Query(query(serde_json::json!({ "tab_id": "tab_1", "format": "png", "quality": 50 }))),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+
+        assert_eq!(
+            *requested.lock().unwrap(),
+            vec![
+                ("jpeg".to_string(), Some(80)),
+                ("png".to_string(), Some(50))
+            ]
+        );
+    }
+}
@@ -320,3 +320,85 @@ pub async fn close_tab(
         }
     }
 }
+
+/// Sends a HAR recording command and returns the data of a successful
+/// response, or the error response to send.
+async fn send_har_command(
+    state: &AppState,
+    command: IpcCommand,
+) -> Result<serde_json::Value, axum::response::Response> {
+    if !state.is_enabled().await {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::<()>::error("API is disabled")),
+        ).into_response());
+    }
+
+    match state.ipc_channel.send_command(IpcMessage::Command(command)).await {
+        Ok(response) if response.success => {
+            Ok(response.data.unwrap_or(serde_json::Value::Null))
+        }
+        Ok(response) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(
+                response.error.unwrap_or_else(|| "HAR recording failed".to_string()),
+            )),
+        ).into_response()),
+        Err(e) => {
+            error!("HAR recording failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(format!("HAR recording failed: {}", e))),
+            ).into_response())
+        }
+    }
+}
+
+/// POST /tabs/{tab_id}/har/start - Start recording the tab's network traffic
+///
+/// Replaces a recording that is already running in the tab.
+#[utoipa::path(
+    post,
+    path = "/tabs/{tab_id}/har/start",
+    tag = "tabs",
+    params(("tab_id" = String, Path, description = "Tab UUID")),
+    responses(
+        (status = 200, description = "Recording started"),
+        (status = 400, description = "Invalid tab ID or tab not found"),
+        (status = 503, description = "API is disabled")
+    )
+)]
+pub async fn start_har_recording(
+    State(state): State<AppState>,
+    Path(tab_id): Path<String>,
+) -> impl IntoResponse {
+    match send_har_command(&state, IpcCommand::StartHarRecording { tab_id }).await {
+        Ok(data) => Json(ApiResponse::success(data)).into_response(),
+        Err(response) => response,
+    }
+}
+
+/// GET /tabs/{tab_id}/har/stop - Stop recording and download the HAR
+///
+/// The body is the HAR 1.2 document itself, ready to be saved as a `.har`
+/// file.
+#[utoipa::path(
+    get,
+    path = "/tabs/{tab_id}/har/stop",
+    tag = "tabs",
+    params(("tab_id" = String, Path, description = "Tab UUID")),
+    responses(
+        (status = 200, description = "HAR 1.2 document of the recording"),
+        (status = 400, description = "Invalid tab ID or no recording running"),
+        (status = 503, description = "API is disabled")
+    )
+)]
+pub async fn stop_har_recording(
+    State(state): State<AppState>,
+    Path(tab_id): Path<String>,
+) -> impl IntoResponse {
+    match send_har_command(&state, IpcCommand::StopHarRecording { tab_id }).await {
+        Ok(har) => Json(har).into_response(),
+        Err(response) => response,
+    }
+}
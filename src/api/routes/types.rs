@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
 use crate::api::server::TabState;
+use crate::browser::ReferrerPolicy;
 
 /// Standard API response wrapper
 #[derive(Debug, Serialize)]
@@ -129,6 +130,20 @@ pub struct NavigateRequest {
     #[serde(default)]
     pub tab_id: Option<String>,
     pub url: String,
+    /// Load the page from the network even if it is cached
+    #[serde(default)]
+    pub bypass_cache: bool,
+    /// Abort after this many redirects (default 20); loops are always aborted
+    #[serde(default)]
+    pub max_redirects: Option<u32>,
+    /// `Referer` sent with the navigation; must be an absolute http(s) URL
+    #[serde(default)]
+    pub referrer: Option<String>,
+    /// Referrer policy for this and later navigations of the tab, e.g.
+    /// `strict-origin-when-cross-origin` (the default) or `unsafe-url`
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub referrer_policy: Option<ReferrerPolicy>,
 }
 
 /// Click request - supports both coordinates and selectors
@@ -216,6 +231,48 @@ pub struct EvaluateResponse {
     pub result: serde_json::Value,
 }
 
+/// Execute JavaScript request for `POST /tabs/{tab_id}/execute`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExecuteJsRequest {
+    /// Expression or function body (with its own `return`) to evaluate
+    pub script: String,
+    /// How long to wait for the result in milliseconds
+    #[serde(default = "default_execute_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_execute_timeout_ms() -> u64 {
+    5_000
+}
+
+/// Execute JavaScript response; `error` is `null` on success
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ExecuteJsResponse {
+    /// JSON value of the result, `null` for `undefined`/`null` or on error
+    #[schema(value_type = Object)]
+    pub result: serde_json::Value,
+    /// Exception, timeout or engine error
+    pub error: Option<String>,
+}
+
+impl ExecuteJsResponse {
+    /// Response carrying a result.
+    pub fn result(value: Option<serde_json::Value>) -> Self {
+        Self {
+            result: value.unwrap_or(serde_json::Value::Null),
+            error: None,
+        }
+    }
+
+    /// Response carrying an error.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            result: serde_json::Value::Null,
+            error: Some(message.into()),
+        }
+    }
+}
+
 /// Frames query parameters
 #[derive(Debug, Deserialize)]
 pub struct FramesQuery {
@@ -227,8 +284,11 @@ pub struct FramesQuery {
 pub struct ScreenshotQuery {
     #[serde(default)]
     pub tab_id: Option<String>,
-    #[serde(default = "default_screenshot_format")]
-    pub format: String,
+    /// Image format (`png`, `jpeg`, `webp` or `avif`); omitted uses the
+    /// configured default (`png` unless set)
+    #[serde(default)]
+    pub format: Option<String>,
+    /// JPEG/WebP/AVIF quality; omitted uses the configured default
     #[serde(default)]
     pub quality: Option<u8>,
     #[serde(default)]
@@ -250,13 +310,16 @@ pub struct ScreenshotQuery {
     /// Scale factor for clip region (default 1.0, use 2.0 to zoom 2x)
     #[serde(default)]
     pub clip_scale: Option<f64>,
+    /// Keep native scrollbars in the capture (default false: hidden)
+    #[serde(default)]
+    pub include_scrollbars: Option<bool>,
     /// If true, return raw binary image instead of JSON with base64 data
     #[serde(default)]
     pub raw: Option<bool>,
-}
-
-fn default_screenshot_format() -> String {
-    "png".to_string()
+    /// If true, store the image and return a handle for
+    /// `GET /screenshots/:handle` instead of the image itself
+    #[serde(default)]
+    pub chunked: Option<bool>,
 }
 
 /// Screenshot response
@@ -0,0 +1,109 @@
+//! JSON Schema export of the IPC/WebSocket protocol.
+//!
+//! Non-Rust clients (Python, TypeScript agents) need a machine-readable
+//! description of the messages they exchange with the browser. The schema is
+//! derived from the actual protocol types via `schemars`, so it cannot drift
+//! from the serde representation:
+//!
+//! - [`IpcCommand`] - commands sent to the browser core
+//! - [`IpcResponse`] - the reply to each command
+//! - [`BrowserEvent`] - events pushed over `/ws`
+//!
+//! Served at `GET /schema` and printed by `ki-browser schema`.
+
+use axum::{response::IntoResponse, routing::get, Json, Router};
+use schemars::gen::SchemaSettings;
+use serde_json::{json, Value};
+
+use crate::api::ipc::{IpcCommand, IpcResponse};
+use crate::api::server::AppState;
+use crate::api::websocket::BrowserEvent;
+
+/// Builds the draft-07 schema document for the protocol types.
+///
+/// The root lists the three top-level message types under `properties` as
+/// references into `definitions`, which also holds all nested types.
+pub fn protocol_schema() -> Value {
+    let mut gen = SchemaSettings::draft07().into_generator();
+    let command = gen.subschema_for::<IpcCommand>();
+    let response = gen.subschema_for::<IpcResponse>();
+    let event = gen.subschema_for::<BrowserEvent>();
+    let definitions = gen.take_definitions();
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "ki-browser protocol",
+        "version": env!("CARGO_PKG_VERSION"),
+        "type": "object",
+        "properties": {
+            "command": command,
+            "response": response,
+            "event": event,
+        },
+        "definitions": definitions,
+    })
+}
+
+/// GET /schema - JSON Schema of the IPC commands, responses and WebSocket events
+#[utoipa::path(
+    get,
+    path = "/schema",
+    tag = "api",
+    responses(
+        (status = 200, description = "Draft-07 JSON Schema of the IPC/WebSocket protocol")
+    )
+)]
+pub async fn get_schema() -> impl IntoResponse {
+    Json(protocol_schema())
+}
+
+/// Router for the protocol schema endpoint.
+pub fn schema_routes() -> Router<AppState> {
+    Router::new().route("/schema", get(get_schema))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collects the `type` tag constants of an internally tagged enum schema.
+    fn variant_tags(schema: &Value) -> Vec<String> {
+        schema["oneOf"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|variant| {
+                let tag = &variant["properties"]["type"];
+                tag["const"]
+                    .as_str()
+                    .or_else(|| tag["enum"][0].as_str())
+                    .map(str::to_string)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_schema_includes_known_command_variants() {
+        let schema = protocol_schema();
+        let definitions = &schema["definitions"];
+
+        let commands = variant_tags(&definitions["IpcCommand"]);
+        for known in [
+            "CreateTab",
+            "CloseTab",
+            "Navigate",
+            "ClickElement",
+            "TypeText",
+            "EvaluateScript",
+            "CaptureScreenshot",
+            "GetTabs",
+        ] {
+            assert!(commands.iter().any(|c| c == known), "missing command {known}: {commands:?}");
+        }
+
+        assert!(definitions["IpcResponse"].is_object());
+        assert!(definitions["BrowserEvent"].is_object());
+        assert!(definitions["Bundle"].is_object(), "nested types are included");
+        assert_eq!(schema["properties"]["command"]["$ref"], "#/definitions/IpcCommand");
+    }
+}
@@ -0,0 +1,370 @@
+//! Chunked transfer for large screenshots.
+//!
+//! Full-page captures of tall pages easily reach several megabytes, which is
+//! unwieldy as a single base64 JSON field or WebSocket message. With
+//! `GET /screenshot?chunked=true` the image is kept in a [`ScreenshotStore`]
+//! under a handle, and clients pull it piecewise:
+//!
+//! - `GET /screenshots/:handle?offset=&len=` returns a byte range
+//! - the WebSocket `FetchScreenshot` command pushes `ScreenshotChunk` events
+//!
+//! Handles expire after a TTL; expired entries are purged lazily on access.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::api::routes::ApiResponse;
+use crate::api::server::AppState;
+
+/// How long a stored screenshot stays fetchable.
+pub const DEFAULT_SCREENSHOT_TTL: Duration = Duration::from_secs(300);
+
+/// Chunk size used when the client does not specify one.
+pub const DEFAULT_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Upper bound for a single chunk.
+pub const MAX_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Maximum number of screenshots held at once; the oldest is evicted first.
+const MAX_STORED_SCREENSHOTS: usize = 32;
+
+/// Errors returned when reading from the [`ScreenshotStore`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ScreenshotStoreError {
+    /// The handle never existed or has expired.
+    #[error("Screenshot handle not found or expired: {0}")]
+    NotFound(String),
+
+    /// The requested offset lies beyond the end of the image.
+    #[error("Offset {offset} is beyond the screenshot size of {total} bytes")]
+    RangeNotSatisfiable { offset: usize, total: usize },
+}
+
+/// Metadata returned when a screenshot is stored for chunked transfer.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ScreenshotHandle {
+    /// Opaque handle used to fetch chunks.
+    pub handle: String,
+    /// Total size of the encoded image in bytes.
+    pub total_bytes: usize,
+    /// MIME type of the image.
+    pub content_type: String,
+    /// Seconds until the handle expires.
+    pub expires_in_secs: u64,
+}
+
+/// A byte range read from a stored screenshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenshotChunk {
+    /// Offset of the first byte in `data`.
+    pub offset: usize,
+    /// Total size of the image in bytes.
+    pub total_bytes: usize,
+    /// Chunk payload.
+    pub data: Vec<u8>,
+    /// MIME type of the image.
+    pub content_type: String,
+}
+
+impl ScreenshotChunk {
+    /// Whether this chunk ends at the last byte of the image.
+    pub fn is_last(&self) -> bool {
+        self.offset + self.data.len() >= self.total_bytes
+    }
+}
+
+struct StoredScreenshot {
+    data: Vec<u8>,
+    content_type: String,
+    created_at: Instant,
+}
+
+/// In-memory store of encoded screenshots addressed by handle.
+pub struct ScreenshotStore {
+    entries: Mutex<HashMap<String, StoredScreenshot>>,
+    ttl: Duration,
+}
+
+impl ScreenshotStore {
+    /// Creates a store whose handles expire after `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /// Stores an encoded image and returns its handle.
+    pub fn insert(&self, data: Vec<u8>, content_type: impl Into<String>) -> ScreenshotHandle {
+        self.insert_at(Instant::now(), data, content_type.into())
+    }
+
+    /// Reads up to `len` bytes starting at `offset`.
+    ///
+    /// `len` defaults to [`DEFAULT_CHUNK_SIZE`] and is capped at
+    /// [`MAX_CHUNK_SIZE`]. Reading at exactly the end of a non-empty image
+    /// is an error so clients cannot loop forever on an empty chunk.
+    pub fn read_range(
+        &self,
+        handle: &str,
+        offset: usize,
+        len: Option<usize>,
+    ) -> Result<ScreenshotChunk, ScreenshotStoreError> {
+        self.read_range_at(Instant::now(), handle, offset, len)
+    }
+
+    /// Drops a handle before its TTL runs out. Returns whether it existed.
+    pub fn remove(&self, handle: &str) -> bool {
+        self.entries.lock().remove(handle).is_some()
+    }
+
+    /// Number of live (unexpired) handles.
+    pub fn len(&self) -> usize {
+        let mut entries = self.entries.lock();
+        self.purge_expired(&mut entries, Instant::now());
+        entries.len()
+    }
+
+    /// Whether the store holds no live handles.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert_at(&self, now: Instant, data: Vec<u8>, content_type: String) -> ScreenshotHandle {
+        let mut entries = self.entries.lock();
+        self.purge_expired(&mut entries, now);
+
+        while entries.len() >= MAX_STORED_SCREENSHOTS {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, e)| e.created_at)
+                .map(|(k, _)| k.clone());
+            match oldest {
+                Some(key) => entries.remove(&key),
+                None => break,
+            };
+        }
+
+        let handle = uuid::Uuid::new_v4().simple().to_string();
+        let info = ScreenshotHandle {
+            handle: handle.clone(),
+            total_bytes: data.len(),
+            content_type: content_type.clone(),
+            expires_in_secs: self.ttl.as_secs(),
+        };
+        entries.insert(
+            handle,
+            StoredScreenshot {
+                data,
+                content_type,
+                created_at: now,
+            },
+        );
+        info
+    }
+
+    fn read_range_at(
+        &self,
+        now: Instant,
+        handle: &str,
+        offset: usize,
+        len: Option<usize>,
+    ) -> Result<ScreenshotChunk, ScreenshotStoreError> {
+        let mut entries = self.entries.lock();
+        self.purge_expired(&mut entries, now);
+
+        let entry = entries
+            .get(handle)
+            .ok_or_else(|| ScreenshotStoreError::NotFound(handle.to_string()))?;
+
+        let total = entry.data.len();
+        if offset > total || (offset == total && total > 0) {
+            return Err(ScreenshotStoreError::RangeNotSatisfiable { offset, total });
+        }
+
+        let len = len.unwrap_or(DEFAULT_CHUNK_SIZE).clamp(1, MAX_CHUNK_SIZE);
+        let end = offset.saturating_add(len).min(total);
+
+        Ok(ScreenshotChunk {
+            offset,
+            total_bytes: total,
+            data: entry.data[offset..end].to_vec(),
+            content_type: entry.content_type.clone(),
+        })
+    }
+
+    fn purge_expired(&self, entries: &mut HashMap<String, StoredScreenshot>, now: Instant) {
+        entries.retain(|_, e| now.saturating_duration_since(e.created_at) < self.ttl);
+    }
+}
+
+impl Default for ScreenshotStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_SCREENSHOT_TTL)
+    }
+}
+
+// -------------------------------------------------------------------------
+// HTTP handler
+// -------------------------------------------------------------------------
+
+/// Query parameters for GET /screenshots/:handle.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ChunkQuery {
+    /// Byte offset to start reading at (default 0).
+    #[serde(default)]
+    pub offset: usize,
+    /// Maximum number of bytes to return (default 256 KiB, max 4 MiB).
+    #[serde(default)]
+    pub len: Option<usize>,
+}
+
+/// GET /screenshots/:handle - Fetch a byte range of a stored screenshot
+///
+/// Returns `206 Partial Content` with a `Content-Range` header, or `200` when
+/// the range covers the whole image.
+#[utoipa::path(
+    get,
+    path = "/screenshots/{handle}",
+    tag = "navigation",
+    params(
+        ("handle" = String, Path, description = "Handle from GET /screenshot?chunked=true"),
+        ChunkQuery
+    ),
+    responses(
+        (status = 200, description = "Complete image"),
+        (status = 206, description = "Partial image data"),
+        (status = 404, description = "Unknown or expired handle"),
+        (status = 416, description = "Offset beyond end of image")
+    )
+)]
+pub async fn get_screenshot_chunk(
+    State(state): State<AppState>,
+    Path(handle): Path<String>,
+    Query(query): Query<ChunkQuery>,
+) -> impl IntoResponse {
+    match state.screenshot_store.read_range(&handle, query.offset, query.len) {
+        Ok(chunk) => {
+            let status = if chunk.offset == 0 && chunk.is_last() {
+                StatusCode::OK
+            } else {
+                StatusCode::PARTIAL_CONTENT
+            };
+            let content_range = format!(
+                "bytes {}-{}/{}",
+                chunk.offset,
+                (chunk.offset + chunk.data.len()).saturating_sub(1),
+                chunk.total_bytes
+            );
+            (
+                status,
+                [
+                    (header::CONTENT_TYPE, chunk.content_type.clone()),
+                    (header::CONTENT_RANGE, content_range),
+                    (header::ACCEPT_RANGES, "bytes".to_string()),
+                ],
+                chunk.data,
+            )
+                .into_response()
+        }
+        Err(e) => {
+            let status = match e {
+                ScreenshotStoreError::NotFound(_) => StatusCode::NOT_FOUND,
+                ScreenshotStoreError::RangeNotSatisfiable { .. } => StatusCode::RANGE_NOT_SATISFIABLE,
+            };
+            (status, Json(ApiResponse::<()>::error(e.to_string()))).into_response()
+        }
+    }
+}
+
+/// Router for chunked screenshot transfer.
+pub fn screenshot_chunk_routes() -> Router<AppState> {
+    Router::new().route("/screenshots/:handle", get(get_screenshot_chunk))
+}
+
+// -------------------------------------------------------------------------
+// Tests
+// -------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_read_range_returns_requested_bytes() {
+        let store = ScreenshotStore::default();
+        let data = sample(1000);
+        let handle = store.insert(data.clone(), "image/png");
+        assert_eq!(handle.total_bytes, 1000);
+
+        let first = store.read_range(&handle.handle, 0, Some(400)).unwrap();
+        assert_eq!(first.data, data[..400]);
+        assert!(!first.is_last());
+
+        let last = store.read_range(&handle.handle, 800, Some(400)).unwrap();
+        assert_eq!(last.data, data[800..]);
+        assert_eq!(last.total_bytes, 1000);
+        assert!(last.is_last());
+
+        // Reassembling sequential chunks yields the original image
+        let mut reassembled: Vec<u8> = Vec::new();
+        let mut offset = 0;
+        loop {
+            let chunk = store.read_range(&handle.handle, offset, Some(333)).unwrap();
+            offset += chunk.data.len();
+            reassembled.extend_from_slice(&chunk.data);
+            if chunk.is_last() {
+                break;
+            }
+        }
+        assert_eq!(reassembled, data);
+
+        assert_eq!(
+            store.read_range(&handle.handle, 1000, None),
+            Err(ScreenshotStoreError::RangeNotSatisfiable { offset: 1000, total: 1000 })
+        );
+    }
+
+    #[test]
+    fn test_handle_expires_after_ttl() {
+        let store = ScreenshotStore::new(Duration::from_secs(60));
+        let start = Instant::now();
+        let handle = store.insert_at(start, sample(10), "image/png".to_string());
+
+        assert!(store
+            .read_range_at(start + Duration::from_secs(59), &handle.handle, 0, None)
+            .is_ok());
+        assert_eq!(
+            store.read_range_at(start + Duration::from_secs(60), &handle.handle, 0, None),
+            Err(ScreenshotStoreError::NotFound(handle.handle.clone()))
+        );
+        assert!(store.entries.lock().is_empty());
+    }
+
+    #[test]
+    fn test_store_evicts_oldest_when_full() {
+        let store = ScreenshotStore::default();
+        let start = Instant::now();
+        let first = store.insert_at(start, sample(1), "image/png".to_string());
+        for i in 1..=MAX_STORED_SCREENSHOTS {
+            store.insert_at(start + Duration::from_millis(i as u64), sample(1), "image/png".to_string());
+        }
+        assert_eq!(store.len(), MAX_STORED_SCREENSHOTS);
+        assert!(store.read_range(&first.handle, 0, None).is_err());
+    }
+}
@@ -5,6 +5,7 @@
 
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -20,9 +21,11 @@ use crate::api::agent_registry::AgentRegistry;
 use crate::api::cdp_mapping::CdpTabMapping;
 use crate::api::openapi::ApiDoc;
 use crate::api::routes::create_router;
+use crate::api::screenshot_chunks::ScreenshotStore;
 use crate::api::websocket::WebSocketHandler;
 use crate::api::ipc::IpcChannel;
 use crate::browser::TabLockManager;
+use crate::config::BrowserSettings;
 
 /// Represents a browser tab's state
 #[derive(Debug, Clone)]
@@ -82,6 +85,34 @@ impl BrowserState {
     }
 }
 
+/// Screenshot format and quality applied when a request omits them
+#[derive(Debug, Clone)]
+pub struct ScreenshotDefaults {
+    /// Image format (`png`, `jpeg`, `webp` or `avif`)
+    pub format: String,
+    /// JPEG/WebP/AVIF quality, `None` for the engine default
+    pub quality: Option<u8>,
+}
+
+impl Default for ScreenshotDefaults {
+    fn default() -> Self {
+        Self {
+            format: "png".to_string(),
+            quality: None,
+        }
+    }
+}
+
+impl ScreenshotDefaults {
+    /// Take the defaults from `default_screenshot_format`/`_quality`
+    pub fn from_settings(settings: &BrowserSettings) -> Self {
+        Self {
+            format: settings.default_screenshot_format.clone(),
+            quality: settings.default_screenshot_quality,
+        }
+    }
+}
+
 /// Application state shared across all handlers
 #[derive(Clone)]
 pub struct AppState {
@@ -120,6 +151,10 @@ pub struct AppState {
     /// Encrypted, persistent store for inheritable session bundles.
     /// `None` when the store could not be opened (sessions disabled).
     pub session_store: Option<crate::api::session_store::SessionStore>,
+    /// Screenshots held for chunked transfer (`GET /screenshots/:handle`).
+    pub screenshot_store: Arc<ScreenshotStore>,
+    /// Format and quality for screenshot requests that omit them.
+    pub screenshot_defaults: ScreenshotDefaults,
 }
 
 impl AppState {
@@ -142,6 +177,8 @@ impl AppState {
             api_token: None,
             ocr_config: Arc::new(RwLock::new(crate::ocr::OcrRuntimeConfig::with_all_enabled())),
             session_store: None,
+            screenshot_store: Arc::new(ScreenshotStore::default()),
+            screenshot_defaults: ScreenshotDefaults::default(),
         }
     }
 
@@ -168,6 +205,8 @@ impl AppState {
             api_token: None,
             ocr_config: Arc::new(RwLock::new(crate::ocr::OcrRuntimeConfig::with_all_enabled())),
             session_store: None,
+            screenshot_store: Arc::new(ScreenshotStore::default()),
+            screenshot_defaults: ScreenshotDefaults::default(),
         }
     }
 
@@ -183,8 +222,16 @@ impl AppState {
     }
 
     /// Attach the CEF browser engine for direct frame-buffer access and input forwarding.
+    ///
+    /// Events emitted by the engine (e.g. `ActiveTabChanged`) are forwarded
+    /// to connected WebSocket clients.
     #[cfg(feature = "cef-browser")]
     pub fn set_cef_engine(&mut self, engine: Arc<crate::browser::cef_engine::CefBrowserEngine>) {
+        use crate::browser::BrowserEngine;
+
+        if let Some(events) = engine.subscribe_events() {
+            self.ws_handler.forward_from(events);
+        }
         self.cef_engine = Some(engine);
     }
 
@@ -206,6 +253,11 @@ pub struct ApiServer {
     port: u16,
     /// IP address to bind to (e.g. "0.0.0.0" or "127.0.0.1"). Default "0.0.0.0".
     bind: String,
+    /// Unix domain socket to listen on (Unix only). When set, TCP is only
+    /// served if `socket_and_tcp` is true.
+    socket: Option<PathBuf>,
+    /// Serve TCP in addition to the Unix domain socket.
+    socket_and_tcp: bool,
     /// Whether the server is enabled
     enabled: bool,
     /// Shared application state
@@ -222,6 +274,8 @@ impl ApiServer {
         Self {
             port,
             bind: String::from("0.0.0.0"),
+            socket: None,
+            socket_and_tcp: false,
             enabled: false,
             state: AppState::new(ipc_channel),
             shutdown_tx: None,
@@ -238,6 +292,8 @@ impl ApiServer {
         Self {
             port,
             bind: String::from("0.0.0.0"),
+            socket: None,
+            socket_and_tcp: false,
             enabled: false,
             state: AppState::new_with_cdp(ipc_channel, cdp_port),
             shutdown_tx: None,
@@ -250,6 +306,8 @@ impl ApiServer {
         Self {
             port,
             bind: String::from("0.0.0.0"),
+            socket: None,
+            socket_and_tcp: false,
             enabled: false,
             state,
             shutdown_tx: None,
@@ -262,6 +320,21 @@ impl ApiServer {
         self.bind = bind.into();
     }
 
+    /// Listen on a Unix domain socket at `path` (Unix only).
+    ///
+    /// The socket is created with mode `0600`, so only the owning user can
+    /// connect. TCP is skipped unless `also_tcp` is true. `None` restores
+    /// TCP-only serving.
+    pub fn set_socket(&mut self, path: Option<PathBuf>, also_tcp: bool) {
+        self.socket = path;
+        self.socket_and_tcp = also_tcp;
+    }
+
+    /// Get the Unix domain socket path, if one is configured
+    pub fn socket(&self) -> Option<&Path> {
+        self.socket.as_deref()
+    }
+
     /// Get the server port
     pub fn port(&self) -> u16 {
         self.port
@@ -327,38 +400,75 @@ impl ApiServer {
             return Ok(());
         }
 
-        let ip: std::net::IpAddr = self.bind.parse().map_err(|e| {
-            format!("Invalid API bind address '{}': {}", self.bind, e)
-        })?;
-        let addr = SocketAddr::new(ip, self.port);
         let router = self.build_router();
 
+        // Bind the listeners before spawning so errors reach the caller
+        let tcp_listener = if self.socket.is_none() || self.socket_and_tcp {
+            let ip: std::net::IpAddr = self
+                .bind
+                .parse()
+                .map_err(|e| format!("Invalid API bind address '{}': {}", self.bind, e))?;
+            let addr = SocketAddr::new(ip, self.port);
+            let listener = TcpListener::bind(addr).await?;
+            info!("API server listening on http://{}", addr);
+            Some(listener)
+        } else {
+            None
+        };
+
+        #[cfg(unix)]
+        let unix_listener = match self.socket {
+            Some(ref path) => {
+                let listener = bind_unix_socket(path)?;
+                info!("API server listening on unix:{}", path.display());
+                Some(listener)
+            }
+            None => None,
+        };
+        #[cfg(not(unix))]
+        if let Some(ref path) = self.socket {
+            return Err(format!(
+                "Cannot listen on '{}': Unix domain sockets are not supported on this platform",
+                path.display()
+            )
+            .into());
+        }
+
         // Create shutdown channel
-        let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
         self.shutdown_tx = Some(shutdown_tx);
 
-        // Bind the listener
-        let listener = TcpListener::bind(addr).await?;
-        info!("API server listening on http://{}", addr);
-
         self.enabled = true;
 
         // Spawn the server task
         let handle = tokio::spawn(async move {
-            axum::serve(listener, router)
-                .with_graceful_shutdown(async move {
-                    // Wait for shutdown signal
-                    while !*shutdown_rx.borrow() {
-                        if shutdown_rx.changed().await.is_err() {
-                            break;
-                        }
-                    }
-                    info!("API server shutting down gracefully");
-                })
-                .await
-                .unwrap_or_else(|e| {
-                    error!("API server error: {}", e);
-                });
+            let tcp = async {
+                let Some(listener) = tcp_listener else {
+                    return;
+                };
+                let mut shutdown_rx = shutdown_rx.clone();
+                axum::serve(listener, router.clone())
+                    .with_graceful_shutdown(async move {
+                        // Wait for shutdown signal
+                        let _ = shutdown_rx.wait_for(|stop| *stop).await;
+                        info!("API server shutting down gracefully");
+                    })
+                    .await
+                    .unwrap_or_else(|e| {
+                        error!("API server error: {}", e);
+                    });
+            };
+
+            #[cfg(unix)]
+            let unix = async {
+                if let Some(listener) = unix_listener {
+                    serve_unix(listener, router.clone(), shutdown_rx.clone()).await;
+                }
+            };
+            #[cfg(not(unix))]
+            let unix = async {};
+
+            tokio::join!(tcp, unix);
         });
 
         self.server_handle = Some(handle);
@@ -393,9 +503,19 @@ impl ApiServer {
             }
         }
 
+        self.remove_socket_file();
         self.enabled = false;
     }
 
+    /// Remove the Unix domain socket file, if one was created.
+    fn remove_socket_file(&self) {
+        if let Some(ref path) = self.socket {
+            if self.enabled {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
     /// Toggle the server on/off
     pub async fn toggle(&mut self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
         if self.enabled {
@@ -414,7 +534,111 @@ impl Drop for ApiServer {
         if let Some(tx) = self.shutdown_tx.take() {
             let _ = tx.send(true);
         }
+        self.remove_socket_file();
+    }
+}
+
+/// Bind a Unix domain socket at `path` that only the current user can use.
+///
+/// A socket left behind by an unclean exit is replaced; any other file at
+/// `path` is left alone and binding fails.
+///
+/// The socket is bound inside a fresh 0700 directory next to `path`,
+/// restricted to 0600 and only then renamed into place, so other users can
+/// never connect through the window between bind and chmod.
+#[cfg(unix)]
+fn bind_unix_socket(path: &Path) -> std::io::Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("'{}' exists and is not a socket", path.display()),
+            ));
+        }
+    }
+
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("'{}' has no file name", path.display()),
+        )
+    })?;
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let private_dir = parent.join(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+    std::fs::DirBuilder::new().mode(0o700).create(&private_dir)?;
+
+    let staged = private_dir.join(file_name);
+    let result = tokio::net::UnixListener::bind(&staged).and_then(|listener| {
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))?;
+        // Atomically replaces a stale socket left at `path`
+        std::fs::rename(&staged, path)?;
+        Ok(listener)
+    });
+    let _ = std::fs::remove_file(&staged);
+    let _ = std::fs::remove_dir(&private_dir);
+    result
+}
+
+/// Serve `router` on a Unix domain socket until shutdown is signalled.
+///
+/// `axum::serve` only accepts TCP listeners, so connections are accepted
+/// here and handed to hyper directly. Upgrades stay enabled for the
+/// WebSocket routes. On shutdown, open connections finish their in-flight
+/// request before closing.
+#[cfg(unix)]
+async fn serve_unix(
+    listener: tokio::net::UnixListener,
+    router: Router,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    use hyper_util::rt::TokioIo;
+    use hyper_util::service::TowerToHyperService;
+
+    let mut connections = tokio::task::JoinSet::new();
+    loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    warn!("API socket accept failed: {}", e);
+                    continue;
+                }
+            },
+            _ = shutdown_rx.wait_for(|stop| *stop) => break,
+        };
+
+        let service = TowerToHyperService::new(router.clone());
+        let mut conn_shutdown_rx = shutdown_rx.clone();
+        connections.spawn(async move {
+            let conn = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .with_upgrades();
+            tokio::pin!(conn);
+            tokio::select! {
+                result = conn.as_mut() => {
+                    if let Err(e) = result {
+                        tracing::debug!("API socket connection error: {}", e);
+                    }
+                }
+                _ = async { conn_shutdown_rx.wait_for(|stop| *stop).await.map(|_| ()) } => {
+                    conn.as_mut().graceful_shutdown();
+                    let _ = conn.await;
+                }
+            }
+        });
     }
+
+    info!("API socket shutting down gracefully");
+    while connections.join_next().await.is_some() {}
 }
 
 #[cfg(test)]
@@ -445,4 +669,35 @@ mod tests {
         assert_eq!(tab.title, "New Tab");
         assert!(!tab.is_loading);
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_api_server_serves_unix_socket() {
+        use std::os::unix::fs::PermissionsExt;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api.sock");
+        let mut server = ApiServer::new(0, IpcChannel::new());
+        server.set_socket(Some(path.clone()), false);
+        server.start().await.unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        // Only the socket is left behind, not the private staging directory
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        stream
+            .write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains("healthy"));
+
+        server.stop().await;
+        assert!(!path.exists());
+    }
 }
@@ -7,6 +7,7 @@
 //! The `fingerprint` object maps 1:1 onto [`crate::api::identity::IdentityOverrides`]
 //! so a tab created from a bundle is fingerprint-consistent with the source.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::api::identity::{IdentityOverrides, IdentitySpec, ScreenSpec};
@@ -15,7 +16,7 @@ use crate::api::identity::{IdentityOverrides, IdentitySpec, ScreenSpec};
 pub const BUNDLE_VERSION: u32 = 1;
 
 /// A complete session bundle (cookies + storage + fingerprint).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Bundle {
     /// Schema version (currently always [`BUNDLE_VERSION`]).
     #[serde(default = "default_version")]
@@ -41,7 +42,7 @@ fn default_version() -> u32 {
 }
 
 /// A single cookie. Field semantics follow CDP `Network.setCookie`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CookieSpec {
     pub name: String,
     pub value: String,
@@ -65,7 +66,7 @@ fn default_path() -> String {
 }
 
 /// local/sessionStorage contents for a single origin.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct StorageEntry {
     pub origin: String,
     #[serde(default)]
@@ -75,7 +76,7 @@ pub struct StorageEntry {
 }
 
 /// Browser fingerprint captured from the source browser.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct FingerprintSpec {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub user_agent: Option<String>,
@@ -98,7 +99,7 @@ pub struct FingerprintSpec {
 }
 
 /// Screen dimensions in the bundle fingerprint.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
 pub struct ScreenSize {
     pub width: u32,
     pub height: u32,
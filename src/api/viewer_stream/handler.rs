@@ -385,7 +385,14 @@ async fn handle_client_message(
             // Go through the API (IpcCommand) like /navigate so the full
             // navigation path runs (CDP/stealth setup, state stays consistent).
             if let Some(tab) = resolve_active() {
-                let cmd = crate::api::ipc::IpcCommand::Navigate { tab_id: tab_id_str(tab), url };
+                let cmd = crate::api::ipc::IpcCommand::Navigate {
+                    tab_id: tab_id_str(tab),
+                    url,
+                    bypass_cache: false,
+                    max_redirects: None,
+                    referrer: None,
+                    referrer_policy: None,
+                };
                 let ipc = state.ipc_channel.clone();
                 tokio::spawn(async move {
                     let _ = ipc
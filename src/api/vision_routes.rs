@@ -64,6 +64,7 @@ fn parse_format(s: &str) -> ScreenshotFormat {
     match s.to_lowercase().as_str() {
         "jpeg" | "jpg" => ScreenshotFormat::Jpeg,
         "webp" => ScreenshotFormat::WebP,
+        "avif" => ScreenshotFormat::Avif,
         _ => ScreenshotFormat::Png,
     }
 }
@@ -269,6 +270,7 @@ fn format_to_string(format: ScreenshotFormat) -> String {
         ScreenshotFormat::Png => "png".to_string(),
         ScreenshotFormat::Jpeg => "jpeg".to_string(),
         ScreenshotFormat::WebP => "webp".to_string(),
+        ScreenshotFormat::Avif => "avif".to_string(),
     }
 }
 
@@ -315,6 +317,7 @@ mod tests {
         assert_eq!(format_to_string(ScreenshotFormat::Png), "png");
         assert_eq!(format_to_string(ScreenshotFormat::Jpeg), "jpeg");
         assert_eq!(format_to_string(ScreenshotFormat::WebP), "webp");
+        assert_eq!(format_to_string(ScreenshotFormat::Avif), "avif");
     }
 
     #[test]
@@ -2,10 +2,14 @@
 //!
 //! Provides WebSocket connectivity for broadcasting browser events
 //! and receiving commands from connected clients.
+//!
+//! Events are sent as JSON text frames, except [`BrowserEvent::BinaryData`],
+//! which is sent as a binary frame in the [`BinaryFrame`] layout.
 
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use axum::{
     extract::{
@@ -14,136 +18,101 @@ use axum::{
     },
     response::IntoResponse,
 };
+use bytes::{BufMut, Bytes, BytesMut};
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use tokio::sync::{broadcast, mpsc, RwLock};
 use tracing::{debug, info, warn};
+use uuid::Uuid;
 
+use crate::api::screenshot_chunks::ScreenshotStore;
 use crate::api::server::AppState;
 
+pub use crate::browser::events::BrowserEvent;
+
 /// Unique client identifier
 static NEXT_CLIENT_ID: AtomicU64 = AtomicU64::new(1);
 
-/// Browser events that can be broadcast to connected clients
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type", content = "data")]
-pub enum BrowserEvent {
-    /// A new tab was created
-    TabCreated {
-        tab_id: String,
-        url: String,
-    },
-
-    /// A tab was closed
-    TabClosed {
-        tab_id: String,
-    },
-
-    /// Navigation completed in a tab
-    NavigationComplete {
-        tab_id: String,
-        url: String,
-        title: String,
-    },
-
-    /// DOM is ready in a tab
-    DomReady {
-        tab_id: String,
-    },
-
-    /// Page finished loading
-    LoadComplete {
-        tab_id: String,
-        url: String,
-    },
-
-    /// Tab title changed
-    TitleChanged {
-        tab_id: String,
-        title: String,
-    },
-
-    /// Tab URL changed (without full navigation)
-    UrlChanged {
-        tab_id: String,
-        url: String,
-    },
-
-    /// Tab favicon changed
-    FaviconChanged {
-        tab_id: String,
-        favicon_url: Option<String>,
-    },
-
-    /// Tab loading state changed
-    LoadingStateChanged {
-        tab_id: String,
-        is_loading: bool,
-    },
-
-    /// Active tab changed
-    ActiveTabChanged {
-        tab_id: String,
-    },
-
-    /// Console message from a tab
-    ConsoleMessage {
-        tab_id: String,
-        level: String, // "log", "warn", "error", "info", "debug"
-        message: String,
-        source: Option<String>,
-        line: Option<u32>,
-    },
-
-    /// JavaScript dialog appeared
-    DialogOpened {
-        tab_id: String,
-        dialog_type: String, // "alert", "confirm", "prompt", "beforeunload"
-        message: String,
-    },
-
-    /// Download started
-    DownloadStarted {
-        download_id: String,
-        url: String,
-        filename: String,
-    },
+/// Binary frame tag: generic data pushed with [`WebSocketHandler::send_binary`]
+pub const BINARY_TAG_DATA: u8 = 0x00;
+
+/// Binary frame tag: encoded screenshot image
+pub const BINARY_TAG_SCREENSHOT: u8 = 0x01;
+
+/// Binary frame tag: rendered frame update
+pub const BINARY_TAG_FRAME_UPDATE: u8 = 0x02;
+
+/// A tagged binary WebSocket frame
+///
+/// Layout on the wire:
+///
+/// ```text
+/// [tag: u8][tab id length: u32 BE][tab id: 16 bytes][payload]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryFrame {
+    /// Kind of payload (`BINARY_TAG_*`)
+    pub tag: u8,
+    /// Tab the payload belongs to
+    pub tab_id: Uuid,
+    /// Raw payload bytes
+    pub payload: Bytes,
+}
 
-    /// Download progress updated
-    DownloadProgress {
-        download_id: String,
-        received_bytes: u64,
-        total_bytes: Option<u64>,
-    },
+/// Errors decoding a [`BinaryFrame`]
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum BinaryFrameError {
+    #[error("Binary frame too short: {0} bytes")]
+    Truncated(usize),
 
-    /// Download completed
-    DownloadComplete {
-        download_id: String,
-        path: String,
-    },
+    #[error("Invalid tab id length: {0} bytes")]
+    InvalidTabId(usize),
+}
 
-    /// An error occurred
-    Error {
-        tab_id: Option<String>,
-        code: String,
-        message: String,
-    },
+impl BinaryFrame {
+    /// Size of the tag and tab id length prefix
+    const HEADER_LEN: usize = 1 + 4;
 
-    /// Connection established (sent to new clients)
-    Connected {
-        client_id: u64,
-        server_version: String,
-    },
+    /// Create a frame
+    pub fn new(tag: u8, tab_id: Uuid, payload: impl Into<Bytes>) -> Self {
+        Self {
+            tag,
+            tab_id,
+            payload: payload.into(),
+        }
+    }
 
-    /// Ping for keepalive
-    Ping {
-        timestamp: u64,
-    },
+    /// Encode the frame into its wire layout
+    pub fn encode(&self) -> Bytes {
+        let tab_id = self.tab_id.as_bytes();
+        let mut buf = BytesMut::with_capacity(Self::HEADER_LEN + tab_id.len() + self.payload.len());
+        buf.put_u8(self.tag);
+        buf.put_u32(tab_id.len() as u32);
+        buf.put_slice(tab_id);
+        buf.put_slice(&self.payload);
+        buf.freeze()
+    }
 
-    /// Pong response
-    Pong {
-        timestamp: u64,
-    },
+    /// Decode a frame from its wire layout
+    pub fn decode(data: &[u8]) -> Result<Self, BinaryFrameError> {
+        if data.len() < Self::HEADER_LEN {
+            return Err(BinaryFrameError::Truncated(data.len()));
+        }
+        let tag = data[0];
+        let id_len = u32::from_be_bytes([data[1], data[2], data[3], data[4]]) as usize;
+        let id_end = Self::HEADER_LEN
+            .checked_add(id_len)
+            .filter(|end| *end <= data.len())
+            .ok_or(BinaryFrameError::Truncated(data.len()))?;
+        let tab_id = Uuid::from_slice(&data[Self::HEADER_LEN..id_end])
+            .map_err(|_| BinaryFrameError::InvalidTabId(id_len))?;
+
+        Ok(Self {
+            tag,
+            tab_id,
+            payload: Bytes::copy_from_slice(&data[id_end..]),
+        })
+    }
 }
 
 /// Commands that can be received via WebSocket
@@ -164,6 +133,13 @@ pub enum WebSocketCommand {
     Ping {
         timestamp: u64,
     },
+
+    /// Stream a stored screenshot as `ScreenshotChunk` events
+    FetchScreenshot {
+        handle: String,
+        #[serde(default)]
+        chunk_size: Option<usize>,
+    },
 }
 
 /// WebSocket message wrapper
@@ -185,12 +161,44 @@ pub enum WebSocketPayload {
     Command(WebSocketCommand),
 }
 
+/// Keepalive settings for WebSocket connections.
+///
+/// Every `ping_interval` the server sends a protocol-level ping (answered
+/// automatically by browsers and most client libraries) plus a `Ping` event.
+/// Any message from the client counts as a sign of life; a client that stays
+/// silent for `max_missed_pongs` intervals is closed and its queue freed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebSocketKeepalive {
+    /// Interval between pings.
+    pub ping_interval: Duration,
+    /// Number of consecutive intervals without a response before the client is reaped.
+    pub max_missed_pongs: u32,
+}
+
+impl WebSocketKeepalive {
+    /// How long a client may stay silent before it is reaped.
+    pub fn idle_timeout(&self) -> Duration {
+        self.ping_interval * self.max_missed_pongs.max(1)
+    }
+}
+
+impl Default for WebSocketKeepalive {
+    fn default() -> Self {
+        Self {
+            ping_interval: Duration::from_secs(30),
+            max_missed_pongs: 3,
+        }
+    }
+}
+
 /// Connected client information
 #[derive(Debug)]
 struct ClientInfo {
     id: u64,
     subscribed_events: Vec<String>,
     tx: mpsc::Sender<BrowserEvent>,
+    /// Last time any message (including pongs) arrived from the client
+    last_seen: Instant,
 }
 
 /// WebSocket handler for managing connections and broadcasting events
@@ -201,33 +209,40 @@ pub struct WebSocketHandler {
     /// Connected clients
     clients: RwLock<HashMap<u64, ClientInfo>>,
 
-    /// Ping interval in seconds
-    ping_interval: Duration,
+    /// Ping interval and idle timeout
+    keepalive: WebSocketKeepalive,
 }
 
 impl WebSocketHandler {
     /// Create a new WebSocket handler
     pub fn new() -> Self {
-        let (broadcast_tx, _) = broadcast::channel(1024);
-
-        Self {
-            broadcast_tx,
-            clients: RwLock::new(HashMap::new()),
-            ping_interval: Duration::from_secs(30),
-        }
+        Self::with_keepalive(WebSocketKeepalive::default())
     }
 
     /// Create a new WebSocket handler with custom ping interval
     pub fn with_ping_interval(ping_interval: Duration) -> Self {
+        Self::with_keepalive(WebSocketKeepalive {
+            ping_interval,
+            ..Default::default()
+        })
+    }
+
+    /// Create a new WebSocket handler with custom keepalive settings
+    pub fn with_keepalive(keepalive: WebSocketKeepalive) -> Self {
         let (broadcast_tx, _) = broadcast::channel(1024);
 
         Self {
             broadcast_tx,
             clients: RwLock::new(HashMap::new()),
-            ping_interval,
+            keepalive,
         }
     }
 
+    /// Get the keepalive settings
+    pub fn keepalive(&self) -> WebSocketKeepalive {
+        self.keepalive
+    }
+
     /// Broadcast an event to all connected clients
     pub async fn broadcast(&self, event: BrowserEvent) {
         let clients = self.clients.read().await;
@@ -262,15 +277,119 @@ impl WebSocketHandler {
             BrowserEvent::FaviconChanged { .. } => "FaviconChanged".to_string(),
             BrowserEvent::LoadingStateChanged { .. } => "LoadingStateChanged".to_string(),
             BrowserEvent::ActiveTabChanged { .. } => "ActiveTabChanged".to_string(),
+            BrowserEvent::ViewportChanged { .. } => "ViewportChanged".to_string(),
             BrowserEvent::ConsoleMessage { .. } => "ConsoleMessage".to_string(),
+            BrowserEvent::RequestFailed { .. } => "RequestFailed".to_string(),
+            BrowserEvent::Binding { .. } => "Binding".to_string(),
+            BrowserEvent::ConsentDismissed { .. } => "ConsentDismissed".to_string(),
             BrowserEvent::DialogOpened { .. } => "DialogOpened".to_string(),
             BrowserEvent::DownloadStarted { .. } => "DownloadStarted".to_string(),
             BrowserEvent::DownloadProgress { .. } => "DownloadProgress".to_string(),
             BrowserEvent::DownloadComplete { .. } => "DownloadComplete".to_string(),
+            BrowserEvent::ScreenshotChunk { .. } => "ScreenshotChunk".to_string(),
             BrowserEvent::Error { .. } => "Error".to_string(),
             BrowserEvent::Connected { .. } => "Connected".to_string(),
             BrowserEvent::Ping { .. } => "Ping".to_string(),
             BrowserEvent::Pong { .. } => "Pong".to_string(),
+            BrowserEvent::BinaryData { .. } => "BinaryData".to_string(),
+        }
+    }
+
+    /// Send binary data for a tab to all clients as a binary frame
+    pub async fn send_binary(&self, tab_id: Uuid, data: Bytes) {
+        self.send_frame(BinaryFrame::new(BINARY_TAG_DATA, tab_id, data))
+            .await;
+    }
+
+    /// Send a tagged binary frame to all clients
+    pub async fn send_frame(&self, frame: BinaryFrame) {
+        self.broadcast(BrowserEvent::BinaryData {
+            tab_id: frame.tab_id,
+            payload: frame.encode(),
+        })
+        .await;
+    }
+
+    /// Decode a binary frame received from a client
+    ///
+    /// Frames are never re-broadcast: clients are not authenticated, so a
+    /// relayed frame could forge screenshot or frame data of any tab in the
+    /// other clients' streams. `BinaryData` only carries engine output.
+    pub async fn handle_binary(&self, data: &[u8]) -> Result<BinaryFrame, BinaryFrameError> {
+        let frame = BinaryFrame::decode(data)?;
+        debug!(
+            "Received binary frame (tag 0x{:02x}, {} bytes) for tab {}",
+            frame.tag,
+            frame.payload.len(),
+            frame.tab_id
+        );
+        Ok(frame)
+    }
+
+    /// Re-broadcast events from an engine event stream to all clients.
+    ///
+    /// The spawned task ends when the engine drops its sender. Events lost
+    /// to a lagging receiver are skipped with a warning.
+    pub fn forward_from(
+        self: &Arc<Self>,
+        mut events: broadcast::Receiver<BrowserEvent>,
+    ) -> tokio::task::JoinHandle<()> {
+        let handler = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => handler.broadcast(event).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Engine event forwarder lagged, skipped {} events", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Push a stored screenshot to one client as a sequence of `ScreenshotChunk` events.
+    ///
+    /// Chunks go through the client's bounded queue, so a slow client applies
+    /// backpressure instead of buffering the whole image. An unknown or expired
+    /// handle is reported to the client as an `Error` event.
+    pub async fn push_screenshot(
+        &self,
+        client_id: u64,
+        store: &ScreenshotStore,
+        handle: &str,
+        chunk_size: Option<usize>,
+    ) {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+        let Some(tx) = self.clients.read().await.get(&client_id).map(|c| c.tx.clone()) else {
+            return;
+        };
+
+        let mut offset = 0;
+        loop {
+            let event = match store.read_range(handle, offset, chunk_size) {
+                Ok(chunk) => {
+                    offset += chunk.data.len();
+                    BrowserEvent::ScreenshotChunk {
+                        handle: handle.to_string(),
+                        offset: chunk.offset,
+                        total_bytes: chunk.total_bytes,
+                        is_last: chunk.is_last(),
+                        data: BASE64.encode(&chunk.data),
+                    }
+                }
+                Err(e) => BrowserEvent::Error {
+                    tab_id: None,
+                    code: "SCREENSHOT_UNAVAILABLE".to_string(),
+                    message: e.to_string(),
+                },
+            };
+
+            let done = !matches!(event, BrowserEvent::ScreenshotChunk { is_last: false, .. });
+            if tx.send(event).await.is_err() || done {
+                break;
+            }
         }
     }
 
@@ -292,6 +411,7 @@ impl WebSocketHandler {
             id: client_id,
             subscribed_events: vec![], // Empty means all events
             tx,
+            last_seen: Instant::now(),
         };
 
         self.clients.write().await.insert(client_id, client);
@@ -307,6 +427,38 @@ impl WebSocketHandler {
         info!("WebSocket client {} disconnected", client_id);
     }
 
+    /// Record that a message arrived from the client
+    async fn record_activity(&self, client_id: u64) {
+        if let Some(client) = self.clients.write().await.get_mut(&client_id) {
+            client.last_seen = Instant::now();
+        }
+    }
+
+    /// Remove the client if it has been silent for longer than the idle timeout.
+    ///
+    /// Dropping the client entry closes its event queue, which ends the
+    /// connection's send task. Returns whether the client was reaped.
+    async fn reap_if_idle(&self, client_id: u64) -> bool {
+        let idle_timeout = self.keepalive.idle_timeout();
+        let mut clients = self.clients.write().await;
+        let idle = match clients.get(&client_id) {
+            Some(client) => client.last_seen.elapsed(),
+            None => return false,
+        };
+        if idle <= idle_timeout {
+            return false;
+        }
+
+        clients.remove(&client_id);
+        warn!(
+            "WebSocket client {} missed {} pongs ({}ms idle), closing connection",
+            client_id,
+            self.keepalive.max_missed_pongs,
+            idle.as_millis()
+        );
+        true
+    }
+
     /// Update client subscriptions
     async fn subscribe_client(&self, client_id: u64, events: Vec<String>) {
         if let Some(client) = self.clients.write().await.get_mut(&client_id) {
@@ -334,6 +486,18 @@ impl Default for WebSocketHandler {
     }
 }
 
+/// Wire message for an event: a binary frame for `BinaryData`, JSON text otherwise
+fn event_message(event: BrowserEvent) -> Result<Message, serde_json::Error> {
+    if let BrowserEvent::BinaryData { payload, .. } = event {
+        return Ok(Message::Binary(payload.to_vec()));
+    }
+    serde_json::to_string(&WebSocketMessage {
+        id: None,
+        payload: WebSocketPayload::Event(event),
+    })
+    .map(Message::Text)
+}
+
 /// WebSocket upgrade handler
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
@@ -376,7 +540,7 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     }
 
     let ws_handler = state.ws_handler.clone();
-    let ping_interval = ws_handler.ping_interval;
+    let ping_interval = ws_handler.keepalive.ping_interval;
 
     // Task to send events to client
     let mut send_task = tokio::spawn(async move {
@@ -385,11 +549,13 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
         loop {
             tokio::select! {
                 // Send events from channel
-                Some(event) = rx.recv() => {
-                    let msg = match serde_json::to_string(&WebSocketMessage {
-                        id: None,
-                        payload: WebSocketPayload::Event(event),
-                    }) {
+                event = rx.recv() => {
+                    // The queue closes when the client was reaped
+                    let Some(event) = event else {
+                        let _ = sender.send(Message::Close(None)).await;
+                        break;
+                    };
+                    let msg = match event_message(event) {
                         Ok(m) => m,
                         Err(e) => {
                             warn!("Failed to serialize WebSocket event: {}", e);
@@ -397,13 +563,21 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                         }
                     };
 
-                    if sender.send(Message::Text(msg)).await.is_err() {
+                    if sender.send(msg).await.is_err() {
                         break;
                     }
                 }
 
-                // Send periodic pings
+                // Send periodic pings and reap silent clients
                 _ = ping_timer.tick() => {
+                    if ws_handler.reap_if_idle(client_id).await {
+                        let _ = sender.send(Message::Close(None)).await;
+                        break;
+                    }
+                    if sender.send(Message::Ping(Vec::new())).await.is_err() {
+                        break;
+                    }
+
                     let timestamp = std::time::SystemTime::now()
                         .duration_since(std::time::UNIX_EPOCH)
                         .unwrap_or_default()
@@ -430,10 +604,12 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     });
 
     let ws_handler_recv = state.ws_handler.clone();
+    let screenshot_store = state.screenshot_store.clone();
 
     // Task to receive messages from client
     let mut recv_task = tokio::spawn(async move {
         while let Some(Ok(msg)) = receiver.next().await {
+            ws_handler_recv.record_activity(client_id).await;
             match msg {
                 Message::Text(text) => {
                     // Try to parse as WebSocket command
@@ -451,6 +627,11 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                                         // Pong is handled by the send task via broadcast
                                         ws_handler_recv.broadcast(BrowserEvent::Pong { timestamp }).await;
                                     }
+                                    WebSocketCommand::FetchScreenshot { handle, chunk_size } => {
+                                        ws_handler_recv
+                                            .push_screenshot(client_id, &screenshot_store, &handle, chunk_size)
+                                            .await;
+                                    }
                                 }
                             }
                         }
@@ -459,9 +640,10 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                         }
                     }
                 }
-                Message::Binary(_) => {
-                    // Binary messages not supported
-                    debug!("Received unsupported binary message");
+                Message::Binary(data) => {
+                    if let Err(e) = ws_handler_recv.handle_binary(&data).await {
+                        debug!("Failed to decode binary WebSocket frame: {}", e);
+                    }
                 }
                 Message::Ping(_data) => {
                     // WebSocket protocol ping - handled automatically by axum
@@ -520,6 +702,116 @@ mod tests {
         assert!(events.contains(&"TabCreated".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_push_screenshot_streams_chunks_in_order() {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+        let handler = WebSocketHandler::new();
+        let (tx, mut rx) = mpsc::channel(16);
+        let client_id = handler.add_client(tx).await;
+
+        let store = ScreenshotStore::default();
+        let data: Vec<u8> = (0..=255).collect();
+        let stored = store.insert(data.clone(), "image/png");
+
+        handler.push_screenshot(client_id, &store, &stored.handle, Some(100)).await;
+
+        let mut reassembled = Vec::new();
+        let mut last_seen = false;
+        while let Ok(event) = rx.try_recv() {
+            let BrowserEvent::ScreenshotChunk { offset, total_bytes, data, is_last, .. } = event else {
+                unreachable!("Expected ScreenshotChunk, got {:?}", event);
+            };
+            assert_eq!(offset, reassembled.len());
+            assert_eq!(total_bytes, 256);
+            reassembled.extend(BASE64.decode(data).unwrap());
+            last_seen = is_last;
+        }
+        assert!(last_seen);
+        assert_eq!(reassembled, data);
+
+        handler.push_screenshot(client_id, &store, "missing", None).await;
+        assert!(matches!(rx.try_recv(), Ok(BrowserEvent::Error { .. })));
+    }
+
+    #[test]
+    fn test_binary_frame_round_trip() {
+        let tab_id = Uuid::new_v4();
+        let frame = BinaryFrame::new(BINARY_TAG_SCREENSHOT, tab_id, vec![1, 2, 3]);
+        let encoded = frame.encode();
+
+        assert_eq!(encoded[0], BINARY_TAG_SCREENSHOT);
+        assert_eq!(&encoded[1..5], &16u32.to_be_bytes());
+        assert_eq!(BinaryFrame::decode(&encoded), Ok(frame));
+
+        assert_eq!(BinaryFrame::decode(&[0x02, 0, 0]), Err(BinaryFrameError::Truncated(3)));
+        assert_eq!(
+            BinaryFrame::decode(&[0x02, 0, 0, 0, 2, 0xAA, 0xBB]),
+            Err(BinaryFrameError::InvalidTabId(2))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_binary_sends_binary_frame() {
+        let handler = WebSocketHandler::new();
+        let (tx, mut rx) = mpsc::channel(16);
+        handler.add_client(tx).await;
+
+        let tab_id = Uuid::new_v4();
+        handler.send_binary(tab_id, Bytes::from_static(b"\x89PNG")).await;
+
+        let event = rx.try_recv().unwrap();
+        let Ok(Message::Binary(data)) = event_message(event) else {
+            unreachable!("Expected a binary frame");
+        };
+        let frame = BinaryFrame::decode(&data).unwrap();
+        assert_eq!(frame.tag, BINARY_TAG_DATA);
+        assert_eq!(frame.tab_id, tab_id);
+        assert_eq!(&frame.payload[..], b"\x89PNG");
+
+        // Other events still go out as JSON text
+        let pong = event_message(BrowserEvent::Pong { timestamp: 1 }).unwrap();
+        assert!(matches!(pong, Message::Text(_)));
+    }
+
+    #[tokio::test]
+    async fn test_handle_binary_decodes_without_relaying() {
+        let handler = WebSocketHandler::new();
+        let (tx, mut rx) = mpsc::channel(16);
+        handler.add_client(tx).await;
+
+        let frame = BinaryFrame::new(BINARY_TAG_FRAME_UPDATE, Uuid::new_v4(), vec![7; 32]);
+        assert_eq!(handler.handle_binary(&frame.encode()).await, Ok(frame.clone()));
+        // A client frame never reaches the other clients
+        assert!(rx.try_recv().is_err());
+        assert!(handler.handle_binary(b"\x01").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unresponsive_client_is_reaped_after_idle_timeout() {
+        let handler = WebSocketHandler::with_keepalive(WebSocketKeepalive {
+            ping_interval: Duration::from_millis(20),
+            max_missed_pongs: 2,
+        });
+        let (dead_tx, mut dead_rx) = mpsc::channel(16);
+        let (live_tx, _live_rx) = mpsc::channel(16);
+        let dead = handler.add_client(dead_tx).await;
+        let live = handler.add_client(live_tx).await;
+
+        // Only the live client answers pings
+        for _ in 0..6 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            handler.record_activity(live).await;
+        }
+
+        assert!(!handler.reap_if_idle(live).await);
+        assert!(handler.reap_if_idle(dead).await);
+        assert_eq!(handler.client_count().await, 1);
+        // The reaped client's queue is closed, which ends its send task
+        assert!(dead_rx.recv().await.is_none());
+        assert!(!handler.reap_if_idle(dead).await);
+    }
+
     #[tokio::test]
     async fn test_websocket_handler_client_count() {
         let handler = WebSocketHandler::new();
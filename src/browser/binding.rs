@@ -0,0 +1,109 @@
+//! Page-to-automation bindings.
+//!
+//! Backs [`BrowserEngine::expose_binding`](crate::browser::BrowserEngine::expose_binding).
+//! A binding is a `window.<name>(payload)` function installed in the page;
+//! each call is forwarded to the engine and published as
+//! [`BrowserEvent::Binding`](crate::browser::events::BrowserEvent::Binding),
+//! so page scripts can push events without the automation side polling.
+//!
+//! The installed function reports through `console.debug` with
+//! [`BINDING_CONSOLE_PREFIX`], the same channel used for failed-request
+//! reports; the engine parses it with [`BindingCall::from_console_report`].
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::BrowserError;
+
+/// Console prefix used by [`binding_script`] to report calls.
+pub const BINDING_CONSOLE_PREFIX: &str = "KI_BINDING:";
+
+/// A call of an exposed binding from page script.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BindingCall {
+    /// Name of the binding.
+    pub name: String,
+
+    /// Argument passed by the page, round-tripped through JSON.
+    #[serde(default)]
+    pub payload: Value,
+}
+
+impl BindingCall {
+    /// Parses a report emitted by a [`binding_script`] function.
+    ///
+    /// Returns `None` if the message does not carry the binding prefix or
+    /// the payload is malformed.
+    pub fn from_console_report(message: &str) -> Option<Self> {
+        let payload = message.strip_prefix(BINDING_CONSOLE_PREFIX)?;
+        serde_json::from_str(payload).ok()
+    }
+}
+
+/// Checks that `name` is a plain JavaScript identifier.
+pub fn validate_binding_name(name: &str) -> Result<(), BrowserError> {
+    let mut chars = name.chars();
+    let valid_start = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$');
+    if valid_start && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$') {
+        Ok(())
+    } else {
+        Err(BrowserError::InvalidRequest(format!(
+            "Invalid binding name '{}': expected a JavaScript identifier",
+            name
+        )))
+    }
+}
+
+/// Builds the page script that installs `window.<name>`.
+///
+/// `console.debug` is captured at install time, so a page replacing it
+/// later does not break the binding. Payloads that cannot be serialized
+/// (cycles, functions) make the binding throw in the page.
+pub fn binding_script(name: &str) -> String {
+    let name_json = serde_json::to_string(name).unwrap_or_else(|_| "\"\"".to_string());
+    format!(
+        r#"(function() {{
+    const name = {name_json};
+    if (window[name] && window[name].__kiBinding) return;
+    const report = console.debug.bind(console);
+    const binding = function(payload) {{
+        const json = JSON.stringify({{ name: name, payload: payload === undefined ? null : payload }});
+        report('{prefix}' + json);
+    }};
+    Object.defineProperty(binding, '__kiBinding', {{ value: true, enumerable: false }});
+    Object.defineProperty(window, name, {{ value: binding, enumerable: false, configurable: true, writable: false }});
+}})();"#,
+        prefix = BINDING_CONSOLE_PREFIX,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_binding_call_from_console_report() {
+        let call = BindingCall::from_console_report(
+            r#"KI_BINDING:{"name":"onEvent","payload":{"kind":"click","n":2}}"#,
+        )
+        .unwrap();
+        assert_eq!(call.name, "onEvent");
+        assert_eq!(call.payload, json!({"kind": "click", "n": 2}));
+
+        assert!(BindingCall::from_console_report("KI_NETFAIL:{}").is_none());
+        assert!(BindingCall::from_console_report("KI_BINDING:not json").is_none());
+    }
+
+    #[test]
+    fn test_validate_binding_name() {
+        assert!(validate_binding_name("onEvent").is_ok());
+        assert!(validate_binding_name("$ki_cb2").is_ok());
+        assert!(validate_binding_name("").is_err());
+        assert!(validate_binding_name("2fast").is_err());
+        assert!(validate_binding_name("a.b").is_err());
+        assert!(binding_script("onEvent").contains(r#"const name = "onEvent";"#));
+    }
+}
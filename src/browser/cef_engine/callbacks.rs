@@ -12,7 +12,8 @@ use cef::{
     LifeSpanHandler, LoadHandler, PaintElementType, Rect, RenderHandler,
     RenderProcessHandler, ScreenInfo, TransitionType, WindowInfo,
     WindowOpenDisposition, PopupFeatures, DictionaryValue, DisplayHandler,
-    LogSeverity,
+    LogSeverity, Request, RequestHandler, ResourceRequestHandler, ReturnValue, Callback,
+    Response, UrlrequestStatus,
     // dialog handler wrap macros (must be imported by name, unlike the older wrap_*!)
     wrap_jsdialog_handler, wrap_dialog_handler,
     JsdialogHandler, JsdialogCallback, JsdialogType,
@@ -27,9 +28,14 @@ use cef::{
     ImplRenderHandler, WrapRenderHandler,
     ImplLifeSpanHandler, WrapLifeSpanHandler,
     ImplLoadHandler, WrapLoadHandler,
+    ImplRequestHandler, WrapRequestHandler,
+    ImplResourceRequestHandler, WrapResourceRequestHandler,
+    Task, ImplTask, WrapTask, ThreadId,
     ImplRenderProcessHandler, WrapRenderProcessHandler,
     // Traits needed to call methods on CEF types
-    ImplCommandLine, ImplFrame, ImplBrowser, ImplV8Context,
+    ImplCommandLine, ImplFrame, ImplBrowser, ImplV8Context, ImplRequest,
+    ImplProcessMessage, ImplListValue, ImplV8Value, ImplV8Exception,
+    ImplBrowserHost, ImplCallback, ImplDictionaryValue, ImplResponse,
     // rc module for Rc trait (needed by wrap macros)
     rc::Rc,
 };
@@ -38,14 +44,35 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::mpsc;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use crate::browser::events::BrowserEvent;
+use crate::browser::binding::{binding_script, BindingCall, BINDING_CONSOLE_PREFIX};
+use crate::browser::consent::{ConsentClick, CONSENT_CONSOLE_PREFIX};
+use crate::browser::engine::CacheMode;
+use crate::browser::har::{HarRecorder, HarResponse};
+use crate::browser::interceptor::{
+    apply_interceptor, InterceptedRequest, InterceptorSlot, RequestInterceptor, BLOCKED_BY_CLIENT,
+};
+use crate::browser::redirect::{RedirectChain, CLIENT_REDIRECT_WINDOW};
+use crate::browser::network::{record_failed_request, FailedRequest, FAILED_REQUEST_CONSOLE_PREFIX};
+use crate::browser::page_wait::NetworkActivity;
+use crate::browser::response_capture::{
+    response_capture_script, CapturedResponse, RESPONSE_CAPTURE_CONSOLE_PREFIX,
+};
 use crate::browser::tab::TabStatus;
+use crate::browser::throttle::{NetworkThrottleConfig, INTERNET_DISCONNECTED};
+use crate::stealth::client_hints::{ClientHints, CLIENT_HINT_HEADERS};
 use crate::stealth::StealthConfig;
+use super::frame_buffer::FrameBufferWriter;
 use super::tab::CefTab;
 use super::CefCommand;
-use super::{BROWSER_ROUTER, RENDERER_ROUTER, JS_RESULT_STORE};
+use super::{BROWSER_ROUTER, RENDERER_ROUTER, JS_RESULT_STORE, ENGINE_EVENTS};
+use super::{EVALUATE_JS_MESSAGE, EVALUATE_JS_RESULT_MESSAGE, PENDING_EVALUATIONS};
+use super::navigation::{
+    evaluate_wrapper_script, expression_function_script, statements_function_script,
+};
 
 use cef::wrapper::message_router::{
     BrowserSideHandler, BrowserSideCallback,
@@ -172,6 +199,107 @@ fn inject_stealth_on_context_created(
     debug!("Render-process stealth injection done for context (url={})", url);
 }
 
+// ============================================================================
+// EvaluateJs round-trip: renderer evaluates, browser resolves the caller
+// ============================================================================
+
+/// Evaluates the script of an [`EVALUATE_JS_MESSAGE`] in `frame`'s V8 context
+/// and sends the JSON-serialized result back as [`EVALUATE_JS_RESULT_MESSAGE`].
+///
+/// Runs in the render process. Returns whether `message` was an evaluation
+/// request (and thus handled here).
+fn evaluate_in_renderer(frame: Option<&Frame>, message: Option<&cef::ProcessMessage>) -> bool {
+    let Some(message) = message else { return false };
+    if CefString::from(&message.name()).to_string() != EVALUATE_JS_MESSAGE {
+        return false;
+    }
+    let Some(frame) = frame else { return true };
+    let Some(args) = message.argument_list() else { return true };
+    let query_id = CefString::from(&args.string(0)).to_string();
+    let script = CefString::from(&args.string(1)).to_string();
+
+    let result = match frame.v8_context() {
+        Some(ctx) => {
+            let eval = |code: &str| {
+                let code = CefString::from(code);
+                let empty_url = CefString::from("");
+                let mut retval: Option<cef::V8Value> = None;
+                let mut exception: Option<cef::V8Exception> = None;
+                ctx.eval(
+                    Some(&code),
+                    Some(&empty_url),
+                    0,
+                    Some(&mut retval),
+                    Some(&mut exception),
+                );
+                match exception {
+                    Some(exception) => Err(CefString::from(&exception.message()).to_string()),
+                    None => Ok(retval),
+                }
+            };
+            // Compiling the expression form runs nothing, so a syntax error
+            // there cannot have side effects before the statements fallback
+            let function = match eval(&expression_function_script(&script)) {
+                Ok(_) => expression_function_script(&script),
+                Err(_) => statements_function_script(&script),
+            };
+            eval(&evaluate_wrapper_script(&function)).map(|value| match value {
+                Some(value) if value.is_string() != 0 => {
+                    Some(CefString::from(&value.string_value()).to_string())
+                }
+                _ => None,
+            })
+        }
+        None => Err("No V8 context for frame".to_string()),
+    };
+
+    let Some(mut reply) =
+        cef::process_message_create(Some(&CefString::from(EVALUATE_JS_RESULT_MESSAGE)))
+    else {
+        return true;
+    };
+    if let Some(reply_args) = reply.argument_list() {
+        let (ok, payload) = match &result {
+            Ok(json) => (true, json.as_deref().unwrap_or_default()),
+            Err(error) => (false, error.as_str()),
+        };
+        reply_args.set_string(0, Some(&CefString::from(query_id.as_str())));
+        reply_args.set_bool(1, ok as i32);
+        reply_args.set_string(2, Some(&CefString::from(payload)));
+    }
+    frame.send_process_message(cef::ProcessId::BROWSER, Some(&mut reply));
+    true
+}
+
+/// Resolves the `execute_js` caller waiting on an [`EVALUATE_JS_RESULT_MESSAGE`].
+///
+/// Runs in the browser process. Returns whether `message` was an evaluation
+/// result. Results for callers that already timed out are dropped.
+fn resolve_evaluation(message: Option<&cef::ProcessMessage>) -> bool {
+    let Some(message) = message else { return false };
+    if CefString::from(&message.name()).to_string() != EVALUATE_JS_RESULT_MESSAGE {
+        return false;
+    }
+    let Some(args) = message.argument_list() else { return true };
+    let Ok(query_id) = CefString::from(&args.string(0)).to_string().parse::<i64>() else {
+        return true;
+    };
+    let ok = args.bool(1) != 0;
+    let payload = CefString::from(&args.string(2)).to_string();
+
+    if let Some(response) = PENDING_EVALUATIONS.lock().remove(&query_id) {
+        let result = if !ok {
+            Err(anyhow::anyhow!("JS error: {}", payload))
+        } else if payload.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(payload))
+        };
+        let _ = response.send(result);
+    }
+    true
+}
+
 cef::wrap_render_process_handler! {
     pub(crate) struct KiBrowserRenderProcessHandler {
         tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
@@ -222,6 +350,9 @@ cef::wrap_render_process_handler! {
             source_process: cef::ProcessId,
             message: Option<&mut cef::ProcessMessage>,
         ) -> ::std::os::raw::c_int {
+            if evaluate_in_renderer(frame.as_deref(), message.as_deref()) {
+                return 1;
+            }
             let handled = RENDERER_ROUTER.on_process_message_received(
                 browser.map(|b| b.clone()),
                 frame.map(|f| f.clone()),
@@ -248,6 +379,9 @@ cef::wrap_app! {
         render_process_handler_val: RenderProcessHandler,
         headless: bool,
         use_egl: bool,
+        cache_mode: CacheMode,
+        single_process: bool,
+        proxy_pac_url: Option<String>,
     }
 
     impl App {
@@ -382,7 +516,20 @@ cef::wrap_app! {
                     Some(&CefString::from("disable-features")),
                     Some(&CefString::from("NetworkServiceSandbox")),
                 );
-                cmd.append_switch(Some(&CefString::from("single-process")));
+                if self.single_process {
+                    cmd.append_switch(Some(&CefString::from("single-process")));
+                }
+
+                if let Some(ref pac_url) = self.proxy_pac_url {
+                    cmd.append_switch_with_value(
+                        Some(&CefString::from("proxy-pac-url")),
+                        Some(&CefString::from(pac_url.as_str())),
+                    );
+                }
+
+                for switch in self.cache_mode.command_line_switches() {
+                    cmd.append_switch(Some(&CefString::from(*switch)));
+                }
 
                 debug!("CEF command line configured for stealth mode");
             }
@@ -413,6 +560,7 @@ cef::wrap_client! {
         display_handler_val: DisplayHandler,
         jsdialog_handler_val: JsdialogHandler,
         dialog_handler_val: DialogHandler,
+        request_handler_val: RequestHandler,
     }
 
     impl Client {
@@ -440,6 +588,10 @@ cef::wrap_client! {
             Some(self.dialog_handler_val.clone())
         }
 
+        fn request_handler(&self) -> Option<RequestHandler> {
+            Some(self.request_handler_val.clone())
+        }
+
         fn on_process_message_received(
             &self,
             browser: Option<&mut Browser>,
@@ -447,6 +599,9 @@ cef::wrap_client! {
             source_process: cef::ProcessId,
             message: Option<&mut cef::ProcessMessage>,
         ) -> ::std::os::raw::c_int {
+            if resolve_evaluation(message.as_deref()) {
+                return 1;
+            }
             let handled = BROWSER_ROUTER.on_process_message_received(
                 browser.map(|b| b.clone()),
                 frame.map(|f| f.clone()),
@@ -470,9 +625,9 @@ cef::wrap_client! {
 cef::wrap_render_handler! {
     pub(crate) struct KiBrowserRenderHandlerImpl {
         tab_id: Uuid,
-        frame_buffer: Arc<RwLock<Vec<u8>>>,
-        frame_size: Arc<RwLock<(u32, u32)>>,
+        frame_writer: Arc<FrameBufferWriter>,
         viewport_size: Arc<RwLock<(u32, u32)>>,
+        device_scale_factor: Arc<RwLock<f64>>,
         frame_version: Arc<std::sync::atomic::AtomicU64>,
     }
 
@@ -490,7 +645,7 @@ cef::wrap_render_handler! {
         fn screen_info(&self, _browser: Option<&mut Browser>, screen_info: Option<&mut ScreenInfo>) -> ::std::os::raw::c_int {
             if let Some(info) = screen_info {
                 let (w, h) = *self.viewport_size.read();
-                info.device_scale_factor = 1.0;
+                info.device_scale_factor = *self.device_scale_factor.read() as f32;
                 info.depth = 32;
                 info.depth_per_component = 8;
                 info.is_monochrome = 0;
@@ -527,12 +682,13 @@ cef::wrap_render_handler! {
                 }
                 let buffer_slice = unsafe { std::slice::from_raw_parts(buffer, buffer_size) };
 
-                let mut fb = self.frame_buffer.write();
-                fb.clear();
-                fb.extend_from_slice(buffer_slice);
-
-                let mut size = self.frame_size.write();
-                *size = (width as u32, height as u32);
+                // CEF hands over tightly packed rows (`width * 4` bytes each)
+                self.frame_writer.publish(
+                    buffer_slice,
+                    width as u32,
+                    height as u32,
+                    Some(width as usize * 4),
+                );
 
                 // Signal that a new frame is available (for stream encoder + GUI).
                 self.frame_version.fetch_add(1, std::sync::atomic::Ordering::Release);
@@ -563,6 +719,8 @@ cef::wrap_life_span_handler! {
         tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
         browser_created: Arc<AtomicBool>,
         popup_tx: Option<mpsc::UnboundedSender<CefCommand>>,
+        /// Simulated connection whose download bandwidth is applied to the browser
+        network_throttle: Option<NetworkThrottleConfig>,
     }
 
     impl LifeSpanHandler {
@@ -645,6 +803,9 @@ cef::wrap_life_span_handler! {
                     "Tab {} mapped to CEF browser_id {} (CDP TargetId)",
                     self.tab_id, bid
                 );
+                if let Some(throttle) = &self.network_throttle {
+                    emulate_download_bandwidth(b, throttle);
+                }
             }
 
             self.browser_created.store(true, Ordering::SeqCst);
@@ -674,12 +835,39 @@ cef::wrap_life_span_handler! {
 // Load handler
 // ============================================================================
 
+/// Records a failed request on the tab and publishes `BrowserEvent::RequestFailed`.
+fn report_failed_request(
+    tabs: &RwLock<HashMap<Uuid, CefTab>>,
+    tab_id: Uuid,
+    failed: FailedRequest,
+) {
+    debug!(
+        "Request failed in tab {}: {} ({:?}, {})",
+        tab_id, failed.url, failed.status, failed.error
+    );
+    let _ = ENGINE_EVENTS.send(BrowserEvent::RequestFailed {
+        tab_id: tab_id.to_string(),
+        url: failed.url.clone(),
+        status: failed.status,
+        error: failed.error.clone(),
+    });
+    if let Some(tab) = tabs.write().get_mut(&tab_id) {
+        record_failed_request(&mut tab.failed_requests, failed);
+    }
+}
+
 /// Load handler for navigation events and stealth injection using v144 API.
 cef::wrap_load_handler! {
     pub(crate) struct KiBrowserLoadHandlerImpl {
         tab_id: Uuid,
         tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
         stealth_config: Arc<StealthConfig>,
+        /// Consent dismissal script run after each page load, if enabled
+        consent_script: Option<Arc<String>>,
+        /// Failed sub-resource observer installed in every frame, if enabled
+        failed_request_observer_script: Option<Arc<String>>,
+        /// Sub-resource timeout script installed in every frame, if enabled
+        resource_timeout_script: Option<Arc<String>>,
     }
 
     impl LoadHandler {
@@ -731,6 +919,40 @@ cef::wrap_load_handler! {
                         "Stealth scripts injected for tab {} on load start",
                         self.tab_id
                     );
+
+                    // A new top-level document starts with a clean failure list
+                    if let Some(tab) = self.tabs.write().get_mut(&self.tab_id) {
+                        tab.failed_requests.clear();
+                    }
+                }
+
+                let empty_url = CefString::from("");
+                if let Some(ref script) = self.failed_request_observer_script {
+                    let script_cef = CefString::from(script.as_str());
+                    f.execute_java_script(Some(&script_cef), Some(&empty_url), 0);
+                }
+                if let Some(ref script) = self.resource_timeout_script {
+                    let script_cef = CefString::from(script.as_str());
+                    f.execute_java_script(Some(&script_cef), Some(&empty_url), 0);
+                }
+
+                // Re-install exposed bindings and the response capture hook
+                let (bindings, capture_max_bytes) = self
+                    .tabs
+                    .read()
+                    .get(&self.tab_id)
+                    .map(|tab| {
+                        let capture = tab.response_capture.as_ref().map(|c| c.max_bytes());
+                        (tab.bindings.clone(), capture)
+                    })
+                    .unwrap_or_default();
+                for name in bindings {
+                    let script_cef = CefString::from(binding_script(&name).as_str());
+                    f.execute_java_script(Some(&script_cef), Some(&empty_url), 0);
+                }
+                if let Some(max_bytes) = capture_max_bytes {
+                    let script_cef = CefString::from(response_capture_script(max_bytes).as_str());
+                    f.execute_java_script(Some(&script_cef), Some(&empty_url), 0);
                 }
             }
         }
@@ -742,18 +964,41 @@ cef::wrap_load_handler! {
             http_status_code: ::std::os::raw::c_int,
         ) {
             if let Some(f) = frame {
+                if http_status_code >= 400 {
+                    let url = CefString::from(&f.url()).to_string();
+                    let resource_type = if f.is_main() != 0 { "document" } else { "subdocument" };
+                    report_failed_request(
+                        &self.tabs,
+                        self.tab_id,
+                        FailedRequest::http_status(url, http_status_code as u16)
+                            .with_resource_type(resource_type),
+                    );
+                }
+
                 if f.is_main() != 0 {
                     // Update tab URL
-                    let mut tabs = self.tabs.write();
-                    if let Some(tab) = tabs.get_mut(&self.tab_id) {
-                        let url = f.url();
-                        tab.url = CefString::from(&url).to_string();
+                    {
+                        let mut tabs = self.tabs.write();
+                        if let Some(tab) = tabs.get_mut(&self.tab_id) {
+                            let url = f.url();
+                            tab.url = CefString::from(&url).to_string();
+                            tab.input_dwell.page_loaded();
+                        }
                     }
 
                     info!(
                         "Page loaded for tab {}: status={}",
                         self.tab_id, http_status_code
                     );
+
+                    // Clicks reach the display handler as KI_CONSENT reports
+                    if let Some(ref script) = self.consent_script {
+                        if http_status_code < 400 {
+                            let script_cef = CefString::from(script.as_str());
+                            let empty_url = CefString::from("");
+                            f.execute_java_script(Some(&script_cef), Some(&empty_url), 0);
+                        }
+                    }
                 }
             }
         }
@@ -767,9 +1012,22 @@ cef::wrap_load_handler! {
             failed_url: Option<&CefString>,
         ) {
             if let Some(f) = frame {
+                let url_str = failed_url.map(|u| u.to_string()).unwrap_or_default();
+                let err_str = error_text.map(|e| e.to_string()).unwrap_or_default();
+
+                // ERR_ABORTED fires when a load is superseded (new navigation, stop),
+                // which is not a failure of the request itself
+                if !err_str.contains("ERR_ABORTED") {
+                    let resource_type = if f.is_main() != 0 { "document" } else { "subdocument" };
+                    report_failed_request(
+                        &self.tabs,
+                        self.tab_id,
+                        FailedRequest::load_error(url_str.clone(), err_str.clone())
+                            .with_resource_type(resource_type),
+                    );
+                }
+
                 if f.is_main() != 0 {
-                    let url_str = failed_url.map(|u| u.to_string()).unwrap_or_default();
-                    let err_str = error_text.map(|e| e.to_string()).unwrap_or_default();
 
                     let error_msg = format!(
                         "Failed to load {}: {:?} - {}",
@@ -780,7 +1038,13 @@ cef::wrap_load_handler! {
 
                     let mut tabs = self.tabs.write();
                     if let Some(tab) = tabs.get_mut(&self.tab_id) {
-                        tab.status = TabStatus::Error(error_msg.clone());
+                        // Keep the reason of a navigation cancelled by the
+                        // request handler (redirect loop/limit)
+                        let cancelled = err_str.contains("ERR_ABORTED")
+                            && matches!(tab.status, TabStatus::Error(_));
+                        if !cancelled {
+                            tab.status = TabStatus::Error(error_msg.clone());
+                        }
                     }
 
                     error!("Load error for tab {}: {}", self.tab_id, error_msg);
@@ -790,6 +1054,352 @@ cef::wrap_load_handler! {
     }
 }
 
+// ============================================================================
+// RequestHandler: redirect tracking for top-level navigations
+// ============================================================================
+
+/// Request handler following the redirect chain of `navigate` calls.
+///
+/// Every main-frame navigation passes through `on_before_browse`. The first
+/// one after `navigate` starts the tab's [`RedirectChain`]; HTTP redirects
+/// and script navigations without a user gesture shortly after the previous
+/// hop extend it. A loop or an exceeded limit cancels the navigation and
+/// puts the tab into the error state, so waits fail fast instead of timing
+/// out on a page that bounces forever.
+cef::wrap_request_handler! {
+    pub(crate) struct KiBrowserRequestHandlerImpl {
+        tab_id: Uuid,
+        tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+        /// Applies the request interceptor and rewrites client hint headers
+        resource_request_handler_val: Option<ResourceRequestHandler>,
+    }
+
+    impl RequestHandler {
+        fn resource_request_handler(
+            &self,
+            _browser: Option<&mut Browser>,
+            _frame: Option<&mut Frame>,
+            _request: Option<&mut Request>,
+            _is_navigation: ::std::os::raw::c_int,
+            _is_download: ::std::os::raw::c_int,
+            _request_initiator: Option<&CefString>,
+            _disable_default_handling: Option<&mut ::std::os::raw::c_int>,
+        ) -> Option<ResourceRequestHandler> {
+            self.resource_request_handler_val.clone()
+        }
+
+        fn on_before_browse(
+            &self,
+            _browser: Option<&mut Browser>,
+            frame: Option<&mut Frame>,
+            request: Option<&mut Request>,
+            user_gesture: ::std::os::raw::c_int,
+            is_redirect: ::std::os::raw::c_int,
+        ) -> ::std::os::raw::c_int {
+            let (Some(frame), Some(request)) = (frame, request) else {
+                return 0;
+            };
+            if frame.is_main() == 0 {
+                return 0;
+            }
+            let url = CefString::from(&request.url()).to_string();
+
+            let error = {
+                let mut tabs = self.tabs.write();
+                let Some(tab) = tabs.get_mut(&self.tab_id) else {
+                    return 0;
+                };
+                if is_redirect == 0 {
+                    if let Some(max_redirects) = tab.pending_max_redirects.take() {
+                        // The request issued by navigate itself
+                        tab.redirect_chain = Some(RedirectChain::new(&url, max_redirects));
+                        return 0;
+                    }
+                }
+                let Some(chain) = tab.redirect_chain.as_mut() else {
+                    return 0;
+                };
+                let is_client_redirect =
+                    user_gesture == 0 && chain.since_last_hop() < CLIENT_REDIRECT_WINDOW;
+                if is_redirect == 0 && !is_client_redirect {
+                    // A new navigation, not part of this chain
+                    tab.redirect_chain = None;
+                    return 0;
+                }
+                match chain.follow(&url) {
+                    Ok(()) => return 0,
+                    Err(e) => {
+                        tab.redirect_chain = None;
+                        tab.status = TabStatus::Error(e.to_string());
+                        e
+                    }
+                }
+            }; // Write lock released before reporting.
+
+            error!("Navigation aborted in tab {}: {}", self.tab_id, error);
+            report_failed_request(
+                &self.tabs,
+                self.tab_id,
+                FailedRequest::load_error(url, error.to_string()).with_resource_type("document"),
+            );
+            1 // cancel the navigation
+        }
+    }
+}
+
+// ============================================================================
+// ResourceRequestHandler: request interception and client hint headers
+// ============================================================================
+
+/// Headers handed to a request interceptor; CEF has no cheap way to list
+/// all headers of a request, so interceptors see the ones they act on.
+const INTERCEPTED_HEADERS: &[&str] = &[
+    "Accept",
+    "Accept-Language",
+    "Content-Type",
+    "Cookie",
+    "Origin",
+    "Referer",
+    "User-Agent",
+];
+
+/// Snapshot of `request` for a [`RequestInterceptor`](crate::browser::RequestInterceptor).
+///
+/// The body is not read: upload data lives in CEF post data elements that
+/// may be file-backed.
+fn intercepted_request(request: &Request) -> InterceptedRequest {
+    let mut intercepted = InterceptedRequest::new(
+        CefString::from(&request.url()).to_string(),
+        CefString::from(&request.method()).to_string(),
+    );
+    for name in INTERCEPTED_HEADERS {
+        let value = CefString::from(&request.header_by_name(Some(&CefString::from(*name)))).to_string();
+        if !value.is_empty() {
+            intercepted.headers.insert((*name).to_string(), value);
+        }
+    }
+    intercepted
+}
+
+/// Writes the parts of `sent` that differ from `original` back to `request`.
+fn apply_intercepted(request: &mut Request, original: &InterceptedRequest, sent: &InterceptedRequest) {
+    if sent.method != original.method {
+        request.set_method(Some(&CefString::from(sent.method.as_str())));
+    }
+    for (name, value) in &sent.headers {
+        if original.headers.get(name) != Some(value) {
+            request.set_header_by_name(
+                Some(&CefString::from(name.as_str())),
+                Some(&CefString::from(value.as_str())),
+                1,
+            );
+        }
+    }
+    // Last, as changing the URL of a resource request restarts it as a redirect
+    if sent.url != original.url {
+        request.set_url(Some(&CefString::from(sent.url.as_str())));
+    }
+}
+
+/// Response headers kept in HAR entries, for the same reason as
+/// [`INTERCEPTED_HEADERS`].
+const RECORDED_RESPONSE_HEADERS: &[&str] = &[
+    "Cache-Control",
+    "Content-Length",
+    "Content-Type",
+    "Location",
+    "Set-Cookie",
+];
+
+/// The running HAR recording of a tab, if any.
+fn har_recorder(tabs: &RwLock<HashMap<Uuid, CefTab>>, tab_id: Uuid) -> Option<Arc<HarRecorder>> {
+    tabs.read().get(&tab_id).and_then(|tab| tab.har_recorder.clone())
+}
+
+/// HAR view of a completed request's response; status `0` if none arrived.
+fn har_response(response: Option<&Response>, received_content_length: i64) -> HarResponse {
+    let Some(response) = response else {
+        return HarResponse::new(0, "");
+    };
+    let mut har = HarResponse::new(
+        u16::try_from(response.status()).unwrap_or(0),
+        CefString::from(&response.status_text()).to_string(),
+    )
+    .with_mime_type(CefString::from(&response.mime_type()).to_string())
+    .with_body_size(received_content_length);
+    for name in RECORDED_RESPONSE_HEADERS {
+        let value = CefString::from(&response.header_by_name(Some(&CefString::from(*name)))).to_string();
+        if value.is_empty() {
+            continue;
+        }
+        if *name == "Location" {
+            har.redirect_url = value.clone();
+        }
+        har = har.with_header(*name, value);
+    }
+    har
+}
+
+/// Counts the requests of a tab for network idle waits, runs every request
+/// through the engine's request interceptor and replaces the `Sec-CH-UA*` headers Chromium derives from its own build
+/// with the spoofed [`ClientHints`], so headers and `navigator.userAgentData`
+/// describe the same browser as the user agent.
+///
+/// Requests that are sent, and their responses, are added to the tab's HAR
+/// recording while one is running.
+///
+/// Blocked requests are cancelled and reported as failed with
+/// [`BLOCKED_BY_CLIENT`]. Only client hint headers Chromium actually sends
+/// are replaced: low-entropy hints go to secure origins only, high-entropy
+/// ones only after an `Accept-CH`.
+///
+/// With a network throttle, requests are held back for its latency plus
+/// the upload time of their body, or cancelled with
+/// [`INTERNET_DISCONNECTED`] while it is offline.
+cef::wrap_resource_request_handler! {
+    pub(crate) struct KiBrowserResourceRequestHandlerImpl {
+        tab_id: Uuid,
+        tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+        interceptor: InterceptorSlot,
+        /// Spoofed client hints, if the identity has any
+        client_hints: Option<Arc<ClientHints>>,
+        /// In-flight requests of the tab, shared with its [`CefTab`]
+        network_activity: Arc<NetworkActivity>,
+        /// Simulated connection delaying every request
+        network_throttle: Option<NetworkThrottleConfig>,
+    }
+
+    impl ResourceRequestHandler {
+        fn on_before_resource_load(
+            &self,
+            _browser: Option<&mut Browser>,
+            _frame: Option<&mut Frame>,
+            request: Option<&mut Request>,
+            callback: Option<&mut Callback>,
+        ) -> ReturnValue {
+            let Some(request) = request else {
+                return ReturnValue::CONTINUE;
+            };
+            // Cancelled requests complete as well, so every start is counted
+            self.network_activity.request_started();
+
+            if self.interceptor.read().is_some() {
+                let original = intercepted_request(request);
+                match apply_interceptor(&self.interceptor, original.clone()) {
+                    Some(sent) => apply_intercepted(request, &original, &sent),
+                    None => {
+                        debug!("Request blocked by interceptor in tab {}: {}", self.tab_id, original.url);
+                        report_failed_request(
+                            &self.tabs,
+                            self.tab_id,
+                            FailedRequest::load_error(original.url, BLOCKED_BY_CLIENT),
+                        );
+                        return ReturnValue::CANCEL;
+                    }
+                }
+            }
+
+            if let Some(client_hints) = &self.client_hints {
+                for name in CLIENT_HINT_HEADERS {
+                    let name_cef = CefString::from(*name);
+                    let sent = CefString::from(&request.header_by_name(Some(&name_cef))).to_string();
+                    if sent.is_empty() {
+                        continue;
+                    }
+                    if let Some(value) = client_hints.header_value(name) {
+                        request.set_header_by_name(Some(&name_cef), Some(&CefString::from(value.as_str())), 1);
+                    }
+                }
+            }
+
+            if let Some(recorder) = har_recorder(&self.tabs, self.tab_id) {
+                recorder.on_request(&mut intercepted_request(request));
+            }
+
+            if let Some(throttle) = &self.network_throttle {
+                if throttle.is_offline() {
+                    let url = CefString::from(&request.url()).to_string();
+                    report_failed_request(
+                        &self.tabs,
+                        self.tab_id,
+                        FailedRequest::load_error(url, INTERNET_DISCONNECTED),
+                    );
+                    return ReturnValue::CANCEL;
+                }
+                let delay = throttle.request_delay(upload_size(request));
+                if let Some(callback) = callback.filter(|_| !delay.is_zero()) {
+                    let mut task = KiContinueRequestTaskImpl::new(callback.clone());
+                    let delay_ms = delay.as_millis() as i64;
+                    if cef::post_delayed_task(ThreadId::IO, Some(&mut task), delay_ms) != 0 {
+                        return ReturnValue::CONTINUE_ASYNC;
+                    }
+                }
+            }
+            ReturnValue::CONTINUE
+        }
+
+        fn on_resource_load_complete(
+            &self,
+            _browser: Option<&mut Browser>,
+            _frame: Option<&mut Frame>,
+            request: Option<&mut Request>,
+            response: Option<&mut Response>,
+            _status: UrlrequestStatus,
+            received_content_length: i64,
+        ) {
+            self.network_activity.request_finished();
+            if let (Some(recorder), Some(request)) = (har_recorder(&self.tabs, self.tab_id), request) {
+                let url = CefString::from(&request.url()).to_string();
+                recorder.record_response(&url, har_response(response.as_deref(), received_content_length));
+            }
+        }
+    }
+}
+
+/// Limits the download bandwidth of a browser through DevTools network
+/// emulation. Latency, uploads and offline mode are applied per request by
+/// [`KiBrowserResourceRequestHandlerImpl`].
+fn emulate_download_bandwidth(browser: &Browser, throttle: &NetworkThrottleConfig) {
+    let (Some(host), Some(mut params)) = (browser.host(), cef::dictionary_value_create()) else {
+        return;
+    };
+    params.set_bool(Some(&CefString::from("offline")), 0);
+    params.set_double(Some(&CefString::from("latency")), 0.0);
+    params.set_double(
+        Some(&CefString::from("downloadThroughput")),
+        throttle.download_bytes_per_sec(),
+    );
+    // Uploads are delayed before the request starts
+    params.set_double(Some(&CefString::from("uploadThroughput")), -1.0);
+    let method = CefString::from("Network.emulateNetworkConditions");
+    if host.execute_dev_tools_method(0, Some(&method), Some(&mut params)) == 0 {
+        warn!("Failed to throttle the download bandwidth of browser {}", browser.identifier());
+    }
+}
+
+/// Resumes a request held back by the network throttle.
+cef::wrap_task! {
+    struct KiContinueRequestTaskImpl {
+        callback: Callback,
+    }
+
+    impl Task {
+        fn execute(&self) {
+            self.callback.cont();
+        }
+    }
+}
+
+/// Size of a request body as announced by its `Content-Length` header.
+fn upload_size(request: &Request) -> u64 {
+    let name = CefString::from("Content-Length");
+    CefString::from(&request.header_by_name(Some(&name)))
+        .to_string()
+        .trim()
+        .parse()
+        .unwrap_or(0)
+}
+
 // ============================================================================
 // DisplayHandler: captures console.log for JS result communication
 // ============================================================================
@@ -829,6 +1439,47 @@ cef::wrap_display_handler! {
         ) -> ::std::os::raw::c_int {
             if let Some(msg) = message {
                 let msg_str = msg.to_string();
+                if msg_str.starts_with(FAILED_REQUEST_CONSOLE_PREFIX) {
+                    if let Some(failed) = FailedRequest::from_console_report(&msg_str) {
+                        report_failed_request(&self.tabs, self.tab_id, failed);
+                    }
+                    return 1;
+                }
+                if msg_str.starts_with(BINDING_CONSOLE_PREFIX) {
+                    if let Some(call) = BindingCall::from_console_report(&msg_str) {
+                        let _ = ENGINE_EVENTS.send(BrowserEvent::Binding {
+                            tab_id: self.tab_id.to_string(),
+                            name: call.name,
+                            payload: call.payload,
+                        });
+                    }
+                    return 1;
+                }
+                if msg_str.starts_with(CONSENT_CONSOLE_PREFIX) {
+                    if let Some(click) = ConsentClick::from_console_report(&msg_str) {
+                        info!("Consent banner dismissed in tab {}: {}", self.tab_id, click.text);
+                        let _ = ENGINE_EVENTS.send(BrowserEvent::ConsentDismissed {
+                            tab_id: self.tab_id.to_string(),
+                            url: click.url,
+                            selector: click.selector,
+                            text: click.text,
+                        });
+                    }
+                    return 1;
+                }
+                if msg_str.starts_with(RESPONSE_CAPTURE_CONSOLE_PREFIX) {
+                    if let Some(response) = CapturedResponse::from_console_report(&msg_str) {
+                        if let Some(capture) = self
+                            .tabs
+                            .write()
+                            .get_mut(&self.tab_id)
+                            .and_then(|tab| tab.response_capture.as_mut())
+                        {
+                            capture.record(response);
+                        }
+                    }
+                    return 1;
+                }
                 if let Some(rest) = msg_str.strip_prefix("KI_RESULT:") {
                     if let Some(colon_pos) = rest.find(':') {
                         let id_str = &rest[..colon_pos];
@@ -862,9 +1513,13 @@ cef::wrap_display_handler! {
 /// UI to answer it), freezing the entire browser (all IPC commands time out).
 /// We answer every dialog immediately so the message loop is never blocked.
 /// Convention (matches Puppeteer/Playwright): dismiss normal dialogs; allow
-/// beforeunload so navigation/close is never blocked.
+/// beforeunload so navigation is never blocked. A close under
+/// `BeforeUnloadPolicy::Cancel` is the one case that stays on the page.
 wrap_jsdialog_handler! {
-    pub(crate) struct KiBrowserJsDialogHandlerImpl;
+    pub(crate) struct KiBrowserJsDialogHandlerImpl {
+        tab_id: Uuid,
+        tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+    }
 
     impl JsdialogHandler {
         fn on_jsdialog(
@@ -891,9 +1546,24 @@ wrap_jsdialog_handler! {
             _is_reload: ::std::os::raw::c_int,
             callback: Option<&mut JsdialogCallback>,
         ) -> ::std::os::raw::c_int {
-            // Always allow leaving the page — never block navigation/close.
+            // Leave the page unless a close asked to stay; a cancelled close
+            // is reported to the waiting close_tab by clearing `closing`.
+            // The lock is released before answering, as CEF may call back.
+            let leave = {
+                let mut tabs = self.tabs.write();
+                match tabs.get_mut(&self.tab_id) {
+                    Some(tab) if tab.closing.is_some_and(|p| !p.leaves_page()) => {
+                        tab.closing = None;
+                        false
+                    }
+                    _ => true,
+                }
+            };
+            if !leave {
+                info!("beforeunload prompt in tab {}: staying on the page", self.tab_id);
+            }
             if let Some(cb) = callback {
-                cb.cont(1, None);
+                cb.cont(leave as ::std::os::raw::c_int, None);
             }
             1 // handled
         }
@@ -0,0 +1,296 @@
+//! Cookie access through CEF's cookie manager.
+//!
+//! All tabs share the global cookie manager, so the tab of a command is only
+//! checked to exist. The manager answers asynchronously on the CEF UI
+//! thread: each operation hands its response sender to a visitor or
+//! completion callback that replies once CEF is done.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use cef::{
+    Basetime, CefString, CookieSameSite,
+    wrap_cookie_visitor, wrap_delete_cookies_callback, wrap_set_cookie_callback,
+    CookieVisitor, DeleteCookiesCallback, SetCookieCallback,
+    ImplCookieManager, ImplCookieVisitor, WrapCookieVisitor,
+    ImplDeleteCookiesCallback, WrapDeleteCookiesCallback,
+    ImplSetCookieCallback, WrapSetCookieCallback,
+    rc::Rc,
+};
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::browser::cookies::{Cookie, SameSitePolicy};
+use super::tab::CefTab;
+
+/// Microseconds between the Windows epoch (1601), used by CEF times, and
+/// the Unix epoch.
+const WINDOWS_EPOCH_OFFSET_MICROS: i64 = 11_644_473_600_000_000;
+
+fn from_basetime(time: &Basetime) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp_micros(time.val - WINDOWS_EPOCH_OFFSET_MICROS)
+}
+
+fn to_basetime(time: DateTime<Utc>) -> Basetime {
+    Basetime {
+        val: time.timestamp_micros() + WINDOWS_EPOCH_OFFSET_MICROS,
+    }
+}
+
+fn from_cef_cookie(cookie: &cef::Cookie) -> Cookie {
+    Cookie {
+        name: CefString::from(&cookie.name).to_string(),
+        value: CefString::from(&cookie.value).to_string(),
+        domain: CefString::from(&cookie.domain).to_string(),
+        path: CefString::from(&cookie.path).to_string(),
+        expires: if cookie.has_expires != 0 {
+            from_basetime(&cookie.expires)
+        } else {
+            None
+        },
+        http_only: cookie.httponly != 0,
+        secure: cookie.secure != 0,
+        same_site: match cookie.same_site {
+            CookieSameSite::NO_RESTRICTION => SameSitePolicy::None,
+            CookieSameSite::LAX_MODE => SameSitePolicy::Lax,
+            CookieSameSite::STRICT_MODE => SameSitePolicy::Strict,
+            _ => SameSitePolicy::Unspecified,
+        },
+    }
+}
+
+fn to_cef_cookie(cookie: &Cookie) -> cef::Cookie {
+    cef::Cookie {
+        name: CefString::from(cookie.name.as_str()),
+        value: CefString::from(cookie.value.as_str()),
+        domain: CefString::from(cookie.domain.as_str()),
+        path: CefString::from(cookie.path.as_str()),
+        secure: cookie.secure as i32,
+        httponly: cookie.http_only as i32,
+        has_expires: cookie.expires.is_some() as i32,
+        expires: cookie.expires.map(to_basetime).unwrap_or_default(),
+        same_site: match cookie.same_site {
+            SameSitePolicy::Unspecified => CookieSameSite::UNSPECIFIED,
+            SameSitePolicy::None => CookieSameSite::NO_RESTRICTION,
+            SameSitePolicy::Lax => CookieSameSite::LAX_MODE,
+            SameSitePolicy::Strict => CookieSameSite::STRICT_MODE,
+        },
+        ..Default::default()
+    }
+}
+
+/// A response sender that a CEF callback fires at most once.
+type PendingResponse<T> = Arc<Mutex<Option<oneshot::Sender<Result<T>>>>>;
+
+fn pending<T>(response: oneshot::Sender<Result<T>>) -> PendingResponse<T> {
+    Arc::new(Mutex::new(Some(response)))
+}
+
+/// Cookies gathered by a visitor.
+///
+/// CEF never calls the visitor when there are no cookies, so the collected
+/// list is sent when CEF releases the visitor rather than on the last visit.
+struct CookieCollector {
+    cookies: Mutex<Vec<Cookie>>,
+    response: PendingResponse<Vec<Cookie>>,
+}
+
+impl Drop for CookieCollector {
+    fn drop(&mut self) {
+        if let Some(tx) = self.response.lock().take() {
+            let _ = tx.send(Ok(std::mem::take(&mut *self.cookies.lock())));
+        }
+    }
+}
+
+wrap_cookie_visitor! {
+    struct KiCookieVisitorImpl {
+        collector: Arc<CookieCollector>,
+    }
+
+    impl CookieVisitor {
+        fn visit(
+            &self,
+            cookie: Option<&cef::Cookie>,
+            _count: ::std::os::raw::c_int,
+            _total: ::std::os::raw::c_int,
+            _delete_cookie: Option<&mut ::std::os::raw::c_int>,
+        ) -> ::std::os::raw::c_int {
+            if let Some(cookie) = cookie {
+                self.collector.cookies.lock().push(from_cef_cookie(cookie));
+            }
+            1 // continue visiting
+        }
+    }
+}
+
+wrap_set_cookie_callback! {
+    struct KiSetCookieCallbackImpl {
+        name: String,
+        response: PendingResponse<()>,
+    }
+
+    impl SetCookieCallback {
+        fn on_complete(&self, success: ::std::os::raw::c_int) {
+            let result = if success != 0 {
+                Ok(())
+            } else {
+                Err(anyhow!("Browser rejected cookie '{}'", self.name))
+            };
+            if let Some(tx) = self.response.lock().take() {
+                let _ = tx.send(result);
+            }
+        }
+    }
+}
+
+wrap_delete_cookies_callback! {
+    struct KiDeleteCookiesCallbackImpl {
+        response: PendingResponse<()>,
+    }
+
+    impl DeleteCookiesCallback {
+        fn on_complete(&self, num_deleted: ::std::os::raw::c_int) {
+            debug!("Deleted {} cookies", num_deleted);
+            if let Some(tx) = self.response.lock().take() {
+                let _ = tx.send(Ok(()));
+            }
+        }
+    }
+}
+
+/// The global cookie manager, after checking that the tab exists.
+fn cookie_manager(
+    tab_id: Uuid,
+    tabs: &Arc<RwLock<HashMap<Uuid, CefTab>>>,
+) -> Result<cef::CookieManager> {
+    if !tabs.read().contains_key(&tab_id) {
+        return Err(anyhow!("Tab not found: {}", tab_id));
+    }
+    cef::cookie_manager_get_global_manager(None)
+        .ok_or_else(|| anyhow!("Cookie manager not available"))
+}
+
+/// Lists the cookies on the CEF thread, all of them or those sent to
+/// `url`; replies once CEF visited them all.
+pub(crate) fn get_cookies_internal(
+    tab_id: Uuid,
+    url: Option<String>,
+    tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+    response: oneshot::Sender<Result<Vec<Cookie>>>,
+) {
+    let manager = match cookie_manager(tab_id, &tabs) {
+        Ok(manager) => manager,
+        Err(e) => {
+            let _ = response.send(Err(e));
+            return;
+        }
+    };
+    let response = pending(response);
+    let mut visitor = KiCookieVisitorImpl::new(Arc::new(CookieCollector {
+        cookies: Mutex::new(Vec::new()),
+        response: response.clone(),
+    }));
+    let started = match url {
+        Some(url) => {
+            let url = CefString::from(url.as_str());
+            manager.visit_url_cookies(Some(&url), 1, Some(&mut visitor))
+        }
+        None => manager.visit_all_cookies(Some(&mut visitor)),
+    };
+    if started == 0 {
+        if let Some(tx) = response.lock().take() {
+            let _ = tx.send(Err(anyhow!("Cookies cannot be accessed")));
+        }
+    }
+}
+
+/// Sets a cookie on the CEF thread; replies once CEF stored it.
+pub(crate) fn set_cookie_internal(
+    tab_id: Uuid,
+    cookie: Cookie,
+    tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+    response: oneshot::Sender<Result<()>>,
+) {
+    let manager = match cookie_manager(tab_id, &tabs) {
+        Ok(manager) => manager,
+        Err(e) => {
+            let _ = response.send(Err(e));
+            return;
+        }
+    };
+    let response = pending(response);
+    let mut callback = KiSetCookieCallbackImpl::new(cookie.name.clone(), response.clone());
+    let url = CefString::from(cookie.url().as_str());
+    if manager.set_cookie(Some(&url), Some(&to_cef_cookie(&cookie)), Some(&mut callback)) == 0 {
+        if let Some(tx) = response.lock().take() {
+            let _ = tx.send(Err(anyhow!("Invalid cookie '{}' for {}", cookie.name, cookie.url())));
+        }
+    }
+}
+
+/// Deletes the cookies named `name` of `domain` on the CEF thread; replies
+/// once CEF deleted them.
+pub(crate) fn delete_cookie_internal(
+    tab_id: Uuid,
+    name: &str,
+    domain: &str,
+    tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+    response: oneshot::Sender<Result<()>>,
+) {
+    let manager = match cookie_manager(tab_id, &tabs) {
+        Ok(manager) => manager,
+        Err(e) => {
+            let _ = response.send(Err(e));
+            return;
+        }
+    };
+    let response = pending(response);
+    let mut callback = KiDeleteCookiesCallbackImpl::new(response.clone());
+    // CEF deletes host and domain cookies of the URL's host, on all paths
+    let url = CefString::from(Cookie::new(name, "", domain).url().as_str());
+    let name = CefString::from(name);
+    if manager.delete_cookies(Some(&url), Some(&name), Some(&mut callback)) == 0 {
+        if let Some(tx) = response.lock().take() {
+            let _ = tx.send(Err(anyhow!("Invalid cookie domain: {}", domain)));
+        }
+    }
+}
+
+/// Deletes all cookies on the CEF thread; replies once CEF deleted them.
+pub(crate) fn clear_cookies_internal(
+    tab_id: Uuid,
+    tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+    response: oneshot::Sender<Result<()>>,
+) {
+    let manager = match cookie_manager(tab_id, &tabs) {
+        Ok(manager) => manager,
+        Err(e) => {
+            let _ = response.send(Err(e));
+            return;
+        }
+    };
+    let response = pending(response);
+    let mut callback = KiDeleteCookiesCallbackImpl::new(response.clone());
+    if manager.delete_cookies(None, None, Some(&mut callback)) == 0 {
+        if let Some(tx) = response.lock().take() {
+            let _ = tx.send(Err(anyhow!("Cookies cannot be accessed")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basetime_round_trip() {
+        let time = DateTime::from_timestamp(1_893_456_000, 0).unwrap();
+        let base = to_basetime(time);
+        assert_eq!(base.val, 13_537_929_600_000_000);
+        assert_eq!(from_basetime(&base), Some(time));
+    }
+}
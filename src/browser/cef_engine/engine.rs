@@ -8,20 +8,55 @@
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use parking_lot::RwLock;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::sync::Arc;
-use tokio::sync::{mpsc, oneshot};
-use tracing::info;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use crate::browser::engine::{BrowserConfig, BrowserEngine};
-use crate::browser::tab::Tab;
+use crate::browser::events::BrowserEvent;
+use crate::browser::binding::{binding_script, validate_binding_name};
+use crate::browser::computed_style::{computed_style_script, resolve_style_properties, StyleReport};
+use crate::browser::content::{content_script, ContentReport};
+use crate::browser::cookies::Cookie;
+use crate::browser::dialog::BeforeUnloadPolicy;
+use crate::browser::dom::{BoundingBox, DomElement};
+use crate::browser::error_capture::write_error_capture;
+use crate::browser::har::{HarLog, HarRecorder};
+use crate::browser::interceptor::{InterceptorSlot, RequestInterceptor};
+use crate::browser::js_call::{
+    build_abort_script, build_call_script, build_poll_script, parse_call_state, EvalOptions,
+};
+use crate::browser::engine::{
+    BrowserConfig, BrowserEngine, EngineCapabilities, NavigateOptions, Viewport,
+};
+use crate::browser::event_buffer::{
+    record_events, EventBuffer, SharedEventBuffer, DEFAULT_EVENT_BUFFER_CAPACITY,
+};
+use crate::browser::mutation::{
+    mutation_wait_args, parse_mutation_result, MutationInfo, MutationKinds, MUTATION_WAIT_FUNCTION,
+};
+use crate::browser::network::FailedRequest;
+use crate::browser::page_wait::{selector_box_script, WaitCondition};
+use crate::browser::referrer::ReferrerPolicy;
+use crate::browser::response_capture::{response_capture_script, CapturedResponse, ResponseCapture};
+use crate::browser::screenshot::{element_clip_region, Screenshot, ScreenshotOptions};
+use crate::browser::tab::{Tab, TabStatus};
+use crate::browser::url_pattern::UrlPattern;
+use crate::browser::shadow::{shadow_query_script, shadow_tree_script, ShadowQueryOptions, ShadowReport};
+use crate::browser::xpath::{xpath_script, XPathReport};
+use crate::error::BrowserError;
 use crate::stealth::StealthConfig;
 use super::CefCommand;
 use super::event_sender::CefBrowserEventSender;
 use super::tab::CefTab;
 use super::TabFrameBuffer;
+use super::ENGINE_EVENTS;
 
 /// CEF-based browser engine implementation.
 ///
@@ -45,6 +80,19 @@ pub struct CefBrowserEngine {
     /// Last known cursor position per tab — anchor for the human-like
     /// Bézier approach of API clicks (see input.rs::click).
     pub(crate) last_mouse_pos: Arc<parking_lot::Mutex<HashMap<Uuid, (i32, i32)>>>,
+    /// RNG for input randomness such as the post-navigation dwell, seeded
+    /// from `BrowserConfig::input_rng_seed`.
+    pub(crate) input_rng: Arc<parking_lot::Mutex<StdRng>>,
+    /// Tab currently brought to the foreground via `set_active_tab`.
+    pub(crate) active_tab: Arc<RwLock<Option<Uuid>>>,
+    /// Viewport for new tabs; read by the message loop on every browser creation.
+    pub(crate) default_viewport: Arc<RwLock<Viewport>>,
+    /// Request interceptor consulted by every tab's resource request handler.
+    pub(crate) request_interceptor: InterceptorSlot,
+    /// Broadcast channel for events emitted by the engine and its CEF callbacks.
+    pub(crate) event_tx: broadcast::Sender<BrowserEvent>,
+    /// Recent events for inspection, filled by a task subscribed to `event_tx`.
+    pub(crate) event_buffer: SharedEventBuffer,
     /// Whether the engine is running.
     pub(crate) is_running: Arc<AtomicBool>,
     /// CEF initialized flag (v144 doesn't have CefContext).
@@ -61,6 +109,13 @@ impl BrowserEngine for CefBrowserEngine {
     {
         info!("Initializing CEF browser engine");
 
+        // Fail before spawning the CEF thread instead of letting subprocess
+        // launches fail opaquely later.
+        config.resolve_cef_subprocess()?;
+        if let Some(device) = &config.device_emulation {
+            device.validate()?;
+        }
+
         // Use external stealth config if provided (ensures HTTP UA = JS UA),
         // otherwise create one internally as fallback.
         let mut stealth = if let Some(ext) = config.stealth_config.clone() {
@@ -71,7 +126,10 @@ impl BrowserEngine for CefBrowserEngine {
             StealthConfig::random_chrome()
         };
 
-        stealth.sync_screen_to_viewport(config.window_size.0, config.window_size.1);
+        match &config.device_emulation {
+            Some(device) => device.apply_to_stealth(&mut stealth),
+            None => stealth.sync_screen_to_viewport(config.window_size.0, config.window_size.1),
+        }
 
         let stealth_config = Arc::new(stealth);
 
@@ -83,6 +141,9 @@ impl BrowserEngine for CefBrowserEngine {
         let tabs = Arc::new(RwLock::new(HashMap::new()));
         let is_running = Arc::new(AtomicBool::new(false));
         let browser_id_counter = Arc::new(AtomicI32::new(0));
+        let default_viewport = Arc::new(RwLock::new(Viewport::from_config(&config)));
+        let request_interceptor: InterceptorSlot =
+            Arc::new(RwLock::new(config.request_interceptor.clone()));
 
         // Create command channel for CEF thread communication
         let (command_tx, command_rx) = mpsc::unbounded_channel::<CefCommand>();
@@ -95,6 +156,8 @@ impl BrowserEngine for CefBrowserEngine {
         let config_clone = config.clone();
         let stealth_config_clone = stealth_config.clone();
         let browser_id_counter_clone = browser_id_counter.clone();
+        let default_viewport_clone = default_viewport.clone();
+        let request_interceptor_clone = request_interceptor.clone();
         let command_tx_clone = command_tx.clone();
 
         // CEF initialized flag (v144 doesn't have CefContext)
@@ -109,6 +172,8 @@ impl BrowserEngine for CefBrowserEngine {
                 tabs_clone,
                 is_running_clone,
                 browser_id_counter_clone,
+                default_viewport_clone,
+                request_interceptor_clone,
                 cef_initialized_clone,
                 command_rx,
                 command_tx_clone,
@@ -129,6 +194,14 @@ impl BrowserEngine for CefBrowserEngine {
 
         info!("CEF browser engine initialized successfully");
 
+        let event_buffer = EventBuffer::shared(DEFAULT_EVENT_BUFFER_CAPACITY);
+        record_events(event_buffer.clone(), ENGINE_EVENTS.subscribe());
+
+        let input_rng = match config.input_rng_seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
         Ok(Self {
             config,
             stealth_config,
@@ -136,6 +209,12 @@ impl BrowserEngine for CefBrowserEngine {
             command_tx,
             input_tx,
             last_mouse_pos: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            input_rng: Arc::new(parking_lot::Mutex::new(input_rng)),
+            active_tab: Arc::new(RwLock::new(None)),
+            default_viewport,
+            request_interceptor,
+            event_tx: ENGINE_EVENTS.clone(),
+            event_buffer,
             is_running,
             _cef_initialized: cef_initialized,
             _browser_id_counter: browser_id_counter,
@@ -165,16 +244,17 @@ impl BrowserEngine for CefBrowserEngine {
             return Err(anyhow!("Browser engine is not running"));
         }
 
-        let (response_tx, response_rx) = oneshot::channel();
-
-        self.command_tx
-            .send(CefCommand::CloseBrowser {
-                tab_id,
-                response: response_tx,
-            })
-            .map_err(|_| anyhow!("Failed to send close browser command"))?;
+        let policy = self.config.before_unload_policy;
+        self.send_close_browser(tab_id, policy).await?;
+        if policy.runs_handler() {
+            self.await_before_unload(tab_id).await?;
+        }
 
-        response_rx.await.context("Failed to receive close browser response")?
+        let mut active = self.active_tab.write();
+        if *active == Some(tab_id) {
+            *active = None;
+        }
+        Ok(())
     }
 
     async fn get_tabs(&self) -> Result<Vec<Tab>> {
@@ -199,12 +279,573 @@ impl BrowserEngine for CefBrowserEngine {
         &self.config
     }
 
+    /// CEF has no native CPU throttling or permission grants, so
+    /// `set_cpu_throttling` and `grant_permissions` keep the trait's "not
+    /// supported" defaults. The API layer goes through DevTools
+    /// (`Emulation.setCPUThrottlingRate`, `Browser.grantPermissions`) when a
+    /// CDP port is open.
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            cpu_throttling: false,
+            permission_grants: false,
+        }
+    }
+
     async fn is_running(&self) -> bool {
         self.is_running.load(Ordering::SeqCst)
     }
+
+    fn subscribe_events(&self) -> Option<broadcast::Receiver<BrowserEvent>> {
+        Some(self.event_tx.subscribe())
+    }
+
+    async fn get_recent_events(
+        &self,
+        tab_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<BrowserEvent>> {
+        let tab_id = tab_id.map(|id| id.to_string());
+        Ok(self.event_buffer.lock().recent(tab_id.as_deref(), limit))
+    }
+
+    async fn clear_event_buffer(&self, tab_id: Option<Uuid>) -> Result<()> {
+        let tab_id = tab_id.map(|id| id.to_string());
+        self.event_buffer.lock().clear(tab_id.as_deref());
+        Ok(())
+    }
+
+    async fn set_active_tab(&self, tab_id: Uuid) -> Result<()> {
+        if !self.is_running.load(Ordering::SeqCst) {
+            return Err(anyhow!("Browser engine is not running"));
+        }
+
+        // Check and claim the foreground under one lock, so concurrent
+        // switches never both blur the same previous tab
+        let previous = {
+            let mut active = self.active_tab.write();
+            if *active == Some(tab_id) {
+                return Ok(());
+            }
+            active.replace(tab_id)
+        };
+
+        // Blur the previous foreground tab first; it may already be closed.
+        if let Some(prev) = previous {
+            let _ = self.set_focus(prev, false).await;
+        }
+        if let Err(e) = self.set_focus(tab_id, true).await {
+            // Hand the foreground back unless another switch took it meanwhile
+            let mut active = self.active_tab.write();
+            if *active == Some(tab_id) {
+                *active = previous;
+            }
+            return Err(e);
+        }
+
+        let _ = self.event_tx.send(BrowserEvent::ActiveTabChanged {
+            tab_id: tab_id.to_string(),
+        });
+        Ok(())
+    }
+
+    async fn active_tab(&self) -> Option<Uuid> {
+        *self.active_tab.read()
+    }
+
+    async fn default_viewport(&self) -> Viewport {
+        *self.default_viewport.read()
+    }
+
+    async fn set_default_viewport(&self, viewport: Viewport, apply_to_existing: bool) -> Result<()> {
+        viewport.validate()?;
+        *self.default_viewport.write() = viewport;
+        if apply_to_existing {
+            let tab_ids: Vec<Uuid> = self.tabs.read().keys().copied().collect();
+            for tab_id in tab_ids {
+                self.resize_tab(tab_id, viewport).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Takes effect for requests sent after the call, in every tab.
+    async fn set_request_interceptor(&self, interceptor: Arc<dyn RequestInterceptor>) -> Result<()> {
+        *self.request_interceptor.write() = Some(interceptor);
+        Ok(())
+    }
+
+    async fn clear_request_interceptor(&self) -> Result<()> {
+        *self.request_interceptor.write() = None;
+        Ok(())
+    }
+
+    async fn get_viewport(&self, tab_id: Uuid) -> Result<Viewport> {
+        let tabs = self.tabs.read();
+        let tab = tabs
+            .get(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        let (width, height) = *tab.viewport_size.read();
+        Ok(Viewport::new(width, height).with_device_scale_factor(*tab.device_scale_factor.read()))
+    }
+
+    async fn resize_tab(&self, tab_id: Uuid, viewport: Viewport) -> Result<()> {
+        viewport.validate()?;
+        let (response_tx, response_rx) = oneshot::channel();
+        self.command_tx
+            .send(CefCommand::ResizeViewport {
+                tab_id,
+                width: viewport.width,
+                height: viewport.height,
+                device_scale_factor: Some(viewport.device_scale_factor),
+                response: response_tx,
+            })
+            .map_err(|_| anyhow!("Failed to send resize viewport command"))?;
+        response_rx.await.context("Failed to receive resize viewport response")??;
+
+        let _ = self.event_tx.send(BrowserEvent::ViewportChanged {
+            tab_id: tab_id.to_string(),
+            width: viewport.width,
+            height: viewport.height,
+            device_scale_factor: viewport.device_scale_factor,
+        });
+        Ok(())
+    }
+
+    async fn capture_failure(
+        &self,
+        tab_id: Uuid,
+        operation: &str,
+        error: &str,
+    ) -> Result<Option<PathBuf>> {
+        let Some(dir) = self.config.screenshot_on_error.clone() else {
+            return Ok(None);
+        };
+        let url = self
+            .tabs
+            .read()
+            .get(&tab_id)
+            .map(|tab| tab.url.clone())
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        let frame = self.screenshot(tab_id, ScreenshotOptions::default()).await;
+        write_error_capture(&dir, tab_id, &url, operation, error, frame)
+            .await
+            .map(Some)
+    }
+
+    async fn get_failed_requests(&self, tab_id: Uuid) -> Result<Vec<FailedRequest>> {
+        let tabs = self.tabs.read();
+        let tab = tabs
+            .get(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        Ok(tab.failed_requests.clone())
+    }
+
+    async fn enable_response_capture(&self, tab_id: Uuid, url_pattern: &str) -> Result<()> {
+        let pattern = UrlPattern::parse(url_pattern)
+            .map_err(|e| BrowserError::InvalidRequest(e.to_string()))?;
+        let max_bytes = {
+            let mut tabs = self.tabs.write();
+            let tab = tabs
+                .get_mut(&tab_id)
+                .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+            let capture = tab
+                .response_capture
+                .get_or_insert_with(|| ResponseCapture::new(self.config.response_capture_max_bytes));
+            capture.add_pattern(pattern);
+            capture.max_bytes()
+        };
+        // Hook the current document; later documents get it on load start
+        self.execute_js(tab_id, &response_capture_script(max_bytes)).await?;
+        Ok(())
+    }
+
+    async fn get_captured_responses(&self, tab_id: Uuid) -> Result<Vec<CapturedResponse>> {
+        let tabs = self.tabs.read();
+        let tab = tabs
+            .get(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        Ok(tab
+            .response_capture
+            .as_ref()
+            .map(ResponseCapture::responses)
+            .unwrap_or_default())
+    }
+
+    /// Records requests sent after the call; requests already in flight
+    /// only appear if their response completes a recorded request.
+    async fn enable_har_recording(&self, tab_id: Uuid) -> Result<Arc<HarRecorder>> {
+        let recorder = Arc::new(HarRecorder::new());
+        recorder.start();
+        let mut tabs = self.tabs.write();
+        let tab = tabs
+            .get_mut(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        tab.har_recorder = Some(recorder.clone());
+        Ok(recorder)
+    }
+
+    async fn stop_har_recording(&self, tab_id: Uuid) -> Result<HarLog> {
+        let recorder = {
+            let mut tabs = self.tabs.write();
+            let tab = tabs
+                .get_mut(&tab_id)
+                .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+            tab.har_recorder.take()
+        };
+        let recorder =
+            recorder.ok_or_else(|| anyhow!("HAR recording not enabled for tab {}", tab_id))?;
+        Ok(recorder.stop())
+    }
+
+    async fn execute_js_with_timeout(
+        &self,
+        tab_id: Uuid,
+        script: &str,
+        timeout: Duration,
+    ) -> Result<Option<serde_json::Value>> {
+        let Some(json) = self.execute_js_timeout(tab_id, script, timeout).await? else {
+            return Ok(None);
+        };
+        let value: serde_json::Value =
+            serde_json::from_str(&json).context("Malformed JavaScript result")?;
+        Ok(Some(value).filter(|value| !value.is_null()))
+    }
+
+    async fn get_content(&self, tab_id: Uuid, max_bytes: Option<usize>) -> Result<String> {
+        self.fetch_content(tab_id, None, max_bytes).await
+    }
+
+    async fn get_content_selector(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        max_bytes: Option<usize>,
+    ) -> Result<String> {
+        self.fetch_content(tab_id, Some(selector), max_bytes).await
+    }
+
+    async fn get_computed_style(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        properties: Vec<String>,
+    ) -> Result<HashMap<String, String>> {
+        let script = computed_style_script(selector, &resolve_style_properties(properties));
+        let json = self
+            .execute_js_with_result(tab_id, &script)
+            .await?
+            .ok_or_else(|| anyhow!("Computed style script returned no data"))?;
+        let report: StyleReport =
+            serde_json::from_str(&json).context("Failed to parse computed style result")?;
+        Ok(report.into_style(selector)?)
+    }
+
+    async fn call_function_with_options(
+        &self,
+        tab_id: Uuid,
+        fn_body: &str,
+        args: Vec<serde_json::Value>,
+        options: EvalOptions,
+    ) -> Result<serde_json::Value> {
+        self.call_function_polled(tab_id, fn_body, &args, options).await
+    }
+
+    async fn wait_for(&self, tab_id: Uuid, condition: WaitCondition, timeout: Duration) -> Result<()> {
+        self.wait_for_condition(tab_id, condition, timeout).await
+    }
+
+    async fn get_cookies(&self, tab_id: Uuid, url: Option<&str>) -> Result<Vec<Cookie>> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.command_tx
+            .send(CefCommand::GetCookies {
+                tab_id,
+                url: url.map(str::to_string),
+                response: response_tx,
+            })
+            .map_err(|_| anyhow!("Failed to send get cookies command"))?;
+        response_rx.await.context("Failed to receive get cookies response")?
+    }
+
+    async fn set_cookie(&self, tab_id: Uuid, cookie: Cookie) -> Result<()> {
+        cookie.validate()?;
+        let (response_tx, response_rx) = oneshot::channel();
+        self.command_tx
+            .send(CefCommand::SetCookie {
+                tab_id,
+                cookie,
+                response: response_tx,
+            })
+            .map_err(|_| anyhow!("Failed to send set cookie command"))?;
+        response_rx.await.context("Failed to receive set cookie response")?
+    }
+
+    async fn delete_cookie(&self, tab_id: Uuid, name: &str, domain: &str) -> Result<()> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.command_tx
+            .send(CefCommand::DeleteCookie {
+                tab_id,
+                name: name.to_string(),
+                domain: domain.to_string(),
+                response: response_tx,
+            })
+            .map_err(|_| anyhow!("Failed to send delete cookie command"))?;
+        response_rx.await.context("Failed to receive delete cookie response")?
+    }
+
+    async fn clear_cookies(&self, tab_id: Uuid) -> Result<()> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.command_tx
+            .send(CefCommand::ClearCookies {
+                tab_id,
+                response: response_tx,
+            })
+            .map_err(|_| anyhow!("Failed to send clear cookies command"))?;
+        response_rx.await.context("Failed to receive clear cookies response")?
+    }
+
+    async fn go_back(&self, tab_id: Uuid) -> Result<()> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.command_tx
+            .send(CefCommand::GoBack {
+                tab_id,
+                response: response_tx,
+            })
+            .map_err(|_| anyhow!("Failed to send go back command"))?;
+        response_rx.await.context("Failed to receive go back response")?
+    }
+
+    async fn go_forward(&self, tab_id: Uuid) -> Result<()> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.command_tx
+            .send(CefCommand::GoForward {
+                tab_id,
+                response: response_tx,
+            })
+            .map_err(|_| anyhow!("Failed to send go forward command"))?;
+        response_rx.await.context("Failed to receive go forward response")?
+    }
+
+    async fn reload(&self, tab_id: Uuid, ignore_cache: bool) -> Result<()> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.command_tx
+            .send(CefCommand::Reload {
+                tab_id,
+                ignore_cache,
+                response: response_tx,
+            })
+            .map_err(|_| anyhow!("Failed to send reload command"))?;
+        response_rx.await.context("Failed to receive reload response")?
+    }
+
+    async fn screenshot_element(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        options: ScreenshotOptions,
+    ) -> Result<Screenshot> {
+        let bounding_box = self
+            .execute_js(tab_id, &selector_box_script(selector))
+            .await?
+            .and_then(|json| serde_json::from_str::<BoundingBox>(&json).ok());
+        let device_scale_factor = self
+            .tabs
+            .read()
+            .get(&tab_id)
+            .map(|tab| *tab.device_scale_factor.read())
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        let clip = element_clip_region(selector, bounding_box, device_scale_factor)?;
+        let options = ScreenshotOptions {
+            clip_region: Some(clip),
+            full_page: false,
+            ..options
+        };
+        self.screenshot(tab_id, options).await
+    }
+
+    async fn wait_for_selector(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        timeout_ms: u64,
+    ) -> Result<BoundingBox> {
+        self.wait_for_selector_box(tab_id, selector, Duration::from_millis(timeout_ms))
+            .await
+    }
+
+    async fn expose_binding(&self, tab_id: Uuid, name: &str) -> Result<()> {
+        validate_binding_name(name)?;
+        {
+            let mut tabs = self.tabs.write();
+            let tab = tabs
+                .get_mut(&tab_id)
+                .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+            if !tab.bindings.iter().any(|b| b == name) {
+                tab.bindings.push(name.to_string());
+            }
+        }
+        // Install in the current document; later documents get it on load start
+        self.execute_js(tab_id, &binding_script(name)).await?;
+        Ok(())
+    }
+
+    async fn set_referrer_policy(&self, tab_id: Uuid, policy: ReferrerPolicy) -> Result<()> {
+        let mut tabs = self.tabs.write();
+        let tab = tabs
+            .get_mut(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        tab.referrer_policy = policy;
+        Ok(())
+    }
+
+    async fn wait_for_mutation(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        kinds: MutationKinds,
+        timeout: Duration,
+    ) -> Result<Vec<MutationInfo>> {
+        kinds.validate()?;
+        // The in-page timer disconnects the observers and resolves with null;
+        // the grace period only covers the bridge round trips.
+        let args = mutation_wait_args(selector, kinds, timeout);
+        let value = self
+            .call_function_polled(
+                tab_id,
+                MUTATION_WAIT_FUNCTION,
+                &args,
+                EvalOptions::default().with_timeout(timeout + MUTATION_WAIT_GRACE),
+            )
+            .await?;
+        Ok(parse_mutation_result(value, selector, timeout)?)
+    }
 }
 
+/// Extra time granted to the bridge after a mutation wait's in-page timeout.
+const MUTATION_WAIT_GRACE: Duration = Duration::from_secs(2);
+
 impl CefBrowserEngine {
+    /// Finds all elements of a tab matching an XPath expression.
+    ///
+    /// Same contract as [`DomAccessor::query_xpath`](crate::browser::DomAccessor::query_xpath):
+    /// elements come in document order with `xpath_index` set.
+    pub async fn query_xpath(&self, tab_id: Uuid, xpath: &str) -> Result<Vec<DomElement>> {
+        self.run_xpath(tab_id, xpath, None).await
+    }
+
+    /// Finds the first element of a tab matching an XPath expression.
+    pub async fn query_xpath_single(&self, tab_id: Uuid, xpath: &str) -> Result<Option<DomElement>> {
+        Ok(self.run_xpath(tab_id, xpath, Some(1)).await?.into_iter().next())
+    }
+
+    /// Evaluates `xpath` in the page and describes up to `limit` elements.
+    async fn run_xpath(&self, tab_id: Uuid, xpath: &str, limit: Option<usize>) -> Result<Vec<DomElement>> {
+        let json = self
+            .execute_js_with_result(tab_id, &xpath_script(xpath, limit))
+            .await?
+            .ok_or_else(|| anyhow!("XPath script returned no data"))?;
+        let report: XPathReport =
+            serde_json::from_str(&json).context("Failed to parse XPath result")?;
+        Ok(report.into_elements(xpath)?)
+    }
+
+    /// Finds elements of a tab inside the open shadow roots of host elements.
+    ///
+    /// Same contract as [`DomAccessor::query_shadow`](crate::browser::DomAccessor::query_shadow).
+    pub async fn query_shadow(
+        &self,
+        tab_id: Uuid,
+        host_selector: &str,
+        inner_selector: &str,
+    ) -> Result<Vec<DomElement>> {
+        self.run_shadow_query(tab_id, inner_selector, &shadow_query_script(host_selector, inner_selector))
+            .await
+    }
+
+    /// Finds elements of a tab in the document and all open shadow roots,
+    /// up to the default depth.
+    pub async fn query_all_shadows(&self, tab_id: Uuid, selector: &str) -> Result<Vec<DomElement>> {
+        self.query_all_shadows_with_options(tab_id, selector, &ShadowQueryOptions::default())
+            .await
+    }
+
+    /// Like [`query_all_shadows`](Self::query_all_shadows), with explicit options.
+    pub async fn query_all_shadows_with_options(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        options: &ShadowQueryOptions,
+    ) -> Result<Vec<DomElement>> {
+        self.run_shadow_query(tab_id, selector, &shadow_tree_script(selector, options))
+            .await
+    }
+
+    /// Runs a shadow DOM script and parses its report.
+    async fn run_shadow_query(&self, tab_id: Uuid, selector: &str, script: &str) -> Result<Vec<DomElement>> {
+        let json = self
+            .execute_js_with_result(tab_id, script)
+            .await?
+            .ok_or_else(|| anyhow!("Shadow DOM script returned no data"))?;
+        let report: ShadowReport =
+            serde_json::from_str(&json).context("Failed to parse shadow DOM result")?;
+        Ok(report.into_elements(selector)?)
+    }
+
+    /// Runs a function via the JS bridge and polls until its promise settles
+    /// or the budget in `options` runs out.
+    async fn call_function_polled(
+        &self,
+        tab_id: Uuid,
+        fn_body: &str,
+        args: &[serde_json::Value],
+        options: EvalOptions,
+    ) -> Result<serde_json::Value> {
+        let call_id = Uuid::new_v4().simple().to_string();
+        self.execute_js_with_result(tab_id, &build_call_script(&call_id, fn_body, args)?)
+            .await?;
+
+        // The JS bridge only returns synchronous values, so poll the settled
+        // outcome with the same backoff used for readiness checks.
+        let poll_script = build_poll_script(&call_id, options.max_result_bytes);
+        let start = std::time::Instant::now();
+        let backoff = self.config.ready_poll_backoff;
+        let mut interval = backoff.initial();
+        loop {
+            let state = self
+                .execute_js_with_result(tab_id, &poll_script)
+                .await?
+                .ok_or_else(|| anyhow!("Function call poll returned no data"))?;
+            if let Some(value) = parse_call_state(&state, options.max_result_bytes)? {
+                return Ok(options.enforce_result_size(value)?);
+            }
+            if start.elapsed() > options.timeout() {
+                // Drop the slot so a late outcome is discarded in the page
+                if let Err(e) = self.execute_js(tab_id, &build_abort_script(&call_id)).await {
+                    debug!("Failed to abort function call in tab {}: {}", tab_id, e);
+                }
+                return Err(options.timeout_error().into());
+            }
+            tokio::time::sleep(interval).await;
+            interval = backoff.next(interval);
+        }
+    }
+
+    /// Serializes the page or a selected element via the JS bridge.
+    async fn fetch_content(
+        &self,
+        tab_id: Uuid,
+        selector: Option<&str>,
+        max_bytes: Option<usize>,
+    ) -> Result<String> {
+        let script = content_script(selector, max_bytes);
+        let json = self
+            .execute_js_with_result(tab_id, &script)
+            .await?
+            .ok_or_else(|| anyhow!("Content script returned no data"))?;
+        let report: ContentReport =
+            serde_json::from_str(&json).context("Failed to parse content script result")?;
+        Ok(report.into_html(selector, max_bytes)?)
+    }
+
     /// Find the CEF directory containing libcef.so and resources (static version for main.rs).
     pub fn find_cef_dir_static() -> Option<std::path::PathBuf> {
         Self::find_cef_dir()
@@ -279,6 +920,21 @@ impl CefBrowserEngine {
         None
     }
 
+    /// Gives or takes input focus from a tab's browser host.
+    async fn set_focus(&self, tab_id: Uuid, focus: bool) -> Result<()> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        self.command_tx
+            .send(CefCommand::SetFocus {
+                tab_id,
+                focus,
+                response: response_tx,
+            })
+            .map_err(|_| anyhow!("Failed to send set focus command"))?;
+
+        response_rx.await.context("Failed to receive set focus response")?
+    }
+
     /// Returns the stealth configuration.
     pub fn stealth_config(&self) -> &StealthConfig {
         &self.stealth_config
@@ -377,19 +1033,81 @@ impl CefBrowserEngine {
     /// Closes a tab without blocking.
     pub fn send_close_tab(&self, tab_id: Uuid) {
         self.last_mouse_pos.lock().remove(&tab_id);
+        {
+            let mut active = self.active_tab.write();
+            if *active == Some(tab_id) {
+                *active = None;
+            }
+        }
         let (response_tx, _) = oneshot::channel();
         let _ = self.command_tx.send(CefCommand::CloseBrowser {
             tab_id,
+            before_unload: BeforeUnloadPolicy::ForceClose,
             response: response_tx,
         });
     }
 
+    /// Sends a close command and awaits its dispatch on the CEF thread.
+    async fn send_close_browser(&self, tab_id: Uuid, before_unload: BeforeUnloadPolicy) -> Result<()> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.command_tx
+            .send(CefCommand::CloseBrowser {
+                tab_id,
+                before_unload,
+                response: response_tx,
+            })
+            .map_err(|_| anyhow!("Failed to send close browser command"))?;
+        response_rx.await.context("Failed to receive close browser response")?
+    }
+
+    /// Waits until a close that runs the page's `beforeunload` handler has
+    /// either closed the browser or been cancelled by the handler.
+    ///
+    /// A page that neither closes nor cancels within the engine timeout is
+    /// force-closed, so cleanup never hangs on it.
+    async fn await_before_unload(&self, tab_id: Uuid) -> Result<()> {
+        let start = std::time::Instant::now();
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let backoff = self.config.ready_poll_backoff;
+        let mut interval = backoff.initial();
+        loop {
+            {
+                let mut tabs = self.tabs.write();
+                let Some(tab) = tabs.get(&tab_id) else {
+                    return Ok(());
+                };
+                if tab.status == TabStatus::Closed {
+                    tabs.remove(&tab_id);
+                    return Ok(());
+                }
+                if tab.closing.is_none() {
+                    return Err(anyhow!(
+                        "Closing tab {} was cancelled by its beforeunload handler",
+                        tab_id
+                    ));
+                }
+            }
+            if start.elapsed() > timeout {
+                warn!("Tab {} did not close within {:?}; forcing it", tab_id, timeout);
+                return self
+                    .send_close_browser(tab_id, BeforeUnloadPolicy::ForceClose)
+                    .await;
+            }
+            tokio::time::sleep(interval).await;
+            interval = backoff.next(interval);
+        }
+    }
+
     /// Navigates a tab without blocking.
     pub fn send_navigate(&self, tab_id: Uuid, url: &str) {
         let (response_tx, _) = oneshot::channel();
+        let options = NavigateOptions::default();
         let _ = self.command_tx.send(CefCommand::Navigate {
             tab_id,
             url: url.to_string(),
+            bypass_cache: self.config.cache_mode.bypasses_cache(&options),
+            max_redirects: options.max_redirects,
+            referrer: None,
             response: response_tx,
         });
     }
@@ -542,6 +1260,7 @@ impl CefBrowserEngine {
             tab_id,
             width,
             height,
+            device_scale_factor: None,
             response: response_tx,
         });
     }
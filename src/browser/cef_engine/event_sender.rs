@@ -1,7 +1,7 @@
 //! Bridge between CefInputHandler and CefBrowserEngine command channel.
 //!
 //! Provides `CefBrowserEventSender` which implements the `CefEventSender` trait
-//! to forward mouse and keyboard events from the input handler to the CEF message
+//! to forward mouse, keyboard, touch and IME events from the input handler to the CEF message
 //! loop thread via the command channel.
 
 use tokio::sync::{mpsc, oneshot};
@@ -89,4 +89,46 @@ impl crate::browser::cef_input::CefEventSender for CefBrowserEventSender {
         };
         let _ = self.command_tx.send(cmd);
     }
+
+    fn send_touch_event(&self, event: &crate::browser::cef_input::CefTouchEvent) {
+        let (tx, _rx) = oneshot::channel();
+        let cmd = CefCommand::TouchEvent {
+            tab_id: self.tab_id,
+            id: event.id,
+            x: event.x,
+            y: event.y,
+            pressure: event.pressure,
+            event_type: event.event_type.to_cef_type(),
+            modifiers: event.modifiers,
+            response: tx,
+        };
+        let _ = self.command_tx.send(cmd);
+    }
+
+    fn send_ime_set_composition(
+        &self,
+        text: &str,
+        underlines: &[crate::browser::cef_input::CompositionUnderline],
+        selection_range: (i32, i32),
+    ) {
+        let (tx, _rx) = oneshot::channel();
+        let cmd = CefCommand::ImeSetComposition {
+            tab_id: self.tab_id,
+            text: text.to_string(),
+            underlines: underlines.to_vec(),
+            selection_range,
+            response: tx,
+        };
+        let _ = self.command_tx.send(cmd);
+    }
+
+    fn send_ime_commit_text(&self, text: &str) {
+        let (tx, _rx) = oneshot::channel();
+        let cmd = CefCommand::ImeCommit {
+            tab_id: self.tab_id,
+            text: text.to_string(),
+            response: tx,
+        };
+        let _ = self.command_tx.send(cmd);
+    }
 }
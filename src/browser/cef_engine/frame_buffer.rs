@@ -0,0 +1,104 @@
+//! Lock-minimizing access to a tab's shared frame buffer.
+//!
+//! `CefTab::frame_buffer` is written by `on_paint` on the CEF thread and read
+//! by screenshot commands, the viewer stream and the GUI. Readers should only
+//! hold the lock while copying the pixels out ([`copy_frame`]); encoding
+//! happens on the copy. The paint side ([`FrameBufferWriter`]) fills a
+//! private staging buffer first and only swaps it in under the write lock, so
+//! a paint waits at most for a reader's memcpy, never for an image encode.
+
+use parking_lot::{Mutex, RwLock};
+use std::sync::Arc;
+
+use crate::browser::cef_render::{row_stride, unpack_rows};
+
+/// Paint-side writer that double-buffers frames into the shared buffer.
+pub(crate) struct FrameBufferWriter {
+    buffer: Arc<RwLock<Vec<u8>>>,
+    size: Arc<RwLock<(u32, u32)>>,
+    /// Back buffer; after a swap it holds the previous frame's allocation.
+    staging: Mutex<Vec<u8>>,
+}
+
+impl FrameBufferWriter {
+    /// Creates a writer for the given shared pixel buffer and frame size.
+    pub(crate) fn new(buffer: Arc<RwLock<Vec<u8>>>, size: Arc<RwLock<(u32, u32)>>) -> Self {
+        let capacity = buffer.read().capacity();
+        Self {
+            buffer,
+            size,
+            staging: Mutex::new(Vec::with_capacity(capacity)),
+        }
+    }
+
+    /// Publishes a new BGRA frame.
+    ///
+    /// The pixels are copied into the back buffer without touching the
+    /// shared lock; the write lock is held only to swap the two vectors and
+    /// update the frame size. Rows `stride` bytes apart (inferred from the
+    /// length if `None`) are packed tightly on the way.
+    pub(crate) fn publish(&self, pixels: &[u8], width: u32, height: u32, stride: Option<usize>) {
+        let stride = row_stride(pixels.len(), width, height, stride);
+        let pixels = unpack_rows(pixels, width, height, stride);
+        let mut staging = self.staging.lock();
+        staging.clear();
+        staging.extend_from_slice(&pixels);
+
+        let mut front = self.buffer.write();
+        std::mem::swap(&mut *front, &mut *staging);
+        // Size is updated while the buffer lock is held so readers that go
+        // through `copy_frame` never see pixels and dimensions of different frames.
+        *self.size.write() = (width, height);
+    }
+}
+
+/// Copies the current frame and its dimensions out of the shared buffer.
+///
+/// The read lock is held only for the copy; callers encode from the
+/// returned vector without blocking `on_paint`.
+pub(crate) fn copy_frame(
+    buffer: &RwLock<Vec<u8>>,
+    size: &RwLock<(u32, u32)>,
+) -> (Vec<u8>, u32, u32) {
+    let pixels = buffer.read();
+    let (width, height) = *size.read();
+    (pixels.clone(), width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_copy_frame_releases_lock_after_clone() {
+        let buffer = Arc::new(RwLock::new(Vec::new()));
+        let size = Arc::new(RwLock::new((0, 0)));
+        let writer = FrameBufferWriter::new(buffer.clone(), size.clone());
+        writer.publish(&[1; 16], 2, 2, None);
+
+        let (pixels, width, height) = copy_frame(&buffer, &size);
+        assert_eq!((pixels.as_slice(), width, height), (&[1u8; 16][..], 2, 2));
+
+        // The lock is free again while the copy is still alive
+        assert!(buffer.try_write().is_some());
+
+        // A slow consumer of the copy does not hold up painting
+        let reader = std::thread::spawn({
+            let (buffer, size) = (buffer.clone(), size.clone());
+            move || {
+                let (pixels, _, _) = copy_frame(&buffer, &size);
+                std::thread::sleep(Duration::from_millis(200));
+                pixels
+            }
+        });
+        std::thread::sleep(Duration::from_millis(20));
+        let start = Instant::now();
+        writer.publish(&[2; 36], 3, 3, None);
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        assert_eq!(reader.join().unwrap(), vec![1; 16]);
+        assert_eq!(pixels, vec![1; 16]);
+        assert_eq!(copy_frame(&buffer, &size), (vec![2; 36], 3, 3));
+    }
+}
@@ -6,9 +6,10 @@
 //! that dispatch through the command channel.
 
 use anyhow::{anyhow, Context, Result};
-use cef::{ImplBrowser, ImplBrowserHost};
+use cef::{CefString, ImplBrowser, ImplBrowserHost};
 use parking_lot::RwLock;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use tokio::sync::oneshot;
@@ -18,6 +19,7 @@ use uuid::Uuid;
 use super::CefCommand;
 use super::engine::CefBrowserEngine;
 use super::tab::CefTab;
+use crate::browser::cef_input::file_upload::{file_input_result, file_input_script, load_upload_files};
 use crate::input::bezier::{generate_human_path, Point};
 use crate::input::timing::HumanTiming;
 
@@ -263,6 +265,135 @@ pub(crate) fn key_event_internal(
     }
 }
 
+/// Sends one touch point of a touch gesture to a tab.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn touch_event_internal(
+    tab_id: Uuid,
+    id: i32,
+    x: f32,
+    y: f32,
+    pressure: f32,
+    event_type: i32,
+    modifiers: u32,
+    tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+) -> Result<()> {
+    // Clone browser ref and release read lock BEFORE calling CEF methods
+    let browser = {
+        let tabs_guard = tabs.read();
+        let tab = tabs_guard
+            .get(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        tab.browser.clone()
+            .ok_or_else(|| anyhow!("Browser not initialized for tab: {}", tab_id))?
+    }; // Read lock released here.
+
+    if let Some(host) = browser.host() {
+        let touch_event_type = match event_type {
+            0 => cef::TouchEventType::RELEASED,
+            1 => cef::TouchEventType::PRESSED,
+            2 => cef::TouchEventType::MOVED,
+            _ => cef::TouchEventType::CANCELLED,
+        };
+
+        let event = cef::TouchEvent {
+            id,
+            x,
+            y,
+            radius_x: 0.0,
+            radius_y: 0.0,
+            rotation_angle: 0.0,
+            pressure,
+            type_: touch_event_type,
+            modifiers,
+            pointer_type: cef::PointerType::TOUCH,
+        };
+
+        host.send_touch_event(Some(&event));
+        trace!(
+            "Touch event sent to tab {}: id={}, type={}, pos=({}, {})",
+            tab_id, id, event_type, x, y
+        );
+        Ok(())
+    } else {
+        Err(anyhow!("No browser host for tab: {}", tab_id))
+    }
+}
+
+/// Replaces the IME composition text of a tab.
+///
+/// `underlines` and `selection_range` use UTF-16 offsets into `text`.
+pub(crate) fn ime_set_composition_internal(
+    tab_id: Uuid,
+    text: &str,
+    underlines: &[crate::browser::cef_input::CompositionUnderline],
+    selection_range: (i32, i32),
+    tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+) -> Result<()> {
+    // Clone browser ref and release read lock BEFORE calling CEF methods
+    let browser = {
+        let tabs_guard = tabs.read();
+        let tab = tabs_guard
+            .get(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        tab.browser.clone()
+            .ok_or_else(|| anyhow!("Browser not initialized for tab: {}", tab_id))?
+    }; // Read lock released here.
+
+    if let Some(host) = browser.host() {
+        let cef_underlines: Vec<cef::CompositionUnderline> = underlines
+            .iter()
+            .map(|u| cef::CompositionUnderline {
+                size: std::mem::size_of::<cef::CompositionUnderline>(),
+                range: cef::Range { from: u.from, to: u.to },
+                color: u.color,
+                background_color: u.background_color,
+                thick: u.thick as i32,
+                style: cef::CompositionUnderlineStyle::SOLID,
+            })
+            .collect();
+        let selection = cef::Range {
+            from: selection_range.0.max(0) as u32,
+            to: selection_range.1.max(0) as u32,
+        };
+
+        host.ime_set_composition(
+            Some(&CefString::from(text)),
+            Some(&cef_underlines),
+            None,
+            Some(&selection),
+        );
+        trace!("IME composition set for tab {}: {:?}", tab_id, text);
+        Ok(())
+    } else {
+        Err(anyhow!("No browser host for tab: {}", tab_id))
+    }
+}
+
+/// Commits `text` through the IME of a tab, ending the composition.
+pub(crate) fn ime_commit_internal(
+    tab_id: Uuid,
+    text: &str,
+    tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+) -> Result<()> {
+    // Clone browser ref and release read lock BEFORE calling CEF methods
+    let browser = {
+        let tabs_guard = tabs.read();
+        let tab = tabs_guard
+            .get(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        tab.browser.clone()
+            .ok_or_else(|| anyhow!("Browser not initialized for tab: {}", tab_id))?
+    }; // Read lock released here.
+
+    if let Some(host) = browser.host() {
+        host.ime_commit_text(Some(&CefString::from(text)), None, 0);
+        trace!("IME text committed to tab {}: {:?}", tab_id, text);
+        Ok(())
+    } else {
+        Err(anyhow!("No browser host for tab: {}", tab_id))
+    }
+}
+
 /// Maps a character to its Windows Virtual Key code for KEYDOWN/KEYUP events.
 /// Without this, characters like '.' (char code 46 = VK_DELETE!) would be
 /// misinterpreted as control keys.
@@ -317,7 +448,8 @@ fn char_to_vk_code(c: char) -> (i32, u32) {
 /// Types text by sending character events internally on the CEF thread.
 ///
 /// For each character, sends a KEYDOWN, CHAR, and KEYUP event sequence
-/// to simulate realistic keyboard input.
+/// to simulate realistic keyboard input. Characters outside the Basic
+/// Multilingual Plane are sent as a surrogate pair of CHAR events.
 pub(crate) fn type_text_internal(
     tab_id: Uuid,
     text: &str,
@@ -335,6 +467,28 @@ pub(crate) fn type_text_internal(
 
     if let Some(host) = browser.host() {
         for c in text.chars() {
+            // Supplementary-plane characters (emoji) do not fit into one u16
+            // and have no physical key: send their UTF-16 surrogate pair as
+            // two char events, high surrogate first.
+            if c.len_utf16() > 1 {
+                let mut units = [0u16; 2];
+                for &unit in c.encode_utf16(&mut units).iter() {
+                    let char_event = cef::KeyEvent {
+                        size: std::mem::size_of::<cef::KeyEvent>(),
+                        type_: cef::KeyEventType::CHAR,
+                        modifiers: 0,
+                        windows_key_code: unit as i32,
+                        native_key_code: 0,
+                        is_system_key: 0,
+                        character: unit,
+                        unmodified_character: unit,
+                        focus_on_editable_field: 0,
+                    };
+                    host.send_key_event(Some(&char_event));
+                }
+                continue;
+            }
+
             let char_code = c as u16;
             let (vk_code, modifiers) = char_to_vk_code(c);
 
@@ -405,6 +559,7 @@ impl CefBrowserEngine {
         if !self.is_running.load(Ordering::SeqCst) {
             return Err(anyhow!("Browser engine is not running"));
         }
+        self.apply_post_navigation_dwell(tab_id).await;
 
         let timing = HumanTiming::fast();
         let target = Point::new(x as f64, y as f64);
@@ -442,6 +597,26 @@ impl CefBrowserEngine {
         Ok(())
     }
 
+    /// Waits out the post-navigation dwell if this is the first input action
+    /// since the tab's main frame finished loading.
+    ///
+    /// Humans take a moment to orient on a fresh page; acting the instant
+    /// `on_load_end` fires is a common bot signal. Applies once per load and
+    /// only when [`BrowserConfig::post_navigation_dwell`](crate::browser::BrowserConfig)
+    /// is set.
+    async fn apply_post_navigation_dwell(&self, tab_id: Uuid) {
+        let dwell = self
+            .tabs
+            .read()
+            .get(&tab_id)
+            .and_then(|t| t.input_dwell.take(&mut *self.input_rng.lock()));
+        let Some(dwell) = dwell else {
+            return;
+        };
+        debug!("Post-navigation dwell of {:?} for tab {}", dwell, tab_id);
+        tokio::time::sleep(dwell).await;
+    }
+
     /// Sends a mouse move via the command channel and awaits delivery.
     async fn mouse_move_and_wait(&self, tab_id: Uuid, x: i32, y: i32) -> Result<()> {
         let (response_tx, response_rx) = oneshot::channel();
@@ -478,6 +653,7 @@ impl CefBrowserEngine {
         if !self.is_running.load(Ordering::SeqCst) {
             return Err(anyhow!("Browser engine is not running"));
         }
+        self.apply_post_navigation_dwell(tab_id).await;
 
         let (response_tx, response_rx) = oneshot::channel();
         self.command_tx
@@ -496,6 +672,7 @@ impl CefBrowserEngine {
         if !self.is_running.load(Ordering::SeqCst) {
             return Err(anyhow!("Browser engine is not running"));
         }
+        self.apply_post_navigation_dwell(tab_id).await;
 
         let (response_tx, response_rx) = oneshot::channel();
         self.command_tx
@@ -517,6 +694,7 @@ impl CefBrowserEngine {
         if !self.is_running.load(Ordering::SeqCst) {
             return Err(anyhow!("Browser engine is not running"));
         }
+        self.apply_post_navigation_dwell(tab_id).await;
 
         let (response_tx, response_rx) = oneshot::channel();
         self.command_tx
@@ -531,12 +709,32 @@ impl CefBrowserEngine {
         response_rx.await.context("Failed to receive mouse move response")?
     }
 
+    /// Sets the files of the `<input type="file">` matching `selector`.
+    ///
+    /// Bypasses the OS file chooser: the files are read here and assigned
+    /// in the page through a `DataTransfer`, then `input` and `change` fire
+    /// (see [`file_input_script`]). Several files need an input with the
+    /// `multiple` attribute. All files are checked before anything is
+    /// injected; a missing one fails with `InputError::PlatformError`.
+    pub async fn set_input_files(&self, tab_id: Uuid, selector: &str, paths: &[PathBuf]) -> Result<()> {
+        let files = load_upload_files(paths)?;
+        let result = self
+            .execute_js(tab_id, &file_input_script(selector, &files))
+            .await?
+            .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+            .unwrap_or_default();
+        let count = file_input_result(selector, &result)?;
+        debug!("Set {} file(s) on '{}' in tab {}", count, selector, tab_id);
+        Ok(())
+    }
+
     /// Performs a drag operation from one point to another.
     #[allow(clippy::too_many_arguments)]
     pub async fn drag(&self, tab_id: Uuid, from_x: i32, from_y: i32, to_x: i32, to_y: i32, steps: u32, duration_ms: u64) -> Result<()> {
         if !self.is_running.load(Ordering::SeqCst) {
             return Err(anyhow!("Browser engine is not running"));
         }
+        self.apply_post_navigation_dwell(tab_id).await;
 
         let (response_tx, response_rx) = oneshot::channel();
         self.command_tx
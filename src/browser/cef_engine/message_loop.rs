@@ -19,13 +19,21 @@ use tokio::sync::mpsc;
 use tracing::{info, warn};
 use uuid::Uuid;
 
-use crate::browser::engine::BrowserConfig;
+use crate::browser::cef_input::PostNavigationDwell;
+use crate::browser::consent::consent_dismiss_script;
+use crate::browser::dialog::BeforeUnloadPolicy;
+use crate::browser::engine::{BrowserConfig, Viewport};
+use crate::browser::interceptor::InterceptorSlot;
+use crate::browser::page_wait::NetworkActivity;
+use crate::browser::network::{failed_request_observer_script, resource_timeout_script};
 use crate::stealth::StealthConfig;
 use super::callbacks::{
     KiBrowserApp, KiBrowserClient, KiBrowserLifeSpanHandlerImpl, KiBrowserLoadHandlerImpl,
     KiBrowserRenderHandlerImpl, KiBrowserDisplayHandlerImpl, KiBrowserRenderProcessHandler,
-    KiBrowserJsDialogHandlerImpl, KiBrowserDialogHandlerImpl,
+    KiBrowserJsDialogHandlerImpl, KiBrowserDialogHandlerImpl, KiBrowserRequestHandlerImpl,
+    KiBrowserResourceRequestHandlerImpl,
 };
+use super::frame_buffer::FrameBufferWriter;
 use super::tab::CefTab;
 use super::{CefCommand, CEF_MESSAGE_LOOP_DELAY_MS, DEFAULT_FRAME_RATE};
 
@@ -41,6 +49,8 @@ pub(crate) fn run_cef_message_loop(
     tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
     is_running: Arc<AtomicBool>,
     browser_id_counter: Arc<AtomicI32>,
+    default_viewport: Arc<RwLock<Viewport>>,
+    request_interceptor: InterceptorSlot,
     cef_initialized: Arc<AtomicBool>,
     mut command_rx: mpsc::UnboundedReceiver<CefCommand>,
     command_tx: mpsc::UnboundedSender<CefCommand>,
@@ -59,10 +69,15 @@ pub(crate) fn run_cef_message_loop(
         ..Default::default()
     };
 
-    // Set unique cache path to avoid singleton conflicts
+    // Set unique cache path to avoid singleton conflicts. Without a
+    // `cache_path` CEF uses an in-memory (incognito-style) profile.
     let cache_dir = format!("/tmp/ki-browser-cef-{}", std::process::id());
     settings.root_cache_path = CefString::from(cache_dir.as_str());
-    settings.cache_path = CefString::from(cache_dir.as_str());
+    if config.cache_mode.uses_disk_cache() {
+        settings.cache_path = CefString::from(cache_dir.as_str());
+    } else {
+        info!("HTTP cache disabled (no disk cache, --disable-cache)");
+    }
 
     // Render mode: OSR (default) vs windowed. Windowed renders into a real
     // X11 window on the Xvfb display instead of off-screen, which defeats
@@ -102,6 +117,18 @@ pub(crate) fn run_cef_message_loop(
         }
     }
 
+    if config.single_process {
+        warn!("CEF single-process mode: unsupported by Chromium, a renderer crash takes down the whole browser");
+    } else if let Some(path) = config.resolve_cef_subprocess()? {
+        settings.browser_subprocess_path = CefString::from(path.to_string_lossy().as_ref());
+        info!("CEF subprocess executable: {}", path.display());
+    }
+
+    let proxy_pac_url = config.proxy_pac_url()?;
+    if proxy_pac_url.is_some() {
+        info!("Proxy routing: {} per-origin rule(s)", config.proxy_rules.len());
+    }
+
     // Set log level
     settings.log_severity = LogSeverity::WARNING;
 
@@ -133,7 +160,15 @@ pub(crate) fn run_cef_message_loop(
     }
 
     // Create app with v144 API (wrap_app! macro generates ::new())
-    let mut app = KiBrowserApp::new(stealth_config.clone(), rph, config.headless, use_egl);
+    let mut app = KiBrowserApp::new(
+        stealth_config.clone(),
+        rph,
+        config.headless,
+        use_egl,
+        config.cache_mode,
+        config.single_process,
+        proxy_pac_url,
+    );
 
     // Initialize CEF using v144 API
     let result = cef::initialize(
@@ -181,24 +216,39 @@ pub(crate) fn run_cef_message_loop(
                                 &url,
                                 tab_id,
                                 &config,
+                                *default_viewport.read(),
                                 stealth.unwrap_or_else(|| stealth_config.clone()),
                                 tabs.clone(),
                                 browser_id_counter.clone(),
+                                request_interceptor.clone(),
                                 command_tx.clone(),
                             );
                             let _ = response.send(result);
                         }
-                        CefCommand::CloseBrowser { tab_id, response } => {
-                            let result = close_browser_internal(tab_id, tabs.clone());
+                        CefCommand::CloseBrowser {
+                            tab_id,
+                            before_unload,
+                            response,
+                        } => {
+                            let result = close_browser_internal(tab_id, before_unload, tabs.clone());
                             let _ = response.send(result);
                         }
                         CefCommand::Navigate {
                             tab_id,
                             url,
+                            bypass_cache,
+                            max_redirects,
+                            referrer,
                             response,
                         } => {
-                            let result =
-                                super::navigation::navigate_internal(tab_id, &url, tabs.clone());
+                            let result = super::navigation::navigate_internal(
+                                tab_id,
+                                &url,
+                                bypass_cache,
+                                max_redirects,
+                                referrer.as_deref(),
+                                tabs.clone(),
+                            );
                             let _ = response.send(result);
                         }
                         CefCommand::ExecuteJs {
@@ -213,6 +263,20 @@ pub(crate) fn run_cef_message_loop(
                             );
                             let _ = response.send(result);
                         }
+                        CefCommand::EvaluateJs {
+                            tab_id,
+                            query_id,
+                            script,
+                            response,
+                        } => {
+                            super::navigation::evaluate_js_internal(
+                                tab_id,
+                                query_id,
+                                &script,
+                                response,
+                                tabs.clone(),
+                            );
+                        }
                         CefCommand::ExecuteJsWithResult {
                             tab_id,
                             script,
@@ -297,6 +361,49 @@ pub(crate) fn run_cef_message_loop(
                             );
                             let _ = response.send(result);
                         }
+                        CefCommand::TouchEvent {
+                            tab_id,
+                            id,
+                            x,
+                            y,
+                            pressure,
+                            event_type,
+                            modifiers,
+                            response,
+                        } => {
+                            let result = super::input::touch_event_internal(
+                                tab_id,
+                                id,
+                                x,
+                                y,
+                                pressure,
+                                event_type,
+                                modifiers,
+                                tabs.clone(),
+                            );
+                            let _ = response.send(result);
+                        }
+                        CefCommand::ImeSetComposition {
+                            tab_id,
+                            text,
+                            underlines,
+                            selection_range,
+                            response,
+                        } => {
+                            let result = super::input::ime_set_composition_internal(
+                                tab_id,
+                                &text,
+                                &underlines,
+                                selection_range,
+                                tabs.clone(),
+                            );
+                            let _ = response.send(result);
+                        }
+                        CefCommand::ImeCommit { tab_id, text, response } => {
+                            let result =
+                                super::input::ime_commit_internal(tab_id, &text, tabs.clone());
+                            let _ = response.send(result);
+                        }
                         CefCommand::TypeText {
                             tab_id,
                             text,
@@ -327,13 +434,40 @@ pub(crate) fn run_cef_message_loop(
                             let result = super::navigation::go_forward_internal(tab_id, tabs.clone());
                             let _ = response.send(result);
                         }
+                        CefCommand::Reload { tab_id, ignore_cache, response } => {
+                            let result = super::navigation::reload_internal(tab_id, ignore_cache, tabs.clone());
+                            let _ = response.send(result);
+                        }
+                        CefCommand::GetCookies { tab_id, url, response } => {
+                            super::cookies::get_cookies_internal(tab_id, url, tabs.clone(), response);
+                        }
+                        CefCommand::SetCookie { tab_id, cookie, response } => {
+                            super::cookies::set_cookie_internal(tab_id, cookie, tabs.clone(), response);
+                        }
+                        CefCommand::DeleteCookie { tab_id, name, domain, response } => {
+                            super::cookies::delete_cookie_internal(tab_id, &name, &domain, tabs.clone(), response);
+                        }
+                        CefCommand::ClearCookies { tab_id, response } => {
+                            super::cookies::clear_cookies_internal(tab_id, tabs.clone(), response);
+                        }
+                        CefCommand::SetFocus { tab_id, focus, response } => {
+                            let result = super::navigation::set_focus_internal(tab_id, focus, tabs.clone());
+                            let _ = response.send(result);
+                        }
                         CefCommand::ResizeViewport {
                             tab_id,
                             width,
                             height,
+                            device_scale_factor,
                             response,
                         } => {
-                            let result = super::navigation::resize_viewport_internal(tab_id, width, height, tabs.clone());
+                            let result = super::navigation::resize_viewport_internal(
+                                tab_id,
+                                width,
+                                height,
+                                device_scale_factor,
+                                tabs.clone(),
+                            );
                             let _ = response.send(result);
                         }
                         CefCommand::Shutdown { response } => {
@@ -346,7 +480,11 @@ pub(crate) fn run_cef_message_loop(
                             };
 
                             for tab_id in &tab_ids {
-                                let _ = close_browser_internal(*tab_id, tabs.clone());
+                                let _ = close_browser_internal(
+                                    *tab_id,
+                                    BeforeUnloadPolicy::ForceClose,
+                                    tabs.clone(),
+                                );
                             }
 
                             // Pump the CEF message loop so on_before_close callbacks
@@ -459,6 +597,9 @@ fn drain_viewer_input(
 /// creates the CEF browser with the specified URL, and waits for the
 /// `on_after_created` callback to fire before returning.
 ///
+/// `viewport` is the engine's current default viewport (see
+/// `set_default_viewport`).
+///
 /// `stealth_config` is the identity of THIS tab (per-tab config or the
 /// engine default) — it is stored on the [`CefTab`] and used by the load
 /// handler, so every tab has exactly one identity everywhere.
@@ -466,13 +607,16 @@ fn create_browser_internal(
     url: &str,
     tab_id: Uuid,
     config: &BrowserConfig,
+    viewport: Viewport,
     stealth_config: Arc<StealthConfig>,
     tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
     browser_id_counter: Arc<AtomicI32>,
+    request_interceptor: InterceptorSlot,
     popup_tx: mpsc::UnboundedSender<CefCommand>,
 ) -> Result<()> {
-    let viewport_dims = config.window_size;
+    let viewport_dims = (viewport.width, viewport.height);
     let viewport_size = Arc::new(RwLock::new(viewport_dims));
+    let device_scale_factor = Arc::new(RwLock::new(viewport.device_scale_factor));
 
     // Create frame buffer for OSR
     let frame_buffer = Arc::new(RwLock::new(Vec::with_capacity(
@@ -485,9 +629,9 @@ fn create_browser_internal(
     // Create render handler using v144 wrap_render_handler! macro
     let render_handler = KiBrowserRenderHandlerImpl::new(
         tab_id,
-        frame_buffer.clone(),
-        frame_size.clone(),
+        Arc::new(FrameBufferWriter::new(frame_buffer.clone(), frame_size.clone())),
         viewport_size.clone(),
+        device_scale_factor.clone(),
         frame_version.clone(),
     );
 
@@ -499,13 +643,27 @@ fn create_browser_internal(
         tabs.clone(),
         browser_created.clone(),
         Some(popup_tx),
+        config.network_throttle,
     );
 
-    // Create load handler
+    // Create load handler (runs the consent dismissal, failed request
+    // observer and resource timeout scripts when enabled)
+    let consent_script = config
+        .auto_dismiss_consent
+        .then(|| Arc::new(consent_dismiss_script(&config.consent_selectors)));
+    let failed_request_observer_script = config
+        .observe_failed_subresources
+        .then(|| Arc::new(failed_request_observer_script()));
+    let resource_timeout_script = config
+        .resource_timeout_ms
+        .map(|timeout_ms| Arc::new(resource_timeout_script(timeout_ms)));
     let load_handler = KiBrowserLoadHandlerImpl::new(
         tab_id,
         tabs.clone(),
         stealth_config.clone(),
+        consent_script,
+        failed_request_observer_script,
+        resource_timeout_script,
     );
 
     // Create display handler (captures console.log for JS result communication)
@@ -513,9 +671,26 @@ fn create_browser_internal(
 
     // Dialog handlers: auto-answer modal JS/file dialogs so a click can never
     // freeze the single-process message loop waiting for a native dialog.
-    let jsdialog_handler = KiBrowserJsDialogHandlerImpl::new();
+    let jsdialog_handler = KiBrowserJsDialogHandlerImpl::new(tab_id, tabs.clone());
     let dialog_handler = KiBrowserDialogHandlerImpl::new();
 
+    // Request handler: follows redirect chains of navigate() calls and
+    // aborts redirect loops instead of letting them run into timeouts. Its
+    // resource request handler counts in-flight requests, applies the
+    // engine's request interceptor and keeps client hint headers in line
+    // with the UA.
+    let network_activity = Arc::new(NetworkActivity::new());
+    let resource_request_handler = KiBrowserResourceRequestHandlerImpl::new(
+        tab_id,
+        tabs.clone(),
+        request_interceptor,
+        stealth_config.navigator.ua_client_hints.clone().map(Arc::new),
+        network_activity.clone(),
+        config.network_throttle,
+    );
+    let request_handler =
+        KiBrowserRequestHandlerImpl::new(tab_id, tabs.clone(), Some(resource_request_handler));
+
     // Create client using v144 API
     let mut client = KiBrowserClient::new(
         tab_id,
@@ -527,6 +702,7 @@ fn create_browser_internal(
         display_handler,
         jsdialog_handler,
         dialog_handler,
+        request_handler,
     );
 
     // Browser settings. The OSR frame rate is tunable via env: higher values
@@ -573,7 +749,18 @@ fn create_browser_internal(
     }
 
     // Store tab BEFORE browser creation (browser will be set in on_after_created)
-    let cef_tab = CefTab::new(tab_id, url.to_string(), frame_buffer, frame_size, viewport_size, frame_version, stealth_config);
+    let mut cef_tab = CefTab::new(
+        tab_id,
+        url.to_string(),
+        frame_buffer,
+        frame_size,
+        viewport_size,
+        device_scale_factor,
+        frame_version,
+        stealth_config,
+    );
+    cef_tab.network_activity = network_activity;
+    cef_tab.input_dwell = PostNavigationDwell::new(config.post_navigation_dwell.clone());
     tabs.write().insert(tab_id, cef_tab);
 
     // Wait for browser to be created (callback will be triggered)
@@ -597,11 +784,35 @@ fn create_browser_internal(
 /// Closes a browser instance internally on the CEF thread.
 ///
 /// Removes the tab from the shared map and requests the CEF browser host
-/// to close the browser window.
+/// to close the browser window. If `before_unload` runs the page's
+/// `beforeunload` handler, the tab stays in the map marked as closing
+/// instead: the handler may still cancel, and `on_before_close` reports the
+/// outcome.
 pub(crate) fn close_browser_internal(
     tab_id: Uuid,
+    before_unload: BeforeUnloadPolicy,
     tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
 ) -> Result<()> {
+    if before_unload.runs_handler() {
+        let host = {
+            let mut tabs_guard = tabs.write();
+            let tab = tabs_guard
+                .get_mut(&tab_id)
+                .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+            let host = tab.browser.as_ref().and_then(|b| b.host());
+            if host.is_some() {
+                tab.closing = Some(before_unload);
+            }
+            host
+        }; // Write lock released before CEF runs the handler.
+        // Without a browser there is no page to ask; close right away.
+        if let Some(host) = host {
+            host.close_browser(0);
+            info!("Requested close of tab {} ({:?})", tab_id, before_unload);
+            return Ok(());
+        }
+    }
+
     let tab = {
         let mut tabs_guard = tabs.write();
         tabs_guard.remove(&tab_id)
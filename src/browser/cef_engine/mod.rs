@@ -16,16 +16,20 @@
 //! # Submodules
 //!
 //! - [`tab`] - Internal CEF tab representation and lifecycle
+//! - [`frame_buffer`] - Lock-minimizing frame buffer access (double-buffered paint, copy-out reads)
 //! - [`event_sender`] - Bridge between CefInputHandler and CefBrowserEngine command channel
 //! - [`callbacks`] - CEF callback handlers (App, Client, Render, LifeSpan, Load, Display)
 //! - [`engine`] - CefBrowserEngine struct and BrowserEngine trait implementation
 //! - [`message_loop`] - CEF message loop, initialization, and browser creation on the CEF thread
 //! - [`navigation`] - Navigation, JavaScript execution, and screenshot internal methods
 //! - [`input`] - Mouse, keyboard, and text input internal methods on the CEF thread
+//! - [`cookies`] - Cookie access through the CEF cookie manager
 
 #[cfg(feature = "cef-browser")]
 mod tab;
 #[cfg(feature = "cef-browser")]
+mod frame_buffer;
+#[cfg(feature = "cef-browser")]
 mod event_sender;
 #[cfg(feature = "cef-browser")]
 pub(crate) mod callbacks;
@@ -37,6 +41,8 @@ mod message_loop;
 mod navigation;
 #[cfg(feature = "cef-browser")]
 mod input;
+#[cfg(feature = "cef-browser")]
+mod cookies;
 
 #[cfg(feature = "cef-browser")]
 pub use engine::CefBrowserEngine;
@@ -58,6 +64,8 @@ use tokio::sync::oneshot;
 #[cfg(feature = "cef-browser")]
 use uuid::Uuid;
 
+#[cfg(feature = "cef-browser")]
+use crate::browser::dialog::BeforeUnloadPolicy;
 #[cfg(feature = "cef-browser")]
 use crate::browser::screenshot::{Screenshot, ScreenshotOptions};
 
@@ -73,6 +81,10 @@ pub(crate) const DEFAULT_FRAME_RATE: i32 = 30;
 #[cfg(feature = "cef-browser")]
 type JsResultStore = parking_lot::Mutex<std::collections::HashMap<i64, std::sync::mpsc::Sender<Result<String, String>>>>;
 
+/// Type alias for the pending `EvaluateJs` map to reduce type complexity.
+#[cfg(feature = "cef-browser")]
+type PendingEvaluations = parking_lot::Mutex<std::collections::HashMap<i64, oneshot::Sender<Result<Option<String>>>>>;
+
 /// Type alias for tab frame buffer data (pixel buffer + dimensions + frame version).
 #[cfg(feature = "cef-browser")]
 pub type TabFrameBuffer = (Arc<RwLock<Vec<u8>>>, Arc<RwLock<(u32, u32)>>, Arc<std::sync::atomic::AtomicU64>);
@@ -83,6 +95,31 @@ pub type TabFrameBuffer = (Arc<RwLock<Vec<u8>>>, Arc<RwLock<(u32, u32)>>, Arc<st
 static JS_RESULT_STORE: once_cell::sync::Lazy<JsResultStore> =
     once_cell::sync::Lazy::new(|| parking_lot::Mutex::new(std::collections::HashMap::new()));
 
+/// Global store of `execute_js` calls waiting for their result: query_id -> response.
+///
+/// Filled on the CEF thread when the evaluation is sent to the renderer and
+/// drained when `KiBrowserClient` receives the matching result message (or
+/// by `execute_js` when it gives up waiting).
+#[cfg(feature = "cef-browser")]
+static PENDING_EVALUATIONS: once_cell::sync::Lazy<PendingEvaluations> =
+    once_cell::sync::Lazy::new(|| parking_lot::Mutex::new(std::collections::HashMap::new()));
+
+/// Source of `execute_js` query ids, unique for the process lifetime so a
+/// result can never resolve another caller's evaluation.
+#[cfg(feature = "cef-browser")]
+static NEXT_EVALUATION_ID: std::sync::atomic::AtomicI64 = std::sync::atomic::AtomicI64::new(1);
+
+/// Process message asking the renderer to evaluate a script.
+/// Arguments: `[query_id: string, script: string]`.
+#[cfg(feature = "cef-browser")]
+pub(crate) const EVALUATE_JS_MESSAGE: &str = "ki_evaluate_js";
+
+/// Process message carrying an evaluation result back to the browser.
+/// Arguments: `[query_id: string, ok: bool, json_or_error: string]`; the
+/// JSON is empty when the script evaluated to `undefined`.
+#[cfg(feature = "cef-browser")]
+pub(crate) const EVALUATE_JS_RESULT_MESSAGE: &str = "ki_evaluate_js_result";
+
 /// Global store for popup URLs intercepted by on_before_popup.
 /// Stores (source_tab_id, target_url, timestamp) tuples.
 /// Agents can query this via API to handle popups that were blocked.
@@ -91,6 +128,18 @@ pub static POPUP_URL_STORE: once_cell::sync::Lazy<
     parking_lot::Mutex<std::collections::VecDeque<(Uuid, String, std::time::Instant)>>,
 > = once_cell::sync::Lazy::new(|| parking_lot::Mutex::new(std::collections::VecDeque::with_capacity(32)));
 
+/// Global engine event bus.
+///
+/// CEF callbacks run on the CEF thread without a handle to the engine, so
+/// events they observe are published here; `CefBrowserEngine` hands out
+/// receivers from the same sender via `subscribe_events`.
+#[cfg(feature = "cef-browser")]
+pub(crate) static ENGINE_EVENTS: once_cell::sync::Lazy<
+    tokio::sync::broadcast::Sender<crate::browser::events::BrowserEvent>,
+> = once_cell::sync::Lazy::new(|| {
+    tokio::sync::broadcast::channel(crate::browser::engine::ENGINE_EVENT_CAPACITY).0
+});
+
 /// Global BrowserSideRouter (initialized once on first use on the CEF thread).
 #[cfg(feature = "cef-browser")]
 static BROWSER_ROUTER: once_cell::sync::Lazy<std::sync::Arc<cef::wrapper::message_router::BrowserSideRouter>> =
@@ -126,11 +175,15 @@ pub(crate) enum CefCommand {
     },
     CloseBrowser {
         tab_id: Uuid,
+        before_unload: BeforeUnloadPolicy,
         response: oneshot::Sender<Result<()>>,
     },
     Navigate {
         tab_id: Uuid,
         url: String,
+        bypass_cache: bool,
+        max_redirects: u32,
+        referrer: Option<String>,
         response: oneshot::Sender<Result<()>>,
     },
     ExecuteJs {
@@ -179,6 +232,31 @@ pub(crate) enum CefCommand {
         text: String,
         response: oneshot::Sender<Result<()>>,
     },
+    /// One finger of a touch gesture (`BrowserHost::send_touch_event`).
+    TouchEvent {
+        tab_id: Uuid,
+        id: i32,
+        x: f32,
+        y: f32,
+        pressure: f32,
+        event_type: i32,
+        modifiers: u32,
+        response: oneshot::Sender<Result<()>>,
+    },
+    /// Replace the IME composition text (`BrowserHost::ime_set_composition`).
+    ImeSetComposition {
+        tab_id: Uuid,
+        text: String,
+        underlines: Vec<crate::browser::cef_input::CompositionUnderline>,
+        selection_range: (i32, i32),
+        response: oneshot::Sender<Result<()>>,
+    },
+    /// Commit text and end the IME composition (`BrowserHost::ime_commit_text`).
+    ImeCommit {
+        tab_id: Uuid,
+        text: String,
+        response: oneshot::Sender<Result<()>>,
+    },
     Drag {
         tab_id: Uuid,
         from_x: i32,
@@ -189,6 +267,14 @@ pub(crate) enum CefCommand {
         duration_ms: u64,
         response: oneshot::Sender<Result<()>>,
     },
+    /// Evaluate a script in the renderer and report its JSON-serialized
+    /// result through process messages (see [`EVALUATE_JS_MESSAGE`]).
+    EvaluateJs {
+        tab_id: Uuid,
+        query_id: i64,
+        script: String,
+        response: oneshot::Sender<Result<Option<String>>>,
+    },
     ExecuteJsWithResult {
         tab_id: Uuid,
         script: String,
@@ -204,11 +290,50 @@ pub(crate) enum CefCommand {
         tab_id: Uuid,
         response: oneshot::Sender<Result<()>>,
     },
+    /// Reload the current page, optionally bypassing the cache.
+    Reload {
+        tab_id: Uuid,
+        ignore_cache: bool,
+        response: oneshot::Sender<Result<()>>,
+    },
+    /// List cookies through the global cookie manager, all of them or those
+    /// sent to `url`.
+    GetCookies {
+        tab_id: Uuid,
+        url: Option<String>,
+        response: oneshot::Sender<Result<Vec<crate::browser::cookies::Cookie>>>,
+    },
+    /// Set a cookie through the global cookie manager.
+    SetCookie {
+        tab_id: Uuid,
+        cookie: crate::browser::cookies::Cookie,
+        response: oneshot::Sender<Result<()>>,
+    },
+    /// Delete the cookies named `name` of a domain through the global cookie manager.
+    DeleteCookie {
+        tab_id: Uuid,
+        name: String,
+        domain: String,
+        response: oneshot::Sender<Result<()>>,
+    },
+    /// Delete all cookies through the global cookie manager.
+    ClearCookies {
+        tab_id: Uuid,
+        response: oneshot::Sender<Result<()>>,
+    },
+    /// Give or take input focus from a tab's browser host.
+    SetFocus {
+        tab_id: Uuid,
+        focus: bool,
+        response: oneshot::Sender<Result<()>>,
+    },
     /// Resize the CEF viewport for a tab and notify the browser.
     ResizeViewport {
         tab_id: Uuid,
         width: u32,
         height: u32,
+        /// New device scale factor; `None` keeps the tab's current one.
+        device_scale_factor: Option<f64>,
         response: oneshot::Sender<Result<()>>,
     },
     Shutdown {
@@ -6,20 +6,82 @@
 
 use anyhow::{anyhow, Context, Result};
 use cef::CefString;
-use cef::{ImplBrowser, ImplBrowserHost, ImplFrame};
-use parking_lot::RwLock;
+use cef::{ImplBrowser, ImplBrowserHost, ImplFrame, ImplListValue, ImplProcessMessage, ImplRequest};
+use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use tokio::sync::oneshot;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use crate::browser::screenshot::{Screenshot, ScreenshotFormat, ScreenshotOptions};
+use crate::browser::dom::BoundingBox;
+use crate::browser::engine::{BrowserEngine, NavigateOptions};
+use crate::browser::error_capture::write_error_capture;
+use crate::browser::page_wait::{poll_condition, selector_box_script, WaitCondition};
+use crate::browser::referrer::{validate_referrer, ReferrerPolicy};
+use crate::browser::url_pattern::UrlPattern;
+use crate::browser::wait_function::is_truthy;
+use crate::browser::screenshot::{
+    capture_with_scrollbar_policy, crop_bgra_to_rgba, encode_avif, encode_bgra_frame, encode_webp,
+    encode_with_fallback,
+    stitch_tiles, wait_for_first_paint, wait_for_new_frame, FrameTile, Screenshot,
+    ScreenshotFormat, ScreenshotOptions, HIDE_FIXED_ELEMENTS_SCRIPT, RESTORE_FIXED_ELEMENTS_SCRIPT,
+};
+use crate::browser::warmup::{wander_points, WarmupOptions};
 use super::CefCommand;
 use super::engine::CefBrowserEngine;
 use super::tab::CefTab;
 use super::JS_RESULT_STORE;
+use super::{EVALUATE_JS_MESSAGE, NEXT_EVALUATION_ID, PENDING_EVALUATIONS};
+
+/// `cef_urlrequest_flags_t::UR_FLAG_SKIP_CACHE`: load from the network even
+/// if a cached response exists.
+const UR_FLAG_SKIP_CACHE: i32 = 1 << 0;
+
+/// How long a screenshot waits for the repaint after hiding scrollbars.
+const SCROLLBAR_REPAINT_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// How long a full-page screenshot waits for the frame at the grown size.
+const FULL_PAGE_REPAINT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Tallest viewport a full-page screenshot grows to, Chromium's largest
+/// off-screen texture. Longer pages are scrolled and stitched instead.
+pub const MAX_FULL_PAGE_HEIGHT: u32 = 16384;
+
+/// Tallest page a stitched full-page screenshot covers, in CSS pixels.
+/// Longer pages are cut off at this height to bound the image memory, and
+/// the screenshot is marked [`truncated`](Screenshot::truncated).
+pub const MAX_STITCHED_PAGE_HEIGHT: u32 = 32768;
+
+/// How long a stitched screenshot waits for the repaint after scrolling.
+const TILE_REPAINT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Page script reporting the height of the whole scrollable document.
+const FULL_PAGE_HEIGHT_SCRIPT: &str = "Math.max(document.body ? document.body.scrollHeight : 0, \
+     document.documentElement.scrollHeight)";
+
+/// CEF's equivalent of a `Referrer-Policy` value.
+fn cef_referrer_policy(policy: ReferrerPolicy) -> cef::ReferrerPolicy {
+    match policy {
+        ReferrerPolicy::NoReferrer => cef::ReferrerPolicy::NO_REFERRER,
+        ReferrerPolicy::NoReferrerWhenDowngrade => {
+            cef::ReferrerPolicy::CLEAR_REFERRER_ON_TRANSITION_FROM_SECURE_TO_INSECURE
+        }
+        ReferrerPolicy::Origin => cef::ReferrerPolicy::ORIGIN,
+        ReferrerPolicy::OriginWhenCrossOrigin => {
+            cef::ReferrerPolicy::ORIGIN_ONLY_ON_TRANSITION_CROSS_ORIGIN
+        }
+        ReferrerPolicy::SameOrigin => cef::ReferrerPolicy::CLEAR_REFERRER_ON_TRANSITION_CROSS_ORIGIN,
+        ReferrerPolicy::StrictOrigin => {
+            cef::ReferrerPolicy::ORIGIN_CLEAR_ON_TRANSITION_FROM_SECURE_TO_INSECURE
+        }
+        ReferrerPolicy::StrictOriginWhenCrossOrigin => {
+            cef::ReferrerPolicy::REDUCE_REFERRER_GRANULARITY_ON_TRANSITION_CROSS_ORIGIN
+        }
+        ReferrerPolicy::UnsafeUrl => cef::ReferrerPolicy::NEVER_CLEAR_REFERRER,
+    }
+}
 
 // ============================================================================
 // Internal methods (called on the CEF thread)
@@ -32,22 +94,51 @@ use super::JS_RESULT_STORE;
 pub(crate) fn navigate_internal(
     tab_id: Uuid,
     url: &str,
+    bypass_cache: bool,
+    max_redirects: u32,
+    referrer: Option<&str>,
     tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
 ) -> Result<()> {
-    // Clone the browser reference, then release the lock BEFORE calling CEF.
-    let browser = {
-        let tabs_guard = tabs.read();
+    // Clone the browser reference and arm redirect tracking (the chain
+    // starts in on_before_browse), then release the lock BEFORE calling CEF.
+    let (browser, referrer_policy) = {
+        let mut tabs_guard = tabs.write();
         let tab = tabs_guard
-            .get(&tab_id)
+            .get_mut(&tab_id)
             .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
-        tab.browser.clone()
-            .ok_or_else(|| anyhow!("Browser not initialized for tab: {}", tab_id))?
-    }; // Read lock released here.
+        let browser = tab.browser.clone()
+            .ok_or_else(|| anyhow!("Browser not initialized for tab: {}", tab_id))?;
+        tab.pending_max_redirects = Some(max_redirects);
+        tab.redirect_chain = None;
+        (browser, tab.referrer_policy)
+    }; // Write lock released here.
 
     if let Some(frame) = browser.main_frame() {
         let url_string = CefString::from(url);
-        frame.load_url(Some(&url_string));
-        info!("Navigating tab {} to: {}", tab_id, url);
+        if bypass_cache || referrer.is_some() {
+            // The referrer is part of the request itself, so it overrides a
+            // Referer from any other source.
+            let mut request =
+                cef::request_create().ok_or_else(|| anyhow!("Failed to create CEF request"))?;
+            request.set_url(Some(&url_string));
+            if bypass_cache {
+                request.set_flags(UR_FLAG_SKIP_CACHE);
+            }
+            if let Some(referrer) = referrer {
+                request.set_referrer(
+                    Some(&CefString::from(referrer)),
+                    cef_referrer_policy(referrer_policy),
+                );
+            }
+            frame.load_request(Some(&mut request));
+            info!(
+                "Navigating tab {} to: {} (bypass cache: {}, referrer: {:?})",
+                tab_id, url, bypass_cache, referrer
+            );
+        } else {
+            frame.load_url(Some(&url_string));
+            info!("Navigating tab {} to: {}", tab_id, url);
+        }
         Ok(())
     } else {
         Err(anyhow!("No main frame for tab: {}", tab_id))
@@ -55,6 +146,8 @@ pub(crate) fn navigate_internal(
 }
 
 /// Navigates the browser back in history on the CEF thread.
+///
+/// Fails when the tab has no previous history entry.
 pub(crate) fn go_back_internal(
     tab_id: Uuid,
     tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
@@ -68,12 +161,17 @@ pub(crate) fn go_back_internal(
             .ok_or_else(|| anyhow!("Browser not initialized for tab: {}", tab_id))?
     };
 
+    if browser.can_go_back() == 0 {
+        return Err(anyhow!("Cannot go back in tab {}: no previous history entry", tab_id));
+    }
     browser.go_back();
     info!("Go back on tab {}", tab_id);
     Ok(())
 }
 
 /// Navigates the browser forward in history on the CEF thread.
+///
+/// Fails when the tab has no next history entry.
 pub(crate) fn go_forward_internal(
     tab_id: Uuid,
     tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
@@ -87,30 +185,60 @@ pub(crate) fn go_forward_internal(
             .ok_or_else(|| anyhow!("Browser not initialized for tab: {}", tab_id))?
     };
 
+    if browser.can_go_forward() == 0 {
+        return Err(anyhow!("Cannot go forward in tab {}: no next history entry", tab_id));
+    }
     browser.go_forward();
     info!("Go forward on tab {}", tab_id);
     Ok(())
 }
 
+/// Reloads the current page on the CEF thread, bypassing the cache when
+/// `ignore_cache` is set.
+pub(crate) fn reload_internal(
+    tab_id: Uuid,
+    ignore_cache: bool,
+    tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+) -> Result<()> {
+    let browser = {
+        let tabs_guard = tabs.read();
+        let tab = tabs_guard
+            .get(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        tab.browser.clone()
+            .ok_or_else(|| anyhow!("Browser not initialized for tab: {}", tab_id))?
+    };
+
+    if ignore_cache {
+        browser.reload_ignore_cache();
+    } else {
+        browser.reload();
+    }
+    info!("Reload on tab {} (ignore cache: {})", tab_id, ignore_cache);
+    Ok(())
+}
+
 /// Resizes the CEF viewport for a tab and notifies the browser host.
 ///
 /// Updates the shared viewport dimensions (read by the render handler's
 /// `view_rect()` and `screen_info()` callbacks) then calls `was_resized()`
-/// on the browser host so CEF re-renders at the new size.
+/// on the browser host so CEF re-renders at the new size. A changed
+/// `device_scale_factor` is announced with `notify_screen_info_changed()`.
 pub(crate) fn resize_viewport_internal(
     tab_id: Uuid,
     width: u32,
     height: u32,
+    device_scale_factor: Option<f64>,
     tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
 ) -> Result<()> {
-    let (browser, viewport_size) = {
+    let (browser, viewport_size, scale) = {
         let tabs_guard = tabs.read();
         let tab = tabs_guard
             .get(&tab_id)
             .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
         let browser = tab.browser.clone()
             .ok_or_else(|| anyhow!("Browser not initialized for tab: {}", tab_id))?;
-        (browser, tab.viewport_size.clone())
+        (browser, tab.viewport_size.clone(), tab.device_scale_factor.clone())
     };
 
     // Update the shared viewport dimensions before notifying CEF.
@@ -119,8 +247,18 @@ pub(crate) fn resize_viewport_internal(
         let mut vp = viewport_size.write();
         *vp = (width, height);
     }
+    let scale_changed = match device_scale_factor {
+        Some(factor) if *scale.read() != factor => {
+            *scale.write() = factor;
+            true
+        }
+        _ => false,
+    };
 
     if let Some(host) = browser.host() {
+        if scale_changed {
+            host.notify_screen_info_changed();
+        }
         host.was_resized();
         info!("Viewport resized for tab {}: {}x{}", tab_id, width, height);
         Ok(())
@@ -129,6 +267,32 @@ pub(crate) fn resize_viewport_internal(
     }
 }
 
+/// Sets or clears input focus on a tab's browser host on the CEF thread.
+///
+/// A focused OSR browser is treated by Chromium as the foreground page.
+pub(crate) fn set_focus_internal(
+    tab_id: Uuid,
+    focus: bool,
+    tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+) -> Result<()> {
+    let browser = {
+        let tabs_guard = tabs.read();
+        let tab = tabs_guard
+            .get(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        tab.browser.clone()
+            .ok_or_else(|| anyhow!("Browser not initialized for tab: {}", tab_id))?
+    };
+
+    if let Some(host) = browser.host() {
+        host.set_focus(focus as i32);
+        debug!("Focus {} for tab {}", if focus { "set" } else { "cleared" }, tab_id);
+        Ok(())
+    } else {
+        Err(anyhow!("No browser host for tab: {}", tab_id))
+    }
+}
+
 /// Executes JavaScript internally on the CEF thread.
 pub(crate) fn execute_js_internal(
     tab_id: Uuid,
@@ -155,6 +319,91 @@ pub(crate) fn execute_js_internal(
     }
 }
 
+/// `script` as the body of a function returning its value, for scripts that
+/// are a single expression.
+pub(crate) fn expression_function_script(script: &str) -> String {
+    format!("(function(){{return (\n{}\n)}})", script)
+}
+
+/// `script` as the body of a function, for statements with their own `return`.
+pub(crate) fn statements_function_script(script: &str) -> String {
+    format!("(function(){{\n{}\n}})", script)
+}
+
+/// Calls `function` (see [`expression_function_script`] and
+/// [`statements_function_script`]) and yields its result as a JSON string.
+///
+/// `undefined` (and values JSON cannot represent) stay `undefined`, so "no
+/// result" remains distinct from `null`. Exceptions propagate so the
+/// renderer reports them as errors. The script is compiled by V8 directly
+/// rather than through `eval`/`new Function`, so a page CSP without
+/// `unsafe-eval` does not block it.
+pub(crate) fn evaluate_wrapper_script(function: &str) -> String {
+    format!(
+        "(function(__f){{var __v=__f();return __v===undefined?undefined:JSON.stringify(__v)}})({})",
+        function
+    )
+}
+
+/// Page script scrolling to `y` and reporting the resulting scroll offset.
+///
+/// A single expression, so the evaluation wrapper returns its value.
+pub(crate) fn scroll_to_script(y: f64) -> String {
+    format!("(window.scrollTo(0, {}), window.scrollY)", y)
+}
+
+/// Sends `script` to the renderer for evaluation on the CEF thread.
+///
+/// Does not wait: `response` is parked in `PENDING_EVALUATIONS` under
+/// `query_id` and resolved by `KiBrowserClient` when the renderer's
+/// [`EVALUATE_JS_RESULT_MESSAGE`](super::EVALUATE_JS_RESULT_MESSAGE) arrives.
+/// Errors before the message is sent are reported through `response` directly.
+pub(crate) fn evaluate_js_internal(
+    tab_id: Uuid,
+    query_id: i64,
+    script: &str,
+    response: oneshot::Sender<Result<Option<String>>>,
+    tabs: Arc<RwLock<HashMap<Uuid, CefTab>>>,
+) {
+    let frame = {
+        let tabs_guard = tabs.read();
+        tabs_guard
+            .get(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))
+            .and_then(|tab| {
+                tab.browser
+                    .clone()
+                    .ok_or_else(|| anyhow!("Browser not initialized for tab: {}", tab_id))
+            })
+            .and_then(|browser| {
+                browser
+                    .main_frame()
+                    .ok_or_else(|| anyhow!("No main frame for tab: {}", tab_id))
+            })
+    };
+    let frame = match frame {
+        Ok(frame) => frame,
+        Err(e) => {
+            let _ = response.send(Err(e));
+            return;
+        }
+    };
+
+    let Some(mut message) = cef::process_message_create(Some(&CefString::from(EVALUATE_JS_MESSAGE)))
+    else {
+        let _ = response.send(Err(anyhow!("Failed to create JS evaluation message")));
+        return;
+    };
+    if let Some(args) = message.argument_list() {
+        args.set_string(0, Some(&CefString::from(query_id.to_string().as_str())));
+        args.set_string(1, Some(&CefString::from(script)));
+    }
+
+    PENDING_EVALUATIONS.lock().insert(query_id, response);
+    frame.send_process_message(cef::ProcessId::RENDERER, Some(&mut message));
+    debug!("JavaScript evaluation {} sent to renderer for tab {}", query_id, tab_id);
+}
+
 /// Executes JavaScript and waits for the result via console.log interception.
 ///
 /// This wraps the user script in a console.log call with a special prefix
@@ -238,89 +487,71 @@ pub(crate) fn screenshot_internal(
 ) -> Result<Screenshot> {
     options.validate()?;
 
-    let tabs_guard = tabs.read();
-    let tab = tabs_guard
+    // Copy the frame out so neither the tab map nor the frame buffer stays
+    // locked while the image is encoded.
+    let (frame_buffer, width, height) = tabs
+        .read()
         .get(&tab_id)
-        .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
-
-    let frame_buffer = tab.frame_buffer.read();
-    let (width, height) = *tab.frame_size.read();
+        .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?
+        .frame_snapshot();
 
     if frame_buffer.is_empty() || width == 0 || height == 0 {
         return Err(anyhow!("No frame data available for screenshot"));
     }
+    encode_frame(&frame_buffer, width, height, options)
+}
 
-    // Convert BGRA to RGB/RGBA based on format, applying clip region if specified
-    let (image_data, out_width, out_height) = if let Some(ref clip) = options.clip_region {
+/// Encodes a BGRA frame as a screenshot, applying the clip region of
+/// `options`.
+fn encode_frame(
+    frame_buffer: &[u8],
+    width: u32,
+    height: u32,
+    options: &ScreenshotOptions,
+) -> Result<Screenshot> {
+    // Convert BGRA to RGB/RGBA based on format, applying clip region if
+    // specified. A failed encoding can fall back to the (unscaled) pixels.
+    if let Some(ref clip) = options.clip_region {
         // Crop and optionally scale the frame buffer
-        let image_data = convert_frame_to_image_with_clip(
-            &frame_buffer,
-            width,
-            height,
-            clip.x, clip.y, clip.width, clip.height, clip.scale,
-            options.format,
-            options.quality,
-        )?;
         let out_w = (clip.width * clip.scale) as u32;
         let out_h = (clip.height * clip.scale) as u32;
-        (image_data, out_w, out_h)
+        encode_with_fallback(
+            options,
+            out_w,
+            out_h,
+            || {
+                convert_frame_to_image_with_clip(
+                    frame_buffer,
+                    width,
+                    height,
+                    clip.x, clip.y, clip.width, clip.height, clip.scale,
+                    options.format,
+                    options.quality,
+                )
+            },
+            || {
+                let (cx, cy, cw, ch) = clamp_clip(width, height, clip.x, clip.y, clip.width, clip.height);
+                (crop_bgra_to_rgba(frame_buffer, width, cx, cy, cw, ch), cw, ch)
+            },
+        )
     } else {
-        let image_data = convert_frame_to_image(
-            &frame_buffer,
+        encode_with_fallback(
+            options,
             width,
             height,
-            options.format,
-            options.quality,
-        )?;
-        (image_data, width, height)
-    };
-
-    let data = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &image_data);
-
-    Ok(Screenshot::new(data, options.format, out_width, out_height, 1.0))
-}
-
-/// Converts raw BGRA frame buffer to encoded image (PNG, JPEG, or WebP).
-fn convert_frame_to_image(
-    buffer: &[u8],
-    width: u32,
-    height: u32,
-    format: ScreenshotFormat,
-    quality: u8,
-) -> Result<Vec<u8>> {
-    use image::{ImageBuffer, ImageOutputFormat, Rgba};
-
-    // Create image from BGRA buffer
-    let mut img: ImageBuffer<Rgba<u8>, Vec<u8>> = ImageBuffer::new(width, height);
-
-    for (x, y, pixel) in img.enumerate_pixels_mut() {
-        let idx = ((y * width + x) * 4) as usize;
-        if idx + 3 < buffer.len() {
-            // BGRA to RGBA conversion
-            *pixel = Rgba([
-                buffer[idx + 2], // R
-                buffer[idx + 1], // G
-                buffer[idx],     // B
-                buffer[idx + 3], // A
-            ]);
-        }
+            || encode_bgra_frame(frame_buffer, width, height, options.format, options.quality),
+            || (crop_bgra_to_rgba(frame_buffer, width, 0, 0, width, height), width, height),
+        )
     }
+}
 
-    // Encode to requested format
-    let mut output = Vec::new();
-    let format = match format {
-        ScreenshotFormat::Png => ImageOutputFormat::Png,
-        ScreenshotFormat::Jpeg => ImageOutputFormat::Jpeg(quality),
-        ScreenshotFormat::WebP => {
-            // WebP not directly supported by image crate, use PNG as fallback
-            ImageOutputFormat::Png
-        }
-    };
-
-    img.write_to(&mut std::io::Cursor::new(&mut output), format)
-        .context("Failed to encode screenshot")?;
-
-    Ok(output)
+/// Clamps a clip region to a `width` x `height` frame, as `(x, y, w, h)`.
+fn clamp_clip(width: u32, height: u32, x: f64, y: f64, w: f64, h: f64) -> (u32, u32, u32, u32) {
+    let cx = (x.max(0.0) as u32).min(width);
+    let cy = (y.max(0.0) as u32).min(height);
+    let cw = (w as u32).min(width - cx);
+    let ch = (h as u32).min(height - cy);
+    (cx, cy, cw, ch)
 }
 
 /// Converts raw BGRA frame buffer to encoded image with clip region and scale.
@@ -339,10 +570,7 @@ fn convert_frame_to_image_with_clip(
     use image::{ImageBuffer, ImageOutputFormat, Rgba};
 
     // Clamp clip region to image bounds
-    let cx = (clip_x.max(0.0) as u32).min(width);
-    let cy = (clip_y.max(0.0) as u32).min(height);
-    let cw = (clip_w as u32).min(width - cx);
-    let ch = (clip_h as u32).min(height - cy);
+    let (cx, cy, cw, ch) = clamp_clip(width, height, clip_x, clip_y, clip_w, clip_h);
 
     // Create cropped image from BGRA buffer
     let mut cropped: ImageBuffer<Rgba<u8>, Vec<u8>> = ImageBuffer::new(cw, ch);
@@ -373,7 +601,14 @@ fn convert_frame_to_image_with_clip(
     let fmt = match format {
         ScreenshotFormat::Png => ImageOutputFormat::Png,
         ScreenshotFormat::Jpeg => ImageOutputFormat::Jpeg(quality),
-        ScreenshotFormat::WebP => ImageOutputFormat::Png,
+        ScreenshotFormat::WebP => {
+            let (w, h) = final_img.dimensions();
+            return encode_webp(final_img.as_raw(), w, h, quality);
+        }
+        ScreenshotFormat::Avif => {
+            let (w, h) = final_img.dimensions();
+            return encode_avif(final_img.as_raw(), w, h, quality);
+        }
     };
 
     final_img.write_to(&mut std::io::Cursor::new(&mut output), fmt)
@@ -389,43 +624,98 @@ fn convert_frame_to_image_with_clip(
 impl CefBrowserEngine {
     /// Navigates a tab to the specified URL.
     pub async fn navigate(&self, tab_id: Uuid, url: &str) -> Result<()> {
-        if !self.is_running.load(Ordering::SeqCst) {
-            return Err(anyhow!("Browser engine is not running"));
-        }
+        self.navigate_with_options(tab_id, url, NavigateOptions::default())
+            .await
+    }
 
-        let (response_tx, response_rx) = oneshot::channel();
+    /// Navigates a tab to the specified URL with per-navigation options.
+    ///
+    /// The cache is skipped if `options.bypass_cache` is set or the engine
+    /// runs with [`CacheMode::ForceRefresh`](crate::browser::CacheMode).
+    /// `options.referrer` is validated and sent under the tab's referrer
+    /// policy.
+    pub async fn navigate_with_options(
+        &self,
+        tab_id: Uuid,
+        url: &str,
+        options: NavigateOptions,
+    ) -> Result<()> {
+        let result = async {
+            if !self.is_running.load(Ordering::SeqCst) {
+                return Err(anyhow!("Browser engine is not running"));
+            }
+            let referrer = options
+                .referrer
+                .as_deref()
+                .map(validate_referrer)
+                .transpose()?;
 
-        self.command_tx
-            .send(CefCommand::Navigate {
-                tab_id,
-                url: url.to_string(),
-                response: response_tx,
-            })
-            .map_err(|_| anyhow!("Failed to send navigate command"))?;
+            let (response_tx, response_rx) = oneshot::channel();
+
+            self.command_tx
+                .send(CefCommand::Navigate {
+                    tab_id,
+                    url: url.to_string(),
+                    bypass_cache: self.config.cache_mode.bypasses_cache(&options),
+                    max_redirects: options.max_redirects,
+                    referrer,
+                    response: response_tx,
+                })
+                .map_err(|_| anyhow!("Failed to send navigate command"))?;
 
-        response_rx.await.context("Failed to receive navigate response")?
+            response_rx.await.context("Failed to receive navigate response")?
+        }
+        .await;
+        self.capture_on_error(tab_id, "navigate", result).await
     }
 
-    /// Executes JavaScript in a tab.
+    /// Executes JavaScript in a tab and returns its JSON-serialized result.
     ///
-    /// Note: CEF doesn't provide synchronous JavaScript return values.
-    /// For complex interactions, use message passing via V8 context.
+    /// The script may be an expression or a function body with its own
+    /// `return`. It is evaluated in the renderer's V8 context and the result
+    /// travels back as a CEF process message. An `undefined` result is
+    /// `None`, `null` is `Some("null")`; a thrown exception is an error, as
+    /// is no answer within
+    /// [`BrowserConfig::js_timeout_ms`](crate::browser::BrowserConfig::js_timeout_ms).
     pub async fn execute_js(&self, tab_id: Uuid, script: &str) -> Result<Option<String>> {
+        let timeout = std::time::Duration::from_millis(self.config.js_timeout_ms);
+        self.execute_js_timeout(tab_id, script, timeout).await
+    }
+
+    /// [`execute_js`](Self::execute_js) with its own result timeout.
+    pub(crate) async fn execute_js_timeout(
+        &self,
+        tab_id: Uuid,
+        script: &str,
+        timeout: std::time::Duration,
+    ) -> Result<Option<String>> {
         if !self.is_running.load(Ordering::SeqCst) {
             return Err(anyhow!("Browser engine is not running"));
         }
 
+        let query_id = NEXT_EVALUATION_ID.fetch_add(1, Ordering::Relaxed);
         let (response_tx, response_rx) = oneshot::channel();
 
         self.command_tx
-            .send(CefCommand::ExecuteJs {
+            .send(CefCommand::EvaluateJs {
                 tab_id,
+                query_id,
                 script: script.to_string(),
                 response: response_tx,
             })
-            .map_err(|_| anyhow!("Failed to send execute JS command"))?;
+            .map_err(|_| anyhow!("Failed to send evaluate JS command"))?;
 
-        response_rx.await.context("Failed to receive execute JS response")?
+        match tokio::time::timeout(timeout, response_rx).await {
+            Ok(result) => result.context("Failed to receive evaluate JS response")?,
+            Err(_) => {
+                PENDING_EVALUATIONS.lock().remove(&query_id);
+                Err(crate::error::BrowserError::Timeout {
+                    operation: format!("JavaScript evaluation in tab {}", tab_id),
+                    duration_ms: timeout.as_millis() as u64,
+                }
+                .into())
+            }
+        }
     }
 
     /// Executes JavaScript in a tab and waits for the return value via CEF MessageRouter.
@@ -434,25 +724,256 @@ impl CefBrowserEngine {
     /// return value by routing it through `window.cefQuery`. The CEF message
     /// loop is pumped on the command thread while waiting so no deadlock occurs.
     pub async fn execute_js_with_result(&self, tab_id: Uuid, script: &str) -> Result<Option<String>> {
+        let result = async {
+            if !self.is_running.load(Ordering::SeqCst) {
+                return Err(anyhow!("Browser engine is not running"));
+            }
+
+            let (response_tx, response_rx) = oneshot::channel();
+
+            self.command_tx
+                .send(CefCommand::ExecuteJsWithResult {
+                    tab_id,
+                    script: script.to_string(),
+                    response: response_tx,
+                })
+                .map_err(|_| anyhow!("Failed to send execute JS with result command"))?;
+
+            response_rx.await.context("Failed to receive JS with result response")?
+        }
+        .await;
+        self.capture_on_error(tab_id, "execute_js", result).await
+    }
+
+    /// Captures a screenshot of a tab.
+    ///
+    /// With [`ScreenshotOptions::full_page`] set this is
+    /// [`screenshot_full_page`](Self::screenshot_full_page).
+    pub async fn screenshot(
+        &self,
+        tab_id: Uuid,
+        options: ScreenshotOptions,
+    ) -> Result<Screenshot> {
+        if options.full_page {
+            return self.screenshot_full_page(tab_id, options).await;
+        }
+        self.capture_viewport(tab_id, options).await
+    }
+
+    /// Captures the whole scrollable page of a tab, not just the viewport.
+    ///
+    /// Reads the document's scroll height, grows the viewport to it, waits
+    /// for CEF to paint the frame at the new size, captures it and restores
+    /// the original viewport, also when the capture fails. Pages taller than
+    /// [`MAX_FULL_PAGE_HEIGHT`] are captured by
+    /// [`screenshot_stitched`](Self::screenshot_stitched) instead, up to
+    /// [`MAX_STITCHED_PAGE_HEIGHT`]. A clip region in `options` selects part
+    /// of the full page.
+    pub async fn screenshot_full_page(
+        &self,
+        tab_id: Uuid,
+        options: ScreenshotOptions,
+    ) -> Result<Screenshot> {
         if !self.is_running.load(Ordering::SeqCst) {
             return Err(anyhow!("Browser engine is not running"));
         }
+        options.validate()?;
+        let options = ScreenshotOptions {
+            full_page: false,
+            ..options
+        };
 
-        let (response_tx, response_rx) = oneshot::channel();
+        let (width, height) = self
+            .tabs
+            .read()
+            .get(&tab_id)
+            .map(|tab| *tab.viewport_size.read())
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        let scroll_height = self
+            .execute_js(tab_id, FULL_PAGE_HEIGHT_SCRIPT)
+            .await?
+            .and_then(|json| json.parse::<f64>().ok())
+            .ok_or_else(|| anyhow!("Failed to read the page height for tab {}", tab_id))?;
+        let full_height = scroll_height.ceil() as u32;
+        if full_height <= height {
+            return self.capture_viewport(tab_id, options).await;
+        }
+        if full_height > MAX_FULL_PAGE_HEIGHT {
+            let page_height = full_height.min(MAX_STITCHED_PAGE_HEIGHT);
+            let truncated = full_height > page_height;
+            if truncated {
+                warn!(
+                    "Page of tab {} is {} px tall, capturing the first {} px",
+                    tab_id, full_height, page_height
+                );
+            }
+            let screenshot = self.screenshot_stitched(tab_id, options, height, page_height).await?;
+            return Ok(Screenshot {
+                truncated,
+                ..screenshot
+            });
+        }
+
+        debug!("Full-page screenshot of tab {}: {}x{}", tab_id, width, full_height);
+        self.resize_viewport_for_capture(tab_id, width, full_height).await?;
+        let result = async {
+            self.wait_for_frame_size(tab_id, (width, full_height)).await?;
+            self.capture_viewport(tab_id, options).await
+        }
+        .await;
+        let restored = self.resize_viewport_for_capture(tab_id, width, height).await;
 
+        let screenshot = result?;
+        restored?;
+        Ok(screenshot)
+    }
+
+    /// Captures a page `page_height` CSS pixels tall by scrolling a viewport
+    /// `viewport_height` pixels tall over it and stitching the frames.
+    ///
+    /// Fixed and sticky elements are hidden after the first tile, so they
+    /// appear once at the top. They, the scroll position and the scrollbars
+    /// are restored afterwards, also when the capture fails.
+    pub async fn screenshot_stitched(
+        &self,
+        tab_id: Uuid,
+        options: ScreenshotOptions,
+        viewport_height: u32,
+        page_height: u32,
+    ) -> Result<Screenshot> {
+        let frame_version = self
+            .tabs
+            .read()
+            .get(&tab_id)
+            .map(|tab| tab.frame_version.clone())
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        let scroll_to = |y: f64| async move {
+            self.execute_js(tab_id, &scroll_to_script(y))
+                .await?
+                .and_then(|json| json.parse::<f64>().ok())
+                .ok_or_else(|| anyhow!("Failed to scroll tab {}", tab_id))
+        };
+        let initial_scroll = self
+            .execute_js(tab_id, "window.scrollY")
+            .await?
+            .and_then(|json| json.parse::<f64>().ok())
+            .unwrap_or(0.0);
+        debug!(
+            "Stitched screenshot of tab {}: {} px in {} px tiles",
+            tab_id, page_height, viewport_height
+        );
+
+        let run_script = |script: &'static str| async move {
+            self.execute_js(tab_id, script).await.map(|_| ())
+        };
+        let result = capture_with_scrollbar_policy(&options, run_script, || async {
+            let mut tiles = Vec::new();
+            let (mut frame_width, mut scale) = (0, 1.0);
+            let mut target = 0.0;
+            loop {
+                let seen = frame_version.load(Ordering::Acquire);
+                let scrolled = scroll_to(target).await?;
+                wait_for_new_frame(
+                    &frame_version,
+                    seen,
+                    TILE_REPAINT_TIMEOUT,
+                    self.config.ready_poll_backoff,
+                )
+                .await;
+
+                let (buffer, width, height) = self
+                    .tabs
+                    .read()
+                    .get(&tab_id)
+                    .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?
+                    .frame_snapshot();
+                if buffer.is_empty() || width == 0 || height == 0 {
+                    return Err(anyhow!("No frame data available for screenshot"));
+                }
+                if tiles.is_empty() {
+                    // Frames are in device pixels, scroll offsets in CSS pixels
+                    frame_width = width;
+                    scale = height as f64 / viewport_height as f64;
+                    run_script(HIDE_FIXED_ELEMENTS_SCRIPT).await?;
+                } else if width != frame_width {
+                    return Err(anyhow!("Viewport of tab {} changed during the capture", tab_id));
+                }
+                tiles.push(FrameTile {
+                    y: (scrolled * scale).round() as u32,
+                    buffer,
+                });
+
+                target += viewport_height as f64;
+                let page_end = page_height as f64;
+                if target >= page_end || scrolled + viewport_height as f64 >= page_end {
+                    break;
+                }
+            }
+
+            let height = (page_height as f64 * scale).round() as u32;
+            let options = options.clone();
+            tokio::task::spawn_blocking(move || {
+                let frame = stitch_tiles(&tiles, frame_width, height);
+                encode_frame(&frame, frame_width, height, &options)
+            })
+            .await
+            .context("Stitched screenshot encoding panicked")?
+        })
+        .await;
+
+        if let Err(e) = run_script(RESTORE_FIXED_ELEMENTS_SCRIPT).await {
+            warn!("Failed to restore fixed elements after screenshot: {}", e);
+        }
+        if let Err(e) = scroll_to(initial_scroll).await {
+            warn!("Failed to restore scroll position after screenshot: {}", e);
+        }
+        result
+    }
+
+    /// Resizes a tab's viewport without announcing a viewport change.
+    async fn resize_viewport_for_capture(&self, tab_id: Uuid, width: u32, height: u32) -> Result<()> {
+        let (response_tx, response_rx) = oneshot::channel();
         self.command_tx
-            .send(CefCommand::ExecuteJsWithResult {
+            .send(CefCommand::ResizeViewport {
                 tab_id,
-                script: script.to_string(),
+                width,
+                height,
+                device_scale_factor: None,
                 response: response_tx,
             })
-            .map_err(|_| anyhow!("Failed to send execute JS with result command"))?;
+            .map_err(|_| anyhow!("Failed to send resize viewport command"))?;
+        response_rx.await.context("Failed to receive resize viewport response")?
+    }
+
+    /// Waits until the tab's last painted frame has `size`.
+    async fn wait_for_frame_size(&self, tab_id: Uuid, size: (u32, u32)) -> Result<()> {
+        let frame_size = self
+            .tabs
+            .read()
+            .get(&tab_id)
+            .map(|tab| tab.frame_size.clone())
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
 
-        response_rx.await.context("Failed to receive JS with result response")?
+        let start = std::time::Instant::now();
+        let backoff = self.config.ready_poll_backoff;
+        let mut interval = backoff.initial();
+        while *frame_size.read() != size {
+            let elapsed = start.elapsed();
+            if elapsed >= FULL_PAGE_REPAINT_TIMEOUT {
+                return Err(crate::error::BrowserError::Timeout {
+                    operation: format!("repaint of tab {} at {}x{}", tab_id, size.0, size.1),
+                    duration_ms: FULL_PAGE_REPAINT_TIMEOUT.as_millis() as u64,
+                }
+                .into());
+            }
+            tokio::time::sleep(interval.min(FULL_PAGE_REPAINT_TIMEOUT - elapsed)).await;
+            interval = backoff.next(interval);
+        }
+        Ok(())
     }
 
-    /// Captures a screenshot of a tab.
-    pub async fn screenshot(
+    /// Captures the tab's current frame, i.e. what the viewport shows.
+    async fn capture_viewport(
         &self,
         tab_id: Uuid,
         options: ScreenshotOptions,
@@ -461,41 +982,236 @@ impl CefBrowserEngine {
             return Err(anyhow!("Browser engine is not running"));
         }
 
-        let (response_tx, response_rx) = oneshot::channel();
+        if let Some(timeout_ms) = options.first_paint_timeout_ms {
+            let frame_version = self
+                .tabs
+                .read()
+                .get(&tab_id)
+                .map(|tab| tab.frame_version.clone())
+                .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+            wait_for_first_paint(
+                &frame_version,
+                std::time::Duration::from_millis(timeout_ms),
+                self.config.ready_poll_backoff,
+            )
+            .await?;
+        }
 
-        self.command_tx
-            .send(CefCommand::Screenshot {
-                tab_id,
-                options,
-                response: response_tx,
-            })
-            .map_err(|_| anyhow!("Failed to send screenshot command"))?;
+        let frame_version = self
+            .tabs
+            .read()
+            .get(&tab_id)
+            .map(|tab| tab.frame_version.clone())
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        let seen = frame_version.load(Ordering::Acquire);
+
+        let run_script = |script: &'static str| async move {
+            self.execute_js(tab_id, script).await.map(|_| ())
+        };
+        capture_with_scrollbar_policy(&options, run_script, || async {
+            if !options.include_scrollbars {
+                // Hiding scrollbars only shows up in the frame after a repaint;
+                // pages without scrollbars may not repaint at all.
+                wait_for_new_frame(
+                    &frame_version,
+                    seen,
+                    SCROLLBAR_REPAINT_TIMEOUT,
+                    self.config.ready_poll_backoff,
+                )
+                .await;
+            }
 
-        response_rx.await.context("Failed to receive screenshot response")?
+            let (response_tx, response_rx) = oneshot::channel();
+            self.command_tx
+                .send(CefCommand::Screenshot {
+                    tab_id,
+                    options: options.clone(),
+                    response: response_tx,
+                })
+                .map_err(|_| anyhow!("Failed to send screenshot command"))?;
+
+            response_rx.await.context("Failed to receive screenshot response")?
+        })
+        .await
     }
 
     /// Waits for a tab to be ready for interaction.
+    ///
+    /// Polls with the increasing intervals configured in
+    /// [`BrowserConfig::ready_poll_backoff`](crate::browser::BrowserConfig).
     pub async fn wait_for_ready(&self, tab_id: Uuid, timeout_ms: u64) -> Result<()> {
-        let start = std::time::Instant::now();
-        let timeout = std::time::Duration::from_millis(timeout_ms);
+        let result = async {
+            let start = std::time::Instant::now();
+            let timeout = std::time::Duration::from_millis(timeout_ms);
+            let backoff = self.config.ready_poll_backoff;
+            let mut interval = backoff.initial();
 
-        loop {
-            {
-                let tabs = self.tabs.read();
-                if let Some(tab) = tabs.get(&tab_id) {
-                    if tab.is_ready.load(Ordering::SeqCst) {
-                        return Ok(());
+            loop {
+                {
+                    let tabs = self.tabs.read();
+                    if let Some(tab) = tabs.get(&tab_id) {
+                        if tab.is_ready.load(Ordering::SeqCst) {
+                            return Ok(());
+                        }
+                    } else {
+                        return Err(anyhow!("Tab not found: {}", tab_id));
                     }
-                } else {
-                    return Err(anyhow!("Tab not found: {}", tab_id));
                 }
+
+                if start.elapsed() > timeout {
+                    return Err(anyhow!("Timeout waiting for tab {} to be ready", tab_id));
+                }
+
+                tokio::time::sleep(interval).await;
+                interval = backoff.next(interval);
+            }
+        }
+        .await;
+        self.capture_on_error(tab_id, "wait_for_ready", result).await
+    }
+
+    /// Waits until a tab reaches the state described by `condition`.
+    ///
+    /// Polls with the backoff of `wait_for_ready`. Load state, URL, title
+    /// and network idleness come from the tab; selectors and expressions
+    /// are evaluated in the page via [`execute_js`](Self::execute_js).
+    pub async fn wait_for_condition(
+        &self,
+        tab_id: Uuid,
+        condition: WaitCondition,
+        timeout: std::time::Duration,
+    ) -> Result<()> {
+        condition.validate()?;
+        let url_pattern = match &condition {
+            WaitCondition::Url(pattern) => UrlPattern::parse(pattern).ok(),
+            _ => None,
+        };
+        let result = poll_condition(&condition, timeout, self.config.ready_poll_backoff, || {
+            self.check_wait_condition(tab_id, &condition, url_pattern.as_ref())
+        })
+        .await;
+        self.capture_on_error(tab_id, "wait_for", result).await
+    }
+
+    /// Waits until an element matches `selector` and returns its box.
+    ///
+    /// Polls like [`wait_for_condition`](Self::wait_for_condition) with a
+    /// script that reports the element's box once it exists.
+    pub async fn wait_for_selector_box(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        timeout: std::time::Duration,
+    ) -> Result<BoundingBox> {
+        let condition = WaitCondition::Selector(selector.to_string());
+        condition.validate()?;
+        let script = selector_box_script(selector);
+        let found = Mutex::new(None);
+        let result = poll_condition(&condition, timeout, self.config.ready_poll_backoff, || async {
+            let bounding_box = self
+                .execute_js(tab_id, &script)
+                .await?
+                .and_then(|json| serde_json::from_str::<BoundingBox>(&json).ok());
+            let present = bounding_box.is_some();
+            *found.lock() = bounding_box;
+            Ok(present)
+        })
+        .await
+        .map(|()| found.lock().take().expect("box recorded by the successful poll"));
+        self.capture_on_error(tab_id, "wait_for_selector", result).await
+    }
+
+    /// Whether `condition` holds for a tab right now.
+    async fn check_wait_condition(
+        &self,
+        tab_id: Uuid,
+        condition: &WaitCondition,
+        url_pattern: Option<&UrlPattern>,
+    ) -> Result<bool> {
+        {
+            let tabs = self.tabs.read();
+            let tab = tabs
+                .get(&tab_id)
+                .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+            match condition {
+                WaitCondition::DomContentLoaded if tab.is_ready.load(Ordering::SeqCst) => {
+                    return Ok(true);
+                }
+                WaitCondition::NetworkIdle { idle_ms } => {
+                    let idle = std::time::Duration::from_millis(*idle_ms);
+                    return Ok(tab.network_activity.is_idle_for(idle));
+                }
+                WaitCondition::Url(_) => {
+                    return Ok(url_pattern.is_some_and(|p| p.matches(&tab.url)));
+                }
+                WaitCondition::TitleContains(text) => return Ok(tab.title.contains(text.as_str())),
+                _ => {}
             }
+        } // Lock released before evaluating in the page.
 
-            if start.elapsed() > timeout {
-                return Err(anyhow!("Timeout waiting for tab {} to be ready", tab_id));
+        let Some(script) = condition.script() else {
+            return Ok(false);
+        };
+        let value = match self.execute_js(tab_id, &script).await {
+            Ok(value) => value,
+            // A document that is still loading may not take scripts yet
+            Err(e) if *condition == WaitCondition::DomContentLoaded => {
+                debug!("DOMContentLoaded check failed in tab {}: {}", tab_id, e);
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        };
+        Ok(value
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .is_some_and(|value| is_truthy(&value)))
+    }
+
+    /// Visits `urls` in order before a protected navigation, staying on each
+    /// loaded page for a time drawn from `options.dwell`.
+    ///
+    /// With `options.mouse_wander` the dwell is split between a few idle
+    /// mouse moves. See [`warmup`](crate::browser::warmup).
+    pub async fn warmup(&self, tab_id: Uuid, urls: &[String], options: &WarmupOptions) -> Result<()> {
+        options.validate()?;
+        for url in urls {
+            // Clear readiness first so the wait below cannot see the
+            // previous page's load.
+            if let Some(tab) = self.tabs.read().get(&tab_id) {
+                tab.is_ready.store(false, Ordering::SeqCst);
             }
+            self.navigate(tab_id, url).await?;
+            self.wait_for_ready(tab_id, self.config.timeout_ms).await?;
 
-            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            let dwell = options.sample_dwell(&mut rand::thread_rng());
+            debug!("Warmup: dwelling {:?} on {} in tab {}", dwell, url, tab_id);
+            if options.mouse_wander {
+                let viewport = self.get_viewport(tab_id).await?;
+                let points = wander_points(viewport.width, viewport.height, &mut rand::thread_rng());
+                let pause = dwell / points.len().max(1) as u32;
+                for (x, y) in points {
+                    tokio::time::sleep(pause).await;
+                    self.mouse_move(tab_id, x, y).await?;
+                }
+            } else {
+                tokio::time::sleep(dwell).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Passes `result` through, writing a screenshot-on-error capture first
+    /// if it is an error and `screenshot_on_error` is configured.
+    async fn capture_on_error<T>(&self, tab_id: Uuid, operation: &str, result: Result<T>) -> Result<T> {
+        if let (Err(e), Some(dir)) = (&result, &self.config.screenshot_on_error) {
+            let url = self.tabs.read().get(&tab_id).map(|t| t.url.clone());
+            if let Some(url) = url {
+                let frame = self.screenshot(tab_id, ScreenshotOptions::default()).await;
+                match write_error_capture(dir, tab_id, &url, operation, &format!("{:#}", e), frame).await {
+                    Ok(path) => info!("Error capture for tab {} written to {}", tab_id, path.display()),
+                    Err(capture_err) => warn!("Failed to write error capture for tab {}: {}", tab_id, capture_err),
+                }
+            }
         }
+        result
     }
 }
@@ -10,8 +10,17 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::browser::cef_input::PostNavigationDwell;
+use crate::browser::dialog::BeforeUnloadPolicy;
+use crate::browser::har::HarRecorder;
+use crate::browser::network::FailedRequest;
+use crate::browser::page_wait::NetworkActivity;
+use crate::browser::redirect::RedirectChain;
+use crate::browser::referrer::ReferrerPolicy;
+use crate::browser::response_capture::ResponseCapture;
 use crate::browser::tab::{Tab, TabStatus};
 use crate::stealth::StealthConfig;
+use super::frame_buffer::copy_frame;
 
 /// Internal representation of a CEF browser tab.
 ///
@@ -43,12 +52,37 @@ pub(crate) struct CefTab {
     pub(crate) can_go_forward: AtomicBool,
     /// Shared viewport dimensions for the render handler (updated on resize).
     pub(crate) viewport_size: Arc<RwLock<(u32, u32)>>,
+    /// Shared device scale factor reported to CEF in `screen_info()`.
+    pub(crate) device_scale_factor: Arc<RwLock<f64>>,
     /// Frame version counter, incremented on every on_paint callback.
     /// Used by the video stream encoder to detect new frames.
     pub(crate) frame_version: Arc<AtomicU64>,
     /// The stealth identity assigned to this tab at creation time.
     /// Single source of truth for all fingerprint spoofing of this tab.
     pub(crate) stealth: Arc<StealthConfig>,
+    /// Requests that failed since the last top-level navigation.
+    pub(crate) failed_requests: Vec<FailedRequest>,
+    /// In-flight requests, counted by the resource request handler.
+    pub(crate) network_activity: Arc<NetworkActivity>,
+    /// Binding names installed via `expose_binding`; re-installed on every load.
+    pub(crate) bindings: Vec<String>,
+    /// Response capture patterns and bodies; `None` until capture is enabled.
+    pub(crate) response_capture: Option<ResponseCapture>,
+    /// Running HAR recording, fed by the resource request handler.
+    pub(crate) har_recorder: Option<Arc<HarRecorder>>,
+    /// Armed when the main frame finishes loading; taken by the first input
+    /// action, which waits for the configured post-navigation dwell first.
+    pub(crate) input_dwell: PostNavigationDwell,
+    /// Redirect limit of a `navigate` call whose request has not started yet.
+    pub(crate) pending_max_redirects: Option<u32>,
+    /// Redirects of the navigation started by `navigate`; `None` once a
+    /// user-initiated navigation takes over.
+    pub(crate) redirect_chain: Option<RedirectChain>,
+    /// Policy applied to the referrer of `navigate` calls.
+    pub(crate) referrer_policy: ReferrerPolicy,
+    /// Policy of a close waiting for the page's `beforeunload` handler;
+    /// reset to `None` when the handler cancels the close.
+    pub(crate) closing: Option<BeforeUnloadPolicy>,
 }
 
 impl CefTab {
@@ -59,6 +93,7 @@ impl CefTab {
         frame_buffer: Arc<RwLock<Vec<u8>>>,
         frame_size: Arc<RwLock<(u32, u32)>>,
         viewport_size: Arc<RwLock<(u32, u32)>>,
+        device_scale_factor: Arc<RwLock<f64>>,
         frame_version: Arc<AtomicU64>,
         stealth: Arc<StealthConfig>,
     ) -> Self {
@@ -75,8 +110,19 @@ impl CefTab {
             can_go_back: AtomicBool::new(false),
             can_go_forward: AtomicBool::new(false),
             viewport_size,
+            device_scale_factor,
             frame_version,
             stealth,
+            failed_requests: Vec::new(),
+            network_activity: Arc::new(NetworkActivity::new()),
+            bindings: Vec::new(),
+            response_capture: None,
+            har_recorder: None,
+            input_dwell: PostNavigationDwell::new(None),
+            pending_max_redirects: None,
+            redirect_chain: None,
+            referrer_policy: ReferrerPolicy::default(),
+            closing: None,
         }
     }
 
@@ -85,6 +131,14 @@ impl CefTab {
         self.browser = Some(browser);
     }
 
+    /// Copies the last rendered frame and its dimensions.
+    ///
+    /// Holds the frame buffer lock only for the copy, so `on_paint` is not
+    /// blocked while the caller encodes the image.
+    pub(crate) fn frame_snapshot(&self) -> (Vec<u8>, u32, u32) {
+        copy_frame(&self.frame_buffer, &self.frame_size)
+    }
+
     /// Converts the internal CefTab to the public Tab type for API consumers.
     pub(crate) fn to_tab(&self) -> Tab {
         let mut tab = Tab::new(self.url.clone());
@@ -39,3 +39,200 @@ async fn test_cef_engine_lifecycle() {
 
     assert!(!engine.is_running().await);
 }
+
+#[test]
+fn test_evaluate_wrapper_script_serializes_result() {
+    use super::navigation::{
+        evaluate_wrapper_script, expression_function_script, statements_function_script,
+    };
+
+    let function = expression_function_script("document.title");
+    assert_eq!(function, "(function(){return (\ndocument.title\n)})");
+    let wrapped = evaluate_wrapper_script(&function);
+    assert!(wrapped.ends_with("})((function(){return (\ndocument.title\n)}))"));
+    assert!(wrapped.contains("__v===undefined?undefined:JSON.stringify(__v)"));
+
+    // Trailing line comments cannot swallow the closing parenthesis or brace
+    assert!(expression_function_script("1 // one").ends_with("// one\n)})"));
+    assert!(statements_function_script("return 1 // one").ends_with("// one\n})"));
+
+    // The page's eval is never involved, so strict CSPs do not block it
+    assert!(!wrapped.contains("new Function"));
+    assert!(!wrapped.contains("eval("));
+}
+
+#[test]
+fn test_scroll_to_script_is_single_expression() {
+    // Statements would make the wrapper's expression attempt fail and the
+    // statement fallback return undefined instead of the scroll offset
+    let script = super::navigation::scroll_to_script(1200.0);
+    assert_eq!(script, "(window.scrollTo(0, 1200), window.scrollY)");
+    assert!(!script.contains(';'));
+    let function = super::navigation::expression_function_script(&script);
+    assert!(function.contains("return (\n(window.scrollTo(0, 1200), window.scrollY)\n)"));
+}
+
+#[tokio::test]
+#[ignore = "Requires CEF runtime"]
+async fn test_scroll_to_script_evaluates_to_offset() {
+    let config = BrowserConfig::default().headless(true);
+    let window_height = config.window_size.1;
+    let engine = CefBrowserEngine::new(config).await.unwrap();
+    let page = format!(
+        "data:text/html,<body style='margin:0'><div style='height:{}px'>tall</div></body>",
+        window_height * 3
+    );
+    let tab = engine.create_tab(&page).await.unwrap();
+    engine.wait_for_ready(tab.id, 10_000).await.unwrap();
+
+    let scrolled = engine
+        .execute_js(tab.id, &super::navigation::scroll_to_script(100.0))
+        .await
+        .unwrap()
+        .and_then(|json| json.parse::<f64>().ok());
+    assert_eq!(scrolled, Some(100.0));
+
+    engine.close_tab(tab.id).await.unwrap();
+    engine.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires CEF runtime"]
+async fn test_execute_js_returns_result() {
+    let config = BrowserConfig::default().headless(true).js_timeout_ms(2_000);
+    let engine = CefBrowserEngine::new(config).await.unwrap();
+    let tab = engine.create_tab("about:blank").await.unwrap();
+    engine.wait_for_ready(tab.id, 10_000).await.unwrap();
+
+    assert_eq!(engine.execute_js(tab.id, "1+1").await.unwrap().as_deref(), Some("2"));
+    assert_eq!(engine.execute_js(tab.id, "var a = 2;\nreturn a * 2;").await.unwrap().as_deref(), Some("4"));
+    assert_eq!(engine.execute_js(tab.id, "undefined").await.unwrap(), None);
+    assert_eq!(engine.execute_js(tab.id, "null").await.unwrap().as_deref(), Some("null"));
+    assert!(engine.execute_js(tab.id, "throw new Error('boom')").await.is_err());
+
+    engine.close_tab(tab.id).await.unwrap();
+    engine.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires CEF runtime"]
+async fn test_execute_js_under_strict_csp() {
+    let config = BrowserConfig::default().headless(true).js_timeout_ms(2_000);
+    let engine = CefBrowserEngine::new(config).await.unwrap();
+    let page = "data:text/html,<meta http-equiv='Content-Security-Policy' \
+                content=\"script-src 'none'\"><title>strict</title>";
+    let tab = engine.create_tab(page).await.unwrap();
+    engine.wait_for_ready(tab.id, 10_000).await.unwrap();
+
+    assert_eq!(engine.execute_js(tab.id, "document.title").await.unwrap().as_deref(), Some("\"strict\""));
+    assert_eq!(engine.execute_js(tab.id, "return 1 + 1;").await.unwrap().as_deref(), Some("2"));
+
+    engine.close_tab(tab.id).await.unwrap();
+    engine.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires CEF runtime"]
+async fn test_full_page_screenshot_exceeds_window_height() {
+    use crate::browser::screenshot::ScreenshotOptions;
+
+    let config = BrowserConfig::default().headless(true);
+    let window_height = config.window_size.1;
+    let engine = CefBrowserEngine::new(config).await.unwrap();
+
+    let page = format!(
+        "data:text/html,<body style='margin:0'><div style='height:{}px'>tall</div></body>",
+        window_height * 3
+    );
+    let tab = engine.create_tab(&page).await.unwrap();
+    engine.wait_for_ready(tab.id, 10_000).await.unwrap();
+
+    let options = ScreenshotOptions::new().wait_for_first_paint(std::time::Duration::from_secs(5));
+    let viewport = engine.screenshot(tab.id, options.clone()).await.unwrap();
+    assert_eq!(viewport.height, window_height);
+
+    let full = engine.screenshot(tab.id, options.full_page(true)).await.unwrap();
+    assert!(full.height > window_height);
+    assert_eq!(full.width, viewport.width);
+
+    // Clips apply to the full page, and the viewport is restored afterwards
+    let clipped = ScreenshotOptions::new().clip_rect(0.0, 0.0, 100.0, 50.0);
+    let after = engine.screenshot_full_page(tab.id, clipped).await.unwrap();
+    assert_eq!((after.width, after.height), (100, 50));
+    let viewport = engine.get_viewport(tab.id).await.unwrap();
+    assert_eq!(viewport.height, window_height);
+
+    engine.close_tab(tab.id).await.unwrap();
+    engine.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires CEF runtime"]
+async fn test_stitched_screenshot_covers_page() {
+    use crate::browser::screenshot::ScreenshotOptions;
+
+    let config = BrowserConfig::default().headless(true);
+    let window_height = config.window_size.1;
+    let engine = CefBrowserEngine::new(config).await.unwrap();
+
+    let page = format!(
+        "data:text/html,<body style='margin:0'><header style='position:fixed;top:0'>nav</header>\
+         <div style='height:{}px'>tall</div></body>",
+        window_height * 3
+    );
+    let tab = engine.create_tab(&page).await.unwrap();
+    engine.wait_for_ready(tab.id, 10_000).await.unwrap();
+
+    let options = ScreenshotOptions::new().wait_for_first_paint(std::time::Duration::from_secs(5));
+    let viewport = engine.screenshot(tab.id, options.clone()).await.unwrap();
+    let stitched = engine
+        .screenshot_stitched(tab.id, options, window_height, window_height * 3)
+        .await
+        .unwrap();
+    assert_eq!(stitched.width, viewport.width);
+    assert_eq!(stitched.height, viewport.height * 3);
+
+    // Scroll position and fixed elements are restored
+    assert_eq!(engine.execute_js(tab.id, "window.scrollY").await.unwrap().as_deref(), Some("0"));
+    let visible = "getComputedStyle(document.querySelector('header')).visibility === 'visible'";
+    assert_eq!(engine.execute_js(tab.id, visible).await.unwrap().as_deref(), Some("true"));
+
+    engine.close_tab(tab.id).await.unwrap();
+    engine.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires CEF runtime"]
+async fn test_post_navigation_dwell_precedes_first_click_only() {
+    use std::time::{Duration, Instant};
+
+    let dwell = Duration::from_secs(1);
+    let config = BrowserConfig::default()
+        .headless(true)
+        .post_navigation_dwell(dwell..=dwell)
+        .input_rng_seed(7);
+    let engine = CefBrowserEngine::new(config).await.unwrap();
+    let tab = engine.create_tab("about:blank").await.unwrap();
+    engine.wait_for_ready(tab.id, 10_000).await.unwrap();
+
+    engine.navigate(tab.id, "data:text/html,<p>first</p>").await.unwrap();
+    engine.wait_for_ready(tab.id, 10_000).await.unwrap();
+    let start = Instant::now();
+    engine.click(tab.id, 100, 100, 0).await.unwrap();
+    assert!(start.elapsed() >= dwell);
+
+    // Later clicks on the same page act without orienting again
+    let start = Instant::now();
+    engine.click(tab.id, 100, 100, 0).await.unwrap();
+    assert!(start.elapsed() < dwell);
+
+    // The next navigation owes a fresh dwell
+    engine.navigate(tab.id, "data:text/html,<p>second</p>").await.unwrap();
+    engine.wait_for_ready(tab.id, 10_000).await.unwrap();
+    let start = Instant::now();
+    engine.click(tab.id, 100, 100, 0).await.unwrap();
+    assert!(start.elapsed() >= dwell);
+
+    engine.close_tab(tab.id).await.unwrap();
+    engine.shutdown().await.unwrap();
+}
@@ -211,9 +211,30 @@ impl CefKeyEvent {
     /// Creates a KEYEVENT_CHAR event for direct Unicode character input.
     ///
     /// Used for text typing where the character value matters more than
-    /// the physical key position.
+    /// the physical key position. Only characters in the Basic Multilingual
+    /// Plane fit into one event; use [`char_events`](Self::char_events) for
+    /// arbitrary text.
     pub fn char_event(character: char) -> Self {
-        let char_code = character as u16;
+        let mut units = [0u16; 2];
+        Self::utf16_char_event(character.encode_utf16(&mut units)[0])
+    }
+
+    /// Creates the KEYEVENT_CHAR events that input `character`.
+    ///
+    /// Characters outside the Basic Multilingual Plane (emoji, rare CJK)
+    /// need two UTF-16 code units and are sent as two events, high surrogate
+    /// first, the same way Windows delivers them as `WM_CHAR` messages.
+    pub fn char_events(character: char) -> Vec<Self> {
+        let mut units = [0u16; 2];
+        character
+            .encode_utf16(&mut units)
+            .iter()
+            .map(|&unit| Self::utf16_char_event(unit))
+            .collect()
+    }
+
+    /// Creates a KEYEVENT_CHAR event for a single UTF-16 code unit.
+    fn utf16_char_event(char_code: u16) -> Self {
         Self {
             event_type: CefKeyEventType::Char,
             modifiers: EVENTFLAG_NONE,
@@ -258,6 +279,105 @@ impl Default for CefKeyEvent {
     }
 }
 
+// ============================================================================
+// Touch Event Types
+// ============================================================================
+
+/// CEF touch event type discriminant, mapping to CEF_TET_* constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CefTouchEventType {
+    /// Finger lifted (CEF_TET_RELEASED = 0).
+    Released,
+    /// Finger placed on the screen (CEF_TET_PRESSED = 1).
+    Pressed,
+    /// Finger moved while touching (CEF_TET_MOVED = 2).
+    Moved,
+    /// Touch sequence aborted (CEF_TET_CANCELLED = 3).
+    Cancelled,
+}
+
+impl CefTouchEventType {
+    /// Returns the CEF integer constant for this touch event type (CEF_TET_*).
+    pub fn to_cef_type(&self) -> i32 {
+        match self {
+            CefTouchEventType::Released => 0,  // CEF_TET_RELEASED
+            CefTouchEventType::Pressed => 1,   // CEF_TET_PRESSED
+            CefTouchEventType::Moved => 2,     // CEF_TET_MOVED
+            CefTouchEventType::Cancelled => 3, // CEF_TET_CANCELLED
+        }
+    }
+}
+
+/// CEF touch event structure for touch screen input simulation.
+///
+/// Each finger is tracked by its `id` from press to release; events of
+/// several fingers interleave for multi-touch gestures.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CefTouchEvent {
+    /// Finger identifier, unique among the touches currently down.
+    pub id: i32,
+    /// X coordinate relative to the browser view.
+    pub x: f32,
+    /// Y coordinate relative to the browser view.
+    pub y: f32,
+    /// Pressure between 0.0 and 1.0.
+    pub pressure: f32,
+    /// The type of touch event.
+    pub event_type: CefTouchEventType,
+    /// Bitmask of active EVENTFLAG_* constants.
+    pub modifiers: u32,
+}
+
+impl CefTouchEvent {
+    /// Creates a touch event without modifiers.
+    pub fn new(id: i32, event_type: CefTouchEventType, x: f32, y: f32, pressure: f32) -> Self {
+        Self {
+            id,
+            x,
+            y,
+            pressure,
+            event_type,
+            modifiers: EVENTFLAG_NONE,
+        }
+    }
+}
+
+// ============================================================================
+// IME Composition Types
+// ============================================================================
+
+/// Underline drawn under part of an IME composition string.
+///
+/// Mirrors CEF's `cef_composition_underline_t`; `from` and `to` are UTF-16
+/// offsets into the composition text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompositionUnderline {
+    /// First UTF-16 offset covered by the underline.
+    pub from: u32,
+    /// UTF-16 offset just past the underline.
+    pub to: u32,
+    /// Underline color (ARGB); 0 uses the text color.
+    pub color: u32,
+    /// Background color (ARGB) of the covered text; 0 for none.
+    pub background_color: u32,
+    /// Thick underline marks the segment currently being converted.
+    pub thick: bool,
+}
+
+impl CompositionUnderline {
+    /// Thin underline in the text color over `from..to`, the style of
+    /// not yet converted composition text.
+    pub fn thin(from: u32, to: u32) -> Self {
+        Self {
+            from,
+            to,
+            color: 0,
+            background_color: 0,
+            thick: false,
+        }
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -305,4 +425,14 @@ mod tests {
         assert_eq!(event.character, 'A' as u16);
         assert_eq!(event.unmodified_character, 'A' as u16);
     }
+
+    #[test]
+    fn test_cef_key_event_char_events_surrogate_pair() {
+        let events = CefKeyEvent::char_events('\u{1F600}');
+        let codes: Vec<u16> = events.iter().map(|e| e.character).collect();
+        assert_eq!(codes, vec![0xD83D, 0xDE00]);
+        assert!(events.iter().all(|e| e.event_type == CefKeyEventType::Char));
+
+        assert_eq!(CefKeyEvent::char_events('\u{00E9}').len(), 1);
+    }
 }
@@ -0,0 +1,342 @@
+//! File upload methods on `CefInputHandler`.
+//!
+//! A click on `<input type="file">` opens the OS file chooser, which
+//! simulated input cannot operate. Instead the files are read on the Rust
+//! side, rebuilt as `File` objects in the page and assigned to the input
+//! through a `DataTransfer`, whose `files` is a genuine `FileList`. The
+//! `input` and `change` events then fire as after a user's choice.
+//!
+//! [`file_input_script`] is shared with `CefBrowserEngine::set_input_files`,
+//! which injects it without moving the mouse.
+
+use std::path::{Path, PathBuf};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::browser::dom::{BoundingBox, DomAccessor};
+use crate::browser::page_wait::selector_box_script;
+use crate::input::{InputError, InputResult};
+
+use super::mouse::{CefEventSender, CefInputHandler};
+
+/// A local file prepared for injection into a file input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UploadFile {
+    /// File name reported by `File.name`.
+    pub name: String,
+
+    /// MIME type reported by `File.type`.
+    #[serde(rename = "type")]
+    pub mime_type: String,
+
+    /// Base64-encoded file contents.
+    pub data: String,
+}
+
+impl UploadFile {
+    /// Reads the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InputError::PlatformError` if the file does not exist or
+    /// cannot be read.
+    pub fn load(path: &Path) -> InputResult<Self> {
+        if !path.is_file() {
+            return Err(InputError::PlatformError {
+                message: format!("File not found: {}", path.display()),
+            });
+        }
+        let bytes = std::fs::read(path).map_err(|e| InputError::PlatformError {
+            message: format!("Failed to read {}: {}", path.display(), e),
+        })?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Ok(Self {
+            name,
+            mime_type: mime_type_for(path).to_string(),
+            data: BASE64.encode(bytes),
+        })
+    }
+}
+
+/// Reads all `paths`, so a missing file fails the upload before anything
+/// is injected.
+///
+/// # Errors
+///
+/// Returns `InputError::PlatformError` if `paths` is empty or a file does
+/// not exist or cannot be read.
+pub fn load_upload_files(paths: &[PathBuf]) -> InputResult<Vec<UploadFile>> {
+    if paths.is_empty() {
+        return Err(InputError::PlatformError {
+            message: "No files to upload".to_string(),
+        });
+    }
+    paths.iter().map(|path| UploadFile::load(path)).collect()
+}
+
+/// MIME type browsers report for a file, by extension.
+fn mime_type_for(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "xml" => "text/xml",
+        "mp3" => "audio/mpeg",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        // Chromium leaves the type empty for unknown extensions
+        _ => "",
+    }
+}
+
+/// Builds the page script assigning `files` to the file input matching
+/// `selector` and dispatching `input` and `change` events.
+///
+/// Evaluates to `{ files: <count> }`, or to `{ error: <reason> }` if no file
+/// input matches or several files are given to an input without `multiple`.
+pub fn file_input_script(selector: &str, files: &[UploadFile]) -> String {
+    let selector_json = serde_json::to_string(selector).unwrap_or_else(|_| "''".to_string());
+    let files_json = serde_json::to_string(files).unwrap_or_else(|_| "[]".to_string());
+    format!(
+        r#"(function() {{
+    const input = document.querySelector({selector_json});
+    if (input === null) return {{ error: 'No element matches selector' }};
+    if (!(input instanceof HTMLInputElement) || input.type !== 'file') {{
+        return {{ error: 'Element is not a file input' }};
+    }}
+    const files = {files_json};
+    if (files.length > 1 && !input.multiple) {{
+        return {{ error: 'File input does not accept multiple files' }};
+    }}
+    const transfer = new DataTransfer();
+    for (const file of files) {{
+        const binary = atob(file.data);
+        const bytes = new Uint8Array(binary.length);
+        for (let i = 0; i < binary.length; i++) bytes[i] = binary.charCodeAt(i);
+        transfer.items.add(new File([bytes], file.name, {{ type: file.type }}));
+    }}
+    input.files = transfer.files;
+    input.dispatchEvent(new Event('input', {{ bubbles: true }}));
+    input.dispatchEvent(new Event('change', {{ bubbles: true }}));
+    return {{ files: input.files.length }};
+}})()"#
+    )
+}
+
+/// Interprets the result of [`file_input_script`], returning the number of
+/// files the input now holds.
+///
+/// # Errors
+///
+/// Returns `InputError::PlatformError` with the script's reason, or if the
+/// result is not one the script produces.
+pub fn file_input_result(selector: &str, result: &Value) -> InputResult<usize> {
+    if let Some(count) = result.get("files").and_then(Value::as_f64) {
+        return Ok(count as usize);
+    }
+    let reason = result
+        .get("error")
+        .and_then(Value::as_str)
+        .unwrap_or("Unexpected script result");
+    Err(InputError::PlatformError {
+        message: format!("Failed to set files of '{}': {}", selector, reason),
+    })
+}
+
+impl<S: CefEventSender> CefInputHandler<S> {
+    // ========================================================================
+    // File Upload Methods
+    // ========================================================================
+
+    /// Uploads `file_path` through the file input matching `selector`.
+    ///
+    /// See [`upload_files`](Self::upload_files).
+    #[allow(clippy::ptr_arg)]
+    pub async fn upload_file<D: DomAccessor + ?Sized>(
+        &mut self,
+        dom: &D,
+        selector: &str,
+        file_path: &PathBuf,
+    ) -> InputResult<()> {
+        self.upload_files(dom, selector, std::slice::from_ref(file_path))
+            .await
+    }
+
+    /// Uploads `paths` through the file input matching `selector`.
+    ///
+    /// The mouse moves onto the input like a user reaching for it (unless it
+    /// is hidden behind a styled label), then the files are assigned via
+    /// [`file_input_script`] without opening the file chooser.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InputError::PlatformError` if a file is missing, nothing
+    /// matches `selector`, the element is not a file input or the page
+    /// rejects the files.
+    pub async fn upload_files<D: DomAccessor + ?Sized>(
+        &mut self,
+        dom: &D,
+        selector: &str,
+        paths: &[PathBuf],
+    ) -> InputResult<()> {
+        let files = load_upload_files(paths)?;
+        let element = dom
+            .find_element(selector)
+            .await
+            .map_err(|e| dom_error(selector, e))?
+            .ok_or_else(|| InputError::PlatformError {
+                message: format!("No element matches '{}'", selector),
+            })?;
+        let is_file_input = element.tag_name.eq_ignore_ascii_case("input")
+            && element
+                .attributes
+                .get("type")
+                .is_some_and(|kind| kind.eq_ignore_ascii_case("file"));
+        if !is_file_input {
+            return Err(InputError::PlatformError {
+                message: format!("'{}' is not an <input type=\"file\">", selector),
+            });
+        }
+
+        let rect = dom
+            .evaluate_js(&selector_box_script(selector))
+            .await
+            .map_err(|e| dom_error(selector, e))?;
+        let bounding_box = serde_json::to_value(&rect)
+            .ok()
+            .and_then(|value| serde_json::from_value::<BoundingBox>(value).ok());
+        if let Some(bounding_box) = bounding_box.filter(|b| b.width > 0.0 && b.height > 0.0) {
+            let (x, y) = bounding_box.center();
+            self.send_mouse_move_to_target(x, y, Some(bounding_box.width))
+                .await?;
+        }
+
+        let result = dom
+            .evaluate_js(&file_input_script(selector, &files))
+            .await
+            .map_err(|e| dom_error(selector, e))?;
+        file_input_result(selector, &serde_json::to_value(&result).unwrap_or_default())?;
+        Ok(())
+    }
+}
+
+/// Wraps a DOM access failure for `selector`.
+fn dom_error(selector: &str, error: anyhow::Error) -> InputError {
+    InputError::PlatformError {
+        message: format!("DOM access for '{}' failed: {}", selector, error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use crate::browser::cef_input::MockCefEventSender;
+    use crate::browser::dom::{DomElement, JsValue, MockDomAccessor};
+    use crate::input::timing::HumanTiming;
+
+    fn handler() -> CefInputHandler<MockCefEventSender> {
+        CefInputHandler::new(MockCefEventSender::new(), HumanTiming::instant())
+    }
+
+    fn file_input(selector: &str) -> DomElement {
+        let mut element = DomElement::new(selector.to_string(), "input".to_string());
+        element.attributes.insert("type".to_string(), "file".to_string());
+        element.bounding_box = Some(BoundingBox::new(100.0, 200.0, 120.0, 40.0));
+        element
+    }
+
+    fn object(entries: &[(&str, JsValue)]) -> JsValue {
+        let map: HashMap<String, JsValue> = entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect();
+        JsValue::Object(map)
+    }
+
+    #[tokio::test]
+    async fn test_upload_file_injects_data_transfer() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("avatar.png");
+        std::fs::write(&path, b"\x89PNG fake").unwrap();
+
+        let dom = MockDomAccessor::new();
+        dom.add_element("#avatar", file_input("#avatar"));
+        dom.set_js_result(
+            &selector_box_script("#avatar"),
+            object(&[
+                ("x", JsValue::Number(100.0)),
+                ("y", JsValue::Number(200.0)),
+                ("width", JsValue::Number(120.0)),
+                ("height", JsValue::Number(40.0)),
+            ]),
+        );
+        let files = load_upload_files(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(files[0].name, "avatar.png");
+        assert_eq!(files[0].mime_type, "image/png");
+        assert_eq!(files[0].data, BASE64.encode(b"\x89PNG fake"));
+
+        let script = file_input_script("#avatar", &files);
+        assert!(script.contains(r##"document.querySelector("#avatar")"##));
+        assert!(script.contains(&format!(
+            r#"[{{"name":"avatar.png","type":"image/png","data":"{}"}}]"#,
+            files[0].data
+        )));
+        assert!(script.contains("new DataTransfer()"));
+        assert!(script.contains("input.files = transfer.files;"));
+        assert!(script.contains("new Event('change', { bubbles: true })"));
+
+        // Without the expected script's result the mock reports undefined
+        let mut handler = handler();
+        let err = handler.upload_file(&dom, "#avatar", &path).await.unwrap_err();
+        assert!(err.to_string().contains("Unexpected script result"));
+
+        dom.set_js_result(&script, object(&[("files", JsValue::Number(1.0))]));
+        handler.upload_file(&dom, "#avatar", &path).await.unwrap();
+        let moves = handler.sender.mouse_moves.lock().unwrap();
+        let last = moves.last().unwrap();
+        assert_eq!((last.x, last.y), (160, 220));
+    }
+
+    #[tokio::test]
+    async fn test_upload_file_rejects_missing_files_and_other_elements() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.pdf");
+        let dom = MockDomAccessor::new();
+        dom.add_element("#avatar", file_input("#avatar"));
+        dom.add_element("#name", DomElement::new("#name".to_string(), "input".to_string()));
+        let mut handler = handler();
+
+        let err = handler.upload_file(&dom, "#avatar", &missing).await.unwrap_err();
+        assert!(matches!(err, InputError::PlatformError { ref message } if message.contains("missing.pdf")));
+        assert!(handler.sender.mouse_moves.lock().unwrap().is_empty());
+
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "notes").unwrap();
+        let err = handler.upload_file(&dom, "#name", &path).await.unwrap_err();
+        assert!(err.to_string().contains("is not an <input type=\"file\">"));
+
+        let rejected = serde_json::json!({ "error": "File input does not accept multiple files" });
+        let err = file_input_result("#avatar", &rejected).unwrap_err();
+        assert!(err.to_string().contains("does not accept multiple files"));
+    }
+}
@@ -425,6 +425,87 @@ pub fn get_key_for_char(c: char) -> String {
     }
 }
 
+/// Returns the keys next to a letter or digit on a US QWERTY keyboard.
+///
+/// Uppercase letters get uppercase neighbours. Other characters have no
+/// neighbours and get an empty list.
+pub fn qwerty_neighbors(c: char) -> Vec<char> {
+    let neighbors = match c.to_ascii_lowercase() {
+        '1' => "2q",
+        '2' => "13qw",
+        '3' => "24we",
+        '4' => "35er",
+        '5' => "46rt",
+        '6' => "57ty",
+        '7' => "68yu",
+        '8' => "79ui",
+        '9' => "80io",
+        '0' => "9op",
+        'q' => "12wa",
+        'w' => "23qeas",
+        'e' => "34wrsd",
+        'r' => "45etdf",
+        't' => "56ryfg",
+        'y' => "67tugh",
+        'u' => "78yihj",
+        'i' => "89uojk",
+        'o' => "90ipkl",
+        'p' => "0ol",
+        'a' => "qwsz",
+        's' => "weadzx",
+        'd' => "ersfxc",
+        'f' => "rtdgcv",
+        'g' => "tyfhvb",
+        'h' => "yugjbn",
+        'j' => "uihknm",
+        'k' => "iojlm",
+        'l' => "opk",
+        'z' => "asx",
+        'x' => "sdzc",
+        'c' => "dfxv",
+        'v' => "fgcb",
+        'b' => "ghvn",
+        'n' => "hjbm",
+        'm' => "jkn",
+        _ => "",
+    };
+    if c.is_ascii_uppercase() {
+        neighbors.chars().map(|n| n.to_ascii_uppercase()).collect()
+    } else {
+        neighbors.chars().collect()
+    }
+}
+
+// ============================================================================
+// IME Helpers
+// ============================================================================
+
+/// Key code of key events consumed by an IME (`VKEY_PROCESSKEY`).
+///
+/// Chromium reports it on every platform, and pages see it as
+/// `keyCode === 229` while a composition is active.
+pub const VK_PROCESSKEY: i32 = 0xE5;
+
+/// Checks whether a character is typed through an IME composition.
+///
+/// Covers Japanese kana, CJK ideographs, Hangul and Bopomofo plus the
+/// CJK punctuation and full-width forms entered through the same IMEs.
+pub fn requires_ime(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}'   // Hangul Jamo
+        | '\u{3000}'..='\u{303F}' // CJK symbols and punctuation
+        | '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+        | '\u{3100}'..='\u{312F}' // Bopomofo
+        | '\u{3130}'..='\u{318F}' // Hangul compatibility Jamo
+        | '\u{3400}'..='\u{4DBF}' // CJK extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK unified ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK compatibility ideographs
+        | '\u{FF00}'..='\u{FFEF}' // Half-width and full-width forms
+        | '\u{20000}'..='\u{2FA1F}' // CJK extensions B-F, supplement
+    )
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -433,6 +514,22 @@ pub fn get_key_for_char(c: char) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_qwerty_neighbors() {
+        assert_eq!(qwerty_neighbors('a'), ['q', 'w', 's', 'z']);
+        assert_eq!(qwerty_neighbors('M'), ['J', 'K', 'N']);
+        assert!(qwerty_neighbors(' ').is_empty());
+        assert!(qwerty_neighbors('é').is_empty());
+    }
+
+    #[test]
+    fn test_requires_ime() {
+        assert!("こんにちは".chars().all(requires_ime));
+        assert!("你好".chars().all(requires_ime));
+        assert!("안녕".chars().all(requires_ime));
+        assert!(!"Hello, é!".chars().any(requires_ime));
+    }
+
     #[test]
     fn test_key_name_to_code_single_chars() {
         // Alphabetic keys map to their uppercase ASCII codes
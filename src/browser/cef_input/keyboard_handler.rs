@@ -2,19 +2,24 @@
 //!
 //! This module extends `CefInputHandler` with keyboard-specific behaviour:
 //! single key events, character input (KEYEVENT_CHAR), full text typing with
-//! human-like per-character delays, key combos (Ctrl+C, Alt+F4, etc.), and
-//! modifier key state tracking.
+//! human-like per-character delays, IME composition for CJK text, key combos
+//! (Ctrl+C, Alt+F4, etc.), and modifier key state tracking including held
+//! ("sticky") modifiers.
 //!
 //! All methods are async and include randomised delays to avoid bot detection
 //! via keystroke timing fingerprinting.
 
 use std::time::Duration;
 
+use rand::seq::SliceRandom;
+use rand::Rng;
+
 use crate::input::{InputError, InputResult, Modifier};
 
-use super::events::{CefKeyEvent, CefKeyEventType};
+use super::events::{CefKeyEvent, CefKeyEventType, CompositionUnderline};
 use super::keyboard::{get_key_for_char, is_shifted_character, key_name_to_code,
-    modifier_to_key_name, modifiers_to_event_flags};
+    modifier_to_key_name, modifiers_to_event_flags, qwerty_neighbors, requires_ime,
+    VK_PROCESSKEY};
 use super::mouse::{CefEventSender, CefInputHandler};
 
 impl<S: CefEventSender> CefInputHandler<S> {
@@ -27,6 +32,7 @@ impl<S: CefEventSender> CefInputHandler<S> {
     /// Translates `key` to a platform-specific virtual key code via
     /// `key_name_to_code`, updates the internal modifier state if `key` is
     /// itself a modifier key, and delivers the event through the sender.
+    /// Modifiers that are currently held are included in the event flags.
     ///
     /// # Arguments
     ///
@@ -46,6 +52,7 @@ impl<S: CefEventSender> CefInputHandler<S> {
         let key_code = key_name_to_code(key).ok_or_else(|| InputError::InvalidKey {
             key: key.to_string(),
         })?;
+        self.apply_post_navigation_dwell().await;
 
         let event_type = if is_down {
             CefKeyEventType::KeyDown
@@ -53,7 +60,7 @@ impl<S: CefEventSender> CefInputHandler<S> {
             CefKeyEventType::KeyUp
         };
 
-        let modifier_flags = modifiers_to_event_flags(modifiers);
+        let modifier_flags = modifiers_to_event_flags(modifiers) | self.current_modifier_flags();
 
         let mut event = CefKeyEvent::new(event_type, key_code);
         event.modifiers = modifier_flags;
@@ -92,8 +99,11 @@ impl<S: CefEventSender> CefInputHandler<S> {
     ///
     /// * `c` - The Unicode character to inject.
     pub async fn send_char(&mut self, c: char) -> InputResult<()> {
-        let event = CefKeyEvent::char_event(c).with_modifier(self.current_modifier_flags());
-        self.sender.send_key_event(&event);
+        self.apply_post_navigation_dwell().await;
+        let modifiers = self.current_modifier_flags();
+        for event in CefKeyEvent::char_events(c) {
+            self.sender.send_key_event(&event.with_modifier(modifiers));
+        }
         Ok(())
     }
 
@@ -103,6 +113,10 @@ impl<S: CefEventSender> CefInputHandler<S> {
     /// sends a key-down + KEYEVENT_CHAR + key-up sequence, then waits a
     /// character-frequency-weighted delay before the next character.
     ///
+    /// With a non-zero `CefInputConfig::typo_rate`, letters and digits are
+    /// occasionally mistyped as a neighbouring QWERTY key, followed by a
+    /// pause, a Backspace and the correct character.
+    ///
     /// # Arguments
     ///
     /// * `text` - The text to type into the CEF browser.
@@ -118,38 +132,155 @@ impl<S: CefEventSender> CefInputHandler<S> {
     /// ```
     pub async fn send_text(&mut self, text: &str) -> InputResult<()> {
         for c in text.chars() {
-            let needs_shift = c.is_uppercase() || is_shifted_character(c);
+            // Supplementary-plane characters (emoji) have no physical key;
+            // they only arrive as a surrogate pair of char events.
+            if c.len_utf16() > 1 {
+                self.send_char(c).await?;
+                tokio::time::sleep(self.get_char_delay(c)).await;
+                continue;
+            }
+
+            if let Some(typo) = self.pick_typo(c) {
+                self.type_key_char(typo).await?;
+                tokio::time::sleep(self.get_char_delay(typo)).await;
 
-            if needs_shift {
-                self.send_key_event("Shift", &[], true).await?;
+                // Noticing the mistake takes a moment
+                tokio::time::sleep(self.timing.get_reaction_delay()).await;
+                self.send_key_event("Backspace", &[], true).await?;
+                tokio::time::sleep(self.timing.get_key_hold_delay()).await;
+                self.send_key_event("Backspace", &[], false).await?;
+                tokio::time::sleep(self.timing.get_type_delay()).await;
             }
 
-            let key = get_key_for_char(c);
+            self.type_key_char(c).await?;
+
+            // Inter-keystroke delay weighted by character typing frequency
+            let delay = self.get_char_delay(c);
+            tokio::time::sleep(delay).await;
+        }
+
+        Ok(())
+    }
+
+    /// Presses and releases the key for `c`, with Shift if needed.
+    async fn type_key_char(&mut self, c: char) -> InputResult<()> {
+        // A held Shift already covers the character
+        let needs_shift = (c.is_uppercase() || is_shifted_character(c))
+            && !self.active_modifiers.contains(&Modifier::Shift);
+
+        if needs_shift {
+            self.send_key_event("Shift", &[], true).await?;
+        }
+
+        let key = get_key_for_char(c);
 
-            self.send_key_event(&key, &[], true).await?;
-            self.send_char(c).await?;
+        self.send_key_event(&key, &[], true).await?;
+        self.send_char(c).await?;
 
-            let hold = self.timing.get_click_delay();
-            tokio::time::sleep(hold).await;
+        let hold = self.timing.get_key_hold_delay();
+        tokio::time::sleep(hold).await;
+
+        self.send_key_event(&key, &[], false).await?;
+
+        if needs_shift {
+            self.send_key_event("Shift", &[], false).await?;
+        }
+
+        Ok(())
+    }
 
-            self.send_key_event(&key, &[], false).await?;
+    /// Picks a neighbouring key to mistype `c` as, at `typo_rate`.
+    ///
+    /// Draws nothing from the RNG with `typo_rate` 0.0, so seeded handlers
+    /// behave exactly as without typo simulation.
+    fn pick_typo(&mut self, c: char) -> Option<char> {
+        if self.config.typo_rate <= 0.0 {
+            return None;
+        }
+        let neighbors = qwerty_neighbors(c);
+        if neighbors.is_empty() || !self.rng.gen_bool(self.config.typo_rate.min(1.0)) {
+            return None;
+        }
+        neighbors.choose(&mut self.rng).copied()
+    }
 
-            if needs_shift {
-                self.send_key_event("Shift", &[], false).await?;
+    /// Types text that may need an IME, such as Chinese, Japanese or Korean.
+    ///
+    /// Runs of characters that [`requires_ime`] are typed as a composition
+    /// session: per character a raw key down with `VK_PROCESSKEY` (what
+    /// pages see as `keyCode 229`), an updated composition string and a key
+    /// up, then the whole run is committed at once. Everything else is
+    /// typed with [`send_text`](Self::send_text).
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to type into the CEF browser.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InputError::InvalidKey` if a non-IME character cannot be
+    /// mapped to a key.
+    pub async fn send_ime_text(&mut self, text: &str) -> InputResult<()> {
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            let composed = requires_ime(c);
+            let end = rest
+                .char_indices()
+                .find(|&(_, c)| requires_ime(c) != composed)
+                .map_or(rest.len(), |(i, _)| i);
+            let (run, tail) = rest.split_at(end);
+
+            if composed {
+                self.compose_text(run).await?;
+            } else {
+                self.send_text(run).await?;
             }
+            rest = tail;
+        }
 
-            // Inter-keystroke delay weighted by character typing frequency
-            let delay = self.get_char_delay(c);
-            tokio::time::sleep(delay).await;
+        Ok(())
+    }
+
+    /// Types `run` as one IME composition session and commits it.
+    async fn compose_text(&mut self, run: &str) -> InputResult<()> {
+        self.apply_post_navigation_dwell().await;
+        let modifiers = self.current_modifier_flags();
+
+        let mut composition = String::new();
+        for c in run.chars() {
+            let key_down = CefKeyEvent::new(CefKeyEventType::RawKeyDown, VK_PROCESSKEY)
+                .with_modifier(modifiers);
+            self.sender.send_key_event(&key_down);
+
+            composition.push(c);
+            let len = composition.encode_utf16().count() as u32;
+            self.sender.send_ime_set_composition(
+                &composition,
+                &[CompositionUnderline::thin(0, len)],
+                (len as i32, len as i32),
+            );
+
+            tokio::time::sleep(self.timing.get_key_hold_delay()).await;
+
+            let key_up = CefKeyEvent::new(CefKeyEventType::KeyUp, VK_PROCESSKEY)
+                .with_modifier(modifiers);
+            self.sender.send_key_event(&key_up);
+
+            tokio::time::sleep(self.timing.get_type_delay()).await;
         }
 
+        // Picking the conversion takes longer than typing a character
+        tokio::time::sleep(self.timing.get_reaction_delay()).await;
+        self.sender.send_ime_commit_text(&composition);
+
         Ok(())
     }
 
     /// Sends a key combination such as Ctrl+C, Alt+F4, or Shift+Tab.
     ///
     /// Presses modifiers in order, presses the main key, then releases
-    /// everything in reverse order with small inter-event delays.
+    /// everything in reverse order with small inter-event delays. Modifiers
+    /// that are already held are neither pressed nor released again.
     ///
     /// # Arguments
     ///
@@ -160,8 +291,14 @@ impl<S: CefEventSender> CefInputHandler<S> {
     ///
     /// Returns `InputError::InvalidKey` if `key` is not recognised.
     pub async fn send_key_combo(&mut self, key: &str, modifiers: &[Modifier]) -> InputResult<()> {
+        let modifiers: Vec<Modifier> = modifiers
+            .iter()
+            .filter(|m| !self.active_modifiers.contains(m))
+            .copied()
+            .collect();
+
         // Press modifiers
-        for modifier in modifiers {
+        for modifier in &modifiers {
             let mod_key = modifier_to_key_name(modifier);
             self.send_key_event(mod_key, &[], true).await?;
             let delay = Duration::from_millis(rand::random::<u64>() % 20 + 10);
@@ -169,10 +306,10 @@ impl<S: CefEventSender> CefInputHandler<S> {
         }
 
         // Press and release main key
-        self.send_key_event(key, modifiers, true).await?;
-        let hold = self.timing.get_click_delay();
+        self.send_key_event(key, &modifiers, true).await?;
+        let hold = self.timing.get_key_hold_delay();
         tokio::time::sleep(hold).await;
-        self.send_key_event(key, modifiers, false).await?;
+        self.send_key_event(key, &modifiers, false).await?;
 
         // Release modifiers in reverse order
         for modifier in modifiers.iter().rev() {
@@ -185,6 +322,44 @@ impl<S: CefEventSender> CefInputHandler<S> {
         Ok(())
     }
 
+    /// Presses `modifier` and keeps it down until [`release_modifier`] is
+    /// called.
+    ///
+    /// Subsequent key events carry the modifier's flag without pressing it
+    /// again, so e.g. holding Shift across several arrow keys produces a
+    /// single Shift press/release around them, like a real range selection.
+    /// Does nothing if the modifier is already held.
+    ///
+    /// [`release_modifier`]: Self::release_modifier
+    ///
+    /// # Arguments
+    ///
+    /// * `modifier` - The modifier key to hold down.
+    pub async fn hold_modifier(&mut self, modifier: Modifier) -> InputResult<()> {
+        if self.active_modifiers.contains(&modifier) {
+            return Ok(());
+        }
+        self.send_key_event(modifier_to_key_name(&modifier), &[], true)
+            .await
+    }
+
+    /// Releases a modifier previously pressed with [`hold_modifier`].
+    ///
+    /// Does nothing if the modifier is not held.
+    ///
+    /// [`hold_modifier`]: Self::hold_modifier
+    ///
+    /// # Arguments
+    ///
+    /// * `modifier` - The modifier key to release.
+    pub async fn release_modifier(&mut self, modifier: Modifier) -> InputResult<()> {
+        if !self.active_modifiers.contains(&modifier) {
+            return Ok(());
+        }
+        self.send_key_event(modifier_to_key_name(&modifier), &[], false)
+            .await
+    }
+
     // ========================================================================
     // Private Keyboard Helpers
     // ========================================================================
@@ -242,9 +417,14 @@ impl<S: CefEventSender> CefInputHandler<S> {
 
 #[cfg(test)]
 mod tests {
-    use crate::browser::cef_input::events::{CefKeyEvent, CefMouseButton, CefMouseEvent};
+    use crate::browser::cef_input::events::{
+        CefKeyEvent, CefKeyEventType, CefMouseButton, CefMouseEvent, CefTouchEvent,
+        CompositionUnderline, EVENTFLAG_SHIFT_DOWN,
+    };
+    use crate::browser::cef_input::keyboard::key_name_to_code;
     use crate::browser::cef_input::mouse::{CefEventSender, CefInputHandler};
     use crate::input::timing::HumanTiming;
+    use crate::input::Modifier;
 
     struct MockSender {
         keys: std::sync::Mutex<Vec<CefKeyEvent>>,
@@ -269,6 +449,9 @@ mod tests {
         fn send_key_event(&self, event: &CefKeyEvent) {
             self.keys.lock().unwrap().push(event.clone());
         }
+        fn send_ime_set_composition(&self, _: &str, _: &[CompositionUnderline], _: (i32, i32)) {}
+        fn send_ime_commit_text(&self, _: &str) {}
+        fn send_touch_event(&self, _: &CefTouchEvent) {}
     }
 
     #[tokio::test]
@@ -283,6 +466,21 @@ mod tests {
         assert!(!events.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_send_text_emits_surrogate_pair_for_emoji() {
+        let mut handler = CefInputHandler::new(MockSender::new(), HumanTiming::instant());
+
+        handler.send_text("\u{1F600}").await.unwrap();
+
+        let events = handler.sender.keys.lock().unwrap();
+        let chars: Vec<(CefKeyEventType, u16)> =
+            events.iter().map(|e| (e.event_type, e.character)).collect();
+        assert_eq!(
+            chars,
+            vec![(CefKeyEventType::Char, 0xD83D), (CefKeyEventType::Char, 0xDE00)]
+        );
+    }
+
     #[tokio::test]
     async fn test_send_key_event_invalid_key_returns_error() {
         let mut handler = CefInputHandler::new(MockSender::new(), HumanTiming::instant());
@@ -300,4 +498,115 @@ mod tests {
         let events = handler.sender.keys.lock().unwrap();
         assert_eq!(events.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_held_shift_brackets_key_events_once() {
+        let mut handler = CefInputHandler::new(MockSender::new(), HumanTiming::instant());
+
+        handler.hold_modifier(Modifier::Shift).await.unwrap();
+        // Holding twice must not press Shift again
+        handler.hold_modifier(Modifier::Shift).await.unwrap();
+        for _ in 0..3 {
+            handler
+                .send_key_combo("ArrowRight", &[Modifier::Shift])
+                .await
+                .unwrap();
+        }
+        handler.release_modifier(Modifier::Shift).await.unwrap();
+        assert!(handler.active_modifiers().is_empty());
+
+        let shift = key_name_to_code("Shift").unwrap();
+        let arrow = key_name_to_code("ArrowRight").unwrap();
+        let events = handler.sender.keys.lock().unwrap();
+        let sequence: Vec<(CefKeyEventType, i32)> = events
+            .iter()
+            .map(|e| (e.event_type, e.windows_key_code))
+            .collect();
+        let mut expected = vec![(CefKeyEventType::KeyDown, shift)];
+        for _ in 0..3 {
+            expected.push((CefKeyEventType::KeyDown, arrow));
+            expected.push((CefKeyEventType::KeyUp, arrow));
+        }
+        expected.push((CefKeyEventType::KeyUp, shift));
+        assert_eq!(sequence, expected);
+        assert!(events[1..7]
+            .iter()
+            .all(|e| e.modifiers & EVENTFLAG_SHIFT_DOWN != 0));
+    }
+
+    #[tokio::test]
+    async fn test_send_text_typos_are_corrected_with_backspace() {
+        use crate::browser::cef_input::mouse::CefInputConfig;
+
+        let backspace = key_name_to_code("Backspace").unwrap();
+        let typed_chars = |events: &[CefKeyEvent]| -> String {
+            events
+                .iter()
+                .filter(|e| e.event_type == CefKeyEventType::Char)
+                .map(|e| e.character as u8 as char)
+                .collect()
+        };
+
+        // Typos disabled: no corrections
+        let mut clean = CefInputHandler::new(MockSender::new(), HumanTiming::instant());
+        clean.send_text("the quick brown fox").await.unwrap();
+        let events = clean.sender.keys.lock().unwrap();
+        assert!(events.iter().all(|e| e.windows_key_code != backspace));
+        assert_eq!(typed_chars(&events), "the quick brown fox");
+
+        // Every letter mistyped once; spaces have no neighbours
+        let config = CefInputConfig {
+            typo_rate: 1.0,
+            rng_seed: Some(42),
+            ..Default::default()
+        };
+        let mut sloppy =
+            CefInputHandler::with_config(MockSender::new(), HumanTiming::instant(), config);
+        sloppy.send_text("the quick brown fox").await.unwrap();
+        let events = sloppy.sender.keys.lock().unwrap();
+        let backspaces = events
+            .iter()
+            .filter(|e| e.event_type == CefKeyEventType::KeyDown && e.windows_key_code == backspace)
+            .count();
+        assert_eq!(backspaces, 16);
+        assert_eq!(typed_chars(&events).len(), 19 + 16);
+    }
+
+    #[tokio::test]
+    async fn test_send_ime_text_composes_japanese() {
+        use crate::browser::cef_input::keyboard::VK_PROCESSKEY;
+        use crate::browser::cef_input::MockCefEventSender;
+
+        let mut handler = CefInputHandler::new(MockCefEventSender::new(), HumanTiming::instant());
+        handler.send_ime_text("こんにちは").await.unwrap();
+
+        let compositions = handler.sender.ime_compositions.lock().unwrap();
+        let texts: Vec<&str> = compositions.iter().map(|(t, _, _)| t.as_str()).collect();
+        assert_eq!(texts, ["こ", "こん", "こんに", "こんにち", "こんにちは"]);
+        let (_, underlines, selection) = &compositions[4];
+        assert_eq!(underlines.as_slice(), [CompositionUnderline::thin(0, 5)]);
+        assert_eq!(*selection, (5, 5));
+        assert_eq!(*handler.sender.ime_commits.lock().unwrap(), ["こんにちは"]);
+
+        let keys = handler.sender.key_events.lock().unwrap();
+        assert_eq!(keys.len(), 10);
+        assert!(keys.iter().all(|e| e.windows_key_code == VK_PROCESSKEY));
+        assert_eq!(keys[0].event_type, CefKeyEventType::RawKeyDown);
+        assert!(keys.iter().all(|e| e.event_type != CefKeyEventType::Char));
+    }
+
+    #[tokio::test]
+    async fn test_send_ime_text_types_ascii_directly() {
+        use crate::browser::cef_input::MockCefEventSender;
+
+        let mut handler = CefInputHandler::new(MockCefEventSender::new(), HumanTiming::instant());
+        handler.send_ime_text("abc 日本").await.unwrap();
+
+        assert_eq!(handler.sender.ime_compositions.lock().unwrap().len(), 2);
+        assert_eq!(*handler.sender.ime_commits.lock().unwrap(), ["日本"]);
+        let keys = handler.sender.key_events.lock().unwrap();
+        assert!(keys
+            .iter()
+            .any(|e| e.event_type == CefKeyEventType::Char && e.character == 'a' as u16));
+    }
 }
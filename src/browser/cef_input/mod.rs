@@ -9,11 +9,17 @@
 //! - [`keyboard`] - Platform key code tables (Windows VK_*, Linux XKB) and conversion utilities
 //! - [`mouse`] - CefInputHandler struct, CefEventSender trait, and all mouse input methods
 //! - [`keyboard_handler`] - Keyboard input methods on CefInputHandler (text, combos, key events)
+//! - [`touch`] - Touch input methods on CefInputHandler (single touches, pinch zoom)
+//! - [`file_upload`] - File input uploads on CefInputHandler without the OS file chooser
 //!
 //! # Features
 //!
 //! - Mouse input with human-like Bezier curve movement paths and micro-jitter
+//! - Scrolling split into wheel events along device-specific easing curves
 //! - Keyboard input with realistic per-character typing delays
+//! - IME composition sessions for Chinese, Japanese and Korean text
+//! - Multi-touch gestures such as two-finger pinch zoom
+//! - File uploads through `<input type="file">` via an injected `DataTransfer`
 //! - Platform-specific key code handling (Windows VK_* / Linux XKB keysyms)
 //! - Integration with the input module's timing and Bezier utilities
 //!
@@ -39,13 +45,22 @@
 //! ```
 
 pub mod events;
+pub mod file_upload;
 pub mod keyboard;
 pub mod keyboard_handler;
 pub mod mouse;
+pub mod touch;
 
 // Re-export all public types for backward-compatible access via `cef_input::*`
-pub use events::{CefKeyEvent, CefKeyEventType, CefMouseButton, CefMouseEvent};
-pub use mouse::{CefEventSender, CefInputConfig, CefInputHandler};
+pub use events::{
+    CefKeyEvent, CefKeyEventType, CefMouseButton, CefMouseEvent, CefTouchEvent,
+    CefTouchEventType, CompositionUnderline,
+};
+pub use file_upload::{file_input_script, UploadFile};
+pub use mouse::{
+    CefEventSender, CefInputConfig, CefInputHandler, PostNavigationDwell, RealismWarning,
+    ScrollProfile,
+};
 
 // Re-export key code tables so downstream code can use `cef_input::key_codes::VK_*`
 pub use keyboard::key_codes;
@@ -64,6 +79,9 @@ pub struct MockCefEventSender {
     pub mouse_clicks: std::sync::Mutex<Vec<(CefMouseEvent, CefMouseButton, bool, i32)>>,
     pub mouse_wheels: std::sync::Mutex<Vec<(CefMouseEvent, i32, i32)>>,
     pub key_events: std::sync::Mutex<Vec<CefKeyEvent>>,
+    pub ime_compositions: std::sync::Mutex<Vec<(String, Vec<CompositionUnderline>, (i32, i32))>>,
+    pub ime_commits: std::sync::Mutex<Vec<String>>,
+    pub touch_events: std::sync::Mutex<Vec<CefTouchEvent>>,
 }
 
 #[cfg(test)]
@@ -75,6 +93,9 @@ impl MockCefEventSender {
             mouse_clicks: std::sync::Mutex::new(Vec::new()),
             mouse_wheels: std::sync::Mutex::new(Vec::new()),
             key_events: std::sync::Mutex::new(Vec::new()),
+            ime_compositions: std::sync::Mutex::new(Vec::new()),
+            ime_commits: std::sync::Mutex::new(Vec::new()),
+            touch_events: std::sync::Mutex::new(Vec::new()),
         }
     }
 }
@@ -105,4 +126,24 @@ impl CefEventSender for MockCefEventSender {
     fn send_key_event(&self, event: &CefKeyEvent) {
         self.key_events.lock().unwrap().push(event.clone());
     }
+
+    fn send_ime_set_composition(
+        &self,
+        text: &str,
+        underlines: &[CompositionUnderline],
+        selection_range: (i32, i32),
+    ) {
+        self.ime_compositions
+            .lock()
+            .unwrap()
+            .push((text.to_string(), underlines.to_vec(), selection_range));
+    }
+
+    fn send_ime_commit_text(&self, text: &str) {
+        self.ime_commits.lock().unwrap().push(text.to_string());
+    }
+
+    fn send_touch_event(&self, event: &CefTouchEvent) {
+        self.touch_events.lock().unwrap().push(*event);
+    }
 }
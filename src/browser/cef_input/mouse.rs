@@ -3,19 +3,29 @@
 //! This module provides the `CefInputHandler` struct and all mouse-related
 //! input methods: move, click, double-click, drag, scroll, mouse-down and
 //! mouse-up. Movement paths are generated as Bezier curves with optional
-//! micro-jitter to simulate realistic hand tremor.
+//! micro-jitter to simulate realistic hand tremor; scrolls are split into
+//! wheel events along the easing curve of a `ScrollProfile`.
 //!
 //! The `CefEventSender` trait decouples the handler from the concrete CEF
 //! browser instance, enabling testing via mock implementations.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
 use crate::input::bezier::{generate_human_path, Point};
-use crate::input::timing::HumanTiming;
+use crate::input::timing::{HumanTiming, TimingProfile};
 use crate::input::{InputError, InputResult, Modifier};
 
-use super::events::{CefKeyEvent, CefMouseButton, CefMouseEvent, EVENTFLAG_NONE};
+use super::events::{
+    CefKeyEvent, CefMouseButton, CefMouseEvent, CefTouchEvent, CompositionUnderline,
+    EVENTFLAG_NONE,
+};
 use super::keyboard::modifier_to_event_flag;
 
 // ============================================================================
@@ -44,6 +54,21 @@ pub trait CefEventSender: Send + Sync {
 
     /// Delivers a keyboard event to the CEF browser view.
     fn send_key_event(&self, event: &CefKeyEvent);
+
+    /// Replaces the IME composition text, with the caret or selection at
+    /// `selection_range` (UTF-16 offsets into `text`).
+    fn send_ime_set_composition(
+        &self,
+        text: &str,
+        underlines: &[CompositionUnderline],
+        selection_range: (i32, i32),
+    );
+
+    /// Commits `text` through the IME, ending the composition.
+    fn send_ime_commit_text(&self, text: &str);
+
+    /// Delivers a touch event for one finger to the CEF browser view.
+    fn send_touch_event(&self, event: &CefTouchEvent);
 }
 
 // ============================================================================
@@ -53,7 +78,8 @@ pub trait CefEventSender: Send + Sync {
 /// Configuration controlling the behaviour of CEF input simulation.
 ///
 /// Controls path point density for mouse movement, jitter intensity for
-/// hand-tremor simulation, and optional view bounds for coordinate validation.
+/// hand-tremor simulation, optional view bounds for coordinate validation,
+/// and the orientation pause taken before acting on a freshly loaded page.
 #[derive(Debug, Clone)]
 pub struct CefInputConfig {
     /// Minimum number of intermediate points in a mouse movement path.
@@ -66,6 +92,17 @@ pub struct CefInputConfig {
     pub jitter_intensity: f64,
     /// Optional view bounds (width, height) for coordinate range validation.
     pub view_bounds: Option<(i32, i32)>,
+    /// Pause before the first input action after a page load, drawn
+    /// uniformly from this range. `None` disables the dwell.
+    pub post_navigation_dwell: Option<RangeInclusive<Duration>>,
+    /// Seed for the handler's RNG; `None` seeds from OS entropy.
+    pub rng_seed: Option<u64>,
+    /// Pressure (0.0 - 1.0) reported by touch events.
+    pub touch_pressure: f32,
+    /// Chance (0.0 - 1.0) per letter or digit that `send_text` first types
+    /// a neighbouring key and corrects it with Backspace. 0.0 types
+    /// flawlessly.
+    pub typo_rate: f64,
 }
 
 impl Default for CefInputConfig {
@@ -76,10 +113,293 @@ impl Default for CefInputConfig {
             add_jitter: true,
             jitter_intensity: 0.3,
             view_bounds: None,
+            post_navigation_dwell: None,
+            rng_seed: None,
+            touch_pressure: 0.5,
+            typo_rate: 0.0,
         }
     }
 }
 
+/// Orientation pause owed before the first input action on a loaded page.
+///
+/// Armed by [`page_loaded`](Self::page_loaded) and taken by the next input
+/// action, so the pause applies once per page. Used by [`CefInputHandler`]
+/// and by the CEF engine's own input path, where the load handler arms it.
+#[derive(Debug)]
+pub struct PostNavigationDwell {
+    range: Option<RangeInclusive<Duration>>,
+    pending: AtomicBool,
+}
+
+impl PostNavigationDwell {
+    /// Creates an unarmed dwell drawn from `range`; `None` never dwells.
+    pub fn new(range: Option<RangeInclusive<Duration>>) -> Self {
+        Self {
+            range,
+            pending: AtomicBool::new(false),
+        }
+    }
+
+    /// Arms the dwell for a newly loaded page; re-arms it if still pending.
+    pub fn page_loaded(&self) {
+        self.pending.store(self.range.is_some(), Ordering::SeqCst);
+    }
+
+    /// Returns whether the dwell is armed.
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::SeqCst)
+    }
+
+    /// Disarms the dwell, returning its duration drawn from `rng` if it was armed.
+    pub fn take(&self, rng: &mut impl Rng) -> Option<Duration> {
+        if !self.pending.swap(false, Ordering::SeqCst) {
+            return None;
+        }
+        let range = self.range.clone()?;
+        Some(if range.start() >= range.end() {
+            *range.start()
+        } else {
+            rng.gen_range(range)
+        })
+    }
+}
+
+/// Shortest plausible delay between two input events in milliseconds.
+///
+/// Even fast, practiced users rarely produce events less than ~20ms apart;
+/// timing floors below this read as scripted.
+pub const HUMAN_MIN_EVENT_DELAY_MS: u64 = 20;
+
+/// Largest plausible distance in pixels between two consecutive mouse moves.
+///
+/// At common mouse polling rates a quick flick still reports points well
+/// under this distance apart; larger steps look like a teleporting cursor.
+pub const HUMAN_MAX_MOVE_STEP_PX: f64 = 100.0;
+
+/// Movement distance used for path density checks when no view bounds are
+/// configured (the diagonal of a 1920x1080 viewport).
+const DEFAULT_MAX_MOVE_DISTANCE_PX: f64 = 2203.0;
+
+/// A setting that makes simulated input faster or more regular than a human.
+///
+/// Returned by [`CefInputConfig::validate_realism`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RealismWarning {
+    /// The timing uses [`HumanTiming::instant`], which is meant for tests.
+    InstantTiming,
+    /// Inter-event delays can drop below [`HUMAN_MIN_EVENT_DELAY_MS`].
+    DelayTooShort { min_delay_ms: u64 },
+    /// Delays are not randomized.
+    NoTimingVariance,
+    /// Mouse paths are perfectly smooth curves without hand tremor.
+    NoJitter,
+    /// Long movements are split into steps larger than [`HUMAN_MAX_MOVE_STEP_PX`].
+    SparsePath {
+        max_path_points: usize,
+        distance_px: f64,
+    },
+    /// Under CPU throttling, inter-event delays can drop below the human
+    /// floor scaled by the throttling rate.
+    ThrottledDelayTooShort {
+        min_delay_ms: u64,
+        cpu_throttling_rate: f64,
+    },
+}
+
+impl fmt::Display for RealismWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RealismWarning::InstantTiming => {
+                write!(f, "instant timing profile is meant for tests, not production")
+            }
+            RealismWarning::DelayTooShort { min_delay_ms } => write!(
+                f,
+                "minimum delay of {}ms is below the human floor of {}ms",
+                min_delay_ms, HUMAN_MIN_EVENT_DELAY_MS
+            ),
+            RealismWarning::NoTimingVariance => write!(f, "delays have no variance"),
+            RealismWarning::NoJitter => write!(f, "mouse paths have no jitter"),
+            RealismWarning::SparsePath {
+                max_path_points,
+                distance_px,
+            } => write!(
+                f,
+                "{} path points for a {:.0}px movement gives {:.0}px steps (max {:.0}px)",
+                max_path_points,
+                distance_px,
+                distance_px / *max_path_points.max(&1) as f64,
+                HUMAN_MAX_MOVE_STEP_PX
+            ),
+            RealismWarning::ThrottledDelayTooShort {
+                min_delay_ms,
+                cpu_throttling_rate,
+            } => write!(
+                f,
+                "minimum delay of {}ms is below {:.0}ms, the human floor at {}x CPU throttling",
+                min_delay_ms,
+                HUMAN_MIN_EVENT_DELAY_MS as f64 * cpu_throttling_rate,
+                cpu_throttling_rate
+            ),
+        }
+    }
+}
+
+impl CefInputConfig {
+    /// Checks this configuration and `timing` for super-human behaviour.
+    ///
+    /// Flags zero or near-zero delays, missing randomization, disabled
+    /// jitter and path densities whose steps would be too large for the
+    /// longest possible movement (the view diagonal). An empty result means
+    /// nothing obviously non-human was found.
+    pub fn validate_realism(&self, timing: &HumanTiming) -> Vec<RealismWarning> {
+        let mut warnings = Vec::new();
+
+        if timing.profile == TimingProfile::Instant {
+            warnings.push(RealismWarning::InstantTiming);
+        }
+        if timing.min_delay_ms < HUMAN_MIN_EVENT_DELAY_MS {
+            warnings.push(RealismWarning::DelayTooShort {
+                min_delay_ms: timing.min_delay_ms,
+            });
+        }
+        if timing.variance <= 0.0 {
+            warnings.push(RealismWarning::NoTimingVariance);
+        }
+        if !self.add_jitter || self.jitter_intensity <= 0.0 {
+            warnings.push(RealismWarning::NoJitter);
+        }
+
+        let distance_px = self
+            .view_bounds
+            .map(|(w, h)| (w as f64).hypot(h as f64))
+            .unwrap_or(DEFAULT_MAX_MOVE_DISTANCE_PX);
+        if distance_px / self.max_path_points.max(1) as f64 > HUMAN_MAX_MOVE_STEP_PX {
+            warnings.push(RealismWarning::SparsePath {
+                max_path_points: self.max_path_points,
+                distance_px,
+            });
+        }
+
+        warnings
+    }
+
+    /// Like [`validate_realism`](Self::validate_realism), for a page running
+    /// under CPU throttling at `cpu_throttling_rate`.
+    ///
+    /// A throttled page handles each event that many times slower. Events
+    /// sent closer together than the scaled human floor queue up and reach
+    /// page listeners back to back, which looks scripted.
+    pub fn validate_realism_under_throttling(
+        &self,
+        timing: &HumanTiming,
+        cpu_throttling_rate: f64,
+    ) -> Vec<RealismWarning> {
+        let mut warnings = self.validate_realism(timing);
+        let scaled_floor = HUMAN_MIN_EVENT_DELAY_MS as f64 * cpu_throttling_rate;
+        if cpu_throttling_rate > 1.0 && (timing.min_delay_ms as f64) < scaled_floor {
+            warnings.push(RealismWarning::ThrottledDelayTooShort {
+                min_delay_ms: timing.min_delay_ms,
+                cpu_throttling_rate,
+            });
+        }
+        warnings
+    }
+}
+
+// ============================================================================
+// Scroll Profiles
+// ============================================================================
+
+/// Pixels scrolled per scroll unit passed to [`CefInputHandler::send_scroll`].
+const SCROLL_UNIT_PX: f64 = 40.0;
+
+/// Input device whose scrolling `send_scroll` imitates.
+///
+/// Each profile splits a scroll into wheel events along its own easing
+/// curve: the progress after event `i` of `n` is `ease(i / n)`, so the
+/// per-event delta follows the curve's slope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrollProfile {
+    /// Many small deltas, fast at the start and decaying with momentum
+    /// (easeOutQuart).
+    Trackpad,
+    /// Few coarse notches that speed up and slow down again
+    /// (easeInOutQuart).
+    #[default]
+    MouseWheel,
+    /// A finger swipe followed by a fling that slows down (easeOutCubic).
+    TouchScreen,
+}
+
+impl ScrollProfile {
+    /// Scroll progress (0.0 to 1.0) at time fraction `t` (0.0 to 1.0).
+    pub fn ease(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            ScrollProfile::Trackpad => 1.0 - (1.0 - t).powi(4),
+            ScrollProfile::MouseWheel => {
+                if t < 0.5 {
+                    8.0 * t.powi(4)
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(4) / 2.0
+                }
+            }
+            ScrollProfile::TouchScreen => 1.0 - (1.0 - t).powi(3),
+        }
+    }
+
+    /// Number of wheel events for a scroll of `distance_px` pixels.
+    pub fn event_count(self, distance_px: f64) -> usize {
+        let (px_per_event, min, max) = match self {
+            ScrollProfile::Trackpad => (8.0, 4, 120),
+            ScrollProfile::MouseWheel => (SCROLL_UNIT_PX, 1, 60),
+            ScrollProfile::TouchScreen => (15.0, 3, 90),
+        };
+        ((distance_px / px_per_event).ceil() as usize).clamp(min, max)
+    }
+
+    /// Delay before the next wheel event, drawn from `timing`.
+    ///
+    /// Trackpads and touch screens report at display rate; wheel notches
+    /// are further apart.
+    fn event_delay(self, timing: &HumanTiming) -> Duration {
+        match self {
+            ScrollProfile::Trackpad | ScrollProfile::TouchScreen => timing.get_move_delay(),
+            ScrollProfile::MouseWheel => timing.get_move_delay() * 3,
+        }
+    }
+}
+
+/// Splits a scroll of `(total_dx, total_dy)` pixels into per-event deltas
+/// along the easing curve of `profile`.
+///
+/// Deltas sum up to the totals exactly; events that would round to no
+/// movement are dropped.
+fn scroll_deltas(total_dx: i32, total_dy: i32, profile: ScrollProfile) -> Vec<(i32, i32)> {
+    let distance = (total_dx as f64).hypot(total_dy as f64);
+    let events = profile.event_count(distance);
+
+    let mut deltas = Vec::with_capacity(events);
+    let (mut prev_x, mut prev_y) = (0, 0);
+    for i in 1..=events {
+        let progress = profile.ease(i as f64 / events as f64);
+        let (x, y) = if i == events {
+            (total_dx, total_dy)
+        } else {
+            (
+                (total_dx as f64 * progress).round() as i32,
+                (total_dy as f64 * progress).round() as i32,
+            )
+        };
+        if (x, y) != (prev_x, prev_y) {
+            deltas.push((x - prev_x, y - prev_y));
+        }
+        (prev_x, prev_y) = (x, y);
+    }
+    deltas
+}
+
 // ============================================================================
 // CEF Input Handler
 // ============================================================================
@@ -102,6 +422,12 @@ pub struct CefInputHandler<S: CefEventSender> {
     pub(super) pressed_buttons: HashSet<CefMouseButton>,
     /// Set of modifier keys currently held down.
     pub(super) active_modifiers: HashSet<Modifier>,
+    /// Position of each finger currently touching the screen, by touch id.
+    pub(super) active_touches: HashMap<i32, Point>,
+    /// Post-navigation dwell owed for the current page.
+    dwell: PostNavigationDwell,
+    /// RNG for handler-level randomness (seeded via `CefInputConfig::rng_seed`).
+    pub(super) rng: StdRng,
 }
 
 impl<S: CefEventSender> CefInputHandler<S> {
@@ -112,18 +438,16 @@ impl<S: CefEventSender> CefInputHandler<S> {
     /// * `sender` - Event delivery channel to the CEF browser instance.
     /// * `timing` - Human timing profile for inter-event delays.
     pub fn new(sender: S, timing: HumanTiming) -> Self {
-        Self {
-            sender,
-            current_position: Point::new(0.0, 0.0),
-            config: CefInputConfig::default(),
-            timing,
-            pressed_buttons: HashSet::new(),
-            active_modifiers: HashSet::new(),
-        }
+        Self::with_config(sender, timing, CefInputConfig::default())
     }
 
     /// Creates a new handler with a custom `CefInputConfig`.
     pub fn with_config(sender: S, timing: HumanTiming, config: CefInputConfig) -> Self {
+        let rng = match config.rng_seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let dwell = PostNavigationDwell::new(config.post_navigation_dwell.clone());
         Self {
             sender,
             current_position: Point::new(0.0, 0.0),
@@ -131,9 +455,35 @@ impl<S: CefEventSender> CefInputHandler<S> {
             timing,
             pressed_buttons: HashSet::new(),
             active_modifiers: HashSet::new(),
+            active_touches: HashMap::new(),
+            dwell,
+            rng,
         }
     }
 
+    /// Notifies the handler that a new page has loaded.
+    ///
+    /// Arms the post-navigation dwell: the next input action waits for a
+    /// duration drawn from `CefInputConfig::post_navigation_dwell` first.
+    /// The dwell applies once per page; calling this again re-arms it.
+    pub fn page_loaded(&mut self) {
+        self.dwell.page_loaded();
+    }
+
+    /// Returns whether the post-navigation dwell is still pending.
+    pub fn dwell_pending(&self) -> bool {
+        self.dwell.is_pending()
+    }
+
+    /// Sleeps for the post-navigation dwell if one is pending, then clears it.
+    ///
+    /// Returns the applied duration.
+    pub(super) async fn apply_post_navigation_dwell(&mut self) -> Option<Duration> {
+        let dwell = self.dwell.take(&mut self.rng)?;
+        tokio::time::sleep(dwell).await;
+        Some(dwell)
+    }
+
     /// Returns the current mouse cursor position.
     pub fn position(&self) -> Point {
         self.current_position
@@ -199,7 +549,28 @@ impl<S: CefEventSender> CefInputHandler<S> {
     ///
     /// Returns `InputError::OutOfBounds` if the target is outside view bounds.
     pub async fn send_mouse_move(&mut self, x: f64, y: f64) -> InputResult<Vec<Point>> {
+        self.send_mouse_move_to_target(x, y, None).await
+    }
+
+    /// Like [`send_mouse_move`](Self::send_mouse_move), but aimed at a
+    /// target `target_width` pixels wide.
+    ///
+    /// With a width the whole movement takes the Fitts's law duration of
+    /// [`HumanTiming::fitts_duration`], spread evenly over the path, so a
+    /// small far button takes longer to reach than a big near one. Without
+    /// one every point waits the timing's per-point move delay.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InputError::OutOfBounds` if the target is outside view bounds.
+    pub async fn send_mouse_move_to_target(
+        &mut self,
+        x: f64,
+        y: f64,
+        target_width: Option<f64>,
+    ) -> InputResult<Vec<Point>> {
         self.validate_position(x, y)?;
+        self.apply_post_navigation_dwell().await;
 
         let target = Point::new(x, y);
         let distance = self.current_position.distance_to(&target);
@@ -216,8 +587,12 @@ impl<S: CefEventSender> CefInputHandler<S> {
             add_jitter_to_path(&mut path, self.config.jitter_intensity);
         }
 
+        let step_delay = target_width.map(|width| {
+            self.timing.fitts_duration(distance, width) / path.len().max(1) as u32
+        });
+
         for point in &path {
-            let delay = self.timing.get_move_delay();
+            let delay = step_delay.unwrap_or_else(|| self.timing.get_move_delay());
             tokio::time::sleep(delay).await;
 
             self.current_position = *point;
@@ -273,6 +648,7 @@ impl<S: CefEventSender> CefInputHandler<S> {
         button: CefMouseButton,
     ) -> InputResult<()> {
         self.validate_position(x, y)?;
+        self.apply_post_navigation_dwell().await;
 
         let delay = Duration::from_millis(rand::random::<u64>() % 10 + 2);
         tokio::time::sleep(delay).await;
@@ -308,10 +684,13 @@ impl<S: CefEventSender> CefInputHandler<S> {
         Ok(())
     }
 
-    /// Sends a smooth scroll wheel event at `(x, y)` split into multiple steps.
+    /// Sends a smooth scroll at `(x, y)` as a sequence of wheel events.
     ///
     /// `delta_x` and `delta_y` are in scroll units (1 unit ≈ 40 pixels).
     /// Positive `delta_y` scrolls down, positive `delta_x` scrolls right.
+    /// The scroll is split along the easing curve of `profile` (default
+    /// [`ScrollProfile::MouseWheel`]), with inter-event delays drawn from
+    /// the handler's [`HumanTiming`].
     ///
     /// # Errors
     ///
@@ -322,31 +701,17 @@ impl<S: CefEventSender> CefInputHandler<S> {
         y: f64,
         delta_x: f64,
         delta_y: f64,
+        profile: Option<ScrollProfile>,
     ) -> InputResult<()> {
         self.validate_position(x, y)?;
+        self.apply_post_navigation_dwell().await;
 
-        let scroll_multiplier = 40.0;
-        let total_dx = (delta_x * scroll_multiplier).round() as i32;
-        let total_dy = (delta_y * scroll_multiplier).round() as i32;
-
-        let steps = ((delta_x.abs() + delta_y.abs()).ceil() as usize).max(1);
-        let step_dx = total_dx / steps as i32;
-        let step_dy = total_dy / steps as i32;
+        let profile = profile.unwrap_or_default();
+        let total_dx = (delta_x * SCROLL_UNIT_PX).round() as i32;
+        let total_dy = (delta_y * SCROLL_UNIT_PX).round() as i32;
 
-        for i in 0..steps {
-            let delay = Duration::from_millis(rand::random::<u64>() % 30 + 10);
-            tokio::time::sleep(delay).await;
-
-            let dx = if i == steps - 1 {
-                total_dx - step_dx * (steps as i32 - 1)
-            } else {
-                step_dx
-            };
-            let dy = if i == steps - 1 {
-                total_dy - step_dy * (steps as i32 - 1)
-            } else {
-                step_dy
-            };
+        for (dx, dy) in scroll_deltas(total_dx, total_dy, profile) {
+            tokio::time::sleep(profile.event_delay(&self.timing)).await;
 
             let event = self.create_mouse_event(x.round() as i32, y.round() as i32);
             self.sender.send_mouse_wheel_event(&event, dx, dy);
@@ -495,6 +860,47 @@ mod tests {
         fn send_key_event(&self, event: &CefKeyEvent) {
             self.keys.lock().unwrap().push(event.clone());
         }
+        fn send_ime_set_composition(&self, _: &str, _: &[CompositionUnderline], _: (i32, i32)) {}
+        fn send_ime_commit_text(&self, _: &str) {}
+        fn send_touch_event(&self, _: &CefTouchEvent) {}
+    }
+
+    #[test]
+    fn test_validate_realism_flags_instant_timing_and_missing_jitter() {
+        let config = CefInputConfig::default();
+        assert!(config.validate_realism(&HumanTiming::normal()).is_empty());
+
+        let warnings = config.validate_realism(&HumanTiming::instant());
+        assert!(warnings.contains(&RealismWarning::InstantTiming));
+        assert!(warnings.contains(&RealismWarning::DelayTooShort { min_delay_ms: 1 }));
+
+        let no_jitter = CefInputConfig {
+            add_jitter: false,
+            max_path_points: 10,
+            view_bounds: Some((1280, 720)),
+            ..Default::default()
+        };
+        let warnings = no_jitter.validate_realism(&HumanTiming::normal());
+        assert!(warnings.contains(&RealismWarning::NoJitter));
+        assert!(warnings
+            .iter()
+            .any(|w| matches!(w, RealismWarning::SparsePath { max_path_points: 10, .. })));
+    }
+
+    #[test]
+    fn test_validate_realism_scales_delay_floor_with_cpu_throttling() {
+        let config = CefInputConfig::default();
+        let timing = HumanTiming::normal();
+        assert!(config.validate_realism_under_throttling(&timing, 2.0).is_empty());
+
+        let warnings = config.validate_realism_under_throttling(&timing, 4.0);
+        assert_eq!(
+            warnings,
+            vec![RealismWarning::ThrottledDelayTooShort {
+                min_delay_ms: 50,
+                cpu_throttling_rate: 4.0,
+            }]
+        );
     }
 
     #[test]
@@ -528,6 +934,117 @@ mod tests {
         assert_eq!(last.y, 100);
     }
 
+    #[tokio::test]
+    async fn test_send_mouse_move_to_target_takes_fitts_duration() {
+        let timing = HumanTiming::instant();
+        let expected = timing.fitts_duration(600.0, 10.0);
+        let mut handler = CefInputHandler::new(MockSender::new(), timing);
+
+        let start = std::time::Instant::now();
+        handler.send_mouse_move_to_target(600.0, 0.0, Some(10.0)).await.unwrap();
+        assert!(start.elapsed() + Duration::from_millis(1) >= expected);
+
+        let moves = handler.sender.moves.lock().unwrap();
+        assert_eq!((moves.last().unwrap().x, moves.last().unwrap().y), (600, 0));
+    }
+
+    #[tokio::test]
+    async fn test_post_navigation_dwell_applies_once_per_page() {
+        let dwell = Duration::from_millis(300);
+        let config = CefInputConfig {
+            post_navigation_dwell: Some(dwell..=dwell),
+            rng_seed: Some(7),
+            ..CefInputConfig::default()
+        };
+        let mut handler =
+            CefInputHandler::with_config(MockSender::new(), HumanTiming::instant(), config);
+
+        handler.page_loaded();
+        assert!(handler.dwell_pending());
+
+        let start = std::time::Instant::now();
+        handler.send_mouse_click(100.0, 100.0, CefMouseButton::Left).await.unwrap();
+        assert!(start.elapsed() >= dwell);
+        assert!(!handler.dwell_pending());
+
+        let start = std::time::Instant::now();
+        handler.send_mouse_click(120.0, 100.0, CefMouseButton::Left).await.unwrap();
+        assert!(start.elapsed() < dwell);
+
+        // The next page load re-arms the dwell
+        handler.page_loaded();
+        assert!(handler.dwell_pending());
+    }
+
+    #[test]
+    fn test_post_navigation_dwell_draws_from_seeded_rng() {
+        let range = Duration::from_millis(200)..=Duration::from_millis(800);
+        let draw = |seed| {
+            let dwell = PostNavigationDwell::new(Some(range.clone()));
+            dwell.page_loaded();
+            dwell.take(&mut StdRng::seed_from_u64(seed))
+        };
+        let first = draw(42).unwrap();
+        assert!(range.contains(&first));
+        assert_eq!(draw(42), Some(first));
+
+        // Unarmed or disabled dwells never pause
+        let dwell = PostNavigationDwell::new(Some(range.clone()));
+        assert_eq!(dwell.take(&mut StdRng::seed_from_u64(1)), None);
+        let disabled = PostNavigationDwell::new(None);
+        disabled.page_loaded();
+        assert!(!disabled.is_pending());
+    }
+
+    #[test]
+    fn test_scroll_deltas_sum_to_total() {
+        for profile in [
+            ScrollProfile::Trackpad,
+            ScrollProfile::MouseWheel,
+            ScrollProfile::TouchScreen,
+        ] {
+            let deltas = scroll_deltas(-35, 413, profile);
+            assert_eq!(deltas.iter().map(|d| d.0).sum::<i32>(), -35);
+            assert_eq!(deltas.iter().map(|d| d.1).sum::<i32>(), 413);
+            assert!(deltas.iter().all(|&d| d != (0, 0)));
+            assert!((profile.ease(0.0)).abs() < 1e-9);
+            assert!((profile.ease(1.0) - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trackpad_scroll_uses_more_smaller_events() {
+        use crate::browser::cef_input::MockCefEventSender;
+
+        let wheel_deltas = |handler: &CefInputHandler<MockCefEventSender>| {
+            handler
+                .sender
+                .mouse_wheels
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|&(_, _, dy)| dy)
+                .collect::<Vec<_>>()
+        };
+
+        let mut wheel = CefInputHandler::new(MockCefEventSender::new(), HumanTiming::instant());
+        wheel.send_scroll(100.0, 100.0, 0.0, 10.0, None).await.unwrap();
+        let wheel_deltas_y = wheel_deltas(&wheel);
+
+        let mut trackpad =
+            CefInputHandler::new(MockCefEventSender::new(), HumanTiming::instant());
+        trackpad
+            .send_scroll(100.0, 100.0, 0.0, 10.0, Some(ScrollProfile::Trackpad))
+            .await
+            .unwrap();
+        let trackpad_deltas_y = wheel_deltas(&trackpad);
+
+        assert_eq!(wheel_deltas_y.iter().sum::<i32>(), 400);
+        assert_eq!(trackpad_deltas_y.iter().sum::<i32>(), 400);
+        assert!(trackpad_deltas_y.len() > wheel_deltas_y.len());
+        assert!(trackpad_deltas_y.iter().max() < wheel_deltas_y.iter().max());
+    }
+
     #[tokio::test]
     async fn test_send_mouse_click_generates_down_and_up() {
         let mut handler = CefInputHandler::new(MockSender::new(), HumanTiming::instant());
@@ -0,0 +1,316 @@
+//! CEF touch input methods on `CefInputHandler`.
+//!
+//! This module extends `CefInputHandler` with touch screen behaviour for
+//! mobile emulation: single-finger press, move, release and cancel events,
+//! and two-finger pinch-to-zoom gestures built on top of them.
+//!
+//! Each finger is identified by a touch id and tracked from press to release,
+//! so move and release events always report the finger's last position.
+
+use std::f64::consts::PI;
+use std::time::Duration;
+
+use crate::input::bezier::Point;
+use crate::input::{InputError, InputResult};
+
+use super::events::{CefTouchEvent, CefTouchEventType};
+use super::mouse::{CefEventSender, CefInputHandler, ScrollProfile};
+
+/// Finger distance in pixels at the narrow end of a pinch gesture.
+const PINCH_BASE_DISTANCE_PX: f64 = 100.0;
+
+/// Distance in pixels each finger travels per touch move event.
+const PINCH_STEP_PX: f64 = 8.0;
+
+/// Bounds for the number of move events in a pinch gesture.
+const PINCH_MIN_STEPS: usize = 6;
+const PINCH_MAX_STEPS: usize = 40;
+
+impl<S: CefEventSender> CefInputHandler<S> {
+    // ========================================================================
+    // Touch Input Methods
+    // ========================================================================
+
+    /// Places finger `id` on the screen at `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InputError::OutOfBounds` if the position is outside view
+    /// bounds, or `InputError::PlatformError` if finger `id` is already down.
+    pub async fn send_touch_start(&mut self, id: i32, x: f64, y: f64) -> InputResult<()> {
+        self.validate_position(x, y)?;
+        if self.active_touches.contains_key(&id) {
+            return Err(InputError::PlatformError {
+                message: format!("Touch {} is already active", id),
+            });
+        }
+        self.apply_post_navigation_dwell().await;
+
+        self.active_touches.insert(id, Point::new(x, y));
+        self.dispatch_touch(id, CefTouchEventType::Pressed, x, y);
+        Ok(())
+    }
+
+    /// Moves the active finger `id` to `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InputError::OutOfBounds` if the position is outside view
+    /// bounds, or `InputError::PlatformError` if finger `id` is not down.
+    pub async fn send_touch_move(&mut self, id: i32, x: f64, y: f64) -> InputResult<()> {
+        self.validate_position(x, y)?;
+        let position = self.active_touch(id)?;
+
+        *position = Point::new(x, y);
+        self.dispatch_touch(id, CefTouchEventType::Moved, x, y);
+        Ok(())
+    }
+
+    /// Lifts finger `id` at its last position.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InputError::PlatformError` if finger `id` is not down.
+    pub async fn send_touch_end(&mut self, id: i32) -> InputResult<()> {
+        self.finish_touch(id, CefTouchEventType::Released)
+    }
+
+    /// Cancels the touch sequence of finger `id`, as when the system takes
+    /// over the gesture.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InputError::PlatformError` if finger `id` is not down.
+    pub async fn send_touch_cancel(&mut self, id: i32) -> InputResult<()> {
+        self.finish_touch(id, CefTouchEventType::Cancelled)
+    }
+
+    /// Performs a two-finger pinch around `(center_x, center_y)`.
+    ///
+    /// A `scale` above 1.0 spreads the fingers apart (zoom in), below 1.0
+    /// pinches them together (zoom out); the finger distance changes by
+    /// exactly `scale`. The fingers land a few milliseconds apart on a
+    /// slightly tilted axis and decelerate towards the end of the gesture,
+    /// with move delays drawn from the handler's `HumanTiming`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InputError::PlatformError` if `scale` is not a positive
+    /// finite number, and `InputError::OutOfBounds` if a finger would
+    /// leave the view.
+    pub async fn send_pinch_zoom(
+        &mut self,
+        center_x: f64,
+        center_y: f64,
+        scale: f64,
+    ) -> InputResult<()> {
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(InputError::PlatformError {
+                message: format!("Invalid pinch scale: {}", scale),
+            });
+        }
+
+        let (start_distance, end_distance) = if scale >= 1.0 {
+            (PINCH_BASE_DISTANCE_PX, PINCH_BASE_DISTANCE_PX * scale)
+        } else {
+            (PINCH_BASE_DISTANCE_PX / scale, PINCH_BASE_DISTANCE_PX)
+        };
+
+        // Two fingers of one hand rarely line up exactly horizontally
+        let angle = (rand::random::<f64>() - 0.5) * PI / 6.0;
+        let fingers = |distance: f64| {
+            let dx = angle.cos() * distance / 2.0;
+            let dy = angle.sin() * distance / 2.0;
+            [
+                (center_x - dx, center_y - dy),
+                (center_x + dx, center_y + dy),
+            ]
+        };
+
+        // Check both ends up front so a failing gesture sends nothing
+        for (x, y) in fingers(start_distance).into_iter().chain(fingers(end_distance)) {
+            self.validate_position(x, y)?;
+        }
+        let first_id = self.free_touch_id();
+        let ids = [first_id, first_id + 1];
+
+        let [first, second] = fingers(start_distance);
+        self.send_touch_start(ids[0], first.0, first.1).await?;
+        let landing = Duration::from_millis(rand::random::<u64>() % 30 + 10);
+        tokio::time::sleep(landing).await;
+        self.send_touch_start(ids[1], second.0, second.1).await?;
+        tokio::time::sleep(self.timing.get_click_delay()).await;
+
+        let travel = (end_distance - start_distance).abs() / 2.0;
+        let steps =
+            ((travel / PINCH_STEP_PX).round() as usize).clamp(PINCH_MIN_STEPS, PINCH_MAX_STEPS);
+        for step in 1..=steps {
+            let progress = ScrollProfile::TouchScreen.ease(step as f64 / steps as f64);
+            let distance = start_distance + (end_distance - start_distance) * progress;
+            for (&id, (x, y)) in ids.iter().zip(fingers(distance)) {
+                self.send_touch_move(id, x, y).await?;
+            }
+            tokio::time::sleep(self.timing.get_move_delay()).await;
+        }
+
+        tokio::time::sleep(self.timing.get_click_delay()).await;
+        self.send_touch_end(ids[0]).await?;
+        let lifting = Duration::from_millis(rand::random::<u64>() % 30 + 10);
+        tokio::time::sleep(lifting).await;
+        self.send_touch_end(ids[1]).await?;
+
+        Ok(())
+    }
+
+    /// Returns the ids of all fingers currently touching the screen.
+    pub fn active_touches(&self) -> Vec<i32> {
+        let mut ids: Vec<i32> = self.active_touches.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Returns the tracked position of active finger `id`.
+    fn active_touch(&mut self, id: i32) -> InputResult<&mut Point> {
+        self.active_touches
+            .get_mut(&id)
+            .ok_or_else(|| InputError::PlatformError {
+                message: format!("Touch {} is not active", id),
+            })
+    }
+
+    /// Lowest touch id above every finger currently down.
+    fn free_touch_id(&self) -> i32 {
+        self.active_touches.keys().max().map_or(0, |id| id + 1)
+    }
+
+    /// Ends finger `id` with a release or cancel event at its last position.
+    fn finish_touch(&mut self, id: i32, event_type: CefTouchEventType) -> InputResult<()> {
+        let position = *self.active_touch(id)?;
+        self.active_touches.remove(&id);
+        self.dispatch_touch(id, event_type, position.x, position.y);
+        Ok(())
+    }
+
+    /// Sends one touch event with the configured pressure and current modifiers.
+    fn dispatch_touch(&self, id: i32, event_type: CefTouchEventType, x: f64, y: f64) {
+        let mut event = CefTouchEvent::new(
+            id,
+            event_type,
+            x as f32,
+            y as f32,
+            self.config.touch_pressure,
+        );
+        event.modifiers = self.current_modifier_flags();
+        self.sender.send_touch_event(&event);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::browser::cef_input::events::CefTouchEventType;
+    use crate::browser::cef_input::mouse::{CefInputConfig, CefInputHandler};
+    use crate::browser::cef_input::MockCefEventSender;
+    use crate::input::timing::HumanTiming;
+
+    fn handler() -> CefInputHandler<MockCefEventSender> {
+        CefInputHandler::new(MockCefEventSender::new(), HumanTiming::instant())
+    }
+
+    #[tokio::test]
+    async fn test_single_touch_sequence() {
+        let mut handler = handler();
+
+        handler.send_touch_start(3, 10.0, 20.0).await.unwrap();
+        handler.send_touch_move(3, 15.0, 40.0).await.unwrap();
+        assert_eq!(handler.active_touches(), [3]);
+        handler.send_touch_end(3).await.unwrap();
+        assert!(handler.active_touches().is_empty());
+
+        let events = handler.sender.touch_events.lock().unwrap();
+        let sequence: Vec<_> = events.iter().map(|e| (e.id, e.event_type, e.x, e.y)).collect();
+        assert_eq!(
+            sequence,
+            [
+                (3, CefTouchEventType::Pressed, 10.0, 20.0),
+                (3, CefTouchEventType::Moved, 15.0, 40.0),
+                (3, CefTouchEventType::Released, 15.0, 40.0),
+            ]
+        );
+        assert!(events.iter().all(|e| e.pressure == 0.5));
+    }
+
+    #[tokio::test]
+    async fn test_touch_requires_active_finger() {
+        let mut handler = handler();
+
+        assert!(handler.send_touch_move(0, 10.0, 10.0).await.is_err());
+        assert!(handler.send_touch_end(0).await.is_err());
+
+        handler.send_touch_start(0, 10.0, 10.0).await.unwrap();
+        assert!(handler.send_touch_start(0, 12.0, 12.0).await.is_err());
+        handler.send_touch_cancel(0).await.unwrap();
+
+        let events = handler.sender.touch_events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].event_type, CefTouchEventType::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_pinch_zoom_gesture_sequence() {
+        let config = CefInputConfig {
+            touch_pressure: 0.8,
+            ..Default::default()
+        };
+        let mut handler =
+            CefInputHandler::with_config(MockCefEventSender::new(), HumanTiming::instant(), config);
+        handler.send_pinch_zoom(400.0, 300.0, 2.0).await.unwrap();
+        assert!(handler.active_touches().is_empty());
+
+        let events = handler.sender.touch_events.lock().unwrap();
+        let n = events.len();
+        let kinds: Vec<_> = events.iter().map(|e| (e.id, e.event_type)).collect();
+        assert_eq!(
+            kinds[..2],
+            [(0, CefTouchEventType::Pressed), (1, CefTouchEventType::Pressed)]
+        );
+        assert_eq!(
+            kinds[n - 2..],
+            [(0, CefTouchEventType::Released), (1, CefTouchEventType::Released)]
+        );
+        let moves = &events[2..n - 2];
+        assert!(moves.len() >= 12);
+        assert!(moves.iter().all(|e| e.event_type == CefTouchEventType::Moved));
+        assert!(events.iter().all(|e| e.pressure == 0.8));
+
+        let spread = |a: usize, b: usize| {
+            (events[a].x - events[b].x).hypot(events[a].y - events[b].y)
+        };
+        assert!((spread(0, 1) - 100.0).abs() < 0.01);
+        assert!((spread(n - 2, n - 1) - 200.0).abs() < 0.01);
+        // The fingers stay centered on the pinch point
+        assert!(((events[n - 2].x + events[n - 1].x) / 2.0 - 400.0).abs() < 0.01);
+        assert!(((events[n - 2].y + events[n - 1].y) / 2.0 - 300.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_pinch_zoom_out_and_invalid_scale() {
+        let mut handler = handler();
+        assert!(handler.send_pinch_zoom(400.0, 300.0, 0.0).await.is_err());
+        assert!(handler.send_pinch_zoom(400.0, 300.0, f64::NAN).await.is_err());
+        // Fingers 400px apart would leave the view on the left
+        assert!(handler.send_pinch_zoom(100.0, 300.0, 0.25).await.is_err());
+        assert!(handler.sender.touch_events.lock().unwrap().is_empty());
+
+        handler.send_pinch_zoom(400.0, 300.0, 0.5).await.unwrap();
+        let events = handler.sender.touch_events.lock().unwrap();
+        let n = events.len();
+        let start = (events[0].x - events[1].x).hypot(events[0].y - events[1].y);
+        let end = (events[n - 2].x - events[n - 1].x).hypot(events[n - 2].y - events[n - 1].y);
+        assert!((start - 200.0).abs() < 0.01);
+        assert!((end - 100.0).abs() < 0.01);
+    }
+}
@@ -32,12 +32,18 @@ use image::{ImageBuffer, ImageEncoder, RgbaImage};
 #[cfg(feature = "cef-browser")]
 use parking_lot::RwLock;
 #[cfg(feature = "cef-browser")]
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::borrow::Cow;
+#[cfg(feature = "cef-browser")]
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 #[cfg(feature = "cef-browser")]
 use std::sync::Arc;
+#[cfg(feature = "cef-browser")]
+use tracing::warn;
 
 #[cfg(feature = "cef-browser")]
-use crate::browser::screenshot::ScreenshotFormat;
+use crate::browser::device::DeviceMetrics;
+#[cfg(feature = "cef-browser")]
+use crate::browser::screenshot::{encode_avif, encode_webp, ScreenshotFormat};
 
 /// Represents a rectangular region that has been modified.
 #[cfg(feature = "cef-browser")]
@@ -157,6 +163,23 @@ impl ScreenInfo {
         }
     }
 
+    /// Creates the ScreenInfo of an emulated device: a screen the size of
+    /// its viewport in DIPs at its device scale factor.
+    pub fn from_device_metrics(device: &DeviceMetrics) -> Self {
+        Self::new(device.width as i32, device.height as i32)
+            .with_scale(device.device_scale_factor as f32)
+    }
+
+    /// Returns the screen size in device pixels.
+    pub fn pixel_size(&self) -> (u32, u32) {
+        let (_, _, width, height) = self.rect;
+        let scale = self.device_scale_factor as f64;
+        (
+            (width as f64 * scale).round() as u32,
+            (height as f64 * scale).round() as u32,
+        )
+    }
+
     /// Sets the device scale factor (DPI).
     pub fn with_scale(mut self, scale: f32) -> Self {
         self.device_scale_factor = scale;
@@ -252,6 +275,66 @@ impl FrameBuffer {
     }
 }
 
+/// Bytes per pixel of the BGRA frames CEF paints.
+#[cfg(feature = "cef-browser")]
+const BYTES_PER_PIXEL: usize = 4;
+
+/// Last padded stride [`row_stride`] warned about, so a stream of padded
+/// frames logs once instead of on every paint.
+#[cfg(feature = "cef-browser")]
+static LAST_PADDED_STRIDE: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the row stride in bytes of a `width` x `height` BGRA frame.
+///
+/// Uses `stride` when given and otherwise infers it from the buffer length
+/// `len`. Some platforms pad every row, which makes the stride larger than
+/// `width * 4`; copying such a buffer as tightly packed shears the image, so
+/// a padded stride is logged as a warning.
+#[cfg(feature = "cef-browser")]
+pub fn row_stride(len: usize, width: u32, height: u32, stride: Option<usize>) -> usize {
+    let tight = width as usize * BYTES_PER_PIXEL;
+    let rows = height as usize;
+    let stride = stride
+        .unwrap_or_else(|| {
+            if rows > 0 && len.is_multiple_of(rows) {
+                len / rows
+            } else {
+                tight
+            }
+        })
+        .max(tight);
+    if stride != tight && LAST_PADDED_STRIDE.swap(stride, Ordering::Relaxed) != stride {
+        warn!(
+            "Frame rows are padded: {}x{} frame with {} bytes per row instead of {}",
+            width, height, stride, tight
+        );
+    }
+    stride
+}
+
+/// Copies a BGRA frame whose rows are `stride` bytes apart into a tightly
+/// packed `width * height * 4` buffer.
+///
+/// A frame that is already tightly packed is borrowed as is. Rows missing
+/// from a short buffer are left black.
+#[cfg(feature = "cef-browser")]
+pub fn unpack_rows(data: &[u8], width: u32, height: u32, stride: usize) -> Cow<'_, [u8]> {
+    let tight = width as usize * BYTES_PER_PIXEL;
+    let rows = height as usize;
+    if stride == tight {
+        return Cow::Borrowed(&data[..data.len().min(tight * rows)]);
+    }
+    let mut packed = vec![0; tight * rows];
+    for (row, dst) in packed.chunks_exact_mut(tight).enumerate() {
+        let start = row * stride;
+        match data.get(start..start + tight) {
+            Some(src) => dst.copy_from_slice(src),
+            None => break,
+        }
+    }
+    Cow::Owned(packed)
+}
+
 /// Off-screen render handler for CEF.
 ///
 /// This handler receives rendered pixels from CEF and stores them in a double-buffered
@@ -362,13 +445,35 @@ impl OffScreenRenderHandler {
     /// * `width` - Width of the rendered area
     /// * `height` - Height of the rendered area
     pub fn on_paint(
+        &self,
+        paint_type: i32,
+        dirty_rects: &[DirtyRect],
+        buffer: &[u8],
+        width: i32,
+        height: i32,
+    ) {
+        self.on_paint_with_stride(paint_type, dirty_rects, buffer, width, height, None)
+    }
+
+    /// Like [`on_paint`](Self::on_paint) for a buffer whose rows are
+    /// `stride` bytes apart.
+    ///
+    /// With `stride` of `None` the stride is inferred from the buffer length
+    /// (see [`row_stride`]).
+    pub fn on_paint_with_stride(
         &self,
         _paint_type: i32,
         dirty_rects: &[DirtyRect],
         buffer: &[u8],
         width: i32,
         height: i32,
+        stride: Option<usize>,
     ) {
+        let (w, h) = (width.max(0) as u32, height.max(0) as u32);
+        let stride = row_stride(buffer.len(), w, h, stride);
+        let buffer = unpack_rows(buffer, w, h, stride);
+        let buffer = buffer.as_ref();
+
         // Update dimensions if they've changed
         let current_width = self.width.load(Ordering::Relaxed) as i32;
         let current_height = self.height.load(Ordering::Relaxed) as i32;
@@ -658,23 +763,10 @@ impl OffScreenRenderHandler {
                     .map_err(|e| anyhow!("JPEG encoding failed: {}", e))?;
             }
             ScreenshotFormat::WebP => {
-                // For WebP, we'll use a simple approach - encode as PNG if webp feature isn't available
-                // In production, you'd want the webp crate for proper WebP encoding
-                #[cfg(feature = "webp")]
-                {
-                    use webp::Encoder as WebPEncoder;
-                    let encoder = WebPEncoder::from_rgba(&img, width, height);
-                    let webp_data = encoder.encode(quality as f32);
-                    buffer = webp_data.to_vec();
-                }
-                #[cfg(not(feature = "webp"))]
-                {
-                    // Fallback to PNG if WebP encoding isn't available
-                    let encoder = image::codecs::png::PngEncoder::new(&mut buffer);
-                    encoder
-                        .write_image(&img, width, height, image::ColorType::Rgba8)
-                        .map_err(|e| anyhow!("PNG encoding (WebP fallback) failed: {}", e))?;
-                }
+                buffer = encode_webp(&img, width, height, quality)?;
+            }
+            ScreenshotFormat::Avif => {
+                buffer = encode_avif(&img, width, height, quality)?;
             }
         }
 
@@ -804,6 +896,17 @@ mod tests {
         assert_eq!(custom.rect, (0, 0, 1280, 720));
     }
 
+    #[test]
+    fn test_screen_info_for_iphone_preset() {
+        let device = DeviceMetrics::iphone_14();
+        let info = ScreenInfo::from_device_metrics(&device);
+        assert_eq!(info.device_scale_factor, 3.0);
+        assert_eq!(info.rect, (0, 0, 390, 844));
+        assert_eq!(info.available_rect, info.rect);
+        assert_eq!(info.pixel_size(), (1170, 2532));
+        assert_eq!(info.pixel_size(), device.pixel_size());
+    }
+
     #[test]
     fn test_render_handler_creation() {
         let handler = OffScreenRenderHandler::with_size(800, 600);
@@ -863,6 +966,35 @@ mod tests {
         assert_eq!(rgba[3], 255); // A
     }
 
+    #[test]
+    fn test_on_paint_unpacks_padded_rows() {
+        // 3x2 frame whose rows are padded from 12 to 16 bytes
+        let mut buffer = Vec::new();
+        for row in 0..2u8 {
+            for col in 0..3u8 {
+                buffer.extend_from_slice(&[row * 10 + col, 0, 0, 255]);
+            }
+            buffer.extend_from_slice(&[0xEE; 4]);
+        }
+        assert_eq!(row_stride(buffer.len(), 3, 2, None), 16);
+        assert_eq!(row_stride(24, 3, 2, None), 12);
+
+        let packed = unpack_rows(&buffer, 3, 2, 16);
+        assert_eq!(packed.len(), 3 * 2 * 4);
+        assert!(!packed.contains(&0xEE));
+
+        let handler = OffScreenRenderHandler::with_size(3, 2);
+        handler.on_paint_with_stride(0, &[DirtyRect::full(3, 2)], &buffer, 3, 2, Some(16));
+        let (rgba, _, _) = handler.get_raw_pixels();
+        let reds: Vec<u8> = rgba.chunks_exact(4).map(|pixel| pixel[2]).collect();
+        assert_eq!(reds, vec![0, 1, 2, 10, 11, 12]);
+
+        // Without an explicit stride it is inferred from the length
+        let handler = OffScreenRenderHandler::with_size(3, 2);
+        handler.on_paint(0, &[DirtyRect::full(3, 2)], &buffer, 3, 2);
+        assert_eq!(handler.get_raw_pixels().0, rgba);
+    }
+
     #[test]
     fn test_capture_screenshot() {
         let handler = OffScreenRenderHandler::with_size(4, 4);
@@ -885,6 +1017,13 @@ mod tests {
         let bytes = decoded.unwrap();
         assert!(bytes.len() > 8);
         assert_eq!(&bytes[0..8], &[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]);
+
+        let screenshot = handler
+            .capture_screenshot(ScreenshotFormat::WebP, 80)
+            .unwrap();
+        let bytes = BASE64.decode(&screenshot).unwrap();
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(&bytes[8..12], b"WEBP");
     }
 
     #[test]
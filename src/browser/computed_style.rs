@@ -0,0 +1,110 @@
+//! Computed CSS of page elements.
+//!
+//! Backs [`BrowserEngine::get_computed_style`](crate::browser::BrowserEngine::get_computed_style).
+//! The page script reads `getComputedStyle` for the first element matching a
+//! selector and returns only the requested properties, so a full style
+//! declaration (several hundred entries) never crosses the JS bridge.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::error::BrowserError;
+
+/// Properties returned when no properties are requested.
+pub const DEFAULT_STYLE_PROPERTIES: &[&str] = &[
+    "display",
+    "visibility",
+    "opacity",
+    "position",
+    "width",
+    "height",
+    "color",
+    "background-color",
+    "font-family",
+    "font-size",
+    "font-weight",
+    "z-index",
+];
+
+/// Returns `properties`, or [`DEFAULT_STYLE_PROPERTIES`] if it is empty.
+pub fn resolve_style_properties(properties: Vec<String>) -> Vec<String> {
+    if properties.is_empty() {
+        DEFAULT_STYLE_PROPERTIES.iter().map(|p| p.to_string()).collect()
+    } else {
+        properties
+    }
+}
+
+/// Result reported by [`computed_style_script`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct StyleReport {
+    /// Whether the selector matched an element.
+    pub found: bool,
+
+    /// Requested property names mapped to their computed values.
+    #[serde(default)]
+    pub style: HashMap<String, String>,
+}
+
+impl StyleReport {
+    /// Converts the report into the style map or a typed error.
+    pub fn into_style(self, selector: &str) -> Result<HashMap<String, String>, BrowserError> {
+        if self.found {
+            Ok(self.style)
+        } else {
+            Err(BrowserError::DomQueryFailed {
+                selector: selector.to_string(),
+                reason: "No element matches selector".to_string(),
+            })
+        }
+    }
+}
+
+/// Builds the page script reading computed `properties` of `selector`.
+///
+/// Property names use CSS syntax (`background-color`). Unknown properties
+/// map to an empty string, as `getPropertyValue` returns for them. The
+/// script returns a JSON string deserializable into [`StyleReport`].
+pub fn computed_style_script(selector: &str, properties: &[String]) -> String {
+    let selector_json = serde_json::to_string(selector).unwrap_or_else(|_| "''".to_string());
+    let properties_json = serde_json::to_string(properties).unwrap_or_else(|_| "[]".to_string());
+    format!(
+        r#"(function() {{
+    const el = document.querySelector({selector_json});
+    if (!el) return JSON.stringify({{ found: false }});
+    const computed = window.getComputedStyle(el);
+    const style = {{}};
+    for (const name of {properties_json}) style[name] = computed.getPropertyValue(name);
+    return JSON.stringify({{ found: true, style: style }});
+}})()"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_style_report_into_style() {
+        let report: StyleReport =
+            serde_json::from_str(r#"{"found":true,"style":{"display":"flex"}}"#).unwrap();
+        assert_eq!(report.into_style("#app").unwrap()["display"], "flex");
+
+        let report: StyleReport = serde_json::from_str(r#"{"found":false}"#).unwrap();
+        assert!(matches!(
+            report.into_style("#missing"),
+            Err(BrowserError::DomQueryFailed { selector, .. }) if selector == "#missing"
+        ));
+    }
+
+    #[test]
+    fn test_computed_style_script_uses_requested_properties() {
+        let script = computed_style_script(r#"a[title="x"]"#, &["color".to_string()]);
+        assert!(script.contains(r#"document.querySelector("a[title=\"x\"]")"#));
+        assert!(script.contains(r#"for (const name of ["color"])"#));
+
+        let defaults = resolve_style_properties(Vec::new());
+        assert_eq!(defaults.len(), DEFAULT_STYLE_PROPERTIES.len());
+        assert_eq!(resolve_style_properties(vec!["color".into()]), ["color"]);
+    }
+}
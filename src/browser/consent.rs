@@ -0,0 +1,154 @@
+//! Automatic cookie-consent dismissal.
+//!
+//! With [`BrowserConfig::auto_dismiss_consent`](crate::browser::BrowserConfig::auto_dismiss_consent)
+//! enabled, every top-level page load runs [`consent_dismiss_script`]. The
+//! script looks for an accept button, first by the configured selectors and
+//! then by common button texts ("Accept all", "Alle akzeptieren", ...), and
+//! clicks the first visible match. Banners injected late by consent managers
+//! are caught by retrying for a few seconds.
+//!
+//! A click is reported back as a console message carrying
+//! [`CONSENT_CONSOLE_PREFIX`] and published as
+//! [`BrowserEvent::ConsentDismissed`](crate::browser::events::BrowserEvent::ConsentDismissed).
+//!
+//! For dismissing a dialog on demand, including consent managers rendered in
+//! iframes, see `POST /debug/consent/accept`.
+
+use serde::Deserialize;
+
+/// Console message prefix of consent click reports.
+pub const CONSENT_CONSOLE_PREFIX: &str = "KI_CONSENT:";
+
+/// Accept buttons of common consent managers, tried in order.
+pub const DEFAULT_CONSENT_SELECTORS: &[&str] = &[
+    "#onetrust-accept-btn-handler",
+    "#CybotCookiebotDialogBodyLevelButtonLevelOptinAllowAll",
+    "#CybotCookiebotDialogBodyButtonAccept",
+    "[data-testid=\"uc-accept-all-button\"]",
+    "#didomi-notice-agree-button",
+    ".qc-cmp2-summary-buttons button[mode=\"primary\"]",
+    ".cmp-accept-all",
+    ".sp_choice_type_11",
+    "#cookie-accept-all",
+    ".cc-allow",
+    ".cc-accept",
+];
+
+/// How often the script looks for a banner after a page load.
+const CONSENT_ATTEMPTS: u32 = 10;
+
+/// Delay between two attempts in milliseconds.
+const CONSENT_ATTEMPT_INTERVAL_MS: u32 = 500;
+
+/// [`DEFAULT_CONSENT_SELECTORS`] as owned strings.
+pub fn default_consent_selectors() -> Vec<String> {
+    DEFAULT_CONSENT_SELECTORS
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// A consent button clicked by [`consent_dismiss_script`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ConsentClick {
+    /// URL of the page the banner was dismissed on.
+    pub url: String,
+    /// Selector that matched, `None` if the button was found by its text.
+    pub selector: Option<String>,
+    /// Text of the clicked button.
+    pub text: String,
+}
+
+impl ConsentClick {
+    /// Parses a report logged by [`consent_dismiss_script`].
+    ///
+    /// Returns `None` if the message does not carry
+    /// [`CONSENT_CONSOLE_PREFIX`] or the payload is malformed.
+    pub fn from_console_report(message: &str) -> Option<Self> {
+        let payload = message.strip_prefix(CONSENT_CONSOLE_PREFIX)?;
+        serde_json::from_str(payload).ok()
+    }
+}
+
+/// Builds the page script that clicks a consent accept button.
+///
+/// `selectors` are tried in order before falling back to text matching.
+/// The script reports at most one click per page.
+pub fn consent_dismiss_script(selectors: &[String]) -> String {
+    let selectors_json = serde_json::to_string(selectors).unwrap_or_else(|_| "[]".to_string());
+    format!(
+        r#"(function() {{
+    if (window.__kiConsentDismiss) return;
+    Object.defineProperty(window, '__kiConsentDismiss', {{ value: true, enumerable: false }});
+    const report = console.debug.bind(console);
+    const selectors = {selectors_json};
+    const patterns = [
+        /^(alle\s+)?akzeptieren$/i,
+        /^(alle\s+)?cookies?\s+(akzeptieren|annehmen|zulassen)$/i,
+        /^zustimmen(\s+und\s+weiter)?$/i,
+        /^accept(\s+all)?(\s+cookies)?$/i,
+        /^allow(\s+all)?(\s+cookies)?$/i,
+        /^agree(\s+and\s+continue)?$/i,
+        /^(i\s+)?agree$/i,
+        /^tout\s+accepter$/i,
+        /^aceptar(\s+todo)?$/i,
+        /^accetta(\s+tutto)?$/i
+    ];
+    const visible = function(el) {{
+        const r = el.getBoundingClientRect();
+        return r.width > 0 && r.height > 0;
+    }};
+    const find = function() {{
+        for (const selector of selectors) {{
+            let el = null;
+            try {{ el = document.querySelector(selector); }} catch (e) {{ continue; }}
+            if (el && visible(el)) return {{ el: el, selector: selector }};
+        }}
+        const buttons = document.querySelectorAll('button, [role="button"], a, input[type="submit"], input[type="button"]');
+        for (const el of buttons) {{
+            const text = (el.innerText || el.value || '').trim();
+            if (text.length > 40 || !visible(el)) continue;
+            if (patterns.some(function(p) {{ return p.test(text); }})) return {{ el: el, selector: null }};
+        }}
+        return null;
+    }};
+    let attempts = 0;
+    const attempt = function() {{
+        const match = find();
+        if (match) {{
+            const text = (match.el.innerText || match.el.value || '').trim();
+            match.el.click();
+            report('{prefix}' + JSON.stringify({{ url: location.href, selector: match.selector, text: text }}));
+            return;
+        }}
+        if (++attempts < {attempts}) setTimeout(attempt, {interval});
+    }};
+    attempt();
+}})();"#,
+        prefix = CONSENT_CONSOLE_PREFIX,
+        attempts = CONSENT_ATTEMPTS,
+        interval = CONSENT_ATTEMPT_INTERVAL_MS,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consent_click_from_console_report() {
+        let click = ConsentClick::from_console_report(
+            r##"KI_CONSENT:{"url":"https://example.com/","selector":"#onetrust-accept-btn-handler","text":"Accept All"}"##,
+        )
+        .unwrap();
+        assert_eq!(
+            click.selector.as_deref(),
+            Some("#onetrust-accept-btn-handler")
+        );
+        assert_eq!(click.text, "Accept All");
+
+        assert!(ConsentClick::from_console_report("KI_BINDING:{}").is_none());
+        let script = consent_dismiss_script(&["#accept \"all\"".to_string()]);
+        assert!(script.contains(r##"const selectors = ["#accept \"all\""];"##));
+    }
+}
@@ -0,0 +1,120 @@
+//! Page HTML source retrieval.
+//!
+//! Backs [`BrowserEngine::get_content`](crate::browser::BrowserEngine::get_content)
+//! and [`get_content_selector`](crate::browser::BrowserEngine::get_content_selector).
+//! The page script measures the serialized HTML before returning it, so a
+//! size cap is enforced inside the page and an oversized document is never
+//! shipped back over the JS bridge.
+
+use serde::Deserialize;
+
+use crate::error::BrowserError;
+
+/// Result reported by [`content_script`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ContentReport {
+    /// Whether the selector matched an element (always true for the full page).
+    pub found: bool,
+
+    /// UTF-8 size of the serialized HTML in bytes.
+    #[serde(default)]
+    pub size: usize,
+
+    /// The HTML, omitted when it exceeds the size cap.
+    #[serde(default)]
+    pub html: Option<String>,
+}
+
+impl ContentReport {
+    /// Converts the report into the HTML string or a typed error.
+    pub fn into_html(
+        self,
+        selector: Option<&str>,
+        max_bytes: Option<usize>,
+    ) -> Result<String, BrowserError> {
+        if !self.found {
+            return Err(BrowserError::DomQueryFailed {
+                selector: selector.unwrap_or("html").to_string(),
+                reason: "No element matches selector".to_string(),
+            });
+        }
+        match self.html {
+            Some(html) => enforce_max_size(html, max_bytes),
+            None => Err(BrowserError::ContentTooLarge {
+                size: self.size,
+                max_bytes: max_bytes.unwrap_or(0),
+            }),
+        }
+    }
+}
+
+/// Returns `html` unchanged, or [`BrowserError::ContentTooLarge`] if it exceeds `max_bytes`.
+pub fn enforce_max_size(html: String, max_bytes: Option<usize>) -> Result<String, BrowserError> {
+    match max_bytes {
+        Some(max_bytes) if html.len() > max_bytes => Err(BrowserError::ContentTooLarge {
+            size: html.len(),
+            max_bytes,
+        }),
+        _ => Ok(html),
+    }
+}
+
+/// Builds the page script that serializes the document or a selected element.
+///
+/// With `selector == None` the script reads `document.documentElement.outerHTML`,
+/// otherwise the `outerHTML` of the first `querySelector` match. The script
+/// returns a JSON string deserializable into [`ContentReport`].
+pub fn content_script(selector: Option<&str>, max_bytes: Option<usize>) -> String {
+    let target = match selector {
+        Some(sel) => format!(
+            "document.querySelector({})",
+            serde_json::to_string(sel).unwrap_or_else(|_| "''".to_string())
+        ),
+        None => "document.documentElement".to_string(),
+    };
+    let max = max_bytes.map_or_else(|| "null".to_string(), |m| m.to_string());
+
+    format!(
+        r#"(function() {{
+    const el = {target};
+    if (!el) return JSON.stringify({{ found: false }});
+    const html = el.outerHTML;
+    const size = new TextEncoder().encode(html).length;
+    const max = {max};
+    if (max !== null && size > max) return JSON.stringify({{ found: true, size: size }});
+    return JSON.stringify({{ found: true, size: size, html: html }});
+}})()"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_report_into_html() {
+        let report: ContentReport =
+            serde_json::from_str(r#"{"found":true,"size":13,"html":"<html></html>"}"#).unwrap();
+        assert_eq!(report.into_html(None, Some(100)).unwrap(), "<html></html>");
+
+        let report: ContentReport = serde_json::from_str(r#"{"found":true,"size":5000}"#).unwrap();
+        assert!(matches!(
+            report.into_html(None, Some(1024)),
+            Err(BrowserError::ContentTooLarge { size: 5000, max_bytes: 1024 })
+        ));
+
+        let report: ContentReport = serde_json::from_str(r#"{"found":false}"#).unwrap();
+        assert!(matches!(
+            report.into_html(Some("#missing"), None),
+            Err(BrowserError::DomQueryFailed { selector, .. }) if selector == "#missing"
+        ));
+    }
+
+    #[test]
+    fn test_content_script_escapes_selector() {
+        let script = content_script(Some(r#"a[title="x"]"#), Some(10));
+        assert!(script.contains(r#"document.querySelector("a[title=\"x\"]")"#));
+        assert!(script.contains("const max = 10;"));
+        assert!(content_script(None, None).contains("document.documentElement"));
+    }
+}
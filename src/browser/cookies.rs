@@ -0,0 +1,244 @@
+//! Browser cookies.
+//!
+//! Backs the cookie methods of
+//! [`BrowserEngine`](crate::browser::BrowserEngine). Unlike `document.cookie`,
+//! they see and set `HttpOnly` cookies and all cookie attributes, which is
+//! what session reuse and login persistence need.
+//!
+//! Cookies belong to the browser profile, not to a tab: a cookie set through
+//! one tab is visible to every tab of the engine.
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::BrowserError;
+
+/// The `SameSite` attribute of a cookie.
+///
+/// Deserializes from the snake case names and from the attribute spelling
+/// (`None`, `Lax`, `Strict`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SameSitePolicy {
+    /// No attribute; the browser default (`Lax` in Chromium) applies.
+    #[default]
+    Unspecified,
+
+    /// `SameSite=None`: sent with cross-site requests (requires `Secure`).
+    #[serde(alias = "None")]
+    None,
+
+    /// `SameSite=Lax`: sent with top-level cross-site navigations.
+    #[serde(alias = "Lax")]
+    Lax,
+
+    /// `SameSite=Strict`: sent with same-site requests only.
+    #[serde(alias = "Strict")]
+    Strict,
+}
+
+/// A browser cookie.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cookie {
+    /// Cookie name.
+    pub name: String,
+
+    /// Cookie value.
+    pub value: String,
+
+    /// Domain the cookie belongs to. A leading dot makes it a domain cookie
+    /// also sent to subdomains.
+    pub domain: String,
+
+    /// Path prefix the cookie is sent for.
+    #[serde(default = "default_path")]
+    pub path: String,
+
+    /// Expiry; `None` for a session cookie.
+    #[serde(default)]
+    pub expires: Option<DateTime<Utc>>,
+
+    /// Whether the cookie is hidden from `document.cookie`.
+    #[serde(default)]
+    pub http_only: bool,
+
+    /// Whether the cookie is only sent over HTTPS.
+    #[serde(default)]
+    pub secure: bool,
+
+    /// `SameSite` attribute.
+    #[serde(default)]
+    pub same_site: SameSitePolicy,
+}
+
+fn default_path() -> String {
+    "/".to_string()
+}
+
+impl Cookie {
+    /// Creates a session cookie for the whole of `domain`.
+    pub fn new(name: impl Into<String>, value: impl Into<String>, domain: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            domain: domain.into(),
+            path: default_path(),
+            expires: None,
+            http_only: false,
+            secure: false,
+            same_site: SameSitePolicy::Unspecified,
+        }
+    }
+
+    /// Rejects cookies no browser would store.
+    pub fn validate(&self) -> Result<(), BrowserError> {
+        if self.name.is_empty() {
+            return Err(BrowserError::InvalidRequest("Empty cookie name".to_string()));
+        }
+        if self.host().is_empty() {
+            return Err(BrowserError::InvalidRequest(format!(
+                "Cookie '{}' has no domain",
+                self.name
+            )));
+        }
+        if !self.path.starts_with('/') {
+            return Err(BrowserError::InvalidRequest(format!(
+                "Cookie path must start with '/': {}",
+                self.path
+            )));
+        }
+        if self.same_site == SameSitePolicy::None && !self.secure {
+            return Err(BrowserError::InvalidRequest(format!(
+                "Cookie '{}' with SameSite=None must be secure",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// The domain without its leading dot.
+    pub fn host(&self) -> &str {
+        self.domain.trim_start_matches('.')
+    }
+
+    /// A URL the cookie is sent to, as browsers require one to set it.
+    pub fn url(&self) -> String {
+        let scheme = if self.secure { "https" } else { "http" };
+        format!("{}://{}{}", scheme, self.host(), self.path)
+    }
+
+    /// Whether this cookie and `other` are the same cookie, i.e. one
+    /// replaces the other when set.
+    pub fn same_identity(&self, other: &Cookie) -> bool {
+        self.name == other.name && self.host() == other.host() && self.path == other.path
+    }
+
+    /// Whether a browser sends this cookie with a request to `url`.
+    ///
+    /// Applies the domain, path and `Secure` rules of RFC 6265; expiry is
+    /// not checked.
+    pub fn matches_url(&self, url: &str) -> bool {
+        let Some((scheme, host, path)) = url_parts(url) else {
+            return false;
+        };
+        let cookie_host = self.host().to_ascii_lowercase();
+        let domain_match = host == cookie_host
+            || (self.domain.starts_with('.') && host.ends_with(&format!(".{}", cookie_host)));
+        let path_match = path == self.path
+            || (path.starts_with(&self.path)
+                && (self.path.ends_with('/') || path[self.path.len()..].starts_with('/')));
+        domain_match && path_match && (!self.secure || scheme == "https")
+    }
+}
+
+/// The host of `url`, lowercase and without port, e.g. to default the
+/// domain of a cookie set for a page.
+pub fn url_host(url: &str) -> Option<String> {
+    url_parts(url).map(|(_, host, _)| host)
+}
+
+/// Splits `url` into lowercase scheme, lowercase host without port and path.
+fn url_parts(url: &str) -> Option<(String, String, &str)> {
+    let (scheme, rest) = url.trim().split_once("://")?;
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let authority = rest[..end].rsplit('@').next().unwrap_or_default();
+    let host = match authority.strip_prefix('[') {
+        // IPv6 literals keep their brackets
+        Some(v6) => format!("[{}]", v6.split(']').next().unwrap_or_default()),
+        None => authority.split(':').next().unwrap_or_default().to_string(),
+    };
+    if host.is_empty() {
+        return None;
+    }
+    let path = rest[end..].split(['?', '#']).next().unwrap_or_default();
+    let path = if path.is_empty() { "/" } else { path };
+    Some((scheme.to_ascii_lowercase(), host.to_ascii_lowercase(), path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cookie_validation_and_url() {
+        let mut cookie = Cookie::new("sid", "abc", ".example.com");
+        assert!(cookie.validate().is_ok());
+        assert_eq!(cookie.url(), "http://example.com/");
+
+        cookie.path = "/app".to_string();
+        cookie.secure = true;
+        assert_eq!(cookie.url(), "https://example.com/app");
+
+        cookie.same_site = SameSitePolicy::None;
+        assert!(cookie.validate().is_ok());
+        cookie.secure = false;
+        assert!(cookie.validate().is_err());
+
+        assert!(Cookie::new("", "v", "example.com").validate().is_err());
+        assert!(Cookie::new("sid", "v", ".").validate().is_err());
+        assert!(Cookie::new("sid", "v", "example.com")
+            .same_identity(&Cookie::new("sid", "other", ".example.com")));
+    }
+
+    #[test]
+    fn test_cookie_matches_url() {
+        let mut cookie = Cookie::new("sid", "abc", ".example.com");
+        cookie.path = "/app".to_string();
+        assert!(cookie.matches_url("http://example.com/app"));
+        assert!(cookie.matches_url("https://shop.Example.com:8443/app/cart?x=1"));
+        assert!(!cookie.matches_url("https://example.com/apple"));
+        assert!(!cookie.matches_url("https://example.com/"));
+        assert!(!cookie.matches_url("https://notexample.com/app"));
+
+        let mut host_only = Cookie::new("sid", "abc", "example.com");
+        host_only.secure = true;
+        assert!(host_only.matches_url("https://example.com"));
+        assert!(!host_only.matches_url("http://example.com/"));
+        assert!(!host_only.matches_url("https://www.example.com/"));
+        assert!(!host_only.matches_url("not a url"));
+
+        assert_eq!(url_host("https://user@Example.com:8080/x").as_deref(), Some("example.com"));
+    }
+
+    #[test]
+    fn test_cookie_deserializes_with_defaults() {
+        let cookie: Cookie =
+            serde_json::from_str(r#"{"name": "sid", "value": "abc", "domain": "example.com"}"#)
+                .unwrap();
+        assert_eq!(cookie, Cookie::new("sid", "abc", "example.com"));
+
+        let cookie: Cookie = serde_json::from_str(
+            r#"{"name": "sid", "value": "abc", "domain": "example.com",
+                "expires": "2030-01-01T00:00:00Z", "same_site": "strict", "http_only": true}"#,
+        )
+        .unwrap();
+        assert_eq!(cookie.same_site, SameSitePolicy::Strict);
+        assert_eq!(
+            serde_json::from_str::<SameSitePolicy>(r#""Lax""#).unwrap(),
+            SameSitePolicy::Lax
+        );
+        assert!(cookie.http_only);
+        assert_eq!(cookie.expires.unwrap().timestamp(), 1_893_456_000);
+    }
+}
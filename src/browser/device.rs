@@ -0,0 +1,238 @@
+//! Device emulation.
+//!
+//! Backs [`BrowserConfig::device_emulation`](crate::browser::BrowserConfig::device_emulation),
+//! which renders pages the way a phone, tablet or high-DPI display would.
+//! [`DeviceMetrics`] sets the viewport and device scale factor of new tabs
+//! and the screen properties the stealth scripts report, so `screen`,
+//! `window.devicePixelRatio`, `visualViewport` and `screen.orientation` agree
+//! with the rendered frame.
+
+use serde::{Deserialize, Serialize};
+
+use crate::browser::engine::Viewport;
+use crate::error::BrowserError;
+use crate::stealth::fingerprint::ScreenResolution;
+use crate::stealth::{StealthConfig, ViewportMetrics};
+
+/// Orientation of an emulated device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Orientation {
+    /// Taller than wide.
+    Portrait,
+    /// Wider than tall.
+    Landscape,
+}
+
+impl Orientation {
+    /// The orientation of a `width` x `height` area; squares are landscape.
+    pub fn of(width: u32, height: u32) -> Self {
+        if width >= height {
+            Self::Landscape
+        } else {
+            Self::Portrait
+        }
+    }
+
+    /// `screen.orientation.type` of this orientation.
+    pub fn orientation_type(&self) -> &'static str {
+        match self {
+            Self::Portrait => "portrait-primary",
+            Self::Landscape => "landscape-primary",
+        }
+    }
+}
+
+/// Screen geometry of an emulated device.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DeviceMetrics {
+    /// Viewport width in CSS pixels, in the current orientation.
+    pub width: u32,
+
+    /// Viewport height in CSS pixels, in the current orientation.
+    pub height: u32,
+
+    /// Device pixels per CSS pixel (`window.devicePixelRatio`).
+    pub device_scale_factor: f64,
+
+    /// Phone or tablet: the screen is the viewport, without browser chrome
+    /// around it, and the natural orientation is portrait.
+    pub mobile: bool,
+
+    /// Current orientation.
+    pub orientation: Orientation,
+}
+
+impl DeviceMetrics {
+    /// A desktop display with a `width` x `height` viewport.
+    pub fn new(width: u32, height: u32, device_scale_factor: f64) -> Self {
+        Self {
+            width,
+            height,
+            device_scale_factor,
+            mobile: false,
+            orientation: Orientation::of(width, height),
+        }
+    }
+
+    /// A phone or tablet with a `width` x `height` viewport.
+    pub fn mobile(width: u32, height: u32, device_scale_factor: f64) -> Self {
+        Self {
+            mobile: true,
+            ..Self::new(width, height, device_scale_factor)
+        }
+    }
+
+    /// iPhone 14: 390x844 at 3x.
+    pub fn iphone_14() -> Self {
+        Self::mobile(390, 844, 3.0)
+    }
+
+    /// Pixel 7: 412x915 at 2.625x.
+    pub fn pixel_7() -> Self {
+        Self::mobile(412, 915, 2.625)
+    }
+
+    /// iPad Air: 820x1180 at 2x.
+    pub fn ipad_air() -> Self {
+        Self::mobile(820, 1180, 2.0)
+    }
+
+    /// Rotates the device to `orientation`, swapping width and height if
+    /// it changes.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        if orientation != Orientation::of(self.width, self.height) {
+            std::mem::swap(&mut self.width, &mut self.height);
+        }
+        self.orientation = orientation;
+        self
+    }
+
+    /// Rejects empty viewports, non-positive scale factors and an
+    /// orientation that contradicts the viewport.
+    pub fn validate(&self) -> Result<(), BrowserError> {
+        self.viewport().validate()?;
+        let portrait = self.width < self.height;
+        let landscape = self.width > self.height;
+        if (portrait && self.orientation == Orientation::Landscape)
+            || (landscape && self.orientation == Orientation::Portrait)
+        {
+            return Err(BrowserError::InvalidRequest(format!(
+                "A {}x{} viewport is not {}",
+                self.width,
+                self.height,
+                self.orientation.orientation_type()
+            )));
+        }
+        Ok(())
+    }
+
+    /// The viewport new tabs get.
+    pub fn viewport(&self) -> Viewport {
+        Viewport::new(self.width, self.height).with_device_scale_factor(self.device_scale_factor)
+    }
+
+    /// Size of the rendered frame in device pixels.
+    pub fn pixel_size(&self) -> (u32, u32) {
+        (
+            (self.width as f64 * self.device_scale_factor).round() as u32,
+            (self.height as f64 * self.device_scale_factor).round() as u32,
+        )
+    }
+
+    /// `screen.orientation.angle`: 0 in the natural orientation (portrait
+    /// for mobile devices, landscape otherwise), 90 when rotated.
+    pub fn orientation_angle(&self) -> u32 {
+        let natural = if self.mobile {
+            Orientation::Portrait
+        } else {
+            Orientation::Landscape
+        };
+        if self.orientation == natural {
+            0
+        } else {
+            90
+        }
+    }
+
+    /// Makes the screen properties of `stealth` match the device.
+    ///
+    /// Mobile devices report a screen exactly the size of the viewport;
+    /// desktop displays get a common resolution around it like
+    /// [`StealthConfig::sync_screen_to_viewport`]. Either way the injected
+    /// viewport metrics carry the device scale factor and the orientation
+    /// of the device.
+    pub fn apply_to_stealth(&self, stealth: &mut StealthConfig) {
+        if self.mobile {
+            stealth.fingerprint.screen_resolution = ScreenResolution {
+                width: self.width,
+                height: self.height,
+                avail_width: self.width,
+                avail_height: self.height,
+                outer_width: self.width,
+                outer_height: self.height,
+                orientation_type: String::new(),
+                orientation_angle: 0,
+            };
+        } else {
+            stealth.sync_screen_to_viewport(self.width, self.height);
+        }
+
+        let screen = &mut stealth.fingerprint.screen_resolution;
+        screen.orientation_type = self.orientation.orientation_type().to_string();
+        screen.orientation_angle = self.orientation_angle();
+        stealth.viewport = Some(ViewportMetrics::new(
+            self.width,
+            self.height,
+            screen,
+            self.device_scale_factor,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_orientation_and_pixel_size() {
+        let iphone = DeviceMetrics::iphone_14();
+        assert_eq!(iphone.orientation, Orientation::Portrait);
+        assert_eq!(iphone.pixel_size(), (1170, 2532));
+        assert_eq!(iphone.orientation_angle(), 0);
+        assert!(iphone.validate().is_ok());
+
+        let rotated = iphone.with_orientation(Orientation::Landscape);
+        assert_eq!((rotated.width, rotated.height), (844, 390));
+        assert_eq!(rotated.orientation_angle(), 90);
+        assert_eq!(rotated.viewport().device_scale_factor, 3.0);
+
+        let contradictory = DeviceMetrics {
+            orientation: Orientation::Landscape,
+            ..iphone
+        };
+        assert!(contradictory.validate().is_err());
+        assert!(DeviceMetrics::new(1280, 720, 0.0).validate().is_err());
+    }
+
+    #[test]
+    fn test_mobile_device_screen_matches_viewport() {
+        let mut stealth = StealthConfig::consistent("device-test");
+        DeviceMetrics::iphone_14().apply_to_stealth(&mut stealth);
+
+        let metrics = stealth.viewport.as_ref().unwrap();
+        assert_eq!((metrics.screen_width, metrics.screen_height), (390, 844));
+        assert_eq!((metrics.outer_width, metrics.outer_height), (390, 844));
+        assert_eq!((metrics.inner_width, metrics.inner_height), (390, 844));
+        assert_eq!(metrics.device_pixel_ratio, 3.0);
+        assert!(metrics.validate().is_ok());
+
+        let screen = &stealth.fingerprint.screen_resolution;
+        assert_eq!(screen.orientation_type, "portrait-primary");
+        assert_eq!(screen.orientation_angle, 0);
+
+        let script = stealth.get_complete_override_script();
+        assert!(script.contains("define(window, 'devicePixelRatio', 3);"));
+        assert!(script.contains("define(vv, 'width', 390);"));
+    }
+}
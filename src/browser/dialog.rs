@@ -0,0 +1,36 @@
+//! Answers to JavaScript dialogs.
+//!
+//! A headless tab has nobody to answer a dialog, and in single-process CEF
+//! an unanswered modal dialog freezes the whole message loop. The engine
+//! therefore answers every dialog itself. For the `beforeunload` prompt a
+//! page can raise while its tab is being closed, [`BeforeUnloadPolicy`]
+//! decides the answer.
+
+use serde::{Deserialize, Serialize};
+
+/// How closing a tab treats the page's `beforeunload` handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BeforeUnloadPolicy {
+    /// Close without running the handler, so cleanup never waits on the page.
+    #[default]
+    ForceClose,
+    /// Run the handler and confirm leaving the page if it prompts.
+    Confirm,
+    /// Run the handler and stay on the page if it prompts; the close fails.
+    Cancel,
+}
+
+impl BeforeUnloadPolicy {
+    /// Whether closing a tab gives the page's `beforeunload` handler a
+    /// chance to run.
+    pub fn runs_handler(self) -> bool {
+        self != BeforeUnloadPolicy::ForceClose
+    }
+
+    /// Answer to a `beforeunload` prompt raised by the close: `true` leaves
+    /// the page.
+    pub fn leaves_page(self) -> bool {
+        self != BeforeUnloadPolicy::Cancel
+    }
+}
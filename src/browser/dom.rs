@@ -26,6 +26,9 @@ use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::browser::shadow::ShadowQueryOptions;
 
 /// Information about a frame in the page's frame tree.
 ///
@@ -161,6 +164,16 @@ pub struct DomElement {
 
     /// Backend node ID for CDP operations.
     pub backend_node_id: Option<i64>,
+
+    /// Position in the results of the XPath query that found the element,
+    /// in document order. `selector` is then an XPath, not a CSS selector.
+    #[serde(default)]
+    pub xpath_index: Option<usize>,
+
+    /// Whether the element hosts an open shadow root, whose content
+    /// `querySelector` does not reach.
+    #[serde(default)]
+    pub shadow_root: bool,
 }
 
 impl DomElement {
@@ -178,6 +191,8 @@ impl DomElement {
             is_focusable: false,
             node_id: None,
             backend_node_id: None,
+            xpath_index: None,
+            shadow_root: false,
         }
     }
 
@@ -428,6 +443,75 @@ pub trait DomAccessor: Send + Sync {
     ///
     /// * `selector` - CSS selector for the element
     async fn is_element_visible(&self, selector: &str) -> Result<bool>;
+
+    /// Finds all elements matching an XPath expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `xpath` - XPath expression, e.g. `//table/tr[2]/td[1]`
+    ///
+    /// # Returns
+    ///
+    /// The matching elements in document order, with `xpath_index` set.
+    async fn query_xpath(&self, tab_id: Uuid, xpath: &str) -> Result<Vec<DomElement>>;
+
+    /// Finds the first element matching an XPath expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `xpath` - XPath expression
+    ///
+    /// # Returns
+    ///
+    /// The first matching element, or None if nothing matches.
+    async fn query_xpath_single(&self, tab_id: Uuid, xpath: &str) -> Result<Option<DomElement>>;
+
+    /// Finds elements inside the shadow DOM of host elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `host_selector` - CSS selector of the shadow hosts
+    /// * `inner_selector` - CSS selector applied inside each host's open shadow root
+    ///
+    /// # Returns
+    ///
+    /// The matching elements of all hosts; hosts without an open shadow
+    /// root contribute none.
+    async fn query_shadow(
+        &self,
+        tab_id: Uuid,
+        host_selector: &str,
+        inner_selector: &str,
+    ) -> Result<Vec<DomElement>>;
+
+    /// Finds elements matching a CSS selector in the document and all open
+    /// shadow roots, up to the default depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `selector` - CSS selector
+    async fn query_all_shadows(&self, tab_id: Uuid, selector: &str) -> Result<Vec<DomElement>> {
+        self.query_all_shadows_with_options(tab_id, selector, &ShadowQueryOptions::default())
+            .await
+    }
+
+    /// Like [`query_all_shadows`](Self::query_all_shadows), with explicit options.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `selector` - CSS selector
+    /// * `options` - Depth limit of the shadow root walk
+    async fn query_all_shadows_with_options(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        options: &ShadowQueryOptions,
+    ) -> Result<Vec<DomElement>>;
 }
 
 /// Mock DOM accessor for testing purposes.
@@ -440,6 +524,16 @@ pub struct MockDomAccessor {
 
     /// Mock JavaScript evaluation results.
     js_results: std::sync::RwLock<HashMap<String, JsValue>>,
+
+    /// Mock elements that can be "found" by XPath expressions.
+    xpath_results: std::sync::RwLock<HashMap<String, Vec<DomElement>>>,
+
+    /// Mock shadow DOM elements, keyed by host and inner selector.
+    shadow_results: std::sync::RwLock<HashMap<(String, String), Vec<DomElement>>>,
+
+    /// Mock elements of the whole shadow tree with their nesting level,
+    /// keyed by selector.
+    shadow_tree_results: std::sync::RwLock<HashMap<String, Vec<(usize, DomElement)>>>,
 }
 
 impl Default for MockDomAccessor {
@@ -454,6 +548,9 @@ impl MockDomAccessor {
         Self {
             elements: std::sync::RwLock::new(HashMap::new()),
             js_results: std::sync::RwLock::new(HashMap::new()),
+            xpath_results: std::sync::RwLock::new(HashMap::new()),
+            shadow_results: std::sync::RwLock::new(HashMap::new()),
+            shadow_tree_results: std::sync::RwLock::new(HashMap::new()),
         }
     }
 
@@ -468,6 +565,29 @@ impl MockDomAccessor {
         self.add_elements(selector, vec![element]);
     }
 
+    /// Adds mock elements for an XPath expression, in document order.
+    ///
+    /// The same elements are returned for every tab.
+    pub fn add_xpath_results(&self, xpath: &str, elements: Vec<DomElement>) {
+        let mut map = self.xpath_results.write().unwrap();
+        map.insert(xpath.to_string(), elements);
+    }
+
+    /// Adds mock elements found in the shadow roots of `host_selector`.
+    pub fn add_shadow_results(&self, host_selector: &str, inner_selector: &str, elements: Vec<DomElement>) {
+        let mut map = self.shadow_results.write().unwrap();
+        map.insert((host_selector.to_string(), inner_selector.to_string()), elements);
+    }
+
+    /// Adds mock elements of the whole shadow tree for a selector.
+    ///
+    /// Each element comes with its shadow root nesting level (0 for the
+    /// document), so queries return only those within their depth limit.
+    pub fn add_shadow_tree_results(&self, selector: &str, elements: Vec<(usize, DomElement)>) {
+        let mut map = self.shadow_tree_results.write().unwrap();
+        map.insert(selector.to_string(), elements);
+    }
+
     /// Sets a mock JavaScript result for a script.
     pub fn set_js_result(&self, script: &str, result: JsValue) {
         let mut map = self.js_results.write().unwrap();
@@ -486,6 +606,9 @@ impl MockDomAccessor {
     pub fn clear(&self) {
         self.elements.write().unwrap().clear();
         self.js_results.write().unwrap().clear();
+        self.xpath_results.write().unwrap().clear();
+        self.shadow_results.write().unwrap().clear();
+        self.shadow_tree_results.write().unwrap().clear();
     }
 }
 
@@ -535,6 +658,57 @@ impl DomAccessor for MockDomAccessor {
         let element = self.find_element(selector).await?;
         Ok(element.map(|e| e.is_visible).unwrap_or(false))
     }
+
+    async fn query_xpath(&self, _tab_id: Uuid, xpath: &str) -> Result<Vec<DomElement>> {
+        let map = self.xpath_results.read().unwrap();
+        Ok(map
+            .get(xpath)
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut element)| {
+                element.xpath_index = Some(index);
+                element
+            })
+            .collect())
+    }
+
+    async fn query_xpath_single(&self, tab_id: Uuid, xpath: &str) -> Result<Option<DomElement>> {
+        Ok(self.query_xpath(tab_id, xpath).await?.into_iter().next())
+    }
+
+    async fn query_shadow(
+        &self,
+        _tab_id: Uuid,
+        host_selector: &str,
+        inner_selector: &str,
+    ) -> Result<Vec<DomElement>> {
+        let map = self.shadow_results.read().unwrap();
+        Ok(map
+            .get(&(host_selector.to_string(), inner_selector.to_string()))
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn query_all_shadows_with_options(
+        &self,
+        _tab_id: Uuid,
+        selector: &str,
+        options: &ShadowQueryOptions,
+    ) -> Result<Vec<DomElement>> {
+        let map = self.shadow_tree_results.read().unwrap();
+        Ok(map
+            .get(selector)
+            .map(|elements| {
+                elements
+                    .iter()
+                    .filter(|(depth, _)| *depth <= options.max_depth)
+                    .map(|(_, element)| element.clone())
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
 }
 
 #[cfg(test)]
@@ -648,6 +822,83 @@ mod tests {
         assert_eq!(text, Some("Test Content".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_mock_dom_accessor_xpath() {
+        let accessor = MockDomAccessor::new();
+        let tab_id = Uuid::new_v4();
+        accessor.add_xpath_results(
+            "//table/tr[2]/td[1]",
+            vec![MockDomAccessor::create_mock_element("(//table/tr[2]/td[1])[1]", "td", "Bob")],
+        );
+        accessor.add_xpath_results(
+            "//table/tr/td[@class='price']",
+            vec![
+                MockDomAccessor::create_mock_element("(//table/tr/td[@class='price'])[1]", "td", "3.50"),
+                MockDomAccessor::create_mock_element("(//table/tr/td[@class='price'])[2]", "td", "7.00"),
+            ],
+        );
+
+        let cell = accessor
+            .query_xpath_single(tab_id, "//table/tr[2]/td[1]")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cell.text_content, "Bob");
+        assert_eq!(cell.xpath_index, Some(0));
+
+        let prices = accessor
+            .query_xpath(tab_id, "//table/tr/td[@class='price']")
+            .await
+            .unwrap();
+        let texts: Vec<_> = prices.iter().map(|e| (e.xpath_index, e.text_content.as_str())).collect();
+        assert_eq!(texts, vec![(Some(0), "3.50"), (Some(1), "7.00")]);
+
+        assert!(accessor.query_xpath(tab_id, "//nav").await.unwrap().is_empty());
+        assert!(accessor.query_xpath_single(tab_id, "//nav").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_mock_dom_accessor_shadow() {
+        let accessor = MockDomAccessor::new();
+        let tab_id = Uuid::new_v4();
+
+        let mut host = MockDomAccessor::create_mock_element("x-app", "x-app", "");
+        host.shadow_root = true;
+        accessor.add_element("x-app", host);
+        accessor.add_shadow_results(
+            "x-app",
+            "button",
+            vec![MockDomAccessor::create_mock_element("x-app >>> button", "button", "Buy")],
+        );
+        accessor.add_shadow_tree_results(
+            "button",
+            vec![
+                (0, MockDomAccessor::create_mock_element("button", "button", "Menu")),
+                (1, MockDomAccessor::create_mock_element("x-app >>> button", "button", "Buy")),
+                (3, MockDomAccessor::create_mock_element("x-app >>> x-card >>> x-row >>> button", "button", "Remove")),
+            ],
+        );
+
+        assert!(accessor.find_element("x-app").await.unwrap().unwrap().shadow_root);
+        // Light DOM queries do not reach into the shadow root
+        assert!(!accessor.element_exists("button").await.unwrap());
+
+        let inner = accessor.query_shadow(tab_id, "x-app", "button").await.unwrap();
+        assert_eq!(inner.len(), 1);
+        assert_eq!(inner[0].text_content, "Buy");
+        assert!(accessor.query_shadow(tab_id, "x-app", "input").await.unwrap().is_empty());
+
+        let all = accessor.query_all_shadows(tab_id, "button").await.unwrap();
+        let texts: Vec<_> = all.iter().map(|e| e.text_content.as_str()).collect();
+        assert_eq!(texts, vec!["Menu", "Buy", "Remove"]);
+
+        let shallow = accessor
+            .query_all_shadows_with_options(tab_id, "button", &ShadowQueryOptions::default().max_depth(1))
+            .await
+            .unwrap();
+        assert_eq!(shallow.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_mock_dom_accessor_js() {
         let accessor = MockDomAccessor::new();
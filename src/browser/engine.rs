@@ -22,15 +22,278 @@
 //! }
 //! ```
 
-use crate::browser::dom::FrameInfo;
+use crate::browser::events::BrowserEvent;
+use crate::browser::binding::validate_binding_name;
+use crate::browser::computed_style::resolve_style_properties;
+use crate::browser::consent::default_consent_selectors;
+use crate::browser::content::enforce_max_size;
+use crate::browser::cookies::Cookie;
+use crate::browser::device::DeviceMetrics;
+use crate::browser::dialog::BeforeUnloadPolicy;
+use crate::browser::dom::{BoundingBox, FrameInfo};
+use crate::browser::error_capture::write_error_capture;
+use crate::browser::interceptor::{
+    apply_interceptor, InterceptedRequest, InterceptorSlot, RequestInterceptor, BLOCKED_BY_CLIENT,
+};
+use crate::browser::har::{reason_phrase, HarLog, HarRecorder, HarResponse};
+use crate::browser::event_buffer::{EventBuffer, SharedEventBuffer, DEFAULT_EVENT_BUFFER_CAPACITY};
+use crate::browser::js_call::EvalOptions;
+use crate::browser::mutation::{mutation_timeout, MutationInfo, MutationKinds};
+use crate::browser::network::{record_failed_request, FailedRequest, RESOURCE_TIMED_OUT};
+use crate::browser::page_wait::{poll_condition, wait_timeout, WaitCondition};
+use crate::browser::permissions::{normalize_origin, Permission, PermissionGrants};
+use crate::browser::redirect::{RedirectChain, DEFAULT_MAX_REDIRECTS};
+use crate::browser::referrer::{validate_referrer, ReferrerPolicy};
+use crate::browser::response_capture::{CapturedResponse, ResponseCapture, DEFAULT_CAPTURE_MAX_BYTES};
+use crate::browser::screenshot::{
+    capture_mock_screenshot, element_clip_region, Screenshot, ScreenshotOptions,
+};
+use crate::browser::scroll::{
+    parse_scrolled_box, scroll_into_view_args, ScrollAlign, SCROLL_INTO_VIEW_FUNCTION,
+};
 use crate::browser::tab::Tab;
+use crate::browser::throttle::{NetworkThrottleConfig, INTERNET_DISCONNECTED};
+use crate::browser::url_pattern::UrlPattern;
+use crate::browser::wait_function::{poll_predicate, predicate_function_body};
+use crate::browser::warmup::WarmupOptions;
+use crate::config::{pac_data_url, ProxyConfig, ProxyRule};
+use crate::error::BrowserError;
+use crate::retry::RetryPolicy;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, RwLock};
 use uuid::Uuid;
 
+/// Capacity of the engine event broadcast channel.
+pub const ENGINE_EVENT_CAPACITY: usize = 256;
+
+/// Polling backoff used when waiting for a tab to become ready.
+///
+/// The first poll happens after `initial_ms`; each subsequent interval is
+/// multiplied by `factor` until it reaches `max_ms`. Short waits stay
+/// responsive while multi-second waits across many tabs poll far less often.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PollBackoff {
+    /// First polling interval in milliseconds.
+    pub initial_ms: u64,
+
+    /// Upper bound for the polling interval in milliseconds.
+    pub max_ms: u64,
+
+    /// Growth factor applied to the interval after each poll (>= 1.0).
+    pub factor: f64,
+}
+
+impl Default for PollBackoff {
+    fn default() -> Self {
+        Self {
+            initial_ms: 10,
+            max_ms: 200,
+            factor: 1.5,
+        }
+    }
+}
+
+impl PollBackoff {
+    /// Creates a backoff ramping from `initial_ms` to `max_ms` by `factor`.
+    pub fn new(initial_ms: u64, max_ms: u64, factor: f64) -> Self {
+        Self {
+            initial_ms,
+            max_ms,
+            factor,
+        }
+    }
+
+    /// Returns the first polling interval, capped at the maximum.
+    pub fn initial(&self) -> Duration {
+        Duration::from_millis(self.initial_ms.min(self.max_ms).max(1))
+    }
+
+    /// Returns the interval following `current`.
+    ///
+    /// The result never decreases and never exceeds `max_ms`. Factors below
+    /// 1.0 are treated as 1.0 (constant polling).
+    pub fn next(&self, current: Duration) -> Duration {
+        let factor = if self.factor.is_finite() {
+            self.factor.max(1.0)
+        } else {
+            1.0
+        };
+        let grown = (current.as_millis() as f64 * factor).ceil() as u64;
+        let cap = self.max_ms.max(1);
+        Duration::from_millis(grown.max(current.as_millis() as u64).min(cap))
+    }
+}
+
+/// HTTP cache behaviour of the browser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheMode {
+    /// Chromium's regular disk cache in the per-process profile directory.
+    #[default]
+    Default,
+    /// No HTTP cache: the profile has no disk cache (like an incognito
+    /// context) and Chromium runs with `--disable-cache`.
+    Disabled,
+    /// The cache is kept, but every navigation skips it
+    /// (reload-ignore-cache semantics), so pages are always fetched fresh.
+    ForceRefresh,
+}
+
+impl CacheMode {
+    /// Chromium command line switches applied for this mode.
+    pub fn command_line_switches(self) -> &'static [&'static str] {
+        match self {
+            CacheMode::Disabled => &["disable-cache"],
+            CacheMode::Default | CacheMode::ForceRefresh => &[],
+        }
+    }
+
+    /// Whether the browser profile keeps a disk cache (CEF `cache_path`).
+    ///
+    /// Without one CEF keeps cache data in memory only.
+    pub fn uses_disk_cache(self) -> bool {
+        self != CacheMode::Disabled
+    }
+
+    /// Whether a navigation with `options` skips the cache.
+    pub fn bypasses_cache(self, options: &NavigateOptions) -> bool {
+        self == CacheMode::ForceRefresh || options.bypass_cache
+    }
+}
+
+/// Per-navigation options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavigateOptions {
+    /// Load the page and its sub-resources from the network even if cached.
+    pub bypass_cache: bool,
+
+    /// Maximum number of redirects before the navigation is aborted.
+    ///
+    /// See [`RedirectChain`] for what counts as a redirect.
+    pub max_redirects: u32,
+
+    /// `Referer` sent with the top-level request, subject to the tab's
+    /// [`ReferrerPolicy`]. Must be an absolute `http(s)` URL.
+    pub referrer: Option<String>,
+}
+
+impl Default for NavigateOptions {
+    fn default() -> Self {
+        Self {
+            bypass_cache: false,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            referrer: None,
+        }
+    }
+}
+
+impl NavigateOptions {
+    /// Skips the HTTP cache for this navigation.
+    pub fn bypass_cache(mut self) -> Self {
+        self.bypass_cache = true;
+        self
+    }
+
+    /// Sets the maximum number of redirects.
+    pub fn max_redirects(mut self, max_redirects: u32) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
+    /// Sends `referrer` as the `Referer` of the navigation.
+    pub fn referrer(mut self, referrer: impl Into<String>) -> Self {
+        self.referrer = Some(referrer.into());
+        self
+    }
+}
+
+/// Viewport geometry of a tab.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Viewport {
+    /// Width in CSS pixels.
+    pub width: u32,
+
+    /// Height in CSS pixels.
+    pub height: u32,
+
+    /// Device pixels per CSS pixel (`window.devicePixelRatio`).
+    pub device_scale_factor: f64,
+}
+
+impl Viewport {
+    /// Creates a viewport with a device scale factor of 1.0.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            device_scale_factor: 1.0,
+        }
+    }
+
+    /// Sets the device scale factor.
+    pub fn with_device_scale_factor(mut self, device_scale_factor: f64) -> Self {
+        self.device_scale_factor = device_scale_factor;
+        self
+    }
+
+    /// The viewport new tabs get from `config` before any
+    /// [`BrowserEngine::set_default_viewport`] call: the emulated device's
+    /// if there is one, otherwise the window size.
+    pub fn from_config(config: &BrowserConfig) -> Self {
+        match &config.device_emulation {
+            Some(device) => device.viewport(),
+            None => Self::new(config.window_size.0, config.window_size.1),
+        }
+    }
+
+    /// Rejects empty viewports and non-positive scale factors.
+    pub fn validate(&self) -> Result<(), BrowserError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(BrowserError::InvalidRequest(format!(
+                "Viewport must not be empty: {}x{}",
+                self.width, self.height
+            )));
+        }
+        if !self.device_scale_factor.is_finite() || self.device_scale_factor <= 0.0 {
+            return Err(BrowserError::InvalidRequest(format!(
+                "Invalid device scale factor: {}",
+                self.device_scale_factor
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Optional features an engine implements.
+///
+/// Returned by [`BrowserEngine::capabilities`] so callers can pick a fallback
+/// instead of probing for "not supported" errors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineCapabilities {
+    /// [`BrowserEngine::set_cpu_throttling`] slows down the page's CPU.
+    pub cpu_throttling: bool,
+    /// [`BrowserEngine::grant_permissions`] pre-grants permissions to origins.
+    pub permission_grants: bool,
+}
+
+/// Rejects CPU throttling rates below 1 (no slowdown) and non-finite rates.
+pub fn validate_cpu_throttling_rate(rate: f64) -> Result<(), BrowserError> {
+    if !rate.is_finite() || rate < 1.0 {
+        return Err(BrowserError::InvalidRequest(format!(
+            "CPU throttling rate must be at least 1, got {}",
+            rate
+        )));
+    }
+    Ok(())
+}
+
 /// Configuration options for browser engine initialization.
 #[derive(Debug, Clone)]
 pub struct BrowserConfig {
@@ -46,15 +309,35 @@ pub struct BrowserConfig {
     /// Proxy server URL (e.g., "http://proxy.example.com:8080").
     pub proxy: Option<String>,
 
+    /// Per-origin proxy rules; requests matching no rule use `proxy`.
+    pub proxy_rules: Vec<ProxyRule>,
+
     /// Path to browser executable. If None, uses system default.
     pub executable_path: Option<String>,
 
     /// Additional browser launch arguments.
     pub args: Vec<String>,
 
-    /// Timeout for browser operations in milliseconds.
+    /// Timeout for browser operations in milliseconds. Also bounds
+    /// navigations: a main document that has not loaded by then fails them.
     pub timeout_ms: u64,
 
+    /// Timeout per sub-resource in milliseconds. A slower `fetch`, XHR or
+    /// image is aborted and reported as a failed request with
+    /// [`RESOURCE_TIMED_OUT`](crate::browser::network::RESOURCE_TIMED_OUT),
+    /// but the navigation still succeeds. `None` (default) disables it.
+    pub resource_timeout_ms: Option<u64>,
+
+    /// Report failed sub-resource requests (`fetch`, XHR, images, scripts,
+    /// ...) through a page script patching `fetch` and XHR. Off by default:
+    /// only failures of the documents themselves, which the engine sees
+    /// without touching the page, are recorded.
+    pub observe_failed_subresources: bool,
+
+    /// How long `execute_js` waits for a script's result in milliseconds
+    /// before failing with a timeout.
+    pub js_timeout_ms: u64,
+
     /// Enable browser DevTools.
     pub devtools: bool,
 
@@ -70,6 +353,72 @@ pub struct BrowserConfig {
     /// External stealth configuration. If set, the CEF engine will use this
     /// instead of generating its own. Ensures HTTP UA and JS UA are identical.
     pub stealth_config: Option<crate::stealth::StealthConfig>,
+
+    /// Polling backoff used by `wait_for_ready`.
+    pub ready_poll_backoff: PollBackoff,
+
+    /// Directory for screenshot-on-error captures. When set, a failed tab
+    /// command writes the tab's current frame plus a sidecar JSON with the
+    /// error (see [`error_capture`](crate::browser::error_capture)). `None`
+    /// disables capturing.
+    pub screenshot_on_error: Option<PathBuf>,
+
+    /// Pause before the first input action after a navigation, drawn
+    /// uniformly from this range. `None` (default) acts immediately.
+    pub post_navigation_dwell: Option<RangeInclusive<Duration>>,
+
+    /// Seed for the RNG behind the engine's input randomness (such as the
+    /// post-navigation dwell); `None` seeds from OS entropy.
+    pub input_rng_seed: Option<u64>,
+
+    /// Cap on the response body bytes kept per tab by response capture;
+    /// the oldest captured responses are evicted first.
+    pub response_capture_max_bytes: usize,
+
+    /// HTTP cache behaviour.
+    pub cache_mode: CacheMode,
+
+    /// Retry policy for transient failures such as CDP connections.
+    pub retry_policy: RetryPolicy,
+
+    /// Helper executable CEF launches for renderer/GPU subprocesses when not
+    /// in single-process mode. `None` re-launches the current executable.
+    pub cef_subprocess_path: Option<PathBuf>,
+
+    /// Click away cookie-consent banners after every top-level page load
+    /// (see [`consent`](crate::browser::consent)). Off by default.
+    pub auto_dismiss_consent: bool,
+
+    /// Accept-button selectors tried by automatic consent dismissal before
+    /// falling back to button texts. Defaults to
+    /// [`DEFAULT_CONSENT_SELECTORS`](crate::browser::DEFAULT_CONSENT_SELECTORS).
+    pub consent_selectors: Vec<String>,
+
+    /// Run all CEF processes inside the browser process (`--single-process`).
+    ///
+    /// Needs no helper executable, but Chromium does not support this mode:
+    /// a renderer crash takes the whole browser down. Enabled by default
+    /// because the engine's JS result path is built around it.
+    pub single_process: bool,
+
+    /// How `close_tab` treats a page's `beforeunload` handler. Defaults to
+    /// force-closing so cleanup never hangs on a page.
+    pub before_unload_policy: BeforeUnloadPolicy,
+
+    /// Interceptor installed when the engine starts; see
+    /// [`BrowserEngine::set_request_interceptor`]. `None` (default) sends
+    /// requests untouched.
+    pub request_interceptor: Option<Arc<dyn RequestInterceptor>>,
+
+    /// Simulated connection speed (see [`throttle`](crate::browser::throttle)).
+    /// `None` (default) leaves the network unthrottled.
+    pub network_throttle: Option<NetworkThrottleConfig>,
+
+    /// Emulated device (see [`device`](crate::browser::device)). Sets the
+    /// viewport and device scale factor of new tabs and the screen the
+    /// stealth scripts report. `None` (default) renders at `window_size`
+    /// with a scale factor of 1.
+    pub device_emulation: Option<DeviceMetrics>,
 }
 
 impl Default for BrowserConfig {
@@ -79,14 +428,33 @@ impl Default for BrowserConfig {
             window_size: (1920, 1080),
             user_agent: None,
             proxy: None,
+            proxy_rules: Vec::new(),
             executable_path: None,
             args: Vec::new(),
             timeout_ms: 30_000,
+            resource_timeout_ms: None,
+            observe_failed_subresources: false,
+            js_timeout_ms: 5_000,
             devtools: false,
             ignore_certificate_errors: false,
             download_path: None,
             cdp_port: None,
             stealth_config: None,
+            ready_poll_backoff: PollBackoff::default(),
+            screenshot_on_error: None,
+            post_navigation_dwell: None,
+            input_rng_seed: None,
+            response_capture_max_bytes: DEFAULT_CAPTURE_MAX_BYTES,
+            cache_mode: CacheMode::Default,
+            retry_policy: RetryPolicy::default(),
+            cef_subprocess_path: None,
+            auto_dismiss_consent: false,
+            consent_selectors: default_consent_selectors(),
+            single_process: true,
+            before_unload_policy: BeforeUnloadPolicy::ForceClose,
+            request_interceptor: None,
+            network_throttle: None,
+            device_emulation: None,
         }
     }
 }
@@ -127,6 +495,24 @@ impl BrowserConfig {
         self
     }
 
+    /// Sets the per sub-resource timeout in milliseconds.
+    pub fn resource_timeout_ms(mut self, timeout: u64) -> Self {
+        self.resource_timeout_ms = Some(timeout);
+        self
+    }
+
+    /// Enables or disables reporting of failed sub-resource requests.
+    pub fn observe_failed_subresources(mut self, enabled: bool) -> Self {
+        self.observe_failed_subresources = enabled;
+        self
+    }
+
+    /// Sets how long `execute_js` waits for a result in milliseconds.
+    pub fn js_timeout_ms(mut self, timeout: u64) -> Self {
+        self.js_timeout_ms = timeout;
+        self
+    }
+
     /// Adds a browser launch argument.
     pub fn add_arg(mut self, arg: impl Into<String>) -> Self {
         self.args.push(arg.into());
@@ -144,6 +530,137 @@ impl BrowserConfig {
         self.cdp_port = port;
         self
     }
+
+    /// Sets the polling backoff used while waiting for tabs to become ready.
+    pub fn ready_poll_backoff(mut self, backoff: PollBackoff) -> Self {
+        self.ready_poll_backoff = backoff;
+        self
+    }
+
+    /// Sets the retry policy for transient failures.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Enables screenshot-on-error captures into the given directory.
+    pub fn screenshot_on_error(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.screenshot_on_error = Some(dir.into());
+        self
+    }
+
+    /// Sets the humanized pause taken before the first input after a navigation.
+    pub fn post_navigation_dwell(mut self, dwell: RangeInclusive<Duration>) -> Self {
+        self.post_navigation_dwell = Some(dwell);
+        self
+    }
+
+    /// Seeds the engine's input randomness for reproducible runs.
+    pub fn input_rng_seed(mut self, seed: u64) -> Self {
+        self.input_rng_seed = Some(seed);
+        self
+    }
+
+    /// Sets the per-tab byte cap for captured response bodies.
+    pub fn response_capture_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.response_capture_max_bytes = max_bytes;
+        self
+    }
+
+    /// Adds a per-origin proxy rule.
+    pub fn proxy_rule(mut self, rule: ProxyRule) -> Self {
+        self.proxy_rules.push(rule);
+        self
+    }
+
+    /// Returns the PAC `data:` URL routing requests by `proxy_rules`.
+    ///
+    /// `None` when no rules are configured. Requests matching no rule fall
+    /// back to `proxy`.
+    pub fn proxy_pac_url(&self) -> Result<Option<String>, BrowserError> {
+        if self.proxy_rules.is_empty() {
+            return Ok(None);
+        }
+        let default = self
+            .proxy
+            .as_deref()
+            .map(ProxyConfig::from_url)
+            .transpose()?;
+        Ok(Some(pac_data_url(&self.proxy_rules, default.as_ref())))
+    }
+
+    /// Sets the CEF subprocess helper executable.
+    pub fn cef_subprocess_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cef_subprocess_path = Some(path.into());
+        self
+    }
+
+    /// Enables or disables CEF single-process mode.
+    pub fn single_process(mut self, single_process: bool) -> Self {
+        self.single_process = single_process;
+        self
+    }
+
+    /// Returns the subprocess helper CEF should launch, checking it exists.
+    ///
+    /// `Ok(None)` means no explicit helper is needed: either single-process
+    /// mode is on or CEF re-launches the current executable.
+    pub fn resolve_cef_subprocess(&self) -> Result<Option<PathBuf>, BrowserError> {
+        if self.single_process {
+            return Ok(None);
+        }
+        match &self.cef_subprocess_path {
+            Some(path) if path.is_file() => Ok(Some(path.clone())),
+            Some(path) => Err(BrowserError::ConfigError(format!(
+                "CEF subprocess executable not found: {} (ship the helper or enable single_process)",
+                path.display()
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Throttles the network to the given connection speed.
+    pub fn with_throttle(mut self, throttle: NetworkThrottleConfig) -> Self {
+        self.network_throttle = Some(throttle);
+        self
+    }
+
+    /// Emulates `device`; the window size becomes its viewport.
+    pub fn device_emulation(mut self, device: DeviceMetrics) -> Self {
+        self.window_size = (device.width, device.height);
+        self.device_emulation = Some(device);
+        self
+    }
+
+    /// Sets the HTTP cache behaviour.
+    pub fn cache_mode(mut self, mode: CacheMode) -> Self {
+        self.cache_mode = mode;
+        self
+    }
+
+    /// Sets how `close_tab` treats a page's `beforeunload` handler.
+    pub fn before_unload_policy(mut self, policy: BeforeUnloadPolicy) -> Self {
+        self.before_unload_policy = policy;
+        self
+    }
+
+    /// Enables or disables automatic cookie-consent dismissal.
+    pub fn auto_dismiss_consent(mut self, enabled: bool) -> Self {
+        self.auto_dismiss_consent = enabled;
+        self
+    }
+
+    /// Replaces the accept-button selectors used for consent dismissal.
+    pub fn consent_selectors(mut self, selectors: Vec<String>) -> Self {
+        self.consent_selectors = selectors;
+        self
+    }
+
+    /// Installs a request interceptor from the start.
+    pub fn with_request_interceptor(mut self, interceptor: Arc<dyn RequestInterceptor>) -> Self {
+        self.request_interceptor = Some(interceptor);
+        self
+    }
 }
 
 /// Trait defining the browser engine interface.
@@ -193,6 +710,11 @@ pub trait BrowserEngine: Send + Sync {
 
     /// Closes a browser tab by its ID.
     ///
+    /// A `beforeunload` handler of the page is treated according to
+    /// [`BrowserConfig::before_unload_policy`]; under
+    /// [`BeforeUnloadPolicy::Cancel`] a prompting page keeps the tab open and
+    /// the close fails.
+    ///
     /// # Arguments
     ///
     /// * `tab_id` - The UUID of the tab to close
@@ -223,286 +745,3473 @@ pub trait BrowserEngine: Send + Sync {
     /// Returns the browser configuration.
     fn config(&self) -> &BrowserConfig;
 
+    /// Returns the optional features this engine supports.
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities::default()
+    }
+
     /// Checks if the browser engine is running.
     async fn is_running(&self) -> bool;
 
-    /// Gets the frame tree for a tab.
+    /// Subscribes to events emitted by the engine itself.
     ///
-    /// Returns information about all frames (main frame and iframes) in the page.
-    /// This is useful for interacting with cross-origin iframes.
+    /// Engines that do not produce events return `None`.
+    fn subscribe_events(&self) -> Option<broadcast::Receiver<BrowserEvent>> {
+        None
+    }
+
+    /// Returns recently emitted engine events, oldest first.
+    ///
+    /// Events are kept in a bounded ring buffer in the order of their
+    /// sequence numbers; the oldest are dropped once it is full.
     ///
     /// # Arguments
     ///
-    /// * `tab_id` - The UUID of the tab to get frames from
+    /// * `tab_id` - Only return events of this tab (`None` for all events)
+    /// * `limit` - Maximum number of events, counted from the newest
+    async fn get_recent_events(
+        &self,
+        tab_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<BrowserEvent>> {
+        let _ = (tab_id, limit);
+        Err(anyhow!("Event buffer not supported by this engine"))
+    }
+
+    /// Removes buffered events.
     ///
-    /// # Returns
+    /// Sequence numbers are not reset, so later events still sort after
+    /// everything returned before.
     ///
-    /// A Result containing a vector of FrameInfo structs.
-    async fn get_frame_tree(&self, tab_id: Uuid) -> Result<Vec<FrameInfo>> {
-        // Default implementation returns empty vec for engines that don't support frames
+    /// # Arguments
+    ///
+    /// * `tab_id` - Only remove events of this tab (`None` for all events)
+    async fn clear_event_buffer(&self, tab_id: Option<Uuid>) -> Result<()> {
         let _ = tab_id;
-        Ok(Vec::new())
+        Err(anyhow!("Event buffer not supported by this engine"))
     }
 
-    /// Evaluates JavaScript in a specific frame.
+    /// Brings a tab to the foreground and gives it input focus.
     ///
-    /// This allows executing JavaScript in cross-origin iframes by targeting
-    /// a specific frame ID obtained from get_frame_tree().
+    /// Emits [`BrowserEvent::ActiveTabChanged`] when the active tab changes.
+    /// Chromium may throttle timers (`setTimeout`, `requestAnimationFrame`)
+    /// in tabs that are not in the foreground, so timing-sensitive work
+    /// should run in the active tab.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab to activate
+    async fn set_active_tab(&self, tab_id: Uuid) -> Result<()> {
+        let _ = tab_id;
+        Err(anyhow!("Active tab switching not supported by this engine"))
+    }
+
+    /// Returns the currently active (foreground) tab, if any.
+    async fn active_tab(&self) -> Option<Uuid> {
+        None
+    }
+
+    /// Returns the viewport new tabs are created with.
+    async fn default_viewport(&self) -> Viewport {
+        Viewport::from_config(self.config())
+    }
+
+    /// Changes the viewport new tabs are created with.
+    ///
+    /// With `apply_to_existing` every open tab is resized as by
+    /// [`resize_tab`](Self::resize_tab), emitting
+    /// [`BrowserEvent::ViewportChanged`] for each of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `viewport` - The new default viewport
+    /// * `apply_to_existing` - Whether to resize the tabs that are already open
+    async fn set_default_viewport(&self, viewport: Viewport, apply_to_existing: bool) -> Result<()> {
+        let _ = (viewport, apply_to_existing);
+        Err(anyhow!("Viewport control not supported by this engine"))
+    }
+
+    /// Returns the current viewport of a tab.
     ///
     /// # Arguments
     ///
     /// * `tab_id` - The UUID of the tab
-    /// * `frame_id` - The frame ID to evaluate in (from get_frame_tree)
-    /// * `script` - JavaScript code to execute
+    async fn get_viewport(&self, tab_id: Uuid) -> Result<Viewport> {
+        let _ = tab_id;
+        Err(anyhow!("Viewport control not supported by this engine"))
+    }
+
+    /// Resizes the viewport of a tab and emits [`BrowserEvent::ViewportChanged`].
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A Result containing the evaluation result as a JSON value.
-    async fn evaluate_in_frame(
-        &self,
-        tab_id: Uuid,
-        frame_id: &str,
-        script: &str,
-    ) -> Result<serde_json::Value> {
-        // Default implementation returns an error
-        let _ = (tab_id, frame_id, script);
-        Err(anyhow!("Frame evaluation not supported by this engine"))
+    /// * `tab_id` - The UUID of the tab
+    /// * `viewport` - The new viewport
+    async fn resize_tab(&self, tab_id: Uuid, viewport: Viewport) -> Result<()> {
+        let _ = (tab_id, viewport);
+        Err(anyhow!("Viewport control not supported by this engine"))
     }
 
-    /// Captures a DOM snapshot with bounding-box information for all visible elements.
+    /// Slows down the tab's CPU to emulate a low-end device.
     ///
-    /// Executes JavaScript in the tab to traverse the DOM tree, collecting element
-    /// metadata, bounding rectangles, ARIA roles, and interactivity flags. The result
-    /// is used by the vision overlay system to map screen regions to DOM elements.
+    /// A rate of 4 makes scripts and layout run four times slower; 1
+    /// disables throttling. Only available when
+    /// [`capabilities`](Self::capabilities) reports `cpu_throttling`.
     ///
     /// # Arguments
     ///
-    /// * `tab_id` - The UUID of the tab to snapshot
-    /// * `config` - Snapshot configuration (max nodes, include text)
+    /// * `tab_id` - The UUID of the tab
+    /// * `rate` - Slowdown factor, at least 1
+    async fn set_cpu_throttling(&self, tab_id: Uuid, rate: f64) -> Result<()> {
+        let _ = (tab_id, rate);
+        Err(anyhow!("CPU throttling not supported by this engine"))
+    }
+
+    /// Grants `permissions` to `origin` without prompting, rejecting all
+    /// others for that origin.
     ///
-    /// # Returns
+    /// Replaces earlier grants for the same origin; an empty list revokes
+    /// them. Only available when [`capabilities`](Self::capabilities)
+    /// reports `permission_grants`.
     ///
-    /// A Result containing the [`DomSnapshot`] or an error.
-    async fn dom_snapshot(
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `origin` - Origin such as `https://maps.example.com`; a page URL is
+    ///   reduced to its origin
+    /// * `permissions` - Permissions to grant
+    async fn grant_permissions(
         &self,
         tab_id: Uuid,
-        config: &crate::browser::dom_snapshot::SnapshotConfig,
-    ) -> Result<crate::browser::dom_snapshot::DomSnapshot> {
-        // Default implementation returns an error for engines that don't support snapshots
-        let _ = (tab_id, config);
-        Err(anyhow!("DOM snapshot not supported by this engine"))
+        origin: &str,
+        permissions: Vec<Permission>,
+    ) -> Result<()> {
+        let _ = (tab_id, origin, permissions);
+        Err(anyhow!("Permission grants not supported by this engine"))
     }
-}
-
-/// Mock browser engine implementation for testing purposes.
-///
-/// This implementation simulates browser behavior without actually
-/// launching a browser, making it suitable for unit tests.
-pub struct MockBrowserEngine {
-    config: BrowserConfig,
-    tabs: Arc<RwLock<HashMap<Uuid, Tab>>>,
-    is_running: Arc<RwLock<bool>>,
-}
 
-#[async_trait]
-impl BrowserEngine for MockBrowserEngine {
-    async fn new(config: BrowserConfig) -> Result<Self> {
-        Ok(Self {
-            config,
-            tabs: Arc::new(RwLock::new(HashMap::new())),
-            is_running: Arc::new(RwLock::new(true)),
+    /// Revokes all permissions granted with
+    /// [`grant_permissions`](Self::grant_permissions).
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    async fn reset_permissions(&self, tab_id: Uuid) -> Result<()> {
+        let _ = tab_id;
+        Err(anyhow!("Permission grants not supported by this engine"))
+    }
+
+    /// Sets how much of a navigation's referrer the tab sends.
+    ///
+    /// Applies to later navigations with
+    /// [`NavigateOptions::referrer`]; the default is
+    /// [`ReferrerPolicy::StrictOriginWhenCrossOrigin`].
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `policy` - Referrer policy for top-level navigations
+    async fn set_referrer_policy(&self, tab_id: Uuid, policy: ReferrerPolicy) -> Result<()> {
+        let _ = (tab_id, policy);
+        Err(anyhow!("Referrer policy not supported by this engine"))
+    }
+
+    /// Writes a screenshot-on-error capture for a failure the caller
+    /// detected itself, e.g. an unmet test expectation.
+    ///
+    /// Returns the path of the capture's sidecar JSON, or `None` when
+    /// [`BrowserConfig::screenshot_on_error`] is not set.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `operation` - Name of the failed operation
+    /// * `error` - Description of the failure
+    async fn capture_failure(
+        &self,
+        tab_id: Uuid,
+        operation: &str,
+        error: &str,
+    ) -> Result<Option<PathBuf>> {
+        let _ = (tab_id, operation, error);
+        Ok(None)
+    }
+
+    /// Returns the network requests that failed since the tab's last
+    /// top-level navigation.
+    ///
+    /// A request counts as failed if it received an HTTP status >= 400 or
+    /// never produced a response. Each failure is also published as
+    /// [`BrowserEvent::RequestFailed`].
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    async fn get_failed_requests(&self, tab_id: Uuid) -> Result<Vec<FailedRequest>> {
+        let _ = tab_id;
+        Err(anyhow!("Failed request tracking not supported by this engine"))
+    }
+
+    /// Starts capturing the bodies of responses whose URL matches `url_pattern`.
+    ///
+    /// Patterns accumulate per tab and stay active across navigations.
+    /// Captured bodies are kept until the tab is closed, bounded by
+    /// [`BrowserConfig::response_capture_max_bytes`] with the oldest
+    /// responses evicted first.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `url_pattern` - URL pattern (exact, glob or `regex:`) of responses to capture
+    async fn enable_response_capture(&self, tab_id: Uuid, url_pattern: &str) -> Result<()> {
+        let _ = (tab_id, url_pattern);
+        Err(anyhow!("Response capture not supported by this engine"))
+    }
+
+    /// Returns the responses captured for a tab, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    async fn get_captured_responses(&self, tab_id: Uuid) -> Result<Vec<CapturedResponse>> {
+        let _ = tab_id;
+        Err(anyhow!("Response capture not supported by this engine"))
+    }
+
+    /// Routes every outbound request of every tab through `interceptor`.
+    ///
+    /// Replaces the previously installed interceptor. Blocked requests fail
+    /// with [`BLOCKED_BY_CLIENT`](crate::browser::BLOCKED_BY_CLIENT) and are
+    /// reported like other failed requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `interceptor` - Decides what happens to each request
+    async fn set_request_interceptor(&self, interceptor: Arc<dyn RequestInterceptor>) -> Result<()> {
+        let _ = interceptor;
+        Err(anyhow!("Request interception not supported by this engine"))
+    }
+
+    /// Removes the interceptor installed with
+    /// [`set_request_interceptor`](Self::set_request_interceptor).
+    async fn clear_request_interceptor(&self) -> Result<()> {
+        Err(anyhow!("Request interception not supported by this engine"))
+    }
+
+    /// Starts recording the network traffic of a tab as a HAR log.
+    ///
+    /// Requests are recorded as sent, after the request interceptor. A
+    /// recording that is already running is replaced by a fresh one.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    async fn enable_har_recording(&self, tab_id: Uuid) -> Result<Arc<HarRecorder>> {
+        let _ = tab_id;
+        Err(anyhow!("HAR recording not supported by this engine"))
+    }
+
+    /// Ends the recording started with
+    /// [`enable_har_recording`](Self::enable_har_recording) and returns it.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    async fn stop_har_recording(&self, tab_id: Uuid) -> Result<HarLog> {
+        let _ = tab_id;
+        Err(anyhow!("HAR recording not supported by this engine"))
+    }
+
+    /// Gets the HTML source of the current page.
+    ///
+    /// Returns `document.documentElement.outerHTML`, i.e. the live DOM
+    /// serialized after scripts ran, not the bytes originally received.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `max_bytes` - Optional cap; larger documents fail with
+    ///   [`BrowserError::ContentTooLarge`] (use `downcast_ref` to match it)
+    async fn get_content(&self, tab_id: Uuid, max_bytes: Option<usize>) -> Result<String> {
+        let _ = (tab_id, max_bytes);
+        Err(anyhow!("Page content retrieval not supported by this engine"))
+    }
+
+    /// Gets the `outerHTML` of the first element matching a CSS selector.
+    ///
+    /// Fails with [`BrowserError::DomQueryFailed`] if nothing matches and
+    /// with [`BrowserError::ContentTooLarge`] if the fragment exceeds `max_bytes`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `selector` - CSS selector of the element to serialize
+    /// * `max_bytes` - Optional size cap in bytes
+    async fn get_content_selector(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        max_bytes: Option<usize>,
+    ) -> Result<String> {
+        let _ = (tab_id, selector, max_bytes);
+        Err(anyhow!("Page content retrieval not supported by this engine"))
+    }
+
+    /// Gets computed CSS properties of the first element matching a selector.
+    ///
+    /// Reads `getComputedStyle` in the page and returns only the requested
+    /// properties (CSS names such as `background-color`); an empty list
+    /// returns [`DEFAULT_STYLE_PROPERTIES`](crate::browser::computed_style::DEFAULT_STYLE_PROPERTIES).
+    /// Fails with [`BrowserError::DomQueryFailed`] if nothing matches.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `selector` - CSS selector of the element
+    /// * `properties` - CSS property names to read
+    async fn get_computed_style(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        properties: Vec<String>,
+    ) -> Result<HashMap<String, String>> {
+        let _ = (tab_id, selector, properties);
+        Err(anyhow!("Computed style queries not supported by this engine"))
+    }
+
+    /// Evaluates `script` in the main frame of a tab and returns its result.
+    ///
+    /// The script may be an expression or a function body with its own
+    /// `return`. `undefined`/`null` results are `None`; a thrown exception
+    /// is an error, as is no result within `timeout`, which fails with
+    /// [`BrowserError::Timeout`].
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `script` - JavaScript to evaluate
+    /// * `timeout` - How long to wait for the result
+    async fn execute_js_with_timeout(
+        &self,
+        tab_id: Uuid,
+        script: &str,
+        timeout: Duration,
+    ) -> Result<Option<serde_json::Value>> {
+        let _ = (tab_id, script, timeout);
+        Err(anyhow!("JavaScript evaluation not supported by this engine"))
+    }
+
+    /// Calls a function in the page with JSON-serialized arguments.
+    ///
+    /// Runs `(function(...args) { <fn_body> })(...args)` where `args` is
+    /// embedded as JSON, so strings and selectors are passed without manual
+    /// escaping. A returned promise is awaited; `undefined` becomes `null`.
+    /// Uses the default [`EvalOptions`] budget.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `fn_body` - Function body; reads its arguments from `args`
+    /// * `args` - Arguments passed to the function
+    async fn call_function(
+        &self,
+        tab_id: Uuid,
+        fn_body: &str,
+        args: Vec<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        self.call_function_with_options(tab_id, fn_body, args, EvalOptions::default())
+            .await
+    }
+
+    /// Calls a function in the page like [`call_function`](Self::call_function),
+    /// within a time and size budget.
+    ///
+    /// A call still running after `options.timeout_ms` is abandoned and
+    /// fails with [`BrowserError::Timeout`]; a result whose JSON exceeds
+    /// `options.max_result_bytes` fails with [`BrowserError::ResultTooLarge`].
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `fn_body` - Function body; reads its arguments from `args`
+    /// * `args` - Arguments passed to the function
+    /// * `options` - Timeout and result size cap
+    async fn call_function_with_options(
+        &self,
+        tab_id: Uuid,
+        fn_body: &str,
+        args: Vec<serde_json::Value>,
+        options: EvalOptions,
+    ) -> Result<serde_json::Value> {
+        let _ = (tab_id, fn_body, args, options);
+        Err(anyhow!("Function calls not supported by this engine"))
+    }
+
+    /// Scrolls the first element matching a selector into view.
+    ///
+    /// `align` picks the vertical and horizontal position, the clearance to
+    /// the aligned edge (e.g. for a fixed header) and whether to scroll
+    /// smoothly. Returns the element's bounding box after scrolling; fails
+    /// with [`BrowserError::DomQueryFailed`] if nothing matches. Runs through
+    /// [`call_function`](Self::call_function).
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `selector` - CSS selector of the element
+    /// * `align` - Alignment, offset and scroll behavior
+    async fn scroll_into_view(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        align: ScrollAlign,
+    ) -> Result<BoundingBox> {
+        let result = self
+            .call_function(
+                tab_id,
+                SCROLL_INTO_VIEW_FUNCTION,
+                scroll_into_view_args(selector, &align),
+            )
+            .await?;
+        Ok(parse_scrolled_box(result, selector)?)
+    }
+
+    /// Captures a screenshot of the element matching `selector`.
+    ///
+    /// The element's `getBoundingClientRect()` is converted to device pixels
+    /// with the tab's scale factor and used as the clip region of a viewport
+    /// capture; any clip region in `options` is replaced. Only the part of
+    /// the element inside the viewport is captured. Fails with
+    /// [`BrowserError::DomQueryFailed`] if nothing matches or the element
+    /// has no area.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `selector` - CSS selector of the element
+    /// * `options` - Format, quality and paint options of the capture
+    async fn screenshot_element(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        options: ScreenshotOptions,
+    ) -> Result<Screenshot> {
+        let _ = (tab_id, selector, options);
+        Err(anyhow!("Element screenshots not supported by this engine"))
+    }
+
+    /// Waits until a JavaScript predicate returns a truthy value.
+    ///
+    /// `js_predicate` is an expression (`window.__appReady === true`) or a
+    /// function expression called with `args`; expressions read the
+    /// arguments from `args`. It is re-evaluated every `poll_interval` via
+    /// [`call_function`](Self::call_function), so returned promises are
+    /// awaited. Returns the truthy value; fails with
+    /// [`BrowserError::Timeout`] if none arrives within `timeout`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `js_predicate` - Predicate expression or function expression
+    /// * `args` - Arguments passed to the predicate as JSON
+    /// * `timeout` - Maximum time to wait
+    /// * `poll_interval` - Pause between evaluations
+    async fn wait_for_function(
+        &self,
+        tab_id: Uuid,
+        js_predicate: &str,
+        args: Vec<serde_json::Value>,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<serde_json::Value> {
+        let body = predicate_function_body(js_predicate);
+        poll_predicate(js_predicate, timeout, poll_interval, || {
+            self.call_function(tab_id, &body, args.clone())
         })
+        .await
+    }
+
+    /// Waits until a tab reaches the state described by `condition`.
+    ///
+    /// Fails with [`BrowserError::Timeout`] if the condition does not hold
+    /// within `timeout`, and right away for a condition that can never hold
+    /// (see [`WaitCondition::validate`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `condition` - The state to wait for
+    /// * `timeout` - Maximum time to wait
+    async fn wait_for(&self, tab_id: Uuid, condition: WaitCondition, timeout: Duration) -> Result<()> {
+        let _ = (tab_id, condition, timeout);
+        Err(anyhow!("Wait conditions not supported by this engine"))
+    }
+
+    /// Waits until an element matches `selector` and returns its box.
+    ///
+    /// Polls `document.querySelector` with the engine's ready poll
+    /// backoff. The box is viewport-relative, as of the poll that found the
+    /// element. Fails with a [`BrowserError::Timeout`] naming the selector
+    /// if nothing matches within `timeout_ms`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `selector` - CSS selector of the element
+    /// * `timeout_ms` - Maximum time to wait in milliseconds
+    async fn wait_for_selector(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        timeout_ms: u64,
+    ) -> Result<BoundingBox> {
+        let _ = (tab_id, selector, timeout_ms);
+        Err(anyhow!("Selector waits not supported by this engine"))
+    }
+
+    /// Installs `window.<name>(payload)` in the page of a tab.
+    ///
+    /// Every call from page script is published as
+    /// [`BrowserEvent::Binding`] with the payload round-tripped through
+    /// JSON. The binding stays installed across navigations of the tab.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `name` - Name of the `window` function; must be a JavaScript identifier
+    async fn expose_binding(&self, tab_id: Uuid, name: &str) -> Result<()> {
+        let _ = (tab_id, name);
+        Err(anyhow!("Bindings not supported by this engine"))
+    }
+
+    /// Waits until the element matching `selector` (or its subtree) mutates.
+    ///
+    /// Uses a `MutationObserver` instead of polling the page, so long waits
+    /// cost nothing while the page is idle. If the element does not exist
+    /// yet, the wait starts once it appears. Returns the first batch of
+    /// mutation records; fails with [`BrowserError::Timeout`] if nothing
+    /// changed within `timeout`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `selector` - CSS selector of the element to observe
+    /// * `kinds` - Which mutation types end the wait
+    /// * `timeout` - Maximum time to wait
+    async fn wait_for_mutation(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        kinds: MutationKinds,
+        timeout: Duration,
+    ) -> Result<Vec<MutationInfo>> {
+        let _ = (tab_id, selector, kinds, timeout);
+        Err(anyhow!("Mutation waits not supported by this engine"))
+    }
+
+    /// Gets the frame tree for a tab.
+    ///
+    /// Returns information about all frames (main frame and iframes) in the page.
+    /// This is useful for interacting with cross-origin iframes.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab to get frames from
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a vector of FrameInfo structs.
+    async fn get_frame_tree(&self, tab_id: Uuid) -> Result<Vec<FrameInfo>> {
+        // Default implementation returns empty vec for engines that don't support frames
+        let _ = tab_id;
+        Ok(Vec::new())
+    }
+
+    /// Evaluates JavaScript in a specific frame.
+    ///
+    /// This allows executing JavaScript in cross-origin iframes by targeting
+    /// a specific frame ID obtained from get_frame_tree().
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `frame_id` - The frame ID to evaluate in (from get_frame_tree)
+    /// * `script` - JavaScript code to execute
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the evaluation result as a JSON value.
+    async fn evaluate_in_frame(
+        &self,
+        tab_id: Uuid,
+        frame_id: &str,
+        script: &str,
+    ) -> Result<serde_json::Value> {
+        // Default implementation returns an error
+        let _ = (tab_id, frame_id, script);
+        Err(anyhow!("Frame evaluation not supported by this engine"))
+    }
+
+    /// Captures a DOM snapshot with bounding-box information for all visible elements.
+    ///
+    /// Executes JavaScript in the tab to traverse the DOM tree, collecting element
+    /// metadata, bounding rectangles, ARIA roles, and interactivity flags. The result
+    /// is used by the vision overlay system to map screen regions to DOM elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab to snapshot
+    /// * `config` - Snapshot configuration (max nodes, include text)
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the [`DomSnapshot`] or an error.
+    async fn dom_snapshot(
+        &self,
+        tab_id: Uuid,
+        config: &crate::browser::dom_snapshot::SnapshotConfig,
+    ) -> Result<crate::browser::dom_snapshot::DomSnapshot> {
+        // Default implementation returns an error for engines that don't support snapshots
+        let _ = (tab_id, config);
+        Err(anyhow!("DOM snapshot not supported by this engine"))
+    }
+
+    /// Returns the cookies of the browser profile a tab belongs to.
+    ///
+    /// Includes `HttpOnly` cookies. Cookies are shared by all tabs of the
+    /// engine.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `url` - Only return the cookies sent with a request to this URL
+    async fn get_cookies(&self, tab_id: Uuid, url: Option<&str>) -> Result<Vec<Cookie>> {
+        let _ = (tab_id, url);
+        Err(anyhow!("Cookies not supported by this engine"))
+    }
+
+    /// Sets a cookie, replacing one with the same name, domain and path.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `cookie` - The cookie to set
+    async fn set_cookie(&self, tab_id: Uuid, cookie: Cookie) -> Result<()> {
+        let _ = (tab_id, cookie);
+        Err(anyhow!("Cookies not supported by this engine"))
+    }
+
+    /// Deletes the cookies named `name` of a domain, on all paths.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `name` - Cookie name
+    /// * `domain` - Cookie domain; a leading dot is ignored
+    async fn delete_cookie(&self, tab_id: Uuid, name: &str, domain: &str) -> Result<()> {
+        let _ = (tab_id, name, domain);
+        Err(anyhow!("Cookies not supported by this engine"))
+    }
+
+    /// Deletes all cookies of the browser profile a tab belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    async fn clear_cookies(&self, tab_id: Uuid) -> Result<()> {
+        let _ = tab_id;
+        Err(anyhow!("Cookies not supported by this engine"))
+    }
+
+    /// Navigates a tab to the previous entry of its history.
+    ///
+    /// Fails when the tab is on its first history entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    async fn go_back(&self, tab_id: Uuid) -> Result<()> {
+        let _ = tab_id;
+        Err(anyhow!("History navigation not supported by this engine"))
+    }
+
+    /// Navigates a tab to the next entry of its history.
+    ///
+    /// Fails when the tab is on its last history entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    async fn go_forward(&self, tab_id: Uuid) -> Result<()> {
+        let _ = tab_id;
+        Err(anyhow!("History navigation not supported by this engine"))
+    }
+
+    /// Reloads the current page of a tab.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab_id` - The UUID of the tab
+    /// * `ignore_cache` - Fetch the page and its resources from the network
+    async fn reload(&self, tab_id: Uuid, ignore_cache: bool) -> Result<()> {
+        let _ = (tab_id, ignore_cache);
+        Err(anyhow!("Reload not supported by this engine"))
+    }
+}
+
+/// Document returned by the mock engine when no content was set.
+const MOCK_EMPTY_DOCUMENT: &str = "<html><head></head><body></body></html>";
+
+/// Canned computed styles of the mock engine, keyed by tab and selector.
+type MockStyleStore = RwLock<HashMap<(Uuid, String), HashMap<String, String>>>;
+
+/// Canned element bounding boxes of the mock engine, keyed by tab and selector.
+type MockBoxStore = RwLock<HashMap<(Uuid, String), BoundingBox>>;
+
+/// Polls before a selector appears in the mock engine, keyed by tab and selector.
+type MockSelectorDelays = RwLock<HashMap<(Uuid, String), usize>>;
+
+/// Scripted predicate values of the mock engine, keyed by tab and predicate.
+type MockPredicateStore = RwLock<HashMap<(Uuid, String), VecDeque<serde_json::Value>>>;
+
+/// Cookie jar of the mock engine, keyed by host, path and name.
+type MockCookieJar = RwLock<HashMap<(String, String, String), Cookie>>;
+
+/// Back/forward history of the mock engine per tab: the visited URLs and
+/// the index of the current one.
+type MockHistory = RwLock<HashMap<Uuid, (Vec<String>, usize)>>;
+
+/// Navigation attempts of the mock engine per tab, with when each started.
+type MockNavigationLog = RwLock<HashMap<Uuid, Vec<(String, Instant)>>>;
+
+/// Mock browser engine implementation for testing purposes.
+///
+/// This implementation simulates browser behavior without actually
+/// launching a browser, making it suitable for unit tests.
+pub struct MockBrowserEngine {
+    config: BrowserConfig,
+    tabs: Arc<RwLock<HashMap<Uuid, Tab>>>,
+    is_running: Arc<RwLock<bool>>,
+    active_tab: Arc<RwLock<Option<Uuid>>>,
+    failed_requests: Arc<RwLock<HashMap<Uuid, Vec<FailedRequest>>>>,
+    page_content: Arc<RwLock<HashMap<Uuid, String>>>,
+    element_content: Arc<RwLock<HashMap<(Uuid, String), String>>>,
+    computed_styles: Arc<MockStyleStore>,
+    element_boxes: Arc<MockBoxStore>,
+    selector_delays: Arc<MockSelectorDelays>,
+    predicate_results: Arc<MockPredicateStore>,
+    mutations: broadcast::Sender<(Uuid, String, MutationInfo)>,
+    bindings: Arc<RwLock<HashMap<Uuid, HashSet<String>>>>,
+    response_captures: Arc<RwLock<HashMap<Uuid, ResponseCapture>>>,
+    redirects: Arc<RwLock<HashMap<String, String>>>,
+    resource_latencies: Arc<RwLock<HashMap<String, Duration>>>,
+    response_sizes: Arc<RwLock<HashMap<String, u64>>>,
+    response_statuses: Arc<RwLock<HashMap<String, u16>>>,
+    har_recorders: Arc<RwLock<HashMap<Uuid, Arc<HarRecorder>>>>,
+    page_resources: Arc<RwLock<HashMap<String, Vec<String>>>>,
+    default_viewport: Arc<RwLock<Viewport>>,
+    viewports: Arc<RwLock<HashMap<Uuid, Viewport>>>,
+    cpu_throttling: Arc<RwLock<HashMap<Uuid, f64>>>,
+    permission_grants: Arc<RwLock<HashMap<Uuid, PermissionGrants>>>,
+    referrer_policies: Arc<RwLock<HashMap<Uuid, ReferrerPolicy>>>,
+    navigations: Arc<RwLock<HashMap<Uuid, NavigateOptions>>>,
+    navigation_history: Arc<MockNavigationLog>,
+    history: Arc<MockHistory>,
+    call_durations: Arc<RwLock<HashMap<Uuid, Duration>>>,
+    function_calls: Arc<RwLock<HashMap<Uuid, Vec<serde_json::Value>>>>,
+    script_results: Arc<RwLock<HashMap<(Uuid, String), serde_json::Value>>>,
+    before_unload_prompts: Arc<RwLock<HashMap<Uuid, String>>>,
+    cookies: Arc<MockCookieJar>,
+    request_interceptor: InterceptorSlot,
+    events: broadcast::Sender<BrowserEvent>,
+    event_buffer: SharedEventBuffer,
+}
+
+#[async_trait]
+impl BrowserEngine for MockBrowserEngine {
+    async fn new(config: BrowserConfig) -> Result<Self> {
+        if let Some(device) = &config.device_emulation {
+            device.validate()?;
+        }
+        let (events, _) = broadcast::channel(ENGINE_EVENT_CAPACITY);
+        let (mutations, _) = broadcast::channel(ENGINE_EVENT_CAPACITY);
+        let default_viewport = Viewport::from_config(&config);
+        let request_interceptor = Arc::new(parking_lot::RwLock::new(config.request_interceptor.clone()));
+        Ok(Self {
+            config,
+            tabs: Arc::new(RwLock::new(HashMap::new())),
+            is_running: Arc::new(RwLock::new(true)),
+            active_tab: Arc::new(RwLock::new(None)),
+            failed_requests: Arc::new(RwLock::new(HashMap::new())),
+            page_content: Arc::new(RwLock::new(HashMap::new())),
+            element_content: Arc::new(RwLock::new(HashMap::new())),
+            computed_styles: Arc::new(RwLock::new(HashMap::new())),
+            element_boxes: Arc::new(RwLock::new(HashMap::new())),
+            selector_delays: Arc::new(RwLock::new(HashMap::new())),
+            predicate_results: Arc::new(RwLock::new(HashMap::new())),
+            mutations,
+            bindings: Arc::new(RwLock::new(HashMap::new())),
+            response_captures: Arc::new(RwLock::new(HashMap::new())),
+            redirects: Arc::new(RwLock::new(HashMap::new())),
+            resource_latencies: Arc::new(RwLock::new(HashMap::new())),
+            response_sizes: Arc::new(RwLock::new(HashMap::new())),
+            response_statuses: Arc::new(RwLock::new(HashMap::new())),
+            har_recorders: Arc::new(RwLock::new(HashMap::new())),
+            page_resources: Arc::new(RwLock::new(HashMap::new())),
+            default_viewport: Arc::new(RwLock::new(default_viewport)),
+            viewports: Arc::new(RwLock::new(HashMap::new())),
+            cpu_throttling: Arc::new(RwLock::new(HashMap::new())),
+            permission_grants: Arc::new(RwLock::new(HashMap::new())),
+            referrer_policies: Arc::new(RwLock::new(HashMap::new())),
+            navigations: Arc::new(RwLock::new(HashMap::new())),
+            navigation_history: Arc::new(RwLock::new(HashMap::new())),
+            history: Arc::new(RwLock::new(HashMap::new())),
+            call_durations: Arc::new(RwLock::new(HashMap::new())),
+            function_calls: Arc::new(RwLock::new(HashMap::new())),
+            script_results: Arc::new(RwLock::new(HashMap::new())),
+            before_unload_prompts: Arc::new(RwLock::new(HashMap::new())),
+            cookies: Arc::new(RwLock::new(HashMap::new())),
+            request_interceptor,
+            events,
+            event_buffer: EventBuffer::shared(DEFAULT_EVENT_BUFFER_CAPACITY),
+        })
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        let mut running = self.is_running.write().await;
+        if !*running {
+            return Err(anyhow!("Browser engine is not running"));
+        }
+
+        // Close all tabs
+        let mut tabs = self.tabs.write().await;
+        tabs.clear();
+
+        *running = false;
+        Ok(())
+    }
+
+    async fn create_tab(&self, url: &str) -> Result<Tab> {
+        let running = self.is_running.read().await;
+        if !*running {
+            return Err(anyhow!("Browser engine is not running"));
+        }
+        drop(running);
+
+        let tab = Tab::new(url.to_string());
+        let tab_id = tab.id;
+
+        let mut tabs = self.tabs.write().await;
+        tabs.insert(tab_id, tab.clone());
+        drop(tabs);
+
+        let viewport = *self.default_viewport.read().await;
+        self.viewports.write().await.insert(tab_id, viewport);
+        self.history
+            .write()
+            .await
+            .insert(tab_id, (vec![url.to_string()], 0));
+
+        Ok(tab)
+    }
+
+    async fn close_tab(&self, tab_id: Uuid) -> Result<()> {
+        let running = self.is_running.read().await;
+        if !*running {
+            return Err(anyhow!("Browser engine is not running"));
+        }
+        drop(running);
+
+        let prompting = self.before_unload_prompts.read().await.contains_key(&tab_id);
+        if prompting && !self.config.before_unload_policy.leaves_page() {
+            return Err(anyhow!(
+                "Closing tab {} was cancelled by its beforeunload handler",
+                tab_id
+            ));
+        }
+
+        let mut tabs = self.tabs.write().await;
+        tabs.remove(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        drop(tabs);
+        self.failed_requests.write().await.remove(&tab_id);
+        self.clear_content(tab_id).await;
+        self.bindings.write().await.remove(&tab_id);
+        self.response_captures.write().await.remove(&tab_id);
+        self.viewports.write().await.remove(&tab_id);
+        self.cpu_throttling.write().await.remove(&tab_id);
+        self.permission_grants.write().await.remove(&tab_id);
+        self.referrer_policies.write().await.remove(&tab_id);
+        self.navigations.write().await.remove(&tab_id);
+        self.navigation_history.write().await.remove(&tab_id);
+        self.history.write().await.remove(&tab_id);
+        self.call_durations.write().await.remove(&tab_id);
+        self.function_calls.write().await.remove(&tab_id);
+
+        let mut active = self.active_tab.write().await;
+        if *active == Some(tab_id) {
+            *active = None;
+        }
+
+        Ok(())
+    }
+
+    async fn get_tabs(&self) -> Result<Vec<Tab>> {
+        let running = self.is_running.read().await;
+        if !*running {
+            return Err(anyhow!("Browser engine is not running"));
+        }
+        drop(running);
+
+        let tabs = self.tabs.read().await;
+        Ok(tabs.values().cloned().collect())
+    }
+
+    async fn get_tab(&self, tab_id: Uuid) -> Result<Option<Tab>> {
+        let running = self.is_running.read().await;
+        if !*running {
+            return Err(anyhow!("Browser engine is not running"));
+        }
+        drop(running);
+
+        let tabs = self.tabs.read().await;
+        Ok(tabs.get(&tab_id).cloned())
+    }
+
+    fn config(&self) -> &BrowserConfig {
+        &self.config
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            cpu_throttling: true,
+            permission_grants: true,
+        }
+    }
+
+    async fn is_running(&self) -> bool {
+        *self.is_running.read().await
+    }
+
+    fn subscribe_events(&self) -> Option<broadcast::Receiver<BrowserEvent>> {
+        Some(self.events.subscribe())
+    }
+
+    async fn get_recent_events(
+        &self,
+        tab_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<BrowserEvent>> {
+        let tab_id = tab_id.map(|id| id.to_string());
+        Ok(self.event_buffer.lock().recent(tab_id.as_deref(), limit))
+    }
+
+    async fn clear_event_buffer(&self, tab_id: Option<Uuid>) -> Result<()> {
+        let tab_id = tab_id.map(|id| id.to_string());
+        self.event_buffer.lock().clear(tab_id.as_deref());
+        Ok(())
+    }
+
+    async fn set_active_tab(&self, tab_id: Uuid) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+
+        let mut active = self.active_tab.write().await;
+        if *active != Some(tab_id) {
+            *active = Some(tab_id);
+            self.emit(BrowserEvent::ActiveTabChanged {
+                tab_id: tab_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    async fn active_tab(&self) -> Option<Uuid> {
+        *self.active_tab.read().await
+    }
+
+    async fn default_viewport(&self) -> Viewport {
+        *self.default_viewport.read().await
+    }
+
+    async fn set_default_viewport(&self, viewport: Viewport, apply_to_existing: bool) -> Result<()> {
+        viewport.validate()?;
+        *self.default_viewport.write().await = viewport;
+        if apply_to_existing {
+            let tab_ids: Vec<Uuid> = self.tabs.read().await.keys().copied().collect();
+            for tab_id in tab_ids {
+                self.resize_tab(tab_id, viewport).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_viewport(&self, tab_id: Uuid) -> Result<Viewport> {
+        self.viewports
+            .read()
+            .await
+            .get(&tab_id)
+            .copied()
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))
+    }
+
+    async fn resize_tab(&self, tab_id: Uuid, viewport: Viewport) -> Result<()> {
+        viewport.validate()?;
+        let mut viewports = self.viewports.write().await;
+        let current = viewports
+            .get_mut(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        *current = viewport;
+        drop(viewports);
+
+        self.emit(BrowserEvent::ViewportChanged {
+            tab_id: tab_id.to_string(),
+            width: viewport.width,
+            height: viewport.height,
+            device_scale_factor: viewport.device_scale_factor,
+        });
+        Ok(())
+    }
+
+    async fn set_cpu_throttling(&self, tab_id: Uuid, rate: f64) -> Result<()> {
+        validate_cpu_throttling_rate(rate)?;
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        let mut throttling = self.cpu_throttling.write().await;
+        if rate == 1.0 {
+            throttling.remove(&tab_id);
+        } else {
+            throttling.insert(tab_id, rate);
+        }
+        Ok(())
+    }
+
+    async fn grant_permissions(
+        &self,
+        tab_id: Uuid,
+        origin: &str,
+        permissions: Vec<Permission>,
+    ) -> Result<()> {
+        let origin = normalize_origin(origin)?;
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        let mut grants = self.permission_grants.write().await;
+        let origins = grants.entry(tab_id).or_default();
+        if permissions.is_empty() {
+            origins.remove(&origin);
+        } else {
+            origins.insert(origin, permissions.into_iter().collect());
+        }
+        Ok(())
+    }
+
+    async fn reset_permissions(&self, tab_id: Uuid) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.permission_grants.write().await.remove(&tab_id);
+        Ok(())
+    }
+
+    async fn set_referrer_policy(&self, tab_id: Uuid, policy: ReferrerPolicy) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.referrer_policies.write().await.insert(tab_id, policy);
+        Ok(())
+    }
+
+    async fn capture_failure(
+        &self,
+        tab_id: Uuid,
+        operation: &str,
+        error: &str,
+    ) -> Result<Option<PathBuf>> {
+        let Some(dir) = &self.config.screenshot_on_error else {
+            return Ok(None);
+        };
+        let url = self
+            .tabs
+            .read()
+            .await
+            .get(&tab_id)
+            .map(|tab| tab.url.clone())
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        let frame = capture_mock_screenshot(&ScreenshotOptions::default());
+        write_error_capture(dir, tab_id, &url, operation, error, frame)
+            .await
+            .map(Some)
+    }
+
+    async fn get_failed_requests(&self, tab_id: Uuid) -> Result<Vec<FailedRequest>> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        Ok(self
+            .failed_requests
+            .read()
+            .await
+            .get(&tab_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn enable_response_capture(&self, tab_id: Uuid, url_pattern: &str) -> Result<()> {
+        let pattern = UrlPattern::parse(url_pattern)
+            .map_err(|e| BrowserError::InvalidRequest(e.to_string()))?;
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        let max_bytes = self.config.response_capture_max_bytes;
+        self.response_captures
+            .write()
+            .await
+            .entry(tab_id)
+            .or_insert_with(|| ResponseCapture::new(max_bytes))
+            .add_pattern(pattern);
+        Ok(())
+    }
+
+    async fn get_captured_responses(&self, tab_id: Uuid) -> Result<Vec<CapturedResponse>> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        Ok(self
+            .response_captures
+            .read()
+            .await
+            .get(&tab_id)
+            .map(ResponseCapture::responses)
+            .unwrap_or_default())
+    }
+
+    async fn set_request_interceptor(&self, interceptor: Arc<dyn RequestInterceptor>) -> Result<()> {
+        *self.request_interceptor.write() = Some(interceptor);
+        Ok(())
+    }
+
+    async fn clear_request_interceptor(&self) -> Result<()> {
+        *self.request_interceptor.write() = None;
+        Ok(())
+    }
+
+    async fn enable_har_recording(&self, tab_id: Uuid) -> Result<Arc<HarRecorder>> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        let recorder = Arc::new(HarRecorder::new());
+        recorder.start();
+        self.har_recorders.write().await.insert(tab_id, recorder.clone());
+        Ok(recorder)
+    }
+
+    async fn stop_har_recording(&self, tab_id: Uuid) -> Result<HarLog> {
+        let recorder = self
+            .har_recorders
+            .write()
+            .await
+            .remove(&tab_id)
+            .ok_or_else(|| anyhow!("HAR recording not enabled for tab {}", tab_id))?;
+        Ok(recorder.stop())
+    }
+
+    async fn get_content(&self, tab_id: Uuid, max_bytes: Option<usize>) -> Result<String> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        let html = self
+            .page_content
+            .read()
+            .await
+            .get(&tab_id)
+            .cloned()
+            .unwrap_or_else(|| MOCK_EMPTY_DOCUMENT.to_string());
+        Ok(enforce_max_size(html, max_bytes)?)
+    }
+
+    async fn get_content_selector(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        max_bytes: Option<usize>,
+    ) -> Result<String> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        let html = self
+            .element_content
+            .read()
+            .await
+            .get(&(tab_id, selector.to_string()))
+            .cloned()
+            .ok_or_else(|| BrowserError::DomQueryFailed {
+                selector: selector.to_string(),
+                reason: "No element matches selector".to_string(),
+            })?;
+        Ok(enforce_max_size(html, max_bytes)?)
+    }
+
+    async fn get_computed_style(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        properties: Vec<String>,
+    ) -> Result<HashMap<String, String>> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        let styles = self.computed_styles.read().await;
+        let style = styles
+            .get(&(tab_id, selector.to_string()))
+            .ok_or_else(|| BrowserError::DomQueryFailed {
+                selector: selector.to_string(),
+                reason: "No element matches selector".to_string(),
+            })?;
+        // Like getPropertyValue, properties without a value read as ""
+        Ok(resolve_style_properties(properties)
+            .into_iter()
+            .map(|name| {
+                let value = style.get(&name).cloned().unwrap_or_default();
+                (name, value)
+            })
+            .collect())
+    }
+
+    async fn call_function_with_options(
+        &self,
+        tab_id: Uuid,
+        fn_body: &str,
+        args: Vec<serde_json::Value>,
+        options: EvalOptions,
+    ) -> Result<serde_json::Value> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        // Mock implementation takes the time set via `set_call_duration`, then
+        // echoes the call so tests can inspect the arguments
+        let duration = self.call_durations.read().await.get(&tab_id).copied();
+        if let Some(duration) = duration {
+            tokio::time::timeout(options.timeout(), tokio::time::sleep(duration))
+                .await
+                .map_err(|_| options.timeout_error())?;
+        }
+        let call = serde_json::json!({
+            "function": fn_body,
+            "args": args
+        });
+        self.function_calls
+            .write()
+            .await
+            .entry(tab_id)
+            .or_default()
+            .push(call.clone());
+        Ok(options.enforce_result_size(call)?)
+    }
+
+    async fn execute_js_with_timeout(
+        &self,
+        tab_id: Uuid,
+        script: &str,
+        timeout: Duration,
+    ) -> Result<Option<serde_json::Value>> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        // Scripts take the time set via `set_call_duration` and evaluate to
+        // the value set via `set_script_result`
+        let duration = self.call_durations.read().await.get(&tab_id).copied();
+        if let Some(duration) = duration {
+            tokio::time::timeout(timeout, tokio::time::sleep(duration))
+                .await
+                .map_err(|_| BrowserError::Timeout {
+                    operation: format!("JavaScript evaluation in tab {}", tab_id),
+                    duration_ms: timeout.as_millis() as u64,
+                })?;
+        }
+        Ok(self
+            .script_results
+            .read()
+            .await
+            .get(&(tab_id, script.to_string()))
+            .filter(|value| !value.is_null())
+            .cloned())
+    }
+
+    async fn scroll_into_view(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        align: ScrollAlign,
+    ) -> Result<BoundingBox> {
+        // Records the injected call like a real engine would run it; the
+        // box comes from `set_element_box`
+        self.call_function(
+            tab_id,
+            SCROLL_INTO_VIEW_FUNCTION,
+            scroll_into_view_args(selector, &align),
+        )
+        .await?;
+        let boxes = self.element_boxes.read().await;
+        let bounding_box = boxes
+            .get(&(tab_id, selector.to_string()))
+            .copied()
+            .ok_or_else(|| BrowserError::DomQueryFailed {
+                selector: selector.to_string(),
+                reason: "No element matches selector".to_string(),
+            })?;
+        Ok(bounding_box)
+    }
+
+    async fn screenshot_element(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        options: ScreenshotOptions,
+    ) -> Result<Screenshot> {
+        // The element box comes from `set_element_box`
+        let viewport = self.get_viewport(tab_id).await?;
+        let bounding_box = self
+            .element_boxes
+            .read()
+            .await
+            .get(&(tab_id, selector.to_string()))
+            .copied();
+        let clip = element_clip_region(selector, bounding_box, viewport.device_scale_factor)?;
+        let mut screenshot = capture_mock_screenshot(&ScreenshotOptions {
+            clip_region: Some(clip),
+            full_page: false,
+            ..options
+        })?;
+        screenshot.device_scale_factor = viewport.device_scale_factor;
+        Ok(screenshot)
+    }
+
+    async fn wait_for_function(
+        &self,
+        tab_id: Uuid,
+        js_predicate: &str,
+        _args: Vec<serde_json::Value>,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<serde_json::Value> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        // Mock implementation replays values scripted via `set_predicate_results`
+        let key = (tab_id, js_predicate.to_string());
+        poll_predicate(js_predicate, timeout, poll_interval, || async {
+            let mut results = self.predicate_results.write().await;
+            let value = match results.get_mut(&key) {
+                Some(queue) if queue.len() > 1 => queue.pop_front(),
+                Some(queue) => queue.front().cloned(),
+                None => None,
+            };
+            Ok(value.unwrap_or(serde_json::Value::Bool(true)))
+        })
+        .await
+    }
+
+    async fn get_cookies(&self, tab_id: Uuid, url: Option<&str>) -> Result<Vec<Cookie>> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        let mut cookies: Vec<Cookie> = self
+            .cookies
+            .read()
+            .await
+            .values()
+            .filter(|cookie| url.is_none_or(|url| cookie.matches_url(url)))
+            .cloned()
+            .collect();
+        cookies.sort_by(|a, b| (&a.domain, &a.path, &a.name).cmp(&(&b.domain, &b.path, &b.name)));
+        Ok(cookies)
+    }
+
+    async fn set_cookie(&self, tab_id: Uuid, cookie: Cookie) -> Result<()> {
+        cookie.validate()?;
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        let key = (cookie.host().to_string(), cookie.path.clone(), cookie.name.clone());
+        self.cookies.write().await.insert(key, cookie);
+        Ok(())
+    }
+
+    async fn delete_cookie(&self, tab_id: Uuid, name: &str, domain: &str) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        let host = domain.trim_start_matches('.');
+        self.cookies
+            .write()
+            .await
+            .retain(|(cookie_host, _, cookie_name), _| !(cookie_host == host && cookie_name == name));
+        Ok(())
+    }
+
+    async fn clear_cookies(&self, tab_id: Uuid) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.cookies.write().await.clear();
+        Ok(())
+    }
+
+    async fn go_back(&self, tab_id: Uuid) -> Result<()> {
+        self.step_history(tab_id, false).await
+    }
+
+    async fn go_forward(&self, tab_id: Uuid) -> Result<()> {
+        self.step_history(tab_id, true).await
+    }
+
+    async fn reload(&self, tab_id: Uuid, _ignore_cache: bool) -> Result<()> {
+        let url = self
+            .get_tab(tab_id)
+            .await?
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?
+            .url;
+        self.simulate_navigation(tab_id, &url).await
+    }
+
+    async fn wait_for_selector(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        timeout_ms: u64,
+    ) -> Result<BoundingBox> {
+        let condition = WaitCondition::Selector(selector.to_string());
+        condition.validate()?;
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+
+        // Mock elements are the boxes set via `set_element_box`, hidden for
+        // the polls requested with `set_selector_appears_after`
+        let key = (tab_id, selector.to_string());
+        let timeout = Duration::from_millis(timeout_ms);
+        poll_condition(&condition, timeout, self.config.ready_poll_backoff, || async {
+            if let Some(remaining) = self.selector_delays.write().await.get_mut(&key) {
+                if *remaining > 0 {
+                    *remaining -= 1;
+                    return Ok(false);
+                }
+            }
+            Ok(self.element_boxes.read().await.contains_key(&key))
+        })
+        .await?;
+
+        let bounding_box = self.element_boxes.read().await.get(&key).copied();
+        bounding_box.ok_or_else(|| wait_timeout(&condition, timeout).into())
+    }
+
+    async fn wait_for(&self, tab_id: Uuid, condition: WaitCondition, _timeout: Duration) -> Result<()> {
+        condition.validate()?;
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        // Mock pages are loaded and idle as soon as they exist
+        Ok(())
+    }
+
+    async fn expose_binding(&self, tab_id: Uuid, name: &str) -> Result<()> {
+        validate_binding_name(name)?;
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.bindings
+            .write()
+            .await
+            .entry(tab_id)
+            .or_default()
+            .insert(name.to_string());
+        Ok(())
+    }
+
+    async fn wait_for_mutation(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        kinds: MutationKinds,
+        timeout: Duration,
+    ) -> Result<Vec<MutationInfo>> {
+        kinds.validate()?;
+        let mut mutations = self.mutations.subscribe();
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+
+        // Mock implementation waits for a matching `simulate_mutation` call
+        let wait = async {
+            loop {
+                match mutations.recv().await {
+                    Ok((id, sel, info))
+                        if id == tab_id && sel == selector && kinds.includes(&info.kind) =>
+                    {
+                        return Ok(vec![info]);
+                    }
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(anyhow!("Browser engine is not running"));
+                    }
+                }
+            }
+        };
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| mutation_timeout(selector, timeout))?
+    }
+
+    async fn get_frame_tree(&self, _tab_id: Uuid) -> Result<Vec<FrameInfo>> {
+        // Mock implementation returns a dummy main frame
+        Ok(vec![FrameInfo {
+            frame_id: "main-frame".to_string(),
+            parent_frame_id: None,
+            url: "about:blank".to_string(),
+            name: String::new(),
+            security_origin: "about:blank".to_string(),
+        }])
+    }
+
+    async fn evaluate_in_frame(
+        &self,
+        _tab_id: Uuid,
+        frame_id: &str,
+        _script: &str,
+    ) -> Result<serde_json::Value> {
+        // Mock implementation returns a simple result indicating frame evaluation
+        Ok(serde_json::json!({
+            "frame_id": frame_id,
+            "result": "mock frame evaluation"
+        }))
+    }
+}
+
+impl MockBrowserEngine {
+    /// Publishes an event to engine subscribers (no-op without subscribers).
+    fn emit(&self, event: BrowserEvent) {
+        self.event_buffer.lock().push(event.clone());
+        let _ = self.events.send(event);
+    }
+
+    /// Simulates a tab finishing loading.
+    ///
+    /// This method is useful for testing scenarios where you need
+    /// to simulate page load completion.
+    ///
+    /// With `auto_dismiss_consent` enabled, the first configured consent
+    /// selector that has content set via `set_element_content` is "clicked":
+    /// the element is removed and [`BrowserEvent::ConsentDismissed`] emitted.
+    pub async fn simulate_tab_ready(&self, tab_id: Uuid) -> Result<()> {
+        let mut tabs = self.tabs.write().await;
+        let Some(tab) = tabs.get_mut(&tab_id) else {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        };
+        tab.set_ready();
+        let url = tab.url.clone();
+        drop(tabs);
+
+        if self.config.auto_dismiss_consent {
+            self.dismiss_consent(tab_id, url).await;
+        }
+        Ok(())
+    }
+
+    /// Clicks the first consent button present in the tab's mock content.
+    async fn dismiss_consent(&self, tab_id: Uuid, url: String) {
+        let mut elements = self.element_content.write().await;
+        let Some((selector, text)) = self.config.consent_selectors.iter().find_map(|selector| {
+            elements
+                .remove(&(tab_id, selector.clone()))
+                .map(|text| (selector.clone(), text))
+        }) else {
+            return;
+        };
+        drop(elements);
+
+        self.emit(BrowserEvent::ConsentDismissed {
+            tab_id: tab_id.to_string(),
+            url,
+            selector: Some(selector),
+            text,
+        });
+    }
+
+    /// Simulates a tab encountering an error.
+    pub async fn simulate_tab_error(&self, tab_id: Uuid, error: &str) -> Result<()> {
+        let mut tabs = self.tabs.write().await;
+        if let Some(tab) = tabs.get_mut(&tab_id) {
+            tab.set_error(error.to_string());
+            Ok(())
+        } else {
+            Err(anyhow!("Tab not found: {}", tab_id))
+        }
+    }
+
+    /// Passes `result` through, writing a screenshot-on-error capture first
+    /// if it is an error and `screenshot_on_error` is configured.
+    async fn capture_on_error<T>(&self, tab_id: Uuid, operation: &str, result: Result<T>) -> Result<T> {
+        if let (Err(e), Some(dir)) = (&result, &self.config.screenshot_on_error) {
+            let url = self.tabs.read().await.get(&tab_id).map(|t| t.url.clone());
+            if let Some(url) = url {
+                let frame = capture_mock_screenshot(&ScreenshotOptions::default());
+                let error = format!("{:#}", e);
+                if let Err(capture_err) =
+                    write_error_capture(dir, tab_id, &url, operation, &error, frame).await
+                {
+                    tracing::warn!("Failed to write error capture for tab {}: {}", tab_id, capture_err);
+                }
+            }
+        }
+        result
+    }
+
+    /// Updates the title of a tab (simulating title change after page load).
+    pub async fn simulate_title_change(&self, tab_id: Uuid, title: &str) -> Result<()> {
+        let mut tabs = self.tabs.write().await;
+        if let Some(tab) = tabs.get_mut(&tab_id) {
+            tab.title = title.to_string();
+            Ok(())
+        } else {
+            Err(anyhow!("Tab not found: {}", tab_id))
+        }
+    }
+
+    /// Simulates a top-level navigation, which resets the tab's failed requests.
+    pub async fn simulate_navigation(&self, tab_id: Uuid, url: &str) -> Result<()> {
+        let mut tabs = self.tabs.write().await;
+        let tab = tabs
+            .get_mut(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        tab.navigate(url.to_string());
+        drop(tabs);
+
+        self.failed_requests.write().await.remove(&tab_id);
+        self.clear_content(tab_id).await;
+        Ok(())
+    }
+
+    /// Navigates a tab, following redirects registered with `set_redirect`.
+    ///
+    /// Fails with [`BrowserError::RedirectLoop`] or
+    /// [`BrowserError::TooManyRedirects`] like the CEF engine does. The
+    /// options, with the referrer validated, are kept for
+    /// [`last_navigation`](Self::last_navigation).
+    ///
+    /// Latencies from `set_resource_latency` are compared against the
+    /// configured timeouts: a main document slower than `timeout_ms` fails
+    /// the navigation with [`BrowserError::Timeout`], while sub-resources
+    /// from `set_page_resources` slower than `resource_timeout_ms` are only
+    /// recorded as failed requests.
+    ///
+    /// The document and its sub-resources pass the installed request
+    /// interceptor. A blocked document fails the navigation with
+    /// [`BrowserError::NavigationFailed`]; blocked sub-resources are
+    /// recorded as failed requests.
+    ///
+    /// With [`BrowserConfig::network_throttle`] set, the navigation takes
+    /// the throttle's latency plus the download time of the document size
+    /// from `set_response_size`, and fails when the throttle is offline.
+    ///
+    /// A failed navigation writes a screenshot-on-error capture when
+    /// [`BrowserConfig::screenshot_on_error`] is set.
+    pub async fn navigate_with_options(
+        &self,
+        tab_id: Uuid,
+        url: &str,
+        options: NavigateOptions,
+    ) -> Result<()> {
+        let result = self.navigate_with_options_inner(tab_id, url, options).await;
+        self.capture_on_error(tab_id, "navigate", result).await
+    }
+
+    async fn navigate_with_options_inner(
+        &self,
+        tab_id: Uuid,
+        url: &str,
+        mut options: NavigateOptions,
+    ) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        if let Some(referrer) = &options.referrer {
+            options.referrer = Some(validate_referrer(referrer)?);
+        }
+        self.navigations.write().await.insert(tab_id, options.clone());
+        self.navigation_history
+            .write()
+            .await
+            .entry(tab_id)
+            .or_default()
+            .push((url.to_string(), Instant::now()));
+        let mut chain = RedirectChain::new(url, options.max_redirects);
+        let mut current = url.to_string();
+        while let Some(next) = self.redirects.read().await.get(&current).cloned() {
+            chain.follow(&next)?;
+            current = next;
+        }
+
+        let Some(request) = apply_interceptor(&self.request_interceptor, InterceptedRequest::get(&current))
+        else {
+            self.failed_requests.write().await.remove(&tab_id);
+            let failed = FailedRequest::load_error(&current, BLOCKED_BY_CLIENT)
+                .with_resource_type("document");
+            self.simulate_request_failed(tab_id, failed).await?;
+            return Err(BrowserError::NavigationFailed {
+                url: current,
+                reason: BLOCKED_BY_CLIENT.to_string(),
+            }
+            .into());
+        };
+        let current = request.url.clone();
+
+        if let Some(throttle) = self.config.network_throttle {
+            if throttle.is_offline() {
+                self.failed_requests.write().await.remove(&tab_id);
+                let failed = FailedRequest::load_error(&current, INTERNET_DISCONNECTED)
+                    .with_resource_type("document");
+                self.simulate_request_failed(tab_id, failed).await?;
+                return Err(BrowserError::NavigationFailed {
+                    url: current,
+                    reason: INTERNET_DISCONNECTED.to_string(),
+                }
+                .into());
+            }
+            let size = self.response_sizes.read().await.get(&current).copied().unwrap_or(0);
+            tokio::time::sleep(throttle.request_delay(0) + throttle.response_delay(size)).await;
+        }
+
+        let latencies = self.resource_latencies.read().await.clone();
+        let latency = |url: &str| latencies.get(url).copied().unwrap_or_default();
+        if latency(&current) > Duration::from_millis(self.config.timeout_ms) {
+            // The failed document still replaces the previous page's failures
+            self.failed_requests.write().await.remove(&tab_id);
+            let failed = FailedRequest::load_error(&current, RESOURCE_TIMED_OUT)
+                .with_resource_type("document");
+            self.simulate_request_failed(tab_id, failed).await?;
+            return Err(BrowserError::Timeout {
+                operation: format!("Navigation to {}", current),
+                duration_ms: self.config.timeout_ms,
+            }
+            .into());
+        }
+        self.record_har_exchange(tab_id, &request).await;
+        self.simulate_navigation(tab_id, &current).await?;
+        self.push_history(tab_id, &current).await;
+
+        let resources = self
+            .page_resources
+            .read()
+            .await
+            .get(&current)
+            .cloned()
+            .unwrap_or_default();
+        for resource in resources {
+            let Some(request) =
+                apply_interceptor(&self.request_interceptor, InterceptedRequest::get(&resource))
+            else {
+                let failed = FailedRequest::load_error(resource, BLOCKED_BY_CLIENT);
+                self.simulate_request_failed(tab_id, failed).await?;
+                continue;
+            };
+            if let Some(limit) = self.config.resource_timeout_ms {
+                if latency(&request.url) > Duration::from_millis(limit) {
+                    let failed = FailedRequest::load_error(request.url, RESOURCE_TIMED_OUT);
+                    self.simulate_request_failed(tab_id, failed).await?;
+                    continue;
+                }
+            }
+            self.record_har_exchange(tab_id, &request).await;
+        }
+        Ok(())
+    }
+
+    /// Records `request` and its response in the tab's HAR recording, if
+    /// one is running.
+    async fn record_har_exchange(&self, tab_id: Uuid, request: &InterceptedRequest) {
+        let Some(recorder) = self.har_recorders.read().await.get(&tab_id).cloned() else {
+            return;
+        };
+        let status = self.response_statuses.read().await.get(&request.url).copied().unwrap_or(200);
+        let mut response = HarResponse::new(status, reason_phrase(status));
+        if let Some(size) = self.response_sizes.read().await.get(&request.url) {
+            response = response.with_body_size(*size as i64);
+        }
+        recorder.on_request(&mut request.clone());
+        recorder.record_response(&request.url, response);
+    }
+
+    /// Visits `urls` in order, dwelling on each for a time drawn from
+    /// `options.dwell`.
+    ///
+    /// The mock has no cursor, so `mouse_wander` only affects the CEF engine.
+    pub async fn warmup(&self, tab_id: Uuid, urls: &[String], options: &WarmupOptions) -> Result<()> {
+        options.validate()?;
+        for url in urls {
+            self.navigate_with_options(tab_id, url, NavigateOptions::default())
+                .await?;
+            let dwell = options.sample_dwell(&mut rand::thread_rng());
+            tokio::time::sleep(dwell).await;
+        }
+        Ok(())
+    }
+
+    /// Makes navigations to `from` redirect to `to`.
+    pub async fn set_redirect(&self, from: &str, to: &str) {
+        self.redirects
+            .write()
+            .await
+            .insert(from.to_string(), to.to_string());
+    }
+
+    /// Sets the HTTP status of the response to `url` (200 unless set), as
+    /// recorded in HAR logs.
+    pub async fn set_response_status(&self, url: &str, status: u16) {
+        self.response_statuses
+            .write()
+            .await
+            .insert(url.to_string(), status);
+    }
+
+    /// Sets the body size of the response to `url`, which throttled
+    /// navigations take to download.
+    pub async fn set_response_size(&self, url: &str, bytes: u64) {
+        self.response_sizes
+            .write()
+            .await
+            .insert(url.to_string(), bytes);
+    }
+
+    /// Makes loading `url` take `latency`, for navigations and as a sub-resource.
+    pub async fn set_resource_latency(&self, url: &str, latency: Duration) {
+        self.resource_latencies
+            .write()
+            .await
+            .insert(url.to_string(), latency);
+    }
+
+    /// Sets the sub-resources loaded by the page at `page_url`.
+    pub async fn set_page_resources(&self, page_url: &str, resources: Vec<String>) {
+        self.page_resources
+            .write()
+            .await
+            .insert(page_url.to_string(), resources);
+    }
+
+    /// Sets the HTML returned by `get_content` for a tab.
+    pub async fn set_page_content(&self, tab_id: Uuid, html: &str) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.page_content.write().await.insert(tab_id, html.to_string());
+        Ok(())
+    }
+
+    /// Sets the fragment returned by `get_content_selector` for a selector.
+    pub async fn set_element_content(&self, tab_id: Uuid, selector: &str, html: &str) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.element_content
+            .write()
+            .await
+            .insert((tab_id, selector.to_string()), html.to_string());
+        Ok(())
+    }
+
+    /// Scripts the values `predicate` yields on successive `wait_for_function` polls.
+    ///
+    /// The last value repeats once the others are used up. Predicates
+    /// without scripted values are truthy on the first poll.
+    pub async fn set_predicate_results(
+        &self,
+        tab_id: Uuid,
+        predicate: &str,
+        values: Vec<serde_json::Value>,
+    ) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.predicate_results
+            .write()
+            .await
+            .insert((tab_id, predicate.to_string()), values.into());
+        Ok(())
+    }
+
+    /// Sets the value `script` evaluates to in a tab via
+    /// `execute_js_with_timeout`; other scripts evaluate to `undefined`.
+    pub async fn set_script_result(
+        &self,
+        tab_id: Uuid,
+        script: &str,
+        value: serde_json::Value,
+    ) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.script_results
+            .write()
+            .await
+            .insert((tab_id, script.to_string()), value);
+        Ok(())
+    }
+
+    /// Makes every function call and script evaluation in a tab take
+    /// `duration` before it returns.
+    pub async fn set_call_duration(&self, tab_id: Uuid, duration: Duration) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.call_durations.write().await.insert(tab_id, duration);
+        Ok(())
+    }
+
+    /// Sets the computed style reported for `selector` by `get_computed_style`.
+    pub async fn set_computed_style(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        style: HashMap<String, String>,
+    ) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.computed_styles
+            .write()
+            .await
+            .insert((tab_id, selector.to_string()), style);
+        Ok(())
+    }
+
+    /// Sets the bounding box reported for `selector` by `scroll_into_view`
+    /// and `screenshot_element`.
+    pub async fn set_element_box(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        bounding_box: BoundingBox,
+    ) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.element_boxes
+            .write()
+            .await
+            .insert((tab_id, selector.to_string()), bounding_box);
+        Ok(())
+    }
+
+    /// Makes `selector` match an element with `bounding_box` after `polls`
+    /// unsuccessful `wait_for_selector` polls.
+    pub async fn set_selector_appears_after(
+        &self,
+        tab_id: Uuid,
+        selector: &str,
+        bounding_box: BoundingBox,
+        polls: usize,
+    ) -> Result<()> {
+        self.set_element_box(tab_id, selector, bounding_box).await?;
+        self.selector_delays
+            .write()
+            .await
+            .insert((tab_id, selector.to_string()), polls);
+        Ok(())
+    }
+
+    /// Returns the functions called in a tab, oldest first, as
+    /// `{"function": ..., "args": [...]}`.
+    pub async fn function_calls(&self, tab_id: Uuid) -> Vec<serde_json::Value> {
+        self.function_calls
+            .read()
+            .await
+            .get(&tab_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    async fn clear_content(&self, tab_id: Uuid) {
+        self.page_content.write().await.remove(&tab_id);
+        self.element_content
+            .write()
+            .await
+            .retain(|(id, _), _| *id != tab_id);
+        self.computed_styles
+            .write()
+            .await
+            .retain(|(id, _), _| *id != tab_id);
+        self.element_boxes
+            .write()
+            .await
+            .retain(|(id, _), _| *id != tab_id);
+        self.selector_delays
+            .write()
+            .await
+            .retain(|(id, _), _| *id != tab_id);
+        self.predicate_results
+            .write()
+            .await
+            .retain(|(id, _), _| *id != tab_id);
+        self.before_unload_prompts.write().await.remove(&tab_id);
+    }
+
+    /// Simulates a `beforeunload` handler on the tab's page that prompts
+    /// before leaving. Lasts until the next navigation.
+    pub async fn simulate_before_unload(&self, tab_id: Uuid, message: &str) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        self.before_unload_prompts
+            .write()
+            .await
+            .insert(tab_id, message.to_string());
+        Ok(())
+    }
+
+    /// Simulates a DOM mutation of the element matching `selector`.
+    ///
+    /// Wakes pending `wait_for_mutation` calls for that tab and selector
+    /// that observe `info.kind`.
+    pub async fn simulate_mutation(&self, tab_id: Uuid, selector: &str, info: MutationInfo) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        let _ = self.mutations.send((tab_id, selector.to_string(), info));
+        Ok(())
+    }
+
+    /// Simulates page script calling `window.<name>(payload)`.
+    ///
+    /// Publishes [`BrowserEvent::Binding`] like a real page call. Fails if
+    /// the binding was not exposed, as the page would get a `TypeError`.
+    pub async fn simulate_binding_call(
+        &self,
+        tab_id: Uuid,
+        name: &str,
+        payload: serde_json::Value,
+    ) -> Result<()> {
+        let exposed = self
+            .bindings
+            .read()
+            .await
+            .get(&tab_id)
+            .is_some_and(|names| names.contains(name));
+        if !exposed {
+            return Err(anyhow!("window.{} is not a function", name));
+        }
+        self.emit(BrowserEvent::Binding {
+            tab_id: tab_id.to_string(),
+            name: name.to_string(),
+            payload,
+        });
+        Ok(())
+    }
+
+    /// Simulates a response arriving in a tab.
+    ///
+    /// The response is captured if it matches a pattern passed to
+    /// `enable_response_capture`; returns whether it was stored.
+    pub async fn simulate_response(&self, tab_id: Uuid, response: CapturedResponse) -> Result<bool> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+        Ok(self
+            .response_captures
+            .write()
+            .await
+            .get_mut(&tab_id)
+            .is_some_and(|capture| capture.record(response)))
+    }
+
+    /// Returns the CPU throttling rate set for a tab, `None` when unthrottled.
+    pub async fn cpu_throttling_rate(&self, tab_id: Uuid) -> Option<f64> {
+        self.cpu_throttling.read().await.get(&tab_id).copied()
+    }
+
+    /// Returns the URLs passed to `navigate_with_options` for a tab, oldest
+    /// first, with the time of each call.
+    pub async fn navigation_history(&self, tab_id: Uuid) -> Vec<(String, Instant)> {
+        self.navigation_history
+            .read()
+            .await
+            .get(&tab_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the back/forward history of a tab and the index of its
+    /// current entry.
+    pub async fn history(&self, tab_id: Uuid) -> Option<(Vec<String>, usize)> {
+        self.history.read().await.get(&tab_id).cloned()
+    }
+
+    /// Makes `url` the current history entry of a tab, dropping the entries
+    /// ahead of the previous one like a browser does.
+    async fn push_history(&self, tab_id: Uuid, url: &str) {
+        let mut history = self.history.write().await;
+        let (entries, index) = history.entry(tab_id).or_default();
+        entries.truncate(*index + 1);
+        entries.push(url.to_string());
+        *index = entries.len() - 1;
+    }
+
+    /// Moves a tab one history entry forward or back and loads it.
+    async fn step_history(&self, tab_id: Uuid, forward: bool) -> Result<()> {
+        let mut history = self.history.write().await;
+        let (entries, index) = history
+            .get_mut(&tab_id)
+            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        let target = if forward {
+            Some(*index + 1).filter(|&i| i < entries.len())
+        } else {
+            index.checked_sub(1)
+        };
+        let Some(target) = target else {
+            let direction = if forward { "forward" } else { "back" };
+            return Err(anyhow!("Cannot go {} in tab {}: no history entry", direction, tab_id));
+        };
+        *index = target;
+        let url = entries[target].clone();
+        drop(history);
+
+        self.simulate_navigation(tab_id, &url).await
+    }
+
+    /// Returns the options of the tab's last `navigate_with_options` call.
+    pub async fn last_navigation(&self, tab_id: Uuid) -> Option<NavigateOptions> {
+        self.navigations.read().await.get(&tab_id).cloned()
+    }
+
+    /// Returns the referrer policy set for a tab.
+    pub async fn referrer_policy(&self, tab_id: Uuid) -> ReferrerPolicy {
+        self.referrer_policies
+            .read()
+            .await
+            .get(&tab_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the permissions granted to `origin` in a tab, sorted.
+    pub async fn granted_permissions(&self, tab_id: Uuid, origin: &str) -> Vec<Permission> {
+        let Ok(origin) = normalize_origin(origin) else {
+            return Vec::new();
+        };
+        self.permission_grants
+            .read()
+            .await
+            .get(&tab_id)
+            .and_then(|origins| origins.get(&origin))
+            .map(|granted| granted.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Simulates a failed network request in a tab.
+    pub async fn simulate_request_failed(&self, tab_id: Uuid, failed: FailedRequest) -> Result<()> {
+        if !self.tabs.read().await.contains_key(&tab_id) {
+            return Err(anyhow!("Tab not found: {}", tab_id));
+        }
+
+        self.emit(BrowserEvent::RequestFailed {
+            tab_id: tab_id.to_string(),
+            url: failed.url.clone(),
+            status: failed.status,
+            error: failed.error.clone(),
+        });
+        let mut failed_requests = self.failed_requests.write().await;
+        record_failed_request(failed_requests.entry(tab_id).or_default(), failed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_browser_config_builder() {
+        let config = BrowserConfig::new()
+            .headless(false)
+            .window_size(1280, 720)
+            .user_agent("TestAgent/1.0")
+            .proxy("http://localhost:8080")
+            .timeout_ms(60_000)
+            .with_devtools();
+
+        assert!(!config.headless);
+        assert_eq!(config.window_size, (1280, 720));
+        assert_eq!(config.user_agent, Some("TestAgent/1.0".to_string()));
+        assert_eq!(config.proxy, Some("http://localhost:8080".to_string()));
+        assert_eq!(config.timeout_ms, 60_000);
+        assert!(config.devtools);
     }
 
-    async fn shutdown(&self) -> Result<()> {
-        let mut running = self.is_running.write().await;
-        if !*running {
-            return Err(anyhow!("Browser engine is not running"));
+    #[test]
+    fn test_cache_mode_maps_to_chromium_settings() {
+        let config = BrowserConfig::new();
+        assert_eq!(config.cache_mode, CacheMode::Default);
+        assert!(config.cache_mode.command_line_switches().is_empty());
+        assert!(config.cache_mode.uses_disk_cache());
+        assert!(!config.cache_mode.bypasses_cache(&NavigateOptions::default()));
+        assert!(config
+            .cache_mode
+            .bypasses_cache(&NavigateOptions::default().bypass_cache()));
+
+        let disabled = BrowserConfig::new().cache_mode(CacheMode::Disabled).cache_mode;
+        assert_eq!(disabled.command_line_switches(), ["disable-cache"]);
+        assert!(!disabled.uses_disk_cache());
+
+        let refresh = BrowserConfig::new().cache_mode(CacheMode::ForceRefresh).cache_mode;
+        assert!(refresh.command_line_switches().is_empty());
+        assert!(refresh.uses_disk_cache());
+        assert!(refresh.bypasses_cache(&NavigateOptions::default()));
+
+        assert_eq!(
+            serde_json::from_str::<CacheMode>("\"force_refresh\"").unwrap(),
+            CacheMode::ForceRefresh
+        );
+    }
+
+    #[test]
+    fn test_poll_backoff_ramps_monotonically_and_caps() {
+        let backoff = PollBackoff::new(10, 200, 1.5);
+        let mut interval = backoff.initial();
+        assert_eq!(interval, Duration::from_millis(10));
+
+        let mut reached_cap = false;
+        for _ in 0..50 {
+            let next = backoff.next(interval);
+            assert!(
+                next >= interval,
+                "interval decreased: {:?} -> {:?}",
+                interval,
+                next
+            );
+            assert!(next <= Duration::from_millis(200));
+            reached_cap |= next == Duration::from_millis(200);
+            interval = next;
         }
+        assert!(reached_cap);
 
-        // Close all tabs
-        let mut tabs = self.tabs.write().await;
-        tabs.clear();
+        // Degenerate factors fall back to constant polling
+        let flat = PollBackoff::new(50, 200, 0.5);
+        assert_eq!(flat.next(flat.initial()), Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_create_and_close_tab() {
+        let config = BrowserConfig::default();
+        let engine = MockBrowserEngine::new(config).await.unwrap();
+
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        assert_eq!(tab.url, "https://example.com");
+
+        let tabs = engine.get_tabs().await.unwrap();
+        assert_eq!(tabs.len(), 1);
+
+        engine.close_tab(tab.id).await.unwrap();
+
+        let tabs = engine.get_tabs().await.unwrap();
+        assert!(tabs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_shutdown() {
+        let config = BrowserConfig::default();
+        let engine = MockBrowserEngine::new(config).await.unwrap();
+
+        assert!(engine.is_running().await);
+
+        engine.shutdown().await.unwrap();
+
+        assert!(!engine.is_running().await);
+
+        // Operations should fail after shutdown
+        assert!(engine.create_tab("https://example.com").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_active_tab_transitions() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let mut events = engine.subscribe_events().unwrap();
+
+        let tab1 = engine.create_tab("https://example.com").await.unwrap();
+        let tab2 = engine.create_tab("https://example.org").await.unwrap();
+        assert_eq!(engine.active_tab().await, None);
+
+        engine.set_active_tab(tab1.id).await.unwrap();
+        assert_eq!(engine.active_tab().await, Some(tab1.id));
+
+        // Re-activating the same tab is not a transition
+        engine.set_active_tab(tab1.id).await.unwrap();
+        engine.set_active_tab(tab2.id).await.unwrap();
+        assert_eq!(engine.active_tab().await, Some(tab2.id));
+
+        let mut seen = Vec::new();
+        while let Ok(BrowserEvent::ActiveTabChanged { tab_id }) = events.try_recv() {
+            seen.push(tab_id);
+        }
+        assert_eq!(seen, vec![tab1.id.to_string(), tab2.id.to_string()]);
+
+        assert!(engine.set_active_tab(Uuid::new_v4()).await.is_err());
+
+        engine.close_tab(tab2.id).await.unwrap();
+        assert_eq!(engine.active_tab().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_failed_requests_reset_on_navigation() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let mut events = engine.subscribe_events().unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+
+        engine
+            .simulate_request_failed(tab.id, FailedRequest::http_status("https://example.com/missing.js", 404))
+            .await
+            .unwrap();
+        engine
+            .simulate_request_failed(
+                tab.id,
+                FailedRequest::load_error("https://cdn.invalid/lib.js", "net::ERR_NAME_NOT_RESOLVED"),
+            )
+            .await
+            .unwrap();
+
+        let failed = engine.get_failed_requests(tab.id).await.unwrap();
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0].url, "https://example.com/missing.js");
+        assert_eq!(failed[0].status, Some(404));
+        assert_eq!(failed[0].error, "Not Found");
+        assert_eq!(failed[1].status, None);
+        assert_eq!(failed[1].error, "net::ERR_NAME_NOT_RESOLVED");
+
+        assert!(matches!(
+            events.try_recv(),
+            Ok(BrowserEvent::RequestFailed { status: Some(404), .. })
+        ));
+        assert!(matches!(
+            events.try_recv(),
+            Ok(BrowserEvent::RequestFailed { status: None, .. })
+        ));
+
+        engine.simulate_navigation(tab.id, "https://example.org").await.unwrap();
+        assert!(engine.get_failed_requests(tab.id).await.unwrap().is_empty());
+        assert!(engine.get_failed_requests(Uuid::new_v4()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_resource_timeout_spares_navigation() {
+        let config = BrowserConfig::default().timeout_ms(5_000).resource_timeout_ms(1_000);
+        let engine = MockBrowserEngine::new(config).await.unwrap();
+        let tab = engine.create_tab("about:blank").await.unwrap();
+
+        let slow_image = "https://cdn.example/hero.png".to_string();
+        engine
+            .set_page_resources("https://example.com/", vec![slow_image.clone()])
+            .await;
+        engine.set_resource_latency(&slow_image, Duration::from_secs(3)).await;
+
+        // A timed-out sub-resource is reported, the navigation succeeds
+        engine
+            .navigate_with_options(tab.id, "https://example.com/", NavigateOptions::default())
+            .await
+            .unwrap();
+        let tab_state = engine.get_tab(tab.id).await.unwrap().unwrap();
+        assert_eq!(tab_state.url, "https://example.com/");
+        let failed = engine.get_failed_requests(tab.id).await.unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].url, slow_image);
+        assert_eq!(failed[0].error, RESOURCE_TIMED_OUT);
+
+        // A timed-out main document fails the navigation
+        engine
+            .set_resource_latency("https://slow.example/", Duration::from_secs(10))
+            .await;
+        let err = engine
+            .navigate_with_options(tab.id, "https://slow.example/", NavigateOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::Timeout { duration_ms: 5_000, .. })
+        ));
+        let failed = engine.get_failed_requests(tab.id).await.unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].resource_type.as_deref(), Some("document"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_request_interceptor_blocks_doubleclick() {
+        use crate::browser::interceptor::{BlockListInterceptor, RequestAction};
+
+        struct DoubleclickBlocker;
+        impl RequestInterceptor for DoubleclickBlocker {
+            fn on_request(&self, request: &mut InterceptedRequest) -> RequestAction {
+                if UrlPattern::parse("*.doubleclick.net").unwrap().matches(&request.url) {
+                    RequestAction::Block
+                } else {
+                    RequestAction::Allow
+                }
+            }
+        }
+
+        let config = BrowserConfig::default().with_request_interceptor(Arc::new(DoubleclickBlocker));
+        let engine = MockBrowserEngine::new(config).await.unwrap();
+        let tab = engine.create_tab("about:blank").await.unwrap();
+
+        // Blocked sub-resources are reported, the page still loads
+        let ad = "https://ad.doubleclick.net/pixel.gif".to_string();
+        engine
+            .set_page_resources("https://example.com/", vec![ad.clone(), "https://example.com/app.js".into()])
+            .await;
+        engine
+            .navigate_with_options(tab.id, "https://example.com/", NavigateOptions::default())
+            .await
+            .unwrap();
+        let failed = engine.get_failed_requests(tab.id).await.unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].url, ad);
+        assert_eq!(failed[0].error, BLOCKED_BY_CLIENT);
+
+        // A blocked document fails the navigation
+        let err = engine
+            .navigate_with_options(tab.id, "https://doubleclick.net/", NavigateOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::NavigationFailed { .. })
+        ));
+        assert_eq!(engine.get_tab(tab.id).await.unwrap().unwrap().url, "https://example.com/");
+
+        // A block list installed later replaces the interceptor
+        let blocker = BlockListInterceptor::new(["*.example.com"]).unwrap();
+        engine.set_request_interceptor(Arc::new(blocker)).await.unwrap();
+        engine
+            .navigate_with_options(tab.id, "https://doubleclick.net/", NavigateOptions::default())
+            .await
+            .unwrap();
+        engine.clear_request_interceptor().await.unwrap();
+        engine
+            .navigate_with_options(tab.id, "https://example.com/", NavigateOptions::default())
+            .await
+            .unwrap();
+        assert!(engine.get_failed_requests(tab.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_har_recording() {
+        use crate::browser::interceptor::BlockListInterceptor;
+
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("about:blank").await.unwrap();
+        assert!(engine.stop_har_recording(tab.id).await.is_err());
+
+        let ad = "https://ad.doubleclick.net/pixel.gif";
+        let missing = "https://example.com/missing.js";
+        engine.set_page_resources("https://example.com/", vec![ad.into(), missing.into()]).await;
+        engine.set_response_status(missing, 404).await;
+        engine
+            .set_request_interceptor(Arc::new(BlockListInterceptor::new(["*.doubleclick.net"]).unwrap()))
+            .await
+            .unwrap();
+
+        let recorder = engine.enable_har_recording(tab.id).await.unwrap();
+        assert!(recorder.is_recording());
+        engine
+            .navigate_with_options(tab.id, "https://example.com/", NavigateOptions::default())
+            .await
+            .unwrap();
+
+        let log = engine.stop_har_recording(tab.id).await.unwrap();
+        assert!(!recorder.is_recording());
+        let entries: Vec<_> = log
+            .entries
+            .iter()
+            .map(|e| (e.request.url.as_str(), e.response.status))
+            .collect();
+        // Blocked requests never leave the browser and are not recorded
+        assert_eq!(entries, [("https://example.com/", 200), (missing, 404)]);
+        assert_eq!(log.entries[0].response.status_text, "OK");
+        assert_eq!(log.entries[0].request.method, "GET");
+
+        // Nothing is recorded once the recording is stopped
+        assert!(engine.stop_har_recording(tab.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_navigate_detects_redirect_loop() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("about:blank").await.unwrap();
+        engine.set_redirect("https://a.example/", "https://b.example/").await;
+        engine.set_redirect("https://b.example/", "https://c.example/").await;
+        engine.set_redirect("https://c.example/", "https://a.example/").await;
+
+        // The loop is reported long before any load timeout would fire
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            engine.navigate_with_options(tab.id, "https://a.example/", NavigateOptions::default()),
+        )
+        .await
+        .expect("redirect loop must be detected, not time out");
+        let err = result.unwrap_err();
+        match err.downcast_ref::<BrowserError>() {
+            Some(BrowserError::RedirectLoop { url, chain }) => {
+                assert_eq!(url, "https://a.example/");
+                assert_eq!(chain.len(), 4);
+            }
+            other => panic!("expected RedirectLoop, got {:?}", other),
+        }
+
+        // The redirect limit applies before a loop is reached
+        let options = NavigateOptions::default().max_redirects(1);
+        let err = engine
+            .navigate_with_options(tab.id, "https://a.example/", options)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::TooManyRedirects { max_redirects: 1, .. })
+        ));
+
+        // Within the limit the tab ends up on the final URL
+        engine.set_redirect("https://c.example/", "https://d.example/").await;
+        engine
+            .navigate_with_options(tab.id, "https://a.example/", NavigateOptions::default())
+            .await
+            .unwrap();
+        let tab = engine.get_tab(tab.id).await.unwrap().unwrap();
+        assert_eq!(tab.url, "https://d.example/");
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_records_navigation_referrer() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("about:blank").await.unwrap();
+        assert_eq!(
+            engine.referrer_policy(tab.id).await,
+            ReferrerPolicy::StrictOriginWhenCrossOrigin
+        );
+
+        engine
+            .set_referrer_policy(tab.id, ReferrerPolicy::UnsafeUrl)
+            .await
+            .unwrap();
+        let options = NavigateOptions::default().referrer("https://search.example/?q=shoes#top");
+        engine
+            .navigate_with_options(tab.id, "https://shop.example/", options)
+            .await
+            .unwrap();
+        let recorded = engine.last_navigation(tab.id).await.unwrap();
+        assert_eq!(recorded.referrer.as_deref(), Some("https://search.example/?q=shoes"));
+        assert_eq!(engine.referrer_policy(tab.id).await, ReferrerPolicy::UnsafeUrl);
+
+        let invalid = NavigateOptions::default().referrer("not a url");
+        let err = engine
+            .navigate_with_options(tab.id, "https://shop.example/", invalid)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::InvalidRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_warmup_visits_urls_in_order() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("about:blank").await.unwrap();
+        let urls = vec![
+            "https://shop.example/".to_string(),
+            "https://shop.example/shoes".to_string(),
+        ];
+        let dwell = Duration::from_millis(30)..=Duration::from_millis(40);
+
+        let start = Instant::now();
+        engine
+            .warmup(tab.id, &urls, &WarmupOptions::new(dwell))
+            .await
+            .unwrap();
+        let elapsed = start.elapsed();
+
+        let history = engine.navigation_history(tab.id).await;
+        let visited: Vec<&str> = history.iter().map(|(url, _)| url.as_str()).collect();
+        assert_eq!(visited, ["https://shop.example/", "https://shop.example/shoes"]);
+        // Each page is dwelled on, including the last one
+        assert!(history[1].1 - history[0].1 >= Duration::from_millis(30));
+        assert!(elapsed >= Duration::from_millis(60));
+
+        let reversed = WarmupOptions::new(Duration::from_secs(2)..=Duration::from_secs(1));
+        assert!(engine.warmup(tab.id, &urls, &reversed).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_close_tab_handles_before_unload() {
+        // Force-close is the default and never waits on the page
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://editor.example/").await.unwrap();
+        engine.simulate_before_unload(tab.id, "Unsaved changes").await.unwrap();
+        engine.close_tab(tab.id).await.unwrap();
+        assert!(engine.get_tab(tab.id).await.unwrap().is_none());
+
+        let config = BrowserConfig::default().before_unload_policy(BeforeUnloadPolicy::Cancel);
+        let engine = MockBrowserEngine::new(config).await.unwrap();
+        let tab = engine.create_tab("https://editor.example/").await.unwrap();
+        engine.simulate_before_unload(tab.id, "Unsaved changes").await.unwrap();
+        assert!(engine.close_tab(tab.id).await.is_err());
+        assert!(engine.get_tab(tab.id).await.unwrap().is_some());
+
+        // The handler belongs to the page, not the tab
+        engine.simulate_navigation(tab.id, "https://other.example/").await.unwrap();
+        engine.close_tab(tab.id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_recent_events_and_clear() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let first = engine.create_tab("about:blank").await.unwrap();
+        let second = engine.create_tab("about:blank").await.unwrap();
+        engine.set_active_tab(first.id).await.unwrap();
+        engine.set_active_tab(second.id).await.unwrap();
+
+        // Newest last, limited from the newest end
+        let events = engine.get_recent_events(None, 2).await.unwrap();
+        let active: Vec<String> = events
+            .iter()
+            .filter_map(|e| match e {
+                BrowserEvent::ActiveTabChanged { tab_id } => Some(tab_id.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(active, [first.id.to_string(), second.id.to_string()]);
+
+        let own = engine.get_recent_events(Some(first.id), 100).await.unwrap();
+        assert!(!own.is_empty());
+        assert!(own.iter().all(|e| e.tab_id() == Some(first.id.to_string().as_str())));
+
+        engine.clear_event_buffer(Some(first.id)).await.unwrap();
+        assert!(engine.get_recent_events(Some(first.id), 100).await.unwrap().is_empty());
+        assert!(!engine.get_recent_events(None, 100).await.unwrap().is_empty());
+
+        engine.clear_event_buffer(None).await.unwrap();
+        assert!(engine.get_recent_events(None, 100).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_get_content_full_page() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+
+        assert_eq!(
+            engine.get_content(tab.id, None).await.unwrap(),
+            "<html><head></head><body></body></html>"
+        );
+
+        let html = "<html><head><title>Hi</title></head><body><p>x</p></body></html>";
+        engine.set_page_content(tab.id, html).await.unwrap();
+        assert_eq!(engine.get_content(tab.id, None).await.unwrap(), html);
+
+        // Navigation resets the stored document
+        engine.simulate_navigation(tab.id, "https://example.org").await.unwrap();
+        assert_ne!(engine.get_content(tab.id, None).await.unwrap(), html);
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_get_content_selector() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        engine
+            .set_element_content(tab.id, "#main", "<main id=\"main\">body</main>")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            engine.get_content_selector(tab.id, "#main", None).await.unwrap(),
+            "<main id=\"main\">body</main>"
+        );
+
+        let err = engine.get_content_selector(tab.id, "#nav", None).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::DomQueryFailed { selector, .. }) if selector == "#nav"
+        ));
+    }
+
+    #[test]
+    fn test_browser_config_cef_subprocess() {
+        let config = BrowserConfig::default();
+        assert!(config.single_process);
+        assert_eq!(config.resolve_cef_subprocess().unwrap(), None);
+
+        let exe = std::env::current_exe().unwrap();
+        let config = BrowserConfig::new()
+            .single_process(false)
+            .cef_subprocess_path(&exe);
+        assert!(!config.single_process);
+        assert_eq!(config.resolve_cef_subprocess().unwrap(), Some(exe));
+
+        let missing = BrowserConfig::new()
+            .single_process(false)
+            .cef_subprocess_path("/nonexistent/ki-browser-helper");
+        assert!(matches!(
+            missing.resolve_cef_subprocess(),
+            Err(BrowserError::ConfigError(msg)) if msg.contains("/nonexistent/ki-browser-helper")
+        ));
+        // Single-process mode never needs the helper
+        assert_eq!(missing.single_process(true).resolve_cef_subprocess().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_wait_for_function() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        let predicate = "window.__appReady === true && window.__items";
+        engine
+            .set_predicate_results(
+                tab.id,
+                predicate,
+                vec![serde_json::json!(false), serde_json::json!(null), serde_json::json!(3)],
+            )
+            .await
+            .unwrap();
+
+        let value = engine
+            .wait_for_function(
+                tab.id,
+                predicate,
+                Vec::new(),
+                Duration::from_secs(1),
+                Duration::from_millis(5),
+            )
+            .await
+            .unwrap();
+        assert_eq!(value, serde_json::json!(3));
+
+        engine
+            .set_predicate_results(tab.id, "window.never", vec![serde_json::json!(false)])
+            .await
+            .unwrap();
+        let err = engine
+            .wait_for_function(
+                tab.id,
+                "window.never",
+                Vec::new(),
+                Duration::from_millis(30),
+                Duration::from_millis(5),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::Timeout { duration_ms: 30, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_cookie_round_trip() {
+        use crate::browser::cookies::SameSitePolicy;
+
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        let other = engine.create_tab("https://example.com/other").await.unwrap();
+        assert!(engine.get_cookies(tab.id, None).await.unwrap().is_empty());
+
+        let mut session = Cookie::new("sid", "abc", ".example.com");
+        session.http_only = true;
+        session.secure = true;
+        session.same_site = SameSitePolicy::Strict;
+        engine.set_cookie(tab.id, session.clone()).await.unwrap();
+        // Cookies belong to the profile, so every tab sees them
+        assert_eq!(engine.get_cookies(other.id, None).await.unwrap(), vec![session.clone()]);
+
+        session.value = "def".to_string();
+        engine.set_cookie(tab.id, session.clone()).await.unwrap();
+        assert_eq!(engine.get_cookies(tab.id, None).await.unwrap(), vec![session]);
+        assert!(engine
+            .set_cookie(tab.id, Cookie::new("", "v", "example.com"))
+            .await
+            .is_err());
+
+        engine.clear_cookies(tab.id).await.unwrap();
+        assert!(engine.get_cookies(tab.id, None).await.unwrap().is_empty());
+        assert!(engine.get_cookies(Uuid::new_v4(), None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_cookie_url_filter_and_delete() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://shop.example.com").await.unwrap();
+
+        let mut cart = Cookie::new("cart", "3", "shop.example.com");
+        cart.path = "/cart".to_string();
+        cart.expires = Some(chrono::DateTime::from_timestamp(1_893_456_000, 0).unwrap());
+        let mut session = Cookie::new("sid", "abc", ".example.com");
+        session.secure = true;
+        let tracker = Cookie::new("sid", "t", "tracker.net");
+        for cookie in [cart.clone(), session.clone(), tracker.clone()] {
+            engine.set_cookie(tab.id, cookie).await.unwrap();
+        }
+
+        let sent = engine
+            .get_cookies(tab.id, Some("https://shop.example.com/cart/items"))
+            .await
+            .unwrap();
+        assert_eq!(sent, vec![session.clone(), cart.clone()]);
+        let sent = engine
+            .get_cookies(tab.id, Some("http://shop.example.com/"))
+            .await
+            .unwrap();
+        assert!(sent.is_empty());
+
+        engine.delete_cookie(tab.id, "sid", "example.com").await.unwrap();
+        let names: Vec<_> = engine
+            .get_cookies(tab.id, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.name, c.domain))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("cart".to_string(), "shop.example.com".to_string()),
+                ("sid".to_string(), "tracker.net".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_history_back_and_forward() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com/a").await.unwrap();
+        let url = || async { engine.get_tab(tab.id).await.unwrap().unwrap().url };
+
+        assert!(engine.go_back(tab.id).await.is_err());
+        assert!(engine.go_forward(tab.id).await.is_err());
+
+        for page in ["https://example.com/b", "https://example.com/c"] {
+            engine
+                .navigate_with_options(tab.id, page, NavigateOptions::default())
+                .await
+                .unwrap();
+        }
+
+        engine.go_back(tab.id).await.unwrap();
+        assert_eq!(url().await, "https://example.com/b");
+        engine.go_back(tab.id).await.unwrap();
+        assert_eq!(url().await, "https://example.com/a");
+        assert!(engine.go_back(tab.id).await.is_err());
+
+        engine.go_forward(tab.id).await.unwrap();
+        assert_eq!(url().await, "https://example.com/b");
+
+        // A new navigation drops the forward entries
+        engine
+            .navigate_with_options(tab.id, "https://example.com/d", NavigateOptions::default())
+            .await
+            .unwrap();
+        assert!(engine.go_forward(tab.id).await.is_err());
+        assert_eq!(
+            engine.history(tab.id).await.unwrap(),
+            (
+                vec![
+                    "https://example.com/a".to_string(),
+                    "https://example.com/b".to_string(),
+                    "https://example.com/d".to_string(),
+                ],
+                2
+            )
+        );
+
+        engine.reload(tab.id, true).await.unwrap();
+        assert_eq!(url().await, "https://example.com/d");
+        assert!(engine.reload(Uuid::new_v4(), false).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_network_throttle() {
+        use crate::browser::throttle::NetworkPreset;
+
+        // 8000 kbps = 1 MB/s
+        let config = BrowserConfig::default().with_throttle(NetworkThrottleConfig::new(8_000, 8_000, 20));
+        let engine = MockBrowserEngine::new(config).await.unwrap();
+        let tab = engine.create_tab("about:blank").await.unwrap();
+        engine.set_response_size("https://example.com/small", 10_000).await;
+        engine.set_response_size("https://example.com/large", 200_000).await;
+
+        let start = Instant::now();
+        engine
+            .navigate_with_options(tab.id, "https://example.com/small", NavigateOptions::default())
+            .await
+            .unwrap();
+        let small = start.elapsed();
+        assert!(small >= Duration::from_millis(30));
+
+        let start = Instant::now();
+        engine
+            .navigate_with_options(tab.id, "https://example.com/large", NavigateOptions::default())
+            .await
+            .unwrap();
+        let large = start.elapsed();
+        assert!(large >= Duration::from_millis(220));
+        assert!(large > small + Duration::from_millis(150));
 
-        *running = false;
-        Ok(())
+        let offline = BrowserConfig::default().with_throttle(NetworkPreset::Offline.config());
+        let engine = MockBrowserEngine::new(offline).await.unwrap();
+        let tab = engine.create_tab("about:blank").await.unwrap();
+        let err = engine
+            .navigate_with_options(tab.id, "https://example.com/", NavigateOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::NavigationFailed { reason, .. }) if reason == INTERNET_DISCONNECTED
+        ));
     }
 
-    async fn create_tab(&self, url: &str) -> Result<Tab> {
-        let running = self.is_running.read().await;
-        if !*running {
-            return Err(anyhow!("Browser engine is not running"));
+    #[tokio::test]
+    async fn test_mock_engine_wait_for_selector() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        let button = BoundingBox::new(40.0, 300.0, 120.0, 32.0);
+        engine
+            .set_selector_appears_after(tab.id, "#checkout", button, 3)
+            .await
+            .unwrap();
+
+        let found = engine.wait_for_selector(tab.id, "#checkout", 5_000).await.unwrap();
+        assert_eq!(found, button);
+        assert_eq!(engine.selector_delays.read().await[&(tab.id, "#checkout".to_string())], 0);
+
+        let err = engine.wait_for_selector(tab.id, "#missing", 30).await.unwrap_err();
+        match err.downcast_ref::<BrowserError>() {
+            Some(BrowserError::Timeout { operation, duration_ms: 30 }) => {
+                assert!(operation.contains("#missing"));
+            }
+            other => panic!("expected timeout, got {:?}", other),
         }
-        drop(running);
+    }
 
-        let tab = Tab::new(url.to_string());
-        let tab_id = tab.id;
+    #[tokio::test]
+    async fn test_mock_engine_wait_for_resolves_immediately() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
 
-        let mut tabs = self.tabs.write().await;
-        tabs.insert(tab_id, tab.clone());
+        for condition in [
+            WaitCondition::DomContentLoaded,
+            WaitCondition::NetworkIdle { idle_ms: 500 },
+            WaitCondition::Selector("#app".to_string()),
+            WaitCondition::TitleContains("Example".to_string()),
+        ] {
+            engine.wait_for(tab.id, condition, Duration::ZERO).await.unwrap();
+        }
 
-        Ok(tab)
+        assert!(engine
+            .wait_for(tab.id, WaitCondition::Url("regex:(".to_string()), Duration::ZERO)
+            .await
+            .is_err());
+        assert!(engine
+            .wait_for(Uuid::new_v4(), WaitCondition::DomContentLoaded, Duration::ZERO)
+            .await
+            .is_err());
     }
 
-    async fn close_tab(&self, tab_id: Uuid) -> Result<()> {
-        let running = self.is_running.read().await;
-        if !*running {
-            return Err(anyhow!("Browser engine is not running"));
-        }
-        drop(running);
+    #[tokio::test]
+    async fn test_mock_engine_get_computed_style() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        let style: HashMap<String, String> = [
+            ("display", "flex"),
+            ("color", "rgb(0, 0, 0)"),
+            ("font-size", "16px"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        engine.set_computed_style(tab.id, "#app", style).await.unwrap();
 
-        let mut tabs = self.tabs.write().await;
-        tabs.remove(&tab_id)
-            .ok_or_else(|| anyhow!("Tab not found: {}", tab_id))?;
+        let requested = engine
+            .get_computed_style(tab.id, "#app", vec!["display".into(), "color".into()])
+            .await
+            .unwrap();
+        assert_eq!(requested.len(), 2);
+        assert_eq!(requested["display"], "flex");
+        assert_eq!(requested["color"], "rgb(0, 0, 0)");
 
-        Ok(())
+        let defaults = engine.get_computed_style(tab.id, "#app", Vec::new()).await.unwrap();
+        assert_eq!(defaults["font-size"], "16px");
+        assert_eq!(defaults["visibility"], "");
+
+        let err = engine
+            .get_computed_style(tab.id, "#missing", Vec::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::DomQueryFailed { selector, .. }) if selector == "#missing"
+        ));
     }
 
-    async fn get_tabs(&self) -> Result<Vec<Tab>> {
-        let running = self.is_running.read().await;
-        if !*running {
-            return Err(anyhow!("Browser engine is not running"));
-        }
-        drop(running);
+    #[tokio::test]
+    async fn test_mock_engine_get_content_size_cap() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        let html = format!("<html><body>{}</body></html>", "a".repeat(2000));
+        engine.set_page_content(tab.id, &html).await.unwrap();
+        engine.set_element_content(tab.id, "body", "<body>ok</body>").await.unwrap();
 
-        let tabs = self.tabs.read().await;
-        Ok(tabs.values().cloned().collect())
+        let err = engine.get_content(tab.id, Some(1024)).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::ContentTooLarge { size, max_bytes: 1024 }) if *size == html.len()
+        ));
+        assert_eq!(engine.get_content(tab.id, Some(html.len())).await.unwrap(), html);
+        assert_eq!(
+            engine.get_content_selector(tab.id, "body", Some(1024)).await.unwrap(),
+            "<body>ok</body>"
+        );
     }
 
-    async fn get_tab(&self, tab_id: Uuid) -> Result<Option<Tab>> {
-        let running = self.is_running.read().await;
-        if !*running {
-            return Err(anyhow!("Browser engine is not running"));
-        }
-        drop(running);
+    #[tokio::test]
+    async fn test_mock_engine_call_function_passes_args_verbatim() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
 
-        let tabs = self.tabs.read().await;
-        Ok(tabs.get(&tab_id).cloned())
-    }
+        let selector = r#"input[name="q"], a[title='it\'s']"#;
+        let result = engine
+            .call_function(
+                tab.id,
+                "return document.querySelector(args[0]).value = args[1];",
+                vec![serde_json::json!(selector), serde_json::json!("say \"hi\"")],
+            )
+            .await
+            .unwrap();
 
-    fn config(&self) -> &BrowserConfig {
-        &self.config
+        assert_eq!(result["args"][0].as_str(), Some(selector));
+        assert_eq!(result["args"][1].as_str(), Some("say \"hi\""));
+        assert!(engine
+            .call_function(Uuid::new_v4(), "return 1;", Vec::new())
+            .await
+            .is_err());
     }
 
-    async fn is_running(&self) -> bool {
-        *self.is_running.read().await
+    #[tokio::test]
+    async fn test_mock_engine_call_function_times_out() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        engine.set_call_duration(tab.id, Duration::from_secs(60)).await.unwrap();
+
+        let options = EvalOptions::default().with_timeout(Duration::from_millis(20));
+        let err = engine
+            .call_function_with_options(tab.id, "while (true) {}", Vec::new(), options)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::Timeout { duration_ms: 20, .. })
+        ));
+
+        // A call within its budget still returns
+        engine.set_call_duration(tab.id, Duration::from_millis(1)).await.unwrap();
+        let options = EvalOptions::default().with_timeout(Duration::from_secs(5));
+        assert!(engine
+            .call_function_with_options(tab.id, "return 1;", Vec::new(), options)
+            .await
+            .is_ok());
     }
 
-    async fn get_frame_tree(&self, _tab_id: Uuid) -> Result<Vec<FrameInfo>> {
-        // Mock implementation returns a dummy main frame
-        Ok(vec![FrameInfo {
-            frame_id: "main-frame".to_string(),
-            parent_frame_id: None,
-            url: "about:blank".to_string(),
-            name: String::new(),
-            security_origin: "about:blank".to_string(),
-        }])
+    #[tokio::test]
+    async fn test_mock_engine_call_function_caps_result_size() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        let big = vec![serde_json::json!("x".repeat(4096))];
+
+        let options = EvalOptions::default().with_max_result_bytes(1024);
+        let err = engine
+            .call_function_with_options(tab.id, "return args[0];", big.clone(), options)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::ResultTooLarge { size, max_bytes: 1024 }) if *size > 4096
+        ));
+
+        // The default cap leaves ordinary results alone
+        let result = engine.call_function(tab.id, "return args[0];", big).await.unwrap();
+        assert_eq!(result["args"][0].as_str().map(str::len), Some(4096));
     }
 
-    async fn evaluate_in_frame(
-        &self,
-        _tab_id: Uuid,
-        frame_id: &str,
-        _script: &str,
-    ) -> Result<serde_json::Value> {
-        // Mock implementation returns a simple result indicating frame evaluation
-        Ok(serde_json::json!({
-            "frame_id": frame_id,
-            "result": "mock frame evaluation"
-        }))
+    #[tokio::test]
+    async fn test_mock_engine_scroll_into_view_passes_alignment() {
+        use crate::browser::scroll::{ScrollAlign, ScrollLogicalPosition};
+
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        let bounding_box = BoundingBox::new(10.0, 80.0, 120.0, 32.0);
+        engine.set_element_box(tab.id, "#buy", bounding_box).await.unwrap();
+
+        let align = ScrollAlign::new(ScrollLogicalPosition::Start, ScrollLogicalPosition::Nearest)
+            .with_offset(72.0)
+            .with_smooth(true);
+        let result = engine.scroll_into_view(tab.id, "#buy", align).await.unwrap();
+        assert_eq!(result, bounding_box);
+
+        let calls = engine.function_calls(tab.id).await;
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0]["function"].as_str(), Some(SCROLL_INTO_VIEW_FUNCTION));
+        assert_eq!(
+            calls[0]["args"],
+            serde_json::json!([
+                "#buy",
+                {"block": "start", "inline": "nearest", "offset": 72.0, "smooth": true}
+            ])
+        );
+
+        let err = engine
+            .scroll_into_view(tab.id, "#missing", ScrollAlign::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::DomQueryFailed { .. })
+        ));
     }
-}
 
-impl MockBrowserEngine {
-    /// Simulates a tab finishing loading.
-    ///
-    /// This method is useful for testing scenarios where you need
-    /// to simulate page load completion.
-    pub async fn simulate_tab_ready(&self, tab_id: Uuid) -> Result<()> {
-        let mut tabs = self.tabs.write().await;
-        if let Some(tab) = tabs.get_mut(&tab_id) {
-            tab.set_ready();
-            Ok(())
-        } else {
-            Err(anyhow!("Tab not found: {}", tab_id))
-        }
+    #[tokio::test]
+    async fn test_mock_engine_screenshot_element_clips_to_device_pixels() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        engine
+            .resize_tab(tab.id, Viewport::new(800, 600).with_device_scale_factor(2.0))
+            .await
+            .unwrap();
+        let bounding_box = BoundingBox::new(10.0, 80.0, 120.0, 32.5);
+        engine.set_element_box(tab.id, "#card", bounding_box).await.unwrap();
+
+        let screenshot = engine
+            .screenshot_element(tab.id, "#card", ScreenshotOptions::default())
+            .await
+            .unwrap();
+        assert_eq!((screenshot.width, screenshot.height), (240, 65));
+        assert_eq!(screenshot.device_scale_factor, 2.0);
+
+        let err = engine
+            .screenshot_element(tab.id, "#missing", ScreenshotOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::DomQueryFailed { .. })
+        ));
     }
 
-    /// Simulates a tab encountering an error.
-    pub async fn simulate_tab_error(&self, tab_id: Uuid, error: &str) -> Result<()> {
-        let mut tabs = self.tabs.write().await;
-        if let Some(tab) = tabs.get_mut(&tab_id) {
-            tab.set_error(error.to_string());
-            Ok(())
-        } else {
-            Err(anyhow!("Tab not found: {}", tab_id))
-        }
+    #[tokio::test]
+    async fn test_mock_engine_binding_call_surfaces_as_event() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let mut events = engine.subscribe_events().unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+
+        let payload = serde_json::json!({"kind": "cart-updated", "items": 3});
+        assert!(engine
+            .simulate_binding_call(tab.id, "onPageEvent", payload.clone())
+            .await
+            .is_err());
+
+        engine.expose_binding(tab.id, "onPageEvent").await.unwrap();
+        // Bindings survive navigation
+        engine.simulate_navigation(tab.id, "https://example.com/next").await.unwrap();
+        engine
+            .simulate_binding_call(tab.id, "onPageEvent", payload.clone())
+            .await
+            .unwrap();
+
+        let event = events.try_recv().unwrap();
+        let BrowserEvent::Binding { tab_id, name, payload: received } = event else {
+            unreachable!("Expected Binding event, got {:?}", event);
+        };
+        assert_eq!(tab_id, tab.id.to_string());
+        assert_eq!(name, "onPageEvent");
+        assert_eq!(received, payload);
+
+        assert!(engine.expose_binding(tab.id, "not-an-identifier").await.is_err());
     }
 
-    /// Updates the title of a tab (simulating title change after page load).
-    pub async fn simulate_title_change(&self, tab_id: Uuid, title: &str) -> Result<()> {
-        let mut tabs = self.tabs.write().await;
-        if let Some(tab) = tabs.get_mut(&tab_id) {
-            tab.title = title.to_string();
-            Ok(())
-        } else {
-            Err(anyhow!("Tab not found: {}", tab_id))
+    #[tokio::test]
+    async fn test_mock_engine_new_tabs_inherit_default_viewport() {
+        let config = BrowserConfig::default().window_size(1280, 720);
+        let engine = MockBrowserEngine::new(config).await.unwrap();
+        let existing = engine.create_tab("https://example.com").await.unwrap();
+        assert_eq!(engine.get_viewport(existing.id).await.unwrap(), Viewport::new(1280, 720));
+
+        let mut events = engine.subscribe_events().unwrap();
+        let mobile = Viewport::new(390, 844).with_device_scale_factor(3.0);
+        engine.set_default_viewport(mobile, false).await.unwrap();
+
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        assert_eq!(engine.default_viewport().await, mobile);
+        assert_eq!(engine.get_viewport(tab.id).await.unwrap(), mobile);
+        assert_eq!(engine.get_viewport(existing.id).await.unwrap(), Viewport::new(1280, 720));
+        assert!(events.try_recv().is_err());
+
+        engine.set_default_viewport(mobile, true).await.unwrap();
+        assert_eq!(engine.get_viewport(existing.id).await.unwrap(), mobile);
+        let mut resized = Vec::new();
+        while let Ok(BrowserEvent::ViewportChanged { tab_id, width, .. }) = events.try_recv() {
+            assert_eq!(width, 390);
+            resized.push(tab_id);
         }
+        resized.sort();
+        let mut expected = vec![existing.id.to_string(), tab.id.to_string()];
+        expected.sort();
+        assert_eq!(resized, expected);
+
+        assert!(engine
+            .set_default_viewport(Viewport::new(0, 720), false)
+            .await
+            .is_err());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[tokio::test]
+    async fn test_mock_engine_records_cpu_throttling() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        assert!(engine.capabilities().cpu_throttling);
+        assert!(!EngineCapabilities::default().cpu_throttling);
+
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        assert_eq!(engine.cpu_throttling_rate(tab.id).await, None);
+        engine.set_cpu_throttling(tab.id, 4.0).await.unwrap();
+        assert_eq!(engine.cpu_throttling_rate(tab.id).await, Some(4.0));
+
+        // A rate of 1 turns throttling off; speed-ups are rejected
+        engine.set_cpu_throttling(tab.id, 1.0).await.unwrap();
+        assert_eq!(engine.cpu_throttling_rate(tab.id).await, None);
+        let err = engine.set_cpu_throttling(tab.id, 0.5).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::InvalidRequest(_))
+        ));
+        assert!(engine.set_cpu_throttling(Uuid::new_v4(), 2.0).await.is_err());
+    }
 
     #[tokio::test]
-    async fn test_browser_config_builder() {
-        let config = BrowserConfig::new()
-            .headless(false)
-            .window_size(1280, 720)
-            .user_agent("TestAgent/1.0")
-            .proxy("http://localhost:8080")
-            .timeout_ms(60_000)
-            .with_devtools();
+    async fn test_mock_engine_tracks_permission_grants() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        assert!(engine.capabilities().permission_grants);
+        let tab = engine.create_tab("https://maps.example.com").await.unwrap();
 
-        assert!(!config.headless);
-        assert_eq!(config.window_size, (1280, 720));
-        assert_eq!(config.user_agent, Some("TestAgent/1.0".to_string()));
-        assert_eq!(config.proxy, Some("http://localhost:8080".to_string()));
-        assert_eq!(config.timeout_ms, 60_000);
-        assert!(config.devtools);
+        engine
+            .grant_permissions(
+                tab.id,
+                "https://maps.example.com/route",
+                vec![Permission::Notifications, Permission::Geolocation, Permission::Geolocation],
+            )
+            .await
+            .unwrap();
+        engine
+            .grant_permissions(tab.id, "https://other.example.com", vec![Permission::ClipboardReadWrite])
+            .await
+            .unwrap();
+        assert_eq!(
+            engine.granted_permissions(tab.id, "https://maps.example.com").await,
+            vec![Permission::Geolocation, Permission::Notifications]
+        );
+
+        // A new grant replaces the origin's previous one
+        engine
+            .grant_permissions(tab.id, "https://maps.example.com", vec![Permission::Camera])
+            .await
+            .unwrap();
+        assert_eq!(
+            engine.granted_permissions(tab.id, "https://maps.example.com").await,
+            vec![Permission::Camera]
+        );
+        assert_eq!(
+            engine.granted_permissions(tab.id, "https://other.example.com").await,
+            vec![Permission::ClipboardReadWrite]
+        );
+
+        let err = engine
+            .grant_permissions(tab.id, "not an origin", vec![Permission::Geolocation])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::InvalidRequest(_))
+        ));
+
+        engine.reset_permissions(tab.id).await.unwrap();
+        assert!(engine
+            .granted_permissions(tab.id, "https://other.example.com")
+            .await
+            .is_empty());
+        assert!(engine.reset_permissions(Uuid::new_v4()).await.is_err());
     }
 
     #[tokio::test]
-    async fn test_mock_engine_create_and_close_tab() {
-        let config = BrowserConfig::default();
+    async fn test_mock_engine_dismisses_consent_banner() {
+        let config = BrowserConfig::default().auto_dismiss_consent(true);
         let engine = MockBrowserEngine::new(config).await.unwrap();
-
         let tab = engine.create_tab("https://example.com").await.unwrap();
-        assert_eq!(tab.url, "https://example.com");
+        let mut events = engine.subscribe_events().unwrap();
 
-        let tabs = engine.get_tabs().await.unwrap();
-        assert_eq!(tabs.len(), 1);
+        engine
+            .set_element_content(tab.id, "#onetrust-accept-btn-handler", "Accept All")
+            .await
+            .unwrap();
+        engine.simulate_tab_ready(tab.id).await.unwrap();
+
+        let event = events.try_recv().unwrap();
+        let BrowserEvent::ConsentDismissed { tab_id, selector, text, .. } = event else {
+            unreachable!("Expected ConsentDismissed event, got {:?}", event);
+        };
+        assert_eq!(tab_id, tab.id.to_string());
+        assert_eq!(selector.as_deref(), Some("#onetrust-accept-btn-handler"));
+        assert_eq!(text, "Accept All");
+
+        // The banner is gone, so the next load has nothing to dismiss
+        engine.simulate_tab_ready(tab.id).await.unwrap();
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_engine_captures_matching_responses() {
+        let engine = MockBrowserEngine::new(BrowserConfig::default()).await.unwrap();
+        let tab = engine.create_tab("https://shop.example.com").await.unwrap();
+
+        engine
+            .enable_response_capture(tab.id, "*.example.com/api/*")
+            .await
+            .unwrap();
+
+        let products = CapturedResponse::new(
+            "https://shop.example.com/api/products?page=1",
+            200,
+            r#"{"items":[{"id":1}]}"#,
+        )
+        .with_mime_type("application/json");
+        let cart = CapturedResponse::new("https://shop.example.com/api/cart", 200, r#"{"count":2}"#)
+            .with_mime_type("application/json");
+        let stylesheet = CapturedResponse::new("https://shop.example.com/static/app.css", 200, "body{}");
+
+        assert!(engine.simulate_response(tab.id, products.clone()).await.unwrap());
+        assert!(!engine.simulate_response(tab.id, stylesheet).await.unwrap());
+        assert!(engine.simulate_response(tab.id, cart.clone()).await.unwrap());
+
+        let captured = engine.get_captured_responses(tab.id).await.unwrap();
+        assert_eq!(captured, vec![products, cart]);
+
+        let err = engine.enable_response_capture(tab.id, "").await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::InvalidRequest(_))
+        ));
 
         engine.close_tab(tab.id).await.unwrap();
+        assert!(engine.get_captured_responses(tab.id).await.is_err());
+    }
 
-        let tabs = engine.get_tabs().await.unwrap();
-        assert!(tabs.is_empty());
+    #[tokio::test]
+    async fn test_mock_engine_wait_for_mutation() {
+        let engine = Arc::new(MockBrowserEngine::new(BrowserConfig::default()).await.unwrap());
+        let tab = engine.create_tab("https://example.com").await.unwrap();
+        let attribute_change = MutationInfo {
+            kind: "attributes".to_string(),
+            target: "div".to_string(),
+            attribute_name: Some("class".to_string()),
+            added_nodes: 0,
+            removed_nodes: 0,
+        };
+
+        let waiter = tokio::spawn({
+            let engine = engine.clone();
+            async move {
+                engine
+                    .wait_for_mutation(tab.id, "#app", MutationKinds::default(), Duration::from_secs(5))
+                    .await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        // Other selectors do not wake the waiter
+        engine
+            .simulate_mutation(tab.id, "#other", attribute_change.clone())
+            .await
+            .unwrap();
+        engine
+            .simulate_mutation(tab.id, "#app", attribute_change.clone())
+            .await
+            .unwrap();
+        assert_eq!(waiter.await.unwrap().unwrap(), vec![attribute_change.clone()]);
+
+        // Unobserved kinds run into the timeout
+        let child_list_only = MutationKinds {
+            attributes: false,
+            character_data: false,
+            ..Default::default()
+        };
+        let waiter = tokio::spawn({
+            let engine = engine.clone();
+            async move {
+                engine
+                    .wait_for_mutation(tab.id, "#app", child_list_only, Duration::from_millis(100))
+                    .await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        engine.simulate_mutation(tab.id, "#app", attribute_change).await.unwrap();
+        let err = waiter.await.unwrap().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::Timeout { duration_ms: 100, .. })
+        ));
     }
 
     #[tokio::test]
-    async fn test_mock_engine_shutdown() {
-        let config = BrowserConfig::default();
+    async fn test_mock_engine_screenshot_on_error_writes_capture() {
+        use crate::browser::interceptor::BlockListInterceptor;
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = BrowserConfig::new().screenshot_on_error(dir.path());
         let engine = MockBrowserEngine::new(config).await.unwrap();
+        let tab = engine.create_tab("https://example.com").await.unwrap();
 
-        assert!(engine.is_running().await);
+        // A successful navigation writes nothing
+        engine
+            .navigate_with_options(tab.id, "https://example.com/ok", NavigateOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_dir(dir.path()).map_or(0, |d| d.count()), 0);
 
-        engine.shutdown().await.unwrap();
+        let blocker = BlockListInterceptor::new(["*.example.net"]).unwrap();
+        engine.set_request_interceptor(Arc::new(blocker)).await.unwrap();
+        engine
+            .navigate_with_options(tab.id, "https://ads.example.net/", NavigateOptions::default())
+            .await
+            .unwrap_err();
 
-        assert!(!engine.is_running().await);
+        let mut files: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        files.sort();
+        assert_eq!(files.len(), 2);
 
-        // Operations should fail after shutdown
-        assert!(engine.create_tab("https://example.com").await.is_err());
+        let sidecar = files.iter().find(|p| p.extension().unwrap() == "json").unwrap();
+        let capture: crate::browser::ErrorCapture =
+            serde_json::from_slice(&std::fs::read(sidecar).unwrap()).unwrap();
+        assert_eq!(capture.tab_id, tab.id);
+        assert_eq!(capture.url, "https://example.com/ok");
+        assert_eq!(capture.operation, "navigate");
+        assert_eq!(
+            capture.error,
+            "Navigation failed for https://ads.example.net/: net::ERR_BLOCKED_BY_CLIENT"
+        );
+
+        let image = dir.path().join(capture.screenshot.unwrap());
+        assert_eq!(image.extension().unwrap(), "png");
+        assert!(image.exists());
     }
 
     #[tokio::test]
@@ -0,0 +1,102 @@
+//! Screenshot-on-error diagnostics.
+//!
+//! When [`BrowserConfig::screenshot_on_error`](crate::browser::BrowserConfig)
+//! points at a directory, engines call [`write_error_capture`] after a failed
+//! command. It stores the tab's current frame under a timestamped name plus a
+//! sidecar JSON describing the failure, so a run can be debugged after the fact.
+//!
+//! Files are named `<timestamp>_<tab>_<operation>.<ext>` and
+//! `<timestamp>_<tab>_<operation>.json`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::browser::screenshot::Screenshot;
+
+/// Sidecar metadata written next to an error screenshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorCapture {
+    /// When the failure was captured.
+    pub timestamp: DateTime<Utc>,
+
+    /// Tab the failed command targeted.
+    pub tab_id: Uuid,
+
+    /// URL of the tab at capture time.
+    pub url: String,
+
+    /// Name of the failed operation (e.g. `"navigate"`, `"wait_for_ready"`).
+    pub operation: String,
+
+    /// Error message of the failed operation.
+    pub error: String,
+
+    /// File name of the screenshot, `None` if no frame could be captured.
+    pub screenshot: Option<String>,
+
+    /// Why the screenshot is missing, if it is.
+    pub screenshot_error: Option<String>,
+}
+
+/// Writes the screenshot (if any) and sidecar JSON for a failed operation.
+///
+/// Creates `dir` if needed and returns the path of the sidecar JSON file.
+pub async fn write_error_capture(
+    dir: &Path,
+    tab_id: Uuid,
+    url: &str,
+    operation: &str,
+    error: &str,
+    screenshot: Result<Screenshot>,
+) -> Result<PathBuf> {
+    tokio::fs::create_dir_all(dir)
+        .await
+        .with_context(|| format!("Failed to create error capture directory {}", dir.display()))?;
+
+    let timestamp = Utc::now();
+    let operation_slug: String = operation
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let stem = format!(
+        "{}_{}_{}",
+        timestamp.format("%Y%m%dT%H%M%S%.3fZ"),
+        &tab_id.simple().to_string()[..8],
+        operation_slug
+    );
+
+    let (screenshot_file, screenshot_error) = match write_screenshot(dir, &stem, screenshot).await {
+        Ok(file_name) => (Some(file_name), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    let capture = ErrorCapture {
+        timestamp,
+        tab_id,
+        url: url.to_string(),
+        operation: operation.to_string(),
+        error: error.to_string(),
+        screenshot: screenshot_file,
+        screenshot_error,
+    };
+
+    let sidecar = dir.join(format!("{}.json", stem));
+    tokio::fs::write(&sidecar, serde_json::to_vec_pretty(&capture)?)
+        .await
+        .with_context(|| format!("Failed to write {}", sidecar.display()))?;
+
+    Ok(sidecar)
+}
+
+/// Writes the error screenshot as `<stem>.<ext>` and returns its file name.
+async fn write_screenshot(dir: &Path, stem: &str, screenshot: Result<Screenshot>) -> Result<String> {
+    let screenshot = screenshot?;
+    let file_name = format!("{}.{}", stem, screenshot.format.extension());
+    tokio::fs::write(dir.join(&file_name), screenshot.decode()?)
+        .await
+        .context("Failed to write error screenshot")?;
+    Ok(file_name)
+}
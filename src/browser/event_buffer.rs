@@ -0,0 +1,163 @@
+//! Ring buffer of recent engine events.
+//!
+//! Backs [`BrowserEngine::get_recent_events`](crate::browser::BrowserEngine::get_recent_events)
+//! and [`BrowserEngine::clear_event_buffer`](crate::browser::BrowserEngine::clear_event_buffer),
+//! so recent events can be inspected without a live WebSocket. Every event
+//! gets a sequence number when it is recorded. Numbers only ever increase and
+//! are not reused after clearing, so events are always returned in the order
+//! they were emitted. Once the buffer is full, the oldest event is dropped.
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::debug;
+
+use crate::browser::events::BrowserEvent;
+
+/// Number of events kept by an engine's event buffer.
+pub const DEFAULT_EVENT_BUFFER_CAPACITY: usize = 512;
+
+/// Event buffer shared between an engine and its recorder task.
+pub type SharedEventBuffer = Arc<Mutex<EventBuffer>>;
+
+/// Bounded buffer of the most recent events with sequence numbers.
+#[derive(Debug)]
+pub struct EventBuffer {
+    events: VecDeque<(u64, BrowserEvent)>,
+    capacity: usize,
+    next_seq: u64,
+}
+
+impl EventBuffer {
+    /// Creates an empty buffer holding at most `capacity` events.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity.min(DEFAULT_EVENT_BUFFER_CAPACITY)),
+            capacity,
+            next_seq: 1,
+        }
+    }
+
+    /// Creates an empty buffer behind a shared lock.
+    pub fn shared(capacity: usize) -> SharedEventBuffer {
+        Arc::new(Mutex::new(Self::new(capacity)))
+    }
+
+    /// Records `event` and returns its sequence number.
+    pub fn push(&mut self, event: BrowserEvent) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        if self.capacity == 0 {
+            return seq;
+        }
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back((seq, event));
+        seq
+    }
+
+    /// Returns up to `limit` of the newest events, oldest first.
+    ///
+    /// With `tab_id`, only events of that tab are considered.
+    pub fn recent(&self, tab_id: Option<&str>, limit: usize) -> Vec<BrowserEvent> {
+        let mut events: Vec<BrowserEvent> = self
+            .events
+            .iter()
+            .rev()
+            .filter(|(_, event)| tab_id.is_none() || event.tab_id() == tab_id)
+            .take(limit)
+            .map(|(_, event)| event.clone())
+            .collect();
+        events.reverse();
+        events
+    }
+
+    /// Removes all events, or only those of `tab_id`.
+    pub fn clear(&mut self, tab_id: Option<&str>) {
+        match tab_id {
+            Some(tab_id) => self
+                .events
+                .retain(|(_, event)| event.tab_id() != Some(tab_id)),
+            None => self.events.clear(),
+        }
+    }
+
+    /// Sequence number the next recorded event will get.
+    pub fn next_seq(&self) -> u64 {
+        self.next_seq
+    }
+
+    /// Number of buffered events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Whether the buffer holds no events.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// Records every event received from `events` into `buffer`.
+///
+/// The spawned task ends when the sender is dropped. Events lost to a
+/// lagging receiver are skipped.
+pub fn record_events(
+    buffer: SharedEventBuffer,
+    mut events: broadcast::Receiver<BrowserEvent>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    buffer.lock().push(event);
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug!("Event buffer missed {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title(tab_id: &str, title: &str) -> BrowserEvent {
+        BrowserEvent::TitleChanged {
+            tab_id: tab_id.to_string(),
+            title: title.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_event_buffer_drops_oldest_and_keeps_sequence() {
+        let mut buffer = EventBuffer::new(2);
+        assert_eq!(buffer.push(title("a", "1")), 1);
+        assert_eq!(buffer.push(title("b", "2")), 2);
+        assert_eq!(buffer.push(title("a", "3")), 3);
+        assert_eq!(buffer.len(), 2);
+
+        let titles: Vec<_> = buffer
+            .recent(None, 10)
+            .into_iter()
+            .map(|e| match e {
+                BrowserEvent::TitleChanged { title, .. } => title,
+                other => panic!("unexpected event {:?}", other),
+            })
+            .collect();
+        assert_eq!(titles, ["2", "3"]);
+        assert_eq!(buffer.recent(Some("a"), 10).len(), 1);
+
+        // Clearing a tab keeps other tabs' events; numbering continues
+        buffer.clear(Some("a"));
+        assert_eq!(buffer.recent(None, 10).len(), 1);
+        buffer.clear(None);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.push(title("a", "4")), 4);
+    }
+}
@@ -0,0 +1,217 @@
+//! Events emitted by browser engines.
+//!
+//! Engines and their callbacks publish [`BrowserEvent`]s on a broadcast
+//! channel; the API layer forwards them to WebSocket clients and the
+//! [`event_buffer`](crate::browser::event_buffer) keeps the recent ones.
+
+use bytes::Bytes;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Browser events that can be broadcast to connected clients
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", content = "data")]
+pub enum BrowserEvent {
+    /// A new tab was created
+    TabCreated {
+        tab_id: String,
+        url: String,
+    },
+
+    /// A tab was closed
+    TabClosed {
+        tab_id: String,
+    },
+
+    /// Navigation completed in a tab
+    NavigationComplete {
+        tab_id: String,
+        url: String,
+        title: String,
+    },
+
+    /// DOM is ready in a tab
+    DomReady {
+        tab_id: String,
+    },
+
+    /// Page finished loading
+    LoadComplete {
+        tab_id: String,
+        url: String,
+    },
+
+    /// Tab title changed
+    TitleChanged {
+        tab_id: String,
+        title: String,
+    },
+
+    /// Tab URL changed (without full navigation)
+    UrlChanged {
+        tab_id: String,
+        url: String,
+    },
+
+    /// Tab favicon changed
+    FaviconChanged {
+        tab_id: String,
+        favicon_url: Option<String>,
+    },
+
+    /// Tab loading state changed
+    LoadingStateChanged {
+        tab_id: String,
+        is_loading: bool,
+    },
+
+    /// Active tab changed
+    ActiveTabChanged {
+        tab_id: String,
+    },
+
+    /// Viewport of a tab was resized
+    ViewportChanged {
+        tab_id: String,
+        width: u32,
+        height: u32,
+        device_scale_factor: f64,
+    },
+
+    /// Console message from a tab
+    ConsoleMessage {
+        tab_id: String,
+        level: String, // "log", "warn", "error", "info", "debug"
+        message: String,
+        source: Option<String>,
+        line: Option<u32>,
+    },
+
+    /// A network request failed (HTTP status >= 400 or load error)
+    RequestFailed {
+        tab_id: String,
+        url: String,
+        status: Option<u16>,
+        error: String,
+    },
+
+    /// Page script called a binding installed with `expose_binding`
+    Binding {
+        tab_id: String,
+        name: String,
+        payload: serde_json::Value,
+    },
+
+    /// A cookie-consent banner was dismissed automatically
+    ConsentDismissed {
+        tab_id: String,
+        url: String,
+        /// Selector that matched; absent when matched by button text
+        selector: Option<String>,
+        text: String,
+    },
+
+    /// JavaScript dialog appeared
+    DialogOpened {
+        tab_id: String,
+        dialog_type: String, // "alert", "confirm", "prompt", "beforeunload"
+        message: String,
+    },
+
+    /// Download started
+    DownloadStarted {
+        download_id: String,
+        url: String,
+        filename: String,
+    },
+
+    /// Download progress updated
+    DownloadProgress {
+        download_id: String,
+        received_bytes: u64,
+        total_bytes: Option<u64>,
+    },
+
+    /// Download completed
+    DownloadComplete {
+        download_id: String,
+        path: String,
+    },
+
+    /// A piece of a stored screenshot, pushed in response to `FetchScreenshot`
+    ScreenshotChunk {
+        handle: String,
+        offset: usize,
+        total_bytes: usize,
+        data: String, // Base64 encoded bytes
+        is_last: bool,
+    },
+
+    /// An error occurred
+    Error {
+        tab_id: Option<String>,
+        code: String,
+        message: String,
+    },
+
+    /// Connection established (sent to new clients)
+    Connected {
+        client_id: u64,
+        server_version: String,
+    },
+
+    /// Ping for keepalive
+    Ping {
+        timestamp: u64,
+    },
+
+    /// Pong response
+    Pong {
+        timestamp: u64,
+    },
+
+    /// Binary data for a tab, sent as a binary frame instead of JSON
+    ///
+    /// `payload` holds the encoded
+    /// [`BinaryFrame`](crate::api::websocket::BinaryFrame).
+    #[serde(skip)]
+    #[schemars(skip)]
+    BinaryData {
+        tab_id: Uuid,
+        payload: Bytes,
+    },
+}
+
+impl BrowserEvent {
+    /// Tab the event belongs to, if any
+    pub fn tab_id(&self) -> Option<&str> {
+        match self {
+            BrowserEvent::TabCreated { tab_id, .. }
+            | BrowserEvent::TabClosed { tab_id }
+            | BrowserEvent::NavigationComplete { tab_id, .. }
+            | BrowserEvent::DomReady { tab_id }
+            | BrowserEvent::LoadComplete { tab_id, .. }
+            | BrowserEvent::TitleChanged { tab_id, .. }
+            | BrowserEvent::UrlChanged { tab_id, .. }
+            | BrowserEvent::FaviconChanged { tab_id, .. }
+            | BrowserEvent::LoadingStateChanged { tab_id, .. }
+            | BrowserEvent::ActiveTabChanged { tab_id }
+            | BrowserEvent::ViewportChanged { tab_id, .. }
+            | BrowserEvent::ConsoleMessage { tab_id, .. }
+            | BrowserEvent::RequestFailed { tab_id, .. }
+            | BrowserEvent::Binding { tab_id, .. }
+            | BrowserEvent::ConsentDismissed { tab_id, .. }
+            | BrowserEvent::DialogOpened { tab_id, .. } => Some(tab_id),
+            BrowserEvent::Error { tab_id, .. } => tab_id.as_deref(),
+            BrowserEvent::DownloadStarted { .. }
+            | BrowserEvent::DownloadProgress { .. }
+            | BrowserEvent::DownloadComplete { .. }
+            | BrowserEvent::ScreenshotChunk { .. }
+            | BrowserEvent::Connected { .. }
+            | BrowserEvent::Ping { .. }
+            | BrowserEvent::Pong { .. }
+            | BrowserEvent::BinaryData { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,528 @@
+//! HTTP Archive (HAR 1.2) recording of a tab's network traffic.
+//!
+//! Backs [`BrowserEngine::enable_har_recording`](crate::browser::BrowserEngine::enable_har_recording).
+//! A [`HarRecorder`] is handed every request a tab sends through its
+//! [`RequestInterceptor`] implementation, which never changes or blocks
+//! anything, and is told about the matching response once it completes.
+//! [`HarRecorder::stop`] turns both into a [`HarLog`] that HAR viewers and
+//! replay tools can load.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ki_browser_standalone::browser::har::{HarRecorder, HarResponse};
+//! use ki_browser_standalone::browser::{InterceptedRequest, RequestInterceptor};
+//!
+//! let recorder = HarRecorder::new();
+//! recorder.start();
+//!
+//! recorder.on_request(&mut InterceptedRequest::get("https://example.com/"));
+//! recorder.record_response("https://example.com/", HarResponse::new(200, "OK"));
+//!
+//! let log = recorder.stop();
+//! assert_eq!(log.entries[0].response.status, 200);
+//! assert!(log.to_json().contains("\"startedDateTime\""));
+//! ```
+
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::browser::interceptor::{InterceptedRequest, RequestAction, RequestInterceptor};
+
+/// HAR format version written by [`HarLog`].
+pub const HAR_VERSION: &str = "1.2";
+
+/// HTTP version reported for requests and responses; CEF does not expose
+/// the negotiated protocol.
+const HTTP_VERSION: &str = "HTTP/1.1";
+
+/// A name/value pair, used for headers, cookies and query parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarNameValue {
+    pub name: String,
+    pub value: String,
+}
+
+impl HarNameValue {
+    /// Creates a pair.
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// The application that wrote the log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+impl Default for HarCreator {
+    fn default() -> Self {
+        Self {
+            name: "ki-browser".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// Request body of a recorded request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    pub mime_type: String,
+    pub text: String,
+}
+
+/// A recorded request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub cookies: Vec<HarNameValue>,
+    pub headers: Vec<HarNameValue>,
+    pub query_string: Vec<HarNameValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+    /// `-1`: the raw header block is not available.
+    pub headers_size: i64,
+    /// Body size in bytes, `0` without a body.
+    pub body_size: i64,
+}
+
+impl HarRequest {
+    /// Converts an intercepted request; headers are sorted by name so logs
+    /// of the same traffic compare equal.
+    pub fn from_intercepted(request: &InterceptedRequest) -> Self {
+        let mut headers: Vec<_> = request
+            .headers
+            .iter()
+            .map(|(name, value)| HarNameValue::new(name, value))
+            .collect();
+        headers.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let post_data = request.body.as_ref().map(|body| HarPostData {
+            mime_type: request
+                .headers
+                .get("Content-Type")
+                .cloned()
+                .unwrap_or_default(),
+            text: String::from_utf8_lossy(body).into_owned(),
+        });
+
+        Self {
+            method: request.method.clone(),
+            url: request.url.clone(),
+            http_version: HTTP_VERSION.to_string(),
+            cookies: Vec::new(),
+            headers,
+            query_string: query_string(&request.url),
+            post_data,
+            headers_size: -1,
+            body_size: request.body.as_ref().map_or(0, |body| body.len() as i64),
+        }
+    }
+}
+
+/// Splits the query of `url` into its parameters, without percent-decoding.
+fn query_string(url: &str) -> Vec<HarNameValue> {
+    let without_fragment = url.split('#').next().unwrap_or_default();
+    let Some((_, query)) = without_fragment.split_once('?') else {
+        return Vec::new();
+    };
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((name, value)) => HarNameValue::new(name, value),
+            None => HarNameValue::new(pair, ""),
+        })
+        .collect()
+}
+
+/// Reason phrase of common HTTP status codes, empty for others.
+pub(crate) fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        206 => "Partial Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        303 => "See Other",
+        304 => "Not Modified",
+        307 => "Temporary Redirect",
+        308 => "Permanent Redirect",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "",
+    }
+}
+
+/// Body details of a recorded response. The body itself is not recorded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    pub mime_type: String,
+}
+
+/// A recorded response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    /// HTTP status code, `0` if no response arrived.
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: String,
+    pub cookies: Vec<HarNameValue>,
+    pub headers: Vec<HarNameValue>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    /// `-1`: the raw header block is not available.
+    pub headers_size: i64,
+    /// Received body bytes, `-1` if unknown.
+    pub body_size: i64,
+}
+
+impl HarResponse {
+    /// Creates a response without headers or body details.
+    pub fn new(status: u16, status_text: impl Into<String>) -> Self {
+        Self {
+            status,
+            status_text: status_text.into(),
+            http_version: HTTP_VERSION.to_string(),
+            cookies: Vec::new(),
+            headers: Vec::new(),
+            content: HarContent {
+                size: -1,
+                mime_type: String::new(),
+            },
+            redirect_url: String::new(),
+            headers_size: -1,
+            body_size: -1,
+        }
+    }
+
+    /// Sets the MIME type of the body.
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.content.mime_type = mime_type.into();
+        self
+    }
+
+    /// Sets the number of body bytes received.
+    pub fn with_body_size(mut self, size: i64) -> Self {
+        self.content.size = size;
+        self.body_size = size;
+        self
+    }
+
+    /// Adds a response header.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push(HarNameValue::new(name, value));
+        self
+    }
+
+    /// Placeholder for a request that never got a response.
+    fn aborted() -> Self {
+        Self::new(0, "")
+    }
+}
+
+/// Phases of a request in milliseconds; `-1` marks a phase that was not
+/// measured.
+///
+/// The recorder only sees when a request started and when it completed,
+/// so the whole duration is attributed to `wait`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HarTimings {
+    pub blocked: f64,
+    pub dns: f64,
+    pub connect: f64,
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+    pub ssl: f64,
+}
+
+impl HarTimings {
+    /// Timings of a request that took `total_ms` overall.
+    pub fn waited(total_ms: f64) -> Self {
+        Self {
+            blocked: -1.0,
+            dns: -1.0,
+            connect: -1.0,
+            send: 0.0,
+            wait: total_ms,
+            receive: 0.0,
+            ssl: -1.0,
+        }
+    }
+}
+
+/// Cache state of a request; nothing is recorded, but the spec requires
+/// the object.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarCache {}
+
+/// One request/response pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: DateTime<Utc>,
+    /// Total duration in milliseconds.
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    pub cache: HarCache,
+    pub timings: HarTimings,
+}
+
+/// A finished recording: the `log` object of a HAR file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarLog {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+impl HarLog {
+    /// Creates a log of `entries`.
+    pub fn new(entries: Vec<HarEntry>) -> Self {
+        Self {
+            version: HAR_VERSION.to_string(),
+            creator: HarCreator::default(),
+            entries,
+        }
+    }
+
+    /// The complete HAR document, `{"log": {...}}`, as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_value()).unwrap_or_default()
+    }
+
+    /// The complete HAR document as a JSON value.
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::json!({ "log": self })
+    }
+}
+
+/// A request waiting for its response.
+#[derive(Debug)]
+struct PendingEntry {
+    started_date_time: DateTime<Utc>,
+    started: Instant,
+    request: HarRequest,
+}
+
+impl PendingEntry {
+    fn complete(self, response: HarResponse) -> HarEntry {
+        let time = self.started.elapsed().as_secs_f64() * 1000.0;
+        HarEntry {
+            started_date_time: self.started_date_time,
+            time,
+            request: self.request,
+            response,
+            cache: HarCache::default(),
+            timings: HarTimings::waited(time),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct RecorderState {
+    recording: bool,
+    pending: Vec<PendingEntry>,
+    entries: Vec<HarEntry>,
+}
+
+/// Records the requests of a tab and their responses as HAR entries.
+///
+/// As a [`RequestInterceptor`] it always answers [`RequestAction::Allow`];
+/// engines consult it after the installed interceptor, so the log shows
+/// requests as they were sent.
+#[derive(Debug, Default)]
+pub struct HarRecorder {
+    state: Mutex<RecorderState>,
+}
+
+impl HarRecorder {
+    /// Creates a recorder that is not recording yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts recording, discarding anything recorded before.
+    pub fn start(&self) {
+        let mut state = self.state.lock();
+        state.pending.clear();
+        state.entries.clear();
+        state.recording = true;
+    }
+
+    /// Whether requests are currently recorded.
+    pub fn is_recording(&self) -> bool {
+        self.state.lock().recording
+    }
+
+    /// Stops recording and returns the log.
+    ///
+    /// Requests still waiting for a response are included with status `0`,
+    /// as the HAR spec has it for aborted requests. Entries are ordered by
+    /// start time.
+    pub fn stop(&self) -> HarLog {
+        let mut state = self.state.lock();
+        state.recording = false;
+        let mut entries = std::mem::take(&mut state.entries);
+        entries.extend(
+            state
+                .pending
+                .drain(..)
+                .map(|pending| pending.complete(HarResponse::aborted())),
+        );
+        entries.sort_by_key(|entry| entry.started_date_time);
+        HarLog::new(entries)
+    }
+
+    /// Completes the oldest pending request to `url` with `response`.
+    ///
+    /// A response without a recorded request, e.g. the target of a
+    /// redirect, gets an entry starting now; without a recorded request
+    /// and without a status (a blocked request) nothing is recorded.
+    pub fn record_response(&self, url: &str, response: HarResponse) {
+        let mut state = self.state.lock();
+        if !state.recording {
+            return;
+        }
+        let pending = match state.pending.iter().position(|p| p.request.url == url) {
+            Some(index) => state.pending.remove(index),
+            None if response.status == 0 => return,
+            None => PendingEntry {
+                started_date_time: Utc::now(),
+                started: Instant::now(),
+                request: HarRequest::from_intercepted(&InterceptedRequest::get(url)),
+            },
+        };
+        let entry = pending.complete(response);
+        state.entries.push(entry);
+    }
+}
+
+impl RequestInterceptor for HarRecorder {
+    fn on_request(&self, request: &mut InterceptedRequest) -> RequestAction {
+        let mut state = self.state.lock();
+        if state.recording {
+            state.pending.push(PendingEntry {
+                started_date_time: Utc::now(),
+                started: Instant::now(),
+                request: HarRequest::from_intercepted(request),
+            });
+        }
+        RequestAction::Allow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_pairs_requests_and_responses() {
+        let recorder = HarRecorder::new();
+
+        // Nothing is recorded before start
+        recorder.on_request(&mut InterceptedRequest::get("https://example.com/early"));
+        recorder.start();
+        assert!(recorder.is_recording());
+
+        let mut form = InterceptedRequest::new("https://example.com/login?next=/home&x", "POST");
+        form.headers.insert("Content-Type".into(), "application/x-www-form-urlencoded".into());
+        form.body = Some(b"user=a".to_vec());
+        assert_eq!(recorder.on_request(&mut form), RequestAction::Allow);
+        recorder.on_request(&mut InterceptedRequest::get("https://example.com/slow.js"));
+        recorder.record_response(
+            "https://example.com/login?next=/home&x",
+            HarResponse::new(302, "Found").with_mime_type("text/html").with_body_size(0),
+        );
+
+        let log = recorder.stop();
+        assert!(!recorder.is_recording());
+        assert_eq!(log.version, HAR_VERSION);
+        assert_eq!(log.entries.len(), 2);
+
+        let login = &log.entries[0];
+        assert_eq!(login.request.method, "POST");
+        assert_eq!(login.response.status, 302);
+        assert_eq!(login.response.content.mime_type, "text/html");
+        assert_eq!(
+            login.request.query_string,
+            [HarNameValue::new("next", "/home"), HarNameValue::new("x", "")]
+        );
+        let post = login.request.post_data.as_ref().unwrap();
+        assert_eq!(post.mime_type, "application/x-www-form-urlencoded");
+        assert_eq!(post.text, "user=a");
+        assert_eq!(login.request.body_size, 6);
+        assert_eq!(login.timings.wait, login.time);
+
+        // No response arrived before stop
+        assert_eq!(log.entries[1].request.url, "https://example.com/slow.js");
+        assert_eq!(log.entries[1].response.status, 0);
+    }
+
+    #[test]
+    fn test_unmatched_response_and_restart() {
+        let recorder = HarRecorder::new();
+        recorder.start();
+        recorder.record_response("https://example.com/redirected", HarResponse::new(200, "OK"));
+        recorder.record_response("https://example.com/blocked", HarResponse::new(0, ""));
+        let log = recorder.stop();
+        assert_eq!(log.entries.len(), 1);
+        assert_eq!(log.entries[0].request.url, "https://example.com/redirected");
+
+        // Stopped recorders ignore traffic; a restart begins a fresh log
+        recorder.record_response("https://example.com/", HarResponse::new(200, "OK"));
+        recorder.start();
+        assert!(recorder.stop().entries.is_empty());
+    }
+
+    #[test]
+    fn test_har_json_field_names() {
+        let recorder = HarRecorder::new();
+        recorder.start();
+        recorder.on_request(&mut InterceptedRequest::get("https://example.com/"));
+        recorder.record_response("https://example.com/", HarResponse::new(200, "OK"));
+
+        let json: serde_json::Value = serde_json::from_str(&recorder.stop().to_json()).unwrap();
+        let log = &json["log"];
+        assert_eq!(log["version"], "1.2");
+        assert_eq!(log["creator"]["name"], "ki-browser");
+
+        let entry = &log["entries"][0];
+        for field in ["startedDateTime", "time", "request", "response", "cache", "timings"] {
+            assert!(entry.get(field).is_some(), "missing {}", field);
+        }
+        assert_eq!(entry["request"]["url"], "https://example.com/");
+        assert_eq!(entry["request"]["httpVersion"], "HTTP/1.1");
+        assert_eq!(entry["request"]["queryString"], serde_json::json!([]));
+        assert!(entry["request"].get("postData").is_none());
+        assert_eq!(entry["response"]["status"], 200);
+        assert_eq!(entry["response"]["redirectURL"], "");
+        assert_eq!(entry["timings"]["dns"], -1.0);
+    }
+}
@@ -0,0 +1,241 @@
+//! Outbound request interception.
+//!
+//! A [`RequestInterceptor`] sees every request a tab sends before it leaves
+//! the browser and decides whether it goes out unchanged, modified,
+//! redirected or not at all. [`BlockListInterceptor`] is the stock
+//! implementation behind the REST block list.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ki_browser_standalone::browser::{
+//!     BlockListInterceptor, InterceptedRequest, RequestAction, RequestInterceptor,
+//! };
+//!
+//! let blocker = BlockListInterceptor::new(["*.doubleclick.net"]).unwrap();
+//!
+//! let mut request = InterceptedRequest::get("https://ad.doubleclick.net/pixel");
+//! assert_eq!(blocker.on_request(&mut request), RequestAction::Block);
+//!
+//! let mut request = InterceptedRequest::get("https://example.com/");
+//! assert_eq!(blocker.on_request(&mut request), RequestAction::Allow);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::browser::url_pattern::{UrlPattern, UrlPatternError};
+
+/// Error of a request cancelled by an interceptor, as Chromium names it.
+pub const BLOCKED_BY_CLIENT: &str = "net::ERR_BLOCKED_BY_CLIENT";
+
+/// An outbound request as seen by a [`RequestInterceptor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterceptedRequest {
+    /// Requested URL.
+    pub url: String,
+
+    /// HTTP method (`GET`, `POST`, ...).
+    pub method: String,
+
+    /// Request headers by name.
+    pub headers: HashMap<String, String>,
+
+    /// Upload data, if the request has an in-memory body.
+    pub body: Option<Vec<u8>>,
+}
+
+impl InterceptedRequest {
+    /// Creates a request without headers or body.
+    pub fn new(url: impl Into<String>, method: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            method: method.into(),
+            headers: HashMap::new(),
+            body: None,
+        }
+    }
+
+    /// Creates a `GET` request without headers.
+    pub fn get(url: impl Into<String>) -> Self {
+        Self::new(url, "GET")
+    }
+}
+
+/// What happens to an intercepted request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestAction {
+    /// Send the request as it was before interception.
+    Allow,
+
+    /// Cancel the request; it fails with [`BLOCKED_BY_CLIENT`].
+    Block,
+
+    /// Send the request with the changes the interceptor made to it.
+    ModifyAndAllow,
+
+    /// Send the request to this URL instead.
+    Redirect(String),
+}
+
+/// Decides the fate of every outbound request.
+///
+/// Called on the browser's network path for each request, so
+/// implementations should not block.
+pub trait RequestInterceptor: Send + Sync {
+    /// Inspects `request` and returns what to do with it.
+    ///
+    /// Changes to `request` only take effect with
+    /// [`RequestAction::ModifyAndAllow`].
+    fn on_request(&self, request: &mut InterceptedRequest) -> RequestAction;
+}
+
+impl fmt::Debug for dyn RequestInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RequestInterceptor")
+    }
+}
+
+/// The interceptor currently installed on an engine, shared with the
+/// callbacks that consult it.
+pub(crate) type InterceptorSlot = Arc<RwLock<Option<Arc<dyn RequestInterceptor>>>>;
+
+/// Runs the interceptor installed in `slot`, if any.
+///
+/// Returns the request to send, or `None` if it was blocked.
+pub(crate) fn apply_interceptor(
+    slot: &InterceptorSlot,
+    request: InterceptedRequest,
+) -> Option<InterceptedRequest> {
+    let Some(interceptor) = slot.read().clone() else {
+        return Some(request);
+    };
+    let mut modified = request.clone();
+    match interceptor.on_request(&mut modified) {
+        RequestAction::Allow => Some(request),
+        RequestAction::Block => None,
+        RequestAction::ModifyAndAllow => Some(modified),
+        RequestAction::Redirect(url) => Some(InterceptedRequest { url, ..request }),
+    }
+}
+
+/// Blocks every request whose URL matches one of its patterns.
+///
+/// Patterns use [`UrlPattern`] syntax, so `*.doubleclick.net` blocks the
+/// domain and all of its subdomains.
+#[derive(Debug, Clone)]
+pub struct BlockListInterceptor {
+    patterns: Vec<UrlPattern>,
+}
+
+impl BlockListInterceptor {
+    /// Compiles a block list from pattern strings.
+    pub fn new<I, S>(patterns: I) -> Result<Self, UrlPatternError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let patterns = patterns
+            .into_iter()
+            .map(|p| UrlPattern::parse(p.as_ref()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// The patterns of the block list, as given (with their `regex:` prefix).
+    pub fn patterns(&self) -> Vec<String> {
+        self.patterns.iter().map(UrlPattern::to_string).collect()
+    }
+}
+
+impl RequestInterceptor for BlockListInterceptor {
+    fn on_request(&self, request: &mut InterceptedRequest) -> RequestAction {
+        if self.patterns.iter().any(|p| p.matches(&request.url)) {
+            RequestAction::Block
+        } else {
+            RequestAction::Allow
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Blocks doubleclick.net and tags every other request.
+    struct AdBlocker;
+
+    impl RequestInterceptor for AdBlocker {
+        fn on_request(&self, request: &mut InterceptedRequest) -> RequestAction {
+            if UrlPattern::parse("*.doubleclick.net").unwrap().matches(&request.url) {
+                return RequestAction::Block;
+            }
+            request.headers.insert("X-Intercepted".to_string(), "1".to_string());
+            RequestAction::ModifyAndAllow
+        }
+    }
+
+    fn slot(interceptor: impl RequestInterceptor + 'static) -> InterceptorSlot {
+        Arc::new(RwLock::new(Some(Arc::new(interceptor))))
+    }
+
+    #[test]
+    fn test_interceptor_blocks_doubleclick() {
+        let slot = slot(AdBlocker);
+
+        for url in ["https://doubleclick.net/", "https://ad.doubleclick.net/pixel?id=1"] {
+            assert_eq!(apply_interceptor(&slot, InterceptedRequest::get(url)), None);
+        }
+
+        let sent = apply_interceptor(&slot, InterceptedRequest::get("https://example.com/")).unwrap();
+        assert_eq!(sent.headers.get("X-Intercepted").map(String::as_str), Some("1"));
+    }
+
+    #[test]
+    fn test_allow_discards_changes_and_redirect_keeps_request() {
+        struct Rewriter(RequestAction);
+        impl RequestInterceptor for Rewriter {
+            fn on_request(&self, request: &mut InterceptedRequest) -> RequestAction {
+                request.method = "DELETE".to_string();
+                self.0.clone()
+            }
+        }
+
+        let mut request = InterceptedRequest::new("https://example.com/form", "POST");
+        request.body = Some(b"a=1".to_vec());
+
+        let sent = apply_interceptor(&slot(Rewriter(RequestAction::Allow)), request.clone());
+        assert_eq!(sent.as_ref(), Some(&request));
+
+        let target = "https://mirror.example.com/form".to_string();
+        let sent = apply_interceptor(&slot(Rewriter(RequestAction::Redirect(target.clone()))), request)
+            .unwrap();
+        assert_eq!(sent.url, target);
+        assert_eq!(sent.method, "POST");
+        assert_eq!(sent.body.as_deref(), Some(&b"a=1"[..]));
+    }
+
+    #[test]
+    fn test_block_list() {
+        let blocker = BlockListInterceptor::new(["*.doubleclick.net", "regex:/ads/"]).unwrap();
+        assert_eq!(blocker.patterns(), ["*.doubleclick.net", "regex:/ads/"]);
+
+        let verdict = |url: &str| blocker.on_request(&mut InterceptedRequest::get(url));
+        assert_eq!(verdict("https://stats.g.doubleclick.net/j/collect"), RequestAction::Block);
+        assert_eq!(verdict("https://example.com/ads/banner.png"), RequestAction::Block);
+        assert_eq!(verdict("https://example.com/"), RequestAction::Allow);
+        assert_eq!(verdict("https://notdoubleclick.net/"), RequestAction::Allow);
+
+        assert!(BlockListInterceptor::new(["regex:("]).is_err());
+    }
+
+    #[test]
+    fn test_empty_slot_allows_everything() {
+        let slot: InterceptorSlot = Arc::new(RwLock::new(None));
+        let request = InterceptedRequest::get("https://ad.doubleclick.net/");
+        assert_eq!(apply_interceptor(&slot, request.clone()), Some(request));
+    }
+}
@@ -0,0 +1,225 @@
+//! Function calls with JSON-serialized arguments.
+//!
+//! Backs [`BrowserEngine::call_function`](crate::browser::BrowserEngine::call_function).
+//! Arguments are embedded as a JSON array literal and spread into the
+//! function, so selectors and user-supplied strings never have to be
+//! spliced into script text by hand. Because the JS bridge only returns
+//! synchronous values, the call is split in two scripts:
+//!
+//! 1. [`build_call_script`] starts the function and parks its (possibly
+//!    asynchronous) outcome in a page-global slot keyed by a call id
+//! 2. [`build_poll_script`] is polled until the slot is settled and
+//!    returns the result, clearing the slot
+//!
+//! [`parse_call_state`] interprets the poll result.
+//!
+//! Every call runs under an [`EvalOptions`] budget, so a hostile or buggy
+//! page can neither stall the bridge forever nor flood it with an enormous
+//! result. A call that runs out of time is abandoned with
+//! [`build_abort_script`]; its late outcome is then discarded in the page.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::error::BrowserError;
+
+/// How long an engine waits for a called function's promise to settle.
+pub const DEFAULT_CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Largest serialized result a call may return by default (16 MiB).
+pub const DEFAULT_MAX_RESULT_BYTES: usize = 16 * 1024 * 1024;
+
+/// Time and size budget of a JavaScript evaluation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvalOptions {
+    /// Abort the evaluation after this many milliseconds.
+    pub timeout_ms: u64,
+    /// Reject results whose JSON serialization exceeds this many bytes.
+    pub max_result_bytes: usize,
+}
+
+impl Default for EvalOptions {
+    fn default() -> Self {
+        Self {
+            timeout_ms: DEFAULT_CALL_TIMEOUT.as_millis() as u64,
+            max_result_bytes: DEFAULT_MAX_RESULT_BYTES,
+        }
+    }
+}
+
+impl EvalOptions {
+    /// Sets the timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_ms = timeout.as_millis() as u64;
+        self
+    }
+
+    /// Sets the result size cap in bytes.
+    pub fn with_max_result_bytes(mut self, max_result_bytes: usize) -> Self {
+        self.max_result_bytes = max_result_bytes;
+        self
+    }
+
+    /// The timeout as a [`Duration`].
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+
+    /// The error for an evaluation that ran out of time.
+    pub fn timeout_error(&self) -> BrowserError {
+        BrowserError::Timeout {
+            operation: "JavaScript evaluation".to_string(),
+            duration_ms: self.timeout_ms,
+        }
+    }
+
+    /// Returns `value` unchanged, or [`BrowserError::ResultTooLarge`] if its
+    /// JSON serialization exceeds `max_result_bytes`.
+    pub fn enforce_result_size(&self, value: Value) -> Result<Value, BrowserError> {
+        let size = serde_json::to_vec(&value)?.len();
+        if size > self.max_result_bytes {
+            return Err(BrowserError::ResultTooLarge {
+                size,
+                max_bytes: self.max_result_bytes,
+            });
+        }
+        Ok(value)
+    }
+}
+
+/// Builds the script that invokes `fn_body` with `args` and stores the outcome.
+///
+/// `fn_body` is the body of `function(...args) { ... }` and reads its
+/// arguments from `args`; it may `return` a value or a promise. The settled
+/// outcome is stored under `call_id`.
+pub fn build_call_script(call_id: &str, fn_body: &str, args: &[Value]) -> Result<String> {
+    let args_json = serde_json::to_string(args).context("Failed to serialize function arguments")?;
+    let id_json = serde_json::to_string(call_id)?;
+
+    Ok(format!(
+        r#"(function() {{
+    if (!window.__kiCalls) Object.defineProperty(window, '__kiCalls', {{ value: {{}}, enumerable: false }});
+    const calls = window.__kiCalls;
+    const id = {id_json};
+    const args = {args_json};
+    calls[id] = {{ done: false }};
+    // An aborted call has no slot left; its late outcome is dropped
+    const settle = (state) => {{ if (id in calls) calls[id] = state; }};
+    try {{
+        Promise.resolve((function(...args) {{
+{fn_body}
+        }})(...args)).then(
+            (value) => settle({{ done: true, value: value === undefined ? null : value }}),
+            (err) => settle({{ done: true, error: String(err && err.message || err) }})
+        );
+    }} catch (err) {{
+        settle({{ done: true, error: String(err && err.message || err) }});
+    }}
+    return id;
+}})()"#
+    ))
+}
+
+/// Builds the script that reports the stored outcome of `call_id`.
+///
+/// Returns `{"done":false}` while the call is pending and removes the slot
+/// once a settled state has been read. A result longer than
+/// `max_result_bytes` is not sent over the bridge; only its size is.
+pub fn build_poll_script(call_id: &str, max_result_bytes: usize) -> String {
+    let id_json = serde_json::to_string(call_id).unwrap_or_else(|_| "\"\"".to_string());
+    format!(
+        r#"(function() {{
+    const calls = window.__kiCalls || {{}};
+    const state = calls[{id_json}];
+    if (!state) return JSON.stringify({{ done: true, error: 'Call state lost (page navigated?)' }});
+    if (state.done) delete calls[{id_json}];
+    let json;
+    try {{ json = JSON.stringify(state); }} catch (err) {{
+        return JSON.stringify({{ done: true, error: 'Result is not serializable: ' + String(err && err.message || err) }});
+    }}
+    if (json.length > {max_result_bytes}) return JSON.stringify({{ done: true, tooLarge: json.length }});
+    return json;
+}})()"#
+    )
+}
+
+/// Builds the script that abandons the pending call `call_id`.
+///
+/// Removes its slot so the outcome is dropped whenever it settles.
+pub fn build_abort_script(call_id: &str) -> String {
+    let id_json = serde_json::to_string(call_id).unwrap_or_else(|_| "\"\"".to_string());
+    format!("(function() {{ if (window.__kiCalls) delete window.__kiCalls[{id_json}]; }})()")
+}
+
+/// Interprets the JSON returned by [`build_poll_script`].
+///
+/// `Ok(None)` means still pending; `Ok(Some(value))` is the resolved value
+/// and a rejection or thrown error becomes `Err`. A result held back for its
+/// size fails with [`BrowserError::ResultTooLarge`].
+pub fn parse_call_state(json: &str, max_result_bytes: usize) -> Result<Option<Value>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct CallState {
+        done: bool,
+        #[serde(default)]
+        value: Value,
+        error: Option<String>,
+        too_large: Option<usize>,
+    }
+
+    let state: CallState = serde_json::from_str(json).context("Malformed function call state")?;
+    match (state.done, state.error, state.too_large) {
+        (false, _, _) => Ok(None),
+        (true, Some(error), _) => Err(anyhow!("Function call failed: {}", error)),
+        // JSON.stringify length counts UTF-16 units, a lower bound of the bytes
+        (true, None, Some(size)) => Err(BrowserError::ResultTooLarge {
+            size,
+            max_bytes: max_result_bytes,
+        }
+        .into()),
+        (true, None, None) => Ok(Some(state.value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_call_script_embeds_args_as_json() {
+        let tricky = r#"He said "hi" it's a \ back'slash </script>"#;
+        let script =
+            build_call_script("c1", "return args[0] + args[1].n;", &[json!(tricky), json!({"n": 1})]).unwrap();
+
+        let literal = script
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("const args = "))
+            .and_then(|l| l.strip_suffix(';'))
+            .unwrap();
+        let parsed: Vec<Value> = serde_json::from_str(literal).unwrap();
+        assert_eq!(parsed, vec![json!(tricky), json!({"n": 1})]);
+        assert!(script.contains("return args[0] + args[1].n;"));
+        assert!(script.contains(r#"const id = "c1";"#));
+    }
+
+    #[test]
+    fn test_parse_call_state() {
+        let max = DEFAULT_MAX_RESULT_BYTES;
+        assert_eq!(parse_call_state(r#"{"done":false}"#, max).unwrap(), None);
+        assert_eq!(
+            parse_call_state(r#"{"done":true,"value":{"x":[1,2]}}"#, max).unwrap(),
+            Some(json!({"x": [1, 2]}))
+        );
+        assert_eq!(parse_call_state(r#"{"done":true,"value":null}"#, max).unwrap(), Some(Value::Null));
+        let err = parse_call_state(r#"{"done":true,"error":"boom"}"#, max).unwrap_err();
+        assert!(err.to_string().contains("boom"));
+        let err = parse_call_state(r#"{"done":true,"tooLarge":4096}"#, 1024).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::ResultTooLarge { size: 4096, max_bytes: 1024 })
+        ));
+    }
+}
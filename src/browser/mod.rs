@@ -10,23 +10,71 @@
 //! - [`dom`] - DOM element access and manipulation
 //! - [`screenshot`] - Screenshot capture functionality
 //! - [`structured_data`] - Structured data extraction (JSON-LD, OpenGraph, microdata)
+//! - [`binding`] - Page-to-automation callbacks via exposed `window` functions
+//! - [`computed_style`] - Computed CSS properties of page elements
+//! - [`content`] - Page HTML source retrieval with an optional size cap
+//! - [`cookies`] - Browser cookies with all attributes
+//! - [`device`] - Device emulation (viewport, device scale factor, orientation)
 //! - [`content_extractor`] - Intelligent content extraction and page structure analysis
+//! - [`error_capture`] - Screenshot-on-error diagnostics
+//! - [`events`] - Events emitted by browser engines
+//! - [`event_buffer`] - Ring buffer of recent engine events for inspection
+//! - [`js_call`] - Function calls with JSON-serialized arguments
+//! - [`mutation`] - MutationObserver-based waits for DOM changes
+//! - [`wait_function`] - Waits until a JavaScript predicate becomes truthy
+//! - [`page_wait`] - Waits for load states, selectors, URLs and network idleness
 //! - [`forms`] - Form detection, analysis, and auto-fill
+//! - [`har`] - HTTP Archive (HAR 1.2) recording of a tab's traffic
+//! - [`interceptor`] - Outbound request interception and URL block lists
+//! - [`network`] - Per-tab failed request tracking
+//! - [`redirect`] - Redirect chains with loop detection and a redirect limit
+//! - [`response_capture`] - Per-tab capture of XHR/fetch response bodies
+//! - [`url_pattern`] - Shared URL matching (exact, glob, regex) for network features
+//! - [`xpath`] - XPath queries against the page DOM
+//! - [`shadow`] - Queries into open shadow roots of web components
+//! - [`throttle`] - Simulated slow network connections
 //! - [`cef_input`] - CEF-specific native input simulation (requires `cef-browser` feature)
 //! - [`cef_render`] - CEF offscreen rendering (requires `cef-browser` feature)
 //! - [`cef_engine`] - CEF browser engine implementation (requires `cef-browser` feature)
 
 pub mod annotate;
+pub mod binding;
+pub mod computed_style;
+pub mod consent;
+pub mod content;
+pub mod cookies;
+pub mod device;
 pub mod content_extractor;
+pub mod dialog;
 pub mod dom;
 pub mod dom_snapshot;
 pub mod engine;
+pub mod error_capture;
+pub mod event_buffer;
+pub mod events;
 pub mod forms;
+pub mod har;
+pub mod interceptor;
+pub mod js_call;
+pub mod mutation;
+pub mod network;
+pub mod page_wait;
+pub mod permissions;
+pub mod redirect;
+pub mod referrer;
+pub mod response_capture;
 pub mod screenshot;
+pub mod scroll;
+pub mod shadow;
 pub mod structured_data;
 pub mod tab;
 pub mod tab_lock;
+pub mod throttle;
+pub mod url_pattern;
 pub mod vision;
+pub mod wait_function;
+pub mod warmup;
+pub mod xpath;
 
 
 #[cfg(feature = "cef-browser")]
@@ -48,21 +96,48 @@ pub use content_extractor::{
     ContentExtractor, ExtractedContent, NavElement, PageSection, PageStructure, PageType,
     SectionRole,
 };
+pub use consent::{ConsentClick, DEFAULT_CONSENT_SELECTORS};
+pub use cookies::{Cookie, SameSitePolicy};
+pub use device::{DeviceMetrics, Orientation};
+pub use dialog::BeforeUnloadPolicy;
 pub use dom::{BoundingBox, DomAccessor, DomElement, FrameInfo, MockDomAccessor};
 pub use dom_snapshot::{DomNode, DomSnapshot, SnapshotConfig, ViewportInfo};
+pub use error_capture::ErrorCapture;
+pub use event_buffer::{EventBuffer, DEFAULT_EVENT_BUFFER_CAPACITY};
+pub use events::BrowserEvent;
 pub use forms::{
     FieldOption, FieldType, FormButton, FormField, FormFillError, FormFillRequest, FormFillResult,
     FormHandler, FormInfo, FormValidationResult, ValidationError,
 };
-pub use engine::{BrowserConfig, BrowserEngine, MockBrowserEngine};
-pub use screenshot::{ClipRegion, ScreenshotFormat, ScreenshotOptions};
+pub use engine::{
+    validate_cpu_throttling_rate, BrowserConfig, BrowserEngine, CacheMode, EngineCapabilities,
+    MockBrowserEngine, NavigateOptions, PollBackoff, Viewport,
+};
+pub use har::{HarEntry, HarLog, HarRecorder};
+pub use interceptor::{
+    BlockListInterceptor, InterceptedRequest, RequestAction, RequestInterceptor, BLOCKED_BY_CLIENT,
+};
+pub use js_call::EvalOptions;
+pub use mutation::{MutationInfo, MutationKinds};
+pub use network::FailedRequest;
+pub use page_wait::{NetworkActivity, WaitCondition};
+pub use permissions::Permission;
+pub use redirect::{RedirectChain, DEFAULT_MAX_REDIRECTS};
+pub use referrer::ReferrerPolicy;
+pub use response_capture::{CapturedResponse, ResponseCapture};
+pub use screenshot::{ClipRegion, ScreenshotEncoding, ScreenshotFormat, ScreenshotOptions};
+pub use scroll::{ScrollAlign, ScrollLogicalPosition};
+pub use shadow::{ShadowQueryOptions, DEFAULT_SHADOW_DEPTH};
+pub use throttle::{NetworkPreset, NetworkThrottleConfig};
 pub use structured_data::{
     AlternateUrl, MetaData, MicrodataItem, OpenGraphData, StructuredDataExtractor,
     StructuredPageData, TwitterCardData,
 };
 pub use tab::{Tab, TabManager, TabStatus};
 pub use tab_lock::TabLockManager;
+pub use url_pattern::{UrlPattern, UrlPatternError, UrlPatternKind};
 pub use vision::{VisionLabel, VisionOverlay};
+pub use warmup::WarmupOptions;
 
 
 #[cfg(feature = "cef-browser")]
@@ -77,5 +152,6 @@ pub use cef_headless::HeadlessRunner;
 #[cfg(feature = "cef-browser")]
 pub use cef_input::{
     CefEventSender, CefInputConfig, CefInputHandler, CefKeyEvent, CefKeyEventType,
-    CefMouseButton, CefMouseEvent,
+    CefMouseButton, CefMouseEvent, CefTouchEvent, CefTouchEventType, CompositionUnderline,
+    RealismWarning, ScrollProfile,
 };
@@ -0,0 +1,210 @@
+//! Waiting for DOM mutations.
+//!
+//! Backs [`BrowserEngine::wait_for_mutation`](crate::browser::BrowserEngine::wait_for_mutation).
+//! Instead of repeatedly serializing the page and comparing it, a
+//! `MutationObserver` is attached to the matched element and the wait ends
+//! with the first batch of mutation records. If the element does not exist
+//! yet, a document-level observer waits for it to appear first.
+//!
+//! The observer runs as a [`call_function`](crate::browser::BrowserEngine::call_function)
+//! body ([`MUTATION_WAIT_FUNCTION`]) whose promise settles on the first
+//! mutation, or with `null` once the in-page timeout disconnects the observers.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::error::BrowserError;
+
+/// Which kinds of mutations end a wait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MutationKinds {
+    /// Children added or removed (`childList`).
+    pub child_list: bool,
+    /// Attribute changes (`attributes`).
+    pub attributes: bool,
+    /// Text node changes (`characterData`).
+    pub character_data: bool,
+    /// Also observe descendants of the matched element.
+    pub subtree: bool,
+}
+
+impl Default for MutationKinds {
+    fn default() -> Self {
+        Self {
+            child_list: true,
+            attributes: true,
+            character_data: true,
+            subtree: true,
+        }
+    }
+}
+
+impl MutationKinds {
+    /// Whether a record of type `kind` (`"childList"`, `"attributes"` or
+    /// `"characterData"`) is observed.
+    pub fn includes(&self, kind: &str) -> bool {
+        match kind {
+            "childList" => self.child_list,
+            "attributes" => self.attributes,
+            "characterData" => self.character_data,
+            _ => false,
+        }
+    }
+
+    /// `MutationObserver` requires at least one mutation kind.
+    pub fn validate(&self) -> Result<(), BrowserError> {
+        if self.child_list || self.attributes || self.character_data {
+            Ok(())
+        } else {
+            Err(BrowserError::InvalidRequest(
+                "At least one mutation kind must be observed".to_string(),
+            ))
+        }
+    }
+}
+
+/// A single observed mutation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MutationInfo {
+    /// Mutation type: `childList`, `attributes` or `characterData`.
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// Lower-case tag name of the mutated node (`#text` for text nodes).
+    pub target: String,
+
+    /// Changed attribute for `attributes` mutations.
+    #[serde(default)]
+    pub attribute_name: Option<String>,
+
+    /// Number of added nodes for `childList` mutations.
+    #[serde(default)]
+    pub added_nodes: usize,
+
+    /// Number of removed nodes for `childList` mutations.
+    #[serde(default)]
+    pub removed_nodes: usize,
+}
+
+/// Function body run via `call_function`.
+///
+/// Arguments: `[selector, observerOptions, timeoutMs]`. Resolves with an
+/// array of [`MutationInfo`] objects, or `null` on timeout.
+pub const MUTATION_WAIT_FUNCTION: &str = r#"const [selector, options, timeoutMs] = args;
+return new Promise((resolve) => {
+    let elementObserver = null;
+    let mutationObserver = null;
+    const finish = (value) => {
+        clearTimeout(timer);
+        if (elementObserver) elementObserver.disconnect();
+        if (mutationObserver) mutationObserver.disconnect();
+        resolve(value);
+    };
+    const timer = setTimeout(() => finish(null), timeoutMs);
+    const observe = (el) => {
+        mutationObserver = new MutationObserver((records) => finish(records.map((r) => ({
+            type: r.type,
+            target: r.target.nodeType === Node.ELEMENT_NODE ? r.target.tagName.toLowerCase() : r.target.nodeName,
+            attribute_name: r.attributeName,
+            added_nodes: r.addedNodes.length,
+            removed_nodes: r.removedNodes.length
+        }))));
+        mutationObserver.observe(el, options);
+    };
+    const el = document.querySelector(selector);
+    if (el) {
+        observe(el);
+        return;
+    }
+    elementObserver = new MutationObserver(() => {
+        const found = document.querySelector(selector);
+        if (found) {
+            elementObserver.disconnect();
+            elementObserver = null;
+            observe(found);
+        }
+    });
+    elementObserver.observe(document.documentElement, { childList: true, subtree: true });
+});"#;
+
+/// Builds the arguments for [`MUTATION_WAIT_FUNCTION`].
+pub fn mutation_wait_args(selector: &str, kinds: MutationKinds, timeout: Duration) -> Vec<Value> {
+    vec![
+        json!(selector),
+        json!({
+            "childList": kinds.child_list,
+            "attributes": kinds.attributes,
+            "characterData": kinds.character_data,
+            "subtree": kinds.subtree,
+        }),
+        json!(timeout.as_millis() as u64),
+    ]
+}
+
+/// Interprets the value [`MUTATION_WAIT_FUNCTION`] resolved with.
+pub fn parse_mutation_result(
+    value: Value,
+    selector: &str,
+    timeout: Duration,
+) -> Result<Vec<MutationInfo>, BrowserError> {
+    if value.is_null() {
+        return Err(mutation_timeout(selector, timeout));
+    }
+    serde_json::from_value(value).map_err(|e| BrowserError::ScriptEvaluationFailed {
+        reason: format!("Malformed mutation records: {}", e),
+    })
+}
+
+/// The error returned when no mutation happened within `timeout`.
+pub fn mutation_timeout(selector: &str, timeout: Duration) -> BrowserError {
+    BrowserError::Timeout {
+        operation: format!("waiting for mutation of '{}'", selector),
+        duration_ms: timeout.as_millis() as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mutation_result() {
+        let records = parse_mutation_result(
+            json!([{"type": "attributes", "target": "div", "attribute_name": "class",
+                    "added_nodes": 0, "removed_nodes": 0}]),
+            "#app",
+            Duration::from_secs(1),
+        )
+        .unwrap();
+        assert_eq!(records[0].kind, "attributes");
+        assert_eq!(records[0].attribute_name.as_deref(), Some("class"));
+
+        assert!(matches!(
+            parse_mutation_result(Value::Null, "#app", Duration::from_millis(500)),
+            Err(BrowserError::Timeout { duration_ms: 500, .. })
+        ));
+    }
+
+    #[test]
+    fn test_mutation_kinds() {
+        let kinds = MutationKinds {
+            attributes: false,
+            ..Default::default()
+        };
+        assert!(kinds.includes("childList"));
+        assert!(!kinds.includes("attributes"));
+
+        let args = mutation_wait_args("#app", kinds, Duration::from_secs(2));
+        assert_eq!(args[1]["attributes"], json!(false));
+        assert_eq!(args[2], json!(2000));
+
+        let none = MutationKinds {
+            child_list: false,
+            attributes: false,
+            character_data: false,
+            subtree: true,
+        };
+        assert!(none.validate().is_err());
+    }
+}
@@ -0,0 +1,344 @@
+//! Per-tab network diagnostics.
+//!
+//! Tracks requests that failed while a page was loading: responses with an
+//! HTTP status of 400 or above and requests that never produced a response
+//! (DNS failure, connection reset, blocked by the client, ...). The list is
+//! reset on every top-level navigation so it always describes the current page.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::stealth::native_functions::mark_native_script;
+
+/// Maximum number of failed requests kept per tab; older entries are dropped first.
+pub const MAX_FAILED_REQUESTS_PER_TAB: usize = 500;
+
+/// Console prefix used by [`failed_request_observer_script`] to report failures.
+pub const FAILED_REQUEST_CONSOLE_PREFIX: &str = "KI_NETFAIL:";
+
+/// Error of a sub-resource aborted after the resource timeout, as Chromium names it.
+pub const RESOURCE_TIMED_OUT: &str = "net::ERR_TIMED_OUT";
+
+/// A network request that failed while loading the current page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailedRequest {
+    /// Requested URL.
+    pub url: String,
+
+    /// HTTP status code, `None` if no response was received.
+    pub status: Option<u16>,
+
+    /// Human-readable failure reason (e.g. `"Not Found"`, `"net::ERR_NAME_NOT_RESOLVED"`).
+    pub error: String,
+
+    /// Resource type as reported by the page (`document`, `script`, `img`, `fetch`, ...).
+    pub resource_type: Option<String>,
+
+    /// When the failure was observed.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl FailedRequest {
+    /// Creates an entry for a response with an error status code.
+    pub fn http_status(url: impl Into<String>, status: u16) -> Self {
+        let error = reqwest::StatusCode::from_u16(status)
+            .ok()
+            .and_then(|s| s.canonical_reason())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("HTTP {}", status));
+
+        Self {
+            url: url.into(),
+            status: Some(status),
+            error,
+            resource_type: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Creates an entry for a request that failed without a response.
+    pub fn load_error(url: impl Into<String>, error: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            status: None,
+            error: error.into(),
+            resource_type: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Sets the resource type.
+    pub fn with_resource_type(mut self, resource_type: impl Into<String>) -> Self {
+        self.resource_type = Some(resource_type.into());
+        self
+    }
+
+    /// Parses a report emitted by [`failed_request_observer_script`].
+    ///
+    /// Returns `None` if the message does not carry the report prefix or the
+    /// payload is malformed.
+    pub fn from_console_report(message: &str) -> Option<Self> {
+        #[derive(Deserialize)]
+        struct Report {
+            url: String,
+            status: Option<u16>,
+            error: Option<String>,
+            #[serde(rename = "type")]
+            resource_type: Option<String>,
+        }
+
+        let payload = message.strip_prefix(FAILED_REQUEST_CONSOLE_PREFIX)?;
+        let report: Report = serde_json::from_str(payload).ok()?;
+
+        let mut failed = match (report.status, report.error) {
+            (Some(status), None) => Self::http_status(report.url, status),
+            (status, error) => Self {
+                status,
+                ..Self::load_error(report.url, error.unwrap_or_else(|| "Request failed".into()))
+            },
+        };
+        failed.resource_type = report.resource_type;
+        Some(failed)
+    }
+}
+
+/// Appends a failed request to a per-tab list, enforcing [`MAX_FAILED_REQUESTS_PER_TAB`].
+pub fn record_failed_request(list: &mut Vec<FailedRequest>, failed: FailedRequest) {
+    if list.len() >= MAX_FAILED_REQUESTS_PER_TAB {
+        list.remove(0);
+    }
+    list.push(failed);
+}
+
+/// Page script that reports failed sub-resource requests via `console.debug`.
+///
+/// Covers `fetch`/XHR responses with an error status or network error,
+/// element loads (`<img>`, `<script>`, `<link>`) that fire `error`, and
+/// resource timing entries with `responseStatus >= 400`. The main document
+/// itself is tracked by the engine's load handler.
+///
+/// Backs [`BrowserConfig::observe_failed_subresources`](crate::browser::BrowserConfig::observe_failed_subresources).
+/// The patched `fetch` and XHR methods are masked through
+/// [`mark_native_script`], and the script leaves no globals or expando
+/// properties behind.
+pub fn failed_request_observer_script() -> String {
+    format!(
+        r#"
+(function() {{
+    {mark_native}
+
+    // Page scripts replacing console.debug later must not see the reports
+    const debug = console.debug.bind(console);
+    const seen = new Set();
+    const report = (url, status, error, type) => {{
+        const key = url + '|' + status + '|' + type;
+        if (seen.has(key)) return;
+        seen.add(key);
+        debug('{prefix}' + JSON.stringify({{ url: String(url), status: status, error: error, type: type }}));
+    }};
+
+    const origFetch = window.fetch;
+    if (origFetch) {{
+        window.fetch = function fetch(input, init) {{
+            const url = (input && input.url) || String(input);
+            return origFetch.apply(this, arguments).then(
+                (resp) => {{ if (resp.status >= 400) report(resp.url || url, resp.status, null, 'fetch'); return resp; }},
+                (err) => {{ report(url, null, String(err && err.message || err), 'fetch'); throw err; }}
+            );
+        }};
+        markNative(window.fetch, 'fetch');
+    }}
+
+    const xhrUrls = new WeakMap();
+    const origOpen = XMLHttpRequest.prototype.open;
+    XMLHttpRequest.prototype.open = function open(method, url) {{
+        xhrUrls.set(this, url);
+        return origOpen.apply(this, arguments);
+    }};
+    markNative(XMLHttpRequest.prototype.open, 'open');
+    const origSend = XMLHttpRequest.prototype.send;
+    XMLHttpRequest.prototype.send = function send() {{
+        this.addEventListener('loadend', () => {{
+            const url = this.responseURL || String(xhrUrls.get(this));
+            if (this.status >= 400) report(url, this.status, null, 'xhr');
+            else if (this.status === 0) report(url, null, 'Network error', 'xhr');
+        }});
+        return origSend.apply(this, arguments);
+    }};
+    markNative(XMLHttpRequest.prototype.send, 'send');
+
+    window.addEventListener('error', (e) => {{
+        const el = e.target;
+        if (!el || el === window || !el.tagName) return;
+        const url = el.currentSrc || el.src || el.href;
+        if (url) report(url, null, 'Failed to load resource', el.tagName.toLowerCase());
+    }}, true);
+
+    if (window.PerformanceObserver) {{
+        try {{
+            new PerformanceObserver((list) => {{
+                for (const entry of list.getEntries()) {{
+                    if (entry.responseStatus >= 400) {{
+                        report(entry.name, entry.responseStatus, null, entry.initiatorType);
+                    }}
+                }}
+            }}).observe({{ type: 'resource', buffered: true }});
+        }} catch (e) {{}}
+    }}
+}})();
+"#,
+        mark_native = mark_native_script(),
+        prefix = FAILED_REQUEST_CONSOLE_PREFIX,
+    )
+}
+
+/// Page script aborting sub-resource loads that take longer than `timeout_ms`.
+///
+/// Backs [`BrowserConfig::resource_timeout_ms`](crate::browser::BrowserConfig::resource_timeout_ms).
+/// `fetch` calls are aborted through an `AbortController` (a caller's own
+/// signal still works), XHRs without their own timeout get one, and `<img>`
+/// loads are cancelled by dropping their source. Each abort is reported like
+/// [`failed_request_observer_script`] does, with [`RESOURCE_TIMED_OUT`] as the
+/// error. Other element loads (scripts, stylesheets) cannot be aborted from
+/// the page and stay bounded by the navigation timeout only.
+pub fn resource_timeout_script(timeout_ms: u64) -> String {
+    format!(
+        r#"
+(function() {{
+    if (window.__kiResourceTimeout) return;
+    Object.defineProperty(window, '__kiResourceTimeout', {{ value: true, enumerable: false }});
+
+    const TIMEOUT = {timeout_ms};
+    const report = (url, type) => {{
+        console.debug('{prefix}' + JSON.stringify({{ url: String(url), status: null, error: '{error}', type: type }}));
+    }};
+
+    const origFetch = window.fetch;
+    if (origFetch) {{
+        window.fetch = function(input, init) {{
+            const url = (input && input.url) || String(input);
+            const controller = new AbortController();
+            const outer = (init && init.signal) || (input && input.signal);
+            if (outer) {{
+                if (outer.aborted) controller.abort(outer.reason);
+                else outer.addEventListener('abort', () => controller.abort(outer.reason), {{ once: true }});
+            }}
+            let timedOut = false;
+            const timer = setTimeout(() => {{ timedOut = true; controller.abort(); }}, TIMEOUT);
+            const args = Object.assign({{}}, init, {{ signal: controller.signal }});
+            return origFetch.call(this, input, args).then(
+                (resp) => {{ clearTimeout(timer); return resp; }},
+                (err) => {{ clearTimeout(timer); if (timedOut) report(url, 'fetch'); throw err; }}
+            );
+        }};
+    }}
+
+    const origSend = XMLHttpRequest.prototype.send;
+    XMLHttpRequest.prototype.send = function() {{
+        if (!this.timeout) {{
+            try {{ this.timeout = TIMEOUT; }} catch (e) {{}}
+            this.addEventListener('timeout', () => report(this.responseURL, 'xhr'));
+        }}
+        return origSend.apply(this, arguments);
+    }};
+
+    const watchImage = (img) => {{
+        // A new source restarts the clock
+        clearTimeout(img.__kiTimeout);
+        if (img.complete || !(img.currentSrc || img.src)) return;
+        img.__kiTimeout = setTimeout(() => {{
+            if (img.complete) return;
+            const url = img.currentSrc || img.src;
+            img.removeAttribute('srcset');
+            img.removeAttribute('src');
+            report(url, 'img');
+        }}, TIMEOUT);
+        const done = () => clearTimeout(img.__kiTimeout);
+        img.addEventListener('load', done, {{ once: true }});
+        img.addEventListener('error', done, {{ once: true }});
+    }};
+    const scan = (root) => {{
+        if (root.tagName === 'IMG') watchImage(root);
+        if (root.querySelectorAll) root.querySelectorAll('img').forEach(watchImage);
+    }};
+    new MutationObserver((mutations) => {{
+        for (const m of mutations) {{
+            if (m.type === 'attributes') watchImage(m.target);
+            else m.addedNodes.forEach(scan);
+        }}
+    }}).observe(document, {{ childList: true, subtree: true, attributes: true, attributeFilter: ['src', 'srcset'] }});
+    if (document.readyState !== 'loading') scan(document);
+    else document.addEventListener('DOMContentLoaded', () => scan(document), {{ once: true }});
+}})();
+"#,
+        timeout_ms = timeout_ms,
+        prefix = FAILED_REQUEST_CONSOLE_PREFIX,
+        error = RESOURCE_TIMED_OUT,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_request_from_console_report() {
+        let failed = FailedRequest::from_console_report(
+            r#"KI_NETFAIL:{"url":"https://example.com/a.js","status":404,"error":null,"type":"script"}"#,
+        )
+        .unwrap();
+        assert_eq!(failed.status, Some(404));
+        assert_eq!(failed.error, "Not Found");
+        assert_eq!(failed.resource_type.as_deref(), Some("script"));
+
+        let failed = FailedRequest::from_console_report(
+            r#"KI_NETFAIL:{"url":"https://nx.invalid/","status":null,"error":"Failed to fetch","type":"fetch"}"#,
+        )
+        .unwrap();
+        assert_eq!(failed.status, None);
+        assert_eq!(failed.error, "Failed to fetch");
+
+        assert!(FailedRequest::from_console_report("hello").is_none());
+        assert!(FailedRequest::from_console_report("KI_NETFAIL:{not json").is_none());
+    }
+
+    #[test]
+    fn test_resource_timeout_script_reports_like_observer() {
+        let script = resource_timeout_script(2500);
+        assert!(script.contains("const TIMEOUT = 2500;"));
+        assert!(script.contains("console.debug('KI_NETFAIL:'"));
+
+        // Aborts arrive through the same console report as other failures
+        let failed = FailedRequest::from_console_report(&format!(
+            r#"KI_NETFAIL:{{"url":"https://cdn.example/slow.png","status":null,"error":"{}","type":"img"}}"#,
+            RESOURCE_TIMED_OUT
+        ))
+        .unwrap();
+        assert_eq!(failed.error, RESOURCE_TIMED_OUT);
+        assert_eq!(failed.resource_type.as_deref(), Some("img"));
+    }
+
+    #[test]
+    fn test_failed_request_observer_script_masks_patches() {
+        let script = failed_request_observer_script();
+        assert!(script.contains("const markNative"));
+        assert!(script.contains("markNative(window.fetch, 'fetch');"));
+        assert!(script.contains("markNative(XMLHttpRequest.prototype.open, 'open');"));
+        assert!(script.contains("markNative(XMLHttpRequest.prototype.send, 'send');"));
+        assert!(script.contains("debug('KI_NETFAIL:'"));
+
+        // No global marker or expando property the page could look for
+        assert!(!script.contains("window.__ki"));
+        assert!(!script.contains("this.__ki"));
+    }
+
+    #[test]
+    fn test_record_failed_request_caps_list() {
+        let mut list = Vec::new();
+        for i in 0..MAX_FAILED_REQUESTS_PER_TAB + 5 {
+            record_failed_request(&mut list, FailedRequest::http_status(format!("/r{}", i), 500));
+        }
+        assert_eq!(list.len(), MAX_FAILED_REQUESTS_PER_TAB);
+        assert_eq!(list[0].url, "/r5");
+    }
+}
@@ -0,0 +1,288 @@
+//! Waiting for a page to reach a state.
+//!
+//! Backs [`BrowserEngine::wait_for`](crate::browser::BrowserEngine::wait_for).
+//! A [`WaitCondition`] names the state; engines check it on every poll with
+//! the increasing intervals of a [`PollBackoff`] until it holds or the
+//! timeout runs out.
+//!
+//! Network idleness is not visible from the page, so engines count the
+//! requests of each tab in a [`NetworkActivity`] fed from their network
+//! callbacks.
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::browser::engine::PollBackoff;
+use crate::browser::url_pattern::UrlPattern;
+use crate::error::BrowserError;
+
+/// A page state to wait for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WaitCondition {
+    /// The document has been parsed (`DOMContentLoaded` fired).
+    DomContentLoaded,
+
+    /// No request is in flight and none started for `idle_ms`.
+    NetworkIdle {
+        /// Quiet period in milliseconds
+        idle_ms: u64,
+    },
+
+    /// An element matches the CSS selector.
+    Selector(String),
+
+    /// The JavaScript expression evaluates to a truthy value.
+    JsExpression(String),
+
+    /// The tab's URL matches the pattern ([`UrlPattern`] syntax, so plain
+    /// URLs match exactly and `*` globs).
+    Url(String),
+
+    /// The page title contains the text.
+    TitleContains(String),
+}
+
+impl WaitCondition {
+    /// Rejects conditions that can never hold.
+    pub fn validate(&self) -> Result<(), BrowserError> {
+        match self {
+            Self::Selector(selector) if selector.trim().is_empty() => {
+                Err(BrowserError::InvalidRequest("Empty selector".to_string()))
+            }
+            Self::JsExpression(expression) if expression.trim().is_empty() => Err(
+                BrowserError::InvalidRequest("Empty JavaScript expression".to_string()),
+            ),
+            Self::Url(pattern) => UrlPattern::parse(pattern)
+                .map(|_| ())
+                .map_err(|e| BrowserError::InvalidRequest(e.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// Human-readable description, used in timeout errors.
+    pub fn description(&self) -> String {
+        match self {
+            Self::DomContentLoaded => "DOMContentLoaded".to_string(),
+            Self::NetworkIdle { idle_ms } => format!("network idle for {}ms", idle_ms),
+            Self::Selector(selector) => format!("selector '{}'", selector),
+            Self::JsExpression(expression) => format!("expression '{}'", expression),
+            Self::Url(pattern) => format!("URL '{}'", pattern),
+            Self::TitleContains(text) => format!("title containing '{}'", text),
+        }
+    }
+
+    /// In-page check for conditions evaluated by script.
+    ///
+    /// Returns an expression whose truthiness tells whether the condition
+    /// holds, or `None` for conditions checked outside the page.
+    pub fn script(&self) -> Option<String> {
+        match self {
+            Self::DomContentLoaded => Some("document.readyState !== 'loading'".to_string()),
+            Self::Selector(selector) => Some(format!(
+                "document.querySelector({}) !== null",
+                serde_json::to_string(selector).unwrap_or_default()
+            )),
+            Self::JsExpression(expression) => Some(expression.clone()),
+            Self::NetworkIdle { .. } | Self::Url(_) | Self::TitleContains(_) => None,
+        }
+    }
+}
+
+/// Builds the page expression locating `selector` for `wait_for_selector`.
+///
+/// Evaluates to the viewport-relative [`BoundingBox`](crate::browser::BoundingBox)
+/// of the first matching element as an object, or `null` while nothing
+/// matches.
+pub fn selector_box_script(selector: &str) -> String {
+    let selector_json = serde_json::to_string(selector).unwrap_or_else(|_| "''".to_string());
+    format!(
+        r#"(function() {{
+    const el = document.querySelector({selector_json});
+    if (el === null) return null;
+    const rect = el.getBoundingClientRect();
+    return {{ x: rect.x, y: rect.y, width: rect.width, height: rect.height }};
+}})()"#
+    )
+}
+
+/// The timeout error of a `wait_for` call.
+pub fn wait_timeout(condition: &WaitCondition, timeout: Duration) -> BrowserError {
+    BrowserError::Timeout {
+        operation: format!("waiting for {}", condition.description()),
+        duration_ms: timeout.as_millis() as u64,
+    }
+}
+
+/// Calls `check` with the intervals of `backoff` until it returns `true`.
+///
+/// Fails with [`BrowserError::Timeout`] once `timeout` elapsed; errors from
+/// `check` end the wait immediately.
+pub async fn poll_condition<F, Fut>(
+    condition: &WaitCondition,
+    timeout: Duration,
+    backoff: PollBackoff,
+    mut check: F,
+) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<bool>>,
+{
+    let start = tokio::time::Instant::now();
+    let mut interval = backoff.initial();
+    loop {
+        if check().await? {
+            return Ok(());
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return Err(wait_timeout(condition, timeout).into());
+        }
+        tokio::time::sleep(interval.min(timeout - elapsed)).await;
+        interval = backoff.next(interval);
+    }
+}
+
+/// Requests of a tab that have started but not completed.
+///
+/// Network callbacks report every request start and completion; a tab is
+/// idle once nothing is in flight and nothing started for the idle period.
+#[derive(Debug)]
+pub struct NetworkActivity {
+    in_flight: AtomicUsize,
+    last_activity: Mutex<Instant>,
+}
+
+impl NetworkActivity {
+    /// Creates a tracker with no requests, idle since now.
+    pub fn new() -> Self {
+        Self {
+            in_flight: AtomicUsize::new(0),
+            last_activity: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Records the start of a request.
+    pub fn request_started(&self) {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        *self.last_activity.lock() = Instant::now();
+    }
+
+    /// Records the completion of a request, successful or not.
+    pub fn request_finished(&self) {
+        let _ = self
+            .in_flight
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1));
+        *self.last_activity.lock() = Instant::now();
+    }
+
+    /// Number of requests in flight.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Whether nothing is in flight and nothing happened for `idle`.
+    pub fn is_idle_for(&self, idle: Duration) -> bool {
+        self.in_flight() == 0 && self.last_activity.lock().elapsed() >= idle
+    }
+}
+
+impl Default for NetworkActivity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_condition_scripts() {
+        let script = WaitCondition::Selector("a[href=\"/x\"]".to_string()).script().unwrap();
+        assert_eq!(script, r#"document.querySelector("a[href=\"/x\"]") !== null"#);
+        assert_eq!(
+            WaitCondition::DomContentLoaded.script().as_deref(),
+            Some("document.readyState !== 'loading'")
+        );
+        assert!(WaitCondition::TitleContains("Inbox".to_string()).script().is_none());
+
+        assert!(WaitCondition::Selector(" ".to_string()).validate().is_err());
+        assert!(WaitCondition::Url("regex:(".to_string()).validate().is_err());
+        assert!(WaitCondition::Url("https://example.com/*".to_string()).validate().is_ok());
+    }
+
+    #[test]
+    fn test_selector_box_script() {
+        let script = selector_box_script("button[name='go']");
+        assert!(script.contains(r#"document.querySelector("button[name='go']")"#));
+        assert!(script.contains("if (el === null) return null;"));
+        assert!(script.contains("getBoundingClientRect()"));
+    }
+
+    #[tokio::test]
+    async fn test_poll_condition_times_out() {
+        let condition = WaitCondition::Selector("#never".to_string());
+        let timeout = Duration::from_millis(100);
+        let start = tokio::time::Instant::now();
+        let err = poll_condition(&condition, timeout, PollBackoff::default(), || async {
+            Ok(false)
+        })
+        .await
+        .unwrap_err();
+        assert!(start.elapsed() >= timeout);
+
+        match err.downcast_ref::<BrowserError>() {
+            Some(BrowserError::Timeout { operation, duration_ms }) => {
+                assert_eq!(operation, "waiting for selector '#never'");
+                assert_eq!(*duration_ms, 100);
+            }
+            other => panic!("expected timeout, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poll_condition_waits_for_dom_content_loaded() {
+        let loaded = Arc::new(AtomicBool::new(false));
+        let flag = loaded.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        let start = tokio::time::Instant::now();
+        poll_condition(
+            &WaitCondition::DomContentLoaded,
+            Duration::from_secs(5),
+            PollBackoff::default(),
+            || async { Ok(loaded.load(Ordering::SeqCst)) },
+        )
+        .await
+        .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_network_activity_idle() {
+        let activity = NetworkActivity::new();
+        assert!(activity.is_idle_for(Duration::ZERO));
+
+        activity.request_started();
+        activity.request_started();
+        assert_eq!(activity.in_flight(), 2);
+        assert!(!activity.is_idle_for(Duration::ZERO));
+
+        activity.request_finished();
+        activity.request_finished();
+        // Completions without a start never underflow
+        activity.request_finished();
+        assert_eq!(activity.in_flight(), 0);
+        assert!(activity.is_idle_for(Duration::ZERO));
+        assert!(!activity.is_idle_for(Duration::from_secs(60)));
+    }
+}
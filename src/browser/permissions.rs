@@ -0,0 +1,117 @@
+//! Pre-granted browser permissions per origin.
+//!
+//! Spoofing `navigator.geolocation` alone does not help when the page first
+//! asks for permission: the prompt blocks until someone answers it.
+//! [`BrowserEngine::grant_permissions`](crate::browser::BrowserEngine::grant_permissions)
+//! grants a set of [`Permission`]s to one origin up front, so the page sees
+//! them as granted without a prompt.
+//!
+//! With CEF, grants go through DevTools (`Browser.grantPermissions`). They
+//! then apply to the whole browser context, not only the addressed tab. The
+//! stealth Permissions API override passes real `granted` states through, so
+//! `navigator.permissions.query` and `Notification.permission` agree with
+//! the grant.
+
+use std::collections::{BTreeSet, HashMap};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::BrowserError;
+
+/// A permission that can be granted to an origin.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    Geolocation,
+    Notifications,
+    /// Reading and writing the clipboard
+    ClipboardReadWrite,
+    /// Writing sanitized content to the clipboard
+    ClipboardSanitizedWrite,
+    Camera,
+    Microphone,
+    Midi,
+    /// MIDI including system exclusive messages
+    MidiSysex,
+    BackgroundSync,
+    /// Persistent storage (`navigator.storage.persist()`)
+    PersistentStorage,
+    /// Accelerometer, gyroscope and magnetometer
+    Sensors,
+    IdleDetection,
+}
+
+impl Permission {
+    /// Name of the permission in CDP's `Browser.PermissionType`.
+    pub fn cdp_name(self) -> &'static str {
+        match self {
+            Permission::Geolocation => "geolocation",
+            Permission::Notifications => "notifications",
+            Permission::ClipboardReadWrite => "clipboardReadWrite",
+            Permission::ClipboardSanitizedWrite => "clipboardSanitizedWrite",
+            Permission::Camera => "videoCapture",
+            Permission::Microphone => "audioCapture",
+            Permission::Midi => "midi",
+            Permission::MidiSysex => "midiSysex",
+            Permission::BackgroundSync => "backgroundSync",
+            Permission::PersistentStorage => "durableStorage",
+            Permission::Sensors => "sensors",
+            Permission::IdleDetection => "idleDetection",
+        }
+    }
+}
+
+/// Permissions granted per normalized origin.
+pub type PermissionGrants = HashMap<String, BTreeSet<Permission>>;
+
+/// Reduces `origin` to `scheme://host[:port]`, lowercased.
+///
+/// A path, query or fragment is dropped, so a page URL can be passed as well.
+/// Fails for anything that is not an absolute URL with a host.
+pub fn normalize_origin(origin: &str) -> Result<String, BrowserError> {
+    let invalid = || BrowserError::InvalidRequest(format!("Invalid origin: {:?}", origin));
+    let (scheme, rest) = origin.trim().split_once("://").ok_or_else(invalid)?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    // Credentials are never part of an origin
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let scheme_valid = scheme
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !scheme_valid || host.is_empty() || host.starts_with(':') {
+        return Err(invalid());
+    }
+    Ok(format!("{}://{}", scheme, host).to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_origin() {
+        assert_eq!(
+            normalize_origin("HTTPS://Maps.Example.com:8443/path?q=1").unwrap(),
+            "https://maps.example.com:8443"
+        );
+        assert_eq!(
+            normalize_origin("https://user:pw@example.com").unwrap(),
+            "https://example.com"
+        );
+        assert!(normalize_origin("example.com").is_err());
+        assert!(normalize_origin("https://").is_err());
+        assert!(normalize_origin("1http://example.com").is_err());
+
+        let json =
+            serde_json::to_string(&[Permission::Geolocation, Permission::ClipboardReadWrite])
+                .unwrap();
+        assert_eq!(json, r#"["geolocation","clipboard_read_write"]"#);
+        assert_eq!(Permission::Camera.cdp_name(), "videoCapture");
+    }
+}
@@ -0,0 +1,116 @@
+//! Redirect chains of top-level navigations.
+//!
+//! A [`RedirectChain`] starts with the URL passed to `navigate` and records
+//! every redirect that follows it, both HTTP redirects and client-side ones
+//! (meta refresh, `location` assignments without a user gesture). It aborts
+//! the navigation with a typed error as soon as the chain revisits a URL
+//! ([`BrowserError::RedirectLoop`]) or exceeds
+//! [`NavigateOptions::max_redirects`](crate::browser::NavigateOptions::max_redirects)
+//! ([`BrowserError::TooManyRedirects`]), instead of letting a looping page
+//! bounce until the caller's load timeout fires.
+
+use std::time::{Duration, Instant};
+
+use crate::error::BrowserError;
+
+/// Default limit for redirects per navigation (Chromium's own limit).
+pub const DEFAULT_MAX_REDIRECTS: u32 = 20;
+
+/// A script navigation counts as a client redirect only if it starts within
+/// this window after the previous hop.
+pub const CLIENT_REDIRECT_WINDOW: Duration = Duration::from_secs(3);
+
+/// URLs visited by one navigation, starting with the requested URL.
+#[derive(Debug, Clone)]
+pub struct RedirectChain {
+    urls: Vec<String>,
+    max_redirects: u32,
+    last_hop: Instant,
+}
+
+impl RedirectChain {
+    /// Starts a chain for a navigation to `url`.
+    pub fn new(url: &str, max_redirects: u32) -> Self {
+        Self {
+            urls: vec![url.to_string()],
+            max_redirects,
+            last_hop: Instant::now(),
+        }
+    }
+
+    /// URLs in the order they were visited.
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    /// Number of redirects followed so far.
+    pub fn redirect_count(&self) -> usize {
+        self.urls.len() - 1
+    }
+
+    /// Time since the navigation started or last redirected.
+    pub fn since_last_hop(&self) -> Duration {
+        self.last_hop.elapsed()
+    }
+
+    /// Records a redirect to `url`.
+    ///
+    /// Fails with [`BrowserError::RedirectLoop`] if `url` was already visited
+    /// and with [`BrowserError::TooManyRedirects`] if the redirect exceeds
+    /// the limit. Both errors carry the chain including `url`.
+    pub fn follow(&mut self, url: &str) -> Result<(), BrowserError> {
+        let is_loop = self.urls.iter().any(|seen| seen == url);
+        self.urls.push(url.to_string());
+        self.last_hop = Instant::now();
+
+        if is_loop {
+            return Err(BrowserError::RedirectLoop {
+                url: url.to_string(),
+                chain: self.urls.clone(),
+            });
+        }
+        if self.redirect_count() > self.max_redirects as usize {
+            return Err(BrowserError::TooManyRedirects {
+                max_redirects: self.max_redirects,
+                chain: self.urls.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redirect_chain_detects_loop() {
+        let mut chain = RedirectChain::new("https://a.example/", 10);
+        chain.follow("https://b.example/").unwrap();
+
+        let err = chain.follow("https://a.example/").unwrap_err();
+        assert!(matches!(
+            err,
+            BrowserError::RedirectLoop { ref url, ref chain }
+                if url == "https://a.example/" && chain.len() == 3
+        ));
+    }
+
+    #[test]
+    fn test_redirect_chain_enforces_limit() {
+        let mut chain = RedirectChain::new("https://example.com/0", 2);
+        chain.follow("https://example.com/1").unwrap();
+        chain.follow("https://example.com/2").unwrap();
+        assert_eq!(chain.redirect_count(), 2);
+
+        let err = chain.follow("https://example.com/3").unwrap_err();
+        assert!(matches!(
+            err,
+            BrowserError::TooManyRedirects { max_redirects: 2, ref chain } if chain.len() == 4
+        ));
+
+        // A limit of zero rejects the first redirect
+        let mut chain = RedirectChain::new("https://example.com/", 0);
+        assert!(chain.follow("https://example.com/login").is_err());
+    }
+}
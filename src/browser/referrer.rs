@@ -0,0 +1,80 @@
+//! Referrer for top-level navigations.
+//!
+//! Some sites reject a navigation that arrives without the `Referer` header
+//! a click from the expected page would send.
+//! [`NavigateOptions::referrer`](crate::browser::NavigateOptions::referrer)
+//! sets it for a single navigation, and
+//! [`BrowserEngine::set_referrer_policy`](crate::browser::BrowserEngine::set_referrer_policy)
+//! chooses how much of it is sent, like the page's `Referrer-Policy` would.
+//!
+//! With CEF the referrer is attached to the top-level load request; it is
+//! not a plain extra header, so it always wins over a `Referer` set any other
+//! way.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::BrowserError;
+
+/// How much of the referrer is sent, as in the `Referrer-Policy` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReferrerPolicy {
+    /// Never send a referrer.
+    NoReferrer,
+    /// Full URL, except from HTTPS to HTTP.
+    NoReferrerWhenDowngrade,
+    /// Only the origin.
+    Origin,
+    /// Full URL same-origin, only the origin cross-origin.
+    OriginWhenCrossOrigin,
+    /// Full URL same-origin, nothing cross-origin.
+    SameOrigin,
+    /// Only the origin, and nothing from HTTPS to HTTP.
+    StrictOrigin,
+    /// Full URL same-origin, the origin cross-origin, and nothing from HTTPS
+    /// to HTTP. The browser default.
+    #[default]
+    StrictOriginWhenCrossOrigin,
+    /// Always the full URL.
+    UnsafeUrl,
+}
+
+/// Checks that `referrer` is an absolute `http(s)` URL with a host.
+///
+/// Returns the URL without its fragment, which is never sent.
+pub fn validate_referrer(referrer: &str) -> Result<String, BrowserError> {
+    let invalid = || BrowserError::InvalidRequest(format!("Invalid referrer: {:?}", referrer));
+    let trimmed = referrer.trim();
+    let (scheme, rest) = trimmed.split_once("://").ok_or_else(invalid)?;
+    if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+        return Err(invalid());
+    }
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    if host.is_empty() || host.starts_with(':') || trimmed.chars().any(char::is_whitespace) {
+        return Err(invalid());
+    }
+    let without_fragment = trimmed.split('#').next().unwrap_or_default();
+    Ok(without_fragment.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_referrer() {
+        assert_eq!(
+            validate_referrer("https://search.example/results?q=1#top").unwrap(),
+            "https://search.example/results?q=1"
+        );
+        assert!(validate_referrer("search.example/results").is_err());
+        assert!(validate_referrer("ftp://files.example/").is_err());
+        assert!(validate_referrer("https:///path").is_err());
+        assert!(validate_referrer("https://a.example/with space").is_err());
+
+        let json = serde_json::to_string(&ReferrerPolicy::StrictOriginWhenCrossOrigin).unwrap();
+        assert_eq!(json, r#""strict-origin-when-cross-origin""#);
+    }
+}
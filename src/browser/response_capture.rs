@@ -0,0 +1,254 @@
+//! Per-tab capture of response bodies.
+//!
+//! Backs [`BrowserEngine::enable_response_capture`](crate::browser::BrowserEngine::enable_response_capture).
+//! Many sites render from JSON fetched by XHR/`fetch`; capturing those
+//! responses gives the data directly instead of scraping it back out of the
+//! DOM. Bodies of responses whose URL matches one of the tab's
+//! [`UrlPattern`]s are kept in a [`ResponseCapture`] store until the tab is
+//! closed.
+//!
+//! The store is bounded by total body bytes ([`DEFAULT_CAPTURE_MAX_BYTES`]
+//! unless configured otherwise); the oldest responses are evicted first, so
+//! a chatty page cannot exhaust memory.
+//!
+//! In the page, [`response_capture_script`] wraps `fetch` and
+//! `XMLHttpRequest` and reports textual bodies through `console.debug` with
+//! [`RESPONSE_CAPTURE_CONSOLE_PREFIX`]; URL matching happens engine-side.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::browser::url_pattern::{matches_any, UrlPattern};
+
+/// Default cap on the captured body bytes kept per tab (32 MiB).
+pub const DEFAULT_CAPTURE_MAX_BYTES: usize = 32 * 1024 * 1024;
+
+/// Console prefix used by [`response_capture_script`] to report responses.
+pub const RESPONSE_CAPTURE_CONSOLE_PREFIX: &str = "KI_RESPONSE:";
+
+/// A captured response body.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapturedResponse {
+    /// Final response URL.
+    pub url: String,
+
+    /// HTTP status code.
+    pub status: u16,
+
+    /// `Content-Type` of the response, if known.
+    pub mime_type: Option<String>,
+
+    /// Response body decoded as text.
+    pub body: String,
+
+    /// When the response was captured.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl CapturedResponse {
+    /// Creates a captured response without a MIME type.
+    pub fn new(url: impl Into<String>, status: u16, body: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            status,
+            mime_type: None,
+            body: body.into(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Sets the MIME type.
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+
+    /// Parses a report emitted by [`response_capture_script`].
+    ///
+    /// Returns `None` if the message does not carry the capture prefix or
+    /// the payload is malformed.
+    pub fn from_console_report(message: &str) -> Option<Self> {
+        #[derive(Deserialize)]
+        struct Report {
+            url: String,
+            status: u16,
+            mime_type: Option<String>,
+            body: String,
+        }
+
+        let payload = message.strip_prefix(RESPONSE_CAPTURE_CONSOLE_PREFIX)?;
+        let report: Report = serde_json::from_str(payload).ok()?;
+        Some(Self {
+            mime_type: report.mime_type,
+            ..Self::new(report.url, report.status, report.body)
+        })
+    }
+}
+
+/// Per-tab capture patterns and captured responses.
+#[derive(Debug, Clone)]
+pub struct ResponseCapture {
+    patterns: Vec<UrlPattern>,
+    responses: VecDeque<CapturedResponse>,
+    total_bytes: usize,
+    max_bytes: usize,
+}
+
+impl ResponseCapture {
+    /// Creates an empty store holding at most `max_bytes` of bodies.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            patterns: Vec::new(),
+            responses: VecDeque::new(),
+            total_bytes: 0,
+            max_bytes,
+        }
+    }
+
+    /// Adds a URL pattern; responses matching any pattern are captured.
+    pub fn add_pattern(&mut self, pattern: UrlPattern) {
+        if !self.patterns.iter().any(|p| p.as_str() == pattern.as_str()) {
+            self.patterns.push(pattern);
+        }
+    }
+
+    /// Whether a response from `url` would be captured.
+    pub fn matches(&self, url: &str) -> bool {
+        matches_any(&self.patterns, url)
+    }
+
+    /// Stores `response` if its URL matches, evicting the oldest responses
+    /// to stay within the byte cap.
+    ///
+    /// Returns `false` if the response was not stored, either because no
+    /// pattern matches or because its body alone exceeds the cap.
+    pub fn record(&mut self, response: CapturedResponse) -> bool {
+        let size = response.body.len();
+        if !self.matches(&response.url) || size > self.max_bytes {
+            return false;
+        }
+        while self.total_bytes + size > self.max_bytes {
+            match self.responses.pop_front() {
+                Some(evicted) => self.total_bytes -= evicted.body.len(),
+                None => break,
+            }
+        }
+        self.total_bytes += size;
+        self.responses.push_back(response);
+        true
+    }
+
+    /// Captured responses, oldest first.
+    pub fn responses(&self) -> Vec<CapturedResponse> {
+        self.responses.iter().cloned().collect()
+    }
+
+    /// Byte cap of the store.
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Total body bytes currently held.
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+}
+
+impl Default for ResponseCapture {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPTURE_MAX_BYTES)
+    }
+}
+
+/// Builds the page script that reports `fetch`/XHR response bodies.
+///
+/// Only textual bodies (JSON, text, XML, JavaScript) up to `max_body_bytes`
+/// characters are reported; everything else would be dropped by the store
+/// anyway and is not worth sending through the console.
+pub fn response_capture_script(max_body_bytes: usize) -> String {
+    format!(
+        r#"(function() {{
+    if (window.__kiResponseCapture) return;
+    Object.defineProperty(window, '__kiResponseCapture', {{ value: true, enumerable: false }});
+
+    const report = console.debug.bind(console);
+    const maxBody = {max_body_bytes};
+    const textual = (type) => !type || /json|text|xml|javascript/i.test(type);
+    const send = (url, status, type, body) => {{
+        if (typeof body !== 'string' || body.length > maxBody || !textual(type)) return;
+        report('{prefix}' + JSON.stringify({{ url: String(url), status: status, mime_type: type || null, body: body }}));
+    }};
+
+    const origFetch = window.fetch;
+    if (origFetch) {{
+        window.fetch = function(input, init) {{
+            return origFetch.apply(this, arguments).then((resp) => {{
+                const type = resp.headers.get('content-type');
+                if (textual(type)) {{
+                    resp.clone().text().then((body) => send(resp.url, resp.status, type, body), () => {{}});
+                }}
+                return resp;
+            }});
+        }};
+    }}
+
+    const origOpen = XMLHttpRequest.prototype.open;
+    XMLHttpRequest.prototype.open = function(method, url) {{
+        this.__kiCaptureUrl = url;
+        return origOpen.apply(this, arguments);
+    }};
+    const origSend = XMLHttpRequest.prototype.send;
+    XMLHttpRequest.prototype.send = function() {{
+        this.addEventListener('load', () => {{
+            let body = null;
+            if (this.responseType === '' || this.responseType === 'text') body = this.responseText;
+            else if (this.responseType === 'json') body = JSON.stringify(this.response);
+            send(this.responseURL || String(this.__kiCaptureUrl), this.status,
+                this.getResponseHeader('content-type'), body);
+        }});
+        return origSend.apply(this, arguments);
+    }};
+}})();"#,
+        prefix = RESPONSE_CAPTURE_CONSOLE_PREFIX,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_enforces_byte_cap() {
+        let mut capture = ResponseCapture::new(10);
+        capture.add_pattern(UrlPattern::parse("*/api/*").unwrap());
+
+        assert!(!capture.record(CapturedResponse::new("https://example.com/app.js", 200, "x")));
+        assert!(capture.record(CapturedResponse::new("https://example.com/api/a", 200, "aaaa")));
+        assert!(capture.record(CapturedResponse::new("https://example.com/api/b", 200, "bbbb")));
+        assert!(capture.record(CapturedResponse::new("https://example.com/api/c", 200, "cccc")));
+
+        // The oldest response was evicted to stay within 10 bytes
+        let urls: Vec<String> = capture.responses().into_iter().map(|r| r.url).collect();
+        assert_eq!(urls, ["https://example.com/api/b", "https://example.com/api/c"]);
+        assert_eq!(capture.total_bytes(), 8);
+
+        // A body larger than the whole cap is rejected without evicting
+        assert!(!capture.record(CapturedResponse::new("https://example.com/api/d", 200, "d".repeat(11))));
+        assert_eq!(capture.responses().len(), 2);
+    }
+
+    #[test]
+    fn test_captured_response_from_console_report() {
+        let response = CapturedResponse::from_console_report(
+            r#"KI_RESPONSE:{"url":"https://example.com/api/items","status":200,"mime_type":"application/json","body":"[1,2]"}"#,
+        )
+        .unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.mime_type.as_deref(), Some("application/json"));
+        assert_eq!(response.body, "[1,2]");
+
+        assert!(CapturedResponse::from_console_report("KI_BINDING:{}").is_none());
+        assert!(response_capture_script(1024).contains("const maxBody = 1024;"));
+    }
+}
@@ -16,9 +16,16 @@
 //! println!("Screenshot size: {} bytes", screenshot.data.len());
 //! ```
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::browser::dom::BoundingBox;
+use crate::browser::engine::PollBackoff;
+use crate::error::BrowserError;
 
 /// Supported image formats for screenshots.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -30,6 +37,8 @@ pub enum ScreenshotFormat {
     Jpeg,
     /// WebP format (modern, efficient compression).
     WebP,
+    /// AVIF format (AV1 compression, smallest files, slowest to encode).
+    Avif,
 }
 
 impl ScreenshotFormat {
@@ -39,6 +48,7 @@ impl ScreenshotFormat {
             ScreenshotFormat::Png => "image/png",
             ScreenshotFormat::Jpeg => "image/jpeg",
             ScreenshotFormat::WebP => "image/webp",
+            ScreenshotFormat::Avif => "image/avif",
         }
     }
 
@@ -48,13 +58,14 @@ impl ScreenshotFormat {
             ScreenshotFormat::Png => "png",
             ScreenshotFormat::Jpeg => "jpg",
             ScreenshotFormat::WebP => "webp",
+            ScreenshotFormat::Avif => "avif",
         }
     }
 
     /// Returns whether this format supports transparency.
     pub fn supports_transparency(&self) -> bool {
         match self {
-            ScreenshotFormat::Png | ScreenshotFormat::WebP => true,
+            ScreenshotFormat::Png | ScreenshotFormat::WebP | ScreenshotFormat::Avif => true,
             ScreenshotFormat::Jpeg => false,
         }
     }
@@ -66,6 +77,7 @@ impl std::fmt::Display for ScreenshotFormat {
             ScreenshotFormat::Png => write!(f, "PNG"),
             ScreenshotFormat::Jpeg => write!(f, "JPEG"),
             ScreenshotFormat::WebP => write!(f, "WebP"),
+            ScreenshotFormat::Avif => write!(f, "AVIF"),
         }
     }
 }
@@ -146,7 +158,7 @@ pub struct ScreenshotOptions {
     /// Image format for the screenshot.
     pub format: ScreenshotFormat,
 
-    /// Quality for JPEG/WebP formats (0-100). Ignored for PNG.
+    /// Quality for JPEG/WebP (0-100) and AVIF (1-100). Ignored for PNG.
     pub quality: u8,
 
     /// Whether to capture the full scrollable page.
@@ -163,6 +175,23 @@ pub struct ScreenshotOptions {
 
     /// Whether to optimize for speed over quality.
     pub optimize_for_speed: bool,
+
+    /// Wait up to this many milliseconds for the first paint instead of
+    /// failing when no frame has been rendered yet. `None` captures immediately.
+    #[serde(default)]
+    pub first_paint_timeout_ms: Option<u64>,
+
+    /// Keep native scrollbars in the capture. Off by default: scrollbars
+    /// are hidden for the capture and restored afterwards, so images look
+    /// the same regardless of platform scrollbar styles.
+    #[serde(default)]
+    pub include_scrollbars: bool,
+
+    /// Return the unencoded RGBA pixels, marked as
+    /// [`ScreenshotEncoding::Raw`], when encoding the image fails instead of
+    /// failing the capture.
+    #[serde(default)]
+    pub fallback_to_raw: bool,
 }
 
 impl Default for ScreenshotOptions {
@@ -175,6 +204,9 @@ impl Default for ScreenshotOptions {
             from_surface: true,
             capture_beyond_viewport: false,
             optimize_for_speed: false,
+            first_paint_timeout_ms: None,
+            include_scrollbars: false,
+            fallback_to_raw: false,
         }
     }
 }
@@ -191,7 +223,7 @@ impl ScreenshotOptions {
         self
     }
 
-    /// Sets the quality (for JPEG/WebP).
+    /// Sets the quality (for JPEG/WebP/AVIF).
     pub fn quality(mut self, quality: u8) -> Self {
         self.quality = quality.min(100);
         self
@@ -221,6 +253,26 @@ impl ScreenshotOptions {
         self
     }
 
+    /// Waits up to `timeout` for the tab's first paint before capturing.
+    ///
+    /// Avoids the "No frame data available" race right after tab creation.
+    pub fn wait_for_first_paint(mut self, timeout: Duration) -> Self {
+        self.first_paint_timeout_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    /// Sets whether native scrollbars appear in the capture.
+    pub fn include_scrollbars(mut self, include: bool) -> Self {
+        self.include_scrollbars = include;
+        self
+    }
+
+    /// Sets whether a failed encoding returns the raw pixels.
+    pub fn fallback_to_raw(mut self, fallback: bool) -> Self {
+        self.fallback_to_raw = fallback;
+        self
+    }
+
     /// Validates the options.
     pub fn validate(&self) -> Result<()> {
         if let Some(ref clip) = self.clip_region {
@@ -233,10 +285,184 @@ impl ScreenshotOptions {
             return Err(anyhow!("Quality must be between 0 and 100"));
         }
 
+        if self.format == ScreenshotFormat::Avif && self.quality == 0 {
+            return Err(anyhow!("AVIF quality must be between 1 and 100"));
+        }
+
         Ok(())
     }
 }
 
+/// Waits until `frame_count` reports at least one paint.
+///
+/// Polls with `backoff` and fails with [`BrowserError::Timeout`] if no frame
+/// arrives within `timeout`. Returns immediately if a frame already exists.
+pub async fn wait_for_first_paint(
+    frame_count: &AtomicU64,
+    timeout: Duration,
+    backoff: PollBackoff,
+) -> Result<()> {
+    let start = Instant::now();
+    let mut interval = backoff.initial();
+    loop {
+        if frame_count.load(Ordering::Acquire) > 0 {
+            return Ok(());
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return Err(BrowserError::Timeout {
+                operation: "waiting for first paint".to_string(),
+                duration_ms: timeout.as_millis() as u64,
+            }
+            .into());
+        }
+        tokio::time::sleep(interval.min(timeout - elapsed)).await;
+        interval = backoff.next(interval);
+    }
+}
+
+/// Page script that hides native scrollbars for a capture.
+///
+/// Injects a style element that is removed again by
+/// [`RESTORE_SCROLLBARS_SCRIPT`]; scroll positions are left untouched.
+pub const HIDE_SCROLLBARS_SCRIPT: &str = r#"(function() {
+    if (document.getElementById('__ki_hide_scrollbars')) return;
+    const style = document.createElement('style');
+    style.id = '__ki_hide_scrollbars';
+    style.textContent = '::-webkit-scrollbar { display: none !important; } '
+        + '* { scrollbar-width: none !important; }';
+    (document.head || document.documentElement).appendChild(style);
+})();"#;
+
+/// Page script that undoes [`HIDE_SCROLLBARS_SCRIPT`].
+pub const RESTORE_SCROLLBARS_SCRIPT: &str = r#"(function() {
+    const style = document.getElementById('__ki_hide_scrollbars');
+    if (style) style.remove();
+})();"#;
+
+/// Page script that hides `position: fixed` and `position: sticky`
+/// elements between the tiles of a stitched full-page capture.
+///
+/// Run after the first tile, so headers and banners appear once at the top
+/// of the image instead of on every tile. [`RESTORE_FIXED_ELEMENTS_SCRIPT`]
+/// makes them visible again.
+pub const HIDE_FIXED_ELEMENTS_SCRIPT: &str = r#"(function() {
+    if (window.__kiHiddenFixed) return;
+    window.__kiHiddenFixed = [];
+    for (const el of document.querySelectorAll('body *')) {
+        const position = window.getComputedStyle(el).position;
+        if (position !== 'fixed' && position !== 'sticky') continue;
+        window.__kiHiddenFixed.push([el, el.style.getPropertyValue('visibility'),
+            el.style.getPropertyPriority('visibility')]);
+        el.style.setProperty('visibility', 'hidden', 'important');
+    }
+})();"#;
+
+/// Page script that undoes [`HIDE_FIXED_ELEMENTS_SCRIPT`].
+pub const RESTORE_FIXED_ELEMENTS_SCRIPT: &str = r#"(function() {
+    for (const [el, value, priority] of window.__kiHiddenFixed || []) {
+        el.style.setProperty('visibility', value, priority);
+    }
+    delete window.__kiHiddenFixed;
+})();"#;
+
+/// A viewport capture taken at a vertical offset of the page, one piece of
+/// a stitched full-page screenshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameTile {
+    /// Offset of the tile's first row in the page, in pixels.
+    pub y: u32,
+
+    /// Pixel rows of the tile, 4 bytes per pixel, as wide as the page.
+    pub buffer: Vec<u8>,
+}
+
+/// Composites `tiles` into one `width` x `height` frame.
+///
+/// Works on any 4-byte pixel format. Tiles are copied in order, so where
+/// they overlap (the page clamps the scroll position of the last one) later
+/// tiles win; rows beyond `height` are dropped and rows no tile covers stay
+/// transparent black.
+pub fn stitch_tiles(tiles: &[FrameTile], width: u32, height: u32) -> Vec<u8> {
+    let row_bytes = width as usize * 4;
+    let mut frame = vec![0u8; row_bytes * height as usize];
+    for tile in tiles {
+        let start = tile.y as usize * row_bytes;
+        if start >= frame.len() {
+            continue;
+        }
+        let len = (tile.buffer.len() / row_bytes * row_bytes).min(frame.len() - start);
+        frame[start..start + len].copy_from_slice(&tile.buffer[..len]);
+    }
+    frame
+}
+
+/// Runs `capture` according to `options.include_scrollbars`.
+///
+/// Unless scrollbars are included, [`HIDE_SCROLLBARS_SCRIPT`] is run
+/// through `run_script` before the capture and [`RESTORE_SCROLLBARS_SCRIPT`]
+/// after it, also when the capture fails. A failed restore is logged rather
+/// than turning a successful capture into an error.
+pub async fn capture_with_scrollbar_policy<R, RF, C, CF, T>(
+    options: &ScreenshotOptions,
+    run_script: R,
+    capture: C,
+) -> Result<T>
+where
+    R: Fn(&'static str) -> RF,
+    RF: Future<Output = Result<()>>,
+    C: FnOnce() -> CF,
+    CF: Future<Output = Result<T>>,
+{
+    if options.include_scrollbars {
+        return capture().await;
+    }
+
+    run_script(HIDE_SCROLLBARS_SCRIPT).await?;
+    let result = capture().await;
+    if let Err(e) = run_script(RESTORE_SCROLLBARS_SCRIPT).await {
+        tracing::warn!("Failed to restore scrollbars after screenshot: {}", e);
+    }
+    result
+}
+
+/// Waits until `frame_count` moves past `seen`, i.e. a new frame was painted.
+///
+/// Returns `false` if no new frame arrived within `timeout`; used after page
+/// style changes that may or may not trigger a repaint.
+pub async fn wait_for_new_frame(
+    frame_count: &AtomicU64,
+    seen: u64,
+    timeout: Duration,
+    backoff: PollBackoff,
+) -> bool {
+    let start = Instant::now();
+    let mut interval = backoff.initial();
+    loop {
+        if frame_count.load(Ordering::Acquire) > seen {
+            return true;
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return false;
+        }
+        tokio::time::sleep(interval.min(timeout - elapsed)).await;
+        interval = backoff.next(interval);
+    }
+}
+
+/// How the data of a [`Screenshot`] is encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenshotEncoding {
+    /// An image in the screenshot's format.
+    #[default]
+    Image,
+    /// Unencoded RGBA pixels, row by row, returned because encoding failed
+    /// with [`ScreenshotOptions::fallback_to_raw`] set.
+    Raw,
+}
+
 /// Result of a screenshot capture operation.
 #[derive(Debug, Clone)]
 pub struct Screenshot {
@@ -246,6 +472,9 @@ pub struct Screenshot {
     /// Image format.
     pub format: ScreenshotFormat,
 
+    /// Whether `data` is an image or raw pixels.
+    pub encoding: ScreenshotEncoding,
+
     /// Width of the captured image in pixels.
     pub width: u32,
 
@@ -254,6 +483,10 @@ pub struct Screenshot {
 
     /// Device scale factor used during capture.
     pub device_scale_factor: f64,
+
+    /// Whether a full-page capture was cut off because the page is taller
+    /// than the engine can stitch.
+    pub truncated: bool,
 }
 
 impl Screenshot {
@@ -268,12 +501,29 @@ impl Screenshot {
         Self {
             data,
             format,
+            encoding: ScreenshotEncoding::Image,
             width,
             height,
             device_scale_factor,
+            truncated: false,
+        }
+    }
+
+    /// Creates a screenshot of unencoded RGBA pixels.
+    ///
+    /// `format` is the format that was requested but could not be encoded.
+    pub fn raw(rgba: &[u8], format: ScreenshotFormat, width: u32, height: u32) -> Self {
+        Self {
+            encoding: ScreenshotEncoding::Raw,
+            ..Self::new(BASE64.encode(rgba), format, width, height, 1.0)
         }
     }
 
+    /// Whether the data holds raw pixels instead of an image.
+    pub fn is_raw(&self) -> bool {
+        self.encoding == ScreenshotEncoding::Raw
+    }
+
     /// Decodes the base64 data to raw bytes.
     pub fn decode(&self) -> Result<Vec<u8>> {
         BASE64.decode(&self.data).map_err(|e| anyhow!("Failed to decode screenshot: {}", e))
@@ -281,7 +531,11 @@ impl Screenshot {
 
     /// Returns the data as a data URL.
     pub fn to_data_url(&self) -> String {
-        format!("data:{};base64,{}", self.format.mime_type(), self.data)
+        let mime_type = match self.encoding {
+            ScreenshotEncoding::Image => self.format.mime_type(),
+            ScreenshotEncoding::Raw => "application/octet-stream",
+        };
+        format!("data:{};base64,{}", mime_type, self.data)
     }
 
     /// Returns the approximate size in bytes.
@@ -296,6 +550,262 @@ impl Screenshot {
     }
 }
 
+/// Edge length in pixels of the checkerboard cells in [`test_pattern_frame`].
+pub const TEST_PATTERN_CELL_PX: u32 = 32;
+
+/// Renders a deterministic BGRA test frame.
+///
+/// A diagonal colour gradient overlaid with a checkerboard of
+/// [`TEST_PATTERN_CELL_PX`] cells. The pixels depend only on the dimensions,
+/// so the frame is identical on every run and platform.
+pub fn test_pattern_frame(width: u32, height: u32) -> Vec<u8> {
+    let mut frame = Vec::with_capacity((width as usize) * (height as usize) * 4);
+    for y in 0..height {
+        for x in 0..width {
+            let r = (x * 255 / width.max(1)) as u8;
+            let g = (y * 255 / height.max(1)) as u8;
+            let b = ((x + y) * 255 / (width + height).max(1)) as u8;
+            let dark = ((x / TEST_PATTERN_CELL_PX) + (y / TEST_PATTERN_CELL_PX)) % 2 == 1;
+            let shade = |c: u8| if dark { c / 2 } else { c };
+            frame.extend_from_slice(&[shade(b), shade(g), shade(r), 0xFF]);
+        }
+    }
+    frame
+}
+
+/// Largest width or height of a WebP image.
+pub const MAX_WEBP_DIMENSION: u32 = 16383;
+
+/// Encodes RGBA pixels as WebP.
+///
+/// A `quality` below 100 encodes lossy (VP8) at that quality, 100 encodes
+/// losslessly (VP8L).
+pub fn encode_webp(rgba: &[u8], width: u32, height: u32, quality: u8) -> Result<Vec<u8>> {
+    if width > MAX_WEBP_DIMENSION || height > MAX_WEBP_DIMENSION {
+        return Err(anyhow!(
+            "{}x{} exceeds the WebP size limit of {} pixels",
+            width,
+            height,
+            MAX_WEBP_DIMENSION
+        ));
+    }
+    if rgba.len() != width as usize * height as usize * 4 {
+        return Err(anyhow!(
+            "RGBA buffer of {} bytes does not match {}x{}",
+            rgba.len(),
+            width,
+            height
+        ));
+    }
+
+    let encoder = libwebp::Encoder::from_rgba(rgba, width, height);
+    let output = encoder
+        .encode_simple(quality >= 100, quality.min(100) as f32)
+        .map_err(|e| anyhow!("Failed to encode WebP screenshot: {:?}", e))?;
+    Ok(output.to_vec())
+}
+
+/// Encoder speed for AVIF screenshots, from 1 (slowest, smallest) to 10.
+///
+/// AV1 encoding is expensive; at the encoder's default speed of 4 a full HD
+/// frame takes seconds.
+pub const AVIF_ENCODER_SPEED: u8 = 8;
+
+/// Encodes RGBA pixels as AVIF.
+///
+/// `quality` (1-100) is mapped onto the AV1 quantizer by the encoder:
+/// 100 selects the finest quantizer, lower values coarser ones.
+pub fn encode_avif(rgba: &[u8], width: u32, height: u32, quality: u8) -> Result<Vec<u8>> {
+    use image::codecs::avif::AvifEncoder;
+    use image::ImageEncoder;
+
+    if !(1..=100).contains(&quality) {
+        return Err(anyhow!("AVIF quality must be between 1 and 100"));
+    }
+    if rgba.len() != width as usize * height as usize * 4 {
+        return Err(anyhow!(
+            "RGBA buffer of {} bytes does not match {}x{}",
+            rgba.len(),
+            width,
+            height
+        ));
+    }
+
+    let mut output = Vec::new();
+    AvifEncoder::new_with_speed_quality(&mut output, AVIF_ENCODER_SPEED, quality)
+        .write_image(rgba, width, height, image::ColorType::Rgba8)
+        .context("Failed to encode AVIF screenshot")?;
+    Ok(output)
+}
+
+/// Encodes a BGRA frame buffer, as painted by the renderer, into `format`.
+pub fn encode_bgra_frame(
+    buffer: &[u8],
+    width: u32,
+    height: u32,
+    format: ScreenshotFormat,
+    quality: u8,
+) -> Result<Vec<u8>> {
+    use image::{ImageBuffer, ImageOutputFormat, Rgba};
+
+    // Create image from BGRA buffer
+    let mut img: ImageBuffer<Rgba<u8>, Vec<u8>> = ImageBuffer::new(width, height);
+
+    for (x, y, pixel) in img.enumerate_pixels_mut() {
+        let idx = ((y * width + x) * 4) as usize;
+        if idx + 3 < buffer.len() {
+            // BGRA to RGBA conversion
+            *pixel = Rgba([
+                buffer[idx + 2], // R
+                buffer[idx + 1], // G
+                buffer[idx],     // B
+                buffer[idx + 3], // A
+            ]);
+        }
+    }
+
+    // Encode to requested format
+    let mut output = Vec::new();
+    let format = match format {
+        ScreenshotFormat::Png => ImageOutputFormat::Png,
+        ScreenshotFormat::Jpeg => ImageOutputFormat::Jpeg(quality),
+        ScreenshotFormat::WebP => return encode_webp(img.as_raw(), width, height, quality),
+        ScreenshotFormat::Avif => return encode_avif(img.as_raw(), width, height, quality),
+    };
+
+    img.write_to(&mut std::io::Cursor::new(&mut output), format)
+        .context("Failed to encode screenshot")?;
+
+    Ok(output)
+}
+
+/// Converts the `width` x `height` region at (`x`, `y`) of a BGRA frame
+/// `frame_width` pixels wide into RGBA pixels.
+///
+/// The region is clamped to the frame; pixels missing from a short buffer
+/// stay transparent black.
+pub fn crop_bgra_to_rgba(
+    buffer: &[u8],
+    frame_width: u32,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Vec<u8> {
+    let mut rgba = vec![0u8; width as usize * height as usize * 4];
+    for row in 0..height as usize {
+        for col in 0..width as usize {
+            let src = ((y as usize + row) * frame_width as usize + x as usize + col) * 4;
+            let dst = (row * width as usize + col) * 4;
+            if let Some(px) = buffer.get(src..src + 4) {
+                rgba[dst..dst + 4].copy_from_slice(&[px[2], px[1], px[0], px[3]]);
+            }
+        }
+    }
+    rgba
+}
+
+/// Builds a screenshot from the output of `encode`, honouring
+/// [`ScreenshotOptions::fallback_to_raw`].
+///
+/// If `encode` fails, the error is logged and, with the fallback enabled,
+/// the RGBA pixels from `raw` (with their width and height) are returned as
+/// a [`ScreenshotEncoding::Raw`] screenshot instead of the error.
+pub fn encode_with_fallback(
+    options: &ScreenshotOptions,
+    width: u32,
+    height: u32,
+    encode: impl FnOnce() -> Result<Vec<u8>>,
+    raw: impl FnOnce() -> (Vec<u8>, u32, u32),
+) -> Result<Screenshot> {
+    match encode() {
+        Ok(encoded) => Ok(Screenshot::new(
+            BASE64.encode(&encoded),
+            options.format,
+            width,
+            height,
+            1.0,
+        )),
+        Err(e) => {
+            tracing::warn!(
+                "Encoding {}x{} {} screenshot failed: {:#}",
+                width,
+                height,
+                options.format,
+                e
+            );
+            if !options.fallback_to_raw {
+                return Err(e);
+            }
+            let (rgba, raw_width, raw_height) = raw();
+            Ok(Screenshot::raw(&rgba, options.format, raw_width, raw_height))
+        }
+    }
+}
+
+/// Converts the viewport-relative box of the element matching `selector`
+/// to the clip region of a frame rendered at `device_scale_factor`.
+///
+/// The box is in CSS pixels, the frame in device pixels; fractional edges
+/// are rounded outwards so the whole element is covered. `bounding_box` is
+/// `None` when nothing matches. Fails with [`BrowserError::DomQueryFailed`]
+/// for a missing element or one without area.
+pub fn element_clip_region(
+    selector: &str,
+    bounding_box: Option<BoundingBox>,
+    device_scale_factor: f64,
+) -> Result<ClipRegion, BrowserError> {
+    let bounding_box = bounding_box.ok_or_else(|| BrowserError::DomQueryFailed {
+        selector: selector.to_string(),
+        reason: "No element matches selector".to_string(),
+    })?;
+    if !(bounding_box.width > 0.0 && bounding_box.height > 0.0) {
+        return Err(BrowserError::DomQueryFailed {
+            selector: selector.to_string(),
+            reason: format!(
+                "Element has no area ({}x{})",
+                bounding_box.width, bounding_box.height
+            ),
+        });
+    }
+
+    let left = (bounding_box.x * device_scale_factor).floor();
+    let top = (bounding_box.y * device_scale_factor).floor();
+    let right = ((bounding_box.x + bounding_box.width) * device_scale_factor).ceil();
+    let bottom = ((bounding_box.y + bounding_box.height) * device_scale_factor).ceil();
+    Ok(ClipRegion::new(left, top, right - left, bottom - top))
+}
+
+/// Captures [`test_pattern_frame`] through the real encoding path.
+///
+/// The frame is `width` x `height`, or the scaled clip size when a clip
+/// region is set. The result is byte-stable for the same options, which
+/// makes it suitable for golden-image tests.
+pub fn capture_test_pattern_screenshot(
+    options: &ScreenshotOptions,
+    width: u32,
+    height: u32,
+) -> Result<Screenshot> {
+    options.validate()?;
+
+    let (width, height) = match options.clip_region {
+        Some(ref clip) => {
+            let (w, h) = clip.scaled_dimensions();
+            (w as u32, h as u32)
+        }
+        None => (width, height),
+    };
+
+    let frame = test_pattern_frame(width, height);
+    encode_with_fallback(
+        options,
+        width,
+        height,
+        || encode_bgra_frame(&frame, width, height, options.format, options.quality),
+        || (crop_bgra_to_rgba(&frame, width, 0, 0, width, height), width, height),
+    )
+}
+
 /// Captures a mock screenshot for testing purposes.
 ///
 /// This function generates a simple placeholder image encoded in base64.
@@ -333,6 +843,8 @@ pub fn capture_mock_screenshot(options: &ScreenshotOptions) -> Result<Screenshot
 /// Creates a minimal placeholder image for the specified format.
 fn create_placeholder_image(format: ScreenshotFormat) -> Result<Vec<u8>> {
     match format {
+        // 1x1 white pixel; the container is too involved to spell out by hand
+        ScreenshotFormat::Avif => encode_avif(&[0xFF; 4], 1, 1, 80),
         ScreenshotFormat::Png => {
             // Minimal valid PNG (1x1 transparent pixel)
             Ok(vec![
@@ -433,7 +945,10 @@ pub trait ScreenshotCapture: Send + Sync {
 }
 
 /// Mock implementation of ScreenshotCapture for testing.
-pub struct MockScreenshotCapture;
+pub struct MockScreenshotCapture {
+    /// Viewport size of the deterministic test pattern, if enabled.
+    test_pattern: Option<(u32, u32)>,
+}
 
 impl Default for MockScreenshotCapture {
     fn default() -> Self {
@@ -444,14 +959,29 @@ impl Default for MockScreenshotCapture {
 impl MockScreenshotCapture {
     /// Creates a new MockScreenshotCapture.
     pub fn new() -> Self {
-        Self
+        Self { test_pattern: None }
+    }
+
+    /// Creates a capture that encodes [`test_pattern_frame`] of the given
+    /// size instead of returning placeholder images.
+    pub fn with_test_pattern(width: u32, height: u32) -> Self {
+        Self {
+            test_pattern: Some((width, height)),
+        }
+    }
+
+    fn capture_sync(&self, options: &ScreenshotOptions) -> Result<Screenshot> {
+        match self.test_pattern {
+            Some((width, height)) => capture_test_pattern_screenshot(options, width, height),
+            None => capture_mock_screenshot(options),
+        }
     }
 }
 
 #[async_trait::async_trait]
 impl ScreenshotCapture for MockScreenshotCapture {
     async fn capture(&self, options: &ScreenshotOptions) -> Result<Screenshot> {
-        capture_mock_screenshot(options)
+        self.capture_sync(options)
     }
 
     async fn capture_element(
@@ -462,13 +992,139 @@ impl ScreenshotCapture for MockScreenshotCapture {
         // For mock, just return a smaller screenshot simulating an element
         let mut element_options = options.clone();
         element_options.clip_region = Some(ClipRegion::new(0.0, 0.0, 200.0, 100.0));
-        capture_mock_screenshot(&element_options)
+        self.capture_sync(&element_options)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_wait_for_first_paint_blocks_until_paint() {
+        let frames = Arc::new(AtomicU64::new(0));
+        let options = ScreenshotOptions::new().wait_for_first_paint(Duration::from_secs(5));
+        let timeout = Duration::from_millis(options.first_paint_timeout_ms.unwrap());
+
+        let painter = tokio::spawn({
+            let frames = frames.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                frames.fetch_add(1, Ordering::Release);
+            }
+        });
+
+        let start = Instant::now();
+        wait_for_first_paint(&frames, timeout, PollBackoff::default())
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(frames.load(Ordering::Acquire), 1);
+        painter.await.unwrap();
+
+        // Without a paint the wait gives up with a typed timeout
+        let idle = AtomicU64::new(0);
+        let err = wait_for_first_paint(&idle, Duration::from_millis(50), PollBackoff::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BrowserError>(),
+            Some(BrowserError::Timeout { duration_ms: 50, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_scrollbars_hidden_around_capture_unless_included() {
+        let log = std::sync::Mutex::new(Vec::new());
+        let run_script = |script: &'static str| {
+            log.lock().unwrap().push(script.to_string());
+            async { Ok(()) }
+        };
+
+        let options = ScreenshotOptions::new();
+        assert!(!options.include_scrollbars);
+        let shot = capture_with_scrollbar_policy(&options, run_script, || async {
+            log.lock().unwrap().push("capture".to_string());
+            capture_mock_screenshot(&options)
+        })
+        .await
+        .unwrap();
+        assert_eq!(shot.format, ScreenshotFormat::Png);
+        assert_eq!(
+            *log.lock().unwrap(),
+            [HIDE_SCROLLBARS_SCRIPT, "capture", RESTORE_SCROLLBARS_SCRIPT]
+        );
+
+        // Scrollbars are restored even if the capture fails
+        log.lock().unwrap().clear();
+        let failed: Result<()> = capture_with_scrollbar_policy(&options, run_script, || async {
+            Err(anyhow!("No frame data available for screenshot"))
+        })
+        .await;
+        assert!(failed.is_err());
+        assert_eq!(
+            *log.lock().unwrap(),
+            [HIDE_SCROLLBARS_SCRIPT, RESTORE_SCROLLBARS_SCRIPT]
+        );
+
+        log.lock().unwrap().clear();
+        let with_scrollbars = ScreenshotOptions::new().include_scrollbars(true);
+        capture_with_scrollbar_policy(&with_scrollbars, run_script, || async {
+            capture_mock_screenshot(&with_scrollbars)
+        })
+        .await
+        .unwrap();
+        assert!(log.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_encode_failure_falls_back_to_raw_pixels() {
+        // JPEG cannot encode images wider than 65535 pixels
+        let options = ScreenshotOptions::new().format(ScreenshotFormat::Jpeg);
+        assert!(capture_test_pattern_screenshot(&options, 70_000, 1).is_err());
+
+        let options = options.fallback_to_raw(true);
+        let screenshot = capture_test_pattern_screenshot(&options, 70_000, 1).unwrap();
+        assert!(screenshot.is_raw());
+        assert_eq!(screenshot.dimensions(), (70_000, 1));
+        assert!(screenshot.to_data_url().starts_with("data:application/octet-stream;"));
+
+        let pixels = screenshot.decode().unwrap();
+        assert_eq!(pixels.len(), 70_000 * 4);
+        // BGRA frame pixels come back as RGBA
+        let frame = test_pattern_frame(70_000, 1);
+        let last = frame.len() - 4;
+        assert_eq!(pixels[last..], [frame[last + 2], frame[last + 1], frame[last], frame[last + 3]]);
+    }
+
+    #[test]
+    fn test_stitch_tiles() {
+        let (width, tile_height) = (4, 1080);
+        let tile = |shade: u8| vec![shade; width as usize * tile_height as usize * 4];
+        let tiles = [
+            FrameTile { y: 0, buffer: tile(0x11) },
+            FrameTile { y: tile_height, buffer: tile(0x22) },
+        ];
+
+        let frame = stitch_tiles(&tiles, width, 2 * tile_height);
+        let row = |y: usize| &frame[y * width as usize * 4..(y + 1) * width as usize * 4];
+        assert_eq!(frame.len(), width as usize * 2160 * 4);
+        assert!(row(0).iter().all(|&b| b == 0x11));
+        assert!(row(1079).iter().all(|&b| b == 0x11));
+        assert!(row(1080).iter().all(|&b| b == 0x22));
+        assert!(row(2159).iter().all(|&b| b == 0x22));
+
+        // An overlapping last tile wins; rows past the frame are dropped
+        let tiles = [
+            FrameTile { y: 0, buffer: tile(0x11) },
+            FrameTile { y: 500, buffer: tile(0x22) },
+        ];
+        let frame = stitch_tiles(&tiles, width, 1500);
+        assert_eq!(frame.len(), width as usize * 1500 * 4);
+        assert_eq!(frame[499 * width as usize * 4], 0x11);
+        assert_eq!(frame[500 * width as usize * 4], 0x22);
+    }
 
     #[test]
     fn test_screenshot_format() {
@@ -539,6 +1195,25 @@ mod tests {
         assert_eq!(screenshot.height, 300);
     }
 
+    #[test]
+    fn test_element_clip_region_scales_to_device_pixels() {
+        let bounding_box = BoundingBox::new(10.25, 20.0, 100.5, 50.0);
+
+        let clip = element_clip_region("#card", Some(bounding_box), 1.0).unwrap();
+        assert_eq!(clip, ClipRegion::new(10.0, 20.0, 101.0, 50.0));
+
+        // At DPR 2 the region doubles and the edges are rounded outwards
+        let clip = element_clip_region("#card", Some(bounding_box), 2.0).unwrap();
+        assert_eq!(clip, ClipRegion::new(20.0, 40.0, 202.0, 100.0));
+        assert_eq!(clip.scale, 1.0);
+
+        let err = element_clip_region("#missing", None, 2.0).unwrap_err();
+        assert!(err.to_string().contains("No element matches selector"));
+        let hidden = BoundingBox::new(10.0, 20.0, 0.0, 50.0);
+        let err = element_clip_region("#hidden", Some(hidden), 2.0).unwrap_err();
+        assert!(err.to_string().contains("no area"));
+    }
+
     #[tokio::test]
     async fn test_mock_screenshot_capture() {
         let capture = MockScreenshotCapture::new();
@@ -558,6 +1233,33 @@ mod tests {
         assert_eq!(element_screenshot.height, 100);
     }
 
+    #[tokio::test]
+    async fn test_test_pattern_capture_is_byte_stable() {
+        let options = ScreenshotOptions::new().format(ScreenshotFormat::Png);
+        let first = MockScreenshotCapture::with_test_pattern(320, 200)
+            .capture(&options)
+            .await
+            .unwrap();
+        let second = MockScreenshotCapture::with_test_pattern(320, 200)
+            .capture(&options)
+            .await
+            .unwrap();
+
+        let png = first.decode().unwrap();
+        assert_eq!(png, second.decode().unwrap());
+        assert_eq!(first.dimensions(), (320, 200));
+
+        // The encoded frame decodes back to the generated pattern
+        let decoded = image::load_from_memory(&png).unwrap().to_rgba8();
+        assert_eq!(decoded.dimensions(), (320, 200));
+        let frame = test_pattern_frame(320, 200);
+        let idx = ((40 * 320 + 40) * 4) as usize;
+        assert_eq!(
+            decoded.get_pixel(40, 40).0,
+            [frame[idx + 2], frame[idx + 1], frame[idx], frame[idx + 3]]
+        );
+    }
+
     #[test]
     fn test_placeholder_images() {
         // Verify all format placeholders are valid
@@ -573,4 +1275,61 @@ mod tests {
         assert!(!webp.is_empty());
         assert_eq!(&webp[0..4], b"RIFF");
     }
+
+    #[test]
+    fn test_webp_magic_bytes() {
+        let frame = test_pattern_frame(64, 48);
+        for quality in [80, 100] {
+            let webp = encode_bgra_frame(&frame, 64, 48, ScreenshotFormat::WebP, quality).unwrap();
+            assert_eq!(&webp[0..4], b"RIFF");
+            assert_eq!(&webp[8..12], b"WEBP");
+            let riff_size = u32::from_le_bytes([webp[4], webp[5], webp[6], webp[7]]);
+            assert_eq!(riff_size as usize, webp.len() - 8);
+        }
+    }
+
+    #[test]
+    fn test_webp_decodes_to_frame_size() {
+        let frame = test_pattern_frame(200, 120);
+        let lossy = encode_bgra_frame(&frame, 200, 120, ScreenshotFormat::WebP, 50).unwrap();
+        let lossless = encode_bgra_frame(&frame, 200, 120, ScreenshotFormat::WebP, 100).unwrap();
+        assert!(lossy.len() < lossless.len());
+
+        for webp in [lossy, lossless] {
+            let decoded = image::load_from_memory_with_format(&webp, image::ImageFormat::WebP)
+                .expect("valid WebP");
+            assert_eq!((decoded.width(), decoded.height()), (200, 120));
+        }
+
+        assert!(encode_webp(&[0; 16], 4, 4, 80).is_err());
+        assert!(encode_webp(&[], MAX_WEBP_DIMENSION + 1, 0, 80).is_err());
+    }
+
+    #[test]
+    fn test_avif_ftyp_signature() {
+        let frame = test_pattern_frame(32, 24);
+        let avif = encode_bgra_frame(&frame, 32, 24, ScreenshotFormat::Avif, 60).unwrap();
+        // ISO BMFF: box size, then the `ftyp` box with major brand `avif`
+        assert_eq!(&avif[4..8], b"ftyp");
+        assert_eq!(&avif[8..12], b"avif");
+
+        let placeholder = create_placeholder_image(ScreenshotFormat::Avif).unwrap();
+        assert_eq!(&placeholder[4..12], b"ftypavif");
+        assert!(encode_avif(&frame, 32, 24, 0).is_err());
+    }
+
+    #[test]
+    fn test_avif_quality_validation() {
+        let options = ScreenshotOptions::new()
+            .format(ScreenshotFormat::Avif)
+            .quality(0);
+        assert!(options.validate().is_err());
+        assert!(options.clone().quality(1).validate().is_ok());
+        assert!(options.clone().quality(100).validate().is_ok());
+        // Quality 0 stays valid for the other lossy formats
+        assert!(options.format(ScreenshotFormat::Jpeg).validate().is_ok());
+
+        assert_eq!(ScreenshotFormat::Avif.mime_type(), "image/avif");
+        assert_eq!(ScreenshotFormat::Avif.extension(), "avif");
+    }
 }
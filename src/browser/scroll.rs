@@ -0,0 +1,137 @@
+//! Scrolling elements into view.
+//!
+//! Backs [`BrowserEngine::scroll_into_view`](crate::browser::BrowserEngine::scroll_into_view).
+//! A plain `scrollIntoView()` aligns the element flush with the viewport
+//! edge, where a sticky header easily covers it. [`ScrollAlign`] selects the
+//! alignment and keeps an `offset` of clearance to the aligned edge by
+//! setting a temporary `scroll-margin` on the element. Smooth scrolling is
+//! awaited until the position settles, so the returned bounding box is the
+//! one after scrolling.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::browser::dom::BoundingBox;
+use crate::error::BrowserError;
+
+/// Position of the element along one axis, as in `scrollIntoView()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScrollLogicalPosition {
+    /// Align with the top (left) edge.
+    Start,
+    /// Center in the viewport.
+    #[default]
+    Center,
+    /// Align with the bottom (right) edge.
+    End,
+    /// Scroll only as far as needed to make the element visible.
+    Nearest,
+}
+
+/// How [`BrowserEngine::scroll_into_view`](crate::browser::BrowserEngine::scroll_into_view)
+/// aligns an element.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScrollAlign {
+    /// Vertical alignment.
+    pub block: ScrollLogicalPosition,
+
+    /// Horizontal alignment.
+    pub inline: ScrollLogicalPosition,
+
+    /// Clearance in CSS pixels between the element and the edge it is
+    /// aligned to, e.g. the height of a fixed header with `block: Start`.
+    pub offset: f64,
+
+    /// Animate the scroll like a user would instead of jumping.
+    pub smooth: bool,
+}
+
+impl Default for ScrollAlign {
+    fn default() -> Self {
+        Self {
+            block: ScrollLogicalPosition::Center,
+            inline: ScrollLogicalPosition::Nearest,
+            offset: 0.0,
+            smooth: false,
+        }
+    }
+}
+
+impl ScrollAlign {
+    /// Aligns with the given vertical and horizontal positions.
+    pub fn new(block: ScrollLogicalPosition, inline: ScrollLogicalPosition) -> Self {
+        Self {
+            block,
+            inline,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the clearance to the aligned edge in CSS pixels.
+    pub fn with_offset(mut self, offset: f64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Enables or disables smooth scrolling.
+    pub fn with_smooth(mut self, smooth: bool) -> Self {
+        self.smooth = smooth;
+        self
+    }
+}
+
+/// `call_function` body scrolling `args[0]` into view with `args[1]`.
+///
+/// Returns the element's bounding box after scrolling, or `null` if the
+/// selector matches nothing.
+pub const SCROLL_INTO_VIEW_FUNCTION: &str = r#"const el = document.querySelector(args[0]);
+if (!el) return null;
+const align = args[1];
+const behavior = align.smooth ? 'smooth' : 'instant';
+const style = el.style;
+const saved = [style.scrollMarginTop, style.scrollMarginBottom];
+const margin = align.offset + 'px';
+if (align.block !== 'end') style.scrollMarginTop = margin;
+if (align.block === 'end' || align.block === 'nearest') style.scrollMarginBottom = margin;
+el.scrollIntoView({ block: align.block, inline: align.inline, behavior: behavior });
+style.scrollMarginTop = saved[0];
+style.scrollMarginBottom = saved[1];
+const rect = function() {
+    const r = el.getBoundingClientRect();
+    return { x: r.x, y: r.y, width: r.width, height: r.height };
+};
+if (!align.smooth) return rect();
+return new Promise(function(resolve) {
+    let last = null, stable = 0, frames = 0;
+    const check = function() {
+        const r = rect();
+        const pos = r.x + ',' + r.y;
+        stable = pos === last ? stable + 1 : 0;
+        last = pos;
+        if (stable >= 3 || ++frames > 180) resolve(r);
+        else requestAnimationFrame(check);
+    };
+    requestAnimationFrame(check);
+});"#;
+
+/// Arguments for [`SCROLL_INTO_VIEW_FUNCTION`].
+pub fn scroll_into_view_args(selector: &str, align: &ScrollAlign) -> Vec<Value> {
+    vec![
+        Value::String(selector.to_string()),
+        serde_json::to_value(align).unwrap_or(Value::Null),
+    ]
+}
+
+/// Converts the result of [`SCROLL_INTO_VIEW_FUNCTION`] into a bounding box.
+pub fn parse_scrolled_box(result: Value, selector: &str) -> Result<BoundingBox, BrowserError> {
+    if result.is_null() {
+        return Err(BrowserError::DomQueryFailed {
+            selector: selector.to_string(),
+            reason: "No element matches selector".to_string(),
+        });
+    }
+    serde_json::from_value(result).map_err(|e| BrowserError::ScriptEvaluationFailed {
+        reason: format!("Invalid scroll result: {}", e),
+    })
+}
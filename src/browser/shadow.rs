@@ -0,0 +1,204 @@
+//! Queries into shadow DOM.
+//!
+//! Backs [`DomAccessor::query_shadow`](crate::browser::DomAccessor::query_shadow)
+//! and [`DomAccessor::query_all_shadows`](crate::browser::DomAccessor::query_all_shadows).
+//! `querySelector` stops at shadow roots, so web components are invisible
+//! to it; the page scripts here step into `Element.shadowRoot` explicitly.
+//! Closed shadow roots are not reachable from the page and are skipped.
+//!
+//! The `selector` of a found element chains the hosts it sits in with
+//! `>>>`, e.g. `x-app >>> x-card#main >>> button`.
+
+use serde::Deserialize;
+
+use crate::browser::dom::DomElement;
+use crate::browser::xpath::XPathNode;
+use crate::error::BrowserError;
+
+/// Shadow root nesting [`ShadowQueryOptions`] descends to by default.
+pub const DEFAULT_SHADOW_DEPTH: usize = 5;
+
+/// Options of a recursive shadow DOM query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShadowQueryOptions {
+    /// Deepest shadow root nesting level searched; elements of the document
+    /// itself are level 0. Bounds the walk on pathological trees.
+    pub max_depth: usize,
+}
+
+impl Default for ShadowQueryOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_SHADOW_DEPTH,
+        }
+    }
+}
+
+impl ShadowQueryOptions {
+    /// Sets the deepest shadow root nesting level searched.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+/// An element found by a shadow DOM script.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ShadowMatch {
+    /// Piercing selector of the element (hosts joined by `>>>`).
+    pub selector: String,
+
+    /// Description of the element.
+    #[serde(flatten)]
+    pub node: XPathNode,
+}
+
+/// Result reported by the shadow DOM scripts.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ShadowReport {
+    /// Evaluation error, e.g. for a malformed selector.
+    #[serde(default)]
+    pub error: Option<String>,
+
+    /// Matched elements, hosts and their shadow trees in document order.
+    #[serde(default)]
+    pub elements: Vec<ShadowMatch>,
+}
+
+impl ShadowReport {
+    /// Converts the report into elements, or a typed error naming `selector`.
+    pub fn into_elements(self, selector: &str) -> Result<Vec<DomElement>, BrowserError> {
+        if let Some(reason) = self.error {
+            return Err(BrowserError::DomQueryFailed {
+                selector: selector.to_string(),
+                reason,
+            });
+        }
+        Ok(self
+            .elements
+            .into_iter()
+            .map(|found| found.node.into_element(found.selector))
+            .collect())
+    }
+}
+
+/// Page helpers shared by the shadow DOM scripts: `label` names a host in a
+/// piercing selector, `describe` reports an element.
+const SHADOW_HELPERS: &str = r#"
+    const label = (el) => el.tagName.toLowerCase() + (el.id ? '#' + CSS.escape(el.id) : '');
+    const describe = (el, path) => {
+        const attributes = {};
+        for (const attr of el.attributes) attributes[attr.name] = attr.value;
+        const rect = el.getBoundingClientRect();
+        const style = window.getComputedStyle(el);
+        return {
+            selector: path.concat([selector]).join(' >>> '),
+            tag_name: el.tagName.toLowerCase(),
+            attributes: attributes,
+            text_content: el.textContent || '',
+            inner_html: el.innerHTML || '',
+            bounding_box: { x: rect.x, y: rect.y, width: rect.width, height: rect.height },
+            is_visible: rect.width > 0 && rect.height > 0 && style.visibility !== 'hidden' && style.display !== 'none',
+            is_enabled: !el.disabled,
+            is_focusable: el.tabIndex >= 0,
+            shadow_root: el.shadowRoot !== null
+        };
+    };"#;
+
+/// Builds the page script querying `inner_selector` in the open shadow
+/// roots of all elements matching `host_selector`.
+///
+/// Returns a JSON string deserializable into [`ShadowReport`].
+pub fn shadow_query_script(host_selector: &str, inner_selector: &str) -> String {
+    let host_json = serde_json::to_string(host_selector).unwrap_or_else(|_| "''".to_string());
+    let selector_json = serde_json::to_string(inner_selector).unwrap_or_else(|_| "''".to_string());
+    format!(
+        r#"(function() {{
+    const selector = {selector_json};{SHADOW_HELPERS}
+    try {{
+        const elements = [];
+        for (const host of document.querySelectorAll({host_json})) {{
+            if (!host.shadowRoot) continue;
+            for (const el of host.shadowRoot.querySelectorAll(selector)) {{
+                elements.push(describe(el, [{host_json}]));
+            }}
+        }}
+        return JSON.stringify({{ elements: elements }});
+    }} catch (e) {{
+        return JSON.stringify({{ error: String(e && e.message || e) }});
+    }}
+}})()"#
+    )
+}
+
+/// Builds the page script querying `selector` in the document and every
+/// open shadow root nested at most `options.max_depth` levels deep.
+///
+/// Returns a JSON string deserializable into [`ShadowReport`].
+pub fn shadow_tree_script(selector: &str, options: &ShadowQueryOptions) -> String {
+    let selector_json = serde_json::to_string(selector).unwrap_or_else(|_| "''".to_string());
+    let max_depth = options.max_depth;
+    format!(
+        r#"(function() {{
+    const selector = {selector_json};{SHADOW_HELPERS}
+    const elements = [];
+    const visit = (root, depth, path) => {{
+        for (const el of root.querySelectorAll(selector)) elements.push(describe(el, path));
+        if (depth >= {max_depth}) return;
+        for (const host of root.querySelectorAll('*')) {{
+            if (host.shadowRoot) visit(host.shadowRoot, depth + 1, path.concat([label(host)]));
+        }}
+    }};
+    try {{
+        visit(document, 0, []);
+        return JSON.stringify({{ elements: elements }});
+    }} catch (e) {{
+        return JSON.stringify({{ error: String(e && e.message || e) }});
+    }}
+}})()"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shadow_scripts_embed_selectors() {
+        let script = shadow_query_script("x-card", "button.primary");
+        assert!(script.contains(r#"document.querySelectorAll("x-card")"#));
+        assert!(script.contains(r#"const selector = "button.primary";"#));
+        assert!(script.contains("host.shadowRoot.querySelectorAll(selector)"));
+
+        let script = shadow_tree_script("input", &ShadowQueryOptions::default().max_depth(2));
+        assert!(script.contains("if (depth >= 2) return;"));
+        assert!(script.contains("visit(document, 0, [])"));
+    }
+
+    #[test]
+    fn test_report_into_elements() {
+        let json = r#"{"elements": [
+            {"selector": "x-app >>> x-card#main >>> button", "tag_name": "button",
+             "text_content": "Buy", "bounding_box": null,
+             "is_visible": true, "is_enabled": true, "is_focusable": true},
+            {"selector": "x-app >>> x-card", "tag_name": "x-card",
+             "bounding_box": {"x": 0, "y": 0, "width": 10, "height": 10},
+             "is_visible": true, "is_enabled": true, "is_focusable": false, "shadow_root": true}
+        ]}"#;
+        let report: ShadowReport = serde_json::from_str(json).unwrap();
+        let elements = report.into_elements("button").unwrap();
+
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[0].selector, "x-app >>> x-card#main >>> button");
+        assert_eq!(elements[0].text_content, "Buy");
+        assert!(!elements[0].shadow_root);
+        assert!(elements[1].shadow_root);
+
+        let report: ShadowReport =
+            serde_json::from_str(r#"{"error": "'[' is not a valid selector"}"#).unwrap();
+        assert!(matches!(
+            report.into_elements("["),
+            Err(BrowserError::DomQueryFailed { selector, .. }) if selector == "["
+        ));
+    }
+}
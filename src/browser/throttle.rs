@@ -0,0 +1,167 @@
+//! Network throttling.
+//!
+//! Backs [`BrowserConfig::network_throttle`](crate::browser::BrowserConfig::network_throttle),
+//! which simulates slow connections for testing. A [`NetworkThrottleConfig`]
+//! adds a fixed latency to every request and sends request bodies and
+//! responses at a limited bandwidth; [`NetworkPreset`]s name common mobile
+//! connections.
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Error of requests made while the throttle is offline, as Chromium names it.
+pub const INTERNET_DISCONNECTED: &str = "net::ERR_INTERNET_DISCONNECTED";
+
+/// Bandwidth and latency of a simulated connection.
+///
+/// A bandwidth of 0 leaves no connection at all: the browser is offline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkThrottleConfig {
+    /// Download bandwidth in kilobits per second.
+    pub download_kbps: u32,
+
+    /// Upload bandwidth in kilobits per second.
+    pub upload_kbps: u32,
+
+    /// Latency added to every request in milliseconds.
+    pub latency_ms: u32,
+}
+
+impl NetworkThrottleConfig {
+    /// Creates a throttle with the given bandwidths and latency.
+    pub fn new(download_kbps: u32, upload_kbps: u32, latency_ms: u32) -> Self {
+        Self {
+            download_kbps,
+            upload_kbps,
+            latency_ms,
+        }
+    }
+
+    /// Whether no request can be made.
+    pub fn is_offline(&self) -> bool {
+        self.download_kbps == 0 || self.upload_kbps == 0
+    }
+
+    /// Delay before a request with a body of `upload_bytes` reaches the
+    /// server: the latency plus the upload time.
+    pub fn request_delay(&self, upload_bytes: u64) -> Duration {
+        Duration::from_millis(self.latency_ms as u64) + transfer_time(upload_bytes, self.upload_kbps)
+    }
+
+    /// Time a response of `download_bytes` takes to arrive.
+    pub fn response_delay(&self, download_bytes: u64) -> Duration {
+        transfer_time(download_bytes, self.download_kbps)
+    }
+
+    /// The download bandwidth in bytes per second.
+    pub fn download_bytes_per_sec(&self) -> f64 {
+        self.download_kbps as f64 * 1000.0 / 8.0
+    }
+}
+
+/// Time `bytes` take at `kbps`; zero when offline, as nothing is sent.
+fn transfer_time(bytes: u64, kbps: u32) -> Duration {
+    if kbps == 0 {
+        return Duration::ZERO;
+    }
+    // 1 kbps moves one bit per millisecond
+    Duration::from_micros(bytes.saturating_mul(8_000) / kbps as u64)
+}
+
+/// Predefined connection profiles, named like the Chrome DevTools presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NetworkPreset {
+    /// A good 4G connection.
+    #[serde(rename = "fast-4g")]
+    Fast4G,
+
+    /// A poor 4G connection, as used for mobile performance audits.
+    #[serde(rename = "slow-4g")]
+    Slow4G,
+
+    /// EDGE (2.75G).
+    #[serde(rename = "edge")]
+    Edge,
+
+    /// No connection.
+    #[serde(rename = "offline")]
+    Offline,
+}
+
+impl NetworkPreset {
+    /// All presets, fastest first.
+    pub const ALL: [NetworkPreset; 4] = [Self::Fast4G, Self::Slow4G, Self::Edge, Self::Offline];
+
+    /// The name used in configuration files and on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Fast4G => "fast-4g",
+            Self::Slow4G => "slow-4g",
+            Self::Edge => "edge",
+            Self::Offline => "offline",
+        }
+    }
+
+    /// The bandwidths and latency of the connection.
+    pub fn config(self) -> NetworkThrottleConfig {
+        match self {
+            Self::Fast4G => NetworkThrottleConfig::new(9_000, 1_500, 60),
+            Self::Slow4G => NetworkThrottleConfig::new(1_600, 750, 150),
+            Self::Edge => NetworkThrottleConfig::new(240, 200, 840),
+            Self::Offline => NetworkThrottleConfig::new(0, 0, 0),
+        }
+    }
+}
+
+impl From<NetworkPreset> for NetworkThrottleConfig {
+    fn from(preset: NetworkPreset) -> Self {
+        preset.config()
+    }
+}
+
+impl FromStr for NetworkPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.name() == name)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown network preset '{}', expected one of: fast-4g, slow-4g, edge, offline",
+                    s
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_delays() {
+        let throttle = NetworkThrottleConfig::new(8_000, 800, 100);
+        // 8000 kbps = 1 MB/s
+        assert_eq!(throttle.response_delay(1_000_000), Duration::from_secs(1));
+        assert_eq!(throttle.response_delay(10_000), Duration::from_millis(10));
+        assert_eq!(throttle.request_delay(0), Duration::from_millis(100));
+        assert_eq!(throttle.request_delay(10_000), Duration::from_millis(200));
+        assert_eq!(throttle.download_bytes_per_sec(), 1_000_000.0);
+        assert!(!throttle.is_offline());
+        assert!(NetworkPreset::Offline.config().is_offline());
+    }
+
+    #[test]
+    fn test_preset_names() {
+        for preset in NetworkPreset::ALL {
+            assert_eq!(preset.name().parse::<NetworkPreset>(), Ok(preset));
+            let json = serde_json::to_string(&preset).unwrap();
+            assert_eq!(json, format!("\"{}\"", preset.name()));
+        }
+        assert_eq!("Slow-4G".parse::<NetworkPreset>(), Ok(NetworkPreset::Slow4G));
+        assert!("3g".parse::<NetworkPreset>().is_err());
+    }
+}
@@ -0,0 +1,518 @@
+//! Shared URL matching for interception, blocking, and allowlists.
+//!
+//! [`UrlPattern`] gives every network-facing feature the same matching
+//! semantics. Three kinds of pattern are supported:
+//!
+//! - **Exact** - the URL must equal the pattern (fragments are ignored).
+//! - **Glob** - `*` matches any run of characters. The pattern is split into
+//!   `[scheme://]host[:port][/path[?query]]` and each part is matched
+//!   separately:
+//!   - a missing scheme matches any scheme;
+//!   - a leading `*.` in the host matches the domain itself and any subdomain;
+//!   - a missing port matches any port, `:*` requires an explicit one;
+//!   - a missing path matches any path;
+//!   - the query is only compared when the pattern contains `?`.
+//!
+//!   `?` is a literal character, so query strings can be written as-is.
+//! - **Regex** - a regular expression matched against the full URL.
+//!
+//! Patterns are compiled once. [`UrlPattern::parse`] additionally keeps a
+//! process-wide cache so rule lists that are re-parsed per request do not
+//! recompile their expressions.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ki_browser_standalone::browser::UrlPattern;
+//!
+//! let pattern = UrlPattern::parse("*.example.com/api/*").unwrap();
+//! assert!(pattern.matches("https://cdn.example.com/api/v1?x=1"));
+//! assert!(!pattern.matches("https://example.org/api/v1"));
+//!
+//! let re = UrlPattern::parse("regex:^https://[^/]+/login").unwrap();
+//! assert!(re.matches("https://example.com/login?next=/"));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Prefix selecting regex syntax in [`UrlPattern::parse`].
+pub const REGEX_PREFIX: &str = "regex:";
+
+/// Upper bound on cached compiled patterns before the cache is reset.
+const PATTERN_CACHE_CAPACITY: usize = 1024;
+
+static PATTERN_CACHE: Lazy<Mutex<HashMap<String, UrlPattern>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Errors produced while compiling a [`UrlPattern`].
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum UrlPatternError {
+    /// The pattern string is empty.
+    #[error("URL pattern is empty")]
+    Empty,
+
+    /// The regular expression failed to compile.
+    #[error("Invalid URL regex '{pattern}': {message}")]
+    InvalidRegex { pattern: String, message: String },
+}
+
+/// The kind of matching a [`UrlPattern`] performs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlPatternKind {
+    /// Full-string equality (fragments ignored).
+    Exact,
+    /// Component-wise glob matching.
+    Glob,
+    /// Regular expression over the full URL.
+    Regex,
+}
+
+/// A compiled URL pattern. Cloning is cheap (the compiled form is shared).
+#[derive(Clone)]
+pub struct UrlPattern {
+    source: Arc<str>,
+    matcher: Arc<Matcher>,
+}
+
+enum Matcher {
+    Exact(String),
+    Glob(GlobMatcher),
+    Regex(Regex),
+}
+
+struct GlobMatcher {
+    scheme: Option<Regex>,
+    host: Regex,
+    port: Option<String>,
+    path: Option<Regex>,
+    match_query: bool,
+}
+
+/// A URL split into the components glob patterns match against.
+struct UrlParts<'a> {
+    scheme: Option<&'a str>,
+    host: &'a str,
+    port: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+}
+
+impl UrlPattern {
+    /// Parses a pattern string, choosing the kind from its syntax.
+    ///
+    /// - `regex:<expr>` is a regular expression;
+    /// - anything containing `*`, or without a `://` scheme separator, is a glob;
+    /// - everything else is an exact URL.
+    ///
+    /// Results are cached by source string.
+    pub fn parse(pattern: &str) -> Result<Self, UrlPatternError> {
+        if let Some(cached) = PATTERN_CACHE.lock().get(pattern) {
+            return Ok(cached.clone());
+        }
+
+        let compiled = if let Some(expr) = pattern.strip_prefix(REGEX_PREFIX) {
+            Self::regex(expr)?
+        } else if pattern.contains('*') || !pattern.contains("://") {
+            Self::glob(pattern)?
+        } else {
+            Self::exact(pattern)?
+        };
+
+        let mut cache = PATTERN_CACHE.lock();
+        if cache.len() >= PATTERN_CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(pattern.to_string(), compiled.clone());
+        Ok(compiled)
+    }
+
+    /// Creates an exact-match pattern.
+    pub fn exact(url: &str) -> Result<Self, UrlPatternError> {
+        if url.is_empty() {
+            return Err(UrlPatternError::Empty);
+        }
+        Ok(Self::new(
+            url,
+            Matcher::Exact(strip_fragment(url).to_string()),
+        ))
+    }
+
+    /// Creates a glob pattern (see the module docs for the syntax).
+    pub fn glob(pattern: &str) -> Result<Self, UrlPatternError> {
+        if pattern.is_empty() {
+            return Err(UrlPatternError::Empty);
+        }
+
+        let parts = split_url(strip_fragment(pattern));
+        let scheme = match parts.scheme {
+            Some(s) if s != "*" => Some(glob_to_regex(s, ".*", true)?),
+            _ => None,
+        };
+        let host = if let Some(rest) = parts.host.strip_prefix("*.") {
+            let inner = glob_body(rest, "[^/]*");
+            compile(&format!("(?i)^(?:[^/]*\\.)?{}$", inner), pattern)?
+        } else {
+            glob_to_regex(parts.host, "[^/]*", true)?
+        };
+        let match_query = parts.query.is_some();
+        let path = if match_query {
+            let full = format!("{}?{}", parts.path, parts.query.unwrap_or_default());
+            Some(glob_to_regex(&full, ".*", false)?)
+        } else if parts.path.is_empty() || parts.path == "/*" {
+            None
+        } else {
+            Some(glob_to_regex(parts.path, ".*", false)?)
+        };
+
+        Ok(Self::new(
+            pattern,
+            Matcher::Glob(GlobMatcher {
+                scheme,
+                host,
+                port: parts.port.map(str::to_string),
+                path,
+                match_query,
+            }),
+        ))
+    }
+
+    /// Creates a regex pattern matched against the full URL.
+    pub fn regex(expr: &str) -> Result<Self, UrlPatternError> {
+        if expr.is_empty() {
+            return Err(UrlPatternError::Empty);
+        }
+        Ok(Self::new(expr, Matcher::Regex(compile(expr, expr)?)))
+    }
+
+    fn new(source: &str, matcher: Matcher) -> Self {
+        Self {
+            source: Arc::from(source),
+            matcher: Arc::new(matcher),
+        }
+    }
+
+    /// Returns the pattern source as given (without the `regex:` prefix).
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the kind of matching this pattern performs.
+    pub fn kind(&self) -> UrlPatternKind {
+        match *self.matcher {
+            Matcher::Exact(_) => UrlPatternKind::Exact,
+            Matcher::Glob(_) => UrlPatternKind::Glob,
+            Matcher::Regex(_) => UrlPatternKind::Regex,
+        }
+    }
+
+    /// Returns `true` if `url` matches this pattern.
+    pub fn matches(&self, url: &str) -> bool {
+        match &*self.matcher {
+            Matcher::Exact(expected) => strip_fragment(url) == expected.as_str(),
+            Matcher::Regex(re) => re.is_match(url),
+            Matcher::Glob(glob) => glob.matches(url),
+        }
+    }
+}
+
+impl GlobMatcher {
+    fn matches(&self, url: &str) -> bool {
+        let parts = split_url(strip_fragment(url));
+
+        if let Some(scheme) = &self.scheme {
+            match parts.scheme {
+                Some(s) if scheme.is_match(s) => {}
+                _ => return false,
+            }
+        }
+
+        if !self.host.is_match(parts.host) {
+            return false;
+        }
+
+        let port_matches = match self.port.as_deref() {
+            None => true,
+            Some("*") => parts.port.is_some(),
+            Some(expected) => parts.port.or_else(|| default_port(parts.scheme)) == Some(expected),
+        };
+        if !port_matches {
+            return false;
+        }
+
+        match &self.path {
+            None => true,
+            Some(path) if self.match_query => {
+                let full = format!(
+                    "{}?{}",
+                    path_or_root(parts.path),
+                    parts.query.unwrap_or_default()
+                );
+                path.is_match(&full)
+            }
+            Some(path) => path.is_match(path_or_root(parts.path)),
+        }
+    }
+}
+
+impl fmt::Debug for UrlPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UrlPattern")
+            .field("kind", &self.kind())
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl fmt::Display for UrlPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind() {
+            UrlPatternKind::Regex => write!(f, "{}{}", REGEX_PREFIX, self.source),
+            _ => f.write_str(&self.source),
+        }
+    }
+}
+
+impl PartialEq for UrlPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind() == other.kind() && self.source == other.source
+    }
+}
+
+impl Serialize for UrlPattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for UrlPattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        UrlPattern::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Returns `true` if any pattern in `patterns` matches `url`.
+pub fn matches_any(patterns: &[UrlPattern], url: &str) -> bool {
+    patterns.iter().any(|p| p.matches(url))
+}
+
+fn strip_fragment(url: &str) -> &str {
+    url.split_once('#').map_or(url, |(head, _)| head)
+}
+
+fn path_or_root(path: &str) -> &str {
+    if path.is_empty() {
+        "/"
+    } else {
+        path
+    }
+}
+
+fn default_port(scheme: Option<&str>) -> Option<&'static str> {
+    match scheme.map(str::to_ascii_lowercase).as_deref() {
+        Some("http") | Some("ws") => Some("80"),
+        Some("https") | Some("wss") => Some("443"),
+        _ => None,
+    }
+}
+
+/// Splits a URL (or glob) into scheme, host, port, path, and query.
+fn split_url(url: &str) -> UrlParts<'_> {
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (Some(scheme), rest),
+        None => (None, url),
+    };
+
+    let authority_end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (authority, tail) = rest.split_at(authority_end);
+    // Drop userinfo ("user:pass@host")
+    let authority = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+
+    let (host, port) = if authority.starts_with('[') {
+        // IPv6 literal: "[::1]:8080"
+        match authority.find(']') {
+            Some(end) => {
+                let (host, after) = authority.split_at(end + 1);
+                (host, after.strip_prefix(':'))
+            }
+            None => (authority, None),
+        }
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+
+    let (path, query) = match tail.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (tail, None),
+    };
+
+    UrlParts {
+        scheme,
+        host,
+        port,
+        path,
+        query,
+    }
+}
+
+/// Escapes a glob, turning each `*` into `wildcard`.
+fn glob_body(glob: &str, wildcard: &str) -> String {
+    glob.split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(wildcard)
+}
+
+fn glob_to_regex(
+    glob: &str,
+    wildcard: &str,
+    case_insensitive: bool,
+) -> Result<Regex, UrlPatternError> {
+    let flags = if case_insensitive { "(?i)" } else { "" };
+    compile(&format!("{}^{}$", flags, glob_body(glob, wildcard)), glob)
+}
+
+fn compile(expr: &str, source: &str) -> Result<Regex, UrlPatternError> {
+    Regex::new(expr).map_err(|e| UrlPatternError::InvalidRegex {
+        pattern: source.to_string(),
+        message: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_selects_kind() {
+        assert_eq!(
+            UrlPattern::parse("https://example.com/").unwrap().kind(),
+            UrlPatternKind::Exact
+        );
+        assert_eq!(
+            UrlPattern::parse("*.example.com/*").unwrap().kind(),
+            UrlPatternKind::Glob
+        );
+        assert_eq!(
+            UrlPattern::parse("example.com").unwrap().kind(),
+            UrlPatternKind::Glob
+        );
+        assert_eq!(
+            UrlPattern::parse("regex:^https://").unwrap().kind(),
+            UrlPatternKind::Regex
+        );
+        assert_eq!(UrlPattern::parse(""), Err(UrlPatternError::Empty));
+        assert!(matches!(
+            UrlPattern::parse("regex:("),
+            Err(UrlPatternError::InvalidRegex { .. })
+        ));
+    }
+
+    #[test]
+    fn test_exact_matching() {
+        let p = UrlPattern::exact("https://example.com/page?a=1").unwrap();
+        assert!(p.matches("https://example.com/page?a=1"));
+        assert!(p.matches("https://example.com/page?a=1#section"));
+        assert!(!p.matches("https://example.com/page?a=2"));
+        assert!(!p.matches("https://example.com/page"));
+        assert!(!p.matches("https://example.com:8443/page?a=1"));
+    }
+
+    #[test]
+    fn test_glob_subdomains_and_paths() {
+        let p = UrlPattern::glob("*.example.com/*").unwrap();
+        assert!(p.matches("https://example.com/"));
+        assert!(p.matches("https://cdn.example.com/a/b.js"));
+        assert!(p.matches("http://a.b.example.com"));
+        assert!(p.matches("https://WWW.Example.com/x"));
+        assert!(!p.matches("https://example.org/"));
+        assert!(!p.matches("https://badexample.com/"));
+        assert!(!p.matches("https://example.com.evil.net/"));
+
+        let p = UrlPattern::glob("https://example.com/static/*").unwrap();
+        assert!(p.matches("https://example.com/static/app.css"));
+        assert!(!p.matches("http://example.com/static/app.css"));
+        assert!(!p.matches("https://example.com/other"));
+    }
+
+    #[test]
+    fn test_glob_ports() {
+        // No port in the pattern: any port matches
+        let any = UrlPattern::glob("example.com/*").unwrap();
+        assert!(any.matches("http://example.com:8080/x"));
+        assert!(any.matches("https://example.com/x"));
+
+        let fixed = UrlPattern::glob("example.com:8080/*").unwrap();
+        assert!(fixed.matches("http://example.com:8080/x"));
+        assert!(!fixed.matches("http://example.com:9090/x"));
+        assert!(!fixed.matches("http://example.com/x"));
+
+        // Default ports are implied by the scheme
+        let https = UrlPattern::glob("https://example.com:443/*").unwrap();
+        assert!(https.matches("https://example.com/x"));
+
+        let explicit = UrlPattern::glob("localhost:*").unwrap();
+        assert!(explicit.matches("http://localhost:3000/"));
+        assert!(!explicit.matches("http://localhost/"));
+
+        let v6 = UrlPattern::glob("[::1]:9222/*").unwrap();
+        assert!(v6.matches("http://[::1]:9222/json"));
+    }
+
+    #[test]
+    fn test_glob_query_strings() {
+        // Query ignored unless the pattern has one
+        let path = UrlPattern::glob("example.com/search").unwrap();
+        assert!(path.matches("https://example.com/search?q=rust"));
+        assert!(!path.matches("https://example.com/search/more"));
+
+        // '?' is literal, '*' spans the query
+        let query = UrlPattern::glob("example.com/search?q=*").unwrap();
+        assert!(query.matches("https://example.com/search?q=rust&page=2"));
+        assert!(!query.matches("https://example.com/search?lang=en"));
+        assert!(!query.matches("https://example.com/search"));
+
+        let tracking = UrlPattern::glob("*/collect?*utm_*").unwrap();
+        assert!(tracking.matches("https://t.example.net/collect?id=1&utm_source=x"));
+    }
+
+    #[test]
+    fn test_regex_matching() {
+        let p = UrlPattern::regex(r"^https://[^/]+\.example\.com(:\d+)?/api/v\d+/").unwrap();
+        assert!(p.matches("https://api.example.com/api/v2/users?id=1"));
+        assert!(p.matches("https://api.example.com:8443/api/v1/"));
+        assert!(!p.matches("http://api.example.com/api/v2/"));
+        assert_eq!(
+            p.to_string(),
+            r"regex:^https://[^/]+\.example\.com(:\d+)?/api/v\d+/"
+        );
+    }
+
+    #[test]
+    fn test_parse_is_cached_and_serde_roundtrips() {
+        let a = UrlPattern::parse("*.cached.test/*").unwrap();
+        let b = UrlPattern::parse("*.cached.test/*").unwrap();
+        assert!(Arc::ptr_eq(&a.matcher, &b.matcher));
+
+        let patterns: Vec<UrlPattern> =
+            serde_json::from_str(r#"["*.ads.test/*", "regex:tracker\\.js$"]"#).unwrap();
+        assert!(matches_any(&patterns, "https://x.ads.test/banner.png"));
+        assert!(matches_any(&patterns, "https://cdn.test/tracker.js"));
+        assert!(!matches_any(&patterns, "https://cdn.test/app.js"));
+        let json = serde_json::to_string(&patterns).unwrap();
+        assert_eq!(json, r#"["*.ads.test/*","regex:tracker\\.js$"]"#);
+    }
+}
@@ -321,6 +321,7 @@ fn encode_image(img: &RgbaImage, format: ScreenshotFormat) -> BrowserResult<Vec<
         ScreenshotFormat::Png => ImageFormat::Png,
         ScreenshotFormat::Jpeg => ImageFormat::Jpeg,
         ScreenshotFormat::WebP => ImageFormat::WebP,
+        ScreenshotFormat::Avif => ImageFormat::Avif,
     };
 
     dyn_img.write_to(&mut buf, img_format).map_err(|e| {
@@ -0,0 +1,98 @@
+//! Waits for arbitrary JavaScript predicates.
+//!
+//! Backs [`BrowserEngine::wait_for_function`](crate::browser::BrowserEngine::wait_for_function).
+//! The predicate is re-evaluated through the function call bridge
+//! ([`js_call`](crate::browser::js_call)) every poll interval until it
+//! returns a truthy value, so arguments travel as JSON exactly like with
+//! `call_function`.
+//!
+//! A predicate is either an expression (`window.__appReady === true`) or a
+//! function expression (`(sel) => !!document.querySelector(sel)`), which is
+//! called with the arguments spread. Expressions see the arguments as
+//! `args`. Promises returned by either form are awaited.
+
+use anyhow::Result;
+use serde_json::Value;
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::BrowserError;
+
+/// Shortest interval between two predicate evaluations.
+pub const MIN_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Builds the `call_function` body evaluating `predicate`.
+pub fn predicate_function_body(predicate: &str) -> String {
+    format!(
+        "const predicate = ({predicate}\n);\nreturn typeof predicate === 'function' ? predicate(...args) : predicate;"
+    )
+}
+
+/// Whether `value` is truthy by JavaScript rules.
+///
+/// `undefined` arrives as `null` through the bridge and is falsy as well.
+pub fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|f| f != 0.0 && !f.is_nan()),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(_) | Value::Object(_) => true,
+    }
+}
+
+/// Calls `evaluate` every `poll_interval` until it yields a truthy value.
+///
+/// Returns that value, or [`BrowserError::Timeout`] once `timeout` elapsed
+/// without one. Errors from `evaluate` end the wait immediately.
+pub async fn poll_predicate<F, Fut>(
+    predicate: &str,
+    timeout: Duration,
+    poll_interval: Duration,
+    mut evaluate: F,
+) -> Result<Value>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Value>>,
+{
+    let start = tokio::time::Instant::now();
+    let poll_interval = poll_interval.max(MIN_POLL_INTERVAL);
+    loop {
+        let value = evaluate().await?;
+        if is_truthy(&value) {
+            return Ok(value);
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return Err(BrowserError::Timeout {
+                operation: format!("waiting for function '{}'", predicate),
+                duration_ms: timeout.as_millis() as u64,
+            }
+            .into());
+        }
+        tokio::time::sleep(poll_interval.min(timeout - elapsed)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_is_truthy_follows_js_rules() {
+        for falsy in [json!(null), json!(false), json!(0), json!(0.0), json!("")] {
+            assert!(!is_truthy(&falsy), "{} should be falsy", falsy);
+        }
+        for truthy in [json!(true), json!(-1), json!("0"), json!([]), json!({})] {
+            assert!(is_truthy(&truthy), "{} should be truthy", truthy);
+        }
+    }
+
+    #[test]
+    fn test_predicate_function_body_wraps_expression() {
+        let body = predicate_function_body("window.__appReady === true");
+        assert!(body.starts_with("const predicate = (window.__appReady === true\n);"));
+        assert!(body.contains("predicate(...args)"));
+    }
+}
@@ -0,0 +1,102 @@
+//! Session warmup before a protected navigation.
+//!
+//! Anti-bot checks often reject a visitor whose first request hits a deep
+//! endpoint with no cookies. Real users arrive through the homepage or a
+//! category page first. A warmup visits such pages in order, staying on each
+//! for a human dwell time (optionally moving the mouse around meanwhile), so
+//! the session has its cookies by the time the target URL is loaded.
+
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::error::BrowserError;
+
+/// Number of idle mouse moves spread over one dwell when wandering.
+const WANDER_MOVES: RangeInclusive<usize> = 2..=4;
+
+/// How a warmup visits its pages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarmupOptions {
+    /// Time spent on each page after it has loaded, drawn uniformly.
+    pub dwell: RangeInclusive<Duration>,
+
+    /// Move the mouse to a few random points while dwelling.
+    pub mouse_wander: bool,
+}
+
+impl WarmupOptions {
+    /// Dwells for a time drawn from `dwell` on every page.
+    pub fn new(dwell: RangeInclusive<Duration>) -> Self {
+        Self {
+            dwell,
+            mouse_wander: false,
+        }
+    }
+
+    /// Enables or disables idle mouse movement while dwelling.
+    pub fn with_mouse_wander(mut self, mouse_wander: bool) -> Self {
+        self.mouse_wander = mouse_wander;
+        self
+    }
+
+    /// Rejects a dwell range whose start lies after its end.
+    pub fn validate(&self) -> Result<(), BrowserError> {
+        if self.dwell.start() > self.dwell.end() {
+            return Err(BrowserError::InvalidRequest(format!(
+                "Invalid warmup dwell range: {:?}",
+                self.dwell
+            )));
+        }
+        Ok(())
+    }
+
+    /// Draws the dwell time for one page.
+    pub fn sample_dwell(&self, rng: &mut impl Rng) -> Duration {
+        if self.dwell.start() >= self.dwell.end() {
+            *self.dwell.start()
+        } else {
+            rng.gen_range(self.dwell.clone())
+        }
+    }
+}
+
+/// Random points inside a `width` x `height` viewport for idle mouse moves,
+/// keeping clear of the edges like a resting hand does.
+pub fn wander_points(width: u32, height: u32, rng: &mut impl Rng) -> Vec<(i32, i32)> {
+    let margin_x = width / 10;
+    let margin_y = height / 10;
+    let count = rng.gen_range(WANDER_MOVES);
+    (0..count)
+        .map(|_| {
+            let x = rng.gen_range(margin_x..=width.saturating_sub(margin_x).max(margin_x));
+            let y = rng.gen_range(margin_y..=height.saturating_sub(margin_y).max(margin_y));
+            (x as i32, y as i32)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dwell_and_wander_stay_in_bounds() {
+        let mut rng = rand::thread_rng();
+        let options = WarmupOptions::new(Duration::from_millis(800)..=Duration::from_millis(1200));
+        for _ in 0..50 {
+            let dwell = options.sample_dwell(&mut rng);
+            assert!(options.dwell.contains(&dwell));
+
+            let points = wander_points(1000, 500, &mut rng);
+            assert!(WANDER_MOVES.contains(&points.len()));
+            assert!(points
+                .iter()
+                .all(|&(x, y)| (100..=900).contains(&x) && (50..=450).contains(&y)));
+        }
+        let fixed = WarmupOptions::new(Duration::from_secs(1)..=Duration::from_secs(1));
+        assert_eq!(fixed.sample_dwell(&mut rng), Duration::from_secs(1));
+        assert!(wander_points(0, 0, &mut rng).iter().all(|&p| p == (0, 0)));
+    }
+}
@@ -0,0 +1,181 @@
+//! XPath queries against the page DOM.
+//!
+//! Backs [`DomAccessor::query_xpath`](crate::browser::DomAccessor::query_xpath).
+//! The page script runs `document.evaluate` with an ordered snapshot and
+//! describes every matched element in one JSON report, so a query costs a
+//! single round trip however many nodes it returns. Matched non-element
+//! nodes (text, attributes) are skipped.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::browser::dom::{BoundingBox, DomElement};
+use crate::error::BrowserError;
+
+/// An element as described by [`xpath_script`] and the shadow DOM
+/// scripts of [`shadow`](crate::browser::shadow).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct XPathNode {
+    /// Lowercase tag name.
+    pub tag_name: String,
+
+    /// Element attributes.
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+
+    /// `textContent` of the element.
+    #[serde(default)]
+    pub text_content: String,
+
+    /// `innerHTML` of the element.
+    #[serde(default)]
+    pub inner_html: String,
+
+    /// Viewport-relative box from `getBoundingClientRect`.
+    pub bounding_box: Option<BoundingBox>,
+
+    /// Whether the element is rendered and not hidden by style.
+    pub is_visible: bool,
+
+    /// Whether the element is not disabled.
+    pub is_enabled: bool,
+
+    /// Whether the element can take keyboard focus.
+    pub is_focusable: bool,
+
+    /// Whether the element hosts an open shadow root.
+    #[serde(default)]
+    pub shadow_root: bool,
+}
+
+impl XPathNode {
+    /// Converts the description into an element found by `selector`.
+    pub fn into_element(self, selector: String) -> DomElement {
+        let mut element = DomElement::new(selector, self.tag_name);
+        element.attributes = self.attributes;
+        element.text_content = self.text_content;
+        element.inner_html = self.inner_html;
+        element.bounding_box = self.bounding_box;
+        element.is_visible = self.is_visible;
+        element.is_enabled = self.is_enabled;
+        element.is_focusable = self.is_focusable;
+        element.shadow_root = self.shadow_root;
+        element
+    }
+}
+
+/// Result reported by [`xpath_script`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct XPathReport {
+    /// Evaluation error, e.g. for a malformed expression.
+    #[serde(default)]
+    pub error: Option<String>,
+
+    /// Matched elements in document order.
+    #[serde(default)]
+    pub elements: Vec<XPathNode>,
+}
+
+impl XPathReport {
+    /// Converts the report into elements or a typed error.
+    ///
+    /// Each element's `selector` is an XPath addressing exactly that
+    /// element, `(xpath)[n]`, and `xpath_index` its position in the result.
+    pub fn into_elements(self, xpath: &str) -> Result<Vec<DomElement>, BrowserError> {
+        if let Some(reason) = self.error {
+            return Err(BrowserError::DomQueryFailed {
+                selector: xpath.to_string(),
+                reason,
+            });
+        }
+        Ok(self
+            .elements
+            .into_iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let mut element = node.into_element(format!("({})[{}]", xpath, index + 1));
+                element.xpath_index = Some(index);
+                element
+            })
+            .collect())
+    }
+}
+
+/// Builds the page script evaluating `xpath` against the document.
+///
+/// At most `limit` elements are described (all when `None`). The script
+/// returns a JSON string deserializable into [`XPathReport`].
+pub fn xpath_script(xpath: &str, limit: Option<usize>) -> String {
+    let xpath_json = serde_json::to_string(xpath).unwrap_or_else(|_| "''".to_string());
+    let limit = limit.map_or_else(|| "Infinity".to_string(), |l| l.to_string());
+    format!(
+        r#"(function() {{
+    let snapshot;
+    try {{
+        snapshot = document.evaluate({xpath_json}, document, null, XPathResult.ORDERED_NODE_SNAPSHOT_TYPE, null);
+    }} catch (e) {{
+        return JSON.stringify({{ error: String(e && e.message || e) }});
+    }}
+    const elements = [];
+    for (let i = 0; i < snapshot.snapshotLength && elements.length < {limit}; i++) {{
+        const el = snapshot.snapshotItem(i);
+        if (!el || el.nodeType !== Node.ELEMENT_NODE) continue;
+        const attributes = {{}};
+        for (const attr of el.attributes) attributes[attr.name] = attr.value;
+        const rect = el.getBoundingClientRect();
+        const style = window.getComputedStyle(el);
+        elements.push({{
+            tag_name: el.tagName.toLowerCase(),
+            attributes: attributes,
+            text_content: el.textContent || '',
+            inner_html: el.innerHTML || '',
+            bounding_box: {{ x: rect.x, y: rect.y, width: rect.width, height: rect.height }},
+            is_visible: rect.width > 0 && rect.height > 0 && style.visibility !== 'hidden' && style.display !== 'none',
+            is_enabled: !el.disabled,
+            is_focusable: el.tabIndex >= 0,
+            shadow_root: el.shadowRoot !== null
+        }});
+    }}
+    return JSON.stringify({{ elements: elements }});
+}})()"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xpath_script_embeds_expression() {
+        let script = xpath_script("//table/tr[2]/td[@class=\"price\"]", Some(1));
+        assert!(script.contains(r#"document.evaluate("//table/tr[2]/td[@class=\"price\"]""#));
+        assert!(script.contains("XPathResult.ORDERED_NODE_SNAPSHOT_TYPE"));
+        assert!(script.contains("elements.length < 1"));
+        assert!(xpath_script("//td", None).contains("elements.length < Infinity"));
+    }
+
+    #[test]
+    fn test_report_into_elements() {
+        let json = r#"{"elements": [
+            {"tag_name": "td", "attributes": {"class": "name"}, "text_content": "Bob",
+             "inner_html": "Bob", "bounding_box": {"x": 8, "y": 40, "width": 60, "height": 20},
+             "is_visible": true, "is_enabled": true, "is_focusable": false}
+        ]}"#;
+        let report: XPathReport = serde_json::from_str(json).unwrap();
+        let elements = report.into_elements("//table/tr[2]/td[1]").unwrap();
+
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].selector, "(//table/tr[2]/td[1])[1]");
+        assert_eq!(elements[0].tag_name, "td");
+        assert_eq!(elements[0].text_content, "Bob");
+        assert_eq!(elements[0].xpath_index, Some(0));
+        assert!(elements[0].has_class("name"));
+
+        let report: XPathReport =
+            serde_json::from_str(r#"{"error": "not a valid XPath expression"}"#).unwrap();
+        assert!(matches!(
+            report.into_elements("//td["),
+            Err(BrowserError::DomQueryFailed { selector, .. }) if selector == "//td["
+        ));
+    }
+}
@@ -21,6 +21,8 @@
 //! let settings = settings.merge_with_env();
 //! ```
 
+mod proxy_rules;
 mod settings;
 
+pub use proxy_rules::{pac_data_url, pac_script, select_proxy, ProxyRule};
 pub use settings::{BrowserSettings, CliArgs, ConfigError, ProxyConfig, ProxyType};
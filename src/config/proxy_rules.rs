@@ -0,0 +1,208 @@
+//! Per-origin proxy routing.
+//!
+//! A list of [`ProxyRule`]s sends requests for some hosts through a
+//! dedicated proxy (or directly) while everything else keeps using the
+//! default proxy. Rules are tried in order and the first matching
+//! [`UrlPattern`] wins; [`select_proxy`] implements that resolution.
+//!
+//! Chromium cannot consult Rust per request, so the browser receives the
+//! same rules as a PAC script ([`pac_script`]). Chromium only hands PAC
+//! scripts the origin of `https` requests, so there glob rules are matched
+//! on their host alone; paths, schemes and ports in globs only affect
+//! [`select_proxy`]. Proxy credentials cannot be expressed in PAC and are
+//! requested through the regular proxy authentication flow.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ki_browser_standalone::browser::UrlPattern;
+//! use ki_browser_standalone::config::{select_proxy, ProxyConfig, ProxyRule};
+//!
+//! let rules = vec![
+//!     ProxyRule::direct(UrlPattern::parse("*.cdn.example.com").unwrap()),
+//!     ProxyRule::new(
+//!         UrlPattern::parse("*.shop.example").unwrap(),
+//!         ProxyConfig::new("residential.proxy", 8000),
+//!     ),
+//! ];
+//! let default = ProxyConfig::new("datacenter.proxy", 3128);
+//!
+//! let chosen = select_proxy(&rules, Some(&default), "https://www.shop.example/cart");
+//! assert_eq!(chosen.map(|p| p.host.as_str()), Some("residential.proxy"));
+//! assert!(select_proxy(&rules, Some(&default), "https://img.cdn.example.com/a.png").is_none());
+//! ```
+
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+
+use super::settings::{ConfigError, ProxyConfig, ProxyType};
+use crate::browser::url_pattern::{UrlPattern, UrlPatternKind};
+
+/// Routes requests whose URL matches `url_pattern` through `proxy`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyRule {
+    /// Requests matching this pattern use this rule.
+    pub url_pattern: UrlPattern,
+
+    /// Proxy for matching requests; `None` connects directly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyConfig>,
+}
+
+impl ProxyRule {
+    /// Creates a rule sending matching requests through `proxy`.
+    pub fn new(url_pattern: UrlPattern, proxy: ProxyConfig) -> Self {
+        Self {
+            url_pattern,
+            proxy: Some(proxy),
+        }
+    }
+
+    /// Creates a rule connecting matching requests directly.
+    pub fn direct(url_pattern: UrlPattern) -> Self {
+        Self {
+            url_pattern,
+            proxy: None,
+        }
+    }
+
+    /// Validates the rule's proxy, if any.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        match &self.proxy {
+            Some(proxy) => proxy.validate(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Selects the proxy for a request to `url`.
+///
+/// Returns the proxy of the first rule matching `url`, or `default` if no
+/// rule matches. `None` means the request connects directly.
+pub fn select_proxy<'a>(
+    rules: &'a [ProxyRule],
+    default: Option<&'a ProxyConfig>,
+    url: &str,
+) -> Option<&'a ProxyConfig> {
+    match rules.iter().find(|rule| rule.url_pattern.matches(url)) {
+        Some(rule) => rule.proxy.as_ref(),
+        None => default,
+    }
+}
+
+/// PAC result string for `proxy` (`DIRECT` when `None`).
+fn pac_directive(proxy: Option<&ProxyConfig>) -> String {
+    match proxy {
+        None => "DIRECT".to_string(),
+        Some(proxy) => {
+            let kind = match proxy.proxy_type {
+                ProxyType::Http => "PROXY",
+                ProxyType::Https => "HTTPS",
+                ProxyType::Socks5 => "SOCKS5",
+            };
+            format!("{} {}:{}", kind, proxy.host, proxy.port)
+        }
+    }
+}
+
+/// JavaScript condition matching `pattern` inside `FindProxyForURL`.
+fn pac_condition(pattern: &UrlPattern) -> String {
+    let source = pattern.as_str();
+    let js = |s: &str| serde_json::to_string(s).unwrap_or_else(|_| "\"\"".to_string());
+    match pattern.kind() {
+        UrlPatternKind::Regex => format!("new RegExp({}).test(url)", js(source)),
+        UrlPatternKind::Exact => format!("url === {}", js(source)),
+        UrlPatternKind::Glob => {
+            let rest = source.split_once("://").map_or(source, |(_, rest)| rest);
+            let authority = rest.split(['/', '?']).next().unwrap_or_default();
+            let host = authority
+                .rsplit_once(':')
+                .map_or(authority, |(host, _)| host);
+            let host = host.to_ascii_lowercase();
+            match host.strip_prefix("*.") {
+                Some(domain) => format!(
+                    "(host === {} || shExpMatch(host, {}))",
+                    js(domain),
+                    js(&host)
+                ),
+                None => format!("shExpMatch(host, {})", js(&host)),
+            }
+        }
+    }
+}
+
+/// Builds a PAC script applying `rules` with `default` as fallback.
+pub fn pac_script(rules: &[ProxyRule], default: Option<&ProxyConfig>) -> String {
+    let mut script =
+        String::from("function FindProxyForURL(url, host) {\n    host = host.toLowerCase();\n");
+    for rule in rules {
+        script.push_str(&format!(
+            "    if ({}) return {};\n",
+            pac_condition(&rule.url_pattern),
+            serde_json::to_string(&pac_directive(rule.proxy.as_ref())).unwrap_or_default()
+        ));
+    }
+    script.push_str(&format!(
+        "    return {};\n}}\n",
+        serde_json::to_string(&pac_directive(default)).unwrap_or_default()
+    ));
+    script
+}
+
+/// [`pac_script`] as a `data:` URL for Chromium's `--proxy-pac-url`.
+pub fn pac_data_url(rules: &[ProxyRule], default: Option<&ProxyConfig>) -> String {
+    format!(
+        "data:application/x-ns-proxy-autoconfig;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(pac_script(rules, default))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> Vec<ProxyRule> {
+        vec![
+            ProxyRule::direct(UrlPattern::parse("*.cdn.example.com").unwrap()),
+            ProxyRule::new(
+                UrlPattern::parse("*.shop.example").unwrap(),
+                ProxyConfig::new("residential.proxy", 8000).with_type(ProxyType::Socks5),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_select_proxy_matching_and_fallback() {
+        let rules = rules();
+        let default = ProxyConfig::new("datacenter.proxy", 3128);
+
+        let chosen = select_proxy(&rules, Some(&default), "https://www.shop.example/cart");
+        assert_eq!(chosen.map(|p| p.host.as_str()), Some("residential.proxy"));
+        // A direct rule overrides the default proxy
+        assert!(
+            select_proxy(&rules, Some(&default), "https://img.cdn.example.com/a.png").is_none()
+        );
+        // No rule matches: default proxy, or direct without one
+        let chosen = select_proxy(&rules, Some(&default), "https://other.example/");
+        assert_eq!(chosen.map(|p| p.host.as_str()), Some("datacenter.proxy"));
+        assert!(select_proxy(&rules, None, "https://other.example/").is_none());
+    }
+
+    #[test]
+    fn test_pac_script_routes_rules_in_order() {
+        let default = ProxyConfig::new("datacenter.proxy", 3128);
+        let script = pac_script(&rules(), Some(&default));
+
+        let cdn = script
+            .find(r#"shExpMatch(host, "*.cdn.example.com"))) return "DIRECT";"#)
+            .unwrap();
+        let shop = script
+            .find(r#"shExpMatch(host, "*.shop.example"))) return "SOCKS5 residential.proxy:8000";"#)
+            .unwrap();
+        assert!(cdn < shop);
+        assert!(script.ends_with("    return \"PROXY datacenter.proxy:3128\";\n}\n"));
+        assert!(
+            pac_data_url(&[], None).starts_with("data:application/x-ns-proxy-autoconfig;base64,")
+        );
+    }
+}
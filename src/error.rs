@@ -61,6 +61,24 @@ pub enum BrowserError {
         reason: String,
     },
 
+    /// A navigation redirected more often than allowed.
+    #[error("Too many redirects (limit {max_redirects}): {}", chain.join(" -> "))]
+    TooManyRedirects {
+        /// The configured redirect limit.
+        max_redirects: u32,
+        /// Visited URLs, starting with the requested one.
+        chain: Vec<String>,
+    },
+
+    /// A navigation redirected back to a URL it already visited.
+    #[error("Redirect loop at {url}: {}", chain.join(" -> "))]
+    RedirectLoop {
+        /// The URL visited twice.
+        url: String,
+        /// Visited URLs, starting with the requested one.
+        chain: Vec<String>,
+    },
+
     /// A DOM query (CSS selector, XPath, ...) failed.
     #[error("DOM query failed for selector '{selector}': {reason}")]
     DomQueryFailed {
@@ -101,6 +119,24 @@ pub enum BrowserError {
         duration_ms: u64,
     },
 
+    /// Retrieved page content exceeds the caller's size cap.
+    #[error("Content too large: {size} bytes exceeds limit of {max_bytes} bytes")]
+    ContentTooLarge {
+        /// Size of the content in bytes (UTF-8).
+        size: usize,
+        /// The configured maximum in bytes.
+        max_bytes: usize,
+    },
+
+    /// A script's serialized result exceeds the evaluation's size cap.
+    #[error("Result too large: {size} bytes exceeds limit of {max_bytes} bytes")]
+    ResultTooLarge {
+        /// Size of the serialized result in bytes.
+        size: usize,
+        /// The configured maximum in bytes.
+        max_bytes: usize,
+    },
+
     /// A low-level browser engine error.
     #[error("Browser engine error: {0}")]
     EngineError(String),
@@ -329,12 +365,16 @@ mod tests {
         let variants: Vec<BrowserError> = vec![
             BrowserError::TabNotFound { tab_id: "t".into() },
             BrowserError::NavigationFailed { url: "u".into(), reason: "r".into() },
+            BrowserError::TooManyRedirects { max_redirects: 1, chain: vec!["u".into()] },
+            BrowserError::RedirectLoop { url: "u".into(), chain: vec!["u".into()] },
             BrowserError::DomQueryFailed { selector: "s".into(), reason: "r".into() },
             BrowserError::ScriptEvaluationFailed { reason: "r".into() },
             BrowserError::ScreenshotFailed { reason: "r".into() },
             BrowserError::IpcError("e".into()),
             BrowserError::ConfigError("e".into()),
             BrowserError::Timeout { operation: "o".into(), duration_ms: 1 },
+            BrowserError::ContentTooLarge { size: 2, max_bytes: 1 },
+            BrowserError::ResultTooLarge { size: 2, max_bytes: 1 },
             BrowserError::EngineError("e".into()),
             BrowserError::StealthError("e".into()),
             BrowserError::SessionError("e".into()),
@@ -0,0 +1,420 @@
+//! Auto-waiting assertions for tests that drive a browser engine.
+//!
+//! Integration tests against a page keep writing the same loop: wait until
+//! an element shows up, then check its text. The helpers here do both. Each
+//! one polls until the expectation holds or [`Page::timeout`] runs out, and
+//! fails with [`ExpectationFailed`] describing what was last observed. When
+//! the engine has [`BrowserConfig::screenshot_on_error`](crate::browser::BrowserConfig)
+//! set, the failure also references a screenshot of the tab.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use ki_browser_standalone::browser::{BrowserConfig, BrowserEngine, MockBrowserEngine, UrlPattern};
+//! use ki_browser_standalone::expect::{expect_text, expect_url, Page, TextMatcher};
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let engine = MockBrowserEngine::new(BrowserConfig::default()).await?;
+//! let tab = engine.create_tab("https://shop.example/cart").await?;
+//! let page = Page::new(&engine, tab.id);
+//!
+//! expect_url(&page, &UrlPattern::parse("*.example/cart")?).await?;
+//! expect_text(&page, "#total", TextMatcher::contains("42,00")).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Requires the `expect` feature.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use uuid::Uuid;
+
+use crate::browser::{BrowserEngine, PollBackoff, UrlPattern};
+use crate::error::BrowserError;
+
+/// How long an expectation waits by default.
+pub const DEFAULT_EXPECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A tab of an engine that expectations are checked against.
+pub struct Page<'a, E: BrowserEngine> {
+    engine: &'a E,
+    tab_id: Uuid,
+    timeout: Duration,
+}
+
+impl<'a, E: BrowserEngine> Page<'a, E> {
+    /// Checks expectations against `tab_id` of `engine`.
+    pub fn new(engine: &'a E, tab_id: Uuid) -> Self {
+        Self {
+            engine,
+            tab_id,
+            timeout: DEFAULT_EXPECT_TIMEOUT,
+        }
+    }
+
+    /// Sets how long each expectation waits before it fails.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// How long each expectation waits before it fails.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+/// Condition on a piece of text such as an element's text content.
+#[derive(Debug, Clone)]
+pub enum TextMatcher {
+    /// The whole text equals the value.
+    Exact(String),
+    /// The text contains the value.
+    Contains(String),
+    /// The regular expression matches somewhere in the text.
+    Regex(Regex),
+}
+
+impl TextMatcher {
+    /// Matches text equal to `text`.
+    pub fn exact(text: impl Into<String>) -> Self {
+        TextMatcher::Exact(text.into())
+    }
+
+    /// Matches text containing `text`.
+    pub fn contains(text: impl Into<String>) -> Self {
+        TextMatcher::Contains(text.into())
+    }
+
+    /// Matches text in which the regular expression `pattern` finds a match.
+    pub fn regex(pattern: &str) -> Result<Self, BrowserError> {
+        Regex::new(pattern)
+            .map(TextMatcher::Regex)
+            .map_err(|e| BrowserError::InvalidRequest(format!("Invalid text pattern: {}", e)))
+    }
+
+    /// Whether `text` satisfies the matcher.
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            TextMatcher::Exact(expected) => text == expected,
+            TextMatcher::Contains(expected) => text.contains(expected.as_str()),
+            TextMatcher::Regex(re) => re.is_match(text),
+        }
+    }
+}
+
+impl fmt::Display for TextMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextMatcher::Exact(expected) => write!(f, "{:?}", expected),
+            TextMatcher::Contains(expected) => write!(f, "containing {:?}", expected),
+            TextMatcher::Regex(re) => write!(f, "matching /{}/", re),
+        }
+    }
+}
+
+/// An expectation that did not hold within the page's timeout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectationFailed {
+    /// What was expected, e.g. `text of "#total" containing "42"`.
+    pub expectation: String,
+
+    /// The state observed last, e.g. the element's actual text.
+    pub actual: String,
+
+    /// How long the expectation waited.
+    pub waited: Duration,
+
+    /// Sidecar JSON of the screenshot-on-error capture, if one was written.
+    pub capture: Option<PathBuf>,
+}
+
+impl fmt::Display for ExpectationFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Expected {} within {:?}, but {}",
+            self.expectation, self.waited, self.actual
+        )?;
+        if let Some(capture) = &self.capture {
+            write!(f, " (screenshot: {})", capture.display())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ExpectationFailed {}
+
+/// Waits until the text of the element matching `selector` satisfies
+/// `matcher`.
+///
+/// The text is the element's markup with tags removed and whitespace
+/// collapsed, like `textContent` after trimming.
+pub async fn expect_text<E: BrowserEngine>(
+    page: &Page<'_, E>,
+    selector: &str,
+    matcher: TextMatcher,
+) -> Result<(), ExpectationFailed> {
+    let expectation = format!("text of {:?} {}", selector, matcher);
+    let check = Check::Text { selector, matcher };
+    wait_for(page, expectation, &check).await
+}
+
+/// Waits until the element matching `selector` exists and is not hidden by
+/// `display: none`, `visibility: hidden` or `opacity: 0`.
+pub async fn expect_visible<E: BrowserEngine>(
+    page: &Page<'_, E>,
+    selector: &str,
+) -> Result<(), ExpectationFailed> {
+    let expectation = format!("{:?} to be visible", selector);
+    wait_for(page, expectation, &Check::Visible { selector }).await
+}
+
+/// Waits until the tab's URL matches `pattern`.
+pub async fn expect_url<E: BrowserEngine>(
+    page: &Page<'_, E>,
+    pattern: &UrlPattern,
+) -> Result<(), ExpectationFailed> {
+    let expectation = format!("URL matching {:?}", pattern.as_str());
+    wait_for(page, expectation, &Check::Url(pattern)).await
+}
+
+/// One expectation, evaluated on every poll.
+enum Check<'a> {
+    Text {
+        selector: &'a str,
+        matcher: TextMatcher,
+    },
+    Visible {
+        selector: &'a str,
+    },
+    Url(&'a UrlPattern),
+}
+
+/// Outcome of evaluating a [`Check`] once.
+enum Probe {
+    /// The expectation holds.
+    Pass,
+    /// Not yet; carries what was observed.
+    Pending(String),
+    /// Waiting cannot help, e.g. the tab is gone.
+    Fatal(String),
+}
+
+impl Check<'_> {
+    async fn probe<E: BrowserEngine>(&self, engine: &E, tab_id: Uuid) -> Probe {
+        match self {
+            Check::Text { selector, matcher } => {
+                match engine.get_content_selector(tab_id, selector, None).await {
+                    Ok(html) => {
+                        let text = html_text(&html);
+                        if matcher.matches(&text) {
+                            Probe::Pass
+                        } else {
+                            Probe::Pending(format!("the text was {:?}", text))
+                        }
+                    }
+                    Err(e) => missing_or_fatal(e, selector),
+                }
+            }
+            Check::Visible { selector } => {
+                let properties = ["display", "visibility", "opacity"]
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect();
+                match engine
+                    .get_computed_style(tab_id, selector, properties)
+                    .await
+                {
+                    Ok(style) => {
+                        let value = |name: &str| style.get(name).map(String::as_str);
+                        if value("display") == Some("none") {
+                            Probe::Pending("it had display: none".to_string())
+                        } else if matches!(value("visibility"), Some("hidden" | "collapse")) {
+                            Probe::Pending("it had visibility: hidden".to_string())
+                        } else if value("opacity").and_then(|o| o.parse::<f64>().ok()) == Some(0.0)
+                        {
+                            Probe::Pending("it had opacity: 0".to_string())
+                        } else {
+                            Probe::Pass
+                        }
+                    }
+                    Err(e) => missing_or_fatal(e, selector),
+                }
+            }
+            Check::Url(pattern) => match engine.get_tab(tab_id).await {
+                Ok(Some(tab)) if pattern.matches(&tab.url) => Probe::Pass,
+                Ok(Some(tab)) => Probe::Pending(format!("the URL was {:?}", tab.url)),
+                Ok(None) => Probe::Fatal(format!("tab {} does not exist", tab_id)),
+                Err(e) => Probe::Fatal(format!("the URL could not be read: {}", e)),
+            },
+        }
+    }
+}
+
+/// A missing element is worth waiting for; any other error is not.
+fn missing_or_fatal(error: anyhow::Error, selector: &str) -> Probe {
+    match error.downcast_ref::<BrowserError>() {
+        Some(BrowserError::DomQueryFailed { .. }) => {
+            Probe::Pending(format!("no element matched {:?}", selector))
+        }
+        _ => Probe::Fatal(format!("the check failed: {}", error)),
+    }
+}
+
+/// Polls `check` until it passes or the page's timeout runs out.
+async fn wait_for<E: BrowserEngine>(
+    page: &Page<'_, E>,
+    expectation: String,
+    check: &Check<'_>,
+) -> Result<(), ExpectationFailed> {
+    let start = Instant::now();
+    let backoff = PollBackoff::default();
+    let mut interval = backoff.initial();
+    let actual = loop {
+        match check.probe(page.engine, page.tab_id).await {
+            Probe::Pass => return Ok(()),
+            Probe::Fatal(actual) => break actual,
+            Probe::Pending(actual) if start.elapsed() >= page.timeout => break actual,
+            Probe::Pending(_) => {}
+        }
+        tokio::time::sleep(interval.min(page.timeout.saturating_sub(start.elapsed()))).await;
+        interval = backoff.next(interval);
+    };
+
+    let mut failure = ExpectationFailed {
+        expectation,
+        actual,
+        waited: start.elapsed(),
+        capture: None,
+    };
+    failure.capture = page
+        .engine
+        .capture_failure(page.tab_id, "expect", &failure.to_string())
+        .await
+        .ok()
+        .flatten();
+    Err(failure)
+}
+
+/// Text content of an HTML fragment: tags dropped, common entities
+/// decoded and whitespace collapsed.
+fn html_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                // Tags separate words like the block layout would
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let decoded = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::{BrowserConfig, MockBrowserEngine};
+    use std::collections::HashMap;
+
+    async fn engine_with_tab(config: BrowserConfig) -> (MockBrowserEngine, Uuid) {
+        let engine = MockBrowserEngine::new(config).await.unwrap();
+        let tab = engine
+            .create_tab("https://shop.example/cart")
+            .await
+            .unwrap();
+        (engine, tab.id)
+    }
+
+    #[test]
+    fn test_text_matchers() {
+        assert_eq!(
+            html_text("<p>Total:\n  <b>42&nbsp;&amp;&nbsp;more</b></p>"),
+            "Total: 42 & more"
+        );
+        assert!(TextMatcher::exact("Total: 42").matches("Total: 42"));
+        assert!(!TextMatcher::exact("Total").matches("Total: 42"));
+        assert!(TextMatcher::contains("42").matches("Total: 42"));
+        assert!(TextMatcher::regex(r"\d+ items").unwrap().matches("3 items"));
+        assert!(TextMatcher::regex("(").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_expect_text_waits_for_element() {
+        let (engine, tab_id) = engine_with_tab(BrowserConfig::default()).await;
+        let engine = std::sync::Arc::new(engine);
+        let page = Page::new(engine.as_ref(), tab_id).with_timeout(Duration::from_secs(2));
+
+        // The element appears only after the expectation started waiting
+        let late = engine.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            late.set_element_content(tab_id, "#total", "<span id=\"total\">42,00 €</span>")
+                .await
+                .unwrap();
+        });
+        expect_text(&page, "#total", TextMatcher::contains("42,00"))
+            .await
+            .unwrap();
+
+        let page = page.with_timeout(Duration::from_millis(100));
+        let failure = expect_text(&page, "#total", TextMatcher::exact("0,00"))
+            .await
+            .unwrap_err();
+        assert_eq!(failure.actual, "the text was \"42,00 €\"");
+        assert!(failure.to_string().contains("text of \"#total\" \"0,00\""));
+        assert!(failure.capture.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expect_visible_and_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = BrowserConfig::default().screenshot_on_error(dir.path());
+        let (engine, tab_id) = engine_with_tab(config).await;
+        let page = Page::new(&engine, tab_id).with_timeout(Duration::from_millis(100));
+
+        let hidden = HashMap::from([("display".to_string(), "none".to_string())]);
+        engine
+            .set_computed_style(tab_id, "#dialog", hidden)
+            .await
+            .unwrap();
+        let failure = expect_visible(&page, "#dialog").await.unwrap_err();
+        assert_eq!(failure.actual, "it had display: none");
+        // The failure points at the screenshot-on-error capture
+        let capture = failure.capture.clone().unwrap();
+        assert!(capture.starts_with(dir.path()));
+        assert!(failure.to_string().contains("screenshot: "));
+
+        let shown = HashMap::from([("display".to_string(), "block".to_string())]);
+        engine
+            .set_computed_style(tab_id, "#dialog", shown)
+            .await
+            .unwrap();
+        expect_visible(&page, "#dialog").await.unwrap();
+
+        expect_url(&page, &UrlPattern::parse("*.example/cart").unwrap())
+            .await
+            .unwrap();
+        let failure = expect_url(&page, &UrlPattern::parse("*.example/checkout").unwrap())
+            .await
+            .unwrap_err();
+        assert_eq!(failure.actual, "the URL was \"https://shop.example/cart\"");
+    }
+}
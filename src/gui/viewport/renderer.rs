@@ -150,10 +150,9 @@ fn collect_keyboard_events(
             for event in &i.events {
                 match event {
                     egui::Event::Text(text) => {
-                        for c in text.chars() {
-                            inputs.push(ViewportInput::CharInput {
-                                character: c as u16,
-                            });
+                        // UTF-16 code units, so emoji arrive as surrogate pairs
+                        for character in text.encode_utf16() {
+                            inputs.push(ViewportInput::CharInput { character });
                         }
                     }
                     egui::Event::Key { key, pressed, .. } => {
@@ -607,6 +607,93 @@ pub fn generate_human_path(start: Point, end: Point, num_points: usize) -> Vec<P
     points
 }
 
+/// Overshoot behaviour for [`generate_human_path_with_overshoot`]
+///
+/// Users moving the cursor a long way often fly slightly past the target
+/// and then correct back to it; short, precise moves rarely do.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OvershootConfig {
+    /// Chance (0.0 - 1.0) that a long enough move overshoots
+    pub probability: f64,
+    /// Largest distance travelled past the target, as a fraction of the
+    /// move distance
+    pub magnitude: f64,
+    /// Moves shorter than this (in pixels) never overshoot
+    pub min_distance: f64,
+}
+
+impl Default for OvershootConfig {
+    fn default() -> Self {
+        Self {
+            probability: 0.3,
+            magnitude: 0.06,
+            min_distance: 200.0,
+        }
+    }
+}
+
+/// Generates a human-like path that may overshoot the target and correct back
+///
+/// With probability `overshoot.probability`, moves of at least
+/// `overshoot.min_distance` pixels aim at a point slightly past `end` and
+/// finish with a short corrective segment back to it. Otherwise the path is
+/// the same as [`generate_human_path`]. The path always has `num_points`
+/// points and ends exactly at `end`.
+///
+/// # Example
+///
+/// ```rust
+/// use ki_browser_standalone::input::bezier::{
+///     generate_human_path_with_overshoot, OvershootConfig, Point,
+/// };
+///
+/// let end = Point::new(800.0, 400.0);
+/// let path = generate_human_path_with_overshoot(
+///     Point::new(100.0, 100.0),
+///     end,
+///     60,
+///     &OvershootConfig::default(),
+/// );
+///
+/// assert_eq!(path.len(), 60);
+/// assert_eq!(path[59], end);
+/// ```
+pub fn generate_human_path_with_overshoot(
+    start: Point,
+    end: Point,
+    num_points: usize,
+    overshoot: &OvershootConfig,
+) -> Vec<Point> {
+    let distance = start.distance_to(&end);
+    // The corrective segment needs a few points of its own
+    if num_points < 8
+        || distance < overshoot.min_distance.max(10.0)
+        || rand::random::<f64>() >= overshoot.probability.clamp(0.0, 1.0)
+    {
+        return generate_human_path(start, end, num_points);
+    }
+
+    // Fly past the target along the movement direction, slightly off-axis
+    let angle = start.angle_to(&end);
+    let past = distance * overshoot.magnitude.max(0.0) * (0.5 + rand::random::<f64>() * 0.5);
+    let drift = (rand::random::<f64>() - 0.5) * past;
+    let overshoot_target = Point::new(
+        end.x + past * angle.cos() - drift * angle.sin(),
+        end.y + past * angle.sin() + drift * angle.cos(),
+    );
+
+    let correction_points = (num_points / 6).max(3);
+    let mut points = generate_human_path(start, overshoot_target, num_points - correction_points);
+    // The corrective path starts where the main one ends; skip the duplicate
+    points.extend(
+        generate_human_path(overshoot_target, end, correction_points + 1)
+            .into_iter()
+            .skip(1),
+    );
+
+    points
+}
+
 /// Generates control points that create human-like curves
 fn generate_human_control_points(start: Point, end: Point, distance: f64) -> (Point, Point) {
     let angle = start.angle_to(&end);
@@ -809,6 +896,41 @@ mod tests {
         assert_eq!(path[19].y, end.y);
     }
 
+    #[test]
+    fn test_overshoot_passes_target_on_long_moves() {
+        let start = Point::new(0.0, 0.0);
+        let end = Point::new(600.0, 0.0);
+        let always = OvershootConfig {
+            probability: 1.0,
+            ..Default::default()
+        };
+
+        let path = generate_human_path_with_overshoot(start, end, 60, &always);
+
+        assert_eq!(path.len(), 60);
+        assert_eq!(path[0], start);
+        assert_eq!(path[59], end);
+        let beyond = path.iter().position(|p| p.x > end.x + 1.0);
+        assert!(beyond.is_some_and(|i| i < 59), "path never passed the target");
+    }
+
+    #[test]
+    fn test_overshoot_skips_short_moves() {
+        let start = Point::new(0.0, 0.0);
+        let end = Point::new(80.0, 0.0);
+        let always = OvershootConfig {
+            probability: 1.0,
+            ..Default::default()
+        };
+
+        for _ in 0..20 {
+            let path = generate_human_path_with_overshoot(start, end, 30, &always);
+            assert_eq!(path.len(), 30);
+            assert_eq!(path[29], end);
+            assert!(path.iter().all(|p| p.x <= end.x + 1e-9));
+        }
+    }
+
     #[test]
     fn test_easing_functions() {
         // Test that easing functions return correct boundary values
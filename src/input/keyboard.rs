@@ -389,7 +389,7 @@ impl KeyboardSimulator {
         tokio::time::sleep(delay).await;
 
         // Hold time
-        let hold_time = self.timing.get_click_delay();
+        let hold_time = self.timing.get_key_hold_delay();
         tokio::time::sleep(hold_time).await;
 
         // Key up happens after hold
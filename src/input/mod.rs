@@ -8,6 +8,7 @@
 //! - [`mouse`] - Mouse event simulation with realistic movement patterns
 //! - [`keyboard`] - Keyboard input simulation with modifier key support
 //! - [`bezier`] - Bézier curve implementation for natural mouse paths
+//! - [`replay`] - Replay of recorded human mouse paths
 //! - [`timing`] - Human-like timing utilities based on behavioral studies
 //!
 //! # Example
@@ -32,13 +33,15 @@
 pub mod bezier;
 pub mod keyboard;
 pub mod mouse;
+pub mod replay;
 pub mod timing;
 
 // Re-export commonly used types for convenience
-pub use bezier::{BezierCurve, Point};
+pub use bezier::{BezierCurve, OvershootConfig, Point};
 pub use keyboard::{KeyboardEvent, KeyboardSimulator, Modifier};
-pub use mouse::{MouseButton, MouseEvent, MouseSimulator};
-pub use timing::HumanTiming;
+pub use mouse::{MouseButton, MouseEvent, MouseSimulator, WanderBounds};
+pub use replay::{MovementLibrary, RecordedPath};
+pub use timing::{DelayDistribution, HumanTiming, HumanTimingBuilder, TimingDistributions};
 
 /// Result type for input operations
 pub type InputResult<T> = Result<T, InputError>;
@@ -56,6 +59,8 @@ pub enum InputError {
     DeviceUnavailable { device: String },
     /// A platform-specific error occurred
     PlatformError { message: String },
+    /// A recorded mouse path or movement library is malformed
+    InvalidRecording { reason: String },
     /// The operation was cancelled
     Cancelled,
 }
@@ -78,6 +83,9 @@ impl std::fmt::Display for InputError {
             InputError::PlatformError { message } => {
                 write!(f, "Platform error: {}", message)
             }
+            InputError::InvalidRecording { reason } => {
+                write!(f, "Invalid movement recording: {}", reason)
+            }
             InputError::Cancelled => {
                 write!(f, "Operation was cancelled")
             }
@@ -2,6 +2,10 @@
 //!
 //! This module provides realistic mouse simulation including movement with
 //! Bézier curves, clicks, double-clicks, scrolling, and drag operations.
+//! With a [`MovementLibrary`] attached, movements replay recorded human
+//! paths instead and fall back to Bézier curves when no recording fits.
+//! During think-time, [`MouseSimulator::idle_wander`] keeps the cursor
+//! drifting slowly instead of sitting perfectly still.
 //!
 //! # Example
 //!
@@ -23,10 +27,23 @@
 //! ```
 
 use super::bezier::{generate_human_path, Point};
+use super::replay::MovementLibrary;
 use super::timing::HumanTiming;
 use super::{InputError, InputResult};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Largest distance in pixels of a single idle-wander drift
+pub const WANDER_MAX_STEP_PX: f64 = 40.0;
+
+/// Number of points in an idle-wander drift path
+const WANDER_PATH_POINTS: usize = 6;
+
+/// Idle-wander moves are this many times slower than regular movement steps
+const WANDER_SLOWDOWN: u32 = 3;
+
 /// Represents the different mouse buttons
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MouseButton {
@@ -89,6 +106,8 @@ pub struct MouseConfig {
     pub jitter_intensity: f64,
     /// Screen bounds for validation
     pub screen_bounds: Option<(f64, f64)>,
+    /// Seed for the simulator's RNG; `None` seeds from OS entropy
+    pub rng_seed: Option<u64>,
 }
 
 impl Default for MouseConfig {
@@ -99,10 +118,52 @@ impl Default for MouseConfig {
             add_jitter: true,
             jitter_intensity: 0.3,
             screen_bounds: None,
+            rng_seed: None,
         }
     }
 }
 
+/// Rectangular region the cursor stays in during [`MouseSimulator::idle_wander`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WanderBounds {
+    /// Left edge
+    pub x: f64,
+    /// Top edge
+    pub y: f64,
+    /// Width of the region
+    pub width: f64,
+    /// Height of the region
+    pub height: f64,
+}
+
+impl WanderBounds {
+    /// Creates a region from its top-left corner and size
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns whether `point` lies inside the region (edges included)
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.x
+            && point.y >= self.y
+            && point.x <= self.x + self.width
+            && point.y <= self.y + self.height
+    }
+
+    /// Returns the point of the region closest to `point`
+    pub fn clamp(&self, point: Point) -> Point {
+        Point::new(
+            point.x.clamp(self.x, self.x + self.width),
+            point.y.clamp(self.y, self.y + self.height),
+        )
+    }
+}
+
 /// Simulates realistic human-like mouse movements and interactions
 #[derive(Debug)]
 pub struct MouseSimulator {
@@ -116,6 +177,10 @@ pub struct MouseSimulator {
     event_history: Vec<MouseEvent>,
     /// Maximum events to keep in history
     history_limit: usize,
+    /// Recorded paths to replay instead of generated curves
+    movement_library: Option<Arc<MovementLibrary>>,
+    /// RNG for idle-wander targets (seeded via `MouseConfig::rng_seed`)
+    rng: StdRng,
 }
 
 impl Default for MouseSimulator {
@@ -135,13 +200,7 @@ impl MouseSimulator {
     /// let mouse = MouseSimulator::new();
     /// ```
     pub fn new() -> Self {
-        Self {
-            current_position: Point::new(0.0, 0.0),
-            config: MouseConfig::default(),
-            timing: HumanTiming::default(),
-            event_history: Vec::new(),
-            history_limit: 100,
-        }
+        Self::with_config(MouseConfig::default(), HumanTiming::default())
     }
 
     /// Creates a new MouseSimulator with custom configuration
@@ -164,15 +223,45 @@ impl MouseSimulator {
     /// let mouse = MouseSimulator::with_config(config, HumanTiming::fast());
     /// ```
     pub fn with_config(config: MouseConfig, timing: HumanTiming) -> Self {
+        let rng = match config.rng_seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
         Self {
             current_position: Point::new(0.0, 0.0),
             config,
             timing,
             event_history: Vec::new(),
             history_limit: 100,
+            movement_library: None,
+            rng,
         }
     }
 
+    /// Switches movements to replaying recordings from `library`
+    ///
+    /// Recordings are shared, so one library can back many simulators.
+    /// Movements without a suitable recording still use Bézier curves.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::sync::Arc;
+    /// use ki_browser_standalone::input::{MouseSimulator, MovementLibrary};
+    ///
+    /// let library = MovementLibrary::load("mouse_traces.json").unwrap();
+    /// let mouse = MouseSimulator::new().with_movement_library(Arc::new(library));
+    /// ```
+    pub fn with_movement_library(mut self, library: Arc<MovementLibrary>) -> Self {
+        self.movement_library = Some(library);
+        self
+    }
+
+    /// Replaces or removes the movement library
+    pub fn set_movement_library(&mut self, library: Option<Arc<MovementLibrary>>) {
+        self.movement_library = library;
+    }
+
     /// Returns the current mouse position
     pub fn position(&self) -> Point {
         self.current_position
@@ -185,6 +274,15 @@ impl MouseSimulator {
         self.current_position = Point::new(x, y);
     }
 
+    /// Predicts how long moving to `(x, y)` takes for a target
+    /// `target_width` pixels wide, using Fitts's law
+    ///
+    /// See [`HumanTiming::fitts_duration`].
+    pub fn movement_duration(&self, x: f64, y: f64, target_width: f64) -> Duration {
+        let distance = self.current_position.distance_to(&Point::new(x, y));
+        self.timing.fitts_duration(distance, target_width)
+    }
+
     /// Validates that coordinates are within screen bounds
     fn validate_position(&self, x: f64, y: f64) -> InputResult<()> {
         if let Some((max_x, max_y)) = self.config.screen_bounds {
@@ -206,7 +304,8 @@ impl MouseSimulator {
     /// Moves the mouse to the specified position using a human-like path
     ///
     /// The movement follows a Bézier curve with random control points
-    /// to simulate natural human hand movement.
+    /// to simulate natural human hand movement, or a recorded path if a
+    /// [`MovementLibrary`] is attached and has a recording that fits.
     ///
     /// # Arguments
     ///
@@ -232,7 +331,37 @@ impl MouseSimulator {
     pub async fn move_to(&mut self, x: f64, y: f64) -> InputResult<Vec<Point>> {
         self.validate_position(x, y)?;
 
-        let target = Point::new(x, y);
+        let path = self.plan_path(Point::new(x, y));
+
+        // Simulate movement along the path
+        for point in &path {
+            // Get delay for this movement step
+            let delay = self.timing.get_move_delay();
+            tokio::time::sleep(delay).await;
+
+            self.current_position = *point;
+
+            // Record move event
+            self.record_event(MouseEvent::Move {
+                x: point.x,
+                y: point.y,
+            });
+        }
+
+        Ok(path)
+    }
+
+    /// Computes the path from the current position to `target`
+    fn plan_path(&self, target: Point) -> Vec<Point> {
+        // Recordings already contain real hand tremor, so no jitter is added
+        if let Some(path) = self
+            .movement_library
+            .as_ref()
+            .and_then(|library| library.fit_path(self.current_position, target))
+        {
+            return path;
+        }
+
         let distance = self.current_position.distance_to(&target);
 
         // Calculate number of points based on distance
@@ -250,22 +379,89 @@ impl MouseSimulator {
             add_jitter_to_path(&mut path, self.config.jitter_intensity);
         }
 
-        // Simulate movement along the path
-        for point in &path {
-            // Get delay for this movement step
-            let delay = self.timing.get_move_delay();
-            tokio::time::sleep(delay).await;
-
-            self.current_position = *point;
+        path
+    }
 
-            // Record move event
-            self.record_event(MouseEvent::Move {
-                x: point.x,
-                y: point.y,
+    /// Makes small, slow, aimless movements inside `bounds` for `duration`
+    ///
+    /// Mimics a user scanning the page during think-time: short drifts of
+    /// at most [`WANDER_MAX_STEP_PX`] separated by reaction-time pauses,
+    /// with targets drawn from the simulator's seeded RNG. Never clicks.
+    /// If the cursor starts outside `bounds` it first moves into them; from
+    /// then on every point stays inside.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - How long to wander
+    /// * `bounds` - Region the cursor must stay in
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vec<Point>)` containing the points visited while wandering.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use ki_browser_standalone::input::mouse::{MouseSimulator, WanderBounds};
+    ///
+    /// async fn example() {
+    ///     let mut mouse = MouseSimulator::new();
+    ///     mouse.set_position(400.0, 300.0);
+    ///     let bounds = WanderBounds::new(200.0, 150.0, 600.0, 400.0);
+    ///     mouse.idle_wander(Duration::from_secs(2), bounds).await.unwrap();
+    /// }
+    /// ```
+    pub async fn idle_wander(
+        &mut self,
+        duration: Duration,
+        bounds: WanderBounds,
+    ) -> InputResult<Vec<Point>> {
+        if !(bounds.width >= 0.0 && bounds.height >= 0.0) {
+            return Err(InputError::OutOfBounds {
+                x: bounds.x + bounds.width,
+                y: bounds.y + bounds.height,
             });
         }
+        self.validate_position(bounds.x, bounds.y)?;
+        self.validate_position(bounds.x + bounds.width, bounds.y + bounds.height)?;
 
-        Ok(path)
+        let start = tokio::time::Instant::now();
+        if !bounds.contains(self.current_position) {
+            let entry = bounds.clamp(self.current_position);
+            self.move_to(entry.x, entry.y).await?;
+        }
+
+        let mut visited = Vec::new();
+        while start.elapsed() < duration {
+            let angle = self.rng.gen_range(0.0..std::f64::consts::TAU);
+            let distance = self.rng.gen_range(0.0..=WANDER_MAX_STEP_PX);
+            let target = bounds.clamp(Point::new(
+                self.current_position.x + distance * angle.cos(),
+                self.current_position.y + distance * angle.sin(),
+            ));
+
+            // Bézier control points may bulge past the region, so clamp
+            for point in generate_human_path(self.current_position, target, WANDER_PATH_POINTS) {
+                if start.elapsed() >= duration {
+                    break;
+                }
+                tokio::time::sleep(self.timing.get_move_delay() * WANDER_SLOWDOWN).await;
+
+                let point = bounds.clamp(point);
+                self.current_position = point;
+                self.record_event(MouseEvent::Move {
+                    x: point.x,
+                    y: point.y,
+                });
+                visited.push(point);
+            }
+
+            let remaining = duration.saturating_sub(start.elapsed());
+            tokio::time::sleep(self.timing.get_reaction_delay().min(remaining)).await;
+        }
+
+        Ok(visited)
     }
 
     /// Performs a single click at the current position
@@ -534,6 +730,18 @@ mod tests {
         assert_eq!(mouse.position(), Point::new(100.0, 200.0));
     }
 
+    #[test]
+    fn test_movement_duration_follows_fitts_law() {
+        let mut mouse = MouseSimulator::new();
+        mouse.set_position(100.0, 100.0);
+
+        let big_near = mouse.movement_duration(180.0, 100.0, 120.0);
+        let small_near = mouse.movement_duration(180.0, 100.0, 8.0);
+        let small_far = mouse.movement_duration(1500.0, 900.0, 8.0);
+        assert!(big_near < small_near);
+        assert!(small_near < small_far);
+    }
+
     #[test]
     fn test_validate_position() {
         let mut mouse = MouseSimulator::new();
@@ -544,6 +752,51 @@ mod tests {
         assert!(mouse.validate_position(2000.0, 500.0).is_err());
     }
 
+    #[test]
+    fn test_plan_path_replays_recording_or_falls_back() {
+        let library = MovementLibrary::from_json(
+            r#"{"paths": [{"distance": 100.0, "points": [[0, 0], [0.5, 0.1], [1, 0]]}]}"#,
+        )
+        .unwrap();
+        let mut mouse = MouseSimulator::new().with_movement_library(Arc::new(library));
+        mouse.set_position(10.0, 10.0);
+
+        // Fits the recording: exactly its three points, ending on target
+        let path = mouse.plan_path(Point::new(110.0, 10.0));
+        assert_eq!(path.len(), 3);
+        assert_eq!(path[2], Point::new(110.0, 10.0));
+        assert!((path[1].y - 20.0).abs() < 1e-9);
+
+        // Too far for the recording: generated Bézier path
+        let path = mouse.plan_path(Point::new(1010.0, 10.0));
+        assert!(path.len() >= mouse.config.min_path_points);
+    }
+
+    #[tokio::test]
+    async fn test_idle_wander_stays_in_bounds_without_clicking() {
+        let config = MouseConfig {
+            rng_seed: Some(42),
+            ..Default::default()
+        };
+        let mut mouse = MouseSimulator::with_config(config, HumanTiming::instant());
+        mouse.set_position(110.0, 110.0);
+        let bounds = WanderBounds::new(100.0, 100.0, 50.0, 30.0);
+
+        let visited = mouse
+            .idle_wander(Duration::from_millis(300), bounds)
+            .await
+            .unwrap();
+
+        assert!(!visited.is_empty());
+        assert!(visited.iter().all(|p| bounds.contains(*p)));
+        let history = mouse.event_history();
+        assert!(history.iter().all(|event| match event {
+            MouseEvent::Move { x, y } => bounds.contains(Point::new(*x, *y)),
+            _ => false,
+        }));
+        assert!(bounds.contains(mouse.position()));
+    }
+
     #[test]
     fn test_jitter() {
         let mut path = vec![
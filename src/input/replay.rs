@@ -0,0 +1,273 @@
+//! Replay of recorded human mouse paths
+//!
+//! Synthetic Bézier curves are smooth in a way real hand movements are not.
+//! A [`MovementLibrary`] holds real recorded traces in a normalized frame
+//! (start at `(0, 0)`, end at `(1, 0)`); for a new movement a recording is
+//! picked and mapped onto the actual start and target with a similarity
+//! transform (rotation, uniform scale, translation), which keeps the shape
+//! of the recording intact.
+//!
+//! # File format
+//!
+//! ```json
+//! {
+//!   "paths": [
+//!     { "distance": 420.0, "points": [[0, 0], [0.31, 0.04], [0.78, 0.02], [1, 0]] }
+//!   ]
+//! }
+//! ```
+//!
+//! `distance` is the straight-line length of the original movement in pixels
+//! and limits which movements the recording is used for; without it the
+//! recording fits any distance. Points that are not yet normalized are
+//! normalized on load, so raw screen coordinates can be stored as well.
+
+use super::bezier::Point;
+use super::{InputError, InputResult};
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Maximum factor a recording may be scaled up or down by
+///
+/// Stretching a 50px correction into a 1500px sweep (or the reverse) gives
+/// implausible speeds and wobble amplitudes, so such movements use Bézier.
+pub const MAX_SCALE_FACTOR: f64 = 2.0;
+
+/// A single recorded movement in the normalized frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedPath {
+    /// Straight-line length of the original movement, if known
+    pub distance: Option<f64>,
+    /// Path points; the first is `(0, 0)` and the last `(1, 0)`
+    pub points: Vec<Point>,
+}
+
+impl RecordedPath {
+    /// Normalizes raw points so the path runs from `(0, 0)` to `(1, 0)`
+    ///
+    /// Returns an error for paths with fewer than two points or identical
+    /// start and end points.
+    pub fn new(points: &[Point], distance: Option<f64>) -> InputResult<Self> {
+        let (first, last) = match (points.first(), points.last()) {
+            (Some(first), Some(last)) if points.len() >= 2 => (*first, *last),
+            _ => return Err(invalid("a path needs at least two points")),
+        };
+        let (dx, dy) = (last.x - first.x, last.y - first.y);
+        let span_sq = dx * dx + dy * dy;
+        if !span_sq.is_normal() {
+            return Err(invalid("path start and end coincide"));
+        }
+        if let Some(d) = distance {
+            if !d.is_finite() || d <= 0.0 {
+                return Err(invalid(format!("invalid distance {}", d)));
+            }
+        }
+
+        // Inverse of the similarity transform used in `fit`
+        let points = points
+            .iter()
+            .map(|p| {
+                let (px, py) = (p.x - first.x, p.y - first.y);
+                Point::new((px * dx + py * dy) / span_sq, (py * dx - px * dy) / span_sq)
+            })
+            .collect();
+
+        Ok(Self { distance, points })
+    }
+
+    /// Whether the recording can be stretched to a movement of `distance`
+    pub fn fits_distance(&self, distance: f64) -> bool {
+        match self.distance {
+            Some(recorded) => {
+                let ratio = distance / recorded;
+                (1.0 / MAX_SCALE_FACTOR..=MAX_SCALE_FACTOR).contains(&ratio)
+            }
+            None => true,
+        }
+    }
+
+    /// Maps the recording onto a movement from `start` to `target`
+    ///
+    /// The last point is exactly `target`.
+    pub fn fit(&self, start: Point, target: Point) -> Vec<Point> {
+        let (dx, dy) = (target.x - start.x, target.y - start.y);
+        let mut path: Vec<Point> = self
+            .points
+            .iter()
+            .map(|p| Point::new(start.x + p.x * dx - p.y * dy, start.y + p.x * dy + p.y * dx))
+            .collect();
+        // Avoid floating point drift on the endpoints
+        if let Some(first) = path.first_mut() {
+            *first = start;
+        }
+        if let Some(last) = path.last_mut() {
+            *last = target;
+        }
+        path
+    }
+}
+
+/// JSON representation of a library file
+#[derive(Deserialize)]
+struct LibraryFile {
+    paths: Vec<PathEntry>,
+}
+
+#[derive(Deserialize)]
+struct PathEntry {
+    #[serde(default)]
+    distance: Option<f64>,
+    points: Vec<(f64, f64)>,
+}
+
+/// A collection of recorded human mouse paths
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MovementLibrary {
+    paths: Vec<RecordedPath>,
+}
+
+impl MovementLibrary {
+    /// Creates a library from already normalized recordings
+    pub fn new(paths: Vec<RecordedPath>) -> Self {
+        Self { paths }
+    }
+
+    /// Parses a library from its JSON representation
+    pub fn from_json(json: &str) -> InputResult<Self> {
+        let file: LibraryFile = serde_json::from_str(json)
+            .map_err(|e| invalid(format!("malformed movement library: {}", e)))?;
+
+        let paths = file
+            .paths
+            .into_iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let points: Vec<Point> = entry
+                    .points
+                    .into_iter()
+                    .map(|(x, y)| Point::new(x, y))
+                    .collect();
+                RecordedPath::new(&points, entry.distance).map_err(|e| match e {
+                    InputError::InvalidRecording { reason } => {
+                        invalid(format!("path {}: {}", i, reason))
+                    }
+                    other => other,
+                })
+            })
+            .collect::<InputResult<Vec<_>>>()?;
+
+        Ok(Self { paths })
+    }
+
+    /// Loads a library from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> InputResult<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| invalid(format!("cannot read {}: {}", path.display(), e)))?;
+        Self::from_json(&json)
+    }
+
+    /// Number of recordings in the library
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Whether the library holds no recordings
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Picks a random suitable recording and fits it from `start` to `target`
+    ///
+    /// Returns `None` when no recording fits the movement distance, in which
+    /// case callers fall back to a generated path.
+    pub fn fit_path(&self, start: Point, target: Point) -> Option<Vec<Point>> {
+        let distance = start.distance_to(&target);
+        if distance <= f64::EPSILON {
+            return None;
+        }
+        let candidates: Vec<&RecordedPath> = self
+            .paths
+            .iter()
+            .filter(|p| p.fits_distance(distance))
+            .collect();
+        candidates
+            .choose(&mut rand::thread_rng())
+            .map(|recording| recording.fit(start, target))
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> InputError {
+    InputError::InvalidRecording {
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TINY_LIBRARY: &str = r#"{
+        "paths": [
+            { "distance": 400.0, "points": [[0, 0], [0.3, 0.05], [0.7, -0.02], [0.95, 0.01], [1, 0]] },
+            { "points": [[100, 100], [160, 130], [260, 110], [300, 100]] }
+        ]
+    }"#;
+
+    #[test]
+    fn test_fitted_path_hits_exact_target() {
+        let library = MovementLibrary::from_json(TINY_LIBRARY).unwrap();
+        assert_eq!(library.len(), 2);
+
+        // Raw coordinates were normalized on load
+        let raw = &library.paths[1];
+        assert_eq!(raw.points.first(), Some(&Point::new(0.0, 0.0)));
+        assert_eq!(raw.points.last(), Some(&Point::new(1.0, 0.0)));
+
+        let start = Point::new(37.3, 512.9);
+        let target = Point::new(401.7, 129.1);
+        for _ in 0..20 {
+            let path = library.fit_path(start, target).unwrap();
+            assert_eq!(path.first(), Some(&start));
+            assert_eq!(path.last(), Some(&target));
+        }
+
+        // Interior points keep their relative position: halfway along a
+        // straight recording stays halfway along the movement
+        let straight = RecordedPath::new(
+            &[
+                Point::new(0.0, 0.0),
+                Point::new(0.5, 0.0),
+                Point::new(1.0, 0.0),
+            ],
+            None,
+        )
+        .unwrap();
+        let fitted = straight.fit(Point::new(0.0, 0.0), Point::new(0.0, 200.0));
+        assert!((fitted[1].x - 0.0).abs() < 1e-9 && (fitted[1].y - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_suitable_recording() {
+        let library = MovementLibrary::new(vec![RecordedPath::new(
+            &[Point::new(0.0, 0.0), Point::new(1.0, 0.0)],
+            Some(100.0),
+        )
+        .unwrap()]);
+
+        assert!(library
+            .fit_path(Point::new(0.0, 0.0), Point::new(150.0, 0.0))
+            .is_some());
+        assert!(library
+            .fit_path(Point::new(0.0, 0.0), Point::new(1000.0, 0.0))
+            .is_none());
+        assert!(MovementLibrary::default()
+            .fit_path(Point::new(0.0, 0.0), Point::new(1.0, 1.0))
+            .is_none());
+
+        assert!(
+            MovementLibrary::from_json(r#"{"paths": [{"points": [[5, 5], [5, 5]]}]}"#).is_err()
+        );
+    }
+}
@@ -19,6 +19,12 @@
 //! let timing = HumanTiming::default();
 //! let click_delay = timing.get_click_delay();
 //! let type_delay = timing.get_type_delay();
+//!
+//! // A persona with explicit delay distributions
+//! let persona = HumanTiming::builder()
+//!     .inter_key(140.0, 40.0)
+//!     .key_hold(90.0, 20.0)
+//!     .build();
 //! ```
 
 use std::time::Duration;
@@ -38,6 +44,57 @@ pub struct HumanTiming {
     pub variance: f64,
     /// Profile name for this timing configuration
     pub profile: TimingProfile,
+    /// Explicit delay distributions, overriding the profile's ranges
+    pub distributions: Option<TimingDistributions>,
+}
+
+/// Normal distribution of a delay in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DelayDistribution {
+    /// Mean delay in milliseconds
+    pub mean_ms: f64,
+    /// Standard deviation in milliseconds
+    pub std_dev_ms: f64,
+}
+
+impl DelayDistribution {
+    /// Creates a distribution with the given mean and standard deviation
+    pub fn new(mean_ms: f64, std_dev_ms: f64) -> Self {
+        Self {
+            mean_ms: mean_ms.max(0.0),
+            std_dev_ms: std_dev_ms.max(0.0),
+        }
+    }
+
+    /// Distribution centered in `min_ms..=max_ms`, spread by `variance`
+    ///
+    /// Uses the same mean and standard deviation as [`random_delay_in_range`].
+    fn from_range(min_ms: u64, max_ms: u64, variance: f64) -> Self {
+        let max_ms = max_ms.max(min_ms);
+        Self::new(
+            (min_ms + max_ms) as f64 / 2.0,
+            (max_ms - min_ms) as f64 * variance / 2.0,
+        )
+    }
+
+    /// Samples a delay, clamped to at least 1ms
+    pub fn sample(&self) -> Duration {
+        let delay = normal_random(self.mean_ms, self.std_dev_ms);
+        Duration::from_millis(delay.round().max(1.0) as u64)
+    }
+}
+
+/// Per-action delay distributions of a typing/clicking persona
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimingDistributions {
+    /// How long a key is held down
+    pub key_hold: DelayDistribution,
+    /// Interval between consecutive keystrokes
+    pub inter_key: DelayDistribution,
+    /// Delay between points of a mouse movement path
+    pub mouse_move: DelayDistribution,
+    /// How long a mouse button is held down
+    pub click_hold: DelayDistribution,
 }
 
 /// Predefined timing profiles for different use cases
@@ -83,9 +140,28 @@ impl HumanTiming {
             max_delay_ms: max_delay_ms.max(min_delay_ms),
             variance: variance.clamp(0.0, 1.0),
             profile: TimingProfile::Custom,
+            distributions: None,
         }
     }
 
+    /// Starts building a custom timing from explicit delay distributions
+    ///
+    /// Distributions that are not set default to those of [`normal`](Self::normal).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ki_browser_standalone::input::timing::HumanTiming;
+    ///
+    /// let timing = HumanTiming::builder()
+    ///     .inter_key(120.0, 30.0)
+    ///     .click_hold(100.0, 20.0)
+    ///     .build();
+    /// ```
+    pub fn builder() -> HumanTimingBuilder {
+        HumanTimingBuilder::new()
+    }
+
     /// Creates timing for normal human speed
     ///
     /// Based on average typing speed of ~50 WPM and typical reaction times.
@@ -95,6 +171,7 @@ impl HumanTiming {
             max_delay_ms: 150,
             variance: 0.3,
             profile: TimingProfile::Normal,
+            distributions: None,
         }
     }
 
@@ -107,6 +184,25 @@ impl HumanTiming {
             max_delay_ms: 80,
             variance: 0.25,
             profile: TimingProfile::Fast,
+            distributions: None,
+        }
+    }
+
+    /// Creates timing for a fast touch typist (~90 WPM)
+    ///
+    /// Short, tightly distributed keystroke intervals and key holds.
+    pub fn fast_typist() -> Self {
+        Self {
+            min_delay_ms: 30,
+            max_delay_ms: 90,
+            variance: 0.25,
+            profile: TimingProfile::Fast,
+            distributions: Some(TimingDistributions {
+                key_hold: DelayDistribution::new(65.0, 15.0),
+                inter_key: DelayDistribution::new(75.0, 20.0),
+                mouse_move: DelayDistribution::new(5.0, 2.0),
+                click_hold: DelayDistribution::new(75.0, 15.0),
+            }),
         }
     }
 
@@ -119,6 +215,26 @@ impl HumanTiming {
             max_delay_ms: 300,
             variance: 0.4,
             profile: TimingProfile::Slow,
+            distributions: None,
+        }
+    }
+
+    /// Creates timing for a hunt-and-peck typist (~15 WPM)
+    ///
+    /// Long keystroke intervals with a wide spread, as each key is searched
+    /// for before it is pressed.
+    pub fn slow_hunt_and_peck() -> Self {
+        Self {
+            min_delay_ms: 150,
+            max_delay_ms: 600,
+            variance: 0.5,
+            profile: TimingProfile::Slow,
+            distributions: Some(TimingDistributions {
+                key_hold: DelayDistribution::new(120.0, 30.0),
+                inter_key: DelayDistribution::new(380.0, 120.0),
+                mouse_move: DelayDistribution::new(15.0, 5.0),
+                click_hold: DelayDistribution::new(130.0, 30.0),
+            }),
         }
     }
 
@@ -131,6 +247,7 @@ impl HumanTiming {
             max_delay_ms: 10,
             variance: 0.1,
             profile: TimingProfile::Instant,
+            distributions: None,
         }
     }
 
@@ -142,6 +259,10 @@ impl HumanTiming {
     ///
     /// Duration representing how long to hold the mouse button
     pub fn get_click_delay(&self) -> Duration {
+        if let Some(distributions) = &self.distributions {
+            return distributions.click_hold.sample();
+        }
+
         // Base click duration from research: 70-150ms
         let base_min = 70_u64;
         let base_max = 150_u64;
@@ -161,6 +282,16 @@ impl HumanTiming {
         random_delay_in_range(min, max, self.variance)
     }
 
+    /// Gets a realistic delay for how long a key is held down
+    ///
+    /// Without explicit distributions this matches [`get_click_delay`](Self::get_click_delay).
+    pub fn get_key_hold_delay(&self) -> Duration {
+        match &self.distributions {
+            Some(distributions) => distributions.key_hold.sample(),
+            None => self.get_click_delay(),
+        }
+    }
+
     /// Gets a realistic delay for typing (inter-keystroke interval)
     ///
     /// The inter-keystroke interval varies based on typing proficiency:
@@ -168,10 +299,24 @@ impl HumanTiming {
     /// - Average: 100-200ms
     /// - Novice: 200-400ms
     ///
+    /// The delay is sampled from a normal distribution and only clamped to
+    /// stay positive, so occasional long hesitations still occur.
+    ///
     /// # Returns
     ///
     /// Duration to wait between keystrokes
     pub fn get_type_delay(&self) -> Duration {
+        self.inter_key_distribution().sample()
+    }
+
+    /// Distribution of inter-keystroke intervals
+    ///
+    /// Derived from the profile's range unless explicit distributions are set.
+    pub fn inter_key_distribution(&self) -> DelayDistribution {
+        if let Some(distributions) = &self.distributions {
+            return distributions.inter_key;
+        }
+
         let (min, max) = match self.profile {
             TimingProfile::Normal => (80, 180),
             TimingProfile::Fast => (50, 100),
@@ -180,7 +325,7 @@ impl HumanTiming {
             TimingProfile::Custom => (self.min_delay_ms, self.max_delay_ms),
         };
 
-        random_delay_in_range(min, max, self.variance)
+        DelayDistribution::from_range(min, max, self.variance)
     }
 
     /// Gets a realistic delay for mouse movement between points
@@ -192,6 +337,10 @@ impl HumanTiming {
     ///
     /// Duration to wait between movement points
     pub fn get_move_delay(&self) -> Duration {
+        if let Some(distributions) = &self.distributions {
+            return distributions.mouse_move.sample();
+        }
+
         let (min, max) = match self.profile {
             TimingProfile::Normal => (5, 15),
             TimingProfile::Fast => (2, 8),
@@ -251,6 +400,35 @@ impl HumanTiming {
         random_delay_in_range(min, max, self.variance)
     }
 
+    /// Predicts the total time of a pointing movement with Fitts's law
+    ///
+    /// `MT = a + b * log2(D/W + 1)` (Shannon formulation), with the
+    /// constants of [`fitts_law_delay`] scaled by the profile's speed. A
+    /// smaller target or a longer distance always takes longer. Unlike
+    /// [`fitts_law_delay`] the result is not randomized, so callers can
+    /// spread it over a path and add their own noise.
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - Distance to the target in pixels
+    /// * `target_width` - Width of the target along the movement, in pixels
+    ///
+    /// # Returns
+    ///
+    /// Predicted duration of the whole movement
+    pub fn fitts_duration(&self, distance: f64, target_width: f64) -> Duration {
+        let speed_factor = match self.profile {
+            TimingProfile::Normal | TimingProfile::Custom => 1.0,
+            TimingProfile::Fast => 0.7,
+            TimingProfile::Slow => 1.5,
+            TimingProfile::Instant => 0.1,
+        };
+
+        let id = fitts_index_of_difficulty(distance, target_width);
+        let ms = speed_factor * (FITTS_INTERCEPT_MS + FITTS_SLOPE_MS * id);
+        Duration::from_secs_f64(ms / 1000.0)
+    }
+
     /// Gets the delay for double-click interval
     ///
     /// The interval between clicks in a double-click is typically 50-150ms.
@@ -267,6 +445,74 @@ impl HumanTiming {
     }
 }
 
+/// Builder for [`HumanTiming`] with explicit delay distributions
+///
+/// Reaction and pause delays follow the `Custom` profile, scaled from the
+/// inter-key distribution's mean plus and minus one standard deviation.
+#[derive(Debug, Clone)]
+pub struct HumanTimingBuilder {
+    distributions: TimingDistributions,
+}
+
+impl HumanTimingBuilder {
+    /// Create a new builder with the distributions of [`HumanTiming::normal`]
+    pub fn new() -> Self {
+        let normal = HumanTiming::normal();
+        Self {
+            distributions: TimingDistributions {
+                key_hold: DelayDistribution::from_range(70, 150, normal.variance),
+                inter_key: normal.inter_key_distribution(),
+                mouse_move: DelayDistribution::from_range(5, 15, normal.variance),
+                click_hold: DelayDistribution::from_range(70, 150, normal.variance),
+            },
+        }
+    }
+
+    /// Set the key hold distribution
+    pub fn key_hold(mut self, mean_ms: f64, std_dev_ms: f64) -> Self {
+        self.distributions.key_hold = DelayDistribution::new(mean_ms, std_dev_ms);
+        self
+    }
+
+    /// Set the inter-keystroke interval distribution
+    pub fn inter_key(mut self, mean_ms: f64, std_dev_ms: f64) -> Self {
+        self.distributions.inter_key = DelayDistribution::new(mean_ms, std_dev_ms);
+        self
+    }
+
+    /// Set the distribution of delays between mouse path points
+    pub fn mouse_move(mut self, mean_ms: f64, std_dev_ms: f64) -> Self {
+        self.distributions.mouse_move = DelayDistribution::new(mean_ms, std_dev_ms);
+        self
+    }
+
+    /// Set the mouse button hold distribution
+    pub fn click_hold(mut self, mean_ms: f64, std_dev_ms: f64) -> Self {
+        self.distributions.click_hold = DelayDistribution::new(mean_ms, std_dev_ms);
+        self
+    }
+
+    /// Build the timing
+    pub fn build(self) -> HumanTiming {
+        let inter_key = self.distributions.inter_key;
+        let min_delay_ms = (inter_key.mean_ms - inter_key.std_dev_ms).max(1.0) as u64;
+        let max_delay_ms = (inter_key.mean_ms + inter_key.std_dev_ms).max(1.0) as u64;
+        HumanTiming {
+            min_delay_ms,
+            max_delay_ms: max_delay_ms.max(min_delay_ms),
+            variance: if inter_key.std_dev_ms > 0.0 { 0.5 } else { 0.0 },
+            profile: TimingProfile::Custom,
+            distributions: Some(self.distributions),
+        }
+    }
+}
+
+impl Default for HumanTimingBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Generates a random delay within a range with normal distribution
 ///
 /// Uses the Box-Muller transform to generate normally distributed values,
@@ -344,6 +590,20 @@ pub fn random_delay(min_ms: u64, max_ms: u64) -> Duration {
     Duration::from_millis(delay_ms)
 }
 
+/// Fitts's law intercept in ms, a typical value from research (MacKenzie, 1992)
+const FITTS_INTERCEPT_MS: f64 = 50.0;
+
+/// Fitts's law slope in ms/bit (MacKenzie, 1992)
+const FITTS_SLOPE_MS: f64 = 150.0;
+
+/// Index of difficulty `log2(D/W + 1)` in bits
+fn fitts_index_of_difficulty(distance: f64, target_width: f64) -> f64 {
+    // Avoid division by zero and negative values
+    let width = target_width.max(1.0);
+    let dist = distance.max(0.0);
+    ((dist / width) + 1.0).log2()
+}
+
 /// Generates a delay based on Fitts's Law for pointing movements
 ///
 /// Fitts's Law predicts movement time based on distance and target size:
@@ -364,19 +624,11 @@ pub fn random_delay(min_ms: u64, max_ms: u64) -> Duration {
 ///
 /// Predicted movement time as Duration
 pub fn fitts_law_delay(distance: f64, target_width: f64) -> Duration {
-    // Typical constants from research (MacKenzie, 1992)
-    const A: f64 = 50.0;  // Intercept in ms
-    const B: f64 = 150.0; // Slope in ms/bit
-
-    // Avoid division by zero and negative values
-    let width = target_width.max(1.0);
-    let dist = distance.max(0.0);
-
     // Index of Difficulty (ID)
-    let id = ((dist / width) + 1.0).log2();
+    let id = fitts_index_of_difficulty(distance, target_width);
 
     // Movement time
-    let mt = A + B * id;
+    let mt = FITTS_INTERCEPT_MS + FITTS_SLOPE_MS * id;
 
     // Add some random variance (typically 10-20%)
     let variance = 0.15;
@@ -541,6 +793,71 @@ mod tests {
         assert_eq!(custom.profile, TimingProfile::Custom);
     }
 
+    #[test]
+    fn test_fast_typist_types_faster_than_hunt_and_peck() {
+        let fast = HumanTiming::fast_typist();
+        let slow = HumanTiming::slow_hunt_and_peck();
+        let mean_ms = |timing: &HumanTiming| {
+            (0..2000)
+                .map(|_| timing.get_type_delay().as_millis() as f64)
+                .sum::<f64>()
+                / 2000.0
+        };
+
+        let fast_mean = mean_ms(&fast);
+        let slow_mean = mean_ms(&slow);
+        assert!(fast_mean < slow_mean, "{} >= {}", fast_mean, slow_mean);
+        assert!((fast_mean - 75.0).abs() < 5.0);
+        assert!((slow_mean - 380.0).abs() < 20.0);
+    }
+
+    #[test]
+    fn test_builder_uses_given_distributions() {
+        let timing = HumanTiming::builder()
+            .key_hold(40.0, 0.0)
+            .inter_key(200.0, 0.0)
+            .mouse_move(7.0, 0.0)
+            .click_hold(90.0, 0.0)
+            .build();
+
+        assert_eq!(timing.profile, TimingProfile::Custom);
+        assert_eq!(timing.get_key_hold_delay(), Duration::from_millis(40));
+        assert_eq!(timing.get_type_delay(), Duration::from_millis(200));
+        assert_eq!(timing.get_move_delay(), Duration::from_millis(7));
+        assert_eq!(timing.get_click_delay(), Duration::from_millis(90));
+    }
+
+    #[test]
+    fn test_type_delay_stays_positive() {
+        let timing = HumanTiming::builder().inter_key(5.0, 50.0).build();
+        for _ in 0..200 {
+            assert!(timing.get_type_delay() >= Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_fitts_duration_grows_with_distance_and_shrinking_target() {
+        let timing = HumanTiming::normal();
+
+        let mut previous = Duration::ZERO;
+        for distance in [0.0, 50.0, 200.0, 800.0, 2000.0] {
+            let duration = timing.fitts_duration(distance, 40.0);
+            assert!(duration > previous, "{distance}px: {duration:?} <= {previous:?}");
+            previous = duration;
+        }
+
+        let mut previous = Duration::ZERO;
+        for width in [400.0, 100.0, 40.0, 10.0, 2.0] {
+            let duration = timing.fitts_duration(600.0, width);
+            assert!(duration > previous, "{width}px wide: {duration:?} <= {previous:?}");
+            previous = duration;
+        }
+
+        // A small far button takes longer than a big near one
+        assert!(timing.fitts_duration(900.0, 12.0) > timing.fitts_duration(100.0, 200.0));
+        assert!(HumanTiming::slow().fitts_duration(600.0, 40.0) > timing.fitts_duration(600.0, 40.0));
+    }
+
     #[test]
     fn test_variance_clamping() {
         // Variance should be clamped to 0.0 - 1.0
@@ -97,6 +97,9 @@ pub const FULL_VERSION: &str = concat!(env!("CARGO_PKG_NAME"), " v", env!("CARGO
 /// Unified error types for the entire crate.
 pub mod error;
 
+/// Retry policy and combinator shared by all retrying operations.
+pub mod retry;
+
 /// Browser engine, tab management, DOM access, and screenshot functionality.
 pub mod browser;
 
@@ -119,6 +122,10 @@ pub mod gui;
 /// OCR engine abstraction with Tesseract, PaddleOCR, and Surya backends.
 pub mod ocr;
 
+/// Auto-waiting test assertions against a browser engine (requires `expect` feature).
+#[cfg(feature = "expect")]
+pub mod expect;
+
 // ============================================================================
 // Re-exports for Convenience
 // ============================================================================
@@ -154,7 +161,7 @@ pub use stealth::{
 // API types
 pub use api::{
     ApiServer, AppState, BrowserCommandHandler, BrowserEngineWrapper, BrowserEvent, IpcChannel,
-    IpcCommand, IpcMessage, IpcProcessor, IpcResponse, WebSocketHandler,
+    IpcCommand, IpcMessage, IpcProcessor, IpcProcessorTask, IpcResponse, WebSocketHandler,
 };
 
 // Batch operation types
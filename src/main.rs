@@ -11,7 +11,8 @@ use tracing::{error, info, warn, Level};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 use ki_browser_standalone::{
-    api::{ApiServer, IpcChannel},
+    api::{ApiServer, IpcChannel, IpcProcessorTask, ScreenshotDefaults},
+    browser::NetworkPreset,
     config::{BrowserSettings, CliArgs},
     stealth::StealthConfig, NAME, VERSION,
 };
@@ -19,6 +20,9 @@ use ki_browser_standalone::{
 #[cfg(feature = "cef-browser")]
 use ki_browser_standalone::browser::cef_headless::HeadlessRunner;
 
+/// How long shutdown waits for the IPC processor to finish its current command
+const IPC_SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
 /// ANSI color codes for terminal output
 #[allow(dead_code)]
 mod colors {
@@ -248,6 +252,27 @@ fn build_cli() -> Command {
                 .help("CDP remote debugging port (default: 9222, 0 to disable)")
                 .value_parser(clap::value_parser!(u16)),
         )
+        .arg(
+            Arg::new("screenshot-format")
+                .long("screenshot-format")
+                .value_name("FORMAT")
+                .help("Default screenshot format: png, jpeg, webp, or avif")
+                .value_parser(["png", "jpeg", "webp", "avif"]),
+        )
+        .arg(
+            Arg::new("screenshot-quality")
+                .long("screenshot-quality")
+                .value_name("1-100")
+                .help("Default JPEG/WebP/AVIF screenshot quality")
+                .value_parser(clap::value_parser!(u8)),
+        )
+        .arg(
+            Arg::new("network-throttle")
+                .long("network-throttle")
+                .value_name("PRESET")
+                .help("Simulate a slow connection: fast-4g, slow-4g, edge, or offline")
+                .value_parser(clap::value_parser!(NetworkPreset)),
+        )
         .arg(
             Arg::new("proxy")
                 .long("proxy")
@@ -288,6 +313,10 @@ fn build_cli() -> Command {
                 .help("Start with custom GUI browser (CEF-based)")
                 .action(ArgAction::SetTrue),
         )
+        .subcommand(
+            Command::new("schema")
+                .about("Print the JSON Schema of the IPC/WebSocket protocol and exit"),
+        )
 }
 
 /// Parse CLI arguments into CliArgs struct
@@ -302,6 +331,9 @@ fn parse_cli_args(matches: &clap::ArgMatches) -> CliArgs {
         max_tabs: matches.get_one::<usize>("max-tabs").copied(),
         timeout_ms: matches.get_one::<u64>("timeout").copied(),
         cdp_port: matches.get_one::<u16>("cdp-port").copied(),
+        screenshot_format: matches.get_one::<String>("screenshot-format").cloned(),
+        screenshot_quality: matches.get_one::<u8>("screenshot-quality").copied(),
+        network_throttle: matches.get_one::<NetworkPreset>("network-throttle").copied(),
         ..Default::default()
     };
 
@@ -382,9 +414,21 @@ fn init_stealth(settings: &BrowserSettings) -> Option<StealthConfig> {
         // Sync screen resolution to the actual viewport so that
         // screen.width >= outerWidth >= innerWidth and orientation is correct.
         config.sync_screen_to_viewport(settings.window_width, settings.window_height);
-        if let Err(e) = config.validate() {
+        // Also warns about settings that undermine the disguise (e.g. a proxy
+        // WebRTC can bypass)
+        if let Err(e) = config.validate_with_settings(settings) {
             warn!("Stealth configuration validation warning: {}", e);
         }
+        // A perfect fingerprint is wasted on super-human input
+        #[cfg(feature = "cef-browser")]
+        {
+            use ki_browser_standalone::browser::CefInputConfig;
+            use ki_browser_standalone::input::HumanTiming;
+
+            for warning in CefInputConfig::default().validate_realism(&HumanTiming::default()) {
+                warn!("Input realism warning: {}", warning);
+            }
+        }
         info!(
             "Stealth mode initialized with random fingerprint (screen synced to {}x{} viewport)",
             settings.window_width, settings.window_height
@@ -422,6 +466,13 @@ async fn main() -> Result<()> {
     // Parse CLI arguments
     let matches = build_cli().get_matches();
 
+    // `ki-browser schema` only prints the protocol schema; no logging or browser
+    if matches.subcommand_matches("schema").is_some() {
+        let schema = ki_browser_standalone::api::schema::protocol_schema();
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
     // Get verbosity settings before loading config
     let verbosity = matches.get_count("verbose");
     let quiet = matches.get_flag("quiet");
@@ -453,7 +504,7 @@ async fn main() -> Result<()> {
     if use_gui {
         use std::sync::Arc;
         use ki_browser_standalone::browser::BrowserConfig;
-        use ki_browser_standalone::api::{ApiServer, IpcChannel};
+        use ki_browser_standalone::api::{ApiServer, IpcChannel, IpcProcessorTask, ScreenshotDefaults};
         use ki_browser_standalone::gui::GuiHandle;
 
         info!("Starting GUI browser mode...");
@@ -474,6 +525,14 @@ async fn main() -> Result<()> {
         if let Some(ref proxy) = settings.proxy {
             browser_config = browser_config.proxy(proxy.to_url());
         }
+        browser_config.proxy_rules = settings.proxy_rules.clone();
+        browser_config = browser_config.auto_dismiss_consent(settings.auto_dismiss_consent);
+        if let Some(ref selectors) = settings.consent_selectors {
+            browser_config = browser_config.consent_selectors(selectors.clone());
+        }
+        if let Some(preset) = settings.network_throttle {
+            browser_config = browser_config.with_throttle(preset.config());
+        }
 
         let api_port = settings.api_port;
 
@@ -487,13 +546,16 @@ async fn main() -> Result<()> {
         let engine = Arc::new(engine);
 
         // Start API server in background if enabled.
-        let mut api_server = if settings.api_enabled {
+        let (mut api_server, ipc_task) = if settings.api_enabled {
             let ipc_channel = IpcChannel::new();
             let mut handler = ki_browser_standalone::api::BrowserCommandHandler::with_cef_shared(engine.clone());
 
             // Wire CDP client for privileged JS evaluation (bypasses CSP/Trusted Types)
             if let Some(cdp_port) = settings.cdp_port {
-                let cdp_client = std::sync::Arc::new(ki_browser_standalone::api::cdp_client::CdpClient::new(cdp_port));
+                let cdp_client = std::sync::Arc::new(
+                    ki_browser_standalone::api::cdp_client::CdpClient::new(cdp_port)
+                        .with_retry_policy(engine.config().retry_policy),
+                );
                 handler.set_cdp_client(cdp_client);
                 info!("CDP client enabled on port {} for CSP-bypass evaluation", cdp_port);
             }
@@ -505,14 +567,13 @@ async fn main() -> Result<()> {
                 info!("Stealth: {} sections set for CDP injection", stealth.get_section_scripts().len());
             }
 
-            let ipc_channel_clone = ipc_channel.clone();
-            tokio::spawn(async move {
-                if let Some(mut processor) = ki_browser_standalone::api::IpcProcessor::new(&ipc_channel_clone).await {
-                    handler.run(&mut processor).await;
-                }
+            let ipc_task = IpcProcessorTask::spawn(&ipc_channel, move |mut processor| async move {
+                handler.run(&mut processor).await;
             });
 
             let mut server = ApiServer::new_with_cdp(api_port, ipc_channel, settings.cdp_port);
+            server.set_socket(settings.api_socket.clone(), settings.api_socket_and_tcp);
+            server.state_mut().screenshot_defaults = ScreenshotDefaults::from_settings(&settings);
             // Store GuiHandle in AppState so GUI toggle endpoints can use it.
             server.state_mut().set_gui_handle(gui_handle.clone());
             // Store CefEngine reference for /ws/viewer frame-buffer access.
@@ -524,9 +585,9 @@ async fn main() -> Result<()> {
                 .map_err(|e| anyhow::anyhow!("Failed to start API server: {}", e))?;
 
             info!("API server started on port {} (connected to CEF engine)", api_port);
-            Some(server)
+            (Some(server), Some(ipc_task))
         } else {
-            None
+            (None, None)
         };
 
         // Spawn a background task that forwards SIGINT/SIGTERM to the GUI handle
@@ -549,6 +610,9 @@ async fn main() -> Result<()> {
             info!("GUI closed, stopping API server...");
             server.stop().await;
         }
+        if let Some(task) = ipc_task {
+            task.shutdown(IPC_SHUTDOWN_TIMEOUT).await;
+        }
 
         info!("KI-Browser stopped successfully.");
         return gui_result;
@@ -581,6 +645,14 @@ async fn main() -> Result<()> {
         if let Some(ref proxy) = settings.proxy {
             browser_config = browser_config.proxy(proxy.to_url());
         }
+        browser_config.proxy_rules = settings.proxy_rules.clone();
+        browser_config = browser_config.auto_dismiss_consent(settings.auto_dismiss_consent);
+        if let Some(ref selectors) = settings.consent_selectors {
+            browser_config = browser_config.consent_selectors(selectors.clone());
+        }
+        if let Some(preset) = settings.network_throttle {
+            browser_config = browser_config.with_throttle(preset.config());
+        }
 
         let engine = CefBrowserEngine::new(browser_config).await
             .context("Failed to initialize CEF browser engine")?;
@@ -597,7 +669,7 @@ async fn main() -> Result<()> {
     }
 
     // Start API server if enabled
-    let mut api_server = if settings.api_enabled {
+    let (mut api_server, ipc_task) = if settings.api_enabled {
         info!("Starting API server on port {}...", settings.api_port);
 
         let mut ipc_channel = IpcChannel::new();
@@ -616,7 +688,11 @@ async fn main() -> Result<()> {
             let mut h = ki_browser_standalone::api::BrowserCommandHandler::with_cef_shared(_cef_engine.clone());
             // Wire CDP client for privileged JS evaluation (bypasses CSP/Trusted Types)
             if let Some(cdp_port) = settings.cdp_port {
-                let cdp_client = std::sync::Arc::new(ki_browser_standalone::api::cdp_client::CdpClient::new(cdp_port));
+                use ki_browser_standalone::browser::BrowserEngine;
+                let cdp_client = std::sync::Arc::new(
+                    ki_browser_standalone::api::cdp_client::CdpClient::new(cdp_port)
+                        .with_retry_policy(_cef_engine.config().retry_policy),
+                );
                 h.set_cdp_client(cdp_client);
                 info!("CDP client enabled on port {} for CSP-bypass evaluation", cdp_port);
             }
@@ -631,18 +707,19 @@ async fn main() -> Result<()> {
         #[cfg(not(feature = "cef-browser"))]
         let handler = ki_browser_standalone::api::BrowserCommandHandler::new();
 
-        // Start IPC processor in background
-        let ipc_channel_clone = ipc_channel.clone();
-        tokio::spawn(async move {
-            if let Some(mut processor) = ki_browser_standalone::api::IpcProcessor::new(&ipc_channel_clone).await {
-                handler.run(&mut processor).await;
-            }
+        // Start IPC processor in background; joined during shutdown below
+        let ipc_task = IpcProcessorTask::spawn(&ipc_channel, move |mut processor| async move {
+            handler.run(&mut processor).await;
         });
 
         let mut server = ApiServer::new_with_cdp(settings.api_port, ipc_channel, settings.cdp_port);
 
         // Apply configurable bind address (KI_BROWSER_API_BIND / api_bind).
         server.set_bind(settings.api_bind.clone());
+        // Optional Unix domain socket (KI_BROWSER_API_SOCKET / api_socket).
+        server.set_socket(settings.api_socket.clone(), settings.api_socket_and_tcp);
+        // Format/quality for screenshot requests that omit them.
+        server.state_mut().screenshot_defaults = ScreenshotDefaults::from_settings(&settings);
 
         // Open the encrypted, persistent session store for login-inheritance.
         // Path: <profile_path-parent or /app/data>/sessions/ (survives restart
@@ -753,10 +830,10 @@ async fn main() -> Result<()> {
         );
         println!();
 
-        Some(server)
+        (Some(server), Some(ipc_task))
     } else {
         info!("API server disabled");
-        None
+        (None, None)
     };
 
     // Wait for shutdown signal
@@ -777,6 +854,10 @@ async fn main() -> Result<()> {
         info!("Stopping API server...");
         server.stop().await;
     }
+    if let Some(task) = ipc_task {
+        info!("Stopping IPC processor...");
+        task.shutdown(IPC_SHUTDOWN_TIMEOUT).await;
+    }
 
     // Browser engine cleanup is handled by HeadlessRunner/CefBrowserEngine Drop impls
 
@@ -831,6 +912,15 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_cli_schema_subcommand() {
+        let matches = build_cli()
+            .try_get_matches_from(["ki-browser", "schema"])
+            .unwrap();
+
+        assert!(matches.subcommand_matches("schema").is_some());
+    }
+
     #[test]
     fn test_parse_cli_args() {
         let cmd = build_cli();
@@ -0,0 +1,314 @@
+//! Retry policy shared across ki-browser-standalone.
+//!
+//! Operations that can fail transiently (CDP connections, target discovery
+//! after tab creation, ...) retry through [`retry`] with a [`RetryPolicy`]
+//! instead of hand-rolled loops, so attempt limits, backoff and jitter are
+//! tuned in one place and behave the same everywhere.
+//!
+//! Retrying is only safe for idempotent operations. IPC commands, for
+//! example, are not retried: a timed-out command may still execute on the
+//! browser thread, and sending it again would run it twice.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ki_browser_standalone::retry::{retry, RetryPolicy};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), String> {
+//! let policy = RetryPolicy::new(5).with_base_delay(Duration::from_millis(50));
+//! let body = retry(&policy, || async { fetch().await }).await?;
+//! # Ok(())
+//! # }
+//! # async fn fetch() -> Result<String, String> { Ok(String::new()) }
+//! ```
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::future::Future;
+use std::time::Duration;
+
+/// How often and how fast a failing operation is retried.
+///
+/// The delay before retry `n` (1-based) is
+/// `base_delay * backoff_factor^(n - 1)`, scaled by a random factor in
+/// `1 ± jitter` and capped at `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first (at least 1).
+    pub max_attempts: u32,
+
+    /// Delay before the first retry.
+    pub base_delay: Duration,
+
+    /// Upper bound for any single delay.
+    pub max_delay: Duration,
+
+    /// Growth factor applied to the delay after each retry (>= 1.0).
+    pub backoff_factor: f64,
+
+    /// Relative random spread of each delay, in `0.0..=1.0`.
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            backoff_factor: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates the default policy with `max_attempts` attempts.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a policy with a constant `delay` between attempts and no jitter.
+    pub fn fixed(max_attempts: u32, delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            base_delay: delay,
+            max_delay: delay,
+            backoff_factor: 1.0,
+            jitter: 0.0,
+        }
+    }
+
+    /// Creates a policy that runs the operation exactly once.
+    pub fn no_retry() -> Self {
+        Self::new(1)
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn with_base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Sets the upper bound for any single delay.
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Sets the backoff growth factor.
+    pub fn with_backoff_factor(mut self, factor: f64) -> Self {
+        self.backoff_factor = factor;
+        self
+    }
+
+    /// Sets the relative jitter.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Delay before retry `retry` (1-based) without jitter.
+    ///
+    /// Factors below 1.0 are treated as 1.0 (constant delay).
+    pub fn backoff_delay(&self, retry: u32) -> Duration {
+        let factor = if self.backoff_factor.is_finite() {
+            self.backoff_factor.max(1.0)
+        } else {
+            1.0
+        };
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let secs = self.base_delay.as_secs_f64() * factor.powi(exponent);
+        let cap = self.max_delay.as_secs_f64();
+        Duration::from_secs_f64(secs.min(cap))
+    }
+
+    /// Delay before retry `retry` (1-based) with jitter drawn from `rng`.
+    ///
+    /// The result stays within `backoff_delay(retry) * (1 ± jitter)` and
+    /// never exceeds `max_delay`.
+    pub fn delay_for<R: Rng + ?Sized>(&self, retry: u32, rng: &mut R) -> Duration {
+        let delay = self.backoff_delay(retry);
+        let jitter = if self.jitter.is_finite() {
+            self.jitter.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        if jitter == 0.0 {
+            return delay;
+        }
+        let scale = 1.0 + rng.gen_range(-jitter..=jitter);
+        Duration::from_secs_f64(delay.as_secs_f64() * scale).min(self.max_delay)
+    }
+}
+
+/// Runs `op` until it succeeds or `policy.max_attempts` attempts failed.
+///
+/// Sleeps between attempts as described by [`RetryPolicy`] and returns the
+/// error of the last attempt on exhaustion.
+pub async fn retry<T, E, F, Fut>(policy: &RetryPolicy, op: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut rng = StdRng::from_entropy();
+    retry_with_sleep(policy, &mut rng, tokio::time::sleep, op).await
+}
+
+/// Like [`retry`], but with an explicit random source and sleep function.
+///
+/// Lets callers (and tests) drive retries with a seeded RNG and a virtual
+/// clock instead of real time.
+pub async fn retry_with_sleep<T, E, F, Fut, S, SFut, R>(
+    policy: &RetryPolicy,
+    rng: &mut R,
+    mut sleep: S,
+    mut op: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    S: FnMut(Duration) -> SFut,
+    SFut: Future<Output = ()>,
+    R: Rng + ?Sized,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= max_attempts => return Err(e),
+            Err(_) => {
+                sleep(policy.delay_for(attempt, rng)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    /// Records requested sleeps instead of waiting.
+    #[derive(Default)]
+    struct VirtualClock {
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl VirtualClock {
+        fn sleep(&self, delay: Duration) -> std::future::Ready<()> {
+            self.sleeps.borrow_mut().push(delay);
+            std::future::ready(())
+        }
+
+        fn elapsed(&self) -> Duration {
+            self.sleeps.borrow().iter().sum()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_counts_attempts() {
+        let policy = RetryPolicy::fixed(4, Duration::from_millis(10));
+        let mut rng = StdRng::seed_from_u64(1);
+
+        // Succeeds on the third attempt after two retries
+        let clock = VirtualClock::default();
+        let calls = Cell::new(0);
+        let result: Result<u32, &str> = retry_with_sleep(
+            &policy,
+            &mut rng,
+            |d| clock.sleep(d),
+            || {
+                calls.set(calls.get() + 1);
+                let n = calls.get();
+                async move {
+                    if n < 3 {
+                        Err("transient")
+                    } else {
+                        Ok(n)
+                    }
+                }
+            },
+        )
+        .await;
+        assert_eq!(result, Ok(3));
+        assert_eq!(clock.sleeps.borrow().len(), 2);
+        assert_eq!(clock.elapsed(), Duration::from_millis(20));
+
+        // Gives up after max_attempts and returns the last error
+        let clock = VirtualClock::default();
+        let calls = Cell::new(0);
+        let result: Result<(), u32> = retry_with_sleep(
+            &policy,
+            &mut rng,
+            |d| clock.sleep(d),
+            || {
+                calls.set(calls.get() + 1);
+                let n = calls.get();
+                async move { Err(n) }
+            },
+        )
+        .await;
+        assert_eq!(result, Err(4));
+        assert_eq!(clock.sleeps.borrow().len(), 3);
+
+        // No retries: a single attempt, no sleep
+        let clock = VirtualClock::default();
+        let result: Result<(), ()> = retry_with_sleep(
+            &RetryPolicy::no_retry(),
+            &mut rng,
+            |d| clock.sleep(d),
+            || async { Err(()) },
+        )
+        .await;
+        assert!(result.is_err());
+        assert!(clock.sleeps.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retry_backoff_grows_to_max_delay() {
+        let policy = RetryPolicy::new(6)
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(500))
+            .with_backoff_factor(2.0)
+            .with_jitter(0.0);
+        let mut rng = StdRng::seed_from_u64(2);
+        let clock = VirtualClock::default();
+
+        let result: Result<(), ()> =
+            retry_with_sleep(&policy, &mut rng, |d| clock.sleep(d), || async { Err(()) }).await;
+        assert!(result.is_err());
+        let millis: Vec<u128> = clock
+            .sleeps
+            .borrow()
+            .iter()
+            .map(|d| d.as_millis())
+            .collect();
+        assert_eq!(millis, [100, 200, 400, 500, 500]);
+        assert_eq!(clock.elapsed(), Duration::from_millis(1700));
+    }
+
+    #[test]
+    fn test_retry_jitter_stays_in_bounds() {
+        let policy = RetryPolicy::new(3)
+            .with_base_delay(Duration::from_millis(1000))
+            .with_max_delay(Duration::from_millis(1100))
+            .with_jitter(0.25);
+        let mut rng = StdRng::seed_from_u64(3);
+
+        let delays: Vec<Duration> = (0..500).map(|_| policy.delay_for(1, &mut rng)).collect();
+        assert!(delays.iter().all(|d| *d >= Duration::from_millis(750)));
+        assert!(delays.iter().all(|d| *d <= Duration::from_millis(1100)));
+        // Jitter actually spreads the delays
+        assert!(delays.iter().any(|d| *d < Duration::from_millis(900)));
+
+        let still = policy.with_jitter(0.0);
+        assert_eq!(still.delay_for(1, &mut rng), Duration::from_millis(1000));
+    }
+}
@@ -0,0 +1,127 @@
+//! JavaScript API Removal
+//!
+//! This module removes selected browser APIs entirely instead of spoofing
+//! them, to shrink the fingerprinting surface a page can probe.
+//!
+//! # Removal vs. spoofing
+//!
+//! Removal is itself observable: `'getBattery' in navigator` is a single
+//! property check, and an API that a real Chrome on the claimed platform
+//! always has is a stronger tell than a slightly unusual spoofed value. Each
+//! [`ApiSurface`] is therefore removed the way real browsers lack it:
+//!
+//! - APIs that are simply absent in other browsers or configurations
+//!   (Battery in Firefox/Safari, WebGPU on Linux Chrome, Web Bluetooth/USB
+//!   behind enterprise policy) are deleted from their prototype, together
+//!   with their interface objects.
+//! - WebGL is not deleted: Chrome keeps the WebGL constructors even when the
+//!   GPU is blocklisted and only `getContext()` returns `null`, so that is
+//!   what the removal does.
+//! - `navigator.mediaDevices` is deleted as in insecure (HTTP) contexts.
+//!
+//! Prefer spoofing for APIs the claimed profile is expected to expose and
+//! only remove surfaces whose absence the profile can plausibly explain.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use ki_browser_standalone::stealth::api_surface::{disable_apis_script, ApiSurface};
+//!
+//! let js = disable_apis_script(&[ApiSurface::Battery, ApiSurface::WebGpu]);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// A browser API that can be removed from pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApiSurface {
+    /// WebGL and WebGL2 contexts (`getContext()` returns `null`)
+    WebGl,
+    /// WebGPU (`navigator.gpu`)
+    WebGpu,
+    /// Battery Status API (`navigator.getBattery`)
+    Battery,
+    /// Web Bluetooth (`navigator.bluetooth`)
+    Bluetooth,
+    /// WebUSB (`navigator.usb`)
+    Usb,
+    /// Media capture and device enumeration (`navigator.mediaDevices`)
+    MediaDevices,
+}
+
+impl ApiSurface {
+    /// JavaScript removing this API from the page
+    pub fn removal_script(self) -> &'static str {
+        match self {
+            ApiSurface::WebGl => {
+                r#"
+    // WebGL: behave like a blocklisted GPU, the constructors stay
+    (function() {
+        const webglContexts = ['webgl', 'webgl2', 'experimental-webgl'];
+        const wrap = (proto) => {
+            if (!proto || !proto.getContext) return;
+            const originalGetContext = proto.getContext;
+            proto.getContext = function(type, ...args) {
+                if (webglContexts.includes(String(type).toLowerCase())) return null;
+                return originalGetContext.call(this, type, ...args);
+            };
+        };
+        if (typeof HTMLCanvasElement !== 'undefined') wrap(HTMLCanvasElement.prototype);
+        if (typeof OffscreenCanvas !== 'undefined') wrap(OffscreenCanvas.prototype);
+    })();
+"#
+            }
+            ApiSurface::WebGpu => {
+                r#"
+    // WebGPU
+    try { delete Navigator.prototype.gpu; delete navigator.gpu; } catch (e) {}
+    try { delete window.GPU; delete window.GPUAdapter; delete window.GPUDevice; } catch (e) {}
+"#
+            }
+            ApiSurface::Battery => {
+                r#"
+    // Battery Status API
+    try { delete Navigator.prototype.getBattery; delete navigator.getBattery; } catch (e) {}
+    try { delete window.BatteryManager; } catch (e) {}
+"#
+            }
+            ApiSurface::Bluetooth => {
+                r#"
+    // Web Bluetooth
+    try { delete Navigator.prototype.bluetooth; delete navigator.bluetooth; } catch (e) {}
+    try { delete window.Bluetooth; delete window.BluetoothDevice; } catch (e) {}
+"#
+            }
+            ApiSurface::Usb => {
+                r#"
+    // WebUSB
+    try { delete Navigator.prototype.usb; delete navigator.usb; } catch (e) {}
+    try { delete window.USB; delete window.USBDevice; } catch (e) {}
+"#
+            }
+            ApiSurface::MediaDevices => {
+                r#"
+    // Media devices, as in insecure contexts
+    try { delete Navigator.prototype.mediaDevices; delete navigator.mediaDevices; } catch (e) {}
+    try { delete window.MediaDevices; } catch (e) {}
+"#
+            }
+        }
+    }
+}
+
+/// Generate the JavaScript removing `apis` from the page
+///
+/// Returns an empty string when nothing is disabled. The script must run
+/// after the other stealth sections, which may stub some of these APIs.
+pub fn disable_apis_script(apis: &[ApiSurface]) -> String {
+    let mut seen = Vec::new();
+    let mut script = String::new();
+    for api in apis {
+        if !seen.contains(api) {
+            seen.push(*api);
+            script.push_str(api.removal_script());
+        }
+    }
+    script
+}
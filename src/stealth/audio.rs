@@ -15,6 +15,8 @@
 //! - `AudioConfig` - Configuration for AudioContext spoofing
 //! - Noise injection for `getChannelData`, `getFloatFrequencyData`, and related methods
 //! - Protection for both `AudioContext` and `OfflineAudioContext`
+//! - Optional fixed seed, so the noise (and the resulting hash) stays the
+//!   same across page loads of one session
 //!
 //! # Example
 //!
@@ -29,13 +31,26 @@
 //!
 //! // Get the JavaScript override script
 //! let js = config.get_override_script();
+//!
+//! // Same noise on every page load
+//! let js = config.generate_override_script(42);
 //! ```
 
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Number of distinct session seeds the script's generator accepts
+///
+/// Generated seeds are reduced to this range up front; it yields the same
+/// script and keeps them representable in TOML (signed 64-bit integers).
+const SEED_MODULUS: u64 = 2_147_483_646;
+
 /// AudioContext fingerprint spoofing configuration
 ///
 /// Controls how noise is injected into audio processing operations
 /// to prevent fingerprinting through the Web Audio API.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioConfig {
     /// Enable AudioContext spoofing
     ///
@@ -47,6 +62,11 @@ pub struct AudioConfig {
     /// so even tiny amounts of noise are effective. Values above 0.01
     /// may cause audible artifacts.
     pub noise_level: f64,
+    /// Session seed baked into the script
+    ///
+    /// When `None`, each page load draws its own seed, so the audio hash
+    /// changes between loads.
+    pub seed: Option<u64>,
 }
 
 impl AudioConfig {
@@ -55,6 +75,7 @@ impl AudioConfig {
         Self {
             enabled: true,
             noise_level: noise_level.clamp(0.0, 0.1),
+            seed: None,
         }
     }
 
@@ -63,13 +84,49 @@ impl AudioConfig {
         Self {
             enabled: false,
             noise_level: 0.0,
+            seed: None,
         }
     }
 
+    /// Default noise with a random session seed
+    pub fn random() -> Self {
+        Self::default().with_seed(rand::random::<u64>() % SEED_MODULUS)
+    }
+
+    /// Default noise with a session seed derived from a seed string
+    pub fn consistent(seed: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        Self::default().with_seed(hasher.finish() % SEED_MODULUS)
+    }
+
+    /// Fix the session seed used by [`get_override_script`](Self::get_override_script)
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     /// Generate JavaScript override script for AudioContext spoofing
     ///
-    /// This script must be injected before any page scripts run.
+    /// This script must be injected before any page scripts run. Uses
+    /// [`seed`](Self::seed) when set.
     pub fn get_override_script(&self) -> String {
+        match self.seed {
+            Some(seed) => self.generate_override_script(seed),
+            None => self.render_script("Math.floor(Math.random() * 2147483646) + 1"),
+        }
+    }
+
+    /// Generate the override script with noise derived from `seed`
+    ///
+    /// The same seed produces the same noise on every page load.
+    pub fn generate_override_script(&self, seed: u64) -> String {
+        // The Park-Miller generator in the script needs a seed in 1..2^31-1
+        let session_seed = seed % SEED_MODULUS + 1;
+        self.render_script(&session_seed.to_string())
+    }
+
+    fn render_script(&self, session_seed: &str) -> String {
         if !self.enabled {
             return String::new();
         }
@@ -85,7 +142,7 @@ impl AudioConfig {
     const AUDIO_NOISE_LEVEL = {noise_level};
 
     // Session seed for consistent noise within a session
-    const AUDIO_SESSION_SEED = Math.floor(Math.random() * 2147483647);
+    const AUDIO_SESSION_SEED = {session_seed};
 
     // Simple seeded PRNG for deterministic noise
     function audioSeededRandom(seed) {{
@@ -219,6 +276,7 @@ impl AudioConfig {
 }})();
 "#,
             noise_level = noise_level,
+            session_seed = session_seed,
         )
     }
 }
@@ -228,6 +286,7 @@ impl Default for AudioConfig {
         Self {
             enabled: true,
             noise_level: 0.0001,
+            seed: None,
         }
     }
 }
@@ -292,6 +351,25 @@ mod tests {
         assert!(js.contains("})();"));
     }
 
+    #[test]
+    fn test_seeded_script_is_deterministic() {
+        let config = AudioConfig::default();
+        let js = config.generate_override_script(7);
+        assert_eq!(js, config.generate_override_script(7));
+        assert_ne!(js, config.generate_override_script(8));
+        assert!(js.contains("AUDIO_SESSION_SEED = 8;"));
+        assert_eq!(config.clone().with_seed(7).get_override_script(), js);
+
+        // Park-Miller degenerates on 0 and 2^31-1
+        assert!(config
+            .generate_override_script(2_147_483_646)
+            .contains("AUDIO_SESSION_SEED = 1;"));
+        assert!(config.get_override_script().contains("Math.random()"));
+        assert!(AudioConfig::disabled()
+            .generate_override_script(7)
+            .is_empty());
+    }
+
     #[test]
     fn test_noise_level_in_script() {
         let config = AudioConfig::new(0.005);
@@ -0,0 +1,249 @@
+//! Battery Status API Spoofing
+//!
+//! This module replaces the values reported by `navigator.getBattery()`.
+//! Battery level, charging state and the time estimates change slowly and
+//! differ between devices, so together they can re-identify a user across
+//! sites within the same session, and a headless machine without a battery
+//! reports a tell-tale constant state.
+//!
+//! # Components
+//!
+//! - `BatteryConfig` - Reported battery state and the override script
+//! - Seeded generation of plausible charging/discharging states
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use ki_browser_standalone::stealth::battery::BatteryConfig;
+//!
+//! // A plugged-in, fully charged device
+//! let config = BatteryConfig::default();
+//!
+//! // Or a plausible state picked from a seed
+//! let config = BatteryConfig::random_from_seed(42);
+//!
+//! // Get the JavaScript override script
+//! let js = config.generate_override_script();
+//! ```
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use super::native_functions::mark_native_script;
+
+/// Battery status spoofing configuration
+///
+/// A `None` time is reported as `Infinity`, which is what browsers return
+/// for the estimate that does not apply (or is unknown).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatteryConfig {
+    /// Replace the Battery Status API
+    ///
+    /// When false, no battery modifications are applied.
+    pub enabled: bool,
+    /// Whether the device is plugged in
+    pub charging: bool,
+    /// Charge level, 0.0 to 1.0
+    pub level: f64,
+    /// Seconds until fully charged (`0` when full)
+    pub charging_time: Option<f64>,
+    /// Seconds until the battery is empty
+    pub discharging_time: Option<f64>,
+}
+
+impl BatteryConfig {
+    /// Create an enabled configuration with the given state
+    ///
+    /// `level` is clamped to 0.0..=1.0 and rounded to whole percent, the
+    /// granularity Chrome reports.
+    pub fn new(
+        charging: bool,
+        level: f64,
+        charging_time: Option<f64>,
+        discharging_time: Option<f64>,
+    ) -> Self {
+        Self {
+            enabled: true,
+            charging,
+            level: (level.clamp(0.0, 1.0) * 100.0).round() / 100.0,
+            charging_time,
+            discharging_time,
+        }
+    }
+
+    /// Create a disabled configuration (real battery state)
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    /// A plausible laptop battery state picked from `seed`
+    ///
+    /// The level stays between 20% and 95%: a full battery on the charger is
+    /// what headless Chromium reports. The same seed always yields the same
+    /// state.
+    pub fn random_from_seed(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let level: f64 = rng.gen_range(0.2..=0.95);
+
+        if rng.gen_bool(0.4) {
+            // Roughly 1.5 to 2.5 hours for a full charge
+            let charging_time = ((1.0 - level) * rng.gen_range(5400.0..9000.0)).round();
+            Self::new(true, level, Some(charging_time), None)
+        } else {
+            // Roughly 4 to 10 hours on a full battery
+            let discharging_time = (level * rng.gen_range(14400.0..36000.0)).round();
+            Self::new(false, level, None, Some(discharging_time))
+        }
+    }
+
+    /// [`random_from_seed`](Self::random_from_seed) with a seed derived from a seed string
+    pub fn consistent(seed: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        Self::random_from_seed(hasher.finish())
+    }
+
+    /// Generate JavaScript override script for battery status spoofing
+    ///
+    /// This script must be injected before any page scripts run.
+    pub fn generate_override_script(&self) -> String {
+        if !self.enabled {
+            return "// Battery spoofing disabled".to_string();
+        }
+
+        let js_time = |time: Option<f64>| match time {
+            Some(seconds) => seconds.max(0.0).to_string(),
+            None => "Infinity".to_string(),
+        };
+
+        format!(
+            r#"
+// Battery Status Spoofing
+(function() {{
+    'use strict';
+
+    if (typeof Navigator === 'undefined') return;
+
+    const VALUES = {{
+        charging: {charging},
+        chargingTime: {charging_time},
+        dischargingTime: {discharging_time},
+        level: {level}
+    }};
+    const BATTERY_EVENTS = new Set([
+        'chargingchange', 'chargingtimechange', 'dischargingtimechange', 'levelchange'
+    ]);
+
+    const proto = typeof BatteryManager !== 'undefined' ? BatteryManager.prototype : null;
+    const originalGetBattery = Navigator.prototype.getBattery;
+    const originalAddEventListener = EventTarget.prototype.addEventListener;
+
+    // Report the configured values from the native getters' place, so a
+    // real BatteryManager instance keeps no own properties
+    function defineValues(target) {{
+        for (const key of Object.keys(VALUES)) {{
+            Object.defineProperty(target, key, {{
+                get: function() {{ return VALUES[key]; }},
+                enumerable: true,
+                configurable: true
+            }});
+        }}
+    }}
+
+    // The configured state never changes, so change listeners never fire
+    function addEventListener(type, listener, options) {{
+        if (BATTERY_EVENTS.has(String(type))) return;
+        return originalAddEventListener.apply(this, arguments);
+    }}
+
+    if (proto) {{
+        defineValues(proto);
+        proto.addEventListener = addEventListener;
+    }}
+
+    function fallbackManager() {{
+        const manager = Object.create(proto || EventTarget.prototype);
+        if (!proto) {{
+            defineValues(manager);
+            manager.addEventListener = addEventListener;
+        }}
+        return manager;
+    }}
+
+    let batteryPromise = null;
+    Navigator.prototype.getBattery = function getBattery() {{
+        if (!batteryPromise) {{
+            batteryPromise = typeof originalGetBattery === 'function'
+                ? originalGetBattery.call(this).catch(() => fallbackManager())
+                : Promise.resolve(fallbackManager());
+        }}
+        return batteryPromise;
+    }};
+
+    // Make the overrides look native
+    {mark_native}
+    markNative(Navigator.prototype.getBattery, 'getBattery');
+    markNative(addEventListener, 'addEventListener');
+}})();
+"#,
+            charging = self.charging,
+            charging_time = js_time(self.charging_time),
+            discharging_time = js_time(self.discharging_time),
+            level = self.level,
+            mark_native = mark_native_script(),
+        )
+    }
+}
+
+impl Default for BatteryConfig {
+    /// A plugged-in, fully charged device
+    fn default() -> Self {
+        Self::new(true, 1.0, Some(0.0), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_from_seed_is_plausible() {
+        for seed in 0..50 {
+            let config = BatteryConfig::random_from_seed(seed);
+            assert_eq!(config, BatteryConfig::random_from_seed(seed));
+            assert!((0.2..=0.95).contains(&config.level));
+            if config.charging {
+                assert!(config.charging_time.is_some());
+                assert!(config.discharging_time.is_none());
+            } else {
+                assert!(config.charging_time.is_none());
+                assert!(config.discharging_time.unwrap() > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_script_resolves_battery_manager() {
+        let js = BatteryConfig::new(false, 0.42, None, Some(9000.0)).generate_override_script();
+        assert!(js.contains("Navigator.prototype.getBattery = function getBattery()"));
+        assert!(js.contains("Promise.resolve(fallbackManager())"));
+        assert!(js.contains("charging: false"));
+        assert!(js.contains("chargingTime: Infinity"));
+        assert!(js.contains("dischargingTime: 9000"));
+        assert!(js.contains("level: 0.42"));
+        assert!(js.contains("proto.addEventListener = addEventListener"));
+        assert!(js.contains("markNative(Navigator.prototype.getBattery, 'getBattery');"));
+        assert_eq!(js.matches("new Proxy(").count(), 1);
+        assert_eq!(js.matches('{').count(), js.matches('}').count());
+        assert_eq!(js.matches('(').count(), js.matches(')').count());
+
+        let js = BatteryConfig::disabled().generate_override_script();
+        assert!(!js.contains("getBattery"));
+    }
+}
@@ -13,13 +13,14 @@
 //! # Components
 //!
 //! - `CanvasConfig` - Configuration for canvas fingerprint protection
+//! - `CanvasNoiseConfig` - Seed and per-channel magnitude of the noise
 //! - Noise injection for `getImageData`, `toDataURL`, and `toBlob`
 //! - Seed-based deterministic noise for consistency
 //!
 //! # Example
 //!
 //! ```rust,no_run
-//! use ki_browser_standalone::stealth::canvas::CanvasConfig;
+//! use ki_browser_standalone::stealth::canvas::{CanvasConfig, CanvasNoiseConfig};
 //!
 //! // Use safe defaults
 //! let config = CanvasConfig::default();
@@ -29,13 +30,27 @@
 //!
 //! // Get the JavaScript override script
 //! let js = config.get_override_script();
+//!
+//! // Same seed, same noise: identical script across sessions
+//! let noise = CanvasNoiseConfig::consistent("my-session-seed").with_magnitude(3);
+//! let js = config.get_seeded_override_script(&noise);
 //! ```
 
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Largest per-channel noise magnitude a [`CanvasNoiseConfig`] accepts
+pub const MAX_CANVAS_NOISE_MAGNITUDE: u8 = 5;
+
+/// Per-channel noise magnitude used unless configured otherwise
+pub const DEFAULT_CANVAS_NOISE_MAGNITUDE: u8 = 2;
+
 /// Canvas fingerprint protection configuration
 ///
 /// Controls how noise is injected into canvas operations to prevent
 /// fingerprinting while maintaining visual appearance.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CanvasConfig {
     /// Enable canvas noise injection
     ///
@@ -99,14 +114,31 @@ impl CanvasConfig {
 
     /// Generate JavaScript override script for canvas fingerprint protection
     ///
-    /// This script must be injected before any page scripts run.
+    /// This script must be injected before any page scripts run. The noise
+    /// seed is drawn anew on every page load; use
+    /// [`get_seeded_override_script`](Self::get_seeded_override_script) for
+    /// noise that stays the same across loads.
     pub fn get_override_script(&self) -> String {
+        self.render_script(
+            self.noise_level.clamp(0.0, 1.0),
+            "Math.floor(Math.random() * 2147483647)",
+        )
+    }
+
+    /// Generate the override script with noise from `noise`
+    ///
+    /// The seed is baked into the script, so the same `noise` yields the same
+    /// pixels on every load. Its magnitude replaces
+    /// [`noise_level`](Self::noise_level).
+    pub fn get_seeded_override_script(&self, noise: &CanvasNoiseConfig) -> String {
+        self.render_script(noise.noise_level(), &noise.seed.to_string())
+    }
+
+    fn render_script(&self, noise_level: f64, session_seed: &str) -> String {
         if !self.noise_enabled {
             return String::new();
         }
 
-        let noise_level = self.noise_level.clamp(0.0, 1.0);
-
         format!(
             r#"
 // Canvas Fingerprint Protection
@@ -117,7 +149,7 @@ impl CanvasConfig {
     const DETERMINISTIC = {deterministic};
 
     // Session seed for deterministic noise
-    const SESSION_SEED = Math.floor(Math.random() * 2147483647);
+    const SESSION_SEED = {session_seed};
 
     // Deterministic pseudo-random number generator (mulberry32)
     function mulberry32(seed) {{
@@ -187,6 +219,7 @@ impl CanvasConfig {
 }})();
 "#,
             noise_level = noise_level,
+            session_seed = session_seed,
             deterministic = self.deterministic,
             to_data_url_override = if self.protect_to_data_url {
                 Self::get_to_data_url_override()
@@ -278,6 +311,57 @@ impl Default for CanvasConfig {
     }
 }
 
+/// Seed and strength of the canvas noise
+///
+/// Noise derived from the same seed is identical on every page load, so a
+/// site sees one stable (but spoofed) canvas hash per session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CanvasNoiseConfig {
+    /// Seed for the per-pixel noise generator
+    pub seed: u32,
+    /// Largest change to a color channel, 0 - 5
+    pub magnitude: u8,
+}
+
+impl CanvasNoiseConfig {
+    /// Noise from `seed` with the default magnitude
+    pub fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            magnitude: DEFAULT_CANVAS_NOISE_MAGNITUDE,
+        }
+    }
+
+    /// Noise from a random seed
+    pub fn random() -> Self {
+        Self::new(rand::random())
+    }
+
+    /// Noise seeded from a session seed string
+    pub fn consistent(seed: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        Self::new(hasher.finish() as u32)
+    }
+
+    /// Set the per-channel magnitude, clamped to [`MAX_CANVAS_NOISE_MAGNITUDE`]
+    pub fn with_magnitude(mut self, magnitude: u8) -> Self {
+        self.magnitude = magnitude.min(MAX_CANVAS_NOISE_MAGNITUDE);
+        self
+    }
+
+    /// Magnitude as a fraction of the channel range, as used by the script
+    fn noise_level(&self) -> f64 {
+        f64::from(self.magnitude.min(MAX_CANVAS_NOISE_MAGNITUDE)) / 255.0
+    }
+}
+
+impl Default for CanvasNoiseConfig {
+    fn default() -> Self {
+        Self::random()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -354,6 +438,25 @@ mod tests {
         assert!(js.contains("DETERMINISTIC = false"));
     }
 
+    #[test]
+    fn test_seeded_noise_is_deterministic() {
+        let config = CanvasConfig::default();
+        let noise = CanvasNoiseConfig::consistent("seed");
+        assert_eq!(noise, CanvasNoiseConfig::consistent("seed"));
+        assert_eq!(noise.magnitude, DEFAULT_CANVAS_NOISE_MAGNITUDE);
+
+        let js = config.get_seeded_override_script(&noise);
+        assert_eq!(js, config.get_seeded_override_script(&noise));
+        assert!(js.contains(&format!("SESSION_SEED = {};", noise.seed)));
+        assert!(!js.contains("Math.floor(Math.random()"));
+
+        let noise = noise.with_magnitude(9);
+        assert_eq!(noise.magnitude, MAX_CANVAS_NOISE_MAGNITUDE);
+        assert!(config
+            .get_seeded_override_script(&noise)
+            .contains(&format!("NOISE_LEVEL = {};", 5.0 / 255.0)));
+    }
+
     #[test]
     fn test_script_is_iife() {
         let config = CanvasConfig::default();
@@ -0,0 +1,438 @@
+//! User-Agent Client Hints
+//!
+//! Chromium describes itself twice: in the `User-Agent` string and through
+//! User-Agent Client Hints, i.e. the `Sec-CH-UA*` request headers and
+//! `navigator.userAgentData`. Detection scripts compare the two, so a
+//! spoofed Chrome 144 user agent whose hints still report the embedded
+//! Chromium's own brands is a strong tell.
+//!
+//! [`ClientHints`] derives a hint set from a [`FingerprintProfile`] and its
+//! user agent string. It produces both the page override for
+//! `navigator.userAgentData` (including `getHighEntropyValues`) and the
+//! matching request header values.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ki_browser_standalone::stealth::client_hints::ClientHints;
+//! use ki_browser_standalone::stealth::FingerprintProfile;
+//!
+//! let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+//!           (KHTML, like Gecko) Chrome/144.0.0.0 Safari/537.36";
+//! let hints = ClientHints::for_profile(&FingerprintProfile::WindowsChrome, ua).unwrap();
+//! assert_eq!(hints.platform, "Windows");
+//! assert!(hints.sec_ch_ua().contains("\"Google Chrome\";v=\"144\""));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::fingerprint::FingerprintProfile;
+
+/// Full Chromium versions behind the reduced `Chrome/<major>.0.0.0` user
+/// agents of the fingerprint database, as reported by `fullVersionList`.
+const FULL_VERSIONS: &[(u32, &str)] = &[
+    (120, "120.0.6099.225"),
+    (142, "142.0.7444.176"),
+    (143, "143.0.7499.170"),
+    (144, "144.0.7559.133"),
+];
+
+/// User agent of Chrome 120 on Windows, the navigator default.
+const CHROME_120_WINDOWS_UA: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+    (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+/// User agent of Edge 120 on Windows.
+const EDGE_120_WINDOWS_UA: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+    (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0";
+
+/// Request headers carrying client hints, low-entropy ones first.
+pub const CLIENT_HINT_HEADERS: &[&str] = &[
+    "Sec-CH-UA",
+    "Sec-CH-UA-Mobile",
+    "Sec-CH-UA-Platform",
+    "Sec-CH-UA-Platform-Version",
+    "Sec-CH-UA-Arch",
+    "Sec-CH-UA-Bitness",
+    "Sec-CH-UA-Model",
+    "Sec-CH-UA-WoW64",
+    "Sec-CH-UA-Full-Version",
+    "Sec-CH-UA-Full-Version-List",
+];
+
+/// Characters and versions Chromium draws its GREASE brand from.
+const GREASE_CHARS: [char; 11] = [' ', '(', ':', '-', '.', '/', ')', ';', '=', '?', '_'];
+const GREASE_VERSIONS: [&str; 3] = ["8", "99", "24"];
+
+/// Brand positions Chromium shuffles its brand list into, indexed by seed.
+const BRAND_ORDERS: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+/// One entry of a brand list, e.g. `{ brand: "Chromium", version: "144" }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Brand {
+    /// Brand name.
+    pub brand: String,
+    /// Major version in `brands`, full version in `fullVersionList`.
+    pub version: String,
+}
+
+impl Brand {
+    fn new(brand: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            brand: brand.into(),
+            version: version.into(),
+        }
+    }
+}
+
+/// User-Agent Client Hints matching a spoofed user agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientHints {
+    /// Low-entropy brand list with major versions (`Sec-CH-UA`).
+    pub brands: Vec<Brand>,
+    /// Brand list with full versions (`Sec-CH-UA-Full-Version-List`).
+    pub full_version_list: Vec<Brand>,
+    /// Full browser version (`Sec-CH-UA-Full-Version`).
+    #[serde(rename = "uaFullVersion")]
+    pub full_version: String,
+    /// Mobile device (`Sec-CH-UA-Mobile`).
+    pub mobile: bool,
+    /// Operating system name, e.g. `"Windows"` (`Sec-CH-UA-Platform`).
+    pub platform: String,
+    /// Operating system version (`Sec-CH-UA-Platform-Version`).
+    pub platform_version: String,
+    /// CPU architecture, e.g. `"x86"` (`Sec-CH-UA-Arch`).
+    pub architecture: String,
+    /// CPU bitness, e.g. `"64"` (`Sec-CH-UA-Bitness`).
+    pub bitness: String,
+    /// Device model, empty on desktops (`Sec-CH-UA-Model`).
+    pub model: String,
+    /// 32-bit browser on 64-bit Windows (`Sec-CH-UA-WoW64`).
+    pub wow64: bool,
+}
+
+impl ClientHints {
+    /// Derives the hints for a Chromium-based profile from its user agent.
+    ///
+    /// Returns `None` for Firefox and Safari profiles, which expose no
+    /// `navigator.userAgentData`, and for user agents without a
+    /// `Chrome/<version>` token. `Custom` profiles are classified by the
+    /// user agent alone.
+    pub fn for_profile(profile: &FingerprintProfile, user_agent: &str) -> Option<Self> {
+        let platform = match profile {
+            FingerprintProfile::WindowsChrome | FingerprintProfile::WindowsEdge => "Windows",
+            FingerprintProfile::MacChrome => "macOS",
+            FingerprintProfile::LinuxChrome => "Linux",
+            FingerprintProfile::Custom => platform_from_user_agent(user_agent)?,
+            FingerprintProfile::WindowsFirefox
+            | FingerprintProfile::MacSafari
+            | FingerprintProfile::MacFirefox
+            | FingerprintProfile::LinuxFirefox => return None,
+        };
+        let major = token_major(user_agent, "Chrome/")?;
+        let chromium_full = full_version(major);
+
+        // Edge reports its own version next to the Chromium one
+        let (brand, brand_major, brand_full) = match token_major(user_agent, "Edg/") {
+            Some(edge) => ("Microsoft Edge", edge, full_version(edge)),
+            None => ("Google Chrome", major, chromium_full.clone()),
+        };
+        let (grease, grease_version) = grease_brand(major);
+        let brands = shuffle(
+            [
+                Brand::new(grease.clone(), grease_version),
+                Brand::new("Chromium", major.to_string()),
+                Brand::new(brand, brand_major.to_string()),
+            ],
+            major,
+        );
+        let full_version_list = shuffle(
+            [
+                Brand::new(grease, format!("{}.0.0.0", grease_version)),
+                Brand::new("Chromium", chromium_full.clone()),
+                Brand::new(brand, brand_full),
+            ],
+            major,
+        );
+
+        let platform_version = match platform {
+            // Windows 11 reports 13+, Windows 10 1903+ reports 1-10
+            "Windows" => "15.0.0".to_string(),
+            "macOS" => mac_version(user_agent),
+            _ => "6.8.0".to_string(),
+        };
+        let architecture = if user_agent.contains("arm64") || user_agent.contains("aarch64") {
+            "arm"
+        } else {
+            "x86"
+        };
+
+        Some(Self {
+            brands,
+            full_version_list,
+            full_version: chromium_full,
+            mobile: user_agent.contains("Mobile"),
+            platform: platform.to_string(),
+            platform_version,
+            architecture: architecture.to_string(),
+            bitness: "64".to_string(),
+            model: String::new(),
+            wow64: false,
+        })
+    }
+
+    /// Hints of Chrome 120 on Windows 10/11
+    pub fn for_chrome_120_windows() -> Self {
+        Self::for_profile(&FingerprintProfile::WindowsChrome, CHROME_120_WINDOWS_UA)
+            .expect("Chrome user agent has client hints")
+    }
+
+    /// Hints of Edge 120 on Windows 10/11
+    pub fn for_edge_120_windows() -> Self {
+        Self::for_profile(&FingerprintProfile::WindowsEdge, EDGE_120_WINDOWS_UA)
+            .expect("Edge user agent has client hints")
+    }
+
+    /// `Sec-CH-UA` header value, e.g. `"Chromium";v="144", ...`.
+    pub fn sec_ch_ua(&self) -> String {
+        brand_header(&self.brands)
+    }
+
+    /// Value of one of the [`CLIENT_HINT_HEADERS`], `None` for other headers.
+    ///
+    /// Header names are matched case-insensitively.
+    pub fn header_value(&self, name: &str) -> Option<String> {
+        let quoted = |value: &str| format!("{:?}", value);
+        let boolean = |value: bool| if value { "?1" } else { "?0" }.to_string();
+        Some(match name.to_ascii_lowercase().as_str() {
+            "sec-ch-ua" => self.sec_ch_ua(),
+            "sec-ch-ua-mobile" => boolean(self.mobile),
+            "sec-ch-ua-platform" => quoted(&self.platform),
+            "sec-ch-ua-platform-version" => quoted(&self.platform_version),
+            "sec-ch-ua-arch" => quoted(&self.architecture),
+            "sec-ch-ua-bitness" => quoted(&self.bitness),
+            "sec-ch-ua-model" => quoted(&self.model),
+            "sec-ch-ua-wow64" => boolean(self.wow64),
+            "sec-ch-ua-full-version" => quoted(&self.full_version),
+            "sec-ch-ua-full-version-list" => brand_header(&self.full_version_list),
+            _ => return None,
+        })
+    }
+
+    /// Generate the JavaScript override for `navigator.userAgentData`
+    ///
+    /// This script must be injected before any page scripts run.
+    pub fn get_override_script(&self) -> String {
+        let hints = serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string());
+        format!(
+            r#"
+// User-Agent Client Hints
+(function() {{
+    'use strict';
+
+    // Only secure contexts expose userAgentData
+    const original = navigator.userAgentData;
+    if (!original) return;
+    const hints = {hints};
+    const lowEntropy = () => ({{
+        brands: hints.brands.map((b) => ({{ brand: b.brand, version: b.version }})),
+        mobile: hints.mobile,
+        platform: hints.platform
+    }});
+    const proto = Object.getPrototypeOf(original);
+
+    const data = Object.create(proto, {{
+        brands: {{ get: () => Object.freeze(lowEntropy().brands.map(Object.freeze)), enumerable: true }},
+        mobile: {{ get: () => hints.mobile, enumerable: true }},
+        platform: {{ get: () => hints.platform, enumerable: true }},
+        getHighEntropyValues: {{
+            value: function getHighEntropyValues(requested) {{
+                if (!Array.isArray(requested)) {{
+                    return Promise.reject(new TypeError(
+                        "Failed to execute 'getHighEntropyValues' on 'NavigatorUAData': " +
+                        "The provided value cannot be converted to a sequence."));
+                }}
+                const values = lowEntropy();
+                for (const hint of requested) {{
+                    if (hint === 'fullVersionList') {{
+                        values.fullVersionList = hints.fullVersionList.map((b) => ({{ brand: b.brand, version: b.version }}));
+                    }} else if (hint === 'formFactors') {{
+                        values.formFactors = [hints.mobile ? 'Mobile' : 'Desktop'];
+                    }} else if (hint in hints) {{
+                        values[hint] = hints[hint];
+                    }}
+                }}
+                return Promise.resolve(values);
+            }}
+        }},
+        toJSON: {{ value: function toJSON() {{ return lowEntropy(); }} }}
+    }});
+
+    Object.defineProperty(Navigator.prototype, 'userAgentData', {{
+        get: function userAgentData() {{ return data; }},
+        enumerable: true,
+        configurable: true
+    }});
+}})();
+"#,
+            hints = hints
+        )
+    }
+}
+
+/// Brand list in structured header form: `"A";v="1", "B";v="2"`.
+fn brand_header(brands: &[Brand]) -> String {
+    brands
+        .iter()
+        .map(|b| format!("{:?};v={:?}", b.brand, b.version))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Major version following `token` in the user agent, e.g. `Chrome/`.
+fn token_major(user_agent: &str, token: &str) -> Option<u32> {
+    let start = user_agent.find(token)? + token.len();
+    let digits: String = user_agent[start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// Full version for a major version, `<major>.0.0.0` if unknown.
+fn full_version(major: u32) -> String {
+    FULL_VERSIONS
+        .iter()
+        .find(|(m, _)| *m == major)
+        .map(|(_, full)| full.to_string())
+        .unwrap_or_else(|| format!("{}.0.0.0", major))
+}
+
+/// Client hints platform named by a user agent's OS token.
+fn platform_from_user_agent(user_agent: &str) -> Option<&'static str> {
+    if user_agent.contains("Windows") {
+        Some("Windows")
+    } else if user_agent.contains("Macintosh") {
+        Some("macOS")
+    } else if user_agent.contains("Android") {
+        Some("Android")
+    } else if user_agent.contains("Linux") || user_agent.contains("X11") {
+        Some("Linux")
+    } else {
+        None
+    }
+}
+
+/// macOS version from `Mac OS X 14_4`, as `14.4.0`.
+///
+/// Chrome freezes the user agent at `10_15_7` while the hints report the
+/// real version, so the frozen value maps to a current release.
+fn mac_version(user_agent: &str) -> String {
+    let version = user_agent
+        .split("Mac OS X ")
+        .nth(1)
+        .and_then(|rest| rest.split([')', ';']).next())
+        .unwrap_or("10_15_7");
+    if version == "10_15_7" {
+        return "15.3.0".to_string();
+    }
+    let mut parts: Vec<&str> = version.split(['_', '.']).collect();
+    parts.resize(3, "0");
+    parts.join(".")
+}
+
+/// Chromium's GREASE brand and version for a major version.
+fn grease_brand(seed: u32) -> (String, &'static str) {
+    let seed = seed as usize;
+    let brand = format!(
+        "Not{}A{}Brand",
+        GREASE_CHARS[seed % GREASE_CHARS.len()],
+        GREASE_CHARS[(seed + 1) % GREASE_CHARS.len()]
+    );
+    (brand, GREASE_VERSIONS[seed % GREASE_VERSIONS.len()])
+}
+
+/// Orders `[grease, chromium, brand]` the way Chromium does for `seed`.
+fn shuffle(brands: [Brand; 3], seed: u32) -> Vec<Brand> {
+    let order = BRAND_ORDERS[seed as usize % BRAND_ORDERS.len()];
+    let mut slots: [Option<Brand>; 3] = Default::default();
+    for (brand, position) in brands.into_iter().zip(order) {
+        slots[position] = Some(brand);
+    }
+    slots.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIN_CHROME: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+        (KHTML, like Gecko) Chrome/143.0.0.0 Safari/537.36";
+
+    #[test]
+    fn test_override_brands_match_user_agent_version() {
+        let hints =
+            ClientHints::for_profile(&FingerprintProfile::WindowsChrome, WIN_CHROME).unwrap();
+        let script = hints.get_override_script();
+        assert!(script.contains("userAgentData"));
+        assert!(script.contains("getHighEntropyValues"));
+
+        // The brand list embedded in the script carries the UA's major version
+        let start = script.find("const hints = ").unwrap() + "const hints = ".len();
+        let end = start + script[start..].find(";\n").unwrap();
+        let embedded: serde_json::Value = serde_json::from_str(&script[start..end]).unwrap();
+        let brands = embedded["brands"].as_array().unwrap();
+        assert_eq!(brands.len(), 3);
+        for name in ["Google Chrome", "Chromium"] {
+            let brand = brands.iter().find(|b| b["brand"] == name).unwrap();
+            assert_eq!(brand["version"], "143");
+        }
+        assert_eq!(embedded["uaFullVersion"], "143.0.7499.170");
+        assert_eq!(embedded["platform"], "Windows");
+
+        assert_eq!(
+            hints.header_value("Sec-CH-UA-Platform").unwrap(),
+            "\"Windows\""
+        );
+        assert_eq!(hints.header_value("sec-ch-ua-mobile").unwrap(), "?0");
+        assert!(hints.sec_ch_ua().contains("\"Chromium\";v=\"143\""));
+        assert!(hints.header_value("Accept").is_none());
+    }
+
+    #[test]
+    fn test_chrome_and_edge_120_presets() {
+        let chrome = ClientHints::for_chrome_120_windows();
+        assert_eq!(chrome.platform, "Windows");
+        assert_eq!(chrome.full_version, "120.0.6099.225");
+        assert!(chrome.brands.iter().any(|b| b.brand == "Google Chrome" && b.version == "120"));
+
+        let edge = ClientHints::for_edge_120_windows();
+        assert!(edge.brands.iter().any(|b| b.brand == "Microsoft Edge" && b.version == "120"));
+        assert!(!edge.brands.iter().any(|b| b.brand == "Google Chrome"));
+    }
+
+    #[test]
+    fn test_profiles_without_client_hints() {
+        let firefox =
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:133.0) Gecko/20100101 Firefox/133.0";
+        assert!(ClientHints::for_profile(&FingerprintProfile::WindowsFirefox, firefox).is_none());
+        assert!(ClientHints::for_profile(&FingerprintProfile::Custom, firefox).is_none());
+
+        let edge = format!("{} Edg/143.0.0.0", WIN_CHROME);
+        let hints = ClientHints::for_profile(&FingerprintProfile::WindowsEdge, &edge).unwrap();
+        assert!(hints.brands.iter().any(|b| b.brand == "Microsoft Edge"));
+
+        let mac = "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_4) AppleWebKit/537.36 \
+            (KHTML, like Gecko) Chrome/144.0.0.0 Safari/537.36";
+        let hints = ClientHints::for_profile(&FingerprintProfile::MacChrome, mac).unwrap();
+        assert_eq!(hints.platform, "macOS");
+        assert_eq!(hints.platform_version, "14.4.0");
+    }
+}
@@ -4,12 +4,15 @@
 //! convert it into JavaScript property overrides for anti-detection injection.
 //! Covers screen, timezone, cookie, DNT, plugin, and font fingerprinting.
 
+use serde::{Deserialize, Serialize};
+
 use super::types::{
-    FontEntry, FingerprintProfile, PluginEntry, ScreenResolution,
+    FontEntry, FingerprintProfile, HardwareTier, PluginEntry, ScreenResolution,
 };
+use super::viewport::{BROWSER_CHROME_HEIGHT, BROWSER_CHROME_WIDTH};
 
 /// Complete browser fingerprint
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BrowserFingerprint {
     /// User agent string
     pub user_agent: String,
@@ -39,6 +42,8 @@ pub struct BrowserFingerprint {
     pub do_not_track: Option<String>,
     /// Cookie enabled
     pub cookie_enabled: bool,
+    /// Device memory and core count, drawn together from realistic tiers
+    pub hardware: HardwareTier,
     /// The fingerprint profile used
     pub profile: FingerprintProfile,
 }
@@ -57,8 +62,8 @@ impl BrowserFingerprint {
         // Browser chrome offsets (typical values):
         // outerWidth = viewport + scrollbar + window border (~16px)
         // outerHeight = viewport + toolbar + tabs + borders (~85px)
-        let outer_width = viewport_width + 16;
-        let outer_height = viewport_height + 85;
+        let outer_width = viewport_width + BROWSER_CHROME_WIDTH;
+        let outer_height = viewport_height + BROWSER_CHROME_HEIGHT;
 
         // Find a common resolution where width >= outer_width AND height >= outer_height
         let resolutions = ScreenResolution::common_resolutions();
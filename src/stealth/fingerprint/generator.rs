@@ -8,7 +8,7 @@ use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
 use super::types::{
-    FingerprintProfile, FontEntry, PluginEntry, ScreenResolution,
+    FingerprintProfile, FontEntry, HardwareTier, LocaleProfile, PluginEntry, ScreenResolution,
 };
 use super::fingerprint::BrowserFingerprint;
 
@@ -17,6 +17,8 @@ use super::fingerprint::BrowserFingerprint;
 pub struct FingerprintGenerator {
     /// Available user agents by profile
     user_agents: UserAgentDatabase,
+    /// Weighted timezone/language combinations
+    locales: Vec<LocaleProfile>,
 }
 
 impl FingerprintGenerator {
@@ -24,9 +26,32 @@ impl FingerprintGenerator {
     pub fn new() -> Self {
         Self {
             user_agents: UserAgentDatabase::new(),
+            locales: LocaleProfile::default_locales(),
         }
     }
 
+    /// Replace the weighted locale table used for timezone/language selection
+    ///
+    /// Entries with a zero weight or no language sets are ignored; an empty
+    /// table falls back to [`LocaleProfile::default_locales`].
+    pub fn with_locales(mut self, locales: Vec<LocaleProfile>) -> Self {
+        let locales: Vec<LocaleProfile> = locales
+            .into_iter()
+            .filter(|l| l.weight > 0 && l.language_sets.iter().any(|set| !set.is_empty()))
+            .collect();
+        self.locales = if locales.is_empty() {
+            LocaleProfile::default_locales()
+        } else {
+            locales
+        };
+        self
+    }
+
+    /// The weighted locale table in use
+    pub fn locales(&self) -> &[LocaleProfile] {
+        &self.locales
+    }
+
     /// Generate a completely random fingerprint
     pub fn generate_random(&self) -> BrowserFingerprint {
         use std::time::{SystemTime, UNIX_EPOCH};
@@ -80,8 +105,7 @@ impl FingerprintGenerator {
         let resolution = resolutions[resolution_index].clone();
 
         let user_agent = self.user_agents.get_user_agent(&profile, seed);
-        let (timezone, timezone_offset) = self.get_timezone(seed);
-        let languages = self.get_languages(&profile, seed);
+        let (timezone, timezone_offset, languages) = self.get_locale(seed);
 
         BrowserFingerprint {
             user_agent,
@@ -102,54 +126,57 @@ impl FingerprintGenerator {
                 None
             },
             cookie_enabled: true,
+            hardware: self.get_hardware(seed),
             profile,
         }
     }
 
-    fn get_timezone(&self, seed: u64) -> (String, i32) {
-        let timezones = vec![
-            ("America/New_York", -300),
-            ("America/Chicago", -360),
-            ("America/Denver", -420),
-            ("America/Los_Angeles", -480),
-            ("Europe/London", 0),
-            ("Europe/Paris", 60),
-            ("Europe/Berlin", 60),
-            ("Asia/Tokyo", 540),
-            ("Asia/Shanghai", 480),
-            ("Australia/Sydney", 600),
-        ];
-        let index = (seed as usize) % timezones.len();
-        let (tz, offset) = timezones[index];
-        (tz.to_string(), offset)
+    /// Pick a device memory / core count pairing by weight
+    fn get_hardware(&self, seed: u64) -> HardwareTier {
+        // Decorrelate from the resolution and locale picks
+        let mixed = seed.wrapping_mul(0xD6E8_FEB8_6659_FD93).rotate_left(17);
+
+        let tiers = HardwareTier::common_tiers();
+        let total: u64 = tiers.iter().map(|t| u64::from(t.weight)).sum();
+        let mut pick = mixed % total.max(1);
+        for tier in &tiers {
+            let weight = u64::from(tier.weight);
+            if pick < weight {
+                return *tier;
+            }
+            pick -= weight;
+        }
+        tiers[0]
     }
 
-    fn get_languages(&self, profile: &FingerprintProfile, seed: u64) -> Vec<String> {
-        let language_sets = match profile {
-            FingerprintProfile::WindowsChrome
-            | FingerprintProfile::WindowsFirefox
-            | FingerprintProfile::WindowsEdge => vec![
-                vec!["en-US", "en"],
-                vec!["en-US", "en", "es"],
-                vec!["en-GB", "en"],
-            ],
-            FingerprintProfile::MacChrome
-            | FingerprintProfile::MacSafari
-            | FingerprintProfile::MacFirefox => vec![
-                vec!["en-US", "en"],
-                vec!["en-US", "en", "fr"],
-                vec!["en-GB", "en"],
-            ],
-            FingerprintProfile::LinuxChrome | FingerprintProfile::LinuxFirefox => vec![
-                vec!["en-US", "en"],
-                vec!["en-US", "en", "de"],
-                vec!["en-GB", "en"],
-            ],
-            FingerprintProfile::Custom => vec![vec!["en-US", "en"]],
+    /// Pick a timezone by weight and a language set consistent with it
+    fn get_locale(&self, seed: u64) -> (String, i32, Vec<String>) {
+        // Decorrelate from the plain `seed % n` picks used for the other fields
+        let mixed = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15).rotate_left(29);
+
+        let total: u64 = self.locales.iter().map(|l| u64::from(l.weight)).sum();
+        let mut pick = mixed % total.max(1);
+        let locale = self
+            .locales
+            .iter()
+            .find(|l| {
+                let weight = u64::from(l.weight);
+                if pick < weight {
+                    true
+                } else {
+                    pick -= weight;
+                    false
+                }
+            })
+            .unwrap_or(&self.locales[0]);
+
+        let sets: Vec<&Vec<String>> = locale.language_sets.iter().filter(|s| !s.is_empty()).collect();
+        let languages = match sets.len() {
+            0 => vec!["en-US".to_string(), "en".to_string()],
+            n => sets[((mixed >> 32) as usize) % n].clone(),
         };
 
-        let index = (seed as usize) % language_sets.len();
-        language_sets[index].iter().map(|s| s.to_string()).collect()
+        (locale.timezone.clone(), locale.timezone_offset, languages)
     }
 
     fn get_plugins(&self, profile: &FingerprintProfile) -> Vec<PluginEntry> {
@@ -157,33 +184,7 @@ impl FingerprintGenerator {
             FingerprintProfile::WindowsChrome
             | FingerprintProfile::MacChrome
             | FingerprintProfile::LinuxChrome
-            | FingerprintProfile::WindowsEdge => vec![
-                PluginEntry {
-                    name: "PDF Viewer".to_string(),
-                    description: "Portable Document Format".to_string(),
-                    filename: "internal-pdf-viewer".to_string(),
-                },
-                PluginEntry {
-                    name: "Chrome PDF Viewer".to_string(),
-                    description: "Portable Document Format".to_string(),
-                    filename: "internal-pdf-viewer".to_string(),
-                },
-                PluginEntry {
-                    name: "Chromium PDF Viewer".to_string(),
-                    description: "Portable Document Format".to_string(),
-                    filename: "internal-pdf-viewer".to_string(),
-                },
-                PluginEntry {
-                    name: "Microsoft Edge PDF Viewer".to_string(),
-                    description: "Portable Document Format".to_string(),
-                    filename: "internal-pdf-viewer".to_string(),
-                },
-                PluginEntry {
-                    name: "WebKit built-in PDF".to_string(),
-                    description: "Portable Document Format".to_string(),
-                    filename: "internal-pdf-viewer".to_string(),
-                },
-            ],
+            | FingerprintProfile::WindowsEdge => PluginEntry::chrome_pdf_plugins(),
             FingerprintProfile::WindowsFirefox
             | FingerprintProfile::MacFirefox
             | FingerprintProfile::LinuxFirefox => vec![
@@ -13,6 +13,7 @@
 //! - Timezone
 //! - Installed plugins and fonts
 //! - Language preferences
+//! - Coordinated screen/window/viewport metrics ([`ViewportMetrics`])
 //!
 //! # Usage
 //!
@@ -35,11 +36,16 @@ mod builder;
 mod fingerprint;
 mod generator;
 mod types;
+mod viewport;
 
 pub use builder::FingerprintBuilder;
 pub use fingerprint::BrowserFingerprint;
 pub use generator::FingerprintGenerator;
-pub use types::{FingerprintProfile, FontEntry, PluginEntry, ScreenResolution};
+pub use types::{
+    FingerprintProfile, FontEntry, HardwareTier, LocaleProfile, PluginEntry, ScreenResolution,
+    CHROME_PDF_PLUGIN_NAMES,
+};
+pub use viewport::{ViewportMetrics, BROWSER_CHROME_HEIGHT, BROWSER_CHROME_WIDTH};
 
 #[cfg(test)]
 mod tests {
@@ -334,4 +340,55 @@ mod tests {
             assert_eq!(s.avail_height, s.height.saturating_sub(40));
         }
     }
+
+    #[test]
+    fn test_viewport_metrics_satisfy_size_invariants() {
+        let generator = FingerprintGenerator::new();
+        let mut fp = generator.generate_from_profile(FingerprintProfile::WindowsChrome);
+        fp.sync_screen_to_viewport(1280, 720);
+
+        let m = ViewportMetrics::new(1280, 720, &fp.screen_resolution, 1.25);
+        assert!(m.validate().is_ok(), "{:?}", m.validate());
+
+        assert_eq!((m.inner_width, m.inner_height), (1280, 720));
+        assert!(m.inner_width <= m.outer_width && m.outer_width <= m.screen_width);
+        assert!(m.inner_height <= m.outer_height && m.outer_height <= m.screen_height);
+        assert!(m.outer_width <= m.avail_width && m.outer_height <= m.avail_height);
+
+        let js = m.to_js_overrides();
+        assert!(js.contains(&format!("define(window, 'outerWidth', {});", m.outer_width)));
+        assert!(js.contains("define(window, 'devicePixelRatio', 1.25);"));
+        assert!(js.contains("visualViewport"));
+
+        // A viewport larger than the screen cannot be made coherent
+        let small_screen = ScreenResolution::new(1366, 768);
+        let err = ViewportMetrics::new(1920, 1080, &small_screen, 1.0).validate();
+        assert!(err.unwrap_err().contains("inner width"));
+    }
+
+    #[test]
+    fn test_japanese_timezone_yields_japanese_language() {
+        let generator = FingerprintGenerator::new();
+        let mut tokyo_seen = 0;
+        for i in 0..500 {
+            let fp = generator.generate_consistent(&format!("locale-{}", i));
+            if fp.timezone == "Asia/Tokyo" {
+                tokyo_seen += 1;
+                assert!(
+                    fp.languages.iter().any(|l| l.starts_with("ja")),
+                    "Asia/Tokyo with languages {:?}",
+                    fp.languages
+                );
+                assert_eq!(fp.language, fp.languages[0]);
+            }
+        }
+        assert!(tokyo_seen > 0, "Asia/Tokyo never selected in 500 seeds");
+
+        // A custom table restricts selection to its entries
+        let generator = FingerprintGenerator::new()
+            .with_locales(vec![LocaleProfile::new("Asia/Tokyo", 540, 1, &[&["ja-JP", "ja"]])]);
+        let fp = generator.generate_consistent("custom");
+        assert_eq!(fp.timezone, "Asia/Tokyo");
+        assert_eq!(fp.languages, vec!["ja-JP".to_string(), "ja".to_string()]);
+    }
 }
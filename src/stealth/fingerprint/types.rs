@@ -4,8 +4,10 @@
 //! common browser/OS combinations, screen resolution configuration with
 //! orientation support, and plugin/font data structures.
 
+use serde::{Deserialize, Serialize};
+
 /// Predefined fingerprint profiles for common browser/OS combinations
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FingerprintProfile {
     /// Windows 10/11 with Chrome (most common)
     WindowsChrome,
@@ -92,7 +94,7 @@ impl FingerprintProfile {
 }
 
 /// Screen resolution configuration
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ScreenResolution {
     pub width: u32,
     pub height: u32,
@@ -147,16 +149,150 @@ impl ScreenResolution {
     }
 }
 
-/// Plugin information for fingerprint
+/// A timezone together with the languages plausible for it.
+///
+/// [`FingerprintGenerator`](super::FingerprintGenerator) picks a locale by
+/// `weight` and then one of its `language_sets`, so the primary language
+/// always matches the region implied by the timezone.
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocaleProfile {
+    /// IANA timezone name (e.g., "Asia/Tokyo")
+    pub timezone: String,
+    /// Timezone offset in minutes, same convention as `BrowserFingerprint::timezone_offset`
+    pub timezone_offset: i32,
+    /// Relative selection weight, roughly the region's share of desktop browser users
+    pub weight: u32,
+    /// Candidate `navigator.languages` lists; each starts with the primary language
+    pub language_sets: Vec<Vec<String>>,
+}
+
+impl LocaleProfile {
+    pub fn new(timezone: &str, timezone_offset: i32, weight: u32, language_sets: &[&[&str]]) -> Self {
+        Self {
+            timezone: timezone.to_string(),
+            timezone_offset,
+            weight,
+            language_sets: language_sets
+                .iter()
+                .map(|set| set.iter().map(|l| l.to_string()).collect())
+                .collect(),
+        }
+    }
+
+    /// Default locale table weighted by approximate desktop browser share
+    pub fn default_locales() -> Vec<LocaleProfile> {
+        vec![
+            LocaleProfile::new("America/New_York", -300, 14, &[&["en-US", "en"], &["en-US", "en", "es"]]),
+            LocaleProfile::new("America/Chicago", -360, 8, &[&["en-US", "en"], &["en-US", "en", "es"]]),
+            LocaleProfile::new("America/Denver", -420, 3, &[&["en-US", "en"]]),
+            LocaleProfile::new("America/Los_Angeles", -480, 9, &[&["en-US", "en"], &["en-US", "en", "es"]]),
+            LocaleProfile::new("America/Mexico_City", -360, 3, &[&["es-MX", "es", "en"], &["es-419", "es"]]),
+            LocaleProfile::new("America/Sao_Paulo", -180, 5, &[&["pt-BR", "pt", "en-US", "en"], &["pt-BR", "pt"]]),
+            LocaleProfile::new("Europe/London", 0, 6, &[&["en-GB", "en"], &["en-GB", "en-US", "en"]]),
+            LocaleProfile::new("Europe/Paris", 60, 5, &[&["fr-FR", "fr", "en-US", "en"], &["fr-FR", "fr"]]),
+            LocaleProfile::new("Europe/Berlin", 60, 6, &[&["de-DE", "de", "en-US", "en"], &["de-DE", "de"], &["de", "en-US", "en"]]),
+            LocaleProfile::new("Europe/Madrid", 60, 3, &[&["es-ES", "es", "en"], &["es-ES", "es"]]),
+            LocaleProfile::new("Europe/Rome", 60, 3, &[&["it-IT", "it", "en-US", "en"], &["it-IT", "it"]]),
+            LocaleProfile::new("Asia/Kolkata", 330, 6, &[&["en-IN", "en", "hi"], &["en-US", "en", "hi"]]),
+            LocaleProfile::new("Asia/Shanghai", 480, 4, &[&["zh-CN", "zh"], &["zh-CN", "zh", "en"]]),
+            LocaleProfile::new("Asia/Tokyo", 540, 5, &[&["ja-JP", "ja"], &["ja", "en-US", "en"], &["ja-JP", "ja", "en-US", "en"]]),
+            LocaleProfile::new("Australia/Sydney", 600, 3, &[&["en-AU", "en"], &["en-AU", "en-GB", "en"]]),
+        ]
+    }
+}
+
+/// A realistic pairing of `navigator.deviceMemory` and `navigator.hardwareConcurrency`.
+///
+/// Chrome rounds `deviceMemory` to a power of two and clamps it at 8 GB, so
+/// high-end machines all report 8 while their core counts keep growing.
+/// Low-memory devices, on the other hand, never come with many cores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareTier {
+    /// Device memory in GB as reported by `navigator.deviceMemory`
+    pub device_memory: u8,
+    /// Logical CPU cores as reported by `navigator.hardwareConcurrency`
+    pub hardware_concurrency: u8,
+    /// Relative selection weight, roughly the tier's share of desktop devices
+    pub weight: u32,
+}
+
+impl HardwareTier {
+    pub const fn new(device_memory: u8, hardware_concurrency: u8, weight: u32) -> Self {
+        Self {
+            device_memory,
+            hardware_concurrency,
+            weight,
+        }
+    }
+
+    /// Common desktop tiers weighted by approximate share
+    pub fn common_tiers() -> Vec<HardwareTier> {
+        vec![
+            HardwareTier::new(2, 2, 2),   // Low-end netbook
+            HardwareTier::new(4, 2, 4),   // Budget dual-core laptop
+            HardwareTier::new(4, 4, 10),  // Budget quad-core laptop
+            HardwareTier::new(8, 4, 16),  // Mainstream quad-core
+            HardwareTier::new(8, 6, 10),  // Mainstream hexa-core
+            HardwareTier::new(8, 8, 24),  // Mainstream 8-thread / Apple M-series
+            HardwareTier::new(8, 12, 14), // Upper mid-range
+            HardwareTier::new(8, 16, 12), // Enthusiast desktop
+            HardwareTier::new(8, 20, 4),  // Hybrid-core desktop
+            HardwareTier::new(8, 24, 4),  // Workstation
+        ]
+    }
+
+    /// Whether `device_memory` GB and `hardware_concurrency` cores fit a real device
+    ///
+    /// Small memory caps the core count (2 GB: 4 cores, 4 GB: 8 cores) and
+    /// large memory rules out single- and dual-core machines.
+    pub fn is_plausible(device_memory: u8, hardware_concurrency: u8) -> bool {
+        if hardware_concurrency == 0 {
+            return false;
+        }
+        let max_cores = match device_memory {
+            0..=2 => 4,
+            3..=4 => 8,
+            _ => 64,
+        };
+        let min_cores = if device_memory >= 16 { 4 } else { 1 };
+        (min_cores..=max_cores).contains(&hardware_concurrency)
+    }
+}
+
+/// Plugin information for fingerprint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PluginEntry {
     pub name: String,
     pub description: String,
     pub filename: String,
 }
 
+/// Names of the PDF viewer plugins every current Chrome and Edge exposes,
+/// in `navigator.plugins` order. All share the `internal-pdf-viewer` file.
+pub const CHROME_PDF_PLUGIN_NAMES: [&str; 5] = [
+    "PDF Viewer",
+    "Chrome PDF Viewer",
+    "Chromium PDF Viewer",
+    "Microsoft Edge PDF Viewer",
+    "WebKit built-in PDF",
+];
+
+impl PluginEntry {
+    /// The canonical Chrome/Edge plugin list.
+    pub fn chrome_pdf_plugins() -> Vec<PluginEntry> {
+        CHROME_PDF_PLUGIN_NAMES
+            .iter()
+            .map(|name| PluginEntry {
+                name: name.to_string(),
+                description: "Portable Document Format".to_string(),
+                filename: "internal-pdf-viewer".to_string(),
+            })
+            .collect()
+    }
+}
+
 /// Font entry for fingerprint
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FontEntry {
     pub name: String,
 }
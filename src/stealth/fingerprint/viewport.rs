@@ -0,0 +1,138 @@
+//! Coordinated screen, window and viewport metrics.
+//!
+//! Overriding `screen`, `outerWidth/Height`, `innerWidth/Height`,
+//! `devicePixelRatio` and `visualViewport` independently makes it easy to end
+//! up with impossible combinations (an inner window larger than the outer
+//! one, a window larger than the screen). [`ViewportMetrics`] derives all of
+//! them from one window size, screen and DPR and renders a single override
+//! block, so the values always agree with each other.
+
+use serde::{Deserialize, Serialize};
+
+use super::types::ScreenResolution;
+
+/// Horizontal browser chrome: scrollbar plus window border.
+pub const BROWSER_CHROME_WIDTH: u32 = 16;
+
+/// Vertical browser chrome: toolbar, tab strip and borders.
+pub const BROWSER_CHROME_HEIGHT: u32 = 85;
+
+/// All size-related window properties, derived from one configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewportMetrics {
+    /// `screen.width`
+    pub screen_width: u32,
+    /// `screen.height`
+    pub screen_height: u32,
+    /// `screen.availWidth`
+    pub avail_width: u32,
+    /// `screen.availHeight`
+    pub avail_height: u32,
+    /// `window.outerWidth`
+    pub outer_width: u32,
+    /// `window.outerHeight`
+    pub outer_height: u32,
+    /// `window.innerWidth` (the layout viewport)
+    pub inner_width: u32,
+    /// `window.innerHeight`
+    pub inner_height: u32,
+    /// `window.devicePixelRatio`
+    pub device_pixel_ratio: f64,
+}
+
+impl ViewportMetrics {
+    /// Derives the metrics from the browser window size, fingerprint screen and DPR.
+    ///
+    /// The window size is the rendered viewport (`innerWidth/Height`); the
+    /// outer window adds [`BROWSER_CHROME_WIDTH`]/[`BROWSER_CHROME_HEIGHT`]
+    /// and is clamped to the available screen area. Call [`validate`](Self::validate)
+    /// to detect a viewport that does not fit on the screen at all.
+    pub fn new(
+        window_width: u32,
+        window_height: u32,
+        screen: &ScreenResolution,
+        device_pixel_ratio: f64,
+    ) -> Self {
+        let avail_width = screen.avail_width.min(screen.width);
+        let avail_height = screen.avail_height.min(screen.height);
+
+        Self {
+            screen_width: screen.width,
+            screen_height: screen.height,
+            avail_width,
+            avail_height,
+            outer_width: (window_width + BROWSER_CHROME_WIDTH).min(avail_width),
+            outer_height: (window_height + BROWSER_CHROME_HEIGHT).min(avail_height),
+            inner_width: window_width,
+            inner_height: window_height,
+            device_pixel_ratio,
+        }
+    }
+
+    /// Checks `inner <= outer <= avail <= screen` on both axes and a sane DPR.
+    pub fn validate(&self) -> Result<(), String> {
+        let axes = [
+            ("width", self.inner_width, self.outer_width, self.avail_width, self.screen_width),
+            ("height", self.inner_height, self.outer_height, self.avail_height, self.screen_height),
+        ];
+        for (axis, inner, outer, avail, screen) in axes {
+            if inner > outer {
+                return Err(format!("inner {axis} ({inner}) exceeds outer {axis} ({outer})"));
+            }
+            if outer > avail {
+                return Err(format!("outer {axis} ({outer}) exceeds available screen {axis} ({avail})"));
+            }
+            if avail > screen {
+                return Err(format!("available {axis} ({avail}) exceeds screen {axis} ({screen})"));
+            }
+        }
+        if !self.device_pixel_ratio.is_finite() || self.device_pixel_ratio <= 0.0 {
+            return Err(format!("invalid devicePixelRatio {}", self.device_pixel_ratio));
+        }
+        Ok(())
+    }
+
+    /// Generates one override block for all size-related properties.
+    ///
+    /// `visualViewport` reports the layout viewport at scale 1 with no
+    /// offset, matching a page that has not been pinch-zoomed.
+    pub fn to_js_overrides(&self) -> String {
+        format!(
+            r#"
+(function() {{
+    const define = (obj, prop, value) => {{
+        if (!obj) return;
+        Object.defineProperty(obj, prop, {{ get: function() {{ return value; }}, configurable: true }});
+    }};
+    define(screen, 'width', {screen_width});
+    define(screen, 'height', {screen_height});
+    define(screen, 'availWidth', {avail_width});
+    define(screen, 'availHeight', {avail_height});
+    define(window, 'outerWidth', {outer_width});
+    define(window, 'outerHeight', {outer_height});
+    define(window, 'innerWidth', {inner_width});
+    define(window, 'innerHeight', {inner_height});
+    define(window, 'devicePixelRatio', {dpr});
+    if (window.visualViewport) {{
+        const vv = Object.getPrototypeOf(window.visualViewport);
+        define(vv, 'width', {inner_width});
+        define(vv, 'height', {inner_height});
+        define(vv, 'scale', 1);
+        define(vv, 'offsetLeft', 0);
+        define(vv, 'offsetTop', 0);
+    }}
+    window.__fp_outer_applied = true;
+}})();
+"#,
+            screen_width = self.screen_width,
+            screen_height = self.screen_height,
+            avail_width = self.avail_width,
+            avail_height = self.avail_height,
+            outer_width = self.outer_width,
+            outer_height = self.outer_height,
+            inner_width = self.inner_width,
+            inner_height = self.inner_height,
+            dpr = self.device_pixel_ratio,
+        )
+    }
+}
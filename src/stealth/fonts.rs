@@ -0,0 +1,322 @@
+//! Font Enumeration Spoofing
+//!
+//! This module limits which fonts pages can detect as installed. The set of
+//! installed fonts is a high-entropy fingerprinting signal: scripts probe a
+//! long list of family names through the `FontFaceSet` API
+//! (`document.fonts.check()` / `document.fonts.load()`) and keep the ones
+//! that report as available.
+//!
+//! The override makes the probes answer from a configured font set instead,
+//! defaulting to what a stock installation of the profile's OS ships.
+//!
+//! # Components
+//!
+//! - `FontConfig` - The reported font set and the override script
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use ki_browser_standalone::stealth::fingerprint::FingerprintProfile;
+//! use ki_browser_standalone::stealth::fonts::FontConfig;
+//!
+//! // Windows 10 defaults
+//! let config = FontConfig::default();
+//!
+//! // Or the default set of a profile's OS
+//! let config = FontConfig::for_profile(&FingerprintProfile::MacSafari);
+//!
+//! // Get the JavaScript override script
+//! let js = config.generate_override_script();
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::fingerprint::FingerprintProfile;
+use super::native_functions::mark_native_script;
+
+/// Fonts shipped with Windows 10, as seen by Chrome
+const WINDOWS_FONTS: [&str; 30] = [
+    "Arial",
+    "Arial Black",
+    "Bahnschrift",
+    "Calibri",
+    "Cambria",
+    "Cambria Math",
+    "Candara",
+    "Comic Sans MS",
+    "Consolas",
+    "Constantia",
+    "Corbel",
+    "Courier New",
+    "Ebrima",
+    "Franklin Gothic Medium",
+    "Gabriola",
+    "Gadugi",
+    "Georgia",
+    "Impact",
+    "Ink Free",
+    "Lucida Console",
+    "Lucida Sans Unicode",
+    "Malgun Gothic",
+    "Microsoft Sans Serif",
+    "Palatino Linotype",
+    "Segoe UI",
+    "Segoe UI Emoji",
+    "Tahoma",
+    "Times New Roman",
+    "Trebuchet MS",
+    "Verdana",
+];
+
+/// Fonts shipped with macOS
+const MACOS_FONTS: [&str; 30] = [
+    "American Typewriter",
+    "Andale Mono",
+    "Arial",
+    "Arial Black",
+    "Avenir",
+    "Avenir Next",
+    "Baskerville",
+    "Chalkboard",
+    "Comic Sans MS",
+    "Courier",
+    "Courier New",
+    "Futura",
+    "Geneva",
+    "Georgia",
+    "Gill Sans",
+    "Helvetica",
+    "Helvetica Neue",
+    "Impact",
+    "Lucida Grande",
+    "Menlo",
+    "Monaco",
+    "Optima",
+    "Palatino",
+    "SF Pro",
+    "Tahoma",
+    "Times",
+    "Times New Roman",
+    "Trebuchet MS",
+    "Verdana",
+    "Zapfino",
+];
+
+/// Fonts of a typical desktop Linux installation
+const LINUX_FONTS: [&str; 16] = [
+    "Cantarell",
+    "DejaVu Sans",
+    "DejaVu Sans Mono",
+    "DejaVu Serif",
+    "FreeMono",
+    "FreeSans",
+    "FreeSerif",
+    "Liberation Mono",
+    "Liberation Sans",
+    "Liberation Serif",
+    "Noto Color Emoji",
+    "Noto Mono",
+    "Noto Sans",
+    "Noto Serif",
+    "Ubuntu",
+    "Ubuntu Mono",
+];
+
+/// Font enumeration spoofing configuration
+///
+/// Pages probing for a family outside [`font_set`](Self::font_set) are told
+/// it is not installed. Generic families and web fonts the page loaded
+/// itself are left alone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontConfig {
+    /// Enable font enumeration spoofing
+    ///
+    /// When false, no font APIs are modified.
+    pub enabled: bool,
+    /// Families reported as installed
+    pub font_set: Vec<String>,
+}
+
+impl FontConfig {
+    /// Create a configuration reporting exactly `fonts` as installed
+    pub fn new<I, S>(fonts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            enabled: true,
+            font_set: fonts.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Create a disabled configuration (no font protection)
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            font_set: Vec::new(),
+        }
+    }
+
+    /// Default fonts of Windows 10
+    pub fn windows_default() -> Self {
+        Self::new(WINDOWS_FONTS)
+    }
+
+    /// Default fonts of macOS
+    pub fn macos_default() -> Self {
+        Self::new(MACOS_FONTS)
+    }
+
+    /// Fonts of a typical desktop Linux installation
+    pub fn linux_default() -> Self {
+        Self::new(LINUX_FONTS)
+    }
+
+    /// Default fonts of the profile's operating system
+    ///
+    /// `Custom` profiles get the Windows set, the most common one.
+    pub fn for_profile(profile: &FingerprintProfile) -> Self {
+        match profile {
+            FingerprintProfile::WindowsChrome
+            | FingerprintProfile::WindowsFirefox
+            | FingerprintProfile::WindowsEdge
+            | FingerprintProfile::Custom => Self::windows_default(),
+            FingerprintProfile::MacChrome
+            | FingerprintProfile::MacSafari
+            | FingerprintProfile::MacFirefox => Self::macos_default(),
+            FingerprintProfile::LinuxChrome | FingerprintProfile::LinuxFirefox => {
+                Self::linux_default()
+            }
+        }
+    }
+
+    /// The font set as a JavaScript array literal
+    pub fn font_list_json(&self) -> String {
+        serde_json::to_string(&self.font_set).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Generate JavaScript override script for font enumeration spoofing
+    ///
+    /// This script must be injected before any page scripts run.
+    pub fn generate_override_script(&self) -> String {
+        if !self.enabled {
+            return "// Font spoofing disabled".to_string();
+        }
+
+        format!(
+            r#"
+// Font Enumeration Spoofing
+(function() {{
+    'use strict';
+
+    if (typeof FontFaceSet === 'undefined' || !document.fonts) return;
+
+    const installed = new Set({fonts}.map((f) => f.toLowerCase()));
+    const generic = new Set([
+        'serif', 'sans-serif', 'monospace', 'cursive', 'fantasy', 'system-ui',
+        'ui-serif', 'ui-sans-serif', 'ui-monospace', 'ui-rounded', 'math',
+        'emoji', 'fangsong', 'inherit', 'initial', 'unset'
+    ]);
+
+    const proto = FontFaceSet.prototype;
+    const originalCheck = proto.check;
+    const originalLoad = proto.load;
+    const originalForEach = proto.forEach;
+
+    // Families of a CSS font shorthand: everything after the size
+    // (and optional line height), split on commas
+    function families(font) {{
+        const match = /(?:^|\s)[\d.]+(?:px|pt|pc|em|rem|ex|ch|%|vw|vh|in|cm|mm|q)?(?:\s*\/\s*\S+)?\s+(.+)$/i.exec(String(font).trim());
+        if (!match) return [];
+        return match[1].split(',').map((f) => f.trim().replace(/^['"]|['"]$/g, '').toLowerCase()).filter(Boolean);
+    }}
+
+    // Families declared through @font-face or the FontFace API
+    function webFonts(set) {{
+        const names = new Set();
+        try {{
+            originalForEach.call(set, (face) => names.add(String(face.family).replace(/^['"]|['"]$/g, '').toLowerCase()));
+        }} catch (e) {{}}
+        return names;
+    }}
+
+    // Whether any requested family is a system font outside the set
+    function probesHiddenFont(set, font) {{
+        const declared = webFonts(set);
+        return families(font).some((f) => !generic.has(f) && !declared.has(f) && !installed.has(f));
+    }}
+
+    proto.check = function check(font, text) {{
+        if (probesHiddenFont(this, font)) {{
+            return false;
+        }}
+        return originalCheck.apply(this, arguments);
+    }};
+
+    proto.load = function load(font, text) {{
+        if (probesHiddenFont(this, font)) {{
+            return Promise.resolve([]);
+        }}
+        return originalLoad.apply(this, arguments);
+    }};
+
+    // Make the overrides look native
+    {mark_native}
+    markNative(proto.check, 'check');
+    markNative(proto.load, 'load');
+}})();
+"#,
+            fonts = self.font_list_json(),
+            mark_native = mark_native_script(),
+        )
+    }
+}
+
+impl Default for FontConfig {
+    fn default() -> Self {
+        Self::windows_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_windows_set() {
+        let config = FontConfig::default();
+        assert!(config.enabled);
+        assert_eq!(config.font_set.len(), 30);
+        assert!(config.font_set.iter().any(|f| f == "Segoe UI"));
+    }
+
+    #[test]
+    fn test_script_hooks_font_face_set() {
+        let js = FontConfig::default().generate_override_script();
+        assert!(js.contains("FontFaceSet"));
+        assert!(js.contains("proto.check = function check"));
+        assert!(js.contains("proto.load = function load"));
+        assert!(js.contains(r#""Segoe UI""#));
+        assert!(js.contains("markNative(proto.check, 'check');"));
+        assert_eq!(js.matches("new Proxy(").count(), 1);
+    }
+
+    #[test]
+    fn test_profiles_report_their_os_fonts() {
+        let windows = FontConfig::for_profile(&FingerprintProfile::WindowsChrome);
+        let mac = FontConfig::for_profile(&FingerprintProfile::MacSafari);
+        assert_ne!(windows.font_list_json(), mac.font_list_json());
+        assert!(mac.font_set.iter().any(|f| f == "Helvetica Neue"));
+        assert!(!mac.font_set.iter().any(|f| f == "Segoe UI"));
+
+        let linux = FontConfig::for_profile(&FingerprintProfile::LinuxFirefox);
+        assert!(linux.font_set.iter().any(|f| f == "DejaVu Sans"));
+    }
+
+    #[test]
+    fn test_disabled_config() {
+        let config = FontConfig::disabled();
+        assert!(!config.generate_override_script().contains("FontFaceSet"));
+    }
+}
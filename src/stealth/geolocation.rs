@@ -0,0 +1,282 @@
+//! Geolocation API Spoofing
+//!
+//! This module replaces the position reported by `navigator.geolocation`.
+//! Sites compare the browser position with the location of the client IP
+//! and the timezone, so a real position next to a spoofed timezone (or a
+//! proxy exit in another country) is an easy correlation signal.
+//!
+//! # Components
+//!
+//! - `GeoConfig` - Reported coordinates and the override script
+//! - Timezone lookup, so the position matches the fingerprint's timezone
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use ki_browser_standalone::stealth::geolocation::GeoConfig;
+//!
+//! // A position in the timezone's main city
+//! let config = GeoConfig::for_timezone("America/New_York");
+//!
+//! // Or exact coordinates
+//! let config = GeoConfig::new(52.52, 13.405, 50.0);
+//!
+//! // Get the JavaScript override script
+//! let js = config.generate_override_script();
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use super::native_functions::mark_native_script;
+
+/// Accuracy in meters reported for timezone-derived positions
+const DEFAULT_ACCURACY: f64 = 100.0;
+
+/// Largest offset in degrees `consistent` adds to a city position (~5 km)
+const MAX_JITTER_DEGREES: f64 = 0.05;
+
+/// Main city position of well-known timezones: (timezone, latitude, longitude)
+const TIMEZONE_POSITIONS: &[(&str, f64, f64)] = &[
+    ("America/New_York", 40.7128, -74.0060),
+    ("America/Chicago", 41.8781, -87.6298),
+    ("America/Denver", 39.7392, -104.9903),
+    ("America/Los_Angeles", 34.0522, -118.2437),
+    ("America/Phoenix", 33.4484, -112.0740),
+    ("America/Toronto", 43.6532, -79.3832),
+    ("America/Vancouver", 49.2827, -123.1207),
+    ("America/Mexico_City", 19.4326, -99.1332),
+    ("America/Sao_Paulo", -23.5505, -46.6333),
+    ("America/Buenos_Aires", -34.6037, -58.3816),
+    ("Europe/London", 51.5074, -0.1278),
+    ("Europe/Dublin", 53.3498, -6.2603),
+    ("Europe/Paris", 48.8566, 2.3522),
+    ("Europe/Berlin", 52.5200, 13.4050),
+    ("Europe/Madrid", 40.4168, -3.7038),
+    ("Europe/Rome", 41.9028, 12.4964),
+    ("Europe/Amsterdam", 52.3676, 4.9041),
+    ("Europe/Vienna", 48.2082, 16.3738),
+    ("Europe/Zurich", 47.3769, 8.5417),
+    ("Europe/Stockholm", 59.3293, 18.0686),
+    ("Europe/Warsaw", 52.2297, 21.0122),
+    ("Europe/Moscow", 55.7558, 37.6173),
+    ("Asia/Kolkata", 19.0760, 72.8777),
+    ("Asia/Shanghai", 31.2304, 121.4737),
+    ("Asia/Hong_Kong", 22.3193, 114.1694),
+    ("Asia/Singapore", 1.3521, 103.8198),
+    ("Asia/Seoul", 37.5665, 126.9780),
+    ("Asia/Tokyo", 35.6762, 139.6503),
+    ("Australia/Sydney", -33.8688, 151.2093),
+    ("Australia/Melbourne", -37.8136, 144.9631),
+    ("Pacific/Auckland", -36.8485, 174.7633),
+];
+
+/// Geolocation spoofing configuration
+///
+/// When `spoof_enabled` is set, `getCurrentPosition` and `watchPosition`
+/// answer with the configured coordinates instead of the real position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeoConfig {
+    /// Latitude in degrees, -90.0 to 90.0
+    pub latitude: f64,
+    /// Longitude in degrees, -180.0 to 180.0
+    pub longitude: f64,
+    /// Reported accuracy radius in meters
+    pub accuracy: f64,
+    /// Replace the Geolocation API
+    ///
+    /// When false, no geolocation modifications are applied.
+    pub spoof_enabled: bool,
+}
+
+impl GeoConfig {
+    /// Create an enabled configuration reporting the given position
+    pub fn new(latitude: f64, longitude: f64, accuracy: f64) -> Self {
+        Self {
+            latitude: latitude.clamp(-90.0, 90.0),
+            longitude: longitude.clamp(-180.0, 180.0),
+            accuracy: accuracy.max(0.0),
+            spoof_enabled: true,
+        }
+    }
+
+    /// Create a disabled configuration (real position)
+    pub fn disabled() -> Self {
+        Self {
+            latitude: 0.0,
+            longitude: 0.0,
+            accuracy: 0.0,
+            spoof_enabled: false,
+        }
+    }
+
+    /// Position of the main city of an IANA timezone
+    ///
+    /// Unknown timezones give a disabled configuration, since any made-up
+    /// position would be as likely to contradict the timezone.
+    pub fn for_timezone(timezone: &str) -> Self {
+        TIMEZONE_POSITIONS
+            .iter()
+            .find(|(tz, _, _)| *tz == timezone)
+            .map(|&(_, latitude, longitude)| Self::new(latitude, longitude, DEFAULT_ACCURACY))
+            .unwrap_or_else(Self::disabled)
+    }
+
+    /// [`for_timezone`](Self::for_timezone), moved a few kilometers in a
+    /// direction derived from `seed`
+    ///
+    /// Keeps sessions with the same timezone from sharing one exact position
+    /// while the same seed always yields the same one.
+    pub fn consistent(timezone: &str, seed: &str) -> Self {
+        let mut config = Self::for_timezone(timezone);
+        if !config.spoof_enabled {
+            return config;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        let hash = hasher.finish();
+        let unit = |bits: u64| (bits & 0xFFFF) as f64 / 65535.0 * 2.0 - 1.0;
+        config.latitude = (config.latitude + unit(hash) * MAX_JITTER_DEGREES).clamp(-90.0, 90.0);
+        config.longitude =
+            (config.longitude + unit(hash >> 16) * MAX_JITTER_DEGREES).clamp(-180.0, 180.0);
+        config
+    }
+
+    /// Generate JavaScript override script for geolocation spoofing
+    ///
+    /// This script must be injected before any page scripts run.
+    pub fn generate_override_script(&self) -> String {
+        if !self.spoof_enabled {
+            return "// Geolocation spoofing disabled".to_string();
+        }
+
+        format!(
+            r#"
+// Geolocation Spoofing
+(function() {{
+    'use strict';
+
+    if (typeof Geolocation === 'undefined' || !navigator.geolocation) return;
+
+    const LATITUDE = {latitude};
+    const LONGITUDE = {longitude};
+    const ACCURACY = {accuracy};
+
+    // Plain object on the native prototype, so instanceof checks still pass
+    function withPrototype(ctor, values) {{
+        let target = {{}};
+        try {{
+            if (typeof ctor === 'function') target = Object.create(ctor.prototype);
+        }} catch (e) {{}}
+        for (const key of Object.keys(values)) {{
+            Object.defineProperty(target, key, {{ value: values[key], enumerable: true }});
+        }}
+        Object.defineProperty(target, 'toJSON', {{ value: function toJSON() {{ return values; }} }});
+        return target;
+    }}
+
+    function makePosition() {{
+        const coords = withPrototype(window.GeolocationCoordinates, {{
+            latitude: LATITUDE,
+            longitude: LONGITUDE,
+            altitude: null,
+            accuracy: ACCURACY,
+            altitudeAccuracy: null,
+            heading: null,
+            speed: null
+        }});
+        return withPrototype(window.GeolocationPosition, {{ coords: coords, timestamp: Date.now() }});
+    }}
+
+    const watches = new Map();
+    let nextWatchId = 1;
+
+    function requireCallback(method, success) {{
+        if (typeof success !== 'function') {{
+            throw new TypeError(
+                "Failed to execute '" + method + "' on 'Geolocation': " +
+                "parameter 1 is not of type 'Function'.");
+        }}
+    }}
+
+    const proto = Geolocation.prototype;
+
+    proto.getCurrentPosition = function getCurrentPosition(success, error, options) {{
+        requireCallback('getCurrentPosition', success);
+        setTimeout(() => success(makePosition()), 0);
+    }};
+
+    proto.watchPosition = function watchPosition(success, error, options) {{
+        requireCallback('watchPosition', success);
+        const id = nextWatchId++;
+        watches.set(id, success);
+        setTimeout(() => {{
+            if (watches.has(id)) success(makePosition());
+        }}, 0);
+        return id;
+    }};
+
+    proto.clearWatch = function clearWatch(id) {{
+        watches.delete(id);
+    }};
+
+    // Make the overrides look native
+    {mark_native}
+    markNative(proto.getCurrentPosition, 'getCurrentPosition');
+    markNative(proto.watchPosition, 'watchPosition');
+    markNative(proto.clearWatch, 'clearWatch');
+}})();
+"#,
+            latitude = self.latitude,
+            longitude = self.longitude,
+            accuracy = self.accuracy,
+            mark_native = mark_native_script(),
+        )
+    }
+}
+
+impl Default for GeoConfig {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_timezone() {
+        let config = GeoConfig::for_timezone("America/New_York");
+        assert!(config.spoof_enabled);
+        assert!((config.latitude - 40.7).abs() < 0.1);
+        assert!((config.longitude + 74.0).abs() < 0.1);
+
+        assert!(!GeoConfig::for_timezone("Mars/Olympus_Mons").spoof_enabled);
+    }
+
+    #[test]
+    fn test_consistent_stays_near_city() {
+        let a = GeoConfig::consistent("Europe/Berlin", "seed");
+        let b = GeoConfig::consistent("Europe/Berlin", "seed");
+        assert_eq!(a, b);
+        assert!((a.latitude - 52.52).abs() <= MAX_JITTER_DEGREES + 1e-9);
+        assert!((a.longitude - 13.405).abs() <= MAX_JITTER_DEGREES + 1e-9);
+    }
+
+    #[test]
+    fn test_script_replaces_position_methods() {
+        let js = GeoConfig::new(52.52, 13.405, 50.0).generate_override_script();
+        assert!(js.contains("getCurrentPosition"));
+        assert!(js.contains("watchPosition"));
+        assert!(js.contains("const LATITUDE = 52.52;"));
+        assert!(js.contains("const ACCURACY = 50;"));
+        assert!(js.contains("markNative(proto.watchPosition, 'watchPosition');"));
+        assert_eq!(js.matches("new Proxy(").count(), 1);
+
+        let js = GeoConfig::disabled().generate_override_script();
+        assert!(!js.contains("getCurrentPosition"));
+    }
+}
@@ -0,0 +1,287 @@
+//! Minification of the injected override scripts.
+//!
+//! The generated stealth script is written for readability: section banners,
+//! explanatory comments and indentation make up a large part of it. Since it
+//! is injected on every navigation, [`minify_js`] can shrink it by removing
+//! comments and collapsing whitespace.
+//!
+//! This is deliberately not a full minifier. Identifiers are never renamed
+//! and line breaks are kept wherever automatic semicolon insertion might
+//! depend on them. String, template and regex literals are copied verbatim.
+
+use parking_lot::Mutex;
+
+/// Minify `source` by stripping comments and collapsing whitespace.
+///
+/// Literals are left untouched, so the output behaves like the input.
+pub fn minify_js(source: &str) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let mut out = String::with_capacity(source.len());
+    // Whitespace seen since the last emitted token: None, space or newline
+    let mut pending: Option<char> = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            '/' if next == Some('/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if next == Some('*') => {
+                let start = i;
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                let has_newline = chars[start..i.min(chars.len())].contains(&'\n');
+                i += 2;
+                pending = merge_whitespace(pending, if has_newline { '\n' } else { ' ' });
+            }
+            c if c.is_whitespace() => {
+                pending = merge_whitespace(pending, if c == '\n' { '\n' } else { ' ' });
+                i += 1;
+            }
+            _ => {
+                if let Some(ws) = pending.take() {
+                    if keeps_whitespace(&out, c, ws) {
+                        out.push(ws);
+                    }
+                }
+                i = match c {
+                    '"' | '\'' => copy_string(&chars, i, &mut out),
+                    '`' => copy_template(&chars, i, &mut out),
+                    '/' if regex_allowed(&out) => copy_regex(&chars, i, &mut out),
+                    _ => {
+                        out.push(c);
+                        i + 1
+                    }
+                };
+            }
+        }
+    }
+
+    out
+}
+
+/// A newline wins over a space, so line breaks relevant to ASI survive.
+fn merge_whitespace(pending: Option<char>, ws: char) -> Option<char> {
+    match pending {
+        Some('\n') => Some('\n'),
+        _ => Some(ws),
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$' || !c.is_ascii()
+}
+
+/// Whether whitespace `ws` between the output so far and `next` is needed.
+fn keeps_whitespace(out: &str, next: char, ws: char) -> bool {
+    let Some(prev) = out.chars().last() else {
+        return false;
+    };
+    if ws == '\n' {
+        // Joining after these or before closers cannot change ASI
+        return !matches!(prev, '{' | '(' | '[' | ',' | ';' | ':')
+            && !matches!(next, '}' | ')' | ']' | ',' | ';');
+    }
+    if is_ident_char(prev) && is_ident_char(next) {
+        return true;
+    }
+    // `1 .toString()` is not `1.toString()`
+    if prev.is_ascii_digit() && next == '.' {
+        return true;
+    }
+    // Keep `a + +b`, `a - -b` and `a / /re/` apart
+    matches!(prev, '+' | '-' | '/') && prev == next
+}
+
+/// Whether a `/` after the output so far starts a regex rather than a division.
+fn regex_allowed(out: &str) -> bool {
+    let trimmed = out.trim_end();
+    let Some(prev) = trimmed.chars().last() else {
+        return true;
+    };
+    if is_ident_char(prev) {
+        let word: String = trimmed
+            .chars()
+            .rev()
+            .take_while(|c| is_ident_char(*c))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect();
+        return matches!(
+            word.as_str(),
+            "return" | "typeof" | "case" | "do" | "else" | "in" | "of" | "new" | "delete" | "void"
+        );
+    }
+    !matches!(prev, ')' | ']' | '}')
+}
+
+/// Copy the string literal starting at `start`; returns the index after it.
+fn copy_string(chars: &[char], start: usize, out: &mut String) -> usize {
+    let quote = chars[start];
+    out.push(quote);
+    let mut i = start + 1;
+    while i < chars.len() {
+        let c = chars[i];
+        out.push(c);
+        i += 1;
+        if c == '\\' {
+            if let Some(&escaped) = chars.get(i) {
+                out.push(escaped);
+                i += 1;
+            }
+        } else if c == quote || c == '\n' {
+            break;
+        }
+    }
+    i
+}
+
+/// Copy the regex literal starting at `start`; returns the index after it.
+fn copy_regex(chars: &[char], start: usize, out: &mut String) -> usize {
+    out.push('/');
+    let mut i = start + 1;
+    let mut in_class = false;
+    while i < chars.len() {
+        let c = chars[i];
+        out.push(c);
+        i += 1;
+        match c {
+            '\\' => {
+                if let Some(&escaped) = chars.get(i) {
+                    out.push(escaped);
+                    i += 1;
+                }
+            }
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '/' if !in_class => break,
+            '\n' => break,
+            _ => {}
+        }
+    }
+    i
+}
+
+/// Copy the template literal starting at `start`, including nested
+/// `${...}` expressions; returns the index after it.
+fn copy_template(chars: &[char], start: usize, out: &mut String) -> usize {
+    out.push('`');
+    let mut i = start + 1;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' => {
+                out.push(c);
+                if let Some(&escaped) = chars.get(i + 1) {
+                    out.push(escaped);
+                }
+                i += 2;
+            }
+            '`' => {
+                out.push(c);
+                return i + 1;
+            }
+            '$' if chars.get(i + 1) == Some(&'{') => {
+                out.push_str("${");
+                i = copy_template_expression(chars, i + 2, out);
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    i
+}
+
+/// Copy a `${...}` expression body verbatim up to and including its `}`.
+fn copy_template_expression(chars: &[char], start: usize, out: &mut String) -> usize {
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' | '\'' => i = copy_string(chars, i, out),
+            '`' => i = copy_template(chars, i, out),
+            '{' => {
+                depth += 1;
+                out.push(c);
+                i += 1;
+            }
+            '}' => {
+                out.push(c);
+                i += 1;
+                if depth == 0 {
+                    return i;
+                }
+                depth -= 1;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    i
+}
+
+/// Remembers the last minified script so it is only recomputed when the
+/// generated source changes.
+#[derive(Debug, Default)]
+pub struct MinifiedScriptCache {
+    entry: Mutex<Option<(String, String)>>,
+}
+
+impl MinifiedScriptCache {
+    /// Returns the minified form of `source`, minifying it only on a miss.
+    pub fn get_or_minify(&self, source: String) -> String {
+        let mut entry = self.entry.lock();
+        if let Some((cached_source, minified)) = entry.as_ref() {
+            if *cached_source == source {
+                return minified.clone();
+            }
+        }
+        let minified = minify_js(&source);
+        *entry = Some((source, minified.clone()));
+        minified
+    }
+}
+
+impl Clone for MinifiedScriptCache {
+    fn clone(&self) -> Self {
+        Self {
+            entry: Mutex::new(self.entry.lock().clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minify_keeps_literals_intact() {
+        let source = r#"
+            // comment
+            var url = "http://example.com/*x*/"; /* block */
+            var re = /\/\/ not a comment/g;
+            var t = `a  ${ "}" }  b // kept`;
+            var n = a + +b;
+            return x
+        "#;
+        let minified = minify_js(source);
+        assert_eq!(
+            minified,
+            "var url=\"http://example.com/*x*/\";var re=/\\/\\/ not a comment/g;\
+             var t=`a  ${ \"}\" }  b // kept`;var n=a+ +b;return x"
+        );
+    }
+}
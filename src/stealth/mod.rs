@@ -12,6 +12,14 @@
 //! - `webrtc` - WebRTC leak prevention to protect real IP addresses
 //! - `canvas` - Canvas fingerprint protection with noise injection
 //! - `audio` - AudioContext fingerprint spoofing
+//! - `battery` - Battery Status API spoofing
+//! - `fonts` - Font enumeration spoofing via the `FontFaceSet` API
+//! - `geolocation` - Geolocation API spoofing matching the timezone
+//! - `timezone` - Timezone spoofing with daylight saving time for `Date` and `Intl`
+//! - `client_hints` - User-Agent Client Hints matching the spoofed user agent
+//! - `api_surface` - Removal of selected JavaScript APIs instead of spoofing them
+//! - `minify` - Comment and whitespace stripping for the injected script
+//! - `native_functions` - Native-looking `toString` shared by all overrides
 //!
 //! # Security Considerations
 //!
@@ -50,23 +58,48 @@
 //! let audio = AudioConfig::default();
 //! ```
 
+pub mod api_surface;
 pub mod audio;
+pub mod battery;
 pub mod canvas;
+pub mod client_hints;
 pub mod fingerprint;
+pub mod fonts;
+pub mod geolocation;
+pub mod minify;
+pub mod native_functions;
 pub mod navigator;
+pub mod timezone;
 pub mod webgl;
 pub mod webrtc;
 
+use serde::{Deserialize, Serialize};
+
+use crate::config::BrowserSettings;
+
 // Re-export commonly used types for convenience
+pub use api_surface::ApiSurface;
 pub use audio::AudioConfig;
-pub use canvas::CanvasConfig;
-pub use fingerprint::{BrowserFingerprint, FingerprintGenerator, FingerprintProfile};
-pub use navigator::{MimeTypeInfo, NavigatorOverrides, PluginInfo};
+pub use battery::BatteryConfig;
+pub use canvas::{CanvasConfig, CanvasNoiseConfig};
+pub use client_hints::ClientHints;
+pub use fingerprint::{BrowserFingerprint, FingerprintGenerator, FingerprintProfile, ViewportMetrics};
+pub use fonts::FontConfig;
+pub use geolocation::GeoConfig;
+pub use navigator::{
+    ConnectionProfile, MediaDevicesConfig, MimeTypeInfo, NavigatorOverrides,
+    NotificationPermission, PluginInfo,
+};
+pub use timezone::TimezoneConfig;
 pub use webgl::{WebGLConfig, WebGLProfile};
-pub use webrtc::{WebRtcConfig, WebRtcIpPolicy};
+pub use webrtc::{IceCandidateFilter, WebRtcConfig, WebRtcIpPolicy, WebRtcPolicy};
 
 /// Combined stealth configuration for easy setup
-#[derive(Debug, Clone)]
+///
+/// Serializable to JSON and TOML (see [`to_json`](Self::to_json) and
+/// [`to_toml`](Self::to_toml)), so an exact identity can be persisted and
+/// reloaded later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StealthConfig {
     /// Browser fingerprint configuration
     pub fingerprint: BrowserFingerprint,
@@ -78,8 +111,30 @@ pub struct StealthConfig {
     pub webrtc: WebRtcConfig,
     /// Canvas fingerprint protection configuration
     pub canvas: CanvasConfig,
+    /// Seed and magnitude of the canvas noise
+    pub canvas_noise: CanvasNoiseConfig,
     /// AudioContext fingerprint spoofing configuration
     pub audio: AudioConfig,
+    /// Battery state reported by `navigator.getBattery()` while the
+    /// navigator's battery spoofing is enabled
+    pub battery: BatteryConfig,
+    /// Fonts reported as installed to font probes
+    pub fonts: FontConfig,
+    /// Position reported by `navigator.geolocation`
+    pub geolocation: GeoConfig,
+    /// Timezone reported by `Date` and `Intl.DateTimeFormat`
+    #[serde(default)]
+    pub timezone: TimezoneConfig,
+    /// Coordinated screen/window/viewport metrics, set by `sync_screen_to_viewport`
+    pub viewport: Option<ViewportMetrics>,
+    /// APIs removed from pages entirely (see [`api_surface`] for the tradeoff)
+    pub disable_apis: Vec<ApiSurface>,
+    /// Strip comments and whitespace from the complete override script.
+    /// Off by default so the injected script stays readable when debugging.
+    pub minify: bool,
+    /// Last minified override script, reused while the source is unchanged
+    #[serde(skip)]
+    minified_script: minify::MinifiedScriptCache,
 }
 
 impl StealthConfig {
@@ -87,10 +142,16 @@ impl StealthConfig {
     pub fn from_profile(profile: FingerprintProfile) -> Self {
         let fingerprint = FingerprintGenerator::new().generate_from_profile(profile.clone());
         let webgl = WebGLConfig::for_profile(&profile);
-        let navigator = NavigatorOverrides::from_fingerprint(&fingerprint);
+        let mut navigator = NavigatorOverrides::from_fingerprint(&fingerprint);
         let webrtc = WebRtcConfig::default();
         let canvas = CanvasConfig::default();
-        let audio = AudioConfig::default();
+        let canvas_noise = CanvasNoiseConfig::random();
+        let audio = AudioConfig::random();
+        let battery = BatteryConfig::random_from_seed(rand::random());
+        let fonts = FontConfig::for_profile(&fingerprint.profile);
+        let geolocation = GeoConfig::for_timezone(&fingerprint.timezone);
+        let timezone = TimezoneConfig::new(fingerprint.timezone.clone());
+        navigator.battery = Some(battery.clone());
 
         Self {
             fingerprint,
@@ -98,18 +159,33 @@ impl StealthConfig {
             navigator,
             webrtc,
             canvas,
+            canvas_noise,
             audio,
+            battery,
+            fonts,
+            geolocation,
+            timezone,
+            viewport: None,
+            disable_apis: Vec::new(),
+            minify: false,
+            minified_script: Default::default(),
         }
     }
 
     /// Create a randomized stealth configuration
     pub fn random() -> Self {
         let fingerprint = FingerprintGenerator::new().generate_random();
-        let webgl = WebGLConfig::random();
-        let navigator = NavigatorOverrides::from_fingerprint(&fingerprint);
+        let webgl = WebGLConfig::for_profile(&fingerprint.profile);
+        let mut navigator = NavigatorOverrides::from_fingerprint(&fingerprint);
         let webrtc = WebRtcConfig::default();
         let canvas = CanvasConfig::default();
-        let audio = AudioConfig::default();
+        let canvas_noise = CanvasNoiseConfig::random();
+        let audio = AudioConfig::random();
+        let battery = BatteryConfig::random_from_seed(rand::random());
+        let fonts = FontConfig::for_profile(&fingerprint.profile);
+        let geolocation = GeoConfig::for_timezone(&fingerprint.timezone);
+        let timezone = TimezoneConfig::new(fingerprint.timezone.clone());
+        navigator.battery = Some(battery.clone());
 
         Self {
             fingerprint,
@@ -117,7 +193,16 @@ impl StealthConfig {
             navigator,
             webrtc,
             canvas,
+            canvas_noise,
             audio,
+            battery,
+            fonts,
+            geolocation,
+            timezone,
+            viewport: None,
+            disable_apis: Vec::new(),
+            minify: false,
+            minified_script: Default::default(),
         }
     }
 
@@ -144,11 +229,17 @@ impl StealthConfig {
     /// Create a consistent stealth configuration based on a seed
     pub fn consistent(seed: &str) -> Self {
         let fingerprint = FingerprintGenerator::new().generate_consistent(seed);
-        let webgl = WebGLConfig::consistent(seed);
-        let navigator = NavigatorOverrides::from_fingerprint(&fingerprint);
+        let webgl = WebGLConfig::consistent_for_profile(&fingerprint.profile, seed);
+        let mut navigator = NavigatorOverrides::from_fingerprint(&fingerprint);
         let webrtc = WebRtcConfig::default();
         let canvas = CanvasConfig::default();
-        let audio = AudioConfig::default();
+        let canvas_noise = CanvasNoiseConfig::consistent(seed);
+        let audio = AudioConfig::consistent(seed);
+        let battery = BatteryConfig::consistent(seed);
+        let fonts = FontConfig::for_profile(&fingerprint.profile);
+        let geolocation = GeoConfig::consistent(&fingerprint.timezone, seed);
+        let timezone = TimezoneConfig::new(fingerprint.timezone.clone());
+        navigator.battery = Some(battery.clone());
 
         Self {
             fingerprint,
@@ -156,10 +247,25 @@ impl StealthConfig {
             navigator,
             webrtc,
             canvas,
+            canvas_noise,
             audio,
+            battery,
+            fonts,
+            geolocation,
+            timezone,
+            viewport: None,
+            disable_apis: Vec::new(),
+            minify: false,
+            minified_script: Default::default(),
         }
     }
 
+    /// Replace the WebRTC leak prevention with a preset policy
+    pub fn with_webrtc_policy(mut self, policy: WebRtcPolicy) -> Self {
+        self.webrtc = WebRtcConfig::from_policy(policy);
+        self
+    }
+
     /// Synchronize the fingerprint's screen resolution to match the actual browser viewport.
     ///
     /// This ensures consistency between screen dimensions, outerWidth/Height, innerWidth/Height,
@@ -168,16 +274,51 @@ impl StealthConfig {
     ///
     /// Guarantees: screen.width >= outerWidth >= viewport_width (innerWidth)
     ///             screen.height >= outerHeight >= viewport_height (innerHeight)
+    ///
+    /// Also records the resulting [`ViewportMetrics`], which the override
+    /// scripts emit as one block after the fingerprint section.
     pub fn sync_screen_to_viewport(&mut self, viewport_width: u32, viewport_height: u32) {
         self.fingerprint
             .sync_screen_to_viewport(viewport_width, viewport_height);
+
+        // The offscreen renderer reports a device scale factor of 1.0 unless
+        // a device is emulated, which syncs through `DeviceMetrics` instead
+        let metrics = ViewportMetrics::new(
+            viewport_width,
+            viewport_height,
+            &self.fingerprint.screen_resolution,
+            1.0,
+        );
+        self.fingerprint.screen_resolution.outer_width = metrics.outer_width;
+        self.fingerprint.screen_resolution.outer_height = metrics.outer_height;
+        self.viewport = Some(metrics);
     }
 
     /// Generate the complete JavaScript override script
     ///
     /// This script should be injected before any page scripts run.
     /// It provides comprehensive protection against fingerprinting and detection.
+    ///
+    /// With [`minify`](Self::minify) set, the script is returned minified. The
+    /// minified form is cached, so repeated calls only redo the minification
+    /// after the configuration changed.
     pub fn get_complete_override_script(&self) -> String {
+        let script = self.build_complete_override_script();
+        if self.minify {
+            self.minified_script.get_or_minify(script)
+        } else {
+            script
+        }
+    }
+
+    /// The navigator overrides, with `battery` as the `getBattery()` state
+    /// unless battery spoofing is turned off in the navigator.
+    fn navigator_script(&self) -> String {
+        let battery = self.navigator.battery.as_ref().map(|_| &self.battery);
+        self.navigator.override_script_with_battery(battery)
+    }
+
+    fn build_complete_override_script(&self) -> String {
         let mut script = String::new();
 
         // Add IIFE wrapper for isolation
@@ -186,7 +327,7 @@ impl StealthConfig {
         // Navigator overrides (MOST CRITICAL - must run first)
         // Not wrapped in try/catch because a failure here is fatal
         script.push_str("// === NAVIGATOR OVERRIDES (CRITICAL) ===\n");
-        script.push_str(&self.navigator.get_override_script());
+        script.push_str(&self.navigator_script());
         script.push_str("\n\n");
 
         // Each subsequent section is wrapped in try/catch so that a failure
@@ -199,22 +340,49 @@ impl StealthConfig {
         script.push_str(&self.webgl.get_js_override_script());
         script.push_str("\n} catch(e) {}\n\n");
 
+        // WebRTC leak prevention (before the fingerprint, so a page that
+        // probes WebRTC first never sees the unfiltered constructor)
+        script.push_str("// === WEBRTC LEAK PREVENTION ===\n");
+        script.push_str("try {\n");
+        script.push_str(&self.webrtc.get_override_script());
+        script.push_str("\n} catch(e) {}\n\n");
+
         // Fingerprint overrides
         script.push_str("// === FINGERPRINT OVERRIDES ===\n");
         script.push_str("try {\n");
         script.push_str(&self.fingerprint.to_js_overrides());
         script.push_str("\n} catch(e) {}\n\n");
 
-        // WebRTC leak prevention
-        script.push_str("// === WEBRTC LEAK PREVENTION ===\n");
+        // Timezone (after the fingerprint, replacing its fixed offset)
+        script.push_str("// === TIMEZONE ===\n");
         script.push_str("try {\n");
-        script.push_str(&self.webrtc.get_override_script());
+        script.push_str(&self.timezone.generate_override_script());
+        script.push_str("\n} catch(e) {}\n\n");
+
+        // Viewport metrics (after the fingerprint so the coherent values win)
+        if let Some(viewport) = &self.viewport {
+            script.push_str("// === VIEWPORT METRICS ===\n");
+            script.push_str("try {\n");
+            script.push_str(&viewport.to_js_overrides());
+            script.push_str("\n} catch(e) {}\n\n");
+        }
+
+        // Geolocation spoofing
+        script.push_str("// === GEOLOCATION ===\n");
+        script.push_str("try {\n");
+        script.push_str(&self.geolocation.generate_override_script());
+        script.push_str("\n} catch(e) {}\n\n");
+
+        // Font enumeration spoofing
+        script.push_str("// === FONT ENUMERATION ===\n");
+        script.push_str("try {\n");
+        script.push_str(&self.fonts.generate_override_script());
         script.push_str("\n} catch(e) {}\n\n");
 
         // Canvas fingerprint protection
-        script.push_str("// === CANVAS FINGERPRINT PROTECTION ===\n");
+        script.push_str("// === CANVAS OVERRIDES ===\n");
         script.push_str("try {\n");
-        script.push_str(&self.canvas.get_override_script());
+        script.push_str(&self.canvas.get_seeded_override_script(&self.canvas_noise));
         script.push_str("\n} catch(e) {}\n\n");
 
         // AudioContext fingerprint spoofing
@@ -223,6 +391,14 @@ impl StealthConfig {
         script.push_str(&self.audio.get_override_script());
         script.push_str("\n} catch(e) {}\n\n");
 
+        // API removal (last, so no earlier section re-adds a removed API)
+        if !self.disable_apis.is_empty() {
+            script.push_str("// === DISABLED APIS ===\n");
+            script.push_str("try {\n");
+            script.push_str(&api_surface::disable_apis_script(&self.disable_apis));
+            script.push_str("\n} catch(e) {}\n\n");
+        }
+
         // Close IIFE
         script.push_str("})();\n");
 
@@ -241,7 +417,7 @@ impl StealthConfig {
         // Navigator overrides (MOST CRITICAL - must run first)
         sections.push(format!(
             "(function() {{ 'use strict';\ntry {{\n{}\n}} catch(e) {{}}\n}})();",
-            self.navigator.get_override_script()
+            self.navigator_script()
         ));
 
         // WebGL overrides
@@ -250,22 +426,48 @@ impl StealthConfig {
             self.webgl.get_js_override_script()
         ));
 
+        // WebRTC leak prevention
+        sections.push(format!(
+            "(function() {{ 'use strict';\ntry {{\n{}\n}} catch(e) {{}}\n}})();",
+            self.webrtc.get_override_script()
+        ));
+
         // Fingerprint overrides
         sections.push(format!(
             "(function() {{ 'use strict';\ntry {{\n{}\n}} catch(e) {{}}\n}})();",
             self.fingerprint.to_js_overrides()
         ));
 
-        // WebRTC leak prevention
+        // Timezone
         sections.push(format!(
             "(function() {{ 'use strict';\ntry {{\n{}\n}} catch(e) {{}}\n}})();",
-            self.webrtc.get_override_script()
+            self.timezone.generate_override_script()
+        ));
+
+        // Viewport metrics
+        if let Some(viewport) = &self.viewport {
+            sections.push(format!(
+                "(function() {{ 'use strict';\ntry {{\n{}\n}} catch(e) {{}}\n}})();",
+                viewport.to_js_overrides()
+            ));
+        }
+
+        // Geolocation spoofing
+        sections.push(format!(
+            "(function() {{ 'use strict';\ntry {{\n{}\n}} catch(e) {{}}\n}})();",
+            self.geolocation.generate_override_script()
+        ));
+
+        // Font enumeration spoofing
+        sections.push(format!(
+            "(function() {{ 'use strict';\ntry {{\n{}\n}} catch(e) {{}}\n}})();",
+            self.fonts.generate_override_script()
         ));
 
         // Canvas fingerprint protection
         sections.push(format!(
             "(function() {{ 'use strict';\ntry {{\n{}\n}} catch(e) {{}}\n}})();",
-            self.canvas.get_override_script()
+            self.canvas.get_seeded_override_script(&self.canvas_noise)
         ));
 
         // AudioContext fingerprint spoofing
@@ -282,6 +484,14 @@ impl StealthConfig {
             );
         }
 
+        // API removal runs after the stubs above, which would re-add some APIs
+        if !self.disable_apis.is_empty() {
+            sections.push(format!(
+                "(function() {{ 'use strict';\ntry {{\n{}\n}} catch(e) {{}}\n}})();",
+                api_surface::disable_apis_script(&self.disable_apis)
+            ));
+        }
+
         sections
     }
 
@@ -305,8 +515,92 @@ impl StealthConfig {
             return Err("Platform cannot be empty".to_string());
         }
 
+        self.navigator.check_consistency()?;
+
+        if let Some(viewport) = &self.viewport {
+            viewport
+                .validate()
+                .map_err(|e| format!("Inconsistent viewport metrics: {}", e))?;
+        }
+
+        self.webrtc.validate()?;
+
         Ok(())
     }
+
+    /// Serialize the configuration to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize stealth config to JSON: {}", e))
+    }
+
+    /// Load a configuration saved with [`to_json`](Self::to_json)
+    ///
+    /// `navigator.webdriver` is forced to `false` and the result is
+    /// [`validate`](Self::validate)d, whatever the input says.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let config: Self = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to parse stealth config JSON: {}", e))?;
+        config.into_loaded()
+    }
+
+    /// Serialize the configuration to TOML
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string(self).map_err(|e| format!("Failed to serialize stealth config to TOML: {}", e))
+    }
+
+    /// Load a configuration saved with [`to_toml`](Self::to_toml)
+    ///
+    /// `navigator.webdriver` is forced to `false` and the result is
+    /// [`validate`](Self::validate)d, whatever the input says.
+    pub fn from_toml(toml: &str) -> Result<Self, String> {
+        let config: Self =
+            toml::from_str(toml).map_err(|e| format!("Failed to parse stealth config TOML: {}", e))?;
+        config.into_loaded()
+    }
+
+    /// Apply the load-time safety rules to a deserialized configuration
+    fn into_loaded(mut self) -> Result<Self, String> {
+        // CRITICAL: a persisted config must never turn webdriver back on
+        self.navigator.webdriver = false;
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// [`validate`](Self::validate), plus warnings for settings that work
+    /// but weaken the disguise under `settings`
+    ///
+    /// Warnings are logged and returned; they never fail validation.
+    pub fn validate_with_settings(&self, settings: &BrowserSettings) -> Result<Vec<String>, String> {
+        self.validate()?;
+
+        let mut warnings = Vec::new();
+        let proxied = settings.proxy.is_some() || !settings.proxy_rules.is_empty();
+        if proxied && !self.webrtc.block_non_proxied_udp() {
+            warnings.push(
+                "A proxy is configured but WebRTC may send UDP around it, exposing the real IP"
+                    .to_string(),
+            );
+        }
+
+        let derived = self.navigator.user_agent_data();
+        let mismatched = match (&self.navigator.ua_client_hints, &derived) {
+            (Some(hints), Some(derived)) => {
+                hints.platform != derived.platform || hints.brands != derived.brands
+            }
+            (hints, derived) => hints.is_some() != derived.is_some(),
+        };
+        if mismatched {
+            warnings.push(
+                "navigator.userAgentData does not match the navigator user agent".to_string(),
+            );
+        }
+
+        for warning in &warnings {
+            tracing::warn!("{}", warning);
+        }
+        Ok(warnings)
+    }
 }
 
 impl Default for StealthConfig {
@@ -326,6 +620,34 @@ mod tests {
         assert!(!config.navigator.webdriver, "webdriver must NEVER be true");
     }
 
+    #[test]
+    fn test_validate_rejects_mismatched_identity() {
+        let mut config = StealthConfig::from_profile(FingerprintProfile::MacSafari);
+        assert!(config.validate().is_ok());
+
+        config.navigator.platform = "Win32".to_string();
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("navigator.platform"), "{}", err);
+
+        let mut config = StealthConfig::from_profile(FingerprintProfile::WindowsFirefox);
+        config.navigator.vendor = "Google Inc.".to_string();
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("navigator.vendor"), "{}", err);
+
+        let mut config = StealthConfig::default();
+        config.navigator.max_touch_points = 5;
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("maxTouchPoints"), "{}", err);
+    }
+
+    #[test]
+    fn test_all_profiles_are_consistent() {
+        for profile in FingerprintProfile::all_standard() {
+            let config = StealthConfig::from_profile(profile.clone());
+            assert!(config.validate().is_ok(), "{:?}: {:?}", profile, config.validate());
+        }
+    }
+
     #[test]
     fn test_random_config_is_valid() {
         for _ in 0..10 {
@@ -345,6 +667,177 @@ mod tests {
         assert_eq!(config1.fingerprint.platform, config2.fingerprint.platform);
     }
 
+    #[test]
+    fn test_validate_warns_about_webrtc_around_proxy() {
+        let mut config = StealthConfig::default();
+        let mut settings = BrowserSettings::default();
+        assert!(config.validate_with_settings(&settings).unwrap().is_empty());
+
+        settings.proxy = Some(crate::config::ProxyConfig::new("localhost", 1080));
+        assert!(config.validate_with_settings(&settings).unwrap().is_empty());
+
+        config.webrtc = WebRtcConfig::new(WebRtcIpPolicy::Default);
+        let warnings = config.validate_with_settings(&settings).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("WebRTC"));
+
+        config.navigator.user_agent = config.navigator.user_agent.replace("Chrome/", "Chrome/1");
+        let warnings = config.validate_with_settings(&settings).unwrap();
+        assert!(warnings.iter().any(|w| w.contains("userAgentData")));
+
+        config.webrtc.spoof_local_ip = Some("not-an-ip".to_string());
+        assert!(config.validate_with_settings(&settings).is_err());
+    }
+
+    #[test]
+    fn test_with_webrtc_policy() {
+        let script = StealthConfig::default()
+            .with_webrtc_policy(WebRtcPolicy::Disable)
+            .get_complete_override_script();
+        assert!(script.contains("RTCPeerConnection is not allowed by browser policy"));
+
+        let config = StealthConfig::default()
+            .with_webrtc_policy(WebRtcPolicy::FakePublicIp("198.51.100.4".to_string()));
+        assert!(!config.webrtc.disabled);
+        assert!(config
+            .get_complete_override_script()
+            .contains(r#"const SPOOF_PUBLIC_IP = "198.51.100.4";"#));
+    }
+
+    #[test]
+    fn test_consistent_geolocation_follows_timezone() {
+        let config = StealthConfig::consistent("geo-seed");
+        let expected = GeoConfig::consistent(&config.fingerprint.timezone, "geo-seed");
+        assert_eq!(config.geolocation, expected);
+        assert!(config.geolocation.spoof_enabled);
+
+        let script = config.get_complete_override_script();
+        assert!(script.contains("getCurrentPosition"));
+        assert!(script.contains("watchPosition"));
+    }
+
+    #[test]
+    fn test_timezone_follows_fingerprint() {
+        let config = StealthConfig::consistent("tz-seed");
+        assert_eq!(config.timezone.iana_timezone, config.fingerprint.timezone);
+        assert!(config.timezone.is_known());
+
+        let script = config.get_complete_override_script();
+        let fingerprint = script.find("// === FINGERPRINT OVERRIDES ===").unwrap();
+        let timezone = script.find("// === TIMEZONE ===").unwrap();
+        assert!(fingerprint < timezone);
+    }
+
+    #[test]
+    fn test_json_round_trip_preserves_config() {
+        let config = StealthConfig::consistent("seed");
+        let json = config.to_json().unwrap();
+        let loaded = StealthConfig::from_json(&json).unwrap();
+
+        assert_eq!(loaded.to_json().unwrap(), json);
+        assert_eq!(loaded.fingerprint.user_agent, config.fingerprint.user_agent);
+        assert_eq!(loaded.fingerprint.timezone, config.fingerprint.timezone);
+        assert_eq!(loaded.fingerprint.screen_resolution, config.fingerprint.screen_resolution);
+        assert_eq!(loaded.webgl.renderer, config.webgl.renderer);
+        assert_eq!(loaded.navigator.user_agent, config.navigator.user_agent);
+        assert_eq!(loaded.canvas_noise, config.canvas_noise);
+        assert_eq!(loaded.audio.seed, config.audio.seed);
+        assert_eq!(loaded.geolocation, config.geolocation);
+        assert_eq!(loaded.navigator.ua_client_hints, config.navigator.ua_client_hints);
+        assert_eq!(
+            loaded.get_complete_override_script(),
+            config.get_complete_override_script()
+        );
+    }
+
+    #[test]
+    fn test_toml_round_trip_preserves_config() {
+        let mut config = StealthConfig::consistent("seed");
+        config.sync_screen_to_viewport(1280, 720);
+        config.disable_apis = vec![ApiSurface::Battery];
+        let toml = config.to_toml().unwrap();
+        let loaded = StealthConfig::from_toml(&toml).unwrap();
+
+        assert_eq!(loaded.to_toml().unwrap(), toml);
+        assert_eq!(loaded.viewport, config.viewport);
+        assert_eq!(loaded.disable_apis, config.disable_apis);
+        assert_eq!(loaded.audio.seed, config.audio.seed);
+        assert_eq!(
+            loaded.get_complete_override_script(),
+            config.get_complete_override_script()
+        );
+    }
+
+    #[test]
+    fn test_from_json_forces_webdriver_false() {
+        let mut config = StealthConfig::consistent("seed");
+        config.navigator.webdriver = true;
+        let json = config.to_json().unwrap();
+        assert!(json.contains(r#""webdriver": true"#));
+
+        let loaded = StealthConfig::from_json(&json).unwrap();
+        assert!(!loaded.navigator.webdriver);
+        assert!(StealthConfig::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_fonts_follow_profile() {
+        let windows = StealthConfig::from_profile(FingerprintProfile::WindowsChrome);
+        let mac = StealthConfig::from_profile(FingerprintProfile::MacSafari);
+        assert_ne!(windows.fonts.font_list_json(), mac.fonts.font_list_json());
+        assert!(windows
+            .get_complete_override_script()
+            .contains("// === FONT ENUMERATION ==="));
+    }
+
+    #[test]
+    fn test_webrtc_section_precedes_fingerprint() {
+        let script = StealthConfig::default().get_complete_override_script();
+        let webrtc = script.find("// === WEBRTC LEAK PREVENTION ===").unwrap();
+        let fingerprint = script.find("// === FINGERPRINT OVERRIDES ===").unwrap();
+        assert!(webrtc < fingerprint);
+    }
+
+    #[test]
+    fn test_consistent_audio_noise_follows_seed() {
+        let config1 = StealthConfig::consistent("audio-seed");
+        let config2 = StealthConfig::consistent("audio-seed");
+        assert!(config1.audio.seed.is_some());
+        assert_eq!(
+            config1.audio.get_override_script(),
+            config2.audio.get_override_script()
+        );
+        assert_ne!(config1.audio.seed, StealthConfig::consistent("other-seed").audio.seed);
+
+        let script = config1.get_complete_override_script();
+        assert!(!config1.navigator.webdriver);
+        assert!(script.contains("delete Navigator.prototype.webdriver"));
+        assert!(script.contains("// === AUDIO FINGERPRINT SPOOFING ==="));
+        assert!(script.contains("AnalyserNode.prototype.getFloatFrequencyData"));
+        assert!(script.contains("AudioCtx.prototype.createOscillator"));
+        assert!(!script.contains("AUDIO_SESSION_SEED = Math.floor"));
+        assert!(StealthConfig::random().audio.seed.is_some());
+    }
+
+    #[test]
+    fn test_consistent_canvas_noise_follows_seed() {
+        let config1 = StealthConfig::consistent("canvas-seed");
+        let config2 = StealthConfig::consistent("canvas-seed");
+        let canvas_js = |config: &StealthConfig| {
+            config.canvas.get_seeded_override_script(&config.canvas_noise)
+        };
+
+        assert_eq!(canvas_js(&config1), canvas_js(&config2));
+        assert!(canvas_js(&config1).contains("toDataURL"));
+        assert_ne!(
+            config1.canvas_noise,
+            StealthConfig::consistent("other-seed").canvas_noise
+        );
+        assert!(config1
+            .get_complete_override_script()
+            .contains("// === CANVAS OVERRIDES ==="));
+    }
+
     #[test]
     fn test_default_config_has_webrtc_protection() {
         let config = StealthConfig::default();
@@ -391,7 +884,7 @@ mod tests {
         assert!(script.contains("RTCPeerConnection"));
 
         // Canvas fingerprint protection
-        assert!(script.contains("CANVAS FINGERPRINT PROTECTION"));
+        assert!(script.contains("CANVAS OVERRIDES"));
         assert!(script.contains("toDataURL"));
 
         // Audio fingerprint spoofing
@@ -399,6 +892,61 @@ mod tests {
         assert!(script.contains("AudioContext") || script.contains("AudioBuffer"));
     }
 
+    #[test]
+    fn test_client_hints_follow_profile() {
+        let config = StealthConfig::from_profile(FingerprintProfile::WindowsChrome);
+        let hints = config.navigator.ua_client_hints.as_ref().unwrap();
+        assert_eq!(hints.platform, "Windows");
+        assert!(config.get_complete_override_script().contains("userAgentData"));
+
+        let config = StealthConfig::from_profile(FingerprintProfile::MacSafari);
+        assert!(config.navigator.ua_client_hints.is_none());
+        assert!(!config.get_complete_override_script().contains("userAgentData"));
+    }
+
+    #[test]
+    fn test_battery_follows_seed() {
+        let mut config = StealthConfig::consistent("seed");
+        assert_eq!(config.battery, BatteryConfig::consistent("seed"));
+        assert_eq!(config.navigator.battery.as_ref(), Some(&config.battery));
+        assert!(config.validate().is_ok());
+        assert!(!config.navigator.webdriver, "webdriver must NEVER be true");
+
+        let script = config.get_complete_override_script();
+        assert!(script.contains("function getBattery()"));
+        assert!(script.contains("BATTERY_EVENTS"));
+
+        // The script reports the config's battery, not the navigator's copy
+        config.battery = BatteryConfig::new(false, 0.42, None, Some(9000.0));
+        assert!(config.get_complete_override_script().contains("level: 0.42"));
+        assert!(config.get_section_scripts().iter().any(|s| s.contains("level: 0.42")));
+
+        config.navigator.battery = None;
+        assert!(!config.get_complete_override_script().contains("BATTERY_EVENTS"));
+    }
+
+    #[test]
+    fn test_disabled_api_is_removed_in_generated_script() {
+        let mut config = StealthConfig::default();
+        assert!(!config.get_complete_override_script().contains("DISABLED APIS"));
+
+        config.disable_apis = vec![ApiSurface::Battery, ApiSurface::Battery];
+        let script = config.get_complete_override_script();
+        assert!(script.contains("// === DISABLED APIS ==="));
+        assert_eq!(script.matches("delete Navigator.prototype.getBattery").count(), 1);
+        assert!(script.contains("delete window.BatteryManager"));
+        assert!(!script.contains("delete Navigator.prototype.gpu"));
+
+        // The removal section comes after the getBattery stub
+        let sections = config.get_section_scripts();
+        let stub = sections.iter().position(|s| s.contains("navigator.getBattery")).unwrap();
+        let removal = sections
+            .iter()
+            .position(|s| s.contains("delete Navigator.prototype.getBattery"))
+            .unwrap();
+        assert!(removal > stub);
+    }
+
     #[test]
     fn test_complete_script_is_wrapped_in_iife() {
         let config = StealthConfig::default();
@@ -408,6 +956,19 @@ mod tests {
         assert!(script.trim_end().ends_with("})();"));
     }
 
+    #[test]
+    fn test_minified_script_is_smaller_and_keeps_webdriver_override() {
+        let mut config = StealthConfig::default();
+        let full = config.get_complete_override_script();
+
+        config.minify = true;
+        let minified = config.get_complete_override_script();
+        assert!(minified.len() < full.len());
+        assert!(!minified.contains("// === NAVIGATOR OVERRIDES"));
+        assert!(minified.contains("delete Navigator.prototype.webdriver"));
+        assert_eq!(config.get_complete_override_script(), minified);
+    }
+
     #[test]
     fn test_random_config_has_all_modules() {
         let config = StealthConfig::random();
@@ -0,0 +1,76 @@
+//! Native-looking `toString` for overridden functions.
+//!
+//! Pages detect a spoofed API by calling `toString()` on it: a native
+//! function prints `function name() { [native code] }`, a replacement prints
+//! its source. Every override script masks its replacements through the
+//! `markNative` helper from [`mark_native_script`].
+//!
+//! Only the first script wraps `Function.prototype.toString` in a `Proxy`.
+//! Later scripts find that Proxy and add their functions to the same
+//! `WeakMap`, so the page sees a single wrapper whatever the number and
+//! order of injected scripts. The Proxy hands out its registry only when
+//! called on a key generated once per process, which page scripts cannot
+//! know.
+
+use once_cell::sync::Lazy;
+use rand::Rng;
+
+/// Value the `toString` Proxy recognizes as a request for its registry.
+static REGISTRY_KEY: Lazy<String> =
+    Lazy::new(|| format!("ki-native-{:016x}", rand::thread_rng().gen::<u64>()));
+
+/// JavaScript declaring `markNative(fn, name)` in the enclosing scope.
+///
+/// `markNative` makes `fn.toString()` report a native function called
+/// `name` (`fn.name` when omitted). The first script to run installs the
+/// `Function.prototype.toString` Proxy; the Proxy masks itself as well.
+pub fn mark_native_script() -> String {
+    format!(
+        r#"const markNative = (function() {{
+        const KEY = '{key}';
+        let registry;
+        try {{
+            // A Proxy installed by an earlier script returns its registry
+            registry = Function.prototype.toString.call(KEY);
+        }} catch (e) {{}}
+        if (!(registry instanceof WeakMap)) {{
+            registry = new WeakMap();
+            const nativeToString = Function.prototype.toString;
+            const toString = new Proxy(nativeToString, {{
+                apply: function(target, thisArg, args) {{
+                    if (thisArg === KEY) {{
+                        return registry;
+                    }}
+                    if (registry.has(thisArg)) {{
+                        return 'function ' + registry.get(thisArg) + '() {{ [native code] }}';
+                    }}
+                    return Reflect.apply(target, thisArg, args);
+                }}
+            }});
+            registry.set(toString, 'toString');
+            Function.prototype.toString = toString;
+        }}
+        return function(fn, name) {{
+            registry.set(fn, name || fn.name);
+        }};
+    }})();"#,
+        key = REGISTRY_KEY.as_str(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_key_is_stable_per_process() {
+        let script = mark_native_script();
+        assert_eq!(script, mark_native_script());
+        assert!(script.contains(&format!("const KEY = '{}';", REGISTRY_KEY.as_str())));
+
+        // One Proxy, installed only when no earlier script did
+        assert_eq!(script.matches("new Proxy(").count(), 1);
+        assert!(script.contains("if (!(registry instanceof WeakMap))"));
+        assert!(script.contains("registry.set(toString, 'toString');"));
+    }
+}
@@ -4,8 +4,12 @@
 //! for anti-detection fingerprint spoofing. The builder guarantees
 //! that `webdriver` is always forced to `false` on `build()`.
 
-use super::helpers::extract_app_version;
-use super::types::{NavigatorOverrides, PluginInfo};
+use super::helpers::{dedup_plugins, extract_app_version};
+use super::types::{
+    ConnectionProfile, MediaDevicesConfig, NavigatorOverrides, NotificationPermission, PluginInfo,
+};
+use crate::stealth::client_hints::ClientHints;
+use crate::stealth::fingerprint::HardwareTier;
 
 /// Builder for creating custom NavigatorOverrides with validated fields
 #[derive(Debug, Clone)]
@@ -50,6 +54,13 @@ impl NavigatorOverridesBuilder {
         self
     }
 
+    /// Set device memory and core count together from a realistic tier
+    pub fn hardware(mut self, tier: HardwareTier) -> Self {
+        self.overrides.hardware_concurrency = tier.hardware_concurrency;
+        self.overrides.device_memory = tier.device_memory;
+        self
+    }
+
     /// Set maximum touch points (0 for non-touch desktop devices)
     pub fn max_touch_points(mut self, points: u8) -> Self {
         self.overrides.max_touch_points = points;
@@ -57,10 +68,23 @@ impl NavigatorOverridesBuilder {
     }
 
     /// Set user agent string (also updates app_version automatically)
+    ///
+    /// `userAgentData` is re-derived from the new user agent, replacing any
+    /// hints set earlier through [`ua_client_hints`](Self::ua_client_hints).
     pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
         let ua: String = user_agent.into();
         self.overrides.app_version = extract_app_version(&ua);
         self.overrides.user_agent = ua;
+        self.overrides.ua_client_hints = self.overrides.user_agent_data();
+        self
+    }
+
+    /// Set the `navigator.userAgentData` reported to pages
+    ///
+    /// e.g. `ClientHints::for_edge_120_windows()`. Call after
+    /// [`user_agent`](Self::user_agent), which re-derives the hints.
+    pub fn ua_client_hints(mut self, hints: ClientHints) -> Self {
+        self.overrides.ua_client_hints = Some(hints);
         self
     }
 
@@ -71,8 +95,10 @@ impl NavigatorOverridesBuilder {
     }
 
     /// Set browser plugins list for navigator.plugins spoofing
+    ///
+    /// Duplicate (name, filename) pairs are dropped.
     pub fn plugins(mut self, plugins: Vec<PluginInfo>) -> Self {
-        self.overrides.plugins = plugins;
+        self.overrides.plugins = dedup_plugins(plugins);
         self
     }
 
@@ -82,6 +108,42 @@ impl NavigatorOverridesBuilder {
         self
     }
 
+    /// Set the `Notification.permission` value reported by the permissions spoof
+    pub fn notification_permission(mut self, permission: NotificationPermission) -> Self {
+        self.overrides.notification_permission = permission;
+        self
+    }
+
+    /// Set the devices listed by `navigator.mediaDevices.enumerateDevices()`
+    pub fn media_devices(mut self, config: MediaDevicesConfig) -> Self {
+        self.overrides.media_devices = Some(config);
+        self
+    }
+
+    /// Set the network reported by `navigator.connection`
+    pub fn connection_profile(mut self, profile: ConnectionProfile) -> Self {
+        self.overrides.connection_profile = Some(profile);
+        self
+    }
+
+    /// Enable or disable `navigator.getBattery()` spoofing (on by default)
+    ///
+    /// Enabling keeps an already configured state; otherwise the state is
+    /// seeded from the values set so far.
+    pub fn with_battery_spoof(mut self, enabled: bool) -> Self {
+        self.overrides = if !enabled {
+            NavigatorOverrides {
+                battery: None,
+                ..self.overrides
+            }
+        } else if self.overrides.battery.is_none() {
+            self.overrides.with_seeded_battery()
+        } else {
+            self.overrides
+        };
+        self
+    }
+
     /// Enable or disable automation signal removal (CDP, Selenium, PhantomJS, etc.)
     pub fn remove_automation_signals(mut self, enabled: bool) -> Self {
         self.overrides.remove_automation_signals = enabled;
@@ -127,4 +189,67 @@ mod tests {
             .build();
         assert_eq!(overrides.device_memory, 16);
     }
+
+    #[test]
+    fn test_ua_client_hints() {
+        let overrides = NavigatorOverridesBuilder::new()
+            .ua_client_hints(ClientHints::for_edge_120_windows())
+            .build();
+        let hints = overrides.ua_client_hints.as_ref().unwrap();
+        assert!(hints.brands.iter().any(|b| b.brand == "Microsoft Edge"));
+        assert!(overrides.get_override_script().contains("userAgentData"));
+
+        let firefox = NavigatorOverridesBuilder::new()
+            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:121.0) Gecko/20100101 Firefox/121.0")
+            .build();
+        assert!(firefox.ua_client_hints.is_none());
+    }
+
+    #[test]
+    fn test_battery_spoof() {
+        let overrides = NavigatorOverridesBuilder::new().build();
+        let js = overrides.get_override_script();
+        assert!(js.contains("getBattery"));
+
+        let level: f64 = js
+            .split("level: ")
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(|value| value.parse().ok())
+            .expect("script reports a numeric level");
+        assert!((0.2..=0.95).contains(&level), "level {}", level);
+
+        let overrides = NavigatorOverridesBuilder::new().with_battery_spoof(false).build();
+        assert!(overrides.battery.is_none());
+        assert!(!overrides.get_override_script().contains("getBattery"));
+    }
+
+    #[test]
+    fn test_connection_profile() {
+        let wifi = NavigatorOverridesBuilder::new().build();
+        let js = wifi.get_override_script();
+        assert!(js.contains("'connection'"));
+        assert!(js.contains("effectiveType: '4g'"));
+
+        let cellular = NavigatorOverridesBuilder::new()
+            .connection_profile(ConnectionProfile::Cellular)
+            .build();
+        let js = cellular.get_override_script();
+        assert!(js.contains("effectiveType: '4g'"));
+        assert!(js.contains("type: 'cellular'"));
+        assert!(ConnectionProfile::Cellular.rtt() > ConnectionProfile::Wifi.rtt());
+        assert!(js.contains(&format!("rtt: {}", ConnectionProfile::Cellular.rtt())));
+    }
+
+    #[test]
+    fn test_media_devices() {
+        let overrides = NavigatorOverridesBuilder::new()
+            .media_devices(MediaDevicesConfig::new(2, 1, 3))
+            .build();
+        assert_eq!(overrides.media_devices, Some(MediaDevicesConfig::new(2, 1, 3)));
+        let js = overrides.get_override_script();
+        assert!(js.contains("enumerateDevices"));
+        assert!(js.contains("['videoinput', 2]"));
+        assert!(js.contains("['audiooutput', 3]"));
+    }
 }
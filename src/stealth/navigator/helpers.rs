@@ -4,28 +4,41 @@
 //! app version extraction, and sub-scripts for permissions spoofing
 //! and automation signal removal.
 
-use super::types::{MimeTypeInfo, PluginInfo};
+use std::collections::HashSet;
+
+use crate::stealth::fingerprint::PluginEntry;
+
+use super::types::{
+    ConnectionProfile, MediaDevicesConfig, MimeTypeInfo, NotificationPermission, PluginInfo,
+};
 
 /// Default Chrome plugins that mimic a real Chrome browser installation
 pub(crate) fn default_chrome_plugins() -> Vec<PluginInfo> {
-    vec![
-        PluginInfo::new("PDF Viewer", "Portable Document Format", "internal-pdf-viewer")
-            .with_mime_type(MimeTypeInfo::pdf()),
-        PluginInfo::chrome_pdf_viewer(),
-        PluginInfo::chromium_pdf_viewer(),
-        PluginInfo::new(
-            "Microsoft Edge PDF Viewer",
-            "Portable Document Format",
-            "internal-pdf-viewer",
-        )
-        .with_mime_type(MimeTypeInfo::pdf()),
-        PluginInfo::new(
-            "WebKit built-in PDF",
-            "Portable Document Format",
-            "internal-pdf-viewer",
-        )
-        .with_mime_type(MimeTypeInfo::pdf()),
-    ]
+    PluginEntry::chrome_pdf_plugins()
+        .iter()
+        .map(pdf_plugin_from_entry)
+        .collect()
+}
+
+/// Navigator plugin for a fingerprint plugin entry.
+///
+/// Every PDF viewer Chrome and Safari expose handles both `application/pdf`
+/// and `text/pdf`.
+pub(crate) fn pdf_plugin_from_entry(entry: &PluginEntry) -> PluginInfo {
+    PluginInfo::new(&entry.name, &entry.description, &entry.filename)
+        .with_mime_type(MimeTypeInfo::pdf())
+        .with_mime_type(MimeTypeInfo::text_pdf())
+}
+
+/// Drop plugins whose (name, filename) pair already occurred, keeping order.
+///
+/// `navigator.plugins` of a real browser never lists the same plugin twice.
+pub(crate) fn dedup_plugins(plugins: Vec<PluginInfo>) -> Vec<PluginInfo> {
+    let mut seen = HashSet::new();
+    plugins
+        .into_iter()
+        .filter(|p| seen.insert((p.name.clone(), p.filename.clone())))
+        .collect()
 }
 
 /// Extract app version from user agent string (everything after "Mozilla/")
@@ -47,26 +60,161 @@ pub(crate) fn escape_js_string(s: &str) -> String {
         .replace('\'', "\\'")
 }
 
+/// Permissions API states of a fresh desktop Chrome profile.
+///
+/// `notifications` and `push` follow `notification` so the query result
+/// always agrees with `Notification.permission`.
+pub(crate) fn permission_query_states(notification: NotificationPermission) -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("notifications", notification.query_state()),
+        ("push", notification.query_state()),
+        ("geolocation", "prompt"),
+        ("camera", "prompt"),
+        ("microphone", "prompt"),
+        ("persistent-storage", "prompt"),
+        ("midi", "granted"),
+        ("background-sync", "granted"),
+        ("clipboard-write", "granted"),
+    ]
+}
+
 /// JavaScript snippet for Permissions API spoofing to hide automation defaults
-pub(crate) fn get_permissions_spoof_script() -> String {
-    r#"
-    // Permissions API spoofing
-    if (typeof Permissions !== 'undefined' && Permissions.prototype.query) {
+pub(crate) fn get_permissions_spoof_script(notification: NotificationPermission) -> String {
+    let states: Vec<String> = permission_query_states(notification)
+        .into_iter()
+        .map(|(name, state)| format!("'{}': '{}'", name, state))
+        .collect();
+    format!(
+        r#"
+    // Permissions API spoofing: automation profiles report "denied" where a
+    // fresh profile reports "prompt"; answer with profile-consistent states.
+    // Permissions actually granted to the origin stay "granted".
+    const notificationPermission = '{notification}';
+    const permissionStates = {{ {states} }};
+    if (typeof Notification !== 'undefined') {{
+        const nativePermission = Object.getOwnPropertyDescriptor(Notification, 'permission');
+        const nativeGet = nativePermission && nativePermission.get;
+        Object.defineProperty(Notification, 'permission', {{
+            get: function() {{
+                try {{
+                    if (nativeGet && nativeGet.call(Notification) === 'granted') return 'granted';
+                }} catch (e) {{}}
+                return notificationPermission;
+            }},
+            configurable: true
+        }});
+    }}
+    if (typeof Permissions !== 'undefined' && Permissions.prototype.query) {{
         const originalQuery = Permissions.prototype.query;
-        Permissions.prototype.query = function(permissionDesc) {
-            return new Promise((resolve, reject) => {
-                originalQuery.call(this, permissionDesc)
-                    .then(result => {
-                        // Don't reveal "prompt" for sensitive permissions
-                        // as automation tools often have different defaults
-                        resolve(result);
-                    })
-                    .catch(reject);
-            });
-        };
-    }
-    "#
-    .to_string()
+        Permissions.prototype.query = function(permissionDesc) {{
+            return originalQuery.call(this, permissionDesc).then(result => {{
+                const name = permissionDesc && permissionDesc.name;
+                if (result.state !== 'granted'
+                    && Object.prototype.hasOwnProperty.call(permissionStates, name)) {{
+                    const state = permissionStates[name];
+                    Object.defineProperty(result, 'state', {{
+                        get: function() {{ return state; }},
+                        configurable: true
+                    }});
+                }}
+                return result;
+            }});
+        }};
+    }}
+    "#,
+        notification = notification.as_str(),
+        states = states.join(", "),
+    )
+}
+
+/// JavaScript snippet replacing `enumerateDevices()` with a synthetic device list.
+///
+/// Device ids and labels are empty, as a real browser reports them before
+/// the page was granted camera or microphone access.
+pub(crate) fn get_media_devices_script(config: MediaDevicesConfig) -> String {
+    format!(
+        r#"
+    // Media device enumeration spoofing
+    if (typeof MediaDevices !== 'undefined' && MediaDevices.prototype.enumerateDevices) {{
+        const deviceCounts = [
+            ['audioinput', {microphones}],
+            ['videoinput', {cameras}],
+            ['audiooutput', {speakers}]
+        ];
+        function makeDevice(kind) {{
+            const ctor = kind === 'audiooutput' ? window.MediaDeviceInfo : (window.InputDeviceInfo || window.MediaDeviceInfo);
+            const values = {{ deviceId: '', kind: kind, label: '', groupId: '' }};
+            const device = Object.create(typeof ctor === 'function' ? ctor.prototype : Object.prototype);
+            for (const key of Object.keys(values)) {{
+                Object.defineProperty(device, key, {{ value: values[key], enumerable: true }});
+            }}
+            Object.defineProperty(device, 'toJSON', {{ value: function toJSON() {{ return values; }} }});
+            return device;
+        }}
+        MediaDevices.prototype.enumerateDevices = function enumerateDevices() {{
+            const devices = [];
+            for (const [kind, count] of deviceCounts) {{
+                for (let i = 0; i < count; i++) devices.push(makeDevice(kind));
+            }}
+            return Promise.resolve(devices);
+        }};
+    }}
+    "#,
+        microphones = config.microphones,
+        cameras = config.cameras,
+        speakers = config.speakers,
+    )
+}
+
+/// JavaScript snippet defining `navigator.connection` for `profile`.
+///
+/// Patches the `NetworkInformation` getters when the API exists, so the
+/// object keeps its native shape; otherwise installs a stand-in.
+pub(crate) fn get_connection_script(profile: ConnectionProfile) -> String {
+    let connection_type = match profile.connection_type() {
+        Some(t) => format!("'{}'", t),
+        None => "undefined".to_string(),
+    };
+    format!(
+        r#"
+    // Network Information API spoofing
+    try {{
+        const connectionValues = {{
+            effectiveType: '{effective_type}',
+            rtt: {rtt},
+            downlink: {downlink},
+            saveData: false,
+            type: {connection_type}
+        }};
+        if (typeof NetworkInformation !== 'undefined') {{
+            for (const key of Object.keys(connectionValues)) {{
+                if (key === 'type' && connectionValues.type === undefined) continue;
+                Object.defineProperty(NetworkInformation.prototype, key, {{
+                    get: function() {{ return connectionValues[key]; }},
+                    enumerable: true,
+                    configurable: true
+                }});
+            }}
+        }} else if (!('connection' in navigator)) {{
+            const connection = Object.create(EventTarget.prototype);
+            for (const key of Object.keys(connectionValues)) {{
+                if (key === 'type' && connectionValues.type === undefined) continue;
+                Object.defineProperty(connection, key, {{ value: connectionValues[key], enumerable: true }});
+            }}
+            connection.onchange = null;
+            Object.defineProperty(Navigator.prototype, 'connection', {{
+                get: function() {{ return connection; }},
+                enumerable: true,
+                configurable: true
+            }});
+        }}
+    }} catch (e) {{}}
+    "#,
+        effective_type = profile.effective_type(),
+        rtt = profile.rtt(),
+        downlink = profile.downlink(),
+        connection_type = connection_type,
+    )
 }
 
 /// JavaScript snippet for removing CDP, Selenium, PhantomJS, and other automation signals
@@ -5,7 +5,8 @@
 //!
 //! # Submodules
 //!
-//! - `types` - Core structs: `PluginInfo`, `MimeTypeInfo`, `NavigatorOverrides`
+//! - `types` - Core types: `PluginInfo`, `MimeTypeInfo`, `NotificationPermission`,
+//!   `MediaDevicesConfig`, `ConnectionProfile`, `NavigatorOverrides`
 //! - `script` - JavaScript override script generation for navigator spoofing
 //! - `builder` - Builder pattern for constructing `NavigatorOverrides`
 //! - `helpers` - Utility functions for JS escaping, default plugins, and sub-scripts
@@ -16,4 +17,7 @@ mod script;
 mod types;
 
 pub use builder::NavigatorOverridesBuilder;
-pub use types::{MimeTypeInfo, NavigatorOverrides, PluginInfo};
+pub use types::{
+    ConnectionProfile, MediaDevicesConfig, MimeTypeInfo, NavigatorOverrides,
+    NotificationPermission, PluginInfo,
+};
@@ -5,19 +5,34 @@
 //! injected before any page scripts run.
 
 use super::helpers::{
-    escape_js_string, get_automation_removal_script, get_permissions_spoof_script,
+    escape_js_string, get_automation_removal_script, get_connection_script,
+    get_media_devices_script, get_permissions_spoof_script,
 };
 use super::types::NavigatorOverrides;
+use crate::stealth::battery::BatteryConfig;
 
 impl NavigatorOverrides {
     /// Generate JavaScript override script for all navigator properties.
     ///
     /// Produces a self-contained IIFE that overrides `navigator.webdriver`,
     /// user agent strings, platform info, language settings, plugins,
-    /// and optionally removes automation signals.
+    /// `navigator.userAgentData` when `ua_client_hints` is set, the
+    /// `enumerateDevices()` list when `media_devices` is set, the
+    /// `getBattery()` state when `battery` is set, `navigator.connection`
+    /// when `connection_profile` is set, and optionally removes automation
+    /// signals.
     ///
     /// CRITICAL: This script MUST be injected before any page scripts run.
     pub fn get_override_script(&self) -> String {
+        self.override_script_with_battery(self.battery.as_ref())
+    }
+
+    /// [`get_override_script`](Self::get_override_script) reporting `battery`
+    /// instead of the own `battery` state.
+    ///
+    /// Used by [`StealthConfig`](crate::stealth::StealthConfig), whose
+    /// battery follows the profile seed.
+    pub(crate) fn override_script_with_battery(&self, battery: Option<&BatteryConfig>) -> String {
         // Safety check
         self.ensure_no_webdriver();
 
@@ -184,12 +199,18 @@ impl NavigatorOverrides {
         const pluginData = {plugins_json};
         const plugins = [];
         const mimeTypes = [];
+        // Chrome lists each MIME type once, owned by the first plugin
+        const mimeTypesByType = {{}};
 
         pluginData.forEach(function(p) {{
             const plugin = Object.create(Plugin.prototype);
             const pluginMimeTypes = [];
 
             (p.mimeTypes || []).forEach(function(mt) {{
+                if (mimeTypesByType[mt.type]) {{
+                    pluginMimeTypes.push(mimeTypesByType[mt.type]);
+                    return;
+                }}
                 const mimeType = Object.create(MimeType.prototype);
                 Object.defineProperties(mimeType, {{
                     'type': {{ value: mt.type, enumerable: true }},
@@ -197,6 +218,7 @@ impl NavigatorOverrides {
                     'suffixes': {{ value: mt.suffixes, enumerable: true }},
                     'enabledPlugin': {{ value: plugin, enumerable: true }}
                 }});
+                mimeTypesByType[mt.type] = mimeType;
                 pluginMimeTypes.push(mimeType);
                 mimeTypes.push(mimeType);
             }});
@@ -293,6 +315,14 @@ impl NavigatorOverrides {
 
     {automation_removal}
 
+    {user_agent_data}
+
+    {media_devices}
+
+    {battery}
+
+    {connection}
+
     // ========================================================================
     // Final Verification
     // ========================================================================
@@ -330,7 +360,7 @@ impl NavigatorOverrides {
             pdf_viewer_enabled = self.pdf_viewer_enabled,
             plugins_json = plugins_json,
             permissions_spoof = if self.spoof_permissions {
-                get_permissions_spoof_script()
+                get_permissions_spoof_script(self.notification_permission)
             } else {
                 String::new()
             },
@@ -339,6 +369,22 @@ impl NavigatorOverrides {
             } else {
                 String::new()
             },
+            user_agent_data = match &self.ua_client_hints {
+                Some(hints) => format!("try {{\n{}\n}} catch (e) {{}}", hints.get_override_script()),
+                None => String::new(),
+            },
+            media_devices = self
+                .media_devices
+                .map(get_media_devices_script)
+                .unwrap_or_default(),
+            battery = match battery {
+                Some(battery) => format!("try {{\n{}\n}} catch (e) {{}}", battery.generate_override_script()),
+                None => String::new(),
+            },
+            connection = self
+                .connection_profile
+                .map(get_connection_script)
+                .unwrap_or_default(),
         )
     }
 
@@ -401,6 +447,19 @@ mod tests {
         assert!(js.contains("Navigator.prototype"));
     }
 
+    #[test]
+    fn test_js_override_contains_user_agent_data() {
+        let js = NavigatorOverrides::default().get_override_script();
+        assert!(js.contains("userAgentData"));
+        assert!(js.contains("Google Chrome"));
+
+        let overrides = NavigatorOverrides {
+            ua_client_hints: None,
+            ..NavigatorOverrides::default()
+        };
+        assert!(!overrides.get_override_script().contains("userAgentData"));
+    }
+
     #[test]
     fn test_js_override_contains_all_properties() {
         let overrides = NavigatorOverrides::default();
@@ -413,4 +472,39 @@ mod tests {
         assert!(js.contains("languages"));
         assert!(js.contains("plugins"));
     }
+
+    #[test]
+    fn test_notification_permission_matches_permissions_query() {
+        use super::super::helpers::permission_query_states;
+        use super::super::types::NotificationPermission;
+
+        for permission in [
+            NotificationPermission::Default,
+            NotificationPermission::Granted,
+            NotificationPermission::Denied,
+        ] {
+            let overrides = NavigatorOverrides {
+                notification_permission: permission,
+                ..Default::default()
+            };
+            let js = overrides.get_override_script();
+            let query_state = permission_query_states(permission)
+                .into_iter()
+                .find(|(name, _)| *name == "notifications")
+                .map(|(_, state)| state)
+                .unwrap();
+
+            assert_eq!(query_state, permission.query_state());
+            assert!(js.contains(&format!("const notificationPermission = '{}';", permission.as_str())));
+            assert!(js.contains(&format!("'notifications': '{}'", query_state)));
+        }
+
+        // A fresh profile: "default" everywhere, never the headless "denied"
+        let js = NavigatorOverrides::default().get_override_script();
+        assert!(js.contains("const notificationPermission = 'default';"));
+        assert!(js.contains("'notifications': 'prompt'"));
+        assert!(js.contains("'geolocation': 'prompt'"));
+        // Real grants (e.g. from grant_permissions) are not masked
+        assert!(js.contains("if (result.state !== 'granted'"));
+    }
 }
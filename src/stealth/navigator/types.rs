@@ -1,14 +1,23 @@
 //! Core types for navigator property anti-detection overrides.
 //!
-//! Contains `PluginInfo`, `MimeTypeInfo`, and `NavigatorOverrides` structs
-//! used to spoof browser navigator properties and prevent automation detection.
+//! Contains `PluginInfo`, `MimeTypeInfo`, `NotificationPermission`, and
+//! `NavigatorOverrides` used to spoof browser navigator properties and
+//! prevent automation detection.
 
-use crate::stealth::fingerprint::BrowserFingerprint;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
-use super::helpers::{default_chrome_plugins, extract_app_version};
+use crate::stealth::battery::BatteryConfig;
+use crate::stealth::client_hints::ClientHints;
+use crate::stealth::fingerprint::{BrowserFingerprint, FingerprintProfile, HardwareTier};
+
+use super::helpers::{
+    dedup_plugins, default_chrome_plugins, extract_app_version, pdf_plugin_from_entry,
+};
 
 /// Information about a browser plugin for navigator.plugins spoofing
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PluginInfo {
     /// Plugin name
     pub name: String,
@@ -58,6 +67,7 @@ impl PluginInfo {
             "internal-pdf-viewer",
         )
         .with_mime_type(MimeTypeInfo::pdf())
+        .with_mime_type(MimeTypeInfo::text_pdf())
     }
 
     /// Chromium PDF Viewer plugin preset
@@ -68,6 +78,7 @@ impl PluginInfo {
             "internal-pdf-viewer",
         )
         .with_mime_type(MimeTypeInfo::pdf())
+        .with_mime_type(MimeTypeInfo::text_pdf())
     }
 
     /// Native Client plugin preset
@@ -77,7 +88,7 @@ impl PluginInfo {
 }
 
 /// Information about a MIME type for navigator.mimeTypes spoofing
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MimeTypeInfo {
     /// MIME type string (e.g., "application/pdf")
     pub mime_type: String,
@@ -117,11 +128,143 @@ impl MimeTypeInfo {
     }
 }
 
+/// Value reported by `Notification.permission`.
+///
+/// The Permissions API state for `notifications` is derived from it, so the
+/// two can never disagree (headless Chrome reports `denied` from one and
+/// `default` from the other, a well-known puppeteer tell).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NotificationPermission {
+    /// The user has not decided yet (fresh profile)
+    #[default]
+    Default,
+    /// Notifications were allowed
+    Granted,
+    /// Notifications were blocked
+    Denied,
+}
+
+impl NotificationPermission {
+    /// Value of `Notification.permission`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotificationPermission::Default => "default",
+            NotificationPermission::Granted => "granted",
+            NotificationPermission::Denied => "denied",
+        }
+    }
+
+    /// Matching `navigator.permissions.query({ name: 'notifications' })` state
+    pub fn query_state(self) -> &'static str {
+        match self {
+            NotificationPermission::Default => "prompt",
+            NotificationPermission::Granted => "granted",
+            NotificationPermission::Denied => "denied",
+        }
+    }
+}
+
+/// Media devices reported by `navigator.mediaDevices.enumerateDevices()`
+///
+/// Headless environments usually have no devices at all, which sets them
+/// apart from real desktops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaDevicesConfig {
+    /// Number of `videoinput` devices
+    pub cameras: u8,
+    /// Number of `audioinput` devices
+    pub microphones: u8,
+    /// Number of `audiooutput` devices
+    pub speakers: u8,
+}
+
+impl MediaDevicesConfig {
+    /// Create a configuration with the given device counts
+    pub fn new(cameras: u8, microphones: u8, speakers: u8) -> Self {
+        Self {
+            cameras,
+            microphones,
+            speakers,
+        }
+    }
+
+    /// Typical devices of the profile's platform
+    ///
+    /// Laptops (Windows, macOS) have a built-in camera, microphone and
+    /// speakers; Linux desktops commonly lack a camera.
+    pub fn for_profile(profile: &FingerprintProfile) -> Self {
+        match profile {
+            FingerprintProfile::LinuxChrome | FingerprintProfile::LinuxFirefox => {
+                Self::new(0, 1, 1)
+            }
+            _ => Self::default(),
+        }
+    }
+}
+
+impl Default for MediaDevicesConfig {
+    fn default() -> Self {
+        Self::new(1, 1, 1)
+    }
+}
+
+/// Network reported by `navigator.connection` (Network Information API)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConnectionProfile {
+    /// Desktop broadband over Wi-Fi or Ethernet
+    Wifi,
+    /// Mobile 4G cellular connection
+    Cellular,
+}
+
+impl ConnectionProfile {
+    /// Profile matching a user agent: cellular for mobile ones
+    pub fn for_user_agent(user_agent: &str) -> Self {
+        if ["Mobile", "Android", "iPhone", "iPad"]
+            .iter()
+            .any(|m| user_agent.contains(m))
+        {
+            ConnectionProfile::Cellular
+        } else {
+            ConnectionProfile::Wifi
+        }
+    }
+
+    /// `connection.effectiveType`
+    pub fn effective_type(self) -> &'static str {
+        "4g"
+    }
+
+    /// `connection.rtt` in milliseconds (Chrome rounds to 25 ms)
+    pub fn rtt(self) -> u32 {
+        match self {
+            ConnectionProfile::Wifi => 50,
+            ConnectionProfile::Cellular => 175,
+        }
+    }
+
+    /// `connection.downlink` in Mbit/s (Chrome rounds to 25 kbit/s)
+    pub fn downlink(self) -> f64 {
+        match self {
+            ConnectionProfile::Wifi => 10.0,
+            ConnectionProfile::Cellular => 3.45,
+        }
+    }
+
+    /// `connection.type`, which desktop Chrome does not expose
+    pub fn connection_type(self) -> Option<&'static str> {
+        match self {
+            ConnectionProfile::Wifi => None,
+            ConnectionProfile::Cellular => Some("cellular"),
+        }
+    }
+}
+
 /// Navigator property overrides for anti-detection fingerprint spoofing.
 ///
 /// Contains all navigator properties that should be overridden
 /// to prevent bot detection. The `webdriver` field MUST always be `false`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NavigatorOverrides {
     /// CRITICAL: Must ALWAYS be false to avoid detection.
     /// This is the primary method websites use to detect automation.
@@ -184,44 +327,68 @@ pub struct NavigatorOverrides {
     /// Whether permissions should be spoofed
     pub spoof_permissions: bool,
 
+    /// `Notification.permission` reported when permissions are spoofed
+    pub notification_permission: NotificationPermission,
+
     /// Additional properties to inject as automation signals removal
     pub remove_automation_signals: bool,
+
+    /// `navigator.userAgentData` to report; `None` leaves it untouched
+    /// (Firefox and Safari don't expose it)
+    pub ua_client_hints: Option<ClientHints>,
+
+    /// Devices listed by `navigator.mediaDevices.enumerateDevices()`;
+    /// `None` leaves the real list
+    pub media_devices: Option<MediaDevicesConfig>,
+
+    /// State reported by `navigator.getBattery()`; `None` leaves the real
+    /// (on headless Chromium: always full and charging) state. Inside a
+    /// [`StealthConfig`](crate::stealth::StealthConfig) this only switches
+    /// spoofing on and off: the state is the config's seeded `battery`
+    pub battery: Option<BatteryConfig>,
+
+    /// Network reported by `navigator.connection`; `None` leaves it
+    /// untouched (Firefox and Safari don't expose it)
+    pub connection_profile: Option<ConnectionProfile>,
 }
 
 impl NavigatorOverrides {
     /// Create navigator overrides from a browser fingerprint.
     ///
     /// Uses the fingerprint's profile to set profile-specific values:
-    /// - Plugins: Chrome/Edge get Chrome plugins, Firefox gets none, Safari gets WebKit PDF only
+    /// - Plugins: Chrome/Edge get the canonical Chrome set, Firefox gets none, Safari gets WebKit PDF only
     /// - product_sub: Chrome/Safari use "20030107", Firefox uses "20100101"
     /// - pdf_viewer_enabled: true for Chrome/Edge/Safari, false for Firefox
+    /// - ua_client_hints: derived from the user agent for Chromium profiles
+    /// - media_devices: the typical devices of the profile's platform
+    /// - battery: a plausible state seeded from the other navigator values
+    /// - connection_profile: Wi-Fi, or cellular for mobile user agents; none for Firefox/Safari
     pub fn from_fingerprint(fingerprint: &BrowserFingerprint) -> Self {
-        use crate::stealth::fingerprint::FingerprintProfile;
-
         let is_firefox = matches!(
             fingerprint.profile,
             FingerprintProfile::WindowsFirefox
                 | FingerprintProfile::MacFirefox
                 | FingerprintProfile::LinuxFirefox
         );
+        // Only Chromium implements the Network Information API
+        let has_connection = !is_firefox && fingerprint.profile != FingerprintProfile::MacSafari;
 
         // Convert fingerprint plugins (PluginEntry) to navigator plugins (PluginInfo)
-        let plugins: Vec<PluginInfo> = fingerprint
-            .plugins
-            .iter()
-            .map(|p| {
-                PluginInfo::new(&p.name, &p.description, &p.filename)
-                    .with_mime_type(MimeTypeInfo::pdf())
-            })
-            .collect();
+        let plugins = dedup_plugins(
+            fingerprint
+                .plugins
+                .iter()
+                .map(pdf_plugin_from_entry)
+                .collect(),
+        );
 
         Self {
             webdriver: false, // CRITICAL: Always false
             languages: fingerprint.languages.clone(),
             platform: fingerprint.platform.clone(),
-            hardware_concurrency: 8, // Common value
-            device_memory: 8,        // Common value
-            max_touch_points: 0,     // Desktop
+            hardware_concurrency: fingerprint.hardware.hardware_concurrency,
+            device_memory: fingerprint.hardware.device_memory,
+            max_touch_points: 0, // Desktop
             vendor: fingerprint.vendor.clone(),
             vendor_sub: String::new(),
             product: "Gecko".to_string(),
@@ -240,8 +407,112 @@ impl NavigatorOverrides {
             pdf_viewer_enabled: !is_firefox,
             plugins,
             spoof_permissions: true,
+            notification_permission: NotificationPermission::Default,
             remove_automation_signals: true,
+            ua_client_hints: ClientHints::for_profile(&fingerprint.profile, &fingerprint.user_agent),
+            media_devices: Some(MediaDevicesConfig::for_profile(&fingerprint.profile)),
+            battery: None,
+            connection_profile: has_connection
+                .then(|| ConnectionProfile::for_user_agent(&fingerprint.user_agent)),
         }
+        .with_seeded_battery()
+    }
+
+    /// Set `battery` to a state seeded from the identity values, so the same
+    /// identity always reports the same battery.
+    pub(crate) fn with_seeded_battery(mut self) -> Self {
+        let mut hasher = DefaultHasher::new();
+        self.user_agent.hash(&mut hasher);
+        self.platform.hash(&mut hasher);
+        self.languages.hash(&mut hasher);
+        self.hardware_concurrency.hash(&mut hasher);
+        self.device_memory.hash(&mut hasher);
+        self.battery = Some(BatteryConfig::random_from_seed(hasher.finish()));
+        self
+    }
+
+    /// Check the overrides for implausible value combinations.
+    ///
+    /// Returns human-readable warnings; an empty list means the overrides
+    /// look like a real device. Unlike [`ensure_no_webdriver`](Self::ensure_no_webdriver)
+    /// these are not fatal, since a custom identity may be deliberately odd.
+    pub fn validate(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if !HardwareTier::is_plausible(self.device_memory, self.hardware_concurrency) {
+            warnings.push(format!(
+                "Implausible hardware: deviceMemory {} GB with hardwareConcurrency {}",
+                self.device_memory, self.hardware_concurrency
+            ));
+        }
+        warnings
+    }
+
+    /// Check that platform, vendor and touch support agree with `user_agent`.
+    ///
+    /// Unlike [`validate`](Self::validate) these conflicts are fatal: a
+    /// Windows user agent on a `MacIntel` platform is a one-line bot check.
+    /// User agents naming an unknown OS or browser are not checked.
+    pub fn check_consistency(&self) -> Result<(), String> {
+        let ua = self.user_agent.as_str();
+
+        let os = if ua.contains("iPhone") || ua.contains("iPad") {
+            Some(("iOS", &["iPhone", "iPad"][..]))
+        } else if ua.contains("Windows") {
+            Some(("Windows", &["Win"][..]))
+        } else if ua.contains("Macintosh") {
+            Some(("macOS", &["Mac"][..]))
+        } else if ua.contains("Android") || ua.contains("Linux") || ua.contains("CrOS") {
+            Some(("Linux", &["Linux"][..]))
+        } else {
+            None
+        };
+        if let Some((os, prefixes)) = os {
+            if !prefixes.iter().any(|p| self.platform.starts_with(p)) {
+                return Err(format!(
+                    "navigator.platform \"{}\" does not match the {} user_agent",
+                    self.platform, os
+                ));
+            }
+        }
+
+        let browser = if ua.contains("Firefox/") {
+            Some(("Firefox", ""))
+        } else if ua.contains("Chrome/") || ua.contains("CriOS/") {
+            Some(("Chrome", "Google Inc."))
+        } else if ua.contains("Safari/") {
+            Some(("Safari", "Apple Computer, Inc."))
+        } else {
+            None
+        };
+        if let Some((browser, vendor)) = browser {
+            if self.vendor != vendor {
+                return Err(format!(
+                    "navigator.vendor \"{}\" does not match the {} user_agent (expected \"{}\")",
+                    self.vendor, browser, vendor
+                ));
+            }
+        }
+
+        let mobile = ["Mobile", "Android", "iPhone", "iPad"]
+            .iter()
+            .any(|m| ua.contains(m));
+        if self.max_touch_points > 0 && !mobile {
+            return Err(format!(
+                "navigator.maxTouchPoints is {} but the user_agent is not a mobile one",
+                self.max_touch_points
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `navigator.userAgentData` matching the spoofed user agent.
+    ///
+    /// Brands, versions and platform are parsed from `user_agent`, so a
+    /// user agent set through the builder gets matching hints. `None` for
+    /// Firefox and Safari user agents, which expose no `userAgentData`.
+    pub fn user_agent_data(&self) -> Option<ClientHints> {
+        ClientHints::for_profile(&FingerprintProfile::Custom, &self.user_agent)
     }
 
     /// CRITICAL: Ensure webdriver is never true.
@@ -278,8 +549,14 @@ impl Default for NavigatorOverrides {
             pdf_viewer_enabled: true,
             plugins: default_chrome_plugins(),
             spoof_permissions: true,
+            notification_permission: NotificationPermission::Default,
             remove_automation_signals: true,
+            ua_client_hints: Some(ClientHints::for_chrome_120_windows()),
+            media_devices: Some(MediaDevicesConfig::default()),
+            battery: None,
+            connection_profile: Some(ConnectionProfile::Wifi),
         }
+        .with_seeded_battery()
     }
 }
 
@@ -287,6 +564,51 @@ impl Default for NavigatorOverrides {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_media_devices_follow_profile() {
+        use crate::stealth::fingerprint::FingerprintGenerator;
+
+        let fingerprint = FingerprintGenerator::new()
+            .generate_from_profile(FingerprintProfile::WindowsChrome);
+        let overrides = NavigatorOverrides::from_fingerprint(&fingerprint);
+        assert_eq!(overrides.media_devices, Some(MediaDevicesConfig::new(1, 1, 1)));
+        assert!(overrides.get_override_script().contains("enumerateDevices"));
+
+        let fingerprint = FingerprintGenerator::new()
+            .generate_from_profile(FingerprintProfile::LinuxChrome);
+        let overrides = NavigatorOverrides::from_fingerprint(&fingerprint);
+        assert_eq!(overrides.media_devices, Some(MediaDevicesConfig::new(0, 1, 1)));
+    }
+
+    #[test]
+    fn test_user_agent_data_follows_user_agent() {
+        use crate::stealth::fingerprint::FingerprintGenerator;
+
+        let fingerprint = FingerprintGenerator::new()
+            .generate_from_profile(FingerprintProfile::WindowsChrome);
+        let overrides = NavigatorOverrides::from_fingerprint(&fingerprint);
+        let hints = overrides.user_agent_data().expect("Chrome exposes userAgentData");
+        assert_eq!(hints.platform, "Windows");
+        assert!(hints.brands.iter().any(|b| b.brand == "Google Chrome"));
+        assert!(hints.brands.iter().any(|b| b.brand == "Chromium"));
+        let script = hints.get_override_script();
+        assert!(script.contains("userAgentData"));
+        assert!(script.contains("getHighEntropyValues"));
+
+        let fingerprint = FingerprintGenerator::new()
+            .generate_from_profile(FingerprintProfile::MacChrome);
+        let hints = NavigatorOverrides::from_fingerprint(&fingerprint)
+            .user_agent_data()
+            .unwrap();
+        assert_eq!(hints.platform, "macOS");
+
+        let fingerprint = FingerprintGenerator::new()
+            .generate_from_profile(FingerprintProfile::WindowsFirefox);
+        assert!(NavigatorOverrides::from_fingerprint(&fingerprint)
+            .user_agent_data()
+            .is_none());
+    }
+
     #[test]
     fn test_webdriver_always_false() {
         let overrides = NavigatorOverrides::default();
@@ -321,6 +643,33 @@ mod tests {
         assert_eq!(overrides.platform, fingerprint.platform);
     }
 
+    #[test]
+    fn test_hardware_pairing_is_plausible() {
+        use crate::stealth::fingerprint::FingerprintGenerator;
+
+        let generator = FingerprintGenerator::new();
+        for i in 0..200 {
+            let fingerprint = generator.generate_consistent(&format!("hardware-{}", i));
+            let overrides = NavigatorOverrides::from_fingerprint(&fingerprint);
+            assert_eq!(overrides.device_memory, fingerprint.hardware.device_memory);
+            assert!(
+                overrides.validate().is_empty(),
+                "generated {} GB / {} cores",
+                overrides.device_memory,
+                overrides.hardware_concurrency
+            );
+        }
+
+        let overrides = NavigatorOverrides {
+            device_memory: 2,
+            hardware_concurrency: 16,
+            ..NavigatorOverrides::default()
+        };
+        let warnings = overrides.validate();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("deviceMemory 2 GB"));
+    }
+
     #[test]
     fn test_from_fingerprint_safari_has_one_plugin() {
         use crate::stealth::fingerprint::{FingerprintGenerator, FingerprintProfile};
@@ -361,6 +710,47 @@ mod tests {
         assert!(overrides.pdf_viewer_enabled);
     }
 
+    #[test]
+    fn test_plugins_match_canonical_chrome_set() {
+        use crate::stealth::fingerprint::{
+            FingerprintGenerator, FingerprintProfile, CHROME_PDF_PLUGIN_NAMES,
+        };
+
+        let generator = FingerprintGenerator::new();
+        for profile in [
+            FingerprintProfile::WindowsChrome,
+            FingerprintProfile::MacChrome,
+            FingerprintProfile::LinuxChrome,
+            FingerprintProfile::WindowsEdge,
+        ] {
+            let fingerprint = generator.generate_from_profile(profile.clone());
+            let overrides = NavigatorOverrides::from_fingerprint(&fingerprint);
+            let names: Vec<&str> = overrides.plugins.iter().map(|p| p.name.as_str()).collect();
+            assert_eq!(names, CHROME_PDF_PLUGIN_NAMES, "{:?}", profile);
+            assert!(overrides
+                .plugins
+                .iter()
+                .all(|p| p.filename == "internal-pdf-viewer" && p.mime_types.len() == 2));
+        }
+        assert_eq!(NavigatorOverrides::default().plugins, default_chrome_plugins());
+
+        for profile in [
+            FingerprintProfile::WindowsFirefox,
+            FingerprintProfile::MacFirefox,
+            FingerprintProfile::LinuxFirefox,
+        ] {
+            let fingerprint = generator.generate_from_profile(profile);
+            let overrides = NavigatorOverrides::from_fingerprint(&fingerprint);
+            assert!(overrides.plugins.is_empty());
+            assert!(overrides
+                .get_override_script()
+                .contains("const pluginData = [];"));
+        }
+
+        let duplicated = vec![PluginInfo::chrome_pdf_viewer(), PluginInfo::chrome_pdf_viewer()];
+        assert_eq!(dedup_plugins(duplicated).len(), 1);
+    }
+
     #[test]
     fn test_plugin_info() {
         let plugin = PluginInfo::chrome_pdf_viewer();
@@ -0,0 +1,560 @@
+//! Timezone Spoofing
+//!
+//! This module makes the page see an IANA timezone other than the host's.
+//! Detection scripts compare `Date.prototype.getTimezoneOffset`, the zone
+//! reported by `Intl.DateTimeFormat().resolvedOptions()` and the output of
+//! `toLocaleString`, so all of them are overridden together. A fixed offset
+//! is not enough: it is off by an hour for half of the year in every zone
+//! with daylight saving time, which is an easy tell.
+//!
+//! # Components
+//!
+//! - `TimezoneConfig` - Spoofed timezone and the override script
+//! - Bundled table of standard offsets and DST rules for the major zones
+//! - Locale lookup, so the timezone matches `navigator.language`
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use ki_browser_standalone::stealth::timezone::TimezoneConfig;
+//!
+//! // A timezone matching the browser language
+//! let config = TimezoneConfig::from_locale("de-DE");
+//! assert_eq!(config.iana_timezone, "Europe/Berlin");
+//!
+//! // Or an explicit zone
+//! let config = TimezoneConfig::new("America/New_York");
+//!
+//! // Get the JavaScript override script
+//! let js = config.generate_override_script();
+//! ```
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::native_functions::mark_native_script;
+
+/// `week` of a [`Transition`] on the last Sunday of the month
+const LAST_WEEK: u32 = 5;
+
+/// Timezone used when a locale names no known region or language
+const FALLBACK_TIMEZONE: &str = "UTC";
+
+/// A daylight saving time switch: the given Sunday of a month, at a time of day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Transition {
+    /// Month, 1-12
+    month: u32,
+    /// Sunday of the month, 1-4, or [`LAST_WEEK`]
+    week: u32,
+    /// Minutes after midnight, in the local time in force before the switch
+    /// (or UTC for rules with `utc` set)
+    minutes: i64,
+}
+
+/// Start and end of daylight saving time, which moves clocks one hour ahead
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DstRule {
+    start: Transition,
+    end: Transition,
+    /// Transition times are given in UTC rather than local time
+    utc: bool,
+}
+
+/// United States and Canada: second Sunday of March to first Sunday of November, 02:00 local
+const US_DST: DstRule = DstRule {
+    start: Transition { month: 3, week: 2, minutes: 120 },
+    end: Transition { month: 11, week: 1, minutes: 120 },
+    utc: false,
+};
+
+/// European Union and UK: last Sunday of March to last Sunday of October, 01:00 UTC
+const EU_DST: DstRule = DstRule {
+    start: Transition { month: 3, week: LAST_WEEK, minutes: 60 },
+    end: Transition { month: 10, week: LAST_WEEK, minutes: 60 },
+    utc: true,
+};
+
+/// South-east Australia: first Sunday of October to first Sunday of April, 02:00 standard time
+const AU_DST: DstRule = DstRule {
+    start: Transition { month: 10, week: 1, minutes: 120 },
+    end: Transition { month: 4, week: 1, minutes: 180 },
+    utc: false,
+};
+
+/// New Zealand: last Sunday of September to first Sunday of April, 02:00 standard time
+const NZ_DST: DstRule = DstRule {
+    start: Transition { month: 9, week: LAST_WEEK, minutes: 120 },
+    end: Transition { month: 4, week: 1, minutes: 180 },
+    utc: false,
+};
+
+/// Known timezones: (IANA name, standard UTC offset in minutes, DST rule)
+///
+/// Offsets use the sign convention of `BrowserFingerprint::timezone_offset`:
+/// positive east of UTC.
+const TIMEZONES: &[(&str, i32, Option<DstRule>)] = &[
+    ("UTC", 0, None),
+    ("Etc/UTC", 0, None),
+    ("America/New_York", -300, Some(US_DST)),
+    ("America/Toronto", -300, Some(US_DST)),
+    ("America/Chicago", -360, Some(US_DST)),
+    ("America/Denver", -420, Some(US_DST)),
+    ("America/Phoenix", -420, None),
+    ("America/Los_Angeles", -480, Some(US_DST)),
+    ("America/Vancouver", -480, Some(US_DST)),
+    ("America/Anchorage", -540, Some(US_DST)),
+    ("Pacific/Honolulu", -600, None),
+    ("America/Mexico_City", -360, None),
+    ("America/Sao_Paulo", -180, None),
+    ("America/Buenos_Aires", -180, None),
+    ("America/Argentina/Buenos_Aires", -180, None),
+    ("Europe/London", 0, Some(EU_DST)),
+    ("Europe/Dublin", 0, Some(EU_DST)),
+    ("Europe/Lisbon", 0, Some(EU_DST)),
+    ("Europe/Paris", 60, Some(EU_DST)),
+    ("Europe/Berlin", 60, Some(EU_DST)),
+    ("Europe/Madrid", 60, Some(EU_DST)),
+    ("Europe/Rome", 60, Some(EU_DST)),
+    ("Europe/Amsterdam", 60, Some(EU_DST)),
+    ("Europe/Brussels", 60, Some(EU_DST)),
+    ("Europe/Vienna", 60, Some(EU_DST)),
+    ("Europe/Zurich", 60, Some(EU_DST)),
+    ("Europe/Stockholm", 60, Some(EU_DST)),
+    ("Europe/Oslo", 60, Some(EU_DST)),
+    ("Europe/Copenhagen", 60, Some(EU_DST)),
+    ("Europe/Prague", 60, Some(EU_DST)),
+    ("Europe/Warsaw", 60, Some(EU_DST)),
+    ("Europe/Budapest", 60, Some(EU_DST)),
+    ("Europe/Helsinki", 120, Some(EU_DST)),
+    ("Europe/Athens", 120, Some(EU_DST)),
+    ("Europe/Bucharest", 120, Some(EU_DST)),
+    ("Europe/Kyiv", 120, Some(EU_DST)),
+    ("Europe/Istanbul", 180, None),
+    ("Europe/Moscow", 180, None),
+    ("Asia/Dubai", 240, None),
+    ("Asia/Kolkata", 330, None),
+    ("Asia/Bangkok", 420, None),
+    ("Asia/Jakarta", 420, None),
+    ("Asia/Shanghai", 480, None),
+    ("Asia/Hong_Kong", 480, None),
+    ("Asia/Taipei", 480, None),
+    ("Asia/Singapore", 480, None),
+    ("Australia/Perth", 480, None),
+    ("Asia/Seoul", 540, None),
+    ("Asia/Tokyo", 540, None),
+    ("Australia/Brisbane", 600, None),
+    ("Australia/Sydney", 600, Some(AU_DST)),
+    ("Australia/Melbourne", 600, Some(AU_DST)),
+    ("Pacific/Auckland", 720, Some(NZ_DST)),
+];
+
+/// Main timezone of a region subtag of a language tag
+const REGION_TIMEZONES: &[(&str, &str)] = &[
+    ("US", "America/New_York"),
+    ("CA", "America/Toronto"),
+    ("MX", "America/Mexico_City"),
+    ("419", "America/Mexico_City"),
+    ("BR", "America/Sao_Paulo"),
+    ("AR", "America/Argentina/Buenos_Aires"),
+    ("GB", "Europe/London"),
+    ("IE", "Europe/Dublin"),
+    ("PT", "Europe/Lisbon"),
+    ("FR", "Europe/Paris"),
+    ("BE", "Europe/Brussels"),
+    ("DE", "Europe/Berlin"),
+    ("AT", "Europe/Vienna"),
+    ("CH", "Europe/Zurich"),
+    ("ES", "Europe/Madrid"),
+    ("IT", "Europe/Rome"),
+    ("NL", "Europe/Amsterdam"),
+    ("SE", "Europe/Stockholm"),
+    ("NO", "Europe/Oslo"),
+    ("DK", "Europe/Copenhagen"),
+    ("CZ", "Europe/Prague"),
+    ("PL", "Europe/Warsaw"),
+    ("HU", "Europe/Budapest"),
+    ("FI", "Europe/Helsinki"),
+    ("GR", "Europe/Athens"),
+    ("RO", "Europe/Bucharest"),
+    ("UA", "Europe/Kyiv"),
+    ("TR", "Europe/Istanbul"),
+    ("RU", "Europe/Moscow"),
+    ("AE", "Asia/Dubai"),
+    ("IN", "Asia/Kolkata"),
+    ("TH", "Asia/Bangkok"),
+    ("ID", "Asia/Jakarta"),
+    ("CN", "Asia/Shanghai"),
+    ("HK", "Asia/Hong_Kong"),
+    ("TW", "Asia/Taipei"),
+    ("SG", "Asia/Singapore"),
+    ("KR", "Asia/Seoul"),
+    ("JP", "Asia/Tokyo"),
+    ("AU", "Australia/Sydney"),
+    ("NZ", "Pacific/Auckland"),
+];
+
+/// Most likely timezone of a language tag without (known) region
+const LANGUAGE_TIMEZONES: &[(&str, &str)] = &[
+    ("en", "America/New_York"),
+    ("es", "Europe/Madrid"),
+    ("pt", "America/Sao_Paulo"),
+    ("fr", "Europe/Paris"),
+    ("de", "Europe/Berlin"),
+    ("it", "Europe/Rome"),
+    ("nl", "Europe/Amsterdam"),
+    ("sv", "Europe/Stockholm"),
+    ("nb", "Europe/Oslo"),
+    ("no", "Europe/Oslo"),
+    ("da", "Europe/Copenhagen"),
+    ("cs", "Europe/Prague"),
+    ("pl", "Europe/Warsaw"),
+    ("hu", "Europe/Budapest"),
+    ("fi", "Europe/Helsinki"),
+    ("el", "Europe/Athens"),
+    ("ro", "Europe/Bucharest"),
+    ("uk", "Europe/Kyiv"),
+    ("tr", "Europe/Istanbul"),
+    ("ru", "Europe/Moscow"),
+    ("ar", "Asia/Dubai"),
+    ("hi", "Asia/Kolkata"),
+    ("th", "Asia/Bangkok"),
+    ("id", "Asia/Jakarta"),
+    ("zh", "Asia/Shanghai"),
+    ("ko", "Asia/Seoul"),
+    ("ja", "Asia/Tokyo"),
+];
+
+/// Timezone spoofing configuration
+///
+/// Zones missing from the bundled table are not spoofed, since their
+/// offsets could not be computed; [`is_known`](Self::is_known) tells.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimezoneConfig {
+    /// IANA name of the spoofed timezone, e.g. `Europe/Berlin`
+    pub iana_timezone: String,
+}
+
+impl TimezoneConfig {
+    /// Create a configuration spoofing the given IANA timezone
+    pub fn new(iana_timezone: impl Into<String>) -> Self {
+        Self {
+            iana_timezone: iana_timezone.into(),
+        }
+    }
+
+    /// A plausible timezone for a `navigator.language` tag such as `en-GB`
+    ///
+    /// The region subtag decides when it is known (`pt-BR` lives in São
+    /// Paulo, `pt-PT` in Lisbon), otherwise the language; anything else
+    /// falls back to UTC.
+    pub fn from_locale(locale: &str) -> Self {
+        let mut subtags = locale.split(['-', '_']).filter(|s| !s.is_empty());
+        let language = subtags.next().unwrap_or_default().to_ascii_lowercase();
+        // Skip script subtags such as `Hant`; regions are 2 letters or 3 digits
+        let region = subtags
+            .find(|s| {
+                (s.len() == 2 && s.chars().all(|c| c.is_ascii_alphabetic()))
+                    || (s.len() == 3 && s.chars().all(|c| c.is_ascii_digit()))
+            })
+            .map(|s| s.to_ascii_uppercase());
+
+        let by_region = region.and_then(|region| {
+            REGION_TIMEZONES
+                .iter()
+                .find(|(r, _)| *r == region)
+                .map(|&(_, tz)| tz)
+        });
+        let by_language = || {
+            LANGUAGE_TIMEZONES
+                .iter()
+                .find(|(l, _)| *l == language)
+                .map(|&(_, tz)| tz)
+        };
+        Self::new(by_region.or_else(by_language).unwrap_or(FALLBACK_TIMEZONE))
+    }
+
+    /// Whether the timezone is in the bundled table and gets spoofed
+    pub fn is_known(&self) -> bool {
+        self.zone().is_some()
+    }
+
+    fn zone(&self) -> Option<(i32, Option<DstRule>)> {
+        TIMEZONES
+            .iter()
+            .find(|(tz, _, _)| *tz == self.iana_timezone)
+            .map(|&(_, standard, dst)| (standard, dst))
+    }
+
+    /// UTC offset in minutes (positive east of UTC) at `timestamp_ms`
+    /// milliseconds since the Unix epoch, including daylight saving time
+    ///
+    /// Note that `getTimezoneOffset` reports the negated value. Returns
+    /// `None` for timezones missing from the bundled table.
+    pub fn utc_offset_minutes(&self, timestamp_ms: i64) -> Option<i32> {
+        let (standard, dst) = self.zone()?;
+        let Some(rule) = dst else {
+            return Some(standard);
+        };
+
+        // No zone switches around New Year, so the local year is unambiguous
+        let local = DateTime::from_timestamp_millis(timestamp_ms + standard as i64 * 60_000)?;
+        let year = local.year();
+        let start = transition_ms(year, &rule.start, rule.utc, standard)?;
+        let end = transition_ms(year, &rule.end, rule.utc, standard + 60)?;
+        let in_dst = if start < end {
+            timestamp_ms >= start && timestamp_ms < end
+        } else {
+            // Southern hemisphere: DST spans the turn of the year
+            timestamp_ms >= start || timestamp_ms < end
+        };
+        Some(if in_dst { standard + 60 } else { standard })
+    }
+
+    /// Generate JavaScript override script for timezone spoofing
+    ///
+    /// This script must be injected before any page scripts run, and after
+    /// the fingerprint overrides, whose fixed offset it replaces.
+    pub fn generate_override_script(&self) -> String {
+        let Some((standard, dst)) = self.zone() else {
+            return format!(
+                "// Timezone spoofing disabled (unknown timezone '{}')",
+                self.iana_timezone
+            );
+        };
+
+        let timezone_json =
+            serde_json::to_string(&self.iana_timezone).unwrap_or_else(|_| "\"UTC\"".to_string());
+        let dst_js = match dst {
+            Some(rule) => format!(
+                "{{ start: [{}, {}, {}], end: [{}, {}, {}], utc: {} }}",
+                rule.start.month,
+                rule.start.week,
+                rule.start.minutes,
+                rule.end.month,
+                rule.end.week,
+                rule.end.minutes,
+                rule.utc
+            ),
+            None => "null".to_string(),
+        };
+
+        format!(
+            r#"
+// Timezone Spoofing
+(function() {{
+    'use strict';
+
+    const TIME_ZONE = {timezone_json};
+    const STANDARD_OFFSET = {standard};
+    // [month, sunday of the month (5 = last), minutes after midnight]
+    const DST = {dst_js};
+
+    const nativeGetTime = Date.prototype.getTime;
+
+    function sunday(year, month, week) {{
+        if (week === {last_week}) {{
+            const last = new Date(Date.UTC(year, month, 0));
+            return last.getUTCDate() - last.getUTCDay();
+        }}
+        const first = new Date(Date.UTC(year, month - 1, 1)).getUTCDay();
+        return 1 + (7 - first) % 7 + 7 * (week - 1);
+    }}
+
+    function transition(year, t, offsetBefore) {{
+        const midnight = Date.UTC(year, t[0] - 1, sunday(year, t[0], t[1]));
+        return midnight + (t[2] - (DST.utc ? 0 : offsetBefore)) * 60000;
+    }}
+
+    // UTC offset in minutes, positive east of UTC
+    function utcOffset(ms) {{
+        if (!DST) return STANDARD_OFFSET;
+        const year = new Date(ms + STANDARD_OFFSET * 60000).getUTCFullYear();
+        const start = transition(year, DST.start, STANDARD_OFFSET);
+        const end = transition(year, DST.end, STANDARD_OFFSET + 60);
+        const inDst = start < end ? (ms >= start && ms < end) : (ms >= start || ms < end);
+        return inDst ? STANDARD_OFFSET + 60 : STANDARD_OFFSET;
+    }}
+
+    function withTimeZone(options) {{
+        const copy = Object.assign({{}}, options);
+        if (copy.timeZone === undefined) copy.timeZone = TIME_ZONE;
+        return copy;
+    }}
+
+    // Make the overrides look native
+    {mark_native}
+
+    Date.prototype.getTimezoneOffset = function getTimezoneOffset() {{
+        const ms = nativeGetTime.call(this);
+        return isNaN(ms) ? NaN : -utcOffset(ms);
+    }};
+    markNative(Date.prototype.getTimezoneOffset, 'getTimezoneOffset');
+
+    for (const name of ['toLocaleString', 'toLocaleDateString', 'toLocaleTimeString']) {{
+        const native = Date.prototype[name];
+        const override = {{
+            [name](locales, options) {{
+                return native.call(this, locales, withTimeZone(options));
+            }}
+        }}[name];
+        Object.defineProperty(Date.prototype, name, {{
+            value: override, writable: true, configurable: true, enumerable: false
+        }});
+        markNative(override, name);
+    }}
+
+    // Formatters that got the spoofed zone because they asked for none
+    const defaulted = new WeakSet();
+    const NativeDateTimeFormat = Intl.DateTimeFormat;
+    function create(make, args) {{
+        const options = args[1];
+        const formatter = make([args[0], withTimeZone(options)]);
+        if (!options || options.timeZone === undefined) defaulted.add(formatter);
+        return formatter;
+    }}
+    const DateTimeFormat = new Proxy(NativeDateTimeFormat, {{
+        construct: function(target, args, newTarget) {{
+            return create((a) => Reflect.construct(target, a, newTarget), args);
+        }},
+        apply: function(target, thisArg, args) {{
+            return create((a) => Reflect.apply(target, thisArg, a), args);
+        }}
+    }});
+    Object.defineProperty(Intl, 'DateTimeFormat', {{
+        value: DateTimeFormat, writable: true, configurable: true, enumerable: false
+    }});
+    Object.defineProperty(NativeDateTimeFormat.prototype, 'constructor', {{
+        value: DateTimeFormat, writable: true, configurable: true, enumerable: false
+    }});
+    markNative(DateTimeFormat, 'DateTimeFormat');
+
+    const nativeResolvedOptions = NativeDateTimeFormat.prototype.resolvedOptions;
+    NativeDateTimeFormat.prototype.resolvedOptions = function resolvedOptions() {{
+        const options = nativeResolvedOptions.call(this);
+        if (defaulted.has(this)) options.timeZone = TIME_ZONE;
+        return options;
+    }};
+    markNative(NativeDateTimeFormat.prototype.resolvedOptions, 'resolvedOptions');
+}})();
+"#,
+            last_week = LAST_WEEK,
+            mark_native = mark_native_script(),
+        )
+    }
+}
+
+/// Sunday `week` of `month` in `year` ([`LAST_WEEK`] for the last one)
+fn nth_sunday(year: i32, month: u32, week: u32) -> Option<NaiveDate> {
+    if week == LAST_WEEK {
+        let next_month = if month == 12 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+        } else {
+            NaiveDate::from_ymd_opt(year, month + 1, 1)?
+        };
+        let last = next_month.pred_opt()?;
+        Some(last - Duration::days(last.weekday().num_days_from_sunday() as i64))
+    } else {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let first_sunday = (7 - first.weekday().num_days_from_sunday()) % 7;
+        Some(first + Duration::days((first_sunday + 7 * (week - 1)) as i64))
+    }
+}
+
+/// Unix time in milliseconds of a transition in `year`, where
+/// `offset_before` is the UTC offset in force until then
+fn transition_ms(year: i32, transition: &Transition, utc: bool, offset_before: i32) -> Option<i64> {
+    let midnight = nth_sunday(year, transition.month, transition.week)?
+        .and_hms_opt(0, 0, 0)?
+        .and_utc()
+        .timestamp_millis();
+    let minutes = if utc {
+        transition.minutes
+    } else {
+        transition.minutes - offset_before as i64
+    };
+    Some(midnight + minutes * 60_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn ms(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> i64 {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap().timestamp_millis()
+    }
+
+    #[test]
+    fn test_new_york_dst_boundaries() {
+        let config = TimezoneConfig::new("America/New_York");
+        let offset = |t| config.utc_offset_minutes(t).unwrap();
+
+        assert_eq!(offset(ms(2024, 1, 15, 12, 0)), -300);
+        // 2024-03-10 02:00 EST = 07:00 UTC
+        assert_eq!(offset(ms(2024, 3, 10, 6, 59)), -300);
+        assert_eq!(offset(ms(2024, 3, 10, 7, 0)), -240);
+        assert_eq!(offset(ms(2024, 7, 4, 12, 0)), -240);
+        // 2024-11-03 02:00 EDT = 06:00 UTC
+        assert_eq!(offset(ms(2024, 11, 3, 5, 59)), -240);
+        assert_eq!(offset(ms(2024, 11, 3, 6, 0)), -300);
+        // 2025-03-09 and 2025-11-02
+        assert_eq!(offset(ms(2025, 3, 9, 7, 0)), -240);
+        assert_eq!(offset(ms(2025, 11, 2, 6, 0)), -300);
+    }
+
+    #[test]
+    fn test_eu_and_southern_dst() {
+        let berlin = TimezoneConfig::new("Europe/Berlin");
+        assert_eq!(berlin.utc_offset_minutes(ms(2024, 3, 31, 0, 59)), Some(60));
+        assert_eq!(berlin.utc_offset_minutes(ms(2024, 3, 31, 1, 0)), Some(120));
+        assert_eq!(berlin.utc_offset_minutes(ms(2024, 10, 27, 1, 0)), Some(60));
+
+        let sydney = TimezoneConfig::new("Australia/Sydney");
+        assert_eq!(sydney.utc_offset_minutes(ms(2024, 1, 15, 0, 0)), Some(660));
+        // 2024-04-07 03:00 AEDT = 04-06 16:00 UTC
+        assert_eq!(sydney.utc_offset_minutes(ms(2024, 4, 6, 16, 0)), Some(600));
+        // 2024-10-06 02:00 AEST = 10-05 16:00 UTC
+        assert_eq!(sydney.utc_offset_minutes(ms(2024, 10, 5, 15, 59)), Some(600));
+        assert_eq!(sydney.utc_offset_minutes(ms(2024, 10, 5, 16, 0)), Some(660));
+
+        let tokyo = TimezoneConfig::new("Asia/Tokyo");
+        assert_eq!(tokyo.utc_offset_minutes(ms(2024, 7, 1, 0, 0)), Some(540));
+        assert_eq!(TimezoneConfig::new("Mars/Olympus_Mons").utc_offset_minutes(0), None);
+    }
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(TimezoneConfig::from_locale("en-US").iana_timezone, "America/New_York");
+        assert_eq!(TimezoneConfig::from_locale("en-GB").iana_timezone, "Europe/London");
+        assert_eq!(TimezoneConfig::from_locale("pt_BR").iana_timezone, "America/Sao_Paulo");
+        assert_eq!(TimezoneConfig::from_locale("zh-Hant-TW").iana_timezone, "Asia/Taipei");
+        assert_eq!(TimezoneConfig::from_locale("es-419").iana_timezone, "America/Mexico_City");
+        assert_eq!(TimezoneConfig::from_locale("de").iana_timezone, "Europe/Berlin");
+        assert_eq!(TimezoneConfig::from_locale("ja").iana_timezone, "Asia/Tokyo");
+        assert_eq!(TimezoneConfig::from_locale("xx").iana_timezone, "UTC");
+        for locale in ["en-US", "fr-CA", "ko-KR", "en-NZ", "ar"] {
+            assert!(TimezoneConfig::from_locale(locale).is_known(), "{}", locale);
+        }
+    }
+
+    #[test]
+    fn test_script_overrides_date_and_intl() {
+        let js = TimezoneConfig::new("America/New_York").generate_override_script();
+        assert!(js.contains(r#"const TIME_ZONE = "America/New_York";"#));
+        assert!(js.contains("const STANDARD_OFFSET = -300;"));
+        assert!(js.contains("const DST = { start: [3, 2, 120], end: [11, 1, 120], utc: false };"));
+        assert!(js.contains("Date.prototype.getTimezoneOffset"));
+        assert!(js.contains("'toLocaleString'"));
+        assert!(js.contains("Object.defineProperty(Intl, 'DateTimeFormat'"));
+        assert!(js.contains("prototype.resolvedOptions"));
+        assert!(js.contains("markNative(DateTimeFormat, 'DateTimeFormat');"));
+        assert_eq!(js.matches("Function.prototype.toString = ").count(), 1);
+
+        let js = TimezoneConfig::new("Asia/Tokyo").generate_override_script();
+        assert!(js.contains("const DST = null;"));
+
+        let js = TimezoneConfig::new("Mars/Olympus_Mons").generate_override_script();
+        assert!(!js.contains("getTimezoneOffset"));
+    }
+}
@@ -5,6 +5,7 @@
 //! canvas noise settings.
 
 use super::config::WebGLConfig;
+use super::precision::ShaderPrecision;
 use super::profiles::WebGLProfile;
 
 /// Builder for custom WebGL configurations
@@ -76,6 +77,13 @@ impl WebGLConfigBuilder {
         self
     }
 
+    /// Set vertex and fragment shader precision formats
+    pub fn shader_precision(mut self, vertex: ShaderPrecision, fragment: ShaderPrecision) -> Self {
+        self.config.vertex_precision = vertex;
+        self.config.fragment_precision = fragment;
+        self
+    }
+
     /// Enable or disable canvas noise
     pub fn canvas_noise(mut self, enabled: bool, intensity: f64) -> Self {
         self.config.enable_canvas_noise = enabled;
@@ -4,14 +4,16 @@
 //! GPU profile and generates comprehensive JavaScript to override WebGL getParameter,
 //! getExtension, OffscreenCanvas contexts, and WebGPU adapter info.
 
+use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
+use super::precision::ShaderPrecision;
 use super::profiles::WebGLProfile;
 use super::scripts::generate_canvas_noise_script;
 
 /// WebGL configuration for fingerprint spoofing
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebGLConfig {
     /// WebGL vendor string (WEBGL_debug_renderer_info extension)
     pub vendor: String,
@@ -37,6 +39,10 @@ pub struct WebGLConfig {
     pub max_vertex_uniform_vectors: u32,
     /// Maximum fragment uniform vectors
     pub max_fragment_uniform_vectors: u32,
+    /// Vertex shader precision formats (getShaderPrecisionFormat)
+    pub vertex_precision: ShaderPrecision,
+    /// Fragment shader precision formats (getShaderPrecisionFormat)
+    pub fragment_precision: ShaderPrecision,
     /// Enable canvas noise injection
     pub enable_canvas_noise: bool,
     /// Noise intensity (0.0 - 1.0, recommended: 0.0001 - 0.001)
@@ -66,6 +72,14 @@ impl WebGLConfig {
                 (16384, (16384, 16384), 16)
             }
             WebGLProfile::IntelIrisXe | WebGLProfile::IntelArcA770 => (16384, (16384, 16384), 16),
+            WebGLProfile::IntelIrisPlus655 => (16384, (16384, 16384), 16),
+            // Mesa drivers
+            WebGLProfile::MesaIntelUhd630 | WebGLProfile::MesaLlvmpipe => {
+                (16384, (16384, 16384), 16)
+            }
+            // Mobile GPUs
+            WebGLProfile::Adreno740 => (16384, (16384, 16384), 16),
+            WebGLProfile::MaliG78 => (8192, (8192, 8192), 16),
             // Apple Silicon
             WebGLProfile::AppleM1 | WebGLProfile::AppleM2 | WebGLProfile::AppleM3 => {
                 (16384, (16384, 16384), 16)
@@ -75,6 +89,7 @@ impl WebGLConfig {
                 (8192, (8192, 8192), 16)
             }
         };
+        let (vertex_precision, fragment_precision) = ShaderPrecision::for_profile(&profile);
 
         Self {
             vendor: profile.vendor().to_string(),
@@ -90,6 +105,8 @@ impl WebGLConfig {
             max_varying_vectors: 30,
             max_vertex_uniform_vectors: 4096,
             max_fragment_uniform_vectors: 1024,
+            vertex_precision,
+            fragment_precision,
             enable_canvas_noise: true,
             canvas_noise_intensity: 0.0001,
             profile: Some(profile),
@@ -178,35 +195,42 @@ impl WebGLConfig {
     }
 
     /// Get a WebGL configuration appropriate for a fingerprint profile
+    ///
+    /// Picks a random GPU from the profile's OS table
+    /// ([`WebGLProfile::for_fingerprint`]), so a Mac never claims an NVIDIA
+    /// card and a Linux machine reports a Mesa driver.
     pub fn for_profile(fp_profile: &crate::stealth::FingerprintProfile) -> Self {
-        use crate::stealth::FingerprintProfile;
-
-        match fp_profile {
-            FingerprintProfile::WindowsChrome
-            | FingerprintProfile::WindowsFirefox
-            | FingerprintProfile::WindowsEdge => {
-                // Windows typically has discrete GPUs or Intel integrated
-                Self::random()
-            }
-            FingerprintProfile::MacChrome
-            | FingerprintProfile::MacSafari
-            | FingerprintProfile::MacFirefox => {
-                // Modern Macs use Apple Silicon
-                Self::apple_m1()
-            }
-            FingerprintProfile::LinuxChrome | FingerprintProfile::LinuxFirefox => {
-                // Linux often has NVIDIA or AMD
-                Self::nvidia_rtx_3060()
-            }
-            FingerprintProfile::Custom => Self::random(),
-        }
+        use std::time::{SystemTime, UNIX_EPOCH};
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64;
+
+        let profiles = WebGLProfile::for_fingerprint(fp_profile);
+        let index = (seed as usize) % profiles.len();
+        Self::from_profile(profiles[index].clone())
+    }
+
+    /// [`for_profile`](Self::for_profile) with the GPU picked from a seed
+    pub fn consistent_for_profile(
+        fp_profile: &crate::stealth::FingerprintProfile,
+        seed: &str,
+    ) -> Self {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let profiles = WebGLProfile::for_fingerprint(fp_profile);
+        let index = (hash as usize) % profiles.len();
+        Self::from_profile(profiles[index].clone())
     }
 
     /// Generate JavaScript code to override WebGL properties
     ///
     /// This script must be injected before any page scripts run.
     /// Covers: WebGLRenderingContext, WebGL2RenderingContext, OffscreenCanvas WebGL,
-    /// WEBGL_debug_renderer_info extension, and WebGPU adapter info.
+    /// WEBGL_debug_renderer_info extension, getShaderPrecisionFormat, and WebGPU
+    /// adapter info.
     pub fn get_js_override_script(&self) -> String {
         let canvas_noise_script = if self.enable_canvas_noise {
             generate_canvas_noise_script(self.canvas_noise_intensity)
@@ -247,6 +271,66 @@ impl WebGLConfig {
     const GL_MAX_VERTEX_UNIFORM_VECTORS = 0x8DFB;
     const GL_MAX_FRAGMENT_UNIFORM_VECTORS = 0x8DFD;
 
+    // === Shader precision constants and per-shader tables ===
+    const GL_FRAGMENT_SHADER = 0x8B30;
+    const GL_VERTEX_SHADER = 0x8B31;
+    const LOW_FLOAT = 0x8DF0;
+    const MEDIUM_FLOAT = 0x8DF1;
+    const HIGH_FLOAT = 0x8DF2;
+    const LOW_INT = 0x8DF3;
+    const MEDIUM_INT = 0x8DF4;
+    const HIGH_INT = 0x8DF5;
+    const SHADER_PRECISION = {{
+        [GL_VERTEX_SHADER]: {vertex_precision},
+        [GL_FRAGMENT_SHADER]: {fragment_precision}
+    }};
+
+    // === Helper: Replace a WebGLShaderPrecisionFormat with profile values ===
+    // Like a native one, the fake is a WebGLShaderPrecisionFormat without own
+    // properties: the prototype getters look its values up in spoofedPrecision.
+    const spoofedPrecision = new WeakMap();
+    const precisionProto = typeof WebGLShaderPrecisionFormat !== 'undefined'
+        ? WebGLShaderPrecisionFormat.prototype
+        : null;
+    if (precisionProto) {{
+        ['rangeMin', 'rangeMax', 'precision'].forEach(function(key, index) {{
+            const descriptor = Object.getOwnPropertyDescriptor(precisionProto, key);
+            if (!descriptor || typeof descriptor.get !== 'function') return;
+            const nativeGet = descriptor.get;
+            // A getter from an accessor literal is named 'get <key>' like the native one
+            const getter = Object.getOwnPropertyDescriptor({{
+                get [key]() {{
+                    const values = spoofedPrecision.get(this);
+                    return values ? values[index] : nativeGet.call(this);
+                }}
+            }}, key).get;
+            Object.defineProperty(precisionProto, key, {{
+                get: getter,
+                enumerable: descriptor.enumerable,
+                configurable: descriptor.configurable
+            }});
+        }});
+    }}
+
+    const spoofPrecision = function(original, shaderType, precisionType) {{
+        const table = SHADER_PRECISION[shaderType];
+        const values = table && table[precisionType];
+        if (!original || !values || !precisionProto) return original;
+        const fake = Object.create(precisionProto);
+        spoofedPrecision.set(fake, values);
+        return fake;
+    }};
+
+    // === Helper: Patch getShaderPrecisionFormat on a prototype or instance ===
+    const overrideShaderPrecision = function(obj) {{
+        const originalGetShaderPrecisionFormat = obj.getShaderPrecisionFormat;
+        if (typeof originalGetShaderPrecisionFormat !== 'function') return;
+        obj.getShaderPrecisionFormat = function(shaderType, precisionType) {{
+            const result = originalGetShaderPrecisionFormat.call(this, shaderType, precisionType);
+            return spoofPrecision(result, shaderType, precisionType);
+        }};
+    }};
+
     // === Helper: Patch getParameter on a WebGL context prototype ===
     const overrideGetParameter = function(target) {{
         const originalGetParameter = target.prototype.getParameter;
@@ -298,6 +382,7 @@ impl WebGLConfig {
         overrideGetParameter(WebGLRenderingContext);
         overrideGetExtension(WebGLRenderingContext);
         overrideGetSupportedExtensions(WebGLRenderingContext);
+        overrideShaderPrecision(WebGLRenderingContext.prototype);
     }}
 
     // === 2. Apply overrides to WebGL2RenderingContext ===
@@ -305,6 +390,7 @@ impl WebGLConfig {
         overrideGetParameter(WebGL2RenderingContext);
         overrideGetExtension(WebGL2RenderingContext);
         overrideGetSupportedExtensions(WebGL2RenderingContext);
+        overrideShaderPrecision(WebGL2RenderingContext.prototype);
     }}
 
     // === 3. Override HTMLCanvasElement.getContext ===
@@ -343,6 +429,7 @@ impl WebGLConfig {
                     }}
                     return origExt(name);
                 }};
+                overrideShaderPrecision(context);
             }}
             return context;
         }};
@@ -392,6 +479,7 @@ impl WebGLConfig {
                     }}
                     return extensions;
                 }};
+                overrideShaderPrecision(ctx);
             }}
             return ctx;
         }};
@@ -451,6 +539,8 @@ impl WebGLConfig {
             max_varying_vectors = self.max_varying_vectors,
             max_vertex_uniform_vectors = self.max_vertex_uniform_vectors,
             max_fragment_uniform_vectors = self.max_fragment_uniform_vectors,
+            vertex_precision = self.vertex_precision.to_js_table(),
+            fragment_precision = self.fragment_precision.to_js_table(),
             canvas_noise_script = canvas_noise_script,
         )
     }
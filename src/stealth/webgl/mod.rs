@@ -11,6 +11,7 @@
 //!
 //! - `WebGLConfig` - Configuration for WebGL spoofing
 //! - `WebGLProfile` - Predefined GPU profiles
+//! - `ShaderPrecision` - Profile-consistent `getShaderPrecisionFormat` values
 //! - Canvas noise injection for additional protection
 //!
 //! # Example
@@ -30,11 +31,13 @@
 
 mod builder;
 mod config;
+mod precision;
 mod profiles;
 mod scripts;
 
 pub use builder::WebGLConfigBuilder;
 pub use config::WebGLConfig;
+pub use precision::{PrecisionFormat, ShaderPrecision};
 pub use profiles::WebGLProfile;
 pub use scripts::generate_canvas_noise_script;
 
@@ -72,6 +75,45 @@ mod tests {
         assert_eq!(WebGLProfile::AppleM2.architecture(), "apple-8");
     }
 
+    #[test]
+    fn test_for_profile_matches_os() {
+        use crate::stealth::FingerprintProfile;
+
+        for _ in 0..20 {
+            let safari = WebGLConfig::for_profile(&FingerprintProfile::MacSafari);
+            assert_eq!(safari.vendor, "Apple Inc.");
+            assert!(!safari.renderer.contains("NVIDIA"));
+        }
+
+        let seeded = WebGLConfig::consistent_for_profile(&FingerprintProfile::MacSafari, "seed");
+        assert_eq!(seeded.vendor, "Apple Inc.");
+        assert_eq!(
+            seeded.renderer,
+            WebGLConfig::consistent_for_profile(&FingerprintProfile::MacSafari, "seed").renderer
+        );
+
+        for gpu in WebGLProfile::for_fingerprint(&FingerprintProfile::MacChrome) {
+            assert!(!gpu.renderer().contains("NVIDIA"));
+            assert!(!gpu.renderer().contains("Direct3D"));
+        }
+        for gpu in WebGLProfile::for_fingerprint(&FingerprintProfile::LinuxFirefox) {
+            assert!(gpu.renderer().contains("Mesa") || gpu.renderer().contains("llvmpipe"));
+        }
+        for gpu in WebGLProfile::for_fingerprint(&FingerprintProfile::WindowsChrome) {
+            assert!(gpu.renderer().contains("Direct3D11"));
+        }
+    }
+
+    #[test]
+    fn test_mobile_gpus_report_mobile_precision() {
+        for gpu in WebGLProfile::mobile() {
+            assert!(["Qualcomm", "ARM"].contains(&gpu.vendor()));
+            let config = WebGLConfig::from_profile(gpu);
+            assert_eq!(config.fragment_precision.medium_float, PrecisionFormat::FLOAT16);
+            assert_eq!(config.fragment_precision.high_float, PrecisionFormat::FLOAT32);
+        }
+    }
+
     #[test]
     fn test_random_config() {
         let config1 = WebGLConfig::random();
@@ -162,6 +204,36 @@ mod tests {
         assert!(js.contains(r#"ARCHITECTURE = "ampere""#), "WebGPU architecture should be 'ampere' for RTX 3060");
     }
 
+    #[test]
+    fn test_js_override_returns_profile_high_float_precision() {
+        let config = WebGLConfig::apple_m1();
+        let (vertex, fragment) = ShaderPrecision::for_profile(&WebGLProfile::AppleM1);
+        assert_eq!(config.vertex_precision, vertex);
+        assert_eq!(config.fragment_precision, fragment);
+
+        let js = config.get_js_override_script();
+        assert!(js.contains("getShaderPrecisionFormat"));
+        // The fake shares the native prototype and has no own properties
+        assert!(js.contains("Object.create(precisionProto)"));
+        assert!(js.contains("spoofedPrecision.set(fake, values);"));
+        assert!(!js.contains("Object.defineProperties(fake"));
+        let hf = config.fragment_precision.high_float;
+        assert!(js.contains(&format!(
+            "[HIGH_FLOAT]: [{}, {}, {}]",
+            hf.range_min, hf.range_max, hf.precision
+        )));
+
+        // A custom HIGH_FLOAT value must be what the override hands back
+        let mut custom = ShaderPrecision::desktop();
+        custom.high_float = PrecisionFormat::new(62, 62, 16);
+        let config = WebGLConfigBuilder::from_profile(WebGLProfile::AppleM1)
+            .shader_precision(custom, custom)
+            .build();
+        let js = config.get_js_override_script();
+        assert!(js.contains("[HIGH_FLOAT]: [62, 62, 16]"));
+        assert!(!js.contains("[HIGH_FLOAT]: [127, 127, 23]"));
+    }
+
     #[test]
     fn test_canvas_noise_script() {
         let script = generate_canvas_noise_script(0.0001);
@@ -0,0 +1,147 @@
+//! Shader precision formats reported by `getShaderPrecisionFormat`.
+//!
+//! Contains [`PrecisionFormat`] (one `rangeMin`/`rangeMax`/`precision` triple)
+//! and [`ShaderPrecision`] (the six precision levels of one shader type).
+//! Detectors read these per shader type and compare them against the claimed
+//! GPU, so they are kept consistent with the selected [`WebGLProfile`].
+
+use serde::{Deserialize, Serialize};
+
+use super::profiles::WebGLProfile;
+
+/// A single `WebGLShaderPrecisionFormat` value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrecisionFormat {
+    /// Base-2 log of the absolute value of the minimum representable value
+    pub range_min: i32,
+    /// Base-2 log of the absolute value of the maximum representable value
+    pub range_max: i32,
+    /// Number of bits of precision (0 for integer formats)
+    pub precision: i32,
+}
+
+impl PrecisionFormat {
+    /// Create a precision format triple
+    pub const fn new(range_min: i32, range_max: i32, precision: i32) -> Self {
+        Self {
+            range_min,
+            range_max,
+            precision,
+        }
+    }
+
+    /// IEEE 754 single precision float (highp on desktop GPUs)
+    pub const FLOAT32: Self = Self::new(127, 127, 23);
+
+    /// IEEE 754 half precision float (mediump on mobile-class GPUs)
+    pub const FLOAT16: Self = Self::new(15, 15, 10);
+
+    /// 32-bit signed integer
+    pub const INT32: Self = Self::new(31, 30, 0);
+
+    /// 16-bit signed integer (lowp/mediump int on mobile-class GPUs)
+    pub const INT16: Self = Self::new(15, 14, 0);
+}
+
+/// Precision formats for every precision level of one shader type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShaderPrecision {
+    /// `LOW_FLOAT`
+    pub low_float: PrecisionFormat,
+    /// `MEDIUM_FLOAT`
+    pub medium_float: PrecisionFormat,
+    /// `HIGH_FLOAT`
+    pub high_float: PrecisionFormat,
+    /// `LOW_INT`
+    pub low_int: PrecisionFormat,
+    /// `MEDIUM_INT`
+    pub medium_int: PrecisionFormat,
+    /// `HIGH_INT`
+    pub high_int: PrecisionFormat,
+}
+
+impl ShaderPrecision {
+    /// Desktop GPUs: every float level is backed by 32-bit floats
+    pub const fn desktop() -> Self {
+        Self {
+            low_float: PrecisionFormat::FLOAT32,
+            medium_float: PrecisionFormat::FLOAT32,
+            high_float: PrecisionFormat::FLOAT32,
+            low_int: PrecisionFormat::INT32,
+            medium_int: PrecisionFormat::INT32,
+            high_int: PrecisionFormat::INT32,
+        }
+    }
+
+    /// Mobile GPUs: only highp is 32-bit, lowp and mediump use half floats
+    /// and 16-bit integers
+    pub const fn mobile() -> Self {
+        Self {
+            low_float: PrecisionFormat::FLOAT16,
+            medium_float: PrecisionFormat::FLOAT16,
+            high_float: PrecisionFormat::FLOAT32,
+            low_int: PrecisionFormat::INT16,
+            medium_int: PrecisionFormat::INT16,
+            high_int: PrecisionFormat::INT32,
+        }
+    }
+
+    /// Precision reported for a profile's vertex and fragment shaders.
+    ///
+    /// Chrome's ANGLE backends (D3D11, Metal, desktop GL and SwiftShader)
+    /// all expose full 32-bit precision at every level on desktop GPUs.
+    /// Adreno and Mali shaders report the reduced mobile precisions.
+    pub fn for_profile(profile: &WebGLProfile) -> (Self, Self) {
+        match profile {
+            WebGLProfile::Adreno740 | WebGLProfile::MaliG78 => (Self::mobile(), Self::mobile()),
+            WebGLProfile::NvidiaGtx1080
+            | WebGLProfile::NvidiaGtx1660
+            | WebGLProfile::NvidiaRtx3060
+            | WebGLProfile::NvidiaRtx3080
+            | WebGLProfile::NvidiaRtx4070
+            | WebGLProfile::NvidiaRtx4090
+            | WebGLProfile::AmdRx580
+            | WebGLProfile::AmdRx6700Xt
+            | WebGLProfile::AmdRx7900Xt
+            | WebGLProfile::IntelUhd620
+            | WebGLProfile::IntelUhd630
+            | WebGLProfile::IntelUhd770
+            | WebGLProfile::IntelIrisXe
+            | WebGLProfile::IntelArcA770
+            | WebGLProfile::IntelIrisPlus655
+            | WebGLProfile::MesaIntelUhd630
+            | WebGLProfile::MesaLlvmpipe
+            | WebGLProfile::AppleM1
+            | WebGLProfile::AppleM2
+            | WebGLProfile::AppleM3
+            | WebGLProfile::SwiftShader
+            | WebGLProfile::AngleDirect3D11 => (Self::desktop(), Self::desktop()),
+        }
+    }
+
+    /// Render as a JS object literal keyed by the precision-type constants
+    /// (`LOW_FLOAT` .. `HIGH_INT`) declared in the override script.
+    pub(crate) fn to_js_table(self) -> String {
+        let entry = |name: &str, f: PrecisionFormat| {
+            format!(
+                "[{}]: [{}, {}, {}]",
+                name, f.range_min, f.range_max, f.precision
+            )
+        };
+        format!(
+            "{{ {}, {}, {}, {}, {}, {} }}",
+            entry("LOW_FLOAT", self.low_float),
+            entry("MEDIUM_FLOAT", self.medium_float),
+            entry("HIGH_FLOAT", self.high_float),
+            entry("LOW_INT", self.low_int),
+            entry("MEDIUM_INT", self.medium_int),
+            entry("HIGH_INT", self.high_int),
+        )
+    }
+}
+
+impl Default for ShaderPrecision {
+    fn default() -> Self {
+        Self::desktop()
+    }
+}
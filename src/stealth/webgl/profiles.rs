@@ -1,11 +1,49 @@
 //! Predefined WebGL/GPU profile definitions for fingerprint spoofing.
 //!
 //! Contains the [`WebGLProfile`] enum with GPU profiles for NVIDIA, AMD, Intel,
-//! Apple Silicon, and software renderers. Each profile provides vendor, renderer,
-//! architecture, and short vendor strings matching real-world GPU configurations.
+//! Apple Silicon, Mesa, mobile, and software renderers. Each profile provides
+//! vendor, renderer, architecture, and short vendor strings matching real-world
+//! GPU configurations, and the tables below list which GPUs each fingerprint
+//! profile's OS plausibly reports.
+
+use serde::{Deserialize, Serialize};
+
+use crate::stealth::fingerprint::FingerprintProfile;
+
+/// Integrated Intel graphics and mainstream NVIDIA cards seen on Windows
+const WINDOWS_GPUS: &[WebGLProfile] = &[
+    WebGLProfile::IntelUhd620,
+    WebGLProfile::IntelUhd630,
+    WebGLProfile::IntelUhd770,
+    WebGLProfile::IntelIrisXe,
+    WebGLProfile::NvidiaGtx1660,
+    WebGLProfile::NvidiaRtx3060,
+    WebGLProfile::NvidiaRtx4070,
+];
+
+/// Apple Silicon and the Intel Iris of the last Intel MacBook Pros
+const MAC_GPUS: &[WebGLProfile] = &[
+    WebGLProfile::AppleM1,
+    WebGLProfile::AppleM2,
+    WebGLProfile::AppleM3,
+    WebGLProfile::IntelIrisPlus655,
+];
+
+/// Apple Silicon only, see [`WebGLProfile::for_fingerprint`]
+const SAFARI_GPUS: &[WebGLProfile] = &[
+    WebGLProfile::AppleM1,
+    WebGLProfile::AppleM2,
+    WebGLProfile::AppleM3,
+];
+
+/// Mesa drivers: Intel hardware or llvmpipe on machines without a GPU
+const LINUX_GPUS: &[WebGLProfile] = &[WebGLProfile::MesaIntelUhd630, WebGLProfile::MesaLlvmpipe];
+
+/// Qualcomm and ARM GPUs of current Android phones
+const MOBILE_GPUS: &[WebGLProfile] = &[WebGLProfile::Adreno740, WebGLProfile::MaliG78];
 
 /// Predefined WebGL/GPU profiles
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WebGLProfile {
     // NVIDIA profiles
     NvidiaGtx1080,
@@ -26,6 +64,15 @@ pub enum WebGLProfile {
     IntelUhd770,
     IntelIrisXe,
     IntelArcA770,
+    IntelIrisPlus655,
+
+    // Mesa drivers (Linux)
+    MesaIntelUhd630,
+    MesaLlvmpipe,
+
+    // Mobile GPUs
+    Adreno740,
+    MaliG78,
 
     // Apple Silicon
     AppleM1,
@@ -55,6 +102,11 @@ impl WebGLProfile {
             WebGLProfile::IntelUhd770,
             WebGLProfile::IntelIrisXe,
             WebGLProfile::IntelArcA770,
+            WebGLProfile::IntelIrisPlus655,
+            WebGLProfile::MesaIntelUhd630,
+            WebGLProfile::MesaLlvmpipe,
+            WebGLProfile::Adreno740,
+            WebGLProfile::MaliG78,
             WebGLProfile::AppleM1,
             WebGLProfile::AppleM2,
             WebGLProfile::AppleM3,
@@ -75,6 +127,29 @@ impl WebGLProfile {
         ]
     }
 
+    /// Get the phone GPUs reported by mobile profiles
+    pub fn mobile() -> Vec<WebGLProfile> {
+        MOBILE_GPUS.to_vec()
+    }
+
+    /// Get the GPUs plausible for a fingerprint profile's OS and browser
+    ///
+    /// Safari only gets Apple Silicon: it reports `Apple Inc.` as the vendor
+    /// on every Mac, so an Intel entry would contradict it. `Custom` profiles
+    /// get the Windows set, the most common one.
+    pub fn for_fingerprint(profile: &FingerprintProfile) -> Vec<WebGLProfile> {
+        let gpus = match profile {
+            FingerprintProfile::WindowsChrome
+            | FingerprintProfile::WindowsFirefox
+            | FingerprintProfile::WindowsEdge
+            | FingerprintProfile::Custom => WINDOWS_GPUS,
+            FingerprintProfile::MacChrome | FingerprintProfile::MacFirefox => MAC_GPUS,
+            FingerprintProfile::MacSafari => SAFARI_GPUS,
+            FingerprintProfile::LinuxChrome | FingerprintProfile::LinuxFirefox => LINUX_GPUS,
+        };
+        gpus.to_vec()
+    }
+
     /// Get the vendor string for this profile
     pub fn vendor(&self) -> &'static str {
         match self {
@@ -93,7 +168,14 @@ impl WebGLProfile {
             | WebGLProfile::IntelUhd630
             | WebGLProfile::IntelUhd770
             | WebGLProfile::IntelIrisXe
-            | WebGLProfile::IntelArcA770 => "Intel Inc.",
+            | WebGLProfile::IntelArcA770
+            | WebGLProfile::IntelIrisPlus655 => "Intel Inc.",
+
+            WebGLProfile::MesaIntelUhd630 => "Google Inc. (Intel)",
+            WebGLProfile::MesaLlvmpipe => "Google Inc. (Mesa)",
+
+            WebGLProfile::Adreno740 => "Qualcomm",
+            WebGLProfile::MaliG78 => "ARM",
 
             WebGLProfile::AppleM1 | WebGLProfile::AppleM2 | WebGLProfile::AppleM3 => "Apple Inc.",
 
@@ -120,7 +202,14 @@ impl WebGLProfile {
             | WebGLProfile::IntelUhd630
             | WebGLProfile::IntelUhd770
             | WebGLProfile::IntelIrisXe
-            | WebGLProfile::IntelArcA770 => "intel",
+            | WebGLProfile::IntelArcA770
+            | WebGLProfile::IntelIrisPlus655
+            | WebGLProfile::MesaIntelUhd630 => "intel",
+
+            WebGLProfile::MesaLlvmpipe => "mesa",
+
+            WebGLProfile::Adreno740 => "qualcomm",
+            WebGLProfile::MaliG78 => "arm",
 
             WebGLProfile::AppleM1 | WebGLProfile::AppleM2 | WebGLProfile::AppleM3 => "apple",
 
@@ -147,6 +236,14 @@ impl WebGLProfile {
             WebGLProfile::IntelUhd770 => "gen-12",
             WebGLProfile::IntelIrisXe => "gen-12",
             WebGLProfile::IntelArcA770 => "alchemist",
+            WebGLProfile::IntelIrisPlus655 | WebGLProfile::MesaIntelUhd630 => "gen-9.5",
+
+            // Mesa software rasterizer
+            WebGLProfile::MesaLlvmpipe => "llvmpipe",
+
+            // Mobile architectures
+            WebGLProfile::Adreno740 => "adreno-7xx",
+            WebGLProfile::MaliG78 => "valhall",
 
             // Apple architectures
             WebGLProfile::AppleM1 => "apple-7",
@@ -206,6 +303,17 @@ impl WebGLProfile {
             WebGLProfile::IntelArcA770 => {
                 "ANGLE (Intel, Intel(R) Arc(TM) A770 Graphics Direct3D11 vs_5_0 ps_5_0, D3D11)"
             }
+            WebGLProfile::IntelIrisPlus655 => "Intel(R) Iris(TM) Plus Graphics 655",
+
+            WebGLProfile::MesaIntelUhd630 => {
+                "ANGLE (Intel, Mesa Intel(R) UHD Graphics 630 (CFL GT2), OpenGL 4.6)"
+            }
+            WebGLProfile::MesaLlvmpipe => {
+                "ANGLE (Mesa, llvmpipe (LLVM 15.0.7, 256 bits), OpenGL 4.5)"
+            }
+
+            WebGLProfile::Adreno740 => "Adreno (TM) 740",
+            WebGLProfile::MaliG78 => "Mali-G78",
 
             WebGLProfile::AppleM1 => "Apple M1",
             WebGLProfile::AppleM2 => "Apple M2",
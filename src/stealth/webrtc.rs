@@ -11,11 +11,15 @@
 //!
 //! - `WebRtcConfig` - Configuration for WebRTC leak prevention
 //! - `WebRtcIpPolicy` - IP handling policy options
+//! - `IceCandidateFilter` - Which gathered ICE candidates pages get to see
+//! - `WebRtcPolicy` - Presets combining the settings above
 //!
 //! # Example
 //!
 //! ```rust,no_run
-//! use ki_browser_standalone::stealth::webrtc::{WebRtcConfig, WebRtcIpPolicy};
+//! use ki_browser_standalone::stealth::webrtc::{
+//!     IceCandidateFilter, WebRtcConfig, WebRtcIpPolicy, WebRtcPolicy,
+//! };
 //!
 //! // Completely disable WebRTC
 //! let config = WebRtcConfig::disabled();
@@ -23,15 +27,25 @@
 //! // Or use a restrictive policy
 //! let config = WebRtcConfig::new(WebRtcIpPolicy::DisableNonProxiedUdp);
 //!
+//! // Or let candidates through, but hide the local network
+//! let config = WebRtcConfig::new(WebRtcIpPolicy::Default)
+//!     .with_ice_candidate_filter(IceCandidateFilter::BlockLocalSubnet)
+//!     .with_spoof_local_ip("203.0.113.7");
+//!
+//! // Or start from a preset
+//! let config = WebRtcConfig::from_policy(WebRtcPolicy::FakePublicIp("203.0.113.7".into()));
+//!
 //! // Get the JavaScript override script
 //! let js = config.get_override_script();
 //! ```
 
+use serde::{Deserialize, Serialize};
+
 /// IP handling policy for WebRTC connections
 ///
 /// Controls how WebRTC handles IP address discovery, which directly
 /// impacts whether the real IP can be leaked.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WebRtcIpPolicy {
     /// Block all non-proxied UDP connections (safest option)
     ///
@@ -62,12 +76,50 @@ impl WebRtcIpPolicy {
     }
 }
 
+/// Filter applied to ICE candidates before `onicecandidate` listeners see them
+///
+/// Runs after the [`WebRtcIpPolicy`] filtering, so it can only remove more.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum IceCandidateFilter {
+    /// Pass every candidate the IP policy allowed
+    #[default]
+    AllowAll,
+    /// Drop candidates with a private, link-local or loopback address
+    BlockLocalSubnet,
+    /// Drop every candidate; pages see gathering finish without any
+    BlockAll,
+}
+
+impl IceCandidateFilter {
+    fn to_filter_string(self) -> &'static str {
+        match self {
+            IceCandidateFilter::AllowAll => "allow_all",
+            IceCandidateFilter::BlockLocalSubnet => "block_local_subnet",
+            IceCandidateFilter::BlockAll => "block_all",
+        }
+    }
+}
+
+/// Preset WebRTC leak prevention policies
+///
+/// Shorthand for the common [`WebRtcConfig`] combinations; see
+/// [`WebRtcConfig::from_policy`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WebRtcPolicy {
+    /// Block WebRTC entirely; constructing a peer connection throws
+    Disable,
+    /// Only relayed (proxied) candidates; host and STUN results are stripped
+    ProxyOnly,
+    /// Strip private candidates and report this address in STUN results
+    FakePublicIp(String),
+}
+
 /// WebRTC leak prevention configuration
 ///
 /// Controls how WebRTC behaves to prevent IP address leaks.
 /// When `disabled` is true, all WebRTC functionality is blocked.
 /// Otherwise, the `ip_handling_policy` controls the level of protection.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebRtcConfig {
     /// Completely disable WebRTC
     ///
@@ -79,6 +131,15 @@ pub struct WebRtcConfig {
     /// Controls which network interfaces WebRTC is allowed to use
     /// for connection establishment.
     pub ip_handling_policy: WebRtcIpPolicy,
+    /// Address written into the host candidates that survive filtering
+    ///
+    /// Hides the real interface address while keeping WebRTC usable.
+    pub spoof_local_ip: Option<String>,
+    /// Extra filtering of ICE candidates on top of the IP policy
+    pub ice_candidate_filter: IceCandidateFilter,
+    /// Address written into the server-reflexive (STUN) candidates that
+    /// survive filtering, in place of the real public IP
+    pub spoof_public_ip: Option<String>,
 }
 
 impl WebRtcConfig {
@@ -87,6 +148,9 @@ impl WebRtcConfig {
         Self {
             disabled: false,
             ip_handling_policy: policy,
+            spoof_local_ip: None,
+            ice_candidate_filter: IceCandidateFilter::AllowAll,
+            spoof_public_ip: None,
         }
     }
 
@@ -95,6 +159,9 @@ impl WebRtcConfig {
         Self {
             disabled: true,
             ip_handling_policy: WebRtcIpPolicy::DisableNonProxiedUdp,
+            spoof_local_ip: None,
+            ice_candidate_filter: IceCandidateFilter::AllowAll,
+            spoof_public_ip: None,
         }
     }
 
@@ -106,9 +173,58 @@ impl WebRtcConfig {
         Self {
             disabled: false,
             ip_handling_policy: WebRtcIpPolicy::DisableNonProxiedUdp,
+            spoof_local_ip: None,
+            ice_candidate_filter: IceCandidateFilter::AllowAll,
+            spoof_public_ip: None,
         }
     }
 
+    /// Create a configuration from a preset policy
+    pub fn from_policy(policy: WebRtcPolicy) -> Self {
+        match policy {
+            WebRtcPolicy::Disable => Self::disabled(),
+            WebRtcPolicy::ProxyOnly => Self::new(WebRtcIpPolicy::DisableNonProxiedUdp),
+            WebRtcPolicy::FakePublicIp(ip) => Self::new(WebRtcIpPolicy::DefaultPublicInterfaceOnly)
+                .with_ice_candidate_filter(IceCandidateFilter::BlockLocalSubnet)
+                .with_spoof_public_ip(ip),
+        }
+    }
+
+    /// Set the filter applied to gathered ICE candidates
+    pub fn with_ice_candidate_filter(mut self, filter: IceCandidateFilter) -> Self {
+        self.ice_candidate_filter = filter;
+        self
+    }
+
+    /// Replace the address of surviving host candidates with `ip`
+    pub fn with_spoof_local_ip(mut self, ip: impl Into<String>) -> Self {
+        self.spoof_local_ip = Some(ip.into());
+        self
+    }
+
+    /// Replace the address of surviving server-reflexive candidates with `ip`
+    pub fn with_spoof_public_ip(mut self, ip: impl Into<String>) -> Self {
+        self.spoof_public_ip = Some(ip.into());
+        self
+    }
+
+    /// Whether UDP traffic outside the proxy is blocked
+    ///
+    /// True when WebRTC is disabled or the policy is
+    /// [`WebRtcIpPolicy::DisableNonProxiedUdp`].
+    pub fn block_non_proxied_udp(&self) -> bool {
+        self.disabled || self.ip_handling_policy == WebRtcIpPolicy::DisableNonProxiedUdp
+    }
+
+    /// Check that the spoofed local and public IPs are valid addresses
+    pub fn validate(&self) -> Result<(), String> {
+        for ip in [&self.spoof_local_ip, &self.spoof_public_ip].into_iter().flatten() {
+            ip.parse::<std::net::IpAddr>()
+                .map_err(|_| format!("Invalid WebRTC spoof IP: {:?}", ip))?;
+        }
+        Ok(())
+    }
+
     /// Generate JavaScript override script for WebRTC leak prevention
     ///
     /// This script must be injected before any page scripts run.
@@ -190,6 +306,14 @@ impl WebRtcConfig {
     /// Generate script that applies IP handling policy
     fn get_policy_script(&self) -> String {
         let policy = self.ip_handling_policy.to_policy_string();
+        let filter = self.ice_candidate_filter.to_filter_string();
+        let to_js = |ip: &Option<String>| {
+            ip.as_ref()
+                .map(|ip| serde_json::to_string(ip).unwrap_or_else(|_| "null".to_string()))
+                .unwrap_or_else(|| "null".to_string())
+        };
+        let spoof_ip = to_js(&self.spoof_local_ip);
+        let spoof_public_ip = to_js(&self.spoof_public_ip);
 
         format!(
             r#"
@@ -198,8 +322,15 @@ impl WebRtcConfig {
     'use strict';
 
     const POLICY = "{policy}";
+    const FILTER = "{filter}";
+    const SPOOF_IP = {spoof_ip};
+    const SPOOF_PUBLIC_IP = {spoof_public_ip};
 
-    // Filter ICE candidates to remove private/local IPs
+    const privateIpPattern = /(?:10\.\d{{1,3}}\.\d{{1,3}}\.\d{{1,3}}|172\.(?:1[6-9]|2\d|3[01])\.\d{{1,3}}\.\d{{1,3}}|192\.168\.\d{{1,3}}\.\d{{1,3}}|fc00:|fe80:)/;
+    const localAddressPattern = /(?:127\.\d{{1,3}}\.\d{{1,3}}\.\d{{1,3}}|169\.254\.\d{{1,3}}\.\d{{1,3}}|::1\b|\.local\b)/;
+
+    // Filter ICE candidates to remove private/local IPs; returns the
+    // candidate to deliver, possibly rewritten, or null to drop it
     function filterIceCandidate(candidate) {{
         if (!candidate || !candidate.candidate) {{
             return candidate;
@@ -219,15 +350,90 @@ impl WebRtcConfig {
             }}
         }} else if (POLICY === 'default_public_interface_only') {{
             // Block private IP ranges
-            const privateIpPattern = /(?:10\.\d{{1,3}}\.\d{{1,3}}\.\d{{1,3}}|172\.(?:1[6-9]|2\d|3[01])\.\d{{1,3}}\.\d{{1,3}}|192\.168\.\d{{1,3}}\.\d{{1,3}}|fc00:|fe80:)/;
             if (privateIpPattern.test(candidateStr)) {{
                 return null;
             }}
         }}
 
+        if (FILTER === 'block_all') {{
+            return null;
+        }}
+        if (FILTER === 'block_local_subnet' &&
+            (privateIpPattern.test(candidateStr) || localAddressPattern.test(candidateStr))) {{
+            return null;
+        }}
+
+        // Rewrite the address (5th field) of host and srflx candidates
+        let spoofed = null;
+        if (SPOOF_IP !== null && candidateStr.includes('typ host')) {{
+            spoofed = SPOOF_IP;
+        }} else if (SPOOF_PUBLIC_IP !== null && candidateStr.includes('typ srflx')) {{
+            spoofed = SPOOF_PUBLIC_IP;
+        }}
+        if (spoofed !== null) {{
+            const parts = candidateStr.split(' ');
+            if (parts.length > 4) {{
+                parts[4] = spoofed;
+                try {{
+                    return new RTCIceCandidate({{
+                        candidate: parts.join(' '),
+                        sdpMid: candidate.sdpMid,
+                        sdpMLineIndex: candidate.sdpMLineIndex,
+                        usernameFragment: candidate.usernameFragment
+                    }});
+                }} catch (e) {{
+                    return null;
+                }}
+            }}
+        }}
+
         return candidate;
     }}
 
+    // Event carrying the filtered candidate, or null to drop the event
+    function filterIceEvent(event) {{
+        if (!event.candidate) {{
+            return event;
+        }}
+        const filtered = filterIceCandidate(event.candidate);
+        if (filtered === null) {{
+            return null;
+        }}
+        if (filtered === event.candidate) {{
+            return event;
+        }}
+        try {{
+            return new RTCPeerConnectionIceEvent('icecandidate', {{ candidate: filtered }});
+        }} catch (e) {{
+            return null;
+        }}
+    }}
+
+    // Apply the candidate filter to the a=candidate lines of an SDP blob
+    function filterSdp(sdp) {{
+        if (typeof sdp !== 'string') {{
+            return sdp;
+        }}
+        return sdp.split('\r\n').map(function(line) {{
+            if (!line.startsWith('a=candidate:')) {{
+                return line;
+            }}
+            const filtered = filterIceCandidate({{ candidate: line.slice(2) }});
+            return filtered === null ? null : 'a=' + filtered.candidate;
+        }}).filter(function(line) {{ return line !== null; }}).join('\r\n');
+    }}
+
+    function filterDescription(desc) {{
+        if (!desc || typeof desc.sdp !== 'string') {{
+            return desc;
+        }}
+        try {{
+            return new RTCSessionDescription({{ type: desc.type, sdp: filterSdp(desc.sdp) }});
+        }} catch (e) {{
+            return {{ type: desc.type, sdp: filterSdp(desc.sdp) }};
+        }}
+    }}
+
     // Override RTCPeerConnection if it exists
     if (typeof window.RTCPeerConnection !== 'undefined') {{
         const OriginalRTCPeerConnection = window.RTCPeerConnection;
@@ -249,20 +455,41 @@ impl WebRtcConfig {
             pc.addEventListener = function(type, listener, options) {{
                 if (type === 'icecandidate') {{
                     const wrappedListener = function(event) {{
-                        if (event.candidate) {{
-                            const filtered = filterIceCandidate(event.candidate);
-                            if (filtered === null) {{
-                                // Block this candidate
-                                return;
-                            }}
+                        const filtered = filterIceEvent(event);
+                        if (filtered === null) {{
+                            // Block this candidate
+                            return;
                         }}
-                        listener.call(this, event);
+                        listener.call(this, filtered);
                     }};
                     return originalAddEventListener(type, wrappedListener, options);
                 }}
                 return originalAddEventListener(type, listener, options);
             }};
 
+            // Strip filtered candidates from offers, answers and the local
+            // description, which carry them once gathering has finished
+            ['createOffer', 'createAnswer'].forEach(function(method) {{
+                const original = pc[method].bind(pc);
+                pc[method] = function() {{
+                    const result = original.apply(null, arguments);
+                    return result && typeof result.then === 'function'
+                        ? result.then(filterDescription)
+                        : result;
+                }};
+            }});
+            ['localDescription', 'currentLocalDescription', 'pendingLocalDescription'].forEach(function(prop) {{
+                const descriptor = Object.getOwnPropertyDescriptor(OriginalRTCPeerConnection.prototype, prop);
+                if (!descriptor || !descriptor.get) {{
+                    return;
+                }}
+                Object.defineProperty(pc, prop, {{
+                    get: function() {{ return filterDescription(descriptor.get.call(pc)); }},
+                    configurable: true,
+                    enumerable: true
+                }});
+            }});
+
             // Override the onicecandidate property setter
             let _onicecandidateHandler = null;
             Object.defineProperty(pc, 'onicecandidate', {{
@@ -270,13 +497,11 @@ impl WebRtcConfig {
                 set: function(handler) {{
                     if (typeof handler === 'function') {{
                         _onicecandidateHandler = function(event) {{
-                            if (event.candidate) {{
-                                const filtered = filterIceCandidate(event.candidate);
-                                if (filtered === null) {{
-                                    return;
-                                }}
+                            const filtered = filterIceEvent(event);
+                            if (filtered === null) {{
+                                return;
                             }}
-                            handler.call(this, event);
+                            handler.call(this, filtered);
                         }};
                     }} else {{
                         _onicecandidateHandler = handler;
@@ -302,6 +527,9 @@ impl WebRtcConfig {
 }})();
 "#,
             policy = policy,
+            filter = filter,
+            spoof_ip = spoof_ip,
+            spoof_public_ip = spoof_public_ip,
         )
     }
 }
@@ -366,6 +594,65 @@ mod tests {
         assert!(js.contains("RTCPeerConnection"));
     }
 
+    #[test]
+    fn test_ice_candidate_filter_modes() {
+        let js = WebRtcConfig::new(WebRtcIpPolicy::Default).get_override_script();
+        assert!(js.contains(r#"const FILTER = "allow_all";"#));
+        assert!(js.contains("const SPOOF_IP = null;"));
+
+        let config = WebRtcConfig::new(WebRtcIpPolicy::Default)
+            .with_ice_candidate_filter(IceCandidateFilter::BlockLocalSubnet);
+        let js = config.get_override_script();
+        assert!(js.contains(r#"const FILTER = "block_local_subnet";"#));
+        assert!(js.contains("localAddressPattern"));
+
+        let config = WebRtcConfig::new(WebRtcIpPolicy::Default)
+            .with_ice_candidate_filter(IceCandidateFilter::BlockAll);
+        let js = config.get_override_script();
+        assert!(js.contains(r#"const FILTER = "block_all";"#));
+        assert!(!config.block_non_proxied_udp());
+        assert!(WebRtcConfig::disabled().block_non_proxied_udp());
+    }
+
+    #[test]
+    fn test_spoof_local_ip() {
+        let config = WebRtcConfig::new(WebRtcIpPolicy::Default).with_spoof_local_ip("203.0.113.7");
+        assert!(config.validate().is_ok());
+        let js = config.get_override_script();
+        assert!(js.contains(r#"const SPOOF_IP = "203.0.113.7";"#));
+        assert!(js.contains("RTCPeerConnectionIceEvent"));
+
+        let config = config.with_spoof_local_ip("1.2.3.4\"; alert(1)");
+        assert!(config.validate().is_err());
+        assert!(config
+            .get_override_script()
+            .contains(r#"const SPOOF_IP = "1.2.3.4\"; alert(1)";"#));
+    }
+
+    #[test]
+    fn test_webrtc_policy_presets() {
+        let js = WebRtcConfig::from_policy(WebRtcPolicy::Disable).get_override_script();
+        assert!(js.contains("RTCPeerConnection"));
+        assert!(js.contains("window.RTCPeerConnection = blockedRtc;"));
+        assert!(js.contains("throw new DOMException"));
+
+        let config = WebRtcConfig::from_policy(WebRtcPolicy::ProxyOnly);
+        assert!(config.block_non_proxied_udp());
+        let js = config.get_override_script();
+        assert!(js.contains("iceTransportPolicy = 'relay'"));
+        assert!(js.contains("filterSdp"));
+
+        let config = WebRtcConfig::from_policy(WebRtcPolicy::FakePublicIp("203.0.113.7".into()));
+        assert!(config.validate().is_ok());
+        assert_eq!(config.ice_candidate_filter, IceCandidateFilter::BlockLocalSubnet);
+        let js = config.get_override_script();
+        assert!(js.contains(r#"const SPOOF_PUBLIC_IP = "203.0.113.7";"#));
+        assert!(js.contains("const SPOOF_IP = null;"));
+
+        let config = WebRtcConfig::from_policy(WebRtcPolicy::FakePublicIp("nope".into()));
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_policy_string_conversion() {
         assert_eq!(